@@ -12,7 +12,10 @@ use solana_sdk::{
     slot_hashes::SlotHashes,
     slot_history::{self, SlotHistory},
     stake_history::{StakeHistory, StakeHistoryEntry},
-    sysvar::{self, fees::Fees, recent_blockhashes::RecentBlockhashes, rewards::Rewards},
+    sysvar::{
+        self, epoch_rewards::EpochRewards, fees::Fees, recent_blockhashes::RecentBlockhashes,
+        rewards::Rewards,
+    },
 };
 
 pub fn parse_sysvar(data: &[u8], pubkey: &Pubkey) -> Result<SysvarAccountType, ParseAccountError> {
@@ -21,6 +24,10 @@ pub fn parse_sysvar(data: &[u8], pubkey: &Pubkey) -> Result<SysvarAccountType, P
             deserialize::<Clock>(data)
                 .ok()
                 .map(|clock| SysvarAccountType::Clock(clock.into()))
+        } else if pubkey == &sysvar::epoch_rewards::id() {
+            deserialize::<EpochRewards>(data)
+                .ok()
+                .map(|epoch_rewards| SysvarAccountType::EpochRewards(epoch_rewards.into()))
         } else if pubkey == &sysvar::epoch_schedule::id() {
             deserialize(data).ok().map(SysvarAccountType::EpochSchedule)
         } else if pubkey == &sysvar::fees::id() {
@@ -90,6 +97,7 @@ pub fn parse_sysvar(data: &[u8], pubkey: &Pubkey) -> Result<SysvarAccountType, P
 #[serde(rename_all = "camelCase", tag = "type", content = "info")]
 pub enum SysvarAccountType {
     Clock(UiClock),
+    EpochRewards(UiEpochRewards),
     EpochSchedule(EpochSchedule),
     Fees(UiFees),
     RecentBlockhashes(Vec<UiRecentBlockhashesEntry>),
@@ -122,6 +130,30 @@ impl From<Clock> for UiClock {
     }
 }
 
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct UiEpochRewards {
+    pub distribution_starting_block_height: u64,
+    pub num_partitions: u64,
+    pub parent_blockhash: String,
+    pub total_rewards: StringAmount,
+    pub distributed_rewards: StringAmount,
+    pub active: bool,
+}
+
+impl From<EpochRewards> for UiEpochRewards {
+    fn from(epoch_rewards: EpochRewards) -> Self {
+        Self {
+            distribution_starting_block_height: epoch_rewards.distribution_starting_block_height,
+            num_partitions: epoch_rewards.num_partitions,
+            parent_blockhash: epoch_rewards.parent_blockhash.to_string(),
+            total_rewards: epoch_rewards.total_rewards.to_string(),
+            distributed_rewards: epoch_rewards.distributed_rewards.to_string(),
+            active: epoch_rewards.active,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, PartialEq, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct UiFees {
@@ -276,6 +308,27 @@ mod test {
             SysvarAccountType::Rent(rent.into()),
         );
 
+        let epoch_rewards = EpochRewards {
+            distribution_starting_block_height: 42,
+            num_partitions: 16,
+            parent_blockhash: hash,
+            total_rewards: 100,
+            distributed_rewards: 10,
+            active: true,
+        };
+        let epoch_rewards_sysvar = create_account_for_test(&epoch_rewards);
+        assert_eq!(
+            parse_sysvar(&epoch_rewards_sysvar.data, &sysvar::epoch_rewards::id()).unwrap(),
+            SysvarAccountType::EpochRewards(UiEpochRewards {
+                distribution_starting_block_height: 42,
+                num_partitions: 16,
+                parent_blockhash: hash.to_string(),
+                total_rewards: "100".to_string(),
+                distributed_rewards: "10".to_string(),
+                active: true,
+            }),
+        );
+
         let rewards_sysvar = create_account_for_test(&Rewards::default());
         assert_eq!(
             parse_sysvar(&rewards_sysvar.data, &sysvar::rewards::id()).unwrap(),
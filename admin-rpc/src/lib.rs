@@ -0,0 +1,70 @@
+//! The validator's Admin RPC interface, served over IPC from the ledger directory
+
+use {
+    jsonrpc_core::Result,
+    jsonrpc_core_client::{transports::ipc, RpcError},
+    jsonrpc_derive::rpc,
+    solana_core::{
+        gossip_inspect::{GossipInspectConfig, GossipInspection},
+        validator::ValidatorStartProgress,
+    },
+    std::{net::SocketAddr, path::Path, time::SystemTime},
+};
+
+#[rpc]
+pub trait AdminRpc {
+    type Metadata;
+
+    #[rpc(meta, name = "exit")]
+    fn exit(&self, meta: Self::Metadata) -> Result<()>;
+
+    #[rpc(meta, name = "rpcAddress")]
+    fn rpc_addr(&self, meta: Self::Metadata) -> Result<Option<SocketAddr>>;
+
+    #[rpc(name = "setLogFilter")]
+    fn set_log_filter(&self, filter: String) -> Result<()>;
+
+    #[rpc(meta, name = "startTime")]
+    fn start_time(&self, meta: Self::Metadata) -> Result<SystemTime>;
+
+    #[rpc(meta, name = "startProgress")]
+    fn start_progress(&self, meta: Self::Metadata) -> Result<ValidatorStartProgress>;
+
+    #[rpc(meta, name = "addAuthorizedVoter")]
+    fn add_authorized_voter(&self, meta: Self::Metadata, keypair_file: String) -> Result<()>;
+
+    #[rpc(meta, name = "removeAllAuthorizedVoters")]
+    fn remove_all_authorized_voters(&self, meta: Self::Metadata) -> Result<()>;
+
+    #[rpc(meta, name = "setIdentity")]
+    fn set_identity(
+        &self,
+        meta: Self::Metadata,
+        keypair_file: String,
+        require_tower: bool,
+    ) -> Result<()>;
+
+    #[rpc(meta, name = "gossipInspect")]
+    fn gossip_inspect(
+        &self,
+        meta: Self::Metadata,
+        config: Option<GossipInspectConfig>,
+    ) -> Result<GossipInspection>;
+}
+
+// Connect to the Admin RPC interface
+pub async fn connect(ledger_path: &Path) -> std::result::Result<gen_client::Client, RpcError> {
+    let admin_rpc_path = ledger_path.join("admin.rpc");
+    if !admin_rpc_path.exists() {
+        Err(RpcError::Client(format!(
+            "{} does not exist",
+            admin_rpc_path.display()
+        )))
+    } else {
+        ipc::connect::<_, gen_client::Client>(&format!("{}", admin_rpc_path.display())).await
+    }
+}
+
+pub fn runtime() -> jsonrpc_server_utils::tokio::runtime::Runtime {
+    jsonrpc_server_utils::tokio::runtime::Runtime::new().expect("new tokio runtime")
+}
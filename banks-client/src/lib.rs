@@ -7,7 +7,9 @@
 
 use borsh::BorshDeserialize;
 use futures::{future::join_all, Future, FutureExt};
-pub use solana_banks_interface::{BanksClient as TarpcClient, TransactionStatus};
+pub use solana_banks_interface::{
+    BanksClient as TarpcClient, BanksTransactionResultWithSimulation, TransactionStatus,
+};
 use solana_banks_interface::{BanksRequest, BanksResponse};
 use solana_program::{
     clock::Slot, fee_calculator::FeeCalculator, hash::Hash, program_pack::Pack, pubkey::Pubkey,
@@ -95,6 +97,16 @@ impl BanksClient {
             .process_transaction_with_commitment_and_context(ctx, transaction, commitment)
     }
 
+    pub fn simulate_transaction_with_commitment_and_context(
+        &mut self,
+        ctx: Context,
+        transaction: Transaction,
+        commitment: CommitmentLevel,
+    ) -> impl Future<Output = io::Result<BanksTransactionResultWithSimulation>> + '_ {
+        self.inner
+            .simulate_transaction_with_commitment_and_context(ctx, transaction, commitment)
+    }
+
     pub fn get_account_with_commitment_and_context(
         &mut self,
         ctx: Context,
@@ -168,6 +180,28 @@ impl BanksClient {
         self.process_transaction_with_commitment(transaction, CommitmentLevel::default())
     }
 
+    /// Simulate a transaction at the given commitment level without committing
+    /// its results, returning its logs and return data
+    pub fn simulate_transaction_with_commitment(
+        &mut self,
+        transaction: Transaction,
+        commitment: CommitmentLevel,
+    ) -> impl Future<Output = io::Result<BanksTransactionResultWithSimulation>> + '_ {
+        self.simulate_transaction_with_commitment_and_context(
+            context::current(),
+            transaction,
+            commitment,
+        )
+    }
+
+    /// Simulate a transaction at the default commitment level
+    pub fn simulate_transaction(
+        &mut self,
+        transaction: Transaction,
+    ) -> impl Future<Output = io::Result<BanksTransactionResultWithSimulation>> + '_ {
+        self.simulate_transaction_with_commitment(transaction, CommitmentLevel::default())
+    }
+
     pub async fn process_transactions_with_commitment(
         &mut self,
         transactions: Vec<Transaction>,
@@ -361,6 +395,40 @@ mod tests {
         })
     }
 
+    #[test]
+    fn test_banks_server_simulate_transaction() -> io::Result<()> {
+        let genesis = create_genesis_config(10);
+        let bank = Bank::new(&genesis.genesis_config);
+        let slot = bank.slot();
+        let block_commitment_cache = Arc::new(RwLock::new(
+            BlockCommitmentCache::new_for_tests_with_slots(slot, slot),
+        ));
+        let bank_forks = Arc::new(RwLock::new(BankForks::new(bank)));
+
+        let bob_pubkey = solana_sdk::pubkey::new_rand();
+        let mint_pubkey = genesis.mint_keypair.pubkey();
+        let instruction = system_instruction::transfer(&mint_pubkey, &bob_pubkey, 1);
+        let message = Message::new(&[instruction], Some(&mint_pubkey));
+
+        Runtime::new()?.block_on(async {
+            let client_transport = start_local_server(bank_forks, block_commitment_cache).await;
+            let mut banks_client = start_client(client_transport).await?;
+
+            let recent_blockhash = banks_client.get_recent_blockhash().await?;
+            let transaction = Transaction::new(&[&genesis.mint_keypair], message, recent_blockhash);
+            let simulation = banks_client.simulate_transaction(transaction).await?;
+            assert_eq!(simulation.result, Some(Ok(())));
+            let details = simulation.simulation_details.unwrap();
+            assert!(!details.logs.is_empty());
+            assert_eq!(details.return_data, None);
+            assert_eq!(details.units_consumed, Some(0));
+
+            // Simulation does not commit the transfer
+            assert_eq!(banks_client.get_balance(bob_pubkey).await?, 0);
+            Ok(())
+        })
+    }
+
     #[test]
     fn test_banks_server_transfer_via_client() -> io::Result<()> {
         // The caller may not want to hold the connection open until the transaction
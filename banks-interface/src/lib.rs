@@ -5,6 +5,7 @@ use solana_sdk::{
     commitment_config::CommitmentLevel,
     fee_calculator::FeeCalculator,
     hash::Hash,
+    process_instruction::TransactionReturnData,
     pubkey::Pubkey,
     signature::Signature,
     transaction::{self, Transaction, TransactionError},
@@ -25,6 +26,19 @@ pub struct TransactionStatus {
     pub confirmation_status: Option<TransactionConfirmationStatus>,
 }
 
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TransactionSimulationDetails {
+    pub logs: Vec<String>,
+    pub return_data: Option<TransactionReturnData>,
+    pub units_consumed: Option<u64>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BanksTransactionResultWithSimulation {
+    pub result: Option<transaction::Result<()>>,
+    pub simulation_details: Option<TransactionSimulationDetails>,
+}
+
 #[tarpc::service]
 pub trait Banks {
     async fn send_transaction_with_context(transaction: Transaction);
@@ -38,6 +52,10 @@ pub trait Banks {
         transaction: Transaction,
         commitment: CommitmentLevel,
     ) -> Option<transaction::Result<()>>;
+    async fn simulate_transaction_with_commitment_and_context(
+        transaction: Transaction,
+        commitment: CommitmentLevel,
+    ) -> BanksTransactionResultWithSimulation;
     async fn get_account_with_commitment_and_context(
         address: Pubkey,
         commitment: CommitmentLevel,
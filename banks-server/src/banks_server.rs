@@ -5,16 +5,19 @@ use futures::{
     prelude::stream::{self, StreamExt},
 };
 use solana_banks_interface::{
-    Banks, BanksRequest, BanksResponse, TransactionConfirmationStatus, TransactionStatus,
+    Banks, BanksRequest, BanksResponse, BanksTransactionResultWithSimulation,
+    TransactionConfirmationStatus, TransactionSimulationDetails, TransactionStatus,
 };
 use solana_runtime::{bank::Bank, bank_forks::BankForks, commitment::BlockCommitmentCache};
 use solana_sdk::{
     account::Account,
     clock::Slot,
     commitment_config::CommitmentLevel,
+    feature_set::FeatureSet,
     fee_calculator::FeeCalculator,
     hash::Hash,
     pubkey::Pubkey,
+    sanitize::Sanitize,
     signature::Signature,
     transaction::{self, Transaction},
 };
@@ -131,10 +134,13 @@ impl BanksServer {
     }
 }
 
-fn verify_transaction(transaction: &Transaction) -> transaction::Result<()> {
+fn verify_transaction(
+    transaction: &Transaction,
+    feature_set: &FeatureSet,
+) -> transaction::Result<()> {
     if let Err(err) = transaction.verify() {
         Err(err)
-    } else if let Err(err) = transaction.verify_precompiles() {
+    } else if let Err(err) = transaction.verify_precompiles_with_features(feature_set) {
         Err(err)
     } else {
         Ok(())
@@ -215,7 +221,7 @@ impl Banks for BanksServer {
         transaction: Transaction,
         commitment: CommitmentLevel,
     ) -> Option<transaction::Result<()>> {
-        if let Err(err) = verify_transaction(&transaction) {
+        if let Err(err) = verify_transaction(&transaction, &self.bank(commitment).feature_set) {
             return Some(Err(err));
         }
 
@@ -235,6 +241,31 @@ impl Banks for BanksServer {
             .await
     }
 
+    async fn simulate_transaction_with_commitment_and_context(
+        self,
+        _: Context,
+        transaction: Transaction,
+        commitment: CommitmentLevel,
+    ) -> BanksTransactionResultWithSimulation {
+        if let Err(err) = transaction.sanitize() {
+            return BanksTransactionResultWithSimulation {
+                result: Some(Err(err.into())),
+                simulation_details: None,
+            };
+        }
+        let (result, logs, return_data, units_consumed) = self
+            .bank(commitment)
+            .simulate_transaction_unchecked(transaction);
+        BanksTransactionResultWithSimulation {
+            result: Some(result),
+            simulation_details: Some(TransactionSimulationDetails {
+                logs,
+                return_data,
+                units_consumed,
+            }),
+        }
+    }
+
     async fn get_account_with_commitment_and_context(
         self,
         _: Context,
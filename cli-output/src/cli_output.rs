@@ -15,8 +15,8 @@ use {
     solana_account_decoder::parse_token::UiTokenAccount,
     solana_clap_utils::keypair::SignOnly,
     solana_client::rpc_response::{
-        RpcAccountBalance, RpcInflationGovernor, RpcInflationRate, RpcKeyedAccount, RpcSupply,
-        RpcVoteAccountInfo,
+        RpcAccountBalance, RpcInflationGovernor, RpcInflationRate, RpcInflationReward,
+        RpcKeyedAccount, RpcStakeAccountEvent, RpcSupply, RpcVoteAccountInfo,
     },
     solana_sdk::{
         clock::{Epoch, Slot, UnixTimestamp},
@@ -1077,6 +1077,113 @@ pub struct CliStakeHistoryEntry {
     pub deactivating_stake: u64,
 }
 
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliStakeAccountHistory {
+    pub address: String,
+    pub events: Vec<RpcStakeAccountEvent>,
+    pub rewards: Vec<RpcInflationReward>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub oldest_signature: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub oldest_reward_epoch: Option<Epoch>,
+    #[serde(skip_serializing)]
+    pub use_lamports_unit: bool,
+}
+
+impl CliStakeAccountHistory {
+    fn write_history(&self, w: &mut dyn fmt::Write, verbose: bool) -> fmt::Result {
+        writeln!(w)?;
+        writeln!(w, "Stake Account: {}", self.address)?;
+        if self.events.is_empty() {
+            writeln!(w, "No stake account events found")?;
+        } else {
+            writeln!(w)?;
+            writeln!(
+                w,
+                "{}",
+                style(format!(
+                    "  {:<11}  {:<20}  {:<20}  {}",
+                    "Slot", "Block Time", "Event", "Signature",
+                ))
+                .bold()
+            )?;
+            for event in &self.events {
+                writeln!(
+                    w,
+                    "  {:<11}  {:<20}  {:<20}  {}{}",
+                    event.slot,
+                    event
+                        .block_time
+                        .map(unix_timestamp_to_string)
+                        .unwrap_or_else(|| "-".to_string()),
+                    event.event_type,
+                    event.signature,
+                    if event.err.is_some() { " (failed)" } else { "" },
+                )?;
+                if verbose {
+                    if let Value::Object(info) = &event.info {
+                        for (key, value) in info {
+                            writeln!(w, "      {}: {}", key, value)?;
+                        }
+                    }
+                }
+            }
+        }
+        if !self.rewards.is_empty() {
+            writeln!(w)?;
+            writeln!(
+                w,
+                "{}",
+                style(format!(
+                    "  {:<6}  {:<11}  {:>20}  {:>20}",
+                    "Epoch", "Reward Slot", "Amount", "New Balance",
+                ))
+                .bold()
+            )?;
+            for reward in &self.rewards {
+                writeln!(
+                    w,
+                    "  {:<6}  {:<11}  {:>20}  {:>20}",
+                    reward.epoch,
+                    reward.effective_slot,
+                    build_balance_message(reward.amount, self.use_lamports_unit, true),
+                    build_balance_message(reward.post_balance, self.use_lamports_unit, true),
+                )?;
+            }
+        }
+        if let Some(oldest_signature) = &self.oldest_signature {
+            writeln!(w)?;
+            match self.oldest_reward_epoch {
+                Some(oldest_reward_epoch) => writeln!(
+                    w,
+                    "Older history is available with `--before {} --rewards-before {}`",
+                    oldest_signature, oldest_reward_epoch
+                )?,
+                None => writeln!(
+                    w,
+                    "Older history is available with `--before {}`",
+                    oldest_signature
+                )?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl QuietDisplay for CliStakeAccountHistory {}
+impl VerboseDisplay for CliStakeAccountHistory {
+    fn write_str(&self, w: &mut dyn std::fmt::Write) -> std::fmt::Result {
+        self.write_history(w, true)
+    }
+}
+
+impl fmt::Display for CliStakeAccountHistory {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.write_history(f, false)
+    }
+}
+
 #[derive(Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CliAuthorized {
@@ -1172,6 +1279,8 @@ pub struct CliVoteAccount {
     pub authorized_withdrawer: String,
     pub credits: u64,
     pub commission: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commission_increase_window: Option<CliCommissionIncreaseWindow>,
     pub root_slot: Option<Slot>,
     pub recent_timestamp: BlockTimestamp,
     pub votes: Vec<CliLockout>,
@@ -1197,6 +1306,9 @@ impl fmt::Display for CliVoteAccount {
         writeln!(f, "Authorized Withdrawer: {}", self.authorized_withdrawer)?;
         writeln!(f, "Credits: {}", self.credits)?;
         writeln!(f, "Commission: {}%", self.commission)?;
+        if let Some(window) = &self.commission_increase_window {
+            writeln!(f, "Commission Increases: {}", window)?;
+        }
         writeln!(
             f,
             "Root Slot: {}",
@@ -1217,6 +1329,26 @@ impl fmt::Display for CliVoteAccount {
     }
 }
 
+/// When the vote account's commission may next be increased, once increases
+/// are restricted to the first half of an epoch
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliCommissionIncreaseWindow {
+    pub allowed: bool,
+    pub last_allowed_slot: Slot,
+    pub next_allowed_slot: Slot,
+}
+
+impl fmt::Display for CliCommissionIncreaseWindow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.allowed {
+            write!(f, "allowed until slot {}", self.last_allowed_slot)
+        } else {
+            write!(f, "not allowed until slot {}", self.next_allowed_slot)
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CliAuthorizedVoters {
@@ -218,6 +218,7 @@ pub enum CliCommand {
     DeactivateStake {
         stake_account_pubkey: Pubkey,
         stake_authority: SignerIndex,
+        deactivate_delinquent: bool,
         sign_only: bool,
         dump_transaction_message: bool,
         blockhash_query: BlockhashQuery,
@@ -265,8 +266,27 @@ pub enum CliCommand {
         memo: Option<String>,
         fee_payer: SignerIndex,
     },
+    RedelegateStake {
+        stake_account_pubkey: Pubkey,
+        vote_account_pubkey: Pubkey,
+        stake_authority: SignerIndex,
+        force: bool,
+        sign_only: bool,
+        dump_transaction_message: bool,
+        blockhash_query: BlockhashQuery,
+        nonce_account: Option<Pubkey>,
+        nonce_authority: SignerIndex,
+        memo: Option<String>,
+        redelegation_stake_account: SignerIndex,
+        seed: Option<String>,
+        fee_payer: SignerIndex,
+    },
     ShowStakeHistory {
         use_lamports_unit: bool,
+        account: Option<Pubkey>,
+        limit: usize,
+        before: Option<Signature>,
+        rewards_before: Option<Epoch>,
     },
     ShowStakeAccount {
         pubkey: Pubkey,
@@ -736,6 +756,9 @@ pub fn parse_command(
         ("merge-stake", Some(matches)) => {
             parse_merge_stake(matches, default_signer, wallet_manager)
         }
+        ("redelegate-stake", Some(matches)) => {
+            parse_redelegate_stake(matches, default_signer, wallet_manager)
+        }
         ("stake-authorize", Some(matches)) => {
             parse_stake_authorize(matches, default_signer, wallet_manager)
         }
@@ -743,7 +766,7 @@ pub fn parse_command(
             parse_stake_set_lockup(matches, default_signer, wallet_manager)
         }
         ("stake-account", Some(matches)) => parse_show_stake_account(matches, wallet_manager),
-        ("stake-history", Some(matches)) => parse_show_stake_history(matches),
+        ("stake-history", Some(matches)) => parse_show_stake_history(matches, wallet_manager),
         // Validator Info Commands
         ("validator-info", Some(matches)) => match matches.subcommand() {
             ("publish", Some(matches)) => {
@@ -1572,6 +1595,7 @@ pub fn process_command(config: &CliConfig) -> ProcessResult {
         CliCommand::DeactivateStake {
             stake_account_pubkey,
             stake_authority,
+            deactivate_delinquent,
             sign_only,
             dump_transaction_message,
             blockhash_query,
@@ -1584,6 +1608,7 @@ pub fn process_command(config: &CliConfig) -> ProcessResult {
             config,
             &stake_account_pubkey,
             *stake_authority,
+            *deactivate_delinquent,
             *sign_only,
             *dump_transaction_message,
             blockhash_query,
@@ -1673,6 +1698,37 @@ pub fn process_command(config: &CliConfig) -> ProcessResult {
             memo.as_ref(),
             *fee_payer,
         ),
+        CliCommand::RedelegateStake {
+            stake_account_pubkey,
+            vote_account_pubkey,
+            stake_authority,
+            force,
+            sign_only,
+            dump_transaction_message,
+            blockhash_query,
+            nonce_account,
+            nonce_authority,
+            memo,
+            redelegation_stake_account,
+            seed,
+            fee_payer,
+        } => process_redelegate_stake(
+            &rpc_client,
+            config,
+            &stake_account_pubkey,
+            &vote_account_pubkey,
+            *stake_authority,
+            *force,
+            *sign_only,
+            *dump_transaction_message,
+            blockhash_query,
+            *nonce_account,
+            *nonce_authority,
+            memo.as_ref(),
+            *redelegation_stake_account,
+            seed,
+            *fee_payer,
+        ),
         CliCommand::ShowStakeAccount {
             pubkey: stake_account_pubkey,
             use_lamports_unit,
@@ -1684,9 +1740,21 @@ pub fn process_command(config: &CliConfig) -> ProcessResult {
             *use_lamports_unit,
             *with_rewards,
         ),
-        CliCommand::ShowStakeHistory { use_lamports_unit } => {
-            process_show_stake_history(&rpc_client, config, *use_lamports_unit)
-        }
+        CliCommand::ShowStakeHistory {
+            use_lamports_unit,
+            account,
+            limit,
+            before,
+            rewards_before,
+        } => process_show_stake_history(
+            &rpc_client,
+            config,
+            *use_lamports_unit,
+            account.as_ref(),
+            *limit,
+            *before,
+            *rewards_before,
+        ),
         CliCommand::StakeAuthorize {
             stake_account_pubkey,
             ref new_authorizations,
@@ -2807,6 +2875,7 @@ mod tests {
         config.command = CliCommand::DeactivateStake {
             stake_account_pubkey,
             stake_authority: 0,
+            deactivate_delinquent: false,
             sign_only: false,
             dump_transaction_message: false,
             blockhash_query: BlockhashQuery::default(),
@@ -7,12 +7,14 @@ use console::style;
 use serde::{Deserialize, Serialize};
 use solana_clap_utils::{input_parsers::*, input_validators::*, keypair::*};
 use solana_cli_output::{QuietDisplay, VerboseDisplay};
-use solana_client::{client_error::ClientError, rpc_client::RpcClient};
+use solana_client::{
+    client_error::ClientError, rpc_client::RpcClient, rpc_request::MAX_MULTIPLE_ACCOUNTS,
+};
 use solana_remote_wallet::remote_wallet::RemoteWalletManager;
 use solana_sdk::{
     clock::Slot,
     feature::{self, Feature},
-    feature_set::FEATURE_NAMES,
+    feature_set::{FeatureSet, FEATURE_NAMES},
     message::Message,
     pubkey::Pubkey,
     transaction::Transaction,
@@ -312,6 +314,25 @@ fn feature_activation_allowed(rpc_client: &RpcClient, quiet: bool) -> Result<boo
     Ok(feature_activation_allowed)
 }
 
+/// Fetch the features active on the cluster
+pub fn get_feature_set(rpc_client: &RpcClient) -> Result<FeatureSet, ClientError> {
+    let feature_ids: Vec<_> = FEATURE_NAMES.keys().cloned().collect();
+    let mut feature_set = FeatureSet::default();
+    for feature_ids in feature_ids.chunks(MAX_MULTIPLE_ACCOUNTS) {
+        let accounts = rpc_client.get_multiple_accounts(feature_ids)?;
+        for (feature_id, account) in feature_ids.iter().zip(accounts) {
+            if let Some(activation_slot) = account
+                .and_then(|account| feature::from_account(&account))
+                .and_then(|feature| feature.activated_at)
+            {
+                feature_set.inactive.remove(feature_id);
+                feature_set.active.insert(*feature_id, activation_slot);
+            }
+        }
+    }
+    Ok(feature_set)
+}
+
 fn process_status(
     rpc_client: &RpcClient,
     config: &CliConfig,
@@ -5,13 +5,16 @@ use crate::{
         log_instruction_custom_error, CliCommand, CliCommandInfo, CliConfig, CliError,
         ProcessResult,
     },
+    feature::get_feature_set,
 };
 use bincode::serialize;
 use bip39::{Language, Mnemonic, MnemonicType, Seed};
 use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
 use log::*;
 use solana_account_decoder::{UiAccountEncoding, UiDataSliceConfig};
-use solana_bpf_loader_program::{bpf_verifier, BpfError, ThisInstructionMeter};
+use solana_bpf_loader_program::{
+    bpf_verifier, elf_verifier::verify_elf, BpfError, ThisInstructionMeter,
+};
 use solana_clap_utils::{self, input_parsers::*, input_validators::*, keypair::*};
 use solana_cli_output::{
     display::new_spinner_progress_bar, CliProgram, CliProgramAccountType, CliProgramAuthority,
@@ -36,6 +39,7 @@ use solana_sdk::{
     bpf_loader_upgradeable::{self, UpgradeableLoaderState},
     clock::Slot,
     commitment_config::CommitmentConfig,
+    feature_set::FeatureSet,
     instruction::Instruction,
     instruction::InstructionError,
     loader_instruction,
@@ -112,6 +116,15 @@ pub enum ProgramCliCommand {
         authority_index: SignerIndex,
         use_lamports_unit: bool,
     },
+    Extend {
+        program_pubkey: Pubkey,
+        upgrade_authority_index: Option<SignerIndex>,
+        additional_bytes: u32,
+    },
+    VerifyElf {
+        program_location: String,
+        all_features: bool,
+    },
 }
 
 pub trait ProgramSubCommands {
@@ -371,6 +384,57 @@ impl ProgramSubCommands for App<'_, '_> {
                                 .help("Display balance in lamports instead of SOL"),
                         ),
                 )
+                .subcommand(
+                    SubCommand::with_name("extend")
+                        .about("Extend the length of an upgradeable program's data account \
+                                so that it can be upgraded to a larger program")
+                        .arg(
+                            Arg::with_name("program_id")
+                                .index(1)
+                                .value_name("PROGRAM_ADDRESS")
+                                .takes_value(true)
+                                .required(true)
+                                .validator(is_valid_pubkey)
+                                .help("Address of the program to extend")
+                        )
+                        .arg(
+                            Arg::with_name("additional_bytes")
+                                .index(2)
+                                .value_name("ADDITIONAL_BYTES")
+                                .takes_value(true)
+                                .required(true)
+                                .validator(is_parsable::<u32>)
+                                .help("Number of bytes to add to the program's data account")
+                        )
+                        .arg(
+                            Arg::with_name("upgrade_authority")
+                                .long("upgrade-authority")
+                                .value_name("UPGRADE_AUTHORITY_SIGNER")
+                                .takes_value(true)
+                                .validator(is_valid_signer)
+                                .help("Upgrade authority [default: the default configured keypair]")
+                        ),
+                )
+                .subcommand(
+                    SubCommand::with_name("verify-elf")
+                        .about("Check a program for everything that would make deploying \
+                                or executing it fail, without deploying it")
+                        .arg(
+                            Arg::with_name("program_location")
+                                .index(1)
+                                .value_name("PROGRAM_FILEPATH")
+                                .takes_value(true)
+                                .required(true)
+                                .help("/path/to/program.so"),
+                        )
+                        .arg(
+                            Arg::with_name("all_features")
+                                .long("all-features")
+                                .takes_value(false)
+                                .help("Verify against all runtime features instead of \
+                                       the features active on the cluster"),
+                        ),
+                )
         )
     }
 }
@@ -616,6 +680,37 @@ pub fn parse_program_subcommand(
                 signers: signer_info.signers,
             }
         }
+        ("extend", Some(matches)) => {
+            let (upgrade_authority_signer, upgrade_authority_pubkey) =
+                signer_of(matches, "upgrade_authority", wallet_manager)?;
+            let program_pubkey = pubkey_of(matches, "program_id").unwrap();
+            let additional_bytes = value_of(matches, "additional_bytes").unwrap();
+
+            let signer_info = default_signer.generate_unique_signers(
+                vec![
+                    Some(default_signer.signer_from_path(matches, wallet_manager)?),
+                    upgrade_authority_signer,
+                ],
+                matches,
+                wallet_manager,
+            )?;
+
+            CliCommandInfo {
+                command: CliCommand::Program(ProgramCliCommand::Extend {
+                    program_pubkey,
+                    upgrade_authority_index: signer_info.index_of(upgrade_authority_pubkey),
+                    additional_bytes,
+                }),
+                signers: signer_info.signers,
+            }
+        }
+        ("verify-elf", Some(matches)) => CliCommandInfo {
+            command: CliCommand::Program(ProgramCliCommand::VerifyElf {
+                program_location: matches.value_of("program_location").unwrap().to_string(),
+                all_features: matches.is_present("all_features"),
+            }),
+            signers: vec![],
+        },
         _ => unreachable!(),
     };
     Ok(response)
@@ -719,6 +814,21 @@ pub fn process_program_subcommand(
             *authority_index,
             *use_lamports_unit,
         ),
+        ProgramCliCommand::Extend {
+            program_pubkey,
+            upgrade_authority_index,
+            additional_bytes,
+        } => process_extend(
+            &rpc_client,
+            config,
+            *program_pubkey,
+            *upgrade_authority_index,
+            *additional_bytes,
+        ),
+        ProgramCliCommand::VerifyElf {
+            program_location,
+            all_features,
+        } => process_verify_elf(&rpc_client, program_location, *all_features),
     }
 }
 
@@ -1448,6 +1558,88 @@ fn process_close(
         }))
 }
 
+/// Extend the data length of an upgradeable program
+fn process_extend(
+    rpc_client: &RpcClient,
+    config: &CliConfig,
+    program_pubkey: Pubkey,
+    upgrade_authority_index: Option<SignerIndex>,
+    additional_bytes: u32,
+) -> ProcessResult {
+    let upgrade_authority_signer = if let Some(index) = upgrade_authority_index {
+        config.signers[index]
+    } else {
+        return Err("Extend requires the upgrade authority".into());
+    };
+
+    let program_account = rpc_client
+        .get_account_with_commitment(&program_pubkey, config.commitment)?
+        .value
+        .ok_or_else(|| format!("Unable to find program {}", program_pubkey))?;
+    if program_account.owner != bpf_loader_upgradeable::id() {
+        return Err(format!("{} is not an upgradeable program", program_pubkey).into());
+    }
+    let programdata_pubkey = if let Ok(UpgradeableLoaderState::Program {
+        programdata_address,
+    }) = program_account.state()
+    {
+        programdata_address
+    } else {
+        return Err(format!("{} is not an upgradeable program", program_pubkey).into());
+    };
+    let programdata_account = rpc_client
+        .get_account_with_commitment(&programdata_pubkey, config.commitment)?
+        .value
+        .ok_or_else(|| format!("Unable to find program data {}", programdata_pubkey))?;
+    let additional_lamports = rpc_client
+        .get_minimum_balance_for_rent_exemption(
+            programdata_account.data.len() + additional_bytes as usize,
+        )?
+        .saturating_sub(programdata_account.lamports);
+
+    trace!("Extend program data");
+    let (blockhash, fee_calculator) = rpc_client.get_recent_blockhash()?;
+    let message = Message::new(
+        &[bpf_loader_upgradeable::extend_program_data(
+            &program_pubkey,
+            &upgrade_authority_signer.pubkey(),
+            &config.signers[0].pubkey(),
+            additional_bytes,
+        )],
+        Some(&config.signers[0].pubkey()),
+    );
+    check_account_for_spend_multiple_fees_with_commitment(
+        rpc_client,
+        &config.signers[0].pubkey(),
+        additional_lamports,
+        &fee_calculator,
+        &[&message],
+        config.commitment,
+    )?;
+    let mut tx = Transaction::new_unsigned(message);
+    tx.try_sign(&[config.signers[0], upgrade_authority_signer], blockhash)?;
+    rpc_client
+        .send_and_confirm_transaction_with_spinner_and_config(
+            &tx,
+            config.commitment,
+            RpcSendTransactionConfig {
+                skip_preflight: true,
+                preflight_commitment: Some(config.commitment.commitment),
+                ..RpcSendTransactionConfig::default()
+            },
+        )
+        .map_err(|e| format!("Extending program failed: {}", e))?;
+
+    process_show(
+        rpc_client,
+        config,
+        Some(program_pubkey),
+        upgrade_authority_signer.pubkey(),
+        false,
+        false,
+    )
+}
+
 /// Deploy using non-upgradeable loader
 pub fn process_deploy(
     rpc_client: &RpcClient,
@@ -1774,6 +1966,38 @@ fn do_process_program_upgrade(
     Ok(config.output_format.formatted_string(&program_id))
 }
 
+fn process_verify_elf(
+    rpc_client: &RpcClient,
+    program_location: &str,
+    all_features: bool,
+) -> ProcessResult {
+    let mut file = File::open(program_location)
+        .map_err(|err| format!("Unable to open program file: {}", err))?;
+    let mut program_data = Vec::new();
+    file.read_to_end(&mut program_data)
+        .map_err(|err| format!("Unable to read program file: {}", err))?;
+
+    let feature_set = if all_features {
+        FeatureSet::all_enabled()
+    } else {
+        get_feature_set(rpc_client)?
+    };
+    let errors = verify_elf(&program_data, &feature_set);
+    if errors.is_empty() {
+        Ok(format!("{} passed verification", program_location))
+    } else {
+        for error in &errors {
+            println!("{}", error);
+        }
+        Err(format!(
+            "{} failed verification with {} error(s)",
+            program_location,
+            errors.len()
+        )
+        .into())
+    }
+}
+
 fn read_and_verify_elf(program_location: &str) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
     let mut file = File::open(program_location)
         .map_err(|err| format!("Unable to open program file: {}", err))?;
@@ -2804,6 +3028,129 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_cli_parse_extend() {
+        let test_commands = app("test", "desc", "version");
+
+        let default_keypair = Keypair::new();
+        let keypair_file = make_tmp_path("keypair_file");
+        write_keypair_file(&default_keypair, &keypair_file).unwrap();
+        let default_signer = DefaultSigner {
+            path: keypair_file.clone(),
+            arg_name: "".to_string(),
+        };
+
+        let program_pubkey = Pubkey::new_unique();
+        let test_command = test_commands.clone().get_matches_from(vec![
+            "test",
+            "program",
+            "extend",
+            &program_pubkey.to_string(),
+            "4096",
+        ]);
+        assert_eq!(
+            parse_command(&test_command, &default_signer, &mut None).unwrap(),
+            CliCommandInfo {
+                command: CliCommand::Program(ProgramCliCommand::Extend {
+                    program_pubkey,
+                    upgrade_authority_index: Some(0),
+                    additional_bytes: 4096,
+                }),
+                signers: vec![read_keypair_file(&keypair_file).unwrap().into()],
+            }
+        );
+
+        let authority = Keypair::new();
+        let authority_keypair_file = make_tmp_path("authority_keypair_file");
+        write_keypair_file(&authority, &authority_keypair_file).unwrap();
+        let test_command = test_commands.clone().get_matches_from(vec![
+            "test",
+            "program",
+            "extend",
+            &program_pubkey.to_string(),
+            "4096",
+            "--upgrade-authority",
+            &authority_keypair_file,
+        ]);
+        assert_eq!(
+            parse_command(&test_command, &default_signer, &mut None).unwrap(),
+            CliCommandInfo {
+                command: CliCommand::Program(ProgramCliCommand::Extend {
+                    program_pubkey,
+                    upgrade_authority_index: Some(1),
+                    additional_bytes: 4096,
+                }),
+                signers: vec![
+                    read_keypair_file(&keypair_file).unwrap().into(),
+                    read_keypair_file(&authority_keypair_file).unwrap().into(),
+                ],
+            }
+        );
+
+        let test_command = test_commands.clone().get_matches_from_safe(vec![
+            "test",
+            "program",
+            "extend",
+            &program_pubkey.to_string(),
+            "-1",
+        ]);
+        assert!(test_command.is_err());
+    }
+
+    #[test]
+    fn test_cli_parse_verify_elf() {
+        let test_commands = app("test", "desc", "version");
+
+        let default_keypair = Keypair::new();
+        let keypair_file = make_tmp_path("keypair_file");
+        write_keypair_file(&default_keypair, &keypair_file).unwrap();
+        let default_signer = DefaultSigner {
+            path: keypair_file,
+            arg_name: "".to_string(),
+        };
+
+        let test_command = test_commands.clone().get_matches_from(vec![
+            "test",
+            "program",
+            "verify-elf",
+            "/Users/test/program.so",
+        ]);
+        assert_eq!(
+            parse_command(&test_command, &default_signer, &mut None).unwrap(),
+            CliCommandInfo {
+                command: CliCommand::Program(ProgramCliCommand::VerifyElf {
+                    program_location: "/Users/test/program.so".to_string(),
+                    all_features: false,
+                }),
+                signers: vec![],
+            }
+        );
+
+        let test_command = test_commands.clone().get_matches_from(vec![
+            "test",
+            "program",
+            "verify-elf",
+            "/Users/test/program.so",
+            "--all-features",
+        ]);
+        assert_eq!(
+            parse_command(&test_command, &default_signer, &mut None).unwrap(),
+            CliCommandInfo {
+                command: CliCommand::Program(ProgramCliCommand::VerifyElf {
+                    program_location: "/Users/test/program.so".to_string(),
+                    all_features: true,
+                }),
+                signers: vec![],
+            }
+        );
+
+        let test_command =
+            test_commands
+                .clone()
+                .get_matches_from_safe(vec!["test", "program", "verify-elf"]);
+        assert!(test_command.is_err());
+    }
+
     #[test]
     #[allow(clippy::cognitive_complexity)]
     fn test_cli_parse_close() {
@@ -7,6 +7,7 @@ use crate::{
     memo::WithMemo,
     nonce::check_nonce_account,
     spend_utils::{resolve_spend_tx_and_check_account_balances, SpendAmount},
+    vote::get_vote_account,
 };
 use clap::{App, Arg, ArgGroup, ArgMatches, SubCommand};
 use solana_clap_utils::{
@@ -20,22 +21,24 @@ use solana_clap_utils::{
     ArgConstant,
 };
 use solana_cli_output::{
-    return_signers_with_config, CliEpochReward, CliStakeHistory, CliStakeHistoryEntry,
-    CliStakeState, CliStakeType, ReturnSignersConfig,
+    return_signers_with_config, CliEpochReward, CliStakeAccountHistory, CliStakeHistory,
+    CliStakeHistoryEntry, CliStakeState, CliStakeType, ReturnSignersConfig,
 };
 use solana_client::{
     blockhash_query::BlockhashQuery, nonce_utils, rpc_client::RpcClient,
-    rpc_request::DELINQUENT_VALIDATOR_SLOT_DISTANCE, rpc_response::RpcInflationReward,
+    rpc_config::RpcStakeAccountHistoryConfig, rpc_request::DELINQUENT_VALIDATOR_SLOT_DISTANCE,
+    rpc_response::RpcInflationReward,
 };
 use solana_remote_wallet::remote_wallet::RemoteWalletManager;
 use solana_sdk::{
     account::from_account,
     account_utils::StateMut,
-    clock::{Clock, UnixTimestamp, SECONDS_PER_DAY},
+    clock::{Clock, Epoch, UnixTimestamp, SECONDS_PER_DAY},
     epoch_schedule::EpochSchedule,
     feature, feature_set,
     message::Message,
     pubkey::Pubkey,
+    signature::Signature,
     system_instruction::SystemError,
     sysvar::{
         clock,
@@ -45,7 +48,10 @@ use solana_sdk::{
 };
 use solana_stake_program::{
     stake_instruction::{self, LockupArgs, StakeError},
-    stake_state::{Authorized, Lockup, Meta, StakeAuthorize, StakeState},
+    stake_state::{
+        acceptable_reference_epoch_credits, eligible_for_deactivate_delinquent, Authorized, Lockup,
+        Meta, StakeAuthorize, StakeState, MINIMUM_DELINQUENT_EPOCHS_FOR_DEACTIVATION,
+    },
 };
 use solana_vote_program::vote_state::VoteState;
 use std::{ops::Deref, sync::Arc};
@@ -248,6 +254,15 @@ impl StakeSubCommands for App<'_, '_> {
                         .required(true),
                         "Stake account to be deactivated. ")
                 )
+                .arg(
+                    Arg::with_name("delinquent")
+                        .long("delinquent")
+                        .takes_value(false)
+                        .conflicts_with(STAKE_AUTHORITY_ARG.name)
+                        .conflicts_with(SIGN_ONLY_ARG.name)
+                        .help("Deactivate abandoned stake that is currently delegated to a delinquent vote account. \
+                               No stake authority signature is required")
+                )
                 .arg(stake_authority_arg())
                 .offline_args()
                 .nonce_args(false)
@@ -315,6 +330,51 @@ impl StakeSubCommands for App<'_, '_> {
                 .nonce_args(false)
                 .arg(fee_payer_arg())
         )
+        .subcommand(
+            SubCommand::with_name("redelegate-stake")
+                .about("Move active stake to a new stake account delegated to another vote account, without waiting for it to cool down")
+                .arg(
+                    Arg::with_name("force")
+                        .long("force")
+                        .takes_value(false)
+                        .hidden(true) // Don't document this argument to discourage its use
+                        .help("Override vote account sanity checks (use carefully!)")
+                )
+                .arg(
+                    pubkey!(Arg::with_name("stake_account_pubkey")
+                        .index(1)
+                        .value_name("STAKE_ACCOUNT_ADDRESS")
+                        .required(true),
+                        "Existing delegated stake account that will be deactivated")
+                )
+                .arg(
+                    pubkey!(Arg::with_name("vote_account_pubkey")
+                        .index(2)
+                        .value_name("VOTE_ACCOUNT_ADDRESS")
+                        .required(true),
+                        "The vote account to which the stake will be redelegated")
+                )
+                .arg(
+                    Arg::with_name("redelegation_stake_account")
+                        .index(3)
+                        .value_name("REDELEGATION_STAKE_ACCOUNT")
+                        .takes_value(true)
+                        .required(true)
+                        .validator(is_valid_signer)
+                        .help("Keypair of the new stake account that will hold the redelegated stake")
+                )
+                .arg(
+                    Arg::with_name("seed")
+                        .long("seed")
+                        .value_name("STRING")
+                        .takes_value(true)
+                        .help("Seed for address generation; if specified, the resulting account will be at a derived address of the REDELEGATION_STAKE_ACCOUNT pubkey")
+                )
+                .arg(stake_authority_arg())
+                .offline_args()
+                .nonce_args(false)
+                .arg(fee_payer_arg())
+        )
         .subcommand(
             SubCommand::with_name("withdraw-stake")
                 .about("Withdraw the unstaked SOL from the stake account")
@@ -438,6 +498,40 @@ impl StakeSubCommands for App<'_, '_> {
                         .takes_value(false)
                         .help("Display balance in lamports instead of SOL")
                 )
+                .arg(
+                    pubkey!(Arg::with_name("account")
+                        .long("account")
+                        .value_name("STAKE_ACCOUNT_ADDRESS"),
+                        "Show the event and reward history of this stake account \
+                        instead of the cluster stake history. ")
+                )
+                .arg(
+                    Arg::with_name("limit")
+                        .long("limit")
+                        .takes_value(true)
+                        .value_name("LIMIT")
+                        .validator(is_parsable::<usize>)
+                        .default_value("100")
+                        .requires("account")
+                        .help("Maximum number of transactions to scan for stake account events")
+                )
+                .arg(
+                    Arg::with_name("before")
+                        .long("before")
+                        .value_name("TRANSACTION_SIGNATURE")
+                        .takes_value(true)
+                        .requires("account")
+                        .help("Start with the first transaction older than this one")
+                )
+                .arg(
+                    Arg::with_name("rewards_before")
+                        .long("rewards-before")
+                        .value_name("EPOCH")
+                        .takes_value(true)
+                        .validator(is_epoch)
+                        .requires("account")
+                        .help("Start with the rewards of the epoch before this one")
+                )
         )
     }
 }
@@ -685,6 +779,60 @@ pub fn parse_split_stake(
     })
 }
 
+pub fn parse_redelegate_stake(
+    matches: &ArgMatches<'_>,
+    default_signer: &DefaultSigner,
+    wallet_manager: &mut Option<Arc<RemoteWalletManager>>,
+) -> Result<CliCommandInfo, CliError> {
+    let stake_account_pubkey =
+        pubkey_of_signer(matches, "stake_account_pubkey", wallet_manager)?.unwrap();
+    let vote_account_pubkey =
+        pubkey_of_signer(matches, "vote_account_pubkey", wallet_manager)?.unwrap();
+    let (redelegation_stake_account, redelegation_stake_account_pubkey) =
+        signer_of(matches, "redelegation_stake_account", wallet_manager)?;
+    let seed = matches.value_of("seed").map(|s| s.to_string());
+    let force = matches.is_present("force");
+
+    let sign_only = matches.is_present(SIGN_ONLY_ARG.name);
+    let dump_transaction_message = matches.is_present(DUMP_TRANSACTION_MESSAGE.name);
+    let blockhash_query = BlockhashQuery::new_from_matches(matches);
+    let nonce_account = pubkey_of(matches, NONCE_ARG.name);
+    let memo = matches.value_of(MEMO_ARG.name).map(String::from);
+    let (stake_authority, stake_authority_pubkey) =
+        signer_of(matches, STAKE_AUTHORITY_ARG.name, wallet_manager)?;
+    let (nonce_authority, nonce_authority_pubkey) =
+        signer_of(matches, NONCE_AUTHORITY_ARG.name, wallet_manager)?;
+    let (fee_payer, fee_payer_pubkey) = signer_of(matches, FEE_PAYER_ARG.name, wallet_manager)?;
+
+    let mut bulk_signers = vec![stake_authority, fee_payer, redelegation_stake_account];
+    if nonce_account.is_some() {
+        bulk_signers.push(nonce_authority);
+    }
+    let signer_info =
+        default_signer.generate_unique_signers(bulk_signers, matches, wallet_manager)?;
+
+    Ok(CliCommandInfo {
+        command: CliCommand::RedelegateStake {
+            stake_account_pubkey,
+            vote_account_pubkey,
+            stake_authority: signer_info.index_of(stake_authority_pubkey).unwrap(),
+            force,
+            sign_only,
+            dump_transaction_message,
+            blockhash_query,
+            nonce_account,
+            nonce_authority: signer_info.index_of(nonce_authority_pubkey).unwrap(),
+            memo,
+            redelegation_stake_account: signer_info
+                .index_of(redelegation_stake_account_pubkey)
+                .unwrap(),
+            seed,
+            fee_payer: signer_info.index_of(fee_payer_pubkey).unwrap(),
+        },
+        signers: signer_info.signers,
+    })
+}
+
 pub fn parse_merge_stake(
     matches: &ArgMatches<'_>,
     default_signer: &DefaultSigner,
@@ -737,6 +885,7 @@ pub fn parse_stake_deactivate_stake(
 ) -> Result<CliCommandInfo, CliError> {
     let stake_account_pubkey =
         pubkey_of_signer(matches, "stake_account_pubkey", wallet_manager)?.unwrap();
+    let deactivate_delinquent = matches.is_present("delinquent");
     let sign_only = matches.is_present(SIGN_ONLY_ARG.name);
     let dump_transaction_message = matches.is_present(DUMP_TRANSACTION_MESSAGE.name);
     let blockhash_query = BlockhashQuery::new_from_matches(matches);
@@ -748,7 +897,12 @@ pub fn parse_stake_deactivate_stake(
         signer_of(matches, NONCE_AUTHORITY_ARG.name, wallet_manager)?;
     let (fee_payer, fee_payer_pubkey) = signer_of(matches, FEE_PAYER_ARG.name, wallet_manager)?;
 
-    let mut bulk_signers = vec![stake_authority, fee_payer];
+    // Deactivating delinquent stake doesn't require the stake authority
+    let mut bulk_signers = if deactivate_delinquent {
+        vec![fee_payer]
+    } else {
+        vec![stake_authority, fee_payer]
+    };
     if nonce_account.is_some() {
         bulk_signers.push(nonce_authority);
     }
@@ -759,6 +913,7 @@ pub fn parse_stake_deactivate_stake(
         command: CliCommand::DeactivateStake {
             stake_account_pubkey,
             stake_authority: signer_info.index_of(stake_authority_pubkey).unwrap(),
+            deactivate_delinquent,
             sign_only,
             dump_transaction_message,
             blockhash_query,
@@ -894,10 +1049,30 @@ pub fn parse_show_stake_account(
     })
 }
 
-pub fn parse_show_stake_history(matches: &ArgMatches<'_>) -> Result<CliCommandInfo, CliError> {
+pub fn parse_show_stake_history(
+    matches: &ArgMatches<'_>,
+    wallet_manager: &mut Option<Arc<RemoteWalletManager>>,
+) -> Result<CliCommandInfo, CliError> {
     let use_lamports_unit = matches.is_present("lamports");
+    let account = pubkey_of_signer(matches, "account", wallet_manager)?;
+    let limit = value_of(matches, "limit").unwrap();
+    let before = match matches.value_of("before") {
+        Some(signature) => Some(
+            signature
+                .parse()
+                .map_err(|err| CliError::BadParameter(format!("Invalid signature: {}", err)))?,
+        ),
+        None => None,
+    };
+    let rewards_before = value_of(matches, "rewards_before");
     Ok(CliCommandInfo {
-        command: CliCommand::ShowStakeHistory { use_lamports_unit },
+        command: CliCommand::ShowStakeHistory {
+            use_lamports_unit,
+            account,
+            limit,
+            before,
+            rewards_before,
+        },
         signers: vec![],
     })
 }
@@ -1128,6 +1303,7 @@ pub fn process_deactivate_stake_account(
     config: &CliConfig,
     stake_account_pubkey: &Pubkey,
     stake_authority: SignerIndex,
+    deactivate_delinquent: bool,
     sign_only: bool,
     dump_transaction_message: bool,
     blockhash_query: &BlockhashQuery,
@@ -1138,11 +1314,61 @@ pub fn process_deactivate_stake_account(
 ) -> ProcessResult {
     let (recent_blockhash, fee_calculator) =
         blockhash_query.get_blockhash_and_fee_calculator(rpc_client, config.commitment)?;
-    let stake_authority = config.signers[stake_authority];
-    let ixs = vec![stake_instruction::deactivate_stake(
-        stake_account_pubkey,
-        &stake_authority.pubkey(),
-    )]
+    let ixs = vec![if deactivate_delinquent {
+        let stake_account = rpc_client.get_account(stake_account_pubkey)?;
+        if stake_account.owner != solana_stake_program::id() {
+            return Err(CliError::BadParameter(format!(
+                "{} is not a stake account",
+                stake_account_pubkey,
+            ))
+            .into());
+        }
+        let vote_account_pubkey = match stake_account.state() {
+            Ok(StakeState::Stake(_, stake)) => stake.delegation.voter_pubkey,
+            _ => {
+                return Err(CliError::BadParameter(format!(
+                    "{} is not a delegated stake account",
+                    stake_account_pubkey,
+                ))
+                .into())
+            }
+        };
+
+        let current_epoch = rpc_client.get_epoch_info()?.epoch;
+        let (_, vote_state) =
+            get_vote_account(rpc_client, &vote_account_pubkey, config.commitment)?;
+        if !eligible_for_deactivate_delinquent(vote_state.epoch_credits(), current_epoch) {
+            return Err(CliError::BadParameter(format!(
+                "Stake has not been delinquent for {} epochs",
+                MINIMUM_DELINQUENT_EPOCHS_FOR_DEACTIVATION,
+            ))
+            .into());
+        }
+
+        // Any vote account that voted in each of the last epochs proves that
+        // the cluster has been making progress
+        let reference_vote_account_pubkey = rpc_client
+            .get_vote_accounts()?
+            .current
+            .into_iter()
+            .find(|vote_account_info| {
+                acceptable_reference_epoch_credits(&vote_account_info.epoch_credits, current_epoch)
+            })
+            .ok_or_else(|| {
+                CliError::RpcRequestError("Unable to find a reference vote account".to_string())
+            })?
+            .vote_pubkey
+            .parse()?;
+
+        stake_instruction::deactivate_delinquent_stake(
+            stake_account_pubkey,
+            &vote_account_pubkey,
+            &reference_vote_account_pubkey,
+        )
+    } else {
+        let stake_authority = config.signers[stake_authority];
+        stake_instruction::deactivate_stake(stake_account_pubkey, &stake_authority.pubkey())
+    }]
     .with_memo(memo);
     let nonce_authority = config.signers[nonce_authority];
     let fee_payer = config.signers[fee_payer];
@@ -1846,7 +2072,22 @@ pub fn process_show_stake_history(
     rpc_client: &RpcClient,
     config: &CliConfig,
     use_lamports_unit: bool,
+    account: Option<&Pubkey>,
+    limit: usize,
+    before: Option<Signature>,
+    rewards_before: Option<Epoch>,
 ) -> ProcessResult {
+    if let Some(account) = account {
+        return process_show_stake_account_history(
+            rpc_client,
+            config,
+            account,
+            use_lamports_unit,
+            limit,
+            before,
+            rewards_before,
+        );
+    }
     let stake_history_account = rpc_client.get_account(&stake_history::id())?;
     let stake_history =
         from_account::<StakeHistory, _>(&stake_history_account).ok_or_else(|| {
@@ -1864,6 +2105,36 @@ pub fn process_show_stake_history(
     Ok(config.output_format.formatted_string(&stake_history_output))
 }
 
+fn process_show_stake_account_history(
+    rpc_client: &RpcClient,
+    config: &CliConfig,
+    stake_account_pubkey: &Pubkey,
+    use_lamports_unit: bool,
+    limit: usize,
+    before: Option<Signature>,
+    rewards_before: Option<Epoch>,
+) -> ProcessResult {
+    let history = rpc_client.get_stake_account_history_with_config(
+        stake_account_pubkey,
+        RpcStakeAccountHistoryConfig {
+            before: before.map(|signature| signature.to_string()),
+            until: None,
+            limit: Some(limit),
+            rewards_before,
+            commitment: Some(config.commitment),
+        },
+    )?;
+    let history_output = CliStakeAccountHistory {
+        address: stake_account_pubkey.to_string(),
+        events: history.events,
+        rewards: history.rewards,
+        oldest_signature: history.oldest_signature,
+        oldest_reward_epoch: history.oldest_reward_epoch,
+        use_lamports_unit,
+    };
+    Ok(config.output_format.formatted_string(&history_output))
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn process_delegate_stake(
     rpc_client: &RpcClient,
@@ -1887,51 +2158,7 @@ pub fn process_delegate_stake(
     let stake_authority = config.signers[stake_authority];
 
     if !sign_only {
-        // Sanity check the vote account to ensure it is attached to a validator that has recently
-        // voted at the tip of the ledger
-        let vote_account_data = rpc_client
-            .get_account(vote_account_pubkey)
-            .map_err(|err| {
-                CliError::RpcRequestError(format!(
-                    "Vote account not found: {}. error: {}",
-                    vote_account_pubkey, err,
-                ))
-            })?
-            .data;
-
-        let vote_state = VoteState::deserialize(&vote_account_data).map_err(|_| {
-            CliError::RpcRequestError(
-                "Account data could not be deserialized to vote state".to_string(),
-            )
-        })?;
-
-        let sanity_check_result = match vote_state.root_slot {
-            None => Err(CliError::BadParameter(
-                "Unable to delegate. Vote account has no root slot".to_string(),
-            )),
-            Some(root_slot) => {
-                let min_root_slot = rpc_client
-                    .get_slot()?
-                    .saturating_sub(DELINQUENT_VALIDATOR_SLOT_DISTANCE);
-                if root_slot < min_root_slot {
-                    Err(CliError::DynamicProgramError(format!(
-                        "Unable to delegate.  Vote account appears delinquent \
-                                 because its current root slot, {}, is less than {}",
-                        root_slot, min_root_slot
-                    )))
-                } else {
-                    Ok(())
-                }
-            }
-        };
-
-        if let Err(err) = &sanity_check_result {
-            if !force {
-                sanity_check_result?;
-            } else {
-                println!("--force supplied, ignoring: {}", err);
-            }
-        }
+        check_vote_account_for_delegation(rpc_client, vote_account_pubkey, force)?;
     }
 
     let (recent_blockhash, fee_calculator) =
@@ -1989,6 +2216,200 @@ pub fn process_delegate_stake(
     }
 }
 
+/// Sanity check the vote account to ensure it is attached to a validator that has recently voted
+/// at the tip of the ledger
+fn check_vote_account_for_delegation(
+    rpc_client: &RpcClient,
+    vote_account_pubkey: &Pubkey,
+    force: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let vote_account_data = rpc_client
+        .get_account(vote_account_pubkey)
+        .map_err(|err| {
+            CliError::RpcRequestError(format!(
+                "Vote account not found: {}. error: {}",
+                vote_account_pubkey, err,
+            ))
+        })?
+        .data;
+
+    let vote_state = VoteState::deserialize(&vote_account_data).map_err(|_| {
+        CliError::RpcRequestError(
+            "Account data could not be deserialized to vote state".to_string(),
+        )
+    })?;
+
+    let sanity_check_result = match vote_state.root_slot {
+        None => Err(CliError::BadParameter(
+            "Unable to delegate. Vote account has no root slot".to_string(),
+        )),
+        Some(root_slot) => {
+            let min_root_slot = rpc_client
+                .get_slot()?
+                .saturating_sub(DELINQUENT_VALIDATOR_SLOT_DISTANCE);
+            if root_slot < min_root_slot {
+                Err(CliError::DynamicProgramError(format!(
+                    "Unable to delegate.  Vote account appears delinquent \
+                             because its current root slot, {}, is less than {}",
+                    root_slot, min_root_slot
+                )))
+            } else {
+                Ok(())
+            }
+        }
+    };
+
+    if let Err(err) = &sanity_check_result {
+        if !force {
+            sanity_check_result?;
+        } else {
+            println!("--force supplied, ignoring: {}", err);
+        }
+    }
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn process_redelegate_stake(
+    rpc_client: &RpcClient,
+    config: &CliConfig,
+    stake_account_pubkey: &Pubkey,
+    vote_account_pubkey: &Pubkey,
+    stake_authority: SignerIndex,
+    force: bool,
+    sign_only: bool,
+    dump_transaction_message: bool,
+    blockhash_query: &BlockhashQuery,
+    nonce_account: Option<Pubkey>,
+    nonce_authority: SignerIndex,
+    memo: Option<&String>,
+    redelegation_stake_account: SignerIndex,
+    redelegation_stake_account_seed: &Option<String>,
+    fee_payer: SignerIndex,
+) -> ProcessResult {
+    let redelegation_stake_account = config.signers[redelegation_stake_account];
+    let fee_payer = config.signers[fee_payer];
+
+    if redelegation_stake_account_seed.is_none() {
+        check_unique_pubkeys(
+            (&fee_payer.pubkey(), "fee-payer keypair".to_string()),
+            (
+                &redelegation_stake_account.pubkey(),
+                "redelegation_stake_account".to_string(),
+            ),
+        )?;
+    }
+    check_unique_pubkeys(
+        (&fee_payer.pubkey(), "fee-payer keypair".to_string()),
+        (&stake_account_pubkey, "stake_account".to_string()),
+    )?;
+    check_unique_pubkeys(
+        (&stake_account_pubkey, "stake_account".to_string()),
+        (
+            &redelegation_stake_account.pubkey(),
+            "redelegation_stake_account".to_string(),
+        ),
+    )?;
+
+    let stake_authority = config.signers[stake_authority];
+
+    let redelegation_stake_account_address = if let Some(seed) = redelegation_stake_account_seed {
+        Pubkey::create_with_seed(
+            &redelegation_stake_account.pubkey(),
+            &seed,
+            &solana_stake_program::id(),
+        )?
+    } else {
+        redelegation_stake_account.pubkey()
+    };
+
+    if !sign_only {
+        if let Ok(stake_account) = rpc_client.get_account(&redelegation_stake_account_address) {
+            let err_msg = if stake_account.owner == solana_stake_program::id() {
+                format!(
+                    "Stake account {} already exists",
+                    redelegation_stake_account_address
+                )
+            } else {
+                format!(
+                    "Account {} already exists and is not a stake account",
+                    redelegation_stake_account_address
+                )
+            };
+            return Err(CliError::BadParameter(err_msg).into());
+        }
+
+        check_vote_account_for_delegation(rpc_client, vote_account_pubkey, force)?;
+    }
+
+    let (recent_blockhash, fee_calculator) =
+        blockhash_query.get_blockhash_and_fee_calculator(rpc_client, config.commitment)?;
+
+    let ixs = if let Some(seed) = redelegation_stake_account_seed {
+        stake_instruction::redelegate_with_seed(
+            &stake_account_pubkey,
+            &stake_authority.pubkey(),
+            &vote_account_pubkey,
+            &redelegation_stake_account_address,
+            &redelegation_stake_account.pubkey(),
+            seed,
+        )
+        .with_memo(memo)
+    } else {
+        stake_instruction::redelegate(
+            &stake_account_pubkey,
+            &stake_authority.pubkey(),
+            &vote_account_pubkey,
+            &redelegation_stake_account_address,
+        )
+        .with_memo(memo)
+    };
+
+    let nonce_authority = config.signers[nonce_authority];
+
+    let message = if let Some(nonce_account) = &nonce_account {
+        Message::new_with_nonce(
+            ixs,
+            Some(&fee_payer.pubkey()),
+            nonce_account,
+            &nonce_authority.pubkey(),
+        )
+    } else {
+        Message::new(&ixs, Some(&fee_payer.pubkey()))
+    };
+    let mut tx = Transaction::new_unsigned(message);
+
+    if sign_only {
+        tx.try_partial_sign(&config.signers, recent_blockhash)?;
+        return_signers_with_config(
+            &tx,
+            &config.output_format,
+            &ReturnSignersConfig {
+                dump_transaction_message,
+            },
+        )
+    } else {
+        tx.try_sign(&config.signers, recent_blockhash)?;
+        if let Some(nonce_account) = &nonce_account {
+            let nonce_account = nonce_utils::get_account_with_commitment(
+                rpc_client,
+                nonce_account,
+                config.commitment,
+            )?;
+            check_nonce_account(&nonce_account, &nonce_authority.pubkey(), &recent_blockhash)?;
+        }
+        check_account_for_fee_with_commitment(
+            rpc_client,
+            &tx.message.account_keys[0],
+            &fee_calculator,
+            &tx.message,
+            config.commitment,
+        )?;
+        let result = rpc_client.send_and_confirm_transaction_with_spinner(&tx);
+        log_instruction_custom_error::<StakeError>(result, &config)
+    }
+}
+
 pub fn is_stake_program_v2_enabled(
     rpc_client: &RpcClient,
 ) -> Result<bool, Box<dyn std::error::Error>> {
@@ -3179,6 +3600,7 @@ mod tests {
                 command: CliCommand::DeactivateStake {
                     stake_account_pubkey,
                     stake_authority: 0,
+                    deactivate_delinquent: false,
                     sign_only: false,
                     dump_transaction_message: false,
                     blockhash_query: BlockhashQuery::default(),
@@ -3191,6 +3613,46 @@ mod tests {
             }
         );
 
+        // Test DeactivateStake Subcommand w/ delinquent
+        let (fee_payer_keypair_file, mut fee_payer_tmp_file) = make_tmp_file();
+        let fee_payer_keypair = Keypair::new();
+        write_keypair(&fee_payer_keypair, fee_payer_tmp_file.as_file_mut()).unwrap();
+        let test_deactivate_stake = test_commands.clone().get_matches_from(vec![
+            "test",
+            "deactivate-stake",
+            &stake_account_string,
+            "--delinquent",
+            "--fee-payer",
+            &fee_payer_keypair_file,
+        ]);
+        assert_eq!(
+            parse_command(&test_deactivate_stake, &default_signer, &mut None).unwrap(),
+            CliCommandInfo {
+                command: CliCommand::DeactivateStake {
+                    stake_account_pubkey,
+                    stake_authority: 0,
+                    deactivate_delinquent: true,
+                    sign_only: false,
+                    dump_transaction_message: false,
+                    blockhash_query: BlockhashQuery::default(),
+                    nonce_account: None,
+                    nonce_authority: 0,
+                    memo: None,
+                    fee_payer: 0,
+                },
+                signers: vec![read_keypair_file(&fee_payer_keypair_file).unwrap().into()],
+            }
+        );
+        let test_deactivate_stake = test_commands.clone().get_matches_from_safe(vec![
+            "test",
+            "deactivate-stake",
+            &stake_account_string,
+            "--delinquent",
+            "--stake-authority",
+            &stake_authority_keypair_file,
+        ]);
+        assert!(test_deactivate_stake.is_err());
+
         // Test DeactivateStake Subcommand w/ authority
         let test_deactivate_stake = test_commands.clone().get_matches_from(vec![
             "test",
@@ -3205,6 +3667,7 @@ mod tests {
                 command: CliCommand::DeactivateStake {
                     stake_account_pubkey,
                     stake_authority: 1,
+                    deactivate_delinquent: false,
                     sign_only: false,
                     dump_transaction_message: false,
                     blockhash_query: BlockhashQuery::default(),
@@ -3238,6 +3701,7 @@ mod tests {
                 command: CliCommand::DeactivateStake {
                     stake_account_pubkey,
                     stake_authority: 0,
+                    deactivate_delinquent: false,
                     sign_only: false,
                     dump_transaction_message: false,
                     blockhash_query: BlockhashQuery::FeeCalculator(
@@ -3267,6 +3731,7 @@ mod tests {
                 command: CliCommand::DeactivateStake {
                     stake_account_pubkey,
                     stake_authority: 0,
+                    deactivate_delinquent: false,
                     sign_only: true,
                     dump_transaction_message: false,
                     blockhash_query: BlockhashQuery::None(blockhash),
@@ -3300,6 +3765,7 @@ mod tests {
                 command: CliCommand::DeactivateStake {
                     stake_account_pubkey,
                     stake_authority: 0,
+                    deactivate_delinquent: false,
                     sign_only: false,
                     dump_transaction_message: false,
                     blockhash_query: BlockhashQuery::FeeCalculator(
@@ -3345,6 +3811,7 @@ mod tests {
                 command: CliCommand::DeactivateStake {
                     stake_account_pubkey,
                     stake_authority: 0,
+                    deactivate_delinquent: false,
                     sign_only: false,
                     dump_transaction_message: false,
                     blockhash_query: BlockhashQuery::FeeCalculator(
@@ -3378,6 +3845,7 @@ mod tests {
                 command: CliCommand::DeactivateStake {
                     stake_account_pubkey,
                     stake_authority: 0,
+                    deactivate_delinquent: false,
                     sign_only: false,
                     dump_transaction_message: false,
                     blockhash_query: BlockhashQuery::All(blockhash_query::Source::Cluster),
@@ -3531,5 +3999,143 @@ mod tests {
                 signers: vec![read_keypair_file(&default_keypair_file).unwrap().into(),],
             }
         );
+
+        // Test RedelegateStake SubCommand
+        let (redelegation_stake_account_keypair_file, mut tmp_file) = make_tmp_file();
+        let redelegation_stake_account_keypair = Keypair::new();
+        write_keypair(&redelegation_stake_account_keypair, tmp_file.as_file_mut()).unwrap();
+
+        let stake_account_pubkey = solana_sdk::pubkey::new_rand();
+        let vote_account_pubkey = solana_sdk::pubkey::new_rand();
+        let test_redelegate_stake = test_commands.clone().get_matches_from(vec![
+            "test",
+            "redelegate-stake",
+            &stake_account_pubkey.to_string(),
+            &vote_account_pubkey.to_string(),
+            &redelegation_stake_account_keypair_file,
+        ]);
+        assert_eq!(
+            parse_command(&test_redelegate_stake, &default_signer, &mut None).unwrap(),
+            CliCommandInfo {
+                command: CliCommand::RedelegateStake {
+                    stake_account_pubkey,
+                    vote_account_pubkey,
+                    stake_authority: 0,
+                    force: false,
+                    sign_only: false,
+                    dump_transaction_message: false,
+                    blockhash_query: BlockhashQuery::default(),
+                    nonce_account: None,
+                    nonce_authority: 0,
+                    memo: None,
+                    redelegation_stake_account: 1,
+                    seed: None,
+                    fee_payer: 0,
+                },
+                signers: vec![
+                    read_keypair_file(&default_keypair_file).unwrap().into(),
+                    read_keypair_file(&redelegation_stake_account_keypair_file)
+                        .unwrap()
+                        .into(),
+                ],
+            }
+        );
+
+        // Test RedelegateStake SubCommand w/ seed and authority
+        let test_redelegate_stake = test_commands.clone().get_matches_from(vec![
+            "test",
+            "redelegate-stake",
+            &stake_account_pubkey.to_string(),
+            &vote_account_pubkey.to_string(),
+            &redelegation_stake_account_keypair_file,
+            "--seed",
+            "redelegation",
+            "--stake-authority",
+            &stake_authority_keypair_file,
+        ]);
+        assert_eq!(
+            parse_command(&test_redelegate_stake, &default_signer, &mut None).unwrap(),
+            CliCommandInfo {
+                command: CliCommand::RedelegateStake {
+                    stake_account_pubkey,
+                    vote_account_pubkey,
+                    stake_authority: 1,
+                    force: false,
+                    sign_only: false,
+                    dump_transaction_message: false,
+                    blockhash_query: BlockhashQuery::default(),
+                    nonce_account: None,
+                    nonce_authority: 0,
+                    memo: None,
+                    redelegation_stake_account: 2,
+                    seed: Some("redelegation".to_string()),
+                    fee_payer: 0,
+                },
+                signers: vec![
+                    read_keypair_file(&default_keypair_file).unwrap().into(),
+                    read_keypair_file(&stake_authority_keypair_file)
+                        .unwrap()
+                        .into(),
+                    read_keypair_file(&redelegation_stake_account_keypair_file)
+                        .unwrap()
+                        .into(),
+                ],
+            }
+        );
+
+        // Test ShowStakeHistory SubCommand
+        let test_stake_history = test_commands
+            .clone()
+            .get_matches_from(vec!["test", "stake-history"]);
+        assert_eq!(
+            parse_command(&test_stake_history, &default_signer, &mut None).unwrap(),
+            CliCommandInfo {
+                command: CliCommand::ShowStakeHistory {
+                    use_lamports_unit: false,
+                    account: None,
+                    limit: 100,
+                    before: None,
+                    rewards_before: None,
+                },
+                signers: vec![],
+            }
+        );
+
+        // Test ShowStakeHistory SubCommand w/ account
+        let before = Signature::new(&[1; 64]);
+        let test_stake_history = test_commands.clone().get_matches_from(vec![
+            "test",
+            "stake-history",
+            "--account",
+            &stake_account_pubkey.to_string(),
+            "--limit",
+            "10",
+            "--before",
+            &before.to_string(),
+            "--rewards-before",
+            "7",
+        ]);
+        assert_eq!(
+            parse_command(&test_stake_history, &default_signer, &mut None).unwrap(),
+            CliCommandInfo {
+                command: CliCommand::ShowStakeHistory {
+                    use_lamports_unit: false,
+                    account: Some(stake_account_pubkey),
+                    limit: 10,
+                    before: Some(before),
+                    rewards_before: Some(7),
+                },
+                signers: vec![],
+            }
+        );
+
+        // --limit requires --account
+        let test_stake_history = test_commands.clone().get_matches_from_safe(vec![
+            "test",
+            "stake-history",
+            "--limit",
+            "10",
+        ]);
+        assert!(test_stake_history.is_err());
     }
 }
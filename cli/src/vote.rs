@@ -14,17 +14,20 @@ use solana_clap_utils::{
     keypair::{DefaultSigner, SignerIndex},
     memo::{memo_arg, MEMO_ARG},
 };
-use solana_cli_output::{CliEpochVotingHistory, CliLockout, CliVoteAccount};
+use solana_cli_output::{
+    CliCommissionIncreaseWindow, CliEpochVotingHistory, CliLockout, CliVoteAccount,
+};
 use solana_client::rpc_client::RpcClient;
 use solana_remote_wallet::remote_wallet::RemoteWalletManager;
 use solana_sdk::{
-    account::Account, commitment_config::CommitmentConfig, message::Message,
+    account::Account, clock::Slot, commitment_config::CommitmentConfig,
+    epoch_schedule::EpochSchedule, feature, feature_set, message::Message,
     native_token::lamports_to_sol, pubkey::Pubkey, system_instruction::SystemError,
     transaction::Transaction,
 };
 use solana_vote_program::{
     vote_instruction::{self, withdraw, VoteError},
-    vote_state::{VoteAuthorize, VoteInit, VoteState},
+    vote_state::{is_commission_increase_allowed, VoteAuthorize, VoteInit, VoteState},
 };
 use std::sync::Arc;
 
@@ -641,6 +644,20 @@ pub fn process_vote_update_commission(
     memo: Option<&String>,
 ) -> ProcessResult {
     let authorized_withdrawer = config.signers[withdraw_authority];
+    let (_, vote_state) = get_vote_account(rpc_client, vote_account_pubkey, config.commitment)?;
+    if commission > vote_state.commission {
+        if let Some(window) = get_commission_increase_window(rpc_client, config)? {
+            if !window.allowed {
+                return Err(CliError::BadParameter(format!(
+                    "Commission increases are only allowed in the first half of an epoch, \
+                    try again at slot {}",
+                    window.next_allowed_slot
+                ))
+                .into());
+            }
+        }
+    }
+
     let (recent_blockhash, fee_calculator) = rpc_client.get_recent_blockhash()?;
     let ixs = vec![vote_instruction::update_commission(
         vote_account_pubkey,
@@ -663,7 +680,43 @@ pub fn process_vote_update_commission(
     log_instruction_custom_error::<VoteError>(result, &config)
 }
 
-fn get_vote_account(
+fn commission_increase_window(
+    slot: Slot,
+    epoch_schedule: &EpochSchedule,
+) -> CliCommissionIncreaseWindow {
+    let (epoch, _) = epoch_schedule.get_epoch_and_slot_index(slot);
+    CliCommissionIncreaseWindow {
+        allowed: is_commission_increase_allowed(slot, epoch_schedule),
+        last_allowed_slot: epoch_schedule.get_first_slot_in_epoch(epoch)
+            + epoch_schedule.get_slots_in_epoch(epoch) / 2,
+        next_allowed_slot: epoch_schedule.get_first_slot_in_epoch(epoch + 1),
+    }
+}
+
+/// The window for commission increases in the current epoch, or `None` if the
+/// cluster doesn't restrict them yet
+fn get_commission_increase_window(
+    rpc_client: &RpcClient,
+    config: &CliConfig,
+) -> Result<Option<CliCommissionIncreaseWindow>, Box<dyn std::error::Error>> {
+    let restricted = rpc_client
+        .get_account_with_commitment(
+            &feature_set::commission_updates_only_allowed_in_first_half_of_epoch::id(),
+            config.commitment,
+        )?
+        .value
+        .and_then(|feature_account| feature::from_account(&feature_account))
+        .and_then(|feature| feature.activated_at)
+        .is_some();
+    if !restricted {
+        return Ok(None);
+    }
+    let epoch_schedule = rpc_client.get_epoch_schedule()?;
+    let slot = rpc_client.get_slot_with_commitment(config.commitment)?;
+    Ok(Some(commission_increase_window(slot, &epoch_schedule)))
+}
+
+pub(crate) fn get_vote_account(
     rpc_client: &RpcClient,
     vote_account_pubkey: &Pubkey,
     commitment_config: CommitmentConfig,
@@ -740,6 +793,7 @@ pub fn process_show_vote_account(
         authorized_withdrawer: vote_state.authorized_withdrawer.to_string(),
         credits: vote_state.credits(),
         commission: vote_state.commission,
+        commission_increase_window: get_commission_increase_window(rpc_client, config)?,
         root_slot: vote_state.root_slot,
         recent_timestamp: vote_state.last_timestamp.clone(),
         votes,
@@ -1130,4 +1184,22 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn test_commission_increase_window() {
+        let epoch_schedule = EpochSchedule::without_warmup();
+        let slots_per_epoch = epoch_schedule.slots_per_epoch;
+
+        let window = commission_increase_window(slots_per_epoch + 1, &epoch_schedule);
+        assert!(window.allowed);
+        assert_eq!(
+            window.last_allowed_slot,
+            slots_per_epoch + slots_per_epoch / 2
+        );
+        assert_eq!(window.next_allowed_slot, 2 * slots_per_epoch);
+
+        let window = commission_increase_window(2 * slots_per_epoch - 1, &epoch_schedule);
+        assert!(!window.allowed);
+        assert_eq!(window.next_allowed_slot, 2 * slots_per_epoch);
+    }
 }
@@ -186,6 +186,7 @@ fn test_seed_stake_delegation_and_deactivation() {
     config_validator.command = CliCommand::DeactivateStake {
         stake_account_pubkey: stake_address,
         stake_authority: 0,
+        deactivate_delinquent: false,
         sign_only: false,
         dump_transaction_message: false,
         blockhash_query: BlockhashQuery::default(),
@@ -265,6 +266,7 @@ fn test_stake_delegation_and_deactivation() {
     config_validator.command = CliCommand::DeactivateStake {
         stake_account_pubkey: stake_keypair.pubkey(),
         stake_authority: 0,
+        deactivate_delinquent: false,
         sign_only: false,
         dump_transaction_message: false,
         blockhash_query: BlockhashQuery::default(),
@@ -386,6 +388,7 @@ fn test_offline_stake_delegation_and_deactivation() {
     config_offline.command = CliCommand::DeactivateStake {
         stake_account_pubkey: stake_keypair.pubkey(),
         stake_authority: 0,
+        deactivate_delinquent: false,
         sign_only: true,
         dump_transaction_message: false,
         blockhash_query: BlockhashQuery::None(blockhash),
@@ -404,6 +407,7 @@ fn test_offline_stake_delegation_and_deactivation() {
     config_payer.command = CliCommand::DeactivateStake {
         stake_account_pubkey: stake_keypair.pubkey(),
         stake_authority: 0,
+        deactivate_delinquent: false,
         sign_only: false,
         dump_transaction_message: false,
         blockhash_query: BlockhashQuery::FeeCalculator(blockhash_query::Source::Cluster, blockhash),
@@ -520,6 +524,7 @@ fn test_nonced_stake_delegation_and_deactivation() {
     config.command = CliCommand::DeactivateStake {
         stake_account_pubkey: stake_keypair.pubkey(),
         stake_authority: 0,
+        deactivate_delinquent: false,
         sign_only: false,
         dump_transaction_message: false,
         blockhash_query: BlockhashQuery::FeeCalculator(
@@ -8,7 +8,7 @@ use {
             RpcConfirmedBlockConfig, RpcConfirmedTransactionConfig, RpcEpochConfig,
             RpcGetConfirmedSignaturesForAddress2Config, RpcLargestAccountsConfig,
             RpcProgramAccountsConfig, RpcSendTransactionConfig, RpcSimulateTransactionConfig,
-            RpcTokenAccountsFilter,
+            RpcStakeAccountHistoryConfig, RpcTokenAccountsFilter,
         },
         rpc_request::{RpcError, RpcRequest, RpcResponseErrorData, TokenAccountsFilter},
         rpc_response::*,
@@ -444,6 +444,30 @@ impl RpcClient {
         )
     }
 
+    pub fn get_stake_account_history(
+        &self,
+        stake_account: &Pubkey,
+    ) -> ClientResult<RpcStakeAccountHistory> {
+        self.get_stake_account_history_with_config(
+            stake_account,
+            RpcStakeAccountHistoryConfig {
+                commitment: Some(self.commitment_config),
+                ..RpcStakeAccountHistoryConfig::default()
+            },
+        )
+    }
+
+    pub fn get_stake_account_history_with_config(
+        &self,
+        stake_account: &Pubkey,
+        config: RpcStakeAccountHistoryConfig,
+    ) -> ClientResult<RpcStakeAccountHistory> {
+        self.send(
+            RpcRequest::GetStakeAccountHistory,
+            json!([stake_account.to_string(), config]),
+        )
+    }
+
     pub fn supply(&self) -> RpcResult<RpcSupply> {
         self.supply_with_commitment(self.commitment_config)
     }
@@ -985,6 +1009,23 @@ impl RpcClient {
         )
     }
 
+    /// Returns the minimum number of lamports a stake account may delegate
+    pub fn get_stake_minimum_delegation(&self) -> ClientResult<u64> {
+        Ok(self
+            .get_stake_minimum_delegation_with_commitment(self.commitment_config)?
+            .value)
+    }
+
+    pub fn get_stake_minimum_delegation_with_commitment(
+        &self,
+        commitment_config: CommitmentConfig,
+    ) -> RpcResult<u64> {
+        self.send(
+            RpcRequest::GetStakeMinimumDelegation,
+            json!([self.maybe_map_commitment(commitment_config)?]),
+        )
+    }
+
     pub fn get_program_accounts(&self, pubkey: &Pubkey) -> ClientResult<Vec<(Pubkey, Account)>> {
         self.get_program_accounts_with_config(
             pubkey,
@@ -1,5 +1,6 @@
 use {
     crate::client_error,
+    serde_json::Value,
     solana_account_decoder::{parse_token::UiTokenAmount, UiAccount},
     solana_sdk::{
         clock::{Epoch, Slot, UnixTimestamp},
@@ -9,6 +10,7 @@ use {
     },
     solana_transaction_status::{
         ConfirmedTransactionStatusWithSignature, TransactionConfirmationStatus,
+        UiTransactionReturnData,
     },
     std::{collections::HashMap, fmt, net::SocketAddr},
 };
@@ -292,6 +294,10 @@ pub struct RpcSignatureConfirmation {
 pub struct RpcSimulateTransactionResult {
     pub err: Option<TransactionError>,
     pub logs: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub return_data: Option<UiTransactionReturnData>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub units_consumed: Option<u64>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -371,6 +377,35 @@ pub struct RpcInflationReward {
     pub post_balance: u64, // lamports
 }
 
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcStakeAccountEvent {
+    pub signature: String,
+    pub slot: Slot,
+    pub block_time: Option<UnixTimestamp>,
+    pub err: Option<TransactionError>,
+    pub instruction_index: u8,
+    pub inner_instruction_index: Option<usize>,
+    #[serde(rename = "type")]
+    pub event_type: String,
+    pub info: Value,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcStakeAccountHistory {
+    /// Stake instructions that reference the account, newest first
+    pub events: Vec<RpcStakeAccountEvent>,
+    /// Inflation rewards paid to the account over the newest epochs spanned by `events`,
+    /// newest first
+    pub rewards: Vec<RpcInflationReward>,
+    /// The oldest signature scanned, if more history remains; pass as `before` to continue
+    pub oldest_signature: Option<String>,
+    /// The oldest epoch whose rewards were looked up, if older epochs of the span remain; pass
+    /// as `rewards_before` to continue
+    pub oldest_reward_epoch: Option<Epoch>,
+}
+
 impl From<ConfirmedTransactionStatusWithSignature> for RpcConfirmedTransactionStatusWithSignature {
     fn from(value: ConfirmedTransactionStatusWithSignature) -> Self {
         let ConfirmedTransactionStatusWithSignature {
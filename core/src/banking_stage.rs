@@ -38,6 +38,7 @@ use solana_sdk::{
         Slot, DEFAULT_TICKS_PER_SLOT, MAX_PROCESSING_AGE, MAX_TRANSACTION_FORWARDING_DELAY,
         MAX_TRANSACTION_FORWARDING_DELAY_GPU,
     },
+    feature_set::FeatureSet,
     message::Message,
     poh_config::PohConfig,
     pubkey::Pubkey,
@@ -255,7 +256,6 @@ impl BankingStage {
         // Single thread to generate entries from many banks.
         // This thread talks to poh_service and broadcasts the entries once they have been recorded.
         // Once an entry has been recorded, its blockhash is registered with the bank.
-        let my_pubkey = cluster_info.id();
         let duplicates = Arc::new(Mutex::new((
             LruCache::new(DEFAULT_LRU_SIZE),
             PacketHasher::default(),
@@ -280,7 +280,6 @@ impl BankingStage {
                     .name("solana-banking-stage-tx".to_string())
                     .spawn(move || {
                         Self::process_loop(
-                            my_pubkey,
                             &verified_receiver,
                             &poh_recorder,
                             &cluster_info,
@@ -591,7 +590,6 @@ impl BankingStage {
 
     #[allow(clippy::too_many_arguments)]
     pub fn process_loop(
-        my_pubkey: Pubkey,
         verified_receiver: &CrossbeamReceiver<Vec<Packets>>,
         poh_recorder: &Arc<Mutex<PohRecorder>>,
         cluster_info: &ClusterInfo,
@@ -608,6 +606,8 @@ impl BankingStage {
         let mut buffered_packets = VecDeque::with_capacity(batch_limit);
         let banking_stage_stats = BankingStageStats::new(id);
         loop {
+            // The identity may be swapped at runtime, so look it up on every pass
+            let my_pubkey = cluster_info.id();
             while !buffered_packets.is_empty() {
                 let decision = Self::process_buffered_packets(
                     &my_pubkey,
@@ -765,6 +765,8 @@ impl BankingStage {
             results,
             inner_instructions,
             transaction_logs,
+            transaction_return_data,
+            transaction_compute_units,
             mut retryable_txs,
             tx_count,
             signature_count,
@@ -822,6 +824,8 @@ impl BankingStage {
                     TransactionTokenBalancesSet::new(pre_token_balances, post_token_balances),
                     inner_instructions,
                     transaction_logs,
+                    transaction_return_data,
+                    transaction_compute_units,
                 );
             }
         }
@@ -985,21 +989,19 @@ impl BankingStage {
     }
 
     // This function deserializes packets into transactions, computes the blake3 hash of transaction messages,
-    // and verifies secp256k1 instructions. A list of valid transactions are returned with their message hashes
-    // and packet indexes.
+    // and verifies precompiled program instructions. A list of valid transactions are returned with their
+    // message hashes and packet indexes.
     fn transactions_from_packets(
         msgs: &Packets,
         transaction_indexes: &[usize],
-        secp256k1_program_enabled: bool,
+        feature_set: &FeatureSet,
     ) -> (Vec<HashedTransaction<'static>>, Vec<usize>) {
         transaction_indexes
             .iter()
             .filter_map(|tx_index| {
                 let p = &msgs.packets[*tx_index];
                 let tx: Transaction = limited_deserialize(&p.data[0..p.meta.size]).ok()?;
-                if secp256k1_program_enabled {
-                    tx.verify_precompiles().ok()?;
-                }
+                tx.verify_precompiles_with_features(feature_set).ok()?;
                 let message_bytes = Self::packet_message(p)?;
                 let message_hash = Message::hash_raw_message(message_bytes);
                 Some((
@@ -1060,11 +1062,8 @@ impl BankingStage {
         banking_stage_stats: &BankingStageStats,
     ) -> (usize, usize, Vec<usize>) {
         let mut packet_conversion_time = Measure::start("packet_conversion");
-        let (transactions, transaction_to_packet_indexes) = Self::transactions_from_packets(
-            msgs,
-            &packet_indexes,
-            bank.secp256k1_program_enabled(),
-        );
+        let (transactions, transaction_to_packet_indexes) =
+            Self::transactions_from_packets(msgs, &packet_indexes, &bank.feature_set);
         packet_conversion_time.stop();
 
         debug!(
@@ -1131,11 +1130,8 @@ impl BankingStage {
             }
         }
 
-        let (transactions, transaction_to_packet_indexes) = Self::transactions_from_packets(
-            msgs,
-            &transaction_indexes,
-            bank.secp256k1_program_enabled(),
-        );
+        let (transactions, transaction_to_packet_indexes) =
+            Self::transactions_from_packets(msgs, &transaction_indexes, &bank.feature_set);
 
         let tx_count = transaction_to_packet_indexes.len();
 
@@ -23,6 +23,7 @@ use crate::{
     },
     data_budget::DataBudget,
     epoch_slots::EpochSlots,
+    gossip_inspect::{CrdsEntry, GossipInspection, GossipTraffic, PruneDirection},
     ping_pong::{self, PingCache, Pong},
     result::{Error, Result},
     weighted_shuffle::weighted_shuffle,
@@ -309,20 +310,20 @@ pub struct ClusterInfo {
     /// The network
     pub gossip: RwLock<CrdsGossip>,
     /// set the keypair that will be used to sign crds values generated. It is unset only in tests.
-    pub(crate) keypair: Arc<Keypair>,
+    keypair: RwLock<Arc<Keypair>>,
     /// Network entrypoints
     entrypoints: RwLock<Vec<ContactInfo>>,
     outbound_budget: DataBudget,
     my_contact_info: RwLock<ContactInfo>,
     ping_cache: RwLock<PingCache>,
-    id: Pubkey,
     stats: GossipStats,
     socket: UdpSocket,
     local_message_pending_push_queue: RwLock<Vec<(CrdsValue, u64)>>,
     contact_debug_interval: u64, // milliseconds, 0 = disabled
     contact_save_interval: u64,  // milliseconds, 0 = disabled
-    instance: NodeInstance,
+    instance: RwLock<NodeInstance>,
     contact_info_path: PathBuf,
+    traffic: GossipTraffic,
 }
 
 impl Default for ClusterInfo {
@@ -577,7 +578,7 @@ impl ClusterInfo {
         let id = contact_info.id;
         let me = Self {
             gossip: RwLock::new(CrdsGossip::default()),
-            keypair,
+            keypair: RwLock::new(keypair),
             entrypoints: RwLock::new(vec![]),
             outbound_budget: DataBudget::default(),
             my_contact_info: RwLock::new(contact_info),
@@ -585,14 +586,14 @@ impl ClusterInfo {
                 GOSSIP_PING_CACHE_TTL,
                 GOSSIP_PING_CACHE_CAPACITY,
             )),
-            id,
             stats: GossipStats::default(),
             socket: UdpSocket::bind("0.0.0.0:0").unwrap(),
             local_message_pending_push_queue: RwLock::new(vec![]),
             contact_debug_interval: DEFAULT_CONTACT_DEBUG_INTERVAL_MILLIS,
-            instance: NodeInstance::new(&mut thread_rng(), id, timestamp()),
+            instance: RwLock::new(NodeInstance::new(&mut thread_rng(), id, timestamp())),
             contact_info_path: PathBuf::default(),
             contact_save_interval: 0, // disabled
+            traffic: GossipTraffic::default(),
         };
         {
             let mut gossip = me.gossip.write().unwrap();
@@ -612,12 +613,11 @@ impl ClusterInfo {
         my_contact_info.id = *new_id;
         ClusterInfo {
             gossip: RwLock::new(gossip),
-            keypair: self.keypair.clone(),
+            keypair: RwLock::new(self.keypair().clone()),
             entrypoints: RwLock::new(self.entrypoints.read().unwrap().clone()),
             outbound_budget: self.outbound_budget.clone_non_atomic(),
             my_contact_info: RwLock::new(my_contact_info),
             ping_cache: RwLock::new(self.ping_cache.read().unwrap().mock_clone()),
-            id: *new_id,
             stats: GossipStats::default(),
             socket: UdpSocket::bind("0.0.0.0:0").unwrap(),
             local_message_pending_push_queue: RwLock::new(
@@ -627,9 +627,10 @@ impl ClusterInfo {
                     .clone(),
             ),
             contact_debug_interval: self.contact_debug_interval,
-            instance: NodeInstance::new(&mut thread_rng(), *new_id, timestamp()),
+            instance: RwLock::new(NodeInstance::new(&mut thread_rng(), *new_id, timestamp())),
             contact_info_path: PathBuf::default(),
             contact_save_interval: 0, // disabled
+            traffic: GossipTraffic::default(),
         }
     }
 
@@ -646,10 +647,10 @@ impl ClusterInfo {
         self.my_contact_info.write().unwrap().wallclock = now;
         let entries: Vec<_> = vec![
             CrdsData::ContactInfo(self.my_contact_info()),
-            CrdsData::NodeInstance(self.instance.with_wallclock(now)),
+            CrdsData::NodeInstance(self.instance.read().unwrap().with_wallclock(now)),
         ]
         .into_iter()
-        .map(|v| CrdsValue::new_signed(v, &self.keypair))
+        .map(|v| CrdsValue::new_signed(v, &self.keypair()))
         .collect();
         {
             let mut local_message_pending_push_queue =
@@ -666,7 +667,7 @@ impl ClusterInfo {
 
     // TODO kill insert_info, only used by tests
     pub fn insert_info(&self, contact_info: ContactInfo) {
-        let value = CrdsValue::new_signed(CrdsData::ContactInfo(contact_info), &self.keypair);
+        let value = CrdsValue::new_signed(CrdsData::ContactInfo(contact_info), &self.keypair());
         let _ = self.gossip.write().unwrap().crds.insert(value, timestamp());
     }
 
@@ -790,7 +791,65 @@ impl ClusterInfo {
     }
 
     pub fn id(&self) -> Pubkey {
-        self.id
+        self.my_contact_info.read().unwrap().id
+    }
+
+    pub fn keypair(&self) -> RwLockReadGuard<Arc<Keypair>> {
+        self.keypair.read().unwrap()
+    }
+
+    /// Swaps the node identity used to sign and advertise gossip values, without a restart.
+    ///
+    /// All of the identity state is replaced while holding every lock that guards it, so a
+    /// reader never observes a mix of the old and new identity.  Gossip is locked first, since
+    /// the gossip paths read the keypair while holding it.
+    pub fn set_keypair(&self, new_keypair: Arc<Keypair>) {
+        let id = new_keypair.pubkey();
+        {
+            let mut gossip = self.gossip.write().unwrap();
+            let mut my_contact_info = self.my_contact_info.write().unwrap();
+            let mut instance = self.instance.write().unwrap();
+            let mut keypair = self.keypair.write().unwrap();
+            gossip.set_self(&id);
+            my_contact_info.id = id;
+            *instance = NodeInstance::new(&mut thread_rng(), id, timestamp());
+            *keypair = new_keypair;
+        }
+
+        self.insert_self();
+        self.push_self(&HashMap::new(), None);
+    }
+
+    /// Returns a read-only snapshot of the gossip state, with crds entries
+    /// optionally filtered by label kind (e.g. `ContactInfo`) and origin.
+    pub fn inspect_gossip(&self, label: Option<&str>, origin: Option<&Pubkey>) -> GossipInspection {
+        let (mut crds, push_active_set) = {
+            let gossip = self.gossip.read().unwrap();
+            let entries: Vec<CrdsEntry> = match origin {
+                Some(origin) => gossip
+                    .crds
+                    .get_records(origin)
+                    .map(CrdsEntry::from)
+                    .collect(),
+                None => gossip.crds.values().map(CrdsEntry::from).collect(),
+            };
+            let push_active_set = gossip
+                .push
+                .active_set_peers()
+                .map(ToString::to_string)
+                .collect();
+            (entries, push_active_set)
+        };
+        if let Some(label) = label {
+            crds.retain(|entry| entry.label_kind() == label);
+        }
+        crds.sort_by(|a, b| (&a.origin, &a.label).cmp(&(&b.origin, &b.label)));
+        GossipInspection {
+            crds,
+            push_active_set,
+            recent_prunes: self.traffic.recent_prunes(),
+            peer_traffic: self.traffic.peer_traffic(),
+        }
     }
 
     pub fn lookup_contact_info<F, Y>(&self, id: &Pubkey, map: F) -> Option<Y>
@@ -1005,7 +1064,7 @@ impl ClusterInfo {
         if min > last {
             let entry = CrdsValue::new_signed(
                 CrdsData::LowestSlot(0, LowestSlot::new(id, min, now)),
-                &self.keypair,
+                &self.keypair(),
             );
             self.local_message_pending_push_queue
                 .write()
@@ -1062,7 +1121,7 @@ impl ClusterInfo {
             };
             let n = slots.fill(&update[num..], now);
             if n > 0 {
-                let entry = CrdsValue::new_signed(CrdsData::EpochSlots(ix, slots), &self.keypair);
+                let entry = CrdsValue::new_signed(CrdsData::EpochSlots(ix, slots), &self.keypair());
                 self.local_message_pending_push_queue
                     .write()
                     .unwrap()
@@ -1110,7 +1169,7 @@ impl ClusterInfo {
         }
 
         let message = CrdsData::AccountsHashes(SnapshotHash::new(self.id(), accounts_hashes));
-        self.push_message(CrdsValue::new_signed(message, &self.keypair));
+        self.push_message(CrdsValue::new_signed(message, &self.keypair()));
     }
 
     pub fn push_snapshot_hashes(&self, snapshot_hashes: Vec<(Slot, Hash)>) {
@@ -1123,7 +1182,7 @@ impl ClusterInfo {
         }
 
         let message = CrdsData::SnapshotHashes(SnapshotHash::new(self.id(), snapshot_hashes));
-        self.push_message(CrdsValue::new_signed(message, &self.keypair));
+        self.push_message(CrdsValue::new_signed(message, &self.keypair()));
     }
 
     pub fn push_vote(&self, tower: &[Slot], vote: Transaction) {
@@ -1175,7 +1234,7 @@ impl ClusterInfo {
         let vote = Vote::new(self_pubkey, vote, now);
         debug_assert_eq!(vote.slot().unwrap(), *tower.last().unwrap());
         let vote = CrdsData::Vote(vote_index, vote);
-        let vote = CrdsValue::new_signed(vote, &self.keypair);
+        let vote = CrdsValue::new_signed(vote, &self.keypair());
         self.gossip
             .write()
             .unwrap()
@@ -1216,7 +1275,7 @@ impl ClusterInfo {
 
     pub(crate) fn push_duplicate_shred(&self, shred: &Shred, other_payload: &[u8]) -> Result<()> {
         self.gossip.write().unwrap().push_duplicate_shred(
-            &self.keypair,
+            &self.keypair(),
             shred,
             other_payload,
             None::<fn(Slot) -> Option<Pubkey>>, // Leader schedule
@@ -1538,8 +1597,10 @@ impl ClusterInfo {
     }
 
     fn insert_self(&self) {
-        let value =
-            CrdsValue::new_signed(CrdsData::ContactInfo(self.my_contact_info()), &self.keypair);
+        let value = CrdsValue::new_signed(
+            CrdsData::ContactInfo(self.my_contact_info()),
+            &self.keypair(),
+        );
         let _ = self.gossip.write().unwrap().crds.insert(value, timestamp());
     }
 
@@ -1793,6 +1854,7 @@ impl ClusterInfo {
             self.stats
                 .packets_sent_gossip_requests_count
                 .add_relaxed(packets.packets.len() as u64);
+            self.traffic.record_sent(packets.packets.iter());
             sender.send(packets)?;
         }
         Ok(())
@@ -1881,7 +1943,7 @@ impl ClusterInfo {
             .unwrap()
             .iter()
             .map(|k| k.id)
-            .chain(std::iter::once(self.id))
+            .chain(std::iter::once(self.id()))
             .collect();
         let mut gossip = self.gossip.write().unwrap();
         match gossip.crds.trim(cap, &keep, stakes) {
@@ -1926,10 +1988,12 @@ impl ClusterInfo {
                 let recycler = PacketsRecycler::new_without_limit("gossip-recycler-shrink-stats");
                 let crds_data = vec![
                     CrdsData::Version(Version::new(self.id())),
-                    CrdsData::NodeInstance(self.instance.with_wallclock(timestamp())),
+                    CrdsData::NodeInstance(
+                        self.instance.read().unwrap().with_wallclock(timestamp()),
+                    ),
                 ];
                 for value in crds_data {
-                    let value = CrdsValue::new_signed(value, &self.keypair);
+                    let value = CrdsValue::new_signed(value, &self.keypair());
                     self.push_message(value);
                 }
                 let mut generate_pull_requests = true;
@@ -2014,6 +2078,9 @@ impl ClusterInfo {
                 .map(|(_, data)| data.prunes.len() as u64)
                 .sum(),
         );
+        for (_, data) in &messages {
+            self.traffic.record_prune(PruneDirection::Received, data);
+        }
         let mut prune_message_timeout = 0;
         let mut bad_prune_destination = 0;
         {
@@ -2107,6 +2174,7 @@ impl ClusterInfo {
                 self.stats
                     .packets_sent_pull_responses_count
                     .add_relaxed(response.packets.len() as u64);
+                self.traffic.record_sent(response.packets.iter());
                 let _ = response_sender.send(response);
             }
         }
@@ -2142,7 +2210,7 @@ impl ClusterInfo {
         let check_enabled = matches!(feature_set, Some(feature_set) if
             feature_set.is_active(&feature_set::pull_request_ping_pong_check::id()));
         let mut cache = HashMap::<(Pubkey, SocketAddr), bool>::new();
-        let mut pingf = move || Ping::new_rand(&mut rng, &self.keypair).ok();
+        let mut pingf = move || Ping::new_rand(&mut rng, &self.keypair()).ok();
         let mut ping_cache = self.ping_cache.write().unwrap();
         let mut hard_check = move |node| {
             let (check, ping) = ping_cache.check(now, node, &mut pingf);
@@ -2361,7 +2429,7 @@ impl ClusterInfo {
         timeouts: &HashMap<Pubkey, u64>,
     ) -> (usize, usize, usize) {
         let len = crds_values.len();
-        trace!("PullResponse me: {} from: {} len={}", self.id, from, len);
+        trace!("PullResponse me: {} from: {} len={}", self.id(), from, len);
         let shred_version = self
             .lookup_contact_info(from, |ci| ci.shred_version)
             .unwrap_or(0);
@@ -2447,6 +2515,7 @@ impl ClusterInfo {
     {
         let _st = ScopedTimer::from(&self.stats.handle_batch_ping_messages_time);
         if let Some(response) = self.handle_ping_messages(pings, recycler) {
+            self.traffic.record_sent(response.packets.iter());
             let _ = response_sender.send(response);
         }
     }
@@ -2458,7 +2527,7 @@ impl ClusterInfo {
         let packets: Vec<_> = pings
             .into_iter()
             .filter_map(|(addr, ping)| {
-                let pong = Pong::new(&ping, &self.keypair).ok()?;
+                let pong = Pong::new(&ping, &self.keypair()).ok()?;
                 let pong = Protocol::PongMessage(pong);
                 match Packet::from_data(Some(&addr), pong) {
                     Ok(packet) => Some(packet),
@@ -2593,7 +2662,7 @@ impl ClusterInfo {
                             destination: from,
                             wallclock,
                         };
-                        prune_data.sign(&self.keypair);
+                        prune_data.sign(&self.keypair());
                         let prune_message = Protocol::PruneMessage(self_pubkey, prune_data);
                         Some((peer.gossip, prune_message))
                     })
@@ -2603,6 +2672,11 @@ impl ClusterInfo {
         if prune_messages.is_empty() {
             return;
         }
+        for (_, prune_message) in &prune_messages {
+            if let Protocol::PruneMessage(_, data) = prune_message {
+                self.traffic.record_prune(PruneDirection::Sent, data);
+            }
+        }
         let mut packets = to_packets_with_destination(recycler.clone(), &prune_messages);
         let num_prune_packets = packets.packets.len();
         self.stats
@@ -2626,6 +2700,7 @@ impl ClusterInfo {
         self.stats
             .packets_sent_push_messages_count
             .add_relaxed((packets.packets.len() - num_prune_packets) as u64);
+        self.traffic.record_sent(packets.packets.iter());
         let _ = response_sender.send(packets);
     }
 
@@ -2713,7 +2788,7 @@ impl ClusterInfo {
         let check_duplicate_instance = |values: &[CrdsValue]| {
             if should_check_duplicate_instance {
                 for value in values {
-                    if self.instance.check_duplicate(value) {
+                    if self.instance.read().unwrap().check_duplicate(value) {
                         return Err(Error::DuplicateNodeInstance);
                     }
                 }
@@ -2817,6 +2892,7 @@ impl ClusterInfo {
                     .add_relaxed(excess_count as u64);
             }
         }
+        self.traffic.record_received(&packets);
         let (stakes, epoch_time_ms) = Self::get_stakes_and_epoch_time(bank_forks);
         // Using root_bank instead of working_bank here so that an enbaled
         // feature does not roll back (if the feature happens to get enabled in
@@ -3864,6 +3940,28 @@ mod tests {
             .is_some());
     }
 
+    #[test]
+    fn test_inspect_gossip() {
+        let d = ContactInfo::new_localhost(&solana_sdk::pubkey::new_rand(), timestamp());
+        let cluster_info = ClusterInfo::new_with_invalid_keypair(d.clone());
+        let other = ContactInfo::new_localhost(&solana_sdk::pubkey::new_rand(), timestamp());
+        cluster_info.insert_info(other.clone());
+
+        let inspection = cluster_info.inspect_gossip(Some("ContactInfo"), None);
+        let mut origins: Vec<_> = inspection.crds.iter().map(|e| e.origin.clone()).collect();
+        origins.sort();
+        let mut expected = vec![d.id.to_string(), other.id.to_string()];
+        expected.sort();
+        assert_eq!(origins, expected);
+
+        let inspection = cluster_info.inspect_gossip(None, Some(&other.id));
+        assert_eq!(inspection.crds.len(), 1);
+        assert_eq!(inspection.crds[0].label_kind(), "ContactInfo");
+
+        let inspection = cluster_info.inspect_gossip(Some("Vote"), None);
+        assert!(inspection.crds.is_empty());
+    }
+
     fn assert_in_range(x: u16, range: (u16, u16)) {
         assert!(x >= range.0);
         assert!(x < range.1);
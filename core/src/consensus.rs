@@ -16,11 +16,11 @@ use solana_sdk::{
 };
 use solana_vote_program::{
     vote_instruction,
-    vote_state::{BlockTimestamp, Lockout, Vote, VoteState, MAX_LOCKOUT_HISTORY},
+    vote_state::{BlockTimestamp, Lockout, Vote, VoteState, VoteStateUpdate, MAX_LOCKOUT_HISTORY},
 };
 use std::{
     cmp::Ordering,
-    collections::{HashMap, HashSet},
+    collections::{HashMap, HashSet, VecDeque},
     fs::{self, File},
     io::BufReader,
     ops::{
@@ -69,6 +69,34 @@ impl SwitchForkDecision {
         }
     }
 
+    pub fn to_vote_state_update_instruction(
+        &self,
+        vote_state_update: VoteStateUpdate,
+        vote_account_pubkey: &Pubkey,
+        authorized_voter_pubkey: &Pubkey,
+    ) -> Option<Instruction> {
+        match self {
+            SwitchForkDecision::FailedSwitchThreshold(_, total_stake) => {
+                assert_ne!(*total_stake, 0);
+                None
+            }
+            SwitchForkDecision::FailedSwitchDuplicateRollback(_) => None,
+            SwitchForkDecision::SameFork => Some(vote_instruction::compact_update_vote_state(
+                vote_account_pubkey,
+                authorized_voter_pubkey,
+                vote_state_update,
+            )),
+            SwitchForkDecision::SwitchProof(switch_proof_hash) => {
+                Some(vote_instruction::compact_update_vote_state_switch(
+                    vote_account_pubkey,
+                    authorized_voter_pubkey,
+                    vote_state_update,
+                    *switch_proof_hash,
+                ))
+            }
+        }
+    }
+
     pub fn can_vote(&self) -> bool {
         match self {
             SwitchForkDecision::FailedSwitchThreshold(_, _) => false,
@@ -451,6 +479,31 @@ impl Tower {
         last_vote
     }
 
+    // The full local tower behind `vote`, which lets the vote account resync
+    // with this tower even if earlier vote transactions were dropped. Votes at
+    // or below the root, such as a vote on the root bank the tower was started
+    // from, are already covered by the root and are left out. Returns None if
+    // no vote above the root remains.
+    pub fn vote_state_update(&self, vote: &Vote) -> Option<VoteStateUpdate> {
+        let root = self.lockouts.root_slot;
+        let lockouts: VecDeque<Lockout> = self
+            .lockouts
+            .votes
+            .iter()
+            .filter(|lockout| root.map_or(true, |root| lockout.slot > root))
+            .cloned()
+            .collect();
+        if lockouts.is_empty() {
+            return None;
+        }
+        Some(VoteStateUpdate {
+            lockouts,
+            root,
+            hash: vote.hash,
+            timestamp: vote.timestamp,
+        })
+    }
+
     fn maybe_timestamp(&mut self, current_slot: Slot) -> Option<UnixTimestamp> {
         if current_slot > self.last_timestamp.slot
             || self.last_timestamp.slot == 0 && current_slot == self.last_timestamp.slot
@@ -1071,10 +1124,6 @@ impl Tower {
                 self.node_pubkey,
                 bank.slot(),
             );
-            assert_eq!(
-                self.lockouts.node_pubkey, self.node_pubkey,
-                "vote account's node_pubkey doesn't match",
-            );
         } else {
             self.initialize_root(root);
             info!(
@@ -1290,7 +1339,7 @@ pub mod test {
     use solana_sdk::{
         account::{Account, AccountSharedData, WritableAccount},
         clock::Slot,
-        hash::Hash,
+        hash::{hash, Hash},
         pubkey::Pubkey,
         signature::Signer,
         slot_history::SlotHistory,
@@ -1683,6 +1732,57 @@ pub mod test {
         );
     }
 
+    #[test]
+    fn test_to_vote_state_update_instruction() {
+        let vote_state_update = VoteStateUpdate::default();
+        let mut decision = SwitchForkDecision::FailedSwitchThreshold(0, 1);
+        assert!(decision
+            .to_vote_state_update_instruction(
+                vote_state_update.clone(),
+                &Pubkey::default(),
+                &Pubkey::default()
+            )
+            .is_none());
+
+        decision = SwitchForkDecision::FailedSwitchDuplicateRollback(0);
+        assert!(decision
+            .to_vote_state_update_instruction(
+                vote_state_update.clone(),
+                &Pubkey::default(),
+                &Pubkey::default()
+            )
+            .is_none());
+
+        decision = SwitchForkDecision::SameFork;
+        assert_eq!(
+            decision.to_vote_state_update_instruction(
+                vote_state_update.clone(),
+                &Pubkey::default(),
+                &Pubkey::default()
+            ),
+            Some(vote_instruction::compact_update_vote_state(
+                &Pubkey::default(),
+                &Pubkey::default(),
+                vote_state_update.clone(),
+            ))
+        );
+
+        decision = SwitchForkDecision::SwitchProof(Hash::default());
+        assert_eq!(
+            decision.to_vote_state_update_instruction(
+                vote_state_update.clone(),
+                &Pubkey::default(),
+                &Pubkey::default()
+            ),
+            Some(vote_instruction::compact_update_vote_state_switch(
+                &Pubkey::default(),
+                &Pubkey::default(),
+                vote_state_update,
+                Hash::default()
+            ))
+        );
+    }
+
     #[test]
     fn test_simple_votes() {
         // Init state
@@ -2420,6 +2520,40 @@ pub mod test {
         assert_eq!(tower_slots, vec![0]);
     }
 
+    #[test]
+    fn test_vote_state_update() {
+        let mut tower = Tower::new_for_tests(0, 0.67);
+        for slot in 1..4 {
+            tower.record_vote(slot, Hash::default());
+        }
+        // The vote only carries the latest slot, but the update carries the whole tower
+        let vote = Vote {
+            timestamp: Some(42),
+            ..Vote::new(vec![3], hash(&[3]))
+        };
+        let vote_state_update = tower.vote_state_update(&vote).unwrap();
+        assert_eq!(vote_state_update.slots(), vec![1, 2, 3]);
+        assert_eq!(vote_state_update.lockouts, tower.lockouts.votes);
+        assert_eq!(vote_state_update.root, Some(tower.root()));
+        assert_eq!(vote_state_update.hash, hash(&[3]));
+        assert_eq!(vote_state_update.timestamp, Some(42));
+    }
+
+    #[test]
+    fn test_vote_state_update_skips_votes_at_root() {
+        // A tower started from root 0 that votes on the root bank itself
+        let mut tower = Tower::new_for_tests(0, 0.67);
+        tower.record_vote(0, Hash::default());
+        let vote = Vote::new(vec![0], hash(&[0]));
+        assert_eq!(tower.vote_state_update(&vote), None);
+
+        tower.record_vote(1, Hash::default());
+        let vote = Vote::new(vec![1], hash(&[1]));
+        let vote_state_update = tower.vote_state_update(&vote).unwrap();
+        assert_eq!(vote_state_update.slots(), vec![1]);
+        assert_eq!(vote_state_update.root, Some(0));
+    }
+
     #[test]
     fn test_new_vote_dup_vote() {
         let local = VoteState::default();
@@ -277,7 +277,6 @@ impl Crds {
         self.table.is_empty()
     }
 
-    #[cfg(test)]
     pub(crate) fn values(&self) -> impl Iterator<Item = &VersionedCrdsValue> {
         self.table.values()
     }
@@ -283,6 +283,11 @@ impl CrdsGossipPush {
         }
     }
 
+    /// Returns the peers which push messages are currently sent to.
+    pub(crate) fn active_set_peers(&self) -> impl Iterator<Item = &Pubkey> {
+        self.active_set.keys()
+    }
+
     fn compute_need(num_active: usize, active_set_len: usize, ratio: usize) -> usize {
         let num = active_set_len / ratio;
         cmp::min(num_active, (num_active - active_set_len) + num)
@@ -0,0 +1,343 @@
+//! Read-only snapshots of gossip state for operators debugging the network:
+//! crds table entries, the push active set, recently exchanged prune messages
+//! and per-peer gossip traffic.
+
+use crate::{cluster_info::PruneData, crds::VersionedCrdsValue};
+use solana_perf::packet::Packet;
+use solana_sdk::timing::timestamp;
+use std::{
+    collections::{HashMap, VecDeque},
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        RwLock,
+    },
+};
+
+/// Maximum number of prune messages kept for inspection.
+const MAX_RECENT_PRUNES: usize = 256;
+/// Maximum number of peer addresses with tracked traffic. Once reached, the
+/// least recently seen quarter of the peers is evicted.
+const MAX_TRACKED_PEERS: usize = 4096;
+
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GossipInspectConfig {
+    /// Only return crds entries whose label kind matches, e.g. `ContactInfo`
+    pub label: Option<String>,
+    /// Only return crds entries originating from this base-58 pubkey
+    pub origin: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GossipInspection {
+    pub crds: Vec<CrdsEntry>,
+    /// Base-58 pubkeys of the peers this node currently pushes to
+    pub push_active_set: Vec<String>,
+    /// Most recent prune messages, oldest first
+    pub recent_prunes: Vec<PruneRecord>,
+    pub peer_traffic: Vec<PeerTraffic>,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CrdsEntry {
+    pub label: String,
+    /// Base-58 pubkey of the node which signed the value
+    pub origin: String,
+    pub wallclock: u64,
+    /// Local time when the value was first inserted
+    pub insert_timestamp: u64,
+    /// Local time when the value was last updated
+    pub local_timestamp: u64,
+}
+
+impl From<&VersionedCrdsValue> for CrdsEntry {
+    fn from(value: &VersionedCrdsValue) -> Self {
+        Self {
+            label: value.value.label().to_string(),
+            origin: value.value.pubkey().to_string(),
+            wallclock: value.value.wallclock(),
+            insert_timestamp: value.insert_timestamp,
+            local_timestamp: value.local_timestamp,
+        }
+    }
+}
+
+impl CrdsEntry {
+    /// Returns the kind of the label, e.g. `Vote` for `Vote(3, <pubkey>)`.
+    pub fn label_kind(&self) -> &str {
+        self.label.split('(').next().unwrap_or_default()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PruneDirection {
+    Sent,
+    Received,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PruneRecord {
+    pub direction: PruneDirection,
+    /// Base-58 pubkey of the node which sent the prune message
+    pub from: String,
+    /// Base-58 pubkey of the node asked to stop pushing
+    pub destination: String,
+    /// Base-58 pubkeys of the origins to prune
+    pub prunes: Vec<String>,
+    pub wallclock: u64,
+}
+
+impl PruneRecord {
+    fn new(direction: PruneDirection, data: &PruneData) -> Self {
+        Self {
+            direction,
+            from: data.pubkey.to_string(),
+            destination: data.destination.to_string(),
+            prunes: data.prunes.iter().map(ToString::to_string).collect(),
+            wallclock: data.wallclock,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeerTraffic {
+    pub addr: Option<SocketAddr>,
+    pub packets_received: u64,
+    pub bytes_received: u64,
+    pub packets_sent: u64,
+    pub bytes_sent: u64,
+}
+
+#[derive(Default)]
+struct PeerCounters {
+    packets_received: AtomicU64,
+    bytes_received: AtomicU64,
+    packets_sent: AtomicU64,
+    bytes_sent: AtomicU64,
+    /// Local time when traffic was last recorded for the peer
+    last_seen: AtomicU64,
+}
+
+impl PeerCounters {
+    fn traffic(&self, addr: SocketAddr) -> PeerTraffic {
+        PeerTraffic {
+            addr: Some(addr),
+            packets_received: self.packets_received.load(Ordering::Relaxed),
+            bytes_received: self.bytes_received.load(Ordering::Relaxed),
+            packets_sent: self.packets_sent.load(Ordering::Relaxed),
+            bytes_sent: self.bytes_sent.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Per-peer traffic counters and recent prune messages, updated by the
+/// gossip listen and gossip threads. Known peers are updated under the read
+/// lock; the write lock is only taken to insert new peers.
+#[derive(Default)]
+pub(crate) struct GossipTraffic {
+    peers: RwLock<HashMap<SocketAddr, PeerCounters>>,
+    prunes: RwLock<VecDeque<PruneRecord>>,
+}
+
+impl GossipTraffic {
+    pub(crate) fn record_received<'a, I>(&self, packets: I)
+    where
+        I: IntoIterator<Item = &'a Packet>,
+    {
+        self.record(packets, timestamp(), |counters, size| {
+            counters.packets_received.fetch_add(1, Ordering::Relaxed);
+            counters.bytes_received.fetch_add(size, Ordering::Relaxed);
+        })
+    }
+
+    pub(crate) fn record_sent<'a, I>(&self, packets: I)
+    where
+        I: IntoIterator<Item = &'a Packet>,
+    {
+        self.record(packets, timestamp(), |counters, size| {
+            counters.packets_sent.fetch_add(1, Ordering::Relaxed);
+            counters.bytes_sent.fetch_add(size, Ordering::Relaxed);
+        })
+    }
+
+    fn record<'a, I, F>(&self, packets: I, now: u64, update: F)
+    where
+        I: IntoIterator<Item = &'a Packet>,
+        F: Fn(&PeerCounters, u64),
+    {
+        let mut new_peers = vec![];
+        {
+            let peers = self.peers.read().unwrap();
+            for packet in packets {
+                let addr = packet.meta.addr();
+                let size = packet.meta.size as u64;
+                match peers.get(&addr) {
+                    Some(counters) => {
+                        update(counters, size);
+                        counters.last_seen.fetch_max(now, Ordering::Relaxed);
+                    }
+                    None => new_peers.push((addr, size)),
+                }
+            }
+        }
+        if new_peers.is_empty() {
+            return;
+        }
+        let mut peers = self.peers.write().unwrap();
+        for (addr, size) in new_peers {
+            if peers.len() >= MAX_TRACKED_PEERS && !peers.contains_key(&addr) {
+                Self::evict_least_recently_seen(&mut peers);
+            }
+            let counters = peers.entry(addr).or_default();
+            update(counters, size);
+            counters.last_seen.fetch_max(now, Ordering::Relaxed);
+        }
+    }
+
+    fn evict_least_recently_seen(peers: &mut HashMap<SocketAddr, PeerCounters>) {
+        let mut last_seen: Vec<_> = peers
+            .iter()
+            .map(|(addr, counters)| (counters.last_seen.load(Ordering::Relaxed), *addr))
+            .collect();
+        let num_evicted = (peers.len() / 4).max(1);
+        last_seen.select_nth_unstable(num_evicted - 1);
+        for (_, addr) in &last_seen[..num_evicted] {
+            peers.remove(addr);
+        }
+    }
+
+    pub(crate) fn record_prune(&self, direction: PruneDirection, data: &PruneData) {
+        let mut prunes = self.prunes.write().unwrap();
+        if prunes.len() >= MAX_RECENT_PRUNES {
+            prunes.pop_front();
+        }
+        prunes.push_back(PruneRecord::new(direction, data));
+    }
+
+    pub(crate) fn recent_prunes(&self) -> Vec<PruneRecord> {
+        self.prunes.read().unwrap().iter().cloned().collect()
+    }
+
+    pub(crate) fn peer_traffic(&self) -> Vec<PeerTraffic> {
+        let mut peers: Vec<_> = self
+            .peers
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(addr, counters)| counters.traffic(*addr))
+            .collect();
+        peers.sort_by_key(|traffic| std::cmp::Reverse(traffic.bytes_received + traffic.bytes_sent));
+        peers
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::pubkey::Pubkey;
+
+    #[test]
+    fn test_gossip_traffic() {
+        let traffic = GossipTraffic::default();
+        let addr1: SocketAddr = "127.0.0.1:8001".parse().unwrap();
+        let addr2: SocketAddr = "127.0.0.1:8002".parse().unwrap();
+        let mut packet1 = Packet::default();
+        packet1.meta.set_addr(&addr1);
+        packet1.meta.size = 100;
+        let mut packet2 = Packet::default();
+        packet2.meta.set_addr(&addr2);
+        packet2.meta.size = 10;
+
+        traffic.record_received(&[packet1.clone(), packet1, packet2.clone()]);
+        traffic.record_sent(&[packet2]);
+        assert_eq!(
+            traffic.peer_traffic(),
+            vec![
+                PeerTraffic {
+                    addr: Some(addr1),
+                    packets_received: 2,
+                    bytes_received: 200,
+                    packets_sent: 0,
+                    bytes_sent: 0,
+                },
+                PeerTraffic {
+                    addr: Some(addr2),
+                    packets_received: 1,
+                    bytes_received: 10,
+                    packets_sent: 1,
+                    bytes_sent: 10,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_gossip_traffic_eviction() {
+        let traffic = GossipTraffic::default();
+        let packet = |port| {
+            let mut packet = Packet::default();
+            packet
+                .meta
+                .set_addr(&SocketAddr::from(([127, 0, 0, 1], port)));
+            packet.meta.size = 1;
+            packet
+        };
+        for port in 0..MAX_TRACKED_PEERS as u16 {
+            traffic.record(&[packet(port)], u64::from(port), |counters, size| {
+                counters.bytes_received.fetch_add(size, Ordering::Relaxed);
+            });
+        }
+        // Refresh the oldest peer so that it survives eviction
+        traffic.record_received(&[packet(0)]);
+        traffic.record_received(&[packet(u16::MAX)]);
+
+        let peers = traffic.peer_traffic();
+        assert_eq!(peers.len(), MAX_TRACKED_PEERS - MAX_TRACKED_PEERS / 4 + 1);
+        let ports: Vec<_> = peers
+            .iter()
+            .map(|traffic| traffic.addr.unwrap().port())
+            .collect();
+        assert!(ports.contains(&0));
+        assert!(ports.contains(&u16::MAX));
+        assert!(!ports.contains(&1));
+        assert!(!ports.contains(&(MAX_TRACKED_PEERS as u16 / 4)));
+        assert!(ports.contains(&(MAX_TRACKED_PEERS as u16 / 4 + 1)));
+        assert_eq!(
+            peers
+                .iter()
+                .find(|traffic| traffic.addr.unwrap().port() == 0),
+            Some(&PeerTraffic {
+                addr: Some(SocketAddr::from(([127, 0, 0, 1], 0))),
+                packets_received: 1,
+                bytes_received: 2,
+                packets_sent: 0,
+                bytes_sent: 0,
+            })
+        );
+    }
+
+    #[test]
+    fn test_recent_prunes() {
+        let traffic = GossipTraffic::default();
+        let data = PruneData {
+            pubkey: Pubkey::new_unique(),
+            prunes: vec![Pubkey::new_unique()],
+            destination: Pubkey::new_unique(),
+            ..PruneData::default()
+        };
+        for _ in 0..MAX_RECENT_PRUNES + 10 {
+            traffic.record_prune(PruneDirection::Received, &data);
+        }
+        traffic.record_prune(PruneDirection::Sent, &data);
+        let prunes = traffic.recent_prunes();
+        assert_eq!(prunes.len(), MAX_RECENT_PRUNES);
+        assert_eq!(prunes.last().unwrap().direction, PruneDirection::Sent);
+        assert_eq!(prunes[0].prunes, vec![data.prunes[0].to_string()]);
+    }
+}
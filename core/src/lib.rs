@@ -39,6 +39,7 @@ pub mod epoch_slots;
 pub mod fetch_stage;
 pub mod fork_choice;
 pub mod gen_keys;
+pub mod gossip_inspect;
 pub mod gossip_service;
 pub mod heaviest_subtree_fork_choice;
 pub mod ledger_cleanup_service;
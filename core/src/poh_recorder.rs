@@ -394,6 +394,10 @@ impl PohRecorder {
         self.leader_last_tick_height = leader_last_tick_height;
     }
 
+    pub fn set_id(&mut self, id: &Pubkey) {
+        self.id = *id;
+    }
+
     pub fn set_working_bank(&mut self, working_bank: WorkingBank) {
         trace!("new working bank");
         assert_eq!(working_bank.bank.ticks_per_slot(), self.ticks_per_slot());
@@ -175,7 +175,6 @@ impl RepairService {
     ) {
         let mut repair_weight = RepairWeight::new(repair_info.bank_forks.read().unwrap().root());
         let serve_repair = ServeRepair::new(cluster_info.clone());
-        let id = cluster_info.id();
         let mut repair_stats = RepairStats::default();
         let mut repair_timing = RepairTiming::default();
         let mut last_stats = Instant::now();
@@ -186,6 +185,8 @@ impl RepairService {
             if exit.load(Ordering::Relaxed) {
                 break;
             }
+            // The identity may be swapped at runtime, so look it up on every pass
+            let id = cluster_info.id();
 
             let mut set_root_elapsed;
             let mut get_votes_elapsed;
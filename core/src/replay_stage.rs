@@ -40,6 +40,7 @@ use solana_runtime::{
 };
 use solana_sdk::{
     clock::{Slot, NUM_CONSECUTIVE_LEADER_SLOTS},
+    feature_set,
     genesis_config::ClusterType,
     hash::Hash,
     pubkey::Pubkey,
@@ -48,7 +49,10 @@ use solana_sdk::{
     timing::timestamp,
     transaction::Transaction,
 };
-use solana_vote_program::{vote_instruction, vote_state::Vote};
+use solana_vote_program::{
+    vote_instruction,
+    vote_state::{Vote, VoteStateUpdate},
+};
 use std::{
     collections::{BTreeMap, HashMap, HashSet},
     result,
@@ -286,7 +290,7 @@ impl ReplayStage {
         gossip_verified_vote_hash_receiver: GossipVerifiedVoteHashReceiver,
     ) -> Self {
         let ReplayStageConfig {
-            my_pubkey,
+            mut my_pubkey,
             vote_account,
             authorized_voter_keypairs,
             exit,
@@ -333,12 +337,34 @@ impl ReplayStage {
                 let mut gossip_verified_vote_hashes: GossipVerifiedVoteHashes = BTreeMap::new();
                 let mut voted_signatures = Vec::new();
                 let mut has_new_vote_been_rooted = !wait_for_vote_to_start_leader;
+                let mut identity_keypair = cluster_info.keypair().clone();
                 loop {
                     // Stop getting entries if we get exit signal
                     if exit.load(Ordering::Relaxed) {
                         break;
                     }
 
+                    // Pick up an identity swapped in through `ClusterInfo::set_keypair()`
+                    if my_pubkey != cluster_info.id() {
+                        identity_keypair = cluster_info.keypair().clone();
+                        let my_old_pubkey = my_pubkey;
+                        my_pubkey = identity_keypair.pubkey();
+
+                        tower = Self::load_tower_for_new_identity(
+                            &blockstore,
+                            &bank_forks,
+                            &my_pubkey,
+                            &vote_account,
+                        );
+                        poh_recorder.lock().unwrap().set_id(&my_pubkey);
+                        // Force a PoH reset so the next leader slot is computed for the new identity
+                        last_reset = Hash::default();
+                        // Ensure the validator can land votes with the new identity before
+                        // becoming leader
+                        has_new_vote_been_rooted = !wait_for_vote_to_start_leader;
+                        warn!("Identity changed from {} to {}", my_old_pubkey, my_pubkey);
+                    }
+
                     let mut generate_new_bank_forks_time =
                         Measure::start("generate_new_bank_forks_time");
                     Self::generate_new_bank_forks(
@@ -531,6 +557,7 @@ impl ReplayStage {
                             &mut tower,
                             &mut progress,
                             &vote_account,
+                            &identity_keypair,
                             &authorized_voter_keypairs.read().unwrap(),
                             &cluster_info,
                             &blockstore,
@@ -690,6 +717,37 @@ impl ReplayStage {
         }
     }
 
+    // Loads the tower saved for a newly adopted identity, rebuilding it from the vote account
+    // like validator startup does if no usable tower was saved
+    fn load_tower_for_new_identity(
+        blockstore: &Blockstore,
+        bank_forks: &RwLock<BankForks>,
+        my_pubkey: &Pubkey,
+        vote_account: &Pubkey,
+    ) -> Tower {
+        let bank_forks = bank_forks.read().unwrap();
+        Tower::restore(blockstore.ledger_path(), my_pubkey)
+            .and_then(|tower| {
+                let root_bank = bank_forks.root_bank();
+                let slot_history = root_bank.get_slot_history();
+                tower.adjust_lockouts_after_replay(root_bank.slot(), &slot_history)
+            })
+            .unwrap_or_else(|err| {
+                if !err.is_file_missing() {
+                    error!(
+                        "Rebuilding a new tower for {} from the latest vote account due to failed tower restore: {}",
+                        my_pubkey, err
+                    );
+                }
+                Tower::new_from_bankforks(
+                    &bank_forks,
+                    blockstore.ledger_path(),
+                    my_pubkey,
+                    vote_account,
+                )
+            })
+    }
+
     fn is_partition_detected(
         ancestors: &HashMap<Slot, HashSet<Slot>>,
         last_voted_slot: Slot,
@@ -1255,6 +1313,7 @@ impl ReplayStage {
         tower: &mut Tower,
         progress: &mut ProgressMap,
         vote_account_pubkey: &Pubkey,
+        identity_keypair: &Arc<Keypair>,
         authorized_voter_keypairs: &[Arc<Keypair>],
         cluster_info: &Arc<ClusterInfo>,
         blockstore: &Arc<Blockstore>,
@@ -1278,8 +1337,18 @@ impl ReplayStage {
         trace!("handle votable bank {}", bank.slot());
         let (new_root, tower_slots) = tower.record_bank_vote(bank, vote_account_pubkey);
         let last_vote = tower.last_vote_and_timestamp();
+        // Send the full tower instead of only the new slots, so a vote account that
+        // missed some of our vote transactions is brought back in line with our tower
+        let vote_state_update = if bank
+            .feature_set
+            .is_active(&feature_set::allow_votes_to_directly_update_vote_state::id())
+        {
+            tower.vote_state_update(&last_vote)
+        } else {
+            None
+        };
 
-        if let Err(err) = tower.save(&cluster_info.keypair) {
+        if let Err(err) = tower.save(identity_keypair) {
             error!("Unable to save tower: {:?}", err);
             std::process::exit(1);
         }
@@ -1351,8 +1420,10 @@ impl ReplayStage {
             cluster_info,
             bank,
             vote_account_pubkey,
+            identity_keypair,
             authorized_voter_keypairs,
             last_vote,
+            vote_state_update,
             &tower_slots,
             switch_fork_decision,
             vote_signatures,
@@ -1360,12 +1431,15 @@ impl ReplayStage {
         );
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn push_vote(
         cluster_info: &ClusterInfo,
         bank: &Arc<Bank>,
         vote_account_pubkey: &Pubkey,
+        identity_keypair: &Keypair,
         authorized_voter_keypairs: &[Arc<Keypair>],
         vote: Vote,
+        vote_state_update: Option<VoteStateUpdate>,
         tower: &[Slot],
         switch_fork_decision: &SwitchForkDecision,
         vote_signatures: &mut Vec<Signature>,
@@ -1418,10 +1492,16 @@ impl ReplayStage {
             }
             Some(authorized_voter_keypair) => authorized_voter_keypair,
         };
-        let node_keypair = cluster_info.keypair.clone();
-
         // Send our last few votes along with the new one
-        let vote_ix = if bank.slot() > Self::get_unlock_switch_vote_slot(bank.cluster_type()) {
+        let vote_ix = if let Some(vote_state_update) = vote_state_update {
+            switch_fork_decision
+                .to_vote_state_update_instruction(
+                    vote_state_update,
+                    &vote_account_pubkey,
+                    &authorized_voter_keypair.pubkey(),
+                )
+                .expect("Switch threshold failure should not lead to voting")
+        } else if bank.slot() > Self::get_unlock_switch_vote_slot(bank.cluster_type()) {
             switch_fork_decision
                 .to_vote_instruction(
                     vote,
@@ -1437,10 +1517,10 @@ impl ReplayStage {
             )
         };
 
-        let mut vote_tx = Transaction::new_with_payer(&[vote_ix], Some(&node_keypair.pubkey()));
+        let mut vote_tx = Transaction::new_with_payer(&[vote_ix], Some(&identity_keypair.pubkey()));
 
         let blockhash = bank.last_blockhash();
-        vote_tx.partial_sign(&[node_keypair.as_ref()], blockhash);
+        vote_tx.partial_sign(&[identity_keypair], blockhash);
         vote_tx.partial_sign(&[authorized_voter_keypair.as_ref()], blockhash);
 
         if !has_new_vote_been_rooted {
@@ -30,8 +30,9 @@ use solana_client::{
         TokenAccountsFilter, DELINQUENT_VALIDATOR_SLOT_DISTANCE, MAX_GET_CONFIRMED_BLOCKS_RANGE,
         MAX_GET_CONFIRMED_SIGNATURES_FOR_ADDRESS2_LIMIT,
         MAX_GET_CONFIRMED_SIGNATURES_FOR_ADDRESS_SLOT_RANGE, MAX_GET_PROGRAM_ACCOUNT_FILTERS,
-        MAX_GET_SIGNATURE_STATUSES_QUERY_ITEMS, MAX_GET_SLOT_LEADERS, MAX_MULTIPLE_ACCOUNTS,
-        NUM_LARGEST_ACCOUNTS,
+        MAX_GET_SIGNATURE_STATUSES_QUERY_ITEMS, MAX_GET_SLOT_LEADERS,
+        MAX_GET_STAKE_ACCOUNT_HISTORY_LIMIT, MAX_GET_STAKE_ACCOUNT_HISTORY_REWARD_EPOCHS,
+        MAX_MULTIPLE_ACCOUNTS, NUM_LARGEST_ACCOUNTS,
     },
     rpc_response::Response as RpcResponse,
     rpc_response::*,
@@ -55,10 +56,11 @@ use solana_runtime::{
 use solana_sdk::{
     account::{AccountSharedData, ReadableAccount},
     account_utils::StateMut,
-    clock::{Slot, UnixTimestamp, MAX_RECENT_BLOCKHASHES},
+    clock::{Epoch, Slot, UnixTimestamp, MAX_RECENT_BLOCKHASHES},
     commitment_config::{CommitmentConfig, CommitmentLevel},
     epoch_info::EpochInfo,
     epoch_schedule::EpochSchedule,
+    feature_set::FeatureSet,
     hash::Hash,
     pubkey::Pubkey,
     sanitize::Sanitize,
@@ -70,8 +72,9 @@ use solana_sdk::{
 };
 use solana_stake_program::stake_state::StakeState;
 use solana_transaction_status::{
-    EncodedConfirmedTransaction, Reward, RewardType, TransactionConfirmationStatus,
-    TransactionStatus, UiConfirmedBlock, UiTransactionEncoding,
+    parse_instruction::ParsedInstructionEnum, EncodedConfirmedTransaction, EncodedTransaction,
+    Reward, RewardType, TransactionConfirmationStatus, TransactionStatus, UiConfirmedBlock,
+    UiInstruction, UiMessage, UiParsedInstruction, UiTransaction, UiTransactionEncoding,
 };
 use solana_vote_program::vote_state::{VoteState, MAX_LOCKOUT_HISTORY};
 use spl_token_v2_0::{
@@ -82,6 +85,7 @@ use std::{
     cmp::{max, min},
     collections::{HashMap, HashSet},
     net::SocketAddr,
+    ops::Range,
     str::FromStr,
     sync::{
         atomic::{AtomicBool, AtomicU64, Ordering},
@@ -114,7 +118,6 @@ fn is_finalized(
 pub struct JsonRpcConfig {
     pub enable_rpc_transaction_history: bool,
     pub enable_cpi_and_log_storage: bool,
-    pub identity_pubkey: Pubkey,
     pub faucet_addr: Option<SocketAddr>,
     pub health_check_slot_distance: u64,
     pub enable_bigtable_ledger_storage: bool,
@@ -342,6 +345,17 @@ impl JsonRpcRequestProcessor {
             .get_minimum_balance_for_rent_exemption(data_len)
     }
 
+    pub fn get_stake_minimum_delegation(
+        &self,
+        commitment: Option<CommitmentConfig>,
+    ) -> RpcResponse<u64> {
+        let bank = self.bank(commitment);
+        let minimum_delegation = solana_stake_program::get_minimum_delegation(|feature_id| {
+            bank.feature_set.is_active(feature_id)
+        });
+        new_response(&bank, minimum_delegation)
+    }
+
     pub fn get_program_accounts(
         &self,
         program_id: &Pubkey,
@@ -1431,6 +1445,116 @@ impl JsonRpcRequestProcessor {
         })
     }
 
+    pub fn get_stake_account_history(
+        &self,
+        address: Pubkey,
+        before: Option<Signature>,
+        until: Option<Signature>,
+        rewards_before: Option<Epoch>,
+        limit: usize,
+        commitment: Option<CommitmentConfig>,
+    ) -> Result<RpcStakeAccountHistory> {
+        if !self.config.enable_rpc_transaction_history {
+            return Ok(RpcStakeAccountHistory {
+                events: vec![],
+                rewards: vec![],
+                oldest_signature: None,
+                oldest_reward_epoch: None,
+            });
+        }
+        let signatures =
+            self.get_signatures_for_address(address, before, until, limit, commitment)?;
+
+        let transaction_config = RpcConfirmedTransactionConfig {
+            encoding: Some(UiTransactionEncoding::JsonParsed),
+            commitment,
+        };
+        let mut events = vec![];
+        for status in &signatures {
+            let signature = verify_signature(&status.signature)?;
+            if let Some(transaction) = self.get_transaction(
+                signature,
+                Some(RpcEncodingConfigWrapper::Current(Some(transaction_config))),
+            )? {
+                events.extend(get_stake_account_events(
+                    &address.to_string(),
+                    &status.signature,
+                    transaction,
+                ));
+            }
+        }
+
+        // Rewards cover the epochs from the oldest scanned transaction, or from `before` when no
+        // older transaction remains, up to the epoch before `rewards_before`; failing that, up to
+        // the epoch where the previous page, ending at `before`, started, or the last completed
+        // epoch. Each epoch costs a block lookup, so they are paged separately, newest first.
+        let epoch_schedule = self.get_epoch_schedule();
+        let before_epoch = match before {
+            Some(before) => Some(
+                self.get_transaction(before, None)?
+                    .map(|transaction| epoch_schedule.get_epoch(transaction.slot))
+                    .ok_or_else(|| {
+                        Error::invalid_params(
+                            "Invalid param: before signature not found".to_string(),
+                        )
+                    })?,
+            ),
+            None => None,
+        };
+        let first_reward_epoch = signatures
+            .last()
+            .map(|oldest| epoch_schedule.get_epoch(oldest.slot))
+            .or(before_epoch);
+        let (rewards, oldest_reward_epoch) = match first_reward_epoch {
+            Some(first_reward_epoch) => {
+                let reward_epochs = stake_account_reward_epochs(
+                    first_reward_epoch,
+                    rewards_before
+                        .or(before_epoch)
+                        .unwrap_or_else(|| epoch_schedule.get_epoch(self.get_slot(commitment))),
+                );
+                let oldest_reward_epoch = if reward_epochs.start > first_reward_epoch {
+                    Some(reward_epochs.start)
+                } else {
+                    None
+                };
+                let rewards = reward_epochs
+                    .rev()
+                    .filter_map(|epoch| {
+                        // Skip epochs whose reward blocks are no longer available.
+                        self.get_inflation_reward(
+                            vec![address],
+                            Some(RpcEpochConfig {
+                                epoch: Some(epoch),
+                                commitment,
+                            }),
+                        )
+                        .ok()?
+                        .pop()?
+                    })
+                    .collect();
+                (rewards, oldest_reward_epoch)
+            }
+            None => (vec![], None),
+        };
+
+        // Older rewards of the span are fetched relative to the same oldest transaction
+        let oldest_signature = if signatures.len() == limit || oldest_reward_epoch.is_some() {
+            signatures
+                .last()
+                .map(|status| status.signature.clone())
+                .or_else(|| before.map(|before| before.to_string()))
+        } else {
+            None
+        };
+        Ok(RpcStakeAccountHistory {
+            events,
+            rewards,
+            oldest_signature,
+            oldest_reward_epoch,
+        })
+    }
+
     pub fn get_token_account_balance(
         &self,
         pubkey: &Pubkey,
@@ -1730,18 +1854,27 @@ impl JsonRpcRequestProcessor {
     }
 }
 
-fn verify_transaction(transaction: &Transaction) -> Result<()> {
+fn verify_transaction(transaction: &Transaction, feature_set: &FeatureSet) -> Result<()> {
     if transaction.verify().is_err() {
         return Err(RpcCustomError::TransactionSignatureVerificationFailure.into());
     }
 
-    if let Err(e) = transaction.verify_precompiles() {
+    if let Err(e) = transaction.verify_precompiles_with_features(feature_set) {
         return Err(RpcCustomError::TransactionPrecompileVerificationFailure(e).into());
     }
 
     Ok(())
 }
 
+/// The epochs whose rewards are returned with a page of stake account history
+/// that starts at `first_epoch` and ends before `before_epoch`, capped to the
+/// newest `MAX_GET_STAKE_ACCOUNT_HISTORY_REWARD_EPOCHS`
+fn stake_account_reward_epochs(first_epoch: Epoch, before_epoch: Epoch) -> Range<Epoch> {
+    let first_epoch =
+        first_epoch.max(before_epoch.saturating_sub(MAX_GET_STAKE_ACCOUNT_HISTORY_REWARD_EPOCHS));
+    first_epoch..before_epoch
+}
+
 fn verify_filter(input: &RpcFilterType) -> Result<()> {
     input
         .verify()
@@ -1760,6 +1893,77 @@ fn verify_signature(input: &str) -> Result<Signature> {
         .map_err(|e| Error::invalid_params(format!("Invalid param: {:?}", e)))
 }
 
+// Return the stake instructions in `transaction`, including inner instructions, whose parsed
+// info references `address`.
+fn get_stake_account_events(
+    address: &str,
+    signature: &str,
+    transaction: EncodedConfirmedTransaction,
+) -> Vec<RpcStakeAccountEvent> {
+    let EncodedConfirmedTransaction {
+        slot,
+        transaction,
+        block_time,
+    } = transaction;
+    let instructions = match transaction.transaction {
+        EncodedTransaction::Json(UiTransaction {
+            message: UiMessage::Parsed(message),
+            ..
+        }) => message.instructions,
+        _ => return vec![],
+    };
+    let (err, inner_instructions) = match transaction.meta {
+        Some(meta) => (meta.err, meta.inner_instructions.unwrap_or_default()),
+        None => (None, vec![]),
+    };
+
+    let stake_program_id = solana_stake_program::id().to_string();
+    let parse_event = |instruction: UiInstruction| match instruction {
+        UiInstruction::Parsed(UiParsedInstruction::Parsed(instruction))
+            if instruction.program_id == stake_program_id =>
+        {
+            serde_json::from_value::<ParsedInstructionEnum>(instruction.parsed)
+                .ok()
+                .filter(|parsed| {
+                    parsed.info.as_object().map_or(false, |info| {
+                        info.values().any(|value| value.as_str() == Some(address))
+                    })
+                })
+        }
+        _ => None,
+    };
+    let new_event = |instruction_index: usize,
+                     inner_instruction_index: Option<usize>,
+                     parsed: ParsedInstructionEnum| RpcStakeAccountEvent {
+        signature: signature.to_string(),
+        slot,
+        block_time,
+        err: err.clone(),
+        instruction_index: instruction_index as u8,
+        inner_instruction_index,
+        event_type: parsed.instruction_type,
+        info: parsed.info,
+    };
+
+    let mut events = vec![];
+    for (i, instruction) in instructions.into_iter().enumerate() {
+        if let Some(parsed) = parse_event(instruction) {
+            events.push(new_event(i, None, parsed));
+        }
+        for inner_instructions in inner_instructions
+            .iter()
+            .filter(|inner_instructions| inner_instructions.index as usize == i)
+        {
+            for (j, instruction) in inner_instructions.instructions.iter().enumerate() {
+                if let Some(parsed) = parse_event(instruction.clone()) {
+                    events.push(new_event(i, Some(j), parsed));
+                }
+            }
+        }
+    }
+    events
+}
+
 fn verify_token_account_filter(
     token_account_filter: RpcTokenAccountsFilter,
 ) -> Result<TokenAccountsFilter> {
@@ -2133,7 +2337,7 @@ pub mod rpc_minimal {
         fn get_identity(&self, meta: Self::Metadata) -> Result<RpcIdentity> {
             debug!("get_identity rpc request received");
             Ok(RpcIdentity {
-                identity: meta.config.identity_pubkey.to_string(),
+                identity: meta.cluster_info.id().to_string(),
             })
         }
 
@@ -2252,6 +2456,13 @@ pub mod rpc_full {
             commitment: Option<CommitmentConfig>,
         ) -> Result<u64>;
 
+        #[rpc(meta, name = "getStakeMinimumDelegation")]
+        fn get_stake_minimum_delegation(
+            &self,
+            meta: Self::Metadata,
+            commitment: Option<CommitmentConfig>,
+        ) -> Result<RpcResponse<u64>>;
+
         #[rpc(meta, name = "getInflationReward")]
         fn get_inflation_reward(
             &self,
@@ -2449,6 +2660,14 @@ pub mod rpc_full {
             config: Option<RpcEpochConfig>,
         ) -> Result<RpcStakeActivation>;
 
+        #[rpc(meta, name = "getStakeAccountHistory")]
+        fn get_stake_account_history(
+            &self,
+            meta: Self::Metadata,
+            pubkey_str: String,
+            config: Option<RpcStakeAccountHistoryConfig>,
+        ) -> Result<RpcStakeAccountHistory>;
+
         // SPL Token-specific RPC endpoints
         // See https://github.com/solana-labs/solana-program-library/releases/tag/token-v2.0.0 for
         // program details
@@ -2555,6 +2774,15 @@ pub mod rpc_full {
             Ok(meta.get_minimum_balance_for_rent_exemption(data_len, commitment))
         }
 
+        fn get_stake_minimum_delegation(
+            &self,
+            meta: Self::Metadata,
+            commitment: Option<CommitmentConfig>,
+        ) -> Result<RpcResponse<u64>> {
+            debug!("get_stake_minimum_delegation rpc request received");
+            Ok(meta.get_stake_minimum_delegation(commitment))
+        }
+
         fn get_program_accounts(
             &self,
             meta: Self::Metadata,
@@ -2862,7 +3090,7 @@ pub mod rpc_full {
             }
 
             if !config.skip_preflight {
-                if let Err(e) = verify_transaction(&transaction) {
+                if let Err(e) = verify_transaction(&transaction, &preflight_bank.feature_set) {
                     return Err(e);
                 }
 
@@ -2882,12 +3110,16 @@ pub mod rpc_full {
                     }
                 }
 
-                if let (Err(err), logs) = preflight_bank.simulate_transaction(transaction.clone()) {
+                if let (Err(err), logs, return_data, units_consumed) =
+                    preflight_bank.simulate_transaction(transaction.clone())
+                {
                     return Err(RpcCustomError::SendTransactionPreflightFailure {
                         message: format!("Transaction simulation failed: {}", err),
                         result: RpcSimulateTransactionResult {
                             err: Some(err),
                             logs: Some(logs),
+                            return_data: return_data.map(|return_data| return_data.into()),
+                            units_consumed,
                         },
                     }
                     .into());
@@ -2914,20 +3146,21 @@ pub mod rpc_full {
             let encoding = config.encoding.unwrap_or(UiTransactionEncoding::Base58);
             let (_, transaction) = deserialize_transaction(data, encoding)?;
 
+            let bank = &*meta.bank(config.commitment);
             if config.sig_verify {
-                if let Err(e) = verify_transaction(&transaction) {
-                    return Err(e);
-                }
+                verify_transaction(&transaction, &bank.feature_set)?;
             }
 
-            let bank = &*meta.bank(config.commitment);
-            let (result, logs) = bank.simulate_transaction(transaction);
+            let (result, logs, return_data, units_consumed) =
+                bank.simulate_transaction(transaction);
 
             Ok(new_response(
                 &bank,
                 RpcSimulateTransactionResult {
                     err: result.err(),
                     logs: Some(logs),
+                    return_data: return_data.map(|return_data| return_data.into()),
+                    units_consumed,
                 },
             ))
         }
@@ -3105,6 +3338,46 @@ pub mod rpc_full {
             meta.get_stake_activation(&pubkey, config)
         }
 
+        fn get_stake_account_history(
+            &self,
+            meta: Self::Metadata,
+            pubkey_str: String,
+            config: Option<RpcStakeAccountHistoryConfig>,
+        ) -> Result<RpcStakeAccountHistory> {
+            debug!(
+                "get_stake_account_history rpc request received: {:?}",
+                pubkey_str
+            );
+            let pubkey = verify_pubkey(pubkey_str)?;
+
+            let config = config.unwrap_or_default();
+            let before = config
+                .before
+                .map(|ref before| verify_signature(before))
+                .transpose()?;
+            let until = config
+                .until
+                .map(|ref until| verify_signature(until))
+                .transpose()?;
+            let limit = config.limit.unwrap_or(MAX_GET_STAKE_ACCOUNT_HISTORY_LIMIT);
+
+            if limit == 0 || limit > MAX_GET_STAKE_ACCOUNT_HISTORY_LIMIT {
+                return Err(Error::invalid_params(format!(
+                    "Invalid limit; max {}",
+                    MAX_GET_STAKE_ACCOUNT_HISTORY_LIMIT
+                )));
+            }
+
+            meta.get_stake_account_history(
+                pubkey,
+                before,
+                until,
+                config.rewards_before,
+                limit,
+                config.commitment,
+            )
+        }
+
         fn get_inflation_reward(
             &self,
             meta: Self::Metadata,
@@ -3598,8 +3871,9 @@ pub mod tests {
         transaction::{self, TransactionError},
     };
     use solana_transaction_status::{
-        EncodedConfirmedBlock, EncodedTransaction, EncodedTransactionWithStatusMeta,
-        TransactionDetails, UiMessage,
+        ConfirmedTransaction, EncodedConfirmedBlock, EncodedTransaction,
+        EncodedTransactionWithStatusMeta, InnerInstructions, TransactionDetails,
+        TransactionStatusMeta, TransactionWithStatusMeta, UiMessage,
     };
     use solana_vote_program::{
         vote_instruction,
@@ -3736,7 +4010,10 @@ pub mod tests {
         let tx = system_transaction::transfer(&alice, pubkey, std::u64::MAX, blockhash);
         let _ = bank.process_transaction(&tx);
 
-        let cluster_info = Arc::new(ClusterInfo::default());
+        let cluster_info = Arc::new(ClusterInfo::new_with_invalid_keypair(ContactInfo {
+            id: *pubkey,
+            ..ContactInfo::default()
+        }));
         let tpu_address = cluster_info.my_contact_info().tpu;
 
         cluster_info.insert_info(ContactInfo::new_with_pubkey_socketaddr(
@@ -3761,7 +4038,6 @@ pub mod tests {
         let (meta, receiver) = JsonRpcRequestProcessor::new(
             JsonRpcConfig {
                 enable_rpc_transaction_history: true,
-                identity_pubkey: *pubkey,
                 ..JsonRpcConfig::default()
             },
             None,
@@ -3954,6 +4230,136 @@ pub mod tests {
         assert_eq!(expected, result);
     }
 
+    #[test]
+    fn test_get_stake_account_events() {
+        let stake_pubkey = solana_sdk::pubkey::new_rand();
+        let stake_authority = solana_sdk::pubkey::new_rand();
+        let vote_pubkey = solana_sdk::pubkey::new_rand();
+        let other_stake_pubkey = solana_sdk::pubkey::new_rand();
+        let other_authority = solana_sdk::pubkey::new_rand();
+        let instructions = vec![
+            solana_stake_program::stake_instruction::delegate_stake(
+                &stake_pubkey,
+                &stake_authority,
+                &vote_pubkey,
+            ),
+            solana_stake_program::stake_instruction::deactivate_stake(
+                &other_stake_pubkey,
+                &other_authority,
+            ),
+            system_instruction::transfer(&stake_authority, &stake_pubkey, 1),
+        ];
+        let message = Message::new(&instructions, Some(&stake_authority));
+        let inner_instruction = message.compile_instruction(
+            &solana_stake_program::stake_instruction::deactivate_stake(
+                &stake_pubkey,
+                &stake_authority,
+            ),
+        );
+        let encoded_transaction = || {
+            ConfirmedTransaction {
+                slot: 3,
+                transaction: TransactionWithStatusMeta {
+                    transaction: Transaction::new_unsigned(message.clone()),
+                    meta: Some(TransactionStatusMeta {
+                        inner_instructions: Some(vec![InnerInstructions {
+                            index: 0,
+                            instructions: vec![inner_instruction.clone()],
+                        }]),
+                        ..TransactionStatusMeta::default()
+                    }),
+                },
+                block_time: Some(42),
+            }
+            .encode(UiTransactionEncoding::JsonParsed)
+        };
+
+        let address = stake_pubkey.to_string();
+        let events = get_stake_account_events(&address, "signature", encoded_transaction());
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].signature, "signature");
+        assert_eq!(events[0].slot, 3);
+        assert_eq!(events[0].block_time, Some(42));
+        assert_eq!(events[0].err, None);
+        assert_eq!(events[0].event_type, "delegate");
+        assert_eq!(events[0].instruction_index, 0);
+        assert_eq!(events[0].inner_instruction_index, None);
+        assert_eq!(
+            events[0].info["voteAccount"],
+            json!(vote_pubkey.to_string())
+        );
+        assert_eq!(events[1].event_type, "deactivate");
+        assert_eq!(events[1].instruction_index, 0);
+        assert_eq!(events[1].inner_instruction_index, Some(0));
+
+        let events = get_stake_account_events(
+            &other_stake_pubkey.to_string(),
+            "signature",
+            encoded_transaction(),
+        );
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event_type, "deactivate");
+        assert_eq!(events[0].instruction_index, 1);
+
+        // System instructions are not stake account events
+        let events = get_stake_account_events(
+            &solana_sdk::pubkey::new_rand().to_string(),
+            "signature",
+            encoded_transaction(),
+        );
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn test_stake_account_reward_epochs() {
+        assert_eq!(stake_account_reward_epochs(3, 6), 3..6);
+        assert_eq!(stake_account_reward_epochs(0, 10), 0..10);
+        assert_eq!(stake_account_reward_epochs(0, 11), 1..11);
+        assert_eq!(stake_account_reward_epochs(0, 1_001), 991..1_001);
+        assert!(stake_account_reward_epochs(6, 6).is_empty());
+        assert!(stake_account_reward_epochs(0, 0).is_empty());
+
+        // paging with the oldest epoch of each page covers the whole span once
+        let mut epochs = vec![];
+        let mut before_epoch = 25;
+        loop {
+            let page = stake_account_reward_epochs(3, before_epoch);
+            epochs.extend(page.clone().rev());
+            if page.start == 3 {
+                break;
+            }
+            before_epoch = page.start;
+        }
+        assert_eq!(epochs, (3..25).rev().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_rpc_get_stake_account_history_invalid_limit() {
+        let bob_pubkey = solana_sdk::pubkey::new_rand();
+        let RpcHandler { io, meta, .. } = start_rpc_handler_with_tx(&bob_pubkey);
+
+        let req = format!(
+            r#"{{"jsonrpc":"2.0","id":1,"method":"getStakeAccountHistory","params":["{}", {{"limit": 1000}}]}}"#,
+            bob_pubkey
+        );
+        let res = io.handle_request_sync(&req, meta);
+        let result: Response = serde_json::from_str(&res.expect("actual response"))
+            .expect("actual response deserialization");
+
+        let expected = json!({
+            "jsonrpc": "2.0",
+            "error": {
+                "code": -32602,
+                "message": "Invalid limit; max 100"
+            },
+            "id": 1
+        });
+
+        let expected: Response =
+            serde_json::from_value(expected).expect("expected response deserialization");
+        assert_eq!(expected, result);
+    }
+
     #[test]
     fn test_rpc_get_slot_leader() {
         let bob_pubkey = solana_sdk::pubkey::new_rand();
@@ -4135,6 +4541,40 @@ pub mod tests {
         );
     }
 
+    #[test]
+    fn test_rpc_get_stake_minimum_delegation() {
+        let get_stake_minimum_delegation = |bank: Bank| {
+            let meta = JsonRpcRequestProcessor::new_from_bank(&Arc::new(bank));
+            let mut io = MetaIoHandler::default();
+            io.extend_with(rpc_full::FullImpl.to_delegate());
+
+            let req = r#"{"jsonrpc":"2.0","id":1,"method":"getStakeMinimumDelegation"}"#;
+            let res = io.handle_request_sync(req, meta);
+            let result: RpcResponse<u64> = serde_json::from_value(
+                serde_json::from_str::<Value>(&res.expect("actual response")).unwrap()["result"]
+                    .clone(),
+            )
+            .expect("actual response deserialization");
+            result.value
+        };
+        let genesis = create_genesis_config(100);
+
+        let mut bank = Bank::new(&genesis.genesis_config);
+        bank.deactivate_feature(
+            &solana_sdk::feature_set::stake_raise_minimum_delegation_to_1_sol::id(),
+        );
+        assert_eq!(get_stake_minimum_delegation(bank), 0);
+
+        let mut bank = Bank::new(&genesis.genesis_config);
+        bank.activate_feature(
+            &solana_sdk::feature_set::stake_raise_minimum_delegation_to_1_sol::id(),
+        );
+        assert_eq!(
+            get_stake_minimum_delegation(bank),
+            solana_sdk::native_token::LAMPORTS_PER_SOL
+        );
+    }
+
     #[test]
     fn test_rpc_get_inflation() {
         let bob_pubkey = solana_sdk::pubkey::new_rand();
@@ -4746,7 +5186,7 @@ pub mod tests {
                 "value":{"err":null, "logs":[
                     "Program 11111111111111111111111111111111 invoke [1]",
                     "Program 11111111111111111111111111111111 success"
-                ]}
+                ], "unitsConsumed":0}
             },
             "id": 1,
         });
@@ -4790,7 +5230,7 @@ pub mod tests {
                 "value":{"err":null, "logs":[
                     "Program 11111111111111111111111111111111 invoke [1]",
                     "Program 11111111111111111111111111111111 success"
-                ]}
+                ], "unitsConsumed":0}
             },
             "id": 1,
         });
@@ -4813,7 +5253,7 @@ pub mod tests {
                 "value":{"err":null, "logs":[
                     "Program 11111111111111111111111111111111 invoke [1]",
                     "Program 11111111111111111111111111111111 success"
-                ]}
+                ], "unitsConsumed":0}
             },
             "id": 1,
         });
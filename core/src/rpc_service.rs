@@ -33,6 +33,7 @@ use std::{
     sync::atomic::{AtomicBool, AtomicU64, Ordering},
     sync::{mpsc::channel, Arc, Mutex, RwLock},
     thread::{self, Builder, JoinHandle},
+    time::UNIX_EPOCH,
 };
 use tokio::runtime;
 use tokio_util::codec::{BytesCodec, FramedRead};
@@ -131,7 +132,30 @@ impl RpcRequestMiddleware {
         tokio_02::fs::File::open(path).await
     }
 
-    fn process_file_get(&self, path: &str) -> RequestMiddlewareAction {
+    /// Parses a single `bytes=` range from a `Range` header value into an
+    /// inclusive (start, end) pair within a file of `file_length` bytes.
+    fn byte_range(range: &str, file_length: u64) -> Option<(u64, u64)> {
+        let mut range = range.strip_prefix("bytes=")?.trim().splitn(2, '-');
+        let (start, end) = (range.next()?, range.next()?);
+        let last = file_length.checked_sub(1)?;
+        match (start.parse::<u64>().ok(), end.parse::<u64>().ok()) {
+            (Some(start), Some(end)) if start <= end && start <= last => {
+                Some((start, end.min(last)))
+            }
+            (Some(start), None) if end.is_empty() && start <= last => Some((start, last)),
+            (None, Some(suffix)) if start.is_empty() && suffix > 0 => {
+                Some((file_length.saturating_sub(suffix), last))
+            }
+            _ => None,
+        }
+    }
+
+    fn process_file_get(
+        &self,
+        path: &str,
+        range: Option<&str>,
+        if_range: Option<&str>,
+    ) -> RequestMiddlewareAction {
         let stem = path.split_at(1).1; // Drop leading '/' from path
         let filename = {
             match path {
@@ -150,27 +174,66 @@ impl RpcRequestMiddleware {
             }
         };
 
-        let file_length = std::fs::metadata(&filename)
-            .map(|m| m.len())
-            .unwrap_or(0)
-            .to_string();
-        info!("get {} -> {:?} ({} bytes)", path, filename, file_length);
+        let metadata = std::fs::metadata(&filename).ok();
+        let file_length = metadata.as_ref().map(|m| m.len()).unwrap_or(0);
+        // Identifies this copy of the file, so that a resumed download isn't
+        // spliced together from two different files
+        let etag = metadata
+            .and_then(|m| m.modified().ok())
+            .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+            .map(|modified| format!("\"{:x}-{:x}\"", file_length, modified.as_nanos()));
+        // A range is only served if the file still matches `If-Range`
+        let range =
+            range.filter(|_| if_range.map_or(true, |if_range| Some(if_range) == etag.as_deref()));
+        let byte_range = range.map(|range| Self::byte_range(range, file_length));
+        info!(
+            "get {} -> {:?} ({} bytes, range {:?})",
+            path, filename, file_length, range
+        );
         RequestMiddlewareAction::Respond {
             should_validate_hosts: true,
-            response: Box::pin(async {
-                match Self::open_no_follow(filename).await {
-                    Err(_) => Ok(Self::internal_server_error()),
-                    Ok(file) => {
-                        let stream =
-                            FramedRead::new(file, BytesCodec::new()).map_ok(|b| b.freeze());
-                        let body = hyper::Body::wrap_stream(stream);
-
-                        Ok(hyper::Response::builder()
-                            .header(hyper::header::CONTENT_LENGTH, file_length)
-                            .body(body)
-                            .unwrap())
+            response: Box::pin(async move {
+                use tokio_02::io::AsyncReadExt;
+
+                let (start, end) = match byte_range {
+                    None => (0, file_length.saturating_sub(1)),
+                    Some(Some(byte_range)) => byte_range,
+                    Some(None) => {
+                        return Ok(hyper::Response::builder()
+                            .status(hyper::StatusCode::RANGE_NOT_SATISFIABLE)
+                            .header(
+                                hyper::header::CONTENT_RANGE,
+                                format!("bytes */{}", file_length),
+                            )
+                            .body(hyper::Body::empty())
+                            .unwrap());
                     }
+                };
+                let mut file = match Self::open_no_follow(filename).await {
+                    Err(_) => return Ok(Self::internal_server_error()),
+                    Ok(file) => file,
+                };
+                if file.seek(std::io::SeekFrom::Start(start)).await.is_err() {
+                    return Ok(Self::internal_server_error());
                 }
+                let content_length = if file_length == 0 { 0 } else { end - start + 1 };
+                let stream = FramedRead::new(file.take(content_length), BytesCodec::new())
+                    .map_ok(|b| b.freeze());
+                let body = hyper::Body::wrap_stream(stream);
+
+                let mut response = hyper::Response::builder()
+                    .header(hyper::header::ACCEPT_RANGES, "bytes")
+                    .header(hyper::header::CONTENT_LENGTH, content_length.to_string());
+                if let Some(etag) = etag {
+                    response = response.header(hyper::header::ETAG, etag);
+                }
+                if byte_range.is_some() {
+                    response = response.status(hyper::StatusCode::PARTIAL_CONTENT).header(
+                        hyper::header::CONTENT_RANGE,
+                        format!("bytes {}-{}/{}", start, end, file_length),
+                    );
+                }
+                Ok(response.body(body).unwrap())
             }),
         }
     }
@@ -219,7 +282,15 @@ impl RequestMiddleware for RpcRequestMiddleware {
                 .unwrap()
                 .into()
         } else if self.is_file_get_path(request.uri().path()) {
-            self.process_file_get(request.uri().path())
+            let range = request
+                .headers()
+                .get(hyper::header::RANGE)
+                .and_then(|range| range.to_str().ok());
+            let if_range = request
+                .headers()
+                .get(hyper::header::IF_RANGE)
+                .and_then(|if_range| if_range.to_str().ok());
+            self.process_file_get(request.uri().path(), range, if_range)
         } else if request.uri().path() == "/health" {
             hyper::Response::builder()
                 .status(hyper::StatusCode::OK)
@@ -632,7 +703,7 @@ mod tests {
         );
 
         // File does not exist => request should fail.
-        let action = rrm.process_file_get("/genesis.tar.bz2");
+        let action = rrm.process_file_get("/genesis.tar.bz2", None, None);
         if let RequestMiddlewareAction::Respond { response, .. } = action {
             let response = runtime.block_on(response);
             let response = response.unwrap();
@@ -647,7 +718,7 @@ mod tests {
         }
 
         // Normal file exist => request should succeed.
-        let action = rrm.process_file_get("/genesis.tar.bz2");
+        let action = rrm.process_file_get("/genesis.tar.bz2", None, None);
         if let RequestMiddlewareAction::Respond { response, .. } = action {
             let response = runtime.block_on(response);
             let response = response.unwrap();
@@ -666,7 +737,7 @@ mod tests {
             symlink::symlink_file("wrong", &genesis_path).unwrap();
 
             // File is a symbolic link => request should fail.
-            let action = rrm.process_file_get("/genesis.tar.bz2");
+            let action = rrm.process_file_get("/genesis.tar.bz2", None, None);
             if let RequestMiddlewareAction::Respond { response, .. } = action {
                 let response = runtime.block_on(response);
                 let response = response.unwrap();
@@ -677,6 +748,100 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_process_file_get_range() {
+        let mut runtime = tokio_02::runtime::Runtime::new().unwrap();
+
+        let ledger_path = get_tmp_ledger_path!();
+        std::fs::create_dir(&ledger_path).unwrap();
+        {
+            let mut file = std::fs::File::create(ledger_path.join("genesis.tar.bz2")).unwrap();
+            file.write_all(b"0123456789").unwrap();
+        }
+        let rrm =
+            RpcRequestMiddleware::new(ledger_path, None, create_bank_forks(), RpcHealth::stub());
+
+        let mut get_with_etag = |range, if_range| {
+            if let RequestMiddlewareAction::Respond { response, .. } =
+                rrm.process_file_get("/genesis.tar.bz2", range, if_range)
+            {
+                let response = runtime.block_on(response).unwrap();
+                let status = response.status();
+                let content_range = response
+                    .headers()
+                    .get(hyper::header::CONTENT_RANGE)
+                    .map(|content_range| content_range.to_str().unwrap().to_string());
+                let etag = response
+                    .headers()
+                    .get(hyper::header::ETAG)
+                    .map(|etag| etag.to_str().unwrap().to_string());
+                let body = runtime
+                    .block_on(hyper::body::to_bytes(response.into_body()))
+                    .unwrap();
+                ((status, content_range, body.to_vec()), etag)
+            } else {
+                panic!("Unexpected RequestMiddlewareAction variant");
+            }
+        };
+        let (_, etag) = get_with_etag(None, None);
+        let etag = etag.unwrap();
+        let mut get = |range| get_with_etag(range, None).0;
+
+        assert_eq!(
+            get(None),
+            (hyper::StatusCode::OK, None, b"0123456789".to_vec())
+        );
+        assert_eq!(
+            get(Some("bytes=2-4")),
+            (
+                hyper::StatusCode::PARTIAL_CONTENT,
+                Some("bytes 2-4/10".to_string()),
+                b"234".to_vec()
+            )
+        );
+        assert_eq!(
+            get(Some("bytes=7-")),
+            (
+                hyper::StatusCode::PARTIAL_CONTENT,
+                Some("bytes 7-9/10".to_string()),
+                b"789".to_vec()
+            )
+        );
+        assert_eq!(
+            get(Some("bytes=-2")),
+            (
+                hyper::StatusCode::PARTIAL_CONTENT,
+                Some("bytes 8-9/10".to_string()),
+                b"89".to_vec()
+            )
+        );
+        assert_eq!(
+            get(Some("bytes=10-")),
+            (
+                hyper::StatusCode::RANGE_NOT_SATISFIABLE,
+                Some("bytes */10".to_string()),
+                vec![]
+            )
+        );
+
+        // A range is only served while the file still matches `If-Range`
+        assert_eq!(
+            get_with_etag(Some("bytes=7-"), Some(&etag)),
+            (
+                (
+                    hyper::StatusCode::PARTIAL_CONTENT,
+                    Some("bytes 7-9/10".to_string()),
+                    b"789".to_vec()
+                ),
+                Some(etag.clone())
+            )
+        );
+        assert_eq!(
+            get_with_etag(Some("bytes=7-"), Some("\"0-0\"")).0,
+            (hyper::StatusCode::OK, None, b"0123456789".to_vec())
+        );
+    }
+
     #[test]
     fn test_health_check_with_no_trusted_validators() {
         let rm = RpcRequestMiddleware::new(
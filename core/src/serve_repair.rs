@@ -13,12 +13,7 @@ use solana_ledger::{blockstore::Blockstore, shred::Nonce};
 use solana_measure::measure::Measure;
 use solana_metrics::inc_new_counter_debug;
 use solana_perf::packet::{limited_deserialize, Packets, PacketsRecycler};
-use solana_sdk::{
-    clock::Slot,
-    pubkey::Pubkey,
-    signature::{Keypair, Signer},
-    timing::duration_as_ms,
-};
+use solana_sdk::{clock::Slot, pubkey::Pubkey, timing::duration_as_ms};
 use solana_streamer::streamer::{PacketReceiver, PacketSender};
 use std::{
     collections::{hash_map::Entry, HashMap, HashSet},
@@ -74,9 +69,6 @@ pub enum RepairProtocol {
 
 #[derive(Clone)]
 pub struct ServeRepair {
-    /// set the keypair that will be used to sign repair responses
-    keypair: Arc<Keypair>,
-    my_info: ContactInfo,
     cluster_info: Arc<ClusterInfo>,
 }
 
@@ -91,20 +83,15 @@ impl ServeRepair {
     }
 
     pub fn new(cluster_info: Arc<ClusterInfo>) -> Self {
-        let (keypair, my_info) = { (cluster_info.keypair.clone(), cluster_info.my_contact_info()) };
-        Self {
-            keypair,
-            my_info,
-            cluster_info,
-        }
+        Self { cluster_info }
     }
 
-    pub fn my_info(&self) -> &ContactInfo {
-        &self.my_info
+    pub fn my_info(&self) -> ContactInfo {
+        self.cluster_info.my_contact_info()
     }
 
-    pub fn keypair(&self) -> &Arc<Keypair> {
-        &self.keypair
+    pub fn my_id(&self) -> Pubkey {
+        self.cluster_info.id()
     }
 
     fn get_repair_sender(request: &RepairProtocol) -> &ContactInfo {
@@ -129,7 +116,7 @@ impl ServeRepair {
         let now = Instant::now();
 
         //TODO verify from is signed
-        let my_id = me.read().unwrap().keypair.pubkey();
+        let my_id = me.read().unwrap().my_id();
         let from = Self::get_repair_sender(&request);
         if from.id == my_id {
             stats.self_repair += 1;
@@ -146,7 +133,7 @@ impl ServeRepair {
                             from,
                             &from_addr,
                             blockstore,
-                            &me.read().unwrap().my_info,
+                            &me.read().unwrap().my_info(),
                             *slot,
                             *shred_index,
                             *nonce,
@@ -244,7 +231,7 @@ impl ServeRepair {
 
     fn report_reset_stats(me: &Arc<RwLock<Self>>, stats: &mut ServeRepairStats) {
         if stats.self_repair > 0 {
-            let my_id = me.read().unwrap().keypair.pubkey();
+            let my_id = me.read().unwrap().my_id();
             warn!(
                 "{}: Ignored received repair requests from ME: {}",
                 my_id, stats.self_repair,
@@ -341,8 +328,7 @@ impl ServeRepair {
         shred_index: u64,
         nonce: Nonce,
     ) -> Result<Vec<u8>> {
-        let req =
-            RepairProtocol::WindowIndexWithNonce(self.my_info.clone(), slot, shred_index, nonce);
+        let req = RepairProtocol::WindowIndexWithNonce(self.my_info(), slot, shred_index, nonce);
         let out = serialize(&req)?;
         Ok(out)
     }
@@ -353,18 +339,14 @@ impl ServeRepair {
         shred_index: u64,
         nonce: Nonce,
     ) -> Result<Vec<u8>> {
-        let req = RepairProtocol::HighestWindowIndexWithNonce(
-            self.my_info.clone(),
-            slot,
-            shred_index,
-            nonce,
-        );
+        let req =
+            RepairProtocol::HighestWindowIndexWithNonce(self.my_info(), slot, shred_index, nonce);
         let out = serialize(&req)?;
         Ok(out)
     }
 
     fn orphan_bytes(&self, slot: Slot, nonce: Nonce) -> Result<Vec<u8>> {
-        let req = RepairProtocol::OrphanWithNonce(self.my_info.clone(), slot, nonce);
+        let req = RepairProtocol::OrphanWithNonce(self.my_info(), slot, nonce);
         let out = serialize(&req)?;
         Ok(out)
     }
@@ -453,10 +435,11 @@ impl ServeRepair {
         slot: Slot,
     ) -> Vec<ContactInfo> {
         if let Some(repair_validators) = repair_validators {
+            let my_id = self.my_id();
             repair_validators
                 .iter()
                 .filter_map(|key| {
-                    if *key != self.my_info.id {
+                    if *key != my_id {
                         self.cluster_info.lookup_contact_info(key, |ci| ci.clone())
                     } else {
                         None
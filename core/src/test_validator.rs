@@ -397,8 +397,7 @@ impl TestValidator {
         let tpu = node.info.tpu;
         let gossip = node.info.gossip;
 
-        let mut rpc_config = config.rpc_config.clone();
-        rpc_config.identity_pubkey = validator_identity.pubkey();
+        let rpc_config = config.rpc_config.clone();
 
         {
             let mut authorized_voter_keypairs = config.authorized_voter_keypairs.write().unwrap();
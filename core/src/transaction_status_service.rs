@@ -7,7 +7,9 @@ use solana_ledger::{
 use solana_runtime::bank::{
     Bank, InnerInstructionsList, NonceRollbackInfo, TransactionLogMessages,
 };
-use solana_transaction_status::{InnerInstructions, TransactionStatusMeta};
+use solana_transaction_status::{
+    InnerInstructions, TransactionComputeUnits, TransactionStatusMeta,
+};
 use std::{
     sync::{
         atomic::{AtomicBool, AtomicU64, Ordering},
@@ -62,6 +64,8 @@ impl TransactionStatusService {
                 token_balances,
                 inner_instructions,
                 transaction_logs,
+                transaction_return_data,
+                transaction_compute_units,
             }) => {
                 let slot = bank.slot();
                 let inner_instructions_iter: Box<
@@ -86,6 +90,8 @@ impl TransactionStatusService {
                     post_token_balances,
                     inner_instructions,
                     log_messages,
+                    return_data,
+                    instruction_compute_units,
                 ) in izip!(
                     &transactions,
                     statuses,
@@ -94,7 +100,9 @@ impl TransactionStatusService {
                     token_balances.pre_token_balances,
                     token_balances.post_token_balances,
                     inner_instructions_iter,
-                    transaction_logs_iter
+                    transaction_logs_iter,
+                    transaction_return_data,
+                    transaction_compute_units
                 ) {
                     if Bank::can_commit(&status) && !transaction.signatures.is_empty() {
                         let fee_calculator = nonce_rollback
@@ -123,6 +131,8 @@ impl TransactionStatusService {
                         let log_messages = Some(log_messages);
                         let pre_token_balances = Some(pre_token_balances);
                         let post_token_balances = Some(post_token_balances);
+                        let compute_units =
+                            Some(TransactionComputeUnits::new(instruction_compute_units));
 
                         blockstore
                             .write_transaction_status(
@@ -139,6 +149,8 @@ impl TransactionStatusService {
                                     log_messages,
                                     pre_token_balances,
                                     post_token_balances,
+                                    return_data,
+                                    compute_units,
                                 },
                             )
                             .expect("Expect database write to succeed");
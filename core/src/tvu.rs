@@ -41,10 +41,7 @@ use solana_runtime::{
     commitment::BlockCommitmentCache,
     vote_sender_types::ReplayVoteSender,
 };
-use solana_sdk::{
-    pubkey::Pubkey,
-    signature::{Keypair, Signer},
-};
+use solana_sdk::{pubkey::Pubkey, signature::Keypair};
 use std::{
     boxed::Box,
     collections::HashSet,
@@ -129,8 +126,6 @@ impl Tvu {
         tvu_config: TvuConfig,
         max_slots: &Arc<MaxSlots>,
     ) -> Self {
-        let keypair: Arc<Keypair> = cluster_info.keypair.clone();
-
         let Sockets {
             repair: repair_socket,
             fetch: fetch_sockets,
@@ -249,7 +244,7 @@ impl Tvu {
         };
 
         let replay_stage_config = ReplayStageConfig {
-            my_pubkey: keypair.pubkey(),
+            my_pubkey: cluster_info.id(),
             vote_account: *vote_account,
             authorized_voter_keypairs,
             exit: exit.clone(),
@@ -343,6 +338,7 @@ pub mod tests {
         genesis_utils::{create_genesis_config, GenesisConfigInfo},
     };
     use solana_runtime::bank::Bank;
+    use solana_sdk::signature::Signer;
     use std::sync::atomic::Ordering;
 
     #[ignore]
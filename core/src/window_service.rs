@@ -367,7 +367,7 @@ impl WindowService {
         );
 
         let t_window = Self::start_recv_window_thread(
-            cluster_info.id(),
+            cluster_info,
             exit,
             &blockstore,
             insert_sender,
@@ -470,7 +470,7 @@ impl WindowService {
     }
 
     fn start_recv_window_thread<F>(
-        id: Pubkey,
+        cluster_info: Arc<ClusterInfo>,
         exit: &Arc<AtomicBool>,
         blockstore: &Arc<Blockstore>,
         insert_sender: CrossbeamSender<(Vec<Shred>, Vec<Option<RepairMeta>>)>,
@@ -491,7 +491,7 @@ impl WindowService {
             .name("solana-window".to_string())
             .spawn(move || {
                 let _exit = Finalizer::new(exit.clone());
-                trace!("{}: RECV_WINDOW started", id);
+                trace!("{}: RECV_WINDOW started", cluster_info.id());
                 let thread_pool = rayon::ThreadPoolBuilder::new()
                     .num_threads(get_thread_count())
                     .build()
@@ -505,6 +505,8 @@ impl WindowService {
                     if exit.load(Ordering::Relaxed) {
                         break;
                     }
+                    // The identity may be swapped at runtime, so look it up on every pass
+                    let id = cluster_info.id();
 
                     let mut handle_timeout = || {
                         if now.elapsed() > Duration::from_secs(30) {
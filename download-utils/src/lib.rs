@@ -5,16 +5,23 @@ use log::*;
 use solana_runtime::{bank_forks::ArchiveFormat, snapshot_utils};
 use solana_sdk::clock::Slot;
 use solana_sdk::hash::Hash;
-use std::fs::{self, File};
-use std::io;
-use std::io::Read;
+use std::collections::{HashMap, VecDeque};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom};
 use std::net::SocketAddr;
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::thread;
 use std::time::Instant;
 
 static TRUCK: Emoji = Emoji("🚚 ", "");
 static SPARKLE: Emoji = Emoji("✨ ", "");
 
+/// Size of the byte ranges requested from each peer during a parallel download
+const DOWNLOAD_CHUNK_SIZE: u64 = 64 * 1024 * 1024;
+/// Maximum number of peers downloaded from concurrently
+const MAX_PARALLEL_DOWNLOADS: usize = 8;
+
 /// Creates a new process bar for processing that will take an unknown amount of time
 fn new_spinner_progress_bar() -> ProgressBar {
     let progress_bar = ProgressBar::new(42);
@@ -24,6 +31,68 @@ fn new_spinner_progress_bar() -> ProgressBar {
     progress_bar
 }
 
+fn temp_download_file(destination_file: &Path) -> PathBuf {
+    let mut temp_destination_file = destination_file.to_path_buf();
+    temp_destination_file.set_file_name(format!(
+        "tmp-{}",
+        destination_file
+            .file_name()
+            .expect("file_name")
+            .to_str()
+            .expect("to_str")
+    ));
+    temp_destination_file
+}
+
+/// Holds the entity tag of the file being downloaded to `temp_destination_file`
+fn temp_download_etag_file(temp_destination_file: &Path) -> PathBuf {
+    let mut etag_file = temp_destination_file.as_os_str().to_owned();
+    etag_file.push(".etag");
+    PathBuf::from(etag_file)
+}
+
+/// Returns the response's entity tag, if it is a strong one and so may be used
+/// with `If-Range`
+fn get_etag(response: &reqwest::blocking::Response) -> Option<String> {
+    response
+        .headers()
+        .get(reqwest::header::ETAG)
+        .and_then(|etag| etag.to_str().ok())
+        .filter(|etag| !etag.starts_with("W/"))
+        .map(|etag| etag.to_string())
+}
+
+/// Parses a `Content-Range: bytes <start>-<end>/<total>` header value
+fn parse_content_range(content_range: &str) -> Option<(u64, u64, u64)> {
+    let content_range = content_range.strip_prefix("bytes ")?;
+    let mut parts = content_range.splitn(2, '/');
+    let (range, total) = (parts.next()?, parts.next()?);
+    let mut range = range.splitn(2, '-');
+    let start = range.next()?.parse().ok()?;
+    let end = range.next()?.parse().ok()?;
+    let total = total.parse().ok()?;
+    if start <= end && end < total {
+        Some((start, end, total))
+    } else {
+        None
+    }
+}
+
+fn get_content_range(response: &reqwest::blocking::Response) -> Option<(u64, u64, u64)> {
+    response
+        .headers()
+        .get(reqwest::header::CONTENT_RANGE)
+        .and_then(|content_range| content_range.to_str().ok())
+        .and_then(parse_content_range)
+}
+
+/// Downloads `url` to `destination_file`.
+///
+/// The download is staged in a `tmp-` file next to `destination_file`. If a
+/// previous attempt left a partial download behind, it is resumed with a range
+/// request, but only if the server confirms through `If-Range` that it still
+/// serves the file the partial download was started from. Otherwise the partial
+/// download is discarded.
 pub fn download_file(
     url: &str,
     destination_file: &Path,
@@ -37,41 +106,81 @@ pub fn download_file(
     fs::create_dir_all(destination_file.parent().expect("parent"))
         .map_err(|err| err.to_string())?;
 
-    let mut temp_destination_file = destination_file.to_path_buf();
-    temp_destination_file.set_file_name(format!(
-        "tmp-{}",
-        destination_file
-            .file_name()
-            .expect("file_name")
-            .to_str()
-            .expect("to_str")
-    ));
+    let temp_destination_file = temp_download_file(destination_file);
+    let etag_file = temp_download_etag_file(&temp_destination_file);
 
     let progress_bar = new_spinner_progress_bar();
     if use_progress_bar {
         progress_bar.set_message(&format!("{}Downloading {}...", TRUCK, url));
     }
 
-    let response = reqwest::blocking::Client::new()
-        .get(url)
-        .send()
-        .and_then(|response| response.error_for_status())
-        .map_err(|err| {
-            progress_bar.finish_and_clear();
-            err.to_string()
-        })?;
+    let client = reqwest::blocking::Client::new();
+    let send = |resume: Option<(u64, &str)>| {
+        let mut request = client.get(url);
+        if let Some((resume_from, etag)) = resume {
+            request = request
+                .header(reqwest::header::RANGE, format!("bytes={}-", resume_from))
+                .header(reqwest::header::IF_RANGE, etag);
+        }
+        request
+            .send()
+            .and_then(|response| response.error_for_status())
+            .map_err(|err| {
+                progress_bar.finish_and_clear();
+                err.to_string()
+            })
+    };
+
+    let partial_download = fs::read_to_string(&etag_file).ok().and_then(|etag| {
+        fs::metadata(&temp_destination_file)
+            .ok()
+            .map(|metadata| (metadata.len(), etag))
+            .filter(|(partial_size, _etag)| *partial_size > 0)
+    });
+    let resumed_response = partial_download.and_then(|(resume_from, etag)| {
+        let response = send(Some((resume_from, &etag))).ok()?;
+        if response.status() == reqwest::StatusCode::OK {
+            // The file changed, and the server sent all of the new one
+            return Some((response, 0));
+        }
+        match get_content_range(&response) {
+            Some((start, _end, _total))
+                if start == resume_from && get_etag(&response).as_ref() == Some(&etag) =>
+            {
+                info!("Resuming download of {} from byte {}", url, resume_from);
+                Some((response, resume_from))
+            }
+            _ => None,
+        }
+    });
+    let (response, resume_from) = match resumed_response {
+        Some(resumed_response) => resumed_response,
+        None => (send(None)?, 0),
+    };
+    if resume_from == 0 {
+        // Without an entity tag, a partial download can't be safely resumed
+        match get_etag(&response) {
+            Some(etag) => fs::write(&etag_file, etag)
+                .map_err(|err| format!("Unable to write {:?}: {:?}", etag_file, err))?,
+            None => {
+                let _ = fs::remove_file(&etag_file);
+            }
+        }
+    }
 
     let download_size = {
         response
             .headers()
             .get(reqwest::header::CONTENT_LENGTH)
             .and_then(|content_length| content_length.to_str().ok())
-            .and_then(|content_length| content_length.parse().ok())
+            .and_then(|content_length| content_length.parse::<u64>().ok())
+            .map(|content_length| content_length + resume_from)
             .unwrap_or(0)
     };
 
     if use_progress_bar {
         progress_bar.set_length(download_size);
+        progress_bar.set_position(resume_from);
         progress_bar.set_style(
             ProgressStyle::default_bar()
                 .template(
@@ -84,56 +193,37 @@ pub fn download_file(
         info!("Downloading {} bytes from {}", download_size, url);
     }
 
-    struct DownloadProgress<R> {
-        progress_bar: ProgressBar,
-        response: R,
-        last_print: Instant,
-        current_bytes: usize,
-        last_print_bytes: usize,
-        download_size: f32,
-        use_progress_bar: bool,
-    }
-
-    impl<R: Read> Read for DownloadProgress<R> {
-        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
-            self.response.read(buf).map(|n| {
-                if self.use_progress_bar {
-                    self.progress_bar.inc(n as u64);
-                } else {
-                    self.current_bytes += n;
-                    if self.last_print.elapsed().as_secs() > 5 {
-                        let total_bytes_f32 = self.current_bytes as f32;
-                        let diff_bytes_f32 = (self.current_bytes - self.last_print_bytes) as f32;
-                        info!(
-                            "downloaded {} bytes {:.1}% {:.1} bytes/s",
-                            self.current_bytes,
-                            100f32 * (total_bytes_f32 / self.download_size),
-                            diff_bytes_f32 / self.last_print.elapsed().as_secs_f32(),
-                        );
-                        self.last_print = Instant::now();
-                        self.last_print_bytes = self.current_bytes;
-                    }
-                }
-                n
-            })
-        }
-    }
-
     let mut source = DownloadProgress {
         progress_bar,
         response,
         last_print: Instant::now(),
-        current_bytes: 0,
-        last_print_bytes: 0,
+        current_bytes: resume_from as usize,
+        last_print_bytes: resume_from as usize,
         download_size: (download_size as f32).max(1f32),
         use_progress_bar,
     };
 
-    File::create(&temp_destination_file)
+    OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(resume_from > 0)
+        .truncate(resume_from == 0)
+        .open(&temp_destination_file)
         .and_then(|mut file| std::io::copy(&mut source, &mut file))
         .map_err(|err| format!("Unable to write {:?}: {:?}", temp_destination_file, err))?;
 
     source.progress_bar.finish_and_clear();
+
+    let downloaded_size = fs::metadata(&temp_destination_file)
+        .map(|metadata| metadata.len())
+        .map_err(|err| format!("Unable to read {:?}: {:?}", temp_destination_file, err))?;
+    if download_size > 0 && downloaded_size != download_size {
+        return Err(format!(
+            "Incomplete download of {}: {} of {} bytes",
+            url, downloaded_size, download_size
+        ));
+    }
+
     info!(
         "  {}{}",
         SPARKLE,
@@ -145,6 +235,226 @@ pub fn download_file(
         )
     );
 
+    std::fs::rename(temp_destination_file, destination_file)
+        .map_err(|err| format!("Unable to rename: {:?}", err))?;
+    let _ = fs::remove_file(&etag_file);
+
+    Ok(())
+}
+
+struct DownloadProgress<R> {
+    progress_bar: ProgressBar,
+    response: R,
+    last_print: Instant,
+    current_bytes: usize,
+    last_print_bytes: usize,
+    download_size: f32,
+    use_progress_bar: bool,
+}
+
+impl<R: Read> Read for DownloadProgress<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.response.read(buf).map(|n| {
+            if self.use_progress_bar {
+                self.progress_bar.inc(n as u64);
+            } else {
+                self.current_bytes += n;
+                if self.last_print.elapsed().as_secs() > 5 {
+                    let total_bytes_f32 = self.current_bytes as f32;
+                    let diff_bytes_f32 = (self.current_bytes - self.last_print_bytes) as f32;
+                    info!(
+                        "downloaded {} bytes {:.1}% {:.1} bytes/s",
+                        self.current_bytes,
+                        100f32 * (total_bytes_f32 / self.download_size),
+                        diff_bytes_f32 / self.last_print.elapsed().as_secs_f32(),
+                    );
+                    self.last_print = Instant::now();
+                    self.last_print_bytes = self.current_bytes;
+                }
+            }
+            n
+        })
+    }
+}
+
+/// Returns the size of the file at `url`, if the server supports range
+/// requests for it.
+fn get_ranged_download_size(client: &reqwest::blocking::Client, url: &str) -> Option<u64> {
+    let response = client
+        .get(url)
+        .header(reqwest::header::RANGE, "bytes=0-0")
+        .send()
+        .and_then(|response| response.error_for_status())
+        .ok()?;
+    if response.status() != reqwest::StatusCode::PARTIAL_CONTENT {
+        return None;
+    }
+    get_content_range(&response).map(|(_start, _end, total)| total)
+}
+
+fn download_range(
+    client: &reqwest::blocking::Client,
+    url: &str,
+    (start, end): (u64, u64),
+    file: &mut File,
+    progress_bar: &ProgressBar,
+) -> Result<(), String> {
+    let mut response = client
+        .get(url)
+        .header(reqwest::header::RANGE, format!("bytes={}-{}", start, end))
+        .send()
+        .and_then(|response| response.error_for_status())
+        .map_err(|err| err.to_string())?;
+    match get_content_range(&response) {
+        Some((range_start, range_end, _total)) if (range_start, range_end) == (start, end) => (),
+        content_range => {
+            return Err(format!(
+                "Unexpected content range {:?}, expected {}-{}",
+                content_range, start, end
+            ))
+        }
+    }
+    file.seek(SeekFrom::Start(start))
+        .map_err(|err| err.to_string())?;
+    let length = end - start + 1;
+    let written =
+        io::copy(&mut (&mut response).take(length), file).map_err(|err| err.to_string())?;
+    progress_bar.inc(written);
+    if written != length {
+        return Err(format!("Short read: {} of {} bytes", written, length));
+    }
+    Ok(())
+}
+
+/// Downloads the same file from several servers at once, each serving a
+/// share of the byte ranges. A server that fails stops taking ranges, and
+/// its outstanding range is handed to the remaining servers.
+///
+/// All servers are expected to serve identical content; the caller is
+/// responsible for verifying the result.
+pub fn download_file_from_peers(
+    urls: &[String],
+    destination_file: &Path,
+    use_progress_bar: bool,
+) -> Result<(), String> {
+    if destination_file.is_file() {
+        return Err(format!("{:?} already exists", destination_file));
+    }
+    let download_start = Instant::now();
+
+    // Only servers that support range requests, and agree on the file size,
+    // can share a download
+    let client = reqwest::blocking::Client::new();
+    let mut urls_by_size = HashMap::<u64, Vec<&String>>::new();
+    for url in urls.iter().take(MAX_PARALLEL_DOWNLOADS) {
+        if let Some(size) = get_ranged_download_size(&client, url) {
+            urls_by_size.entry(size).or_default().push(url);
+        }
+    }
+    let (download_size, urls) = urls_by_size
+        .into_iter()
+        .max_by_key(|(_size, urls)| urls.len())
+        .filter(|(size, urls)| *size > 0 && urls.len() > 1)
+        .ok_or_else(|| "Not enough servers support range requests".to_string())?;
+
+    fs::create_dir_all(destination_file.parent().expect("parent"))
+        .map_err(|err| err.to_string())?;
+    let temp_destination_file = temp_download_file(destination_file);
+    // The ranges are written out of order, so download_file() must never
+    // resume from what's left of this file
+    let _ = fs::remove_file(temp_download_etag_file(&temp_destination_file));
+    File::create(&temp_destination_file)
+        .and_then(|file| file.set_len(download_size))
+        .map_err(|err| format!("Unable to create {:?}: {:?}", temp_destination_file, err))?;
+
+    let progress_bar = if use_progress_bar {
+        let progress_bar = ProgressBar::new(download_size);
+        progress_bar.set_style(
+            ProgressStyle::default_bar()
+                .template(
+                    "{spinner:.green}{msg_wide}[{bar:40.cyan/blue}] {bytes}/{total_bytes} ({eta})",
+                )
+                .progress_chars("=> "),
+        );
+        progress_bar.set_message(&format!(
+            "{}Downloading~ {:?} from {} servers",
+            TRUCK,
+            destination_file.file_name().expect("file_name"),
+            urls.len(),
+        ));
+        progress_bar
+    } else {
+        info!(
+            "Downloading {} bytes from {} servers: {:?}",
+            download_size,
+            urls.len(),
+            urls
+        );
+        ProgressBar::hidden()
+    };
+
+    let ranges: VecDeque<_> = (0..download_size)
+        .step_by(DOWNLOAD_CHUNK_SIZE as usize)
+        .map(|start| (start, (start + DOWNLOAD_CHUNK_SIZE).min(download_size) - 1))
+        .collect();
+    let ranges = Arc::new(Mutex::new(ranges));
+
+    let threads: Vec<_> = urls
+        .into_iter()
+        .map(|url| {
+            let url = url.clone();
+            let client = client.clone();
+            let ranges = ranges.clone();
+            let progress_bar = progress_bar.clone();
+            let temp_destination_file = temp_destination_file.clone();
+            thread::spawn(move || {
+                let mut file = match OpenOptions::new().write(true).open(&temp_destination_file) {
+                    Ok(file) => file,
+                    Err(err) => {
+                        warn!("Unable to open {:?}: {:?}", temp_destination_file, err);
+                        return;
+                    }
+                };
+                loop {
+                    let range = match ranges.lock().unwrap().pop_front() {
+                        Some(range) => range,
+                        None => return,
+                    };
+                    if let Err(err) = download_range(&client, &url, range, &mut file, &progress_bar)
+                    {
+                        warn!("Failed to download range {:?} from {}: {}", range, url, err);
+                        ranges.lock().unwrap().push_back(range);
+                        return;
+                    }
+                }
+            })
+        })
+        .collect();
+    for thread in threads {
+        let _ = thread.join();
+    }
+    progress_bar.finish_and_clear();
+
+    let remaining_ranges = ranges.lock().unwrap().len();
+    if remaining_ranges > 0 {
+        let _ = fs::remove_file(&temp_destination_file);
+        return Err(format!(
+            "Download of {:?} failed with {} ranges remaining",
+            destination_file, remaining_ranges
+        ));
+    }
+
+    info!(
+        "  {}{}",
+        SPARKLE,
+        format!(
+            "Downloaded {:?} ({} bytes) in {:?}",
+            destination_file,
+            download_size,
+            Instant::now().duration_since(download_start),
+        )
+    );
+
     std::fs::rename(temp_destination_file, destination_file)
         .map_err(|err| format!("Unable to rename: {:?}", err))?;
 
@@ -178,6 +488,28 @@ pub fn download_snapshot(
     snapshot_output_dir: &Path,
     desired_snapshot_hash: (Slot, Hash),
     use_progress_bar: bool,
+) -> Result<(), String> {
+    download_snapshot_from_peers(
+        &[*rpc_addr],
+        snapshot_output_dir,
+        desired_snapshot_hash,
+        use_progress_bar,
+    )
+}
+
+/// Downloads the snapshot archive for `desired_snapshot_hash` from any of
+/// `rpc_addrs`, which should all advertise that snapshot hash.
+///
+/// When several peers serve the same archive, it is fetched from them in
+/// parallel. Otherwise each peer is tried in turn. The archive contents are
+/// not checked here: its accounts are rehashed and compared with
+/// `desired_snapshot_hash`, which is part of the archive's file name, when the
+/// validator unpacks it to load its bank.
+pub fn download_snapshot_from_peers(
+    rpc_addrs: &[SocketAddr],
+    snapshot_output_dir: &Path,
+    desired_snapshot_hash: (Slot, Hash),
+    use_progress_bar: bool,
 ) -> Result<(), String> {
     snapshot_utils::purge_old_snapshot_archives(snapshot_output_dir);
 
@@ -196,23 +528,49 @@ pub fn download_snapshot(
             return Ok(());
         }
 
-        if download_file(
-            &format!(
-                "http://{}/{}",
-                rpc_addr,
-                desired_snapshot_package
-                    .file_name()
-                    .unwrap()
-                    .to_str()
-                    .unwrap()
-            ),
-            &desired_snapshot_package,
-            use_progress_bar,
-        )
-        .is_ok()
-        {
-            return Ok(());
+        let urls: Vec<_> = rpc_addrs
+            .iter()
+            .map(|rpc_addr| {
+                format!(
+                    "http://{}/{}",
+                    rpc_addr,
+                    desired_snapshot_package
+                        .file_name()
+                        .unwrap()
+                        .to_str()
+                        .unwrap()
+                )
+            })
+            .collect();
+
+        if urls.len() > 1 {
+            match download_file_from_peers(&urls, &desired_snapshot_package, use_progress_bar) {
+                Ok(()) => return Ok(()),
+                Err(err) => info!("Parallel snapshot download failed: {}", err),
+            }
+        }
+
+        for url in &urls {
+            match download_file(url, &desired_snapshot_package, use_progress_bar) {
+                Ok(()) => return Ok(()),
+                Err(err) => info!("Snapshot download from {} failed: {}", url, err),
+            }
         }
     }
     Err("Snapshot couldn't be downloaded".to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_content_range() {
+        assert_eq!(parse_content_range("bytes 0-0/10"), Some((0, 0, 10)));
+        assert_eq!(parse_content_range("bytes 2-9/10"), Some((2, 9, 10)));
+        assert_eq!(parse_content_range("bytes 2-10/10"), None);
+        assert_eq!(parse_content_range("bytes 3-2/10"), None);
+        assert_eq!(parse_content_range("bytes */10"), None);
+        assert_eq!(parse_content_range("0-0/10"), None);
+    }
+}
@@ -8,11 +8,16 @@ use solana_clap_utils::{
     input_parsers::keypair_of,
     input_validators::{is_keypair_or_ask_keyword, is_port, is_pubkey},
 };
-use solana_core::{contact_info::ContactInfo, gossip_service::discover};
+use solana_core::{
+    contact_info::ContactInfo,
+    gossip_inspect::{GossipInspectConfig, GossipInspection, PruneDirection},
+    gossip_service::discover,
+};
 use solana_sdk::pubkey::Pubkey;
 use std::{
     error,
     net::{IpAddr, Ipv4Addr, SocketAddr},
+    path::PathBuf,
     process::exit,
     sync::Arc,
 };
@@ -140,6 +145,41 @@ fn parse_matches() -> ArgMatches<'static> {
                         .help("Maximum time to wait in seconds [default: wait forever]"),
                 ),
         )
+        .subcommand(
+            SubCommand::with_name("inspect")
+                .about("Inspect the gossip state of a running validator")
+                .setting(AppSettings::DisableVersion)
+                .arg(
+                    Arg::with_name("ledger_path")
+                        .short("l")
+                        .long("ledger")
+                        .value_name("DIR")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Ledger directory of the validator to inspect"),
+                )
+                .arg(
+                    Arg::with_name("label")
+                        .long("label")
+                        .value_name("KIND")
+                        .takes_value(true)
+                        .help("Only show crds entries of this kind, e.g. ContactInfo or Vote"),
+                )
+                .arg(
+                    Arg::with_name("origin")
+                        .long("origin")
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .validator(is_pubkey)
+                        .help("Only show crds entries originating from this node"),
+                )
+                .arg(
+                    Arg::with_name("json")
+                        .long("json")
+                        .takes_value(false)
+                        .help("Output as JSON"),
+                ),
+        )
         .get_matches()
 }
 
@@ -308,6 +348,91 @@ fn process_rpc_url(matches: &ArgMatches) -> std::io::Result<()> {
     Ok(())
 }
 
+fn print_gossip_inspection(inspection: &GossipInspection) {
+    println!("Crds table ({} entries):", inspection.crds.len());
+    println!(
+        "  {:<44} | {:>13} | {:>13} | Label",
+        "Origin", "Wallclock", "Updated"
+    );
+    for entry in &inspection.crds {
+        println!(
+            "  {:<44} | {:>13} | {:>13} | {}",
+            entry.origin,
+            entry.wallclock,
+            entry.local_timestamp,
+            entry.label_kind()
+        );
+    }
+
+    println!();
+    println!(
+        "Push active set ({} peers):",
+        inspection.push_active_set.len()
+    );
+    for peer in &inspection.push_active_set {
+        println!("  {}", peer);
+    }
+
+    println!();
+    println!("Recent prunes ({}):", inspection.recent_prunes.len());
+    for prune in &inspection.recent_prunes {
+        let direction = match prune.direction {
+            PruneDirection::Sent => "sent",
+            PruneDirection::Received => "received",
+        };
+        println!(
+            "  {:<8} {} -> {} at {}: {} origins pruned",
+            direction,
+            prune.from,
+            prune.destination,
+            prune.wallclock,
+            prune.prunes.len()
+        );
+    }
+
+    println!();
+    println!("Peer traffic ({} peers):", inspection.peer_traffic.len());
+    println!(
+        "  {:<21} | {:>10} | {:>12} | {:>10} | {:>12}",
+        "Address", "Pkts recv", "Bytes recv", "Pkts sent", "Bytes sent"
+    );
+    for traffic in &inspection.peer_traffic {
+        println!(
+            "  {:<21} | {:>10} | {:>12} | {:>10} | {:>12}",
+            traffic
+                .addr
+                .map(|addr| addr.to_string())
+                .unwrap_or_else(|| "none".to_string()),
+            traffic.packets_received,
+            traffic.bytes_received,
+            traffic.packets_sent,
+            traffic.bytes_sent,
+        );
+    }
+}
+
+fn process_inspect(matches: &ArgMatches) {
+    let ledger_path = value_t_or_exit!(matches, "ledger_path", PathBuf);
+    let config = GossipInspectConfig {
+        label: matches.value_of("label").map(ToString::to_string),
+        origin: matches.value_of("origin").map(ToString::to_string),
+    };
+
+    let admin_client = solana_admin_rpc::connect(&ledger_path);
+    let inspection = solana_admin_rpc::runtime()
+        .block_on(async move { admin_client.await?.gossip_inspect(Some(config)).await })
+        .unwrap_or_else(|err| {
+            eprintln!("gossipInspect request failed: {}", err);
+            exit(1);
+        });
+
+    if matches.is_present("json") {
+        println!("{}", serde_json::to_string_pretty(&inspection).unwrap());
+    } else {
+        print_gossip_inspection(&inspection);
+    }
+}
+
 fn main() -> Result<(), Box<dyn error::Error>> {
     solana_logger::setup_with_default("solana=info");
 
@@ -320,6 +445,9 @@ fn main() -> Result<(), Box<dyn error::Error>> {
         ("rpc-url", Some(matches)) => {
             process_rpc_url(matches)?;
         }
+        ("inspect", Some(matches)) => {
+            process_inspect(matches);
+        }
         _ => unreachable!(),
     }
 
@@ -21,12 +21,14 @@ use solana_ledger::{
     blockstore::{create_new_ledger, Blockstore, PurgeType},
     blockstore_db::{self, AccessType, BlockstoreRecoveryMode, Column, Database},
     blockstore_processor::ProcessOptions,
-    shred::Shred,
+    duplicate_slot_evidence::DuplicateSlotEvidence,
+    shred::{Shred, ShredType, DATA_SHRED},
 };
 use solana_runtime::{
     bank::{Bank, RewardCalculationEvent},
     bank_forks::{ArchiveFormat, BankForks, SnapshotConfig},
     hardened_unpack::{open_genesis_config, MAX_GENESIS_ARCHIVE_UNPACKED_SIZE},
+    program_trace::ProgramTraceFilter,
     snapshot_utils,
     snapshot_utils::SnapshotVersion,
 };
@@ -42,6 +44,7 @@ use solana_sdk::{
     pubkey::Pubkey,
     rent::Rent,
     shred_version::compute_shred_version,
+    signature::Signature,
     system_program,
 };
 use solana_stake_program::stake_state::{self, PointValue, StakeState};
@@ -926,6 +929,23 @@ fn main() {
             .arg(&starting_slot_arg)
             .about("Print all the duplicate slots in the ledger")
         )
+        .subcommand(
+            SubCommand::with_name("duplicate-slot-proofs")
+            .about("Verify the duplicate slot proofs in the ledger against the leader schedule")
+            .arg(&starting_slot_arg)
+            .arg(&no_snapshot_arg)
+            .arg(&account_paths_arg)
+            .arg(&halt_at_slot_arg)
+            .arg(&hard_forks_arg)
+            .arg(&max_genesis_archive_unpacked_size_arg)
+            .arg(
+                Arg::with_name("output_directory")
+                    .long("output-directory")
+                    .value_name("DIR")
+                    .takes_value(true)
+                    .help("Export each verified proof as a JSON file in this directory"),
+            )
+        )
         .subcommand(
             SubCommand::with_name("set-dead-slot")
             .about("Mark one or more slots dead")
@@ -1043,6 +1063,24 @@ fn main() {
                     .takes_value(false)
                     .help("After verifying the ledger, print some information about the account stores"),
             )
+            .arg(
+                Arg::with_name("trace_bpf_transaction")
+                    .long("trace-bpf-transaction")
+                    .value_name("SIGNATURE")
+                    .takes_value(true)
+                    .multiple(true)
+                    .validator(is_parsable::<Signature>)
+                    .requires("bpf_trace_output_dir")
+                    .help("Record an instruction trace of the BPF programs invoked by this transaction"),
+            )
+            .arg(
+                Arg::with_name("bpf_trace_output_dir")
+                    .long("bpf-trace-output-dir")
+                    .value_name("DIR")
+                    .takes_value(true)
+                    .help("Write the instruction traces and folded-stack compute unit \
+                           profiles of traced transactions to this directory"),
+            )
         ).subcommand(
             SubCommand::with_name("graph")
             .about("Create a Graphviz rendering of the ledger")
@@ -1258,6 +1296,26 @@ fn main() {
                     .takes_value(true)
                     .help("Output file in the csv format"),
             )
+        ).subcommand(
+            SubCommand::with_name("minimum-delegation-report")
+            .about("Report how many stake delegations fall below candidate minimum \
+                    delegation amounts")
+            .arg(&no_snapshot_arg)
+            .arg(&account_paths_arg)
+            .arg(&halt_at_slot_arg)
+            .arg(&hard_forks_arg)
+            .arg(&max_genesis_archive_unpacked_size_arg)
+            .arg(
+                Arg::with_name("thresholds")
+                    .long("threshold")
+                    .value_name("SOL")
+                    .takes_value(true)
+                    .multiple(true)
+                    .use_delimiter(true)
+                    .validator(is_parsable::<f64>)
+                    .default_value("0.01,0.1,1,10")
+                    .help("Candidate minimum delegation amounts, in SOL"),
+            )
         ).subcommand(
             SubCommand::with_name("purge")
             .about("Delete a range of slots from the ledger")
@@ -1617,6 +1675,91 @@ fn main() {
                 println!("{}", slot);
             }
         }
+        ("duplicate-slot-proofs", Some(arg_matches)) => {
+            let starting_slot = value_t_or_exit!(arg_matches, "starting_slot", Slot);
+            let output_directory = value_t!(arg_matches, "output_directory", PathBuf).ok();
+            let process_options = ProcessOptions {
+                dev_halt_at_slot: value_t!(arg_matches, "halt_at_slot", Slot).ok(),
+                new_hard_forks: hardforks_of(arg_matches, "hard_forks"),
+                poh_verify: false,
+                ..ProcessOptions::default()
+            };
+            let genesis_config = open_genesis_config_by(&ledger_path, arg_matches);
+            let blockstore = open_blockstore(
+                &ledger_path,
+                AccessType::TryPrimaryThenSecondary,
+                wal_recovery_mode,
+            );
+            let (bank_forks, leader_schedule_cache, _snapshot_hash) = load_bank_forks(
+                arg_matches,
+                &genesis_config,
+                &blockstore,
+                process_options,
+                snapshot_archive_path,
+            )
+            .unwrap_or_else(|err| {
+                eprintln!("Failed to load ledger: {:?}", err);
+                exit(1);
+            });
+            let bank = bank_forks.working_bank();
+
+            if let Some(output_directory) = &output_directory {
+                fs::create_dir_all(output_directory).unwrap_or_else(|err| {
+                    eprintln!(
+                        "Error: Unable to create {}: {}",
+                        output_directory.display(),
+                        err
+                    );
+                    exit(1);
+                });
+            }
+
+            for slot in blockstore.duplicate_slots_iterator(starting_slot).unwrap() {
+                let proof = match blockstore.get_duplicate_slot(slot) {
+                    Some(proof) => proof,
+                    None => continue,
+                };
+                let evidence = match DuplicateSlotEvidence::new(&proof, |slot| {
+                    leader_schedule_cache.slot_leader_at(slot, Some(&bank))
+                }) {
+                    Ok(evidence) => evidence,
+                    Err(err) => {
+                        println!("Slot {}: invalid proof: {}", slot, err);
+                        continue;
+                    }
+                };
+                println!(
+                    "Slot {}: leader {} signed two {} shreds at index {}",
+                    slot,
+                    evidence.leader,
+                    if evidence.shred_type == ShredType(DATA_SHRED) {
+                        "data"
+                    } else {
+                        "coding"
+                    },
+                    evidence.shred_index,
+                );
+                println!("  Signature 1: {}", evidence.signature1);
+                println!("  Signature 2: {}", evidence.signature2);
+                if let Some(output_directory) = &output_directory {
+                    let path = output_directory.join(format!("duplicate-slot-{}.json", slot));
+                    let result =
+                        File::create(&path)
+                            .map_err(|err| err.to_string())
+                            .and_then(|file| {
+                                serde_json::to_writer_pretty(file, &evidence)
+                                    .map_err(|err| err.to_string())
+                            });
+                    match result {
+                        Ok(()) => println!("  Exported to {}", path.display()),
+                        Err(err) => {
+                            eprintln!("Error: Unable to write {}: {}", path.display(), err);
+                            exit(1);
+                        }
+                    }
+                }
+            }
+        }
         ("set-dead-slot", Some(arg_matches)) => {
             let slots = values_t_or_exit!(arg_matches, "slots", Slot);
             let blockstore =
@@ -1705,6 +1848,15 @@ fn main() {
                 bpf_jit: !matches.is_present("no_bpf_jit"),
                 accounts_db_caching_enabled: !arg_matches.is_present("no_accounts_db_caching"),
                 allow_dead_slots: arg_matches.is_present("allow_dead_slots"),
+                program_trace_filter: if arg_matches.is_present("trace_bpf_transaction") {
+                    ProgramTraceFilter::OnlySignatures(
+                        values_t_or_exit!(arg_matches, "trace_bpf_transaction", Signature)
+                            .into_iter()
+                            .collect(),
+                    )
+                } else {
+                    ProgramTraceFilter::None
+                },
                 ..ProcessOptions::default()
             };
             let print_accounts_stats = arg_matches.is_present("print_accounts_stats");
@@ -1733,6 +1885,28 @@ fn main() {
                 let working_bank = bank_forks.working_bank();
                 working_bank.print_accounts_stats();
             }
+            if let Ok(bpf_trace_output_dir) = value_t!(arg_matches, "bpf_trace_output_dir", PathBuf)
+            {
+                let working_bank = bank_forks.working_bank();
+                let program_trace_collector = working_bank.program_trace_collector.read().unwrap();
+                for transaction_traces in &program_trace_collector.traces {
+                    transaction_traces
+                        .write_to_dir(&bpf_trace_output_dir)
+                        .unwrap_or_else(|err| {
+                            eprintln!(
+                                "Unable to write BPF traces to {}: {}",
+                                bpf_trace_output_dir.display(),
+                                err
+                            );
+                            exit(1);
+                        });
+                    println!(
+                        "Wrote BPF traces of transaction {} to {}",
+                        transaction_traces.signature,
+                        bpf_trace_output_dir.display()
+                    );
+                }
+            }
             println!("Ok");
         }
         ("graph", Some(arg_matches)) => {
@@ -2653,6 +2827,82 @@ fn main() {
                 }
             }
         }
+        ("minimum-delegation-report", Some(arg_matches)) => {
+            let dev_halt_at_slot = value_t!(arg_matches, "halt_at_slot", Slot).ok();
+            let process_options = ProcessOptions {
+                dev_halt_at_slot,
+                new_hard_forks: hardforks_of(arg_matches, "hard_forks"),
+                poh_verify: false,
+                ..ProcessOptions::default()
+            };
+            let genesis_config = open_genesis_config_by(&ledger_path, arg_matches);
+            let mut thresholds: Vec<_> = values_t_or_exit!(arg_matches, "thresholds", f64)
+                .into_iter()
+                .map(sol_to_lamports)
+                .collect();
+            thresholds.sort_unstable();
+            thresholds.dedup();
+            let blockstore = open_blockstore(
+                &ledger_path,
+                AccessType::TryPrimaryThenSecondary,
+                wal_recovery_mode,
+            );
+            match load_bank_forks(
+                arg_matches,
+                &genesis_config,
+                &blockstore,
+                process_options,
+                snapshot_archive_path,
+            ) {
+                Ok((bank_forks, _leader_schedule_cache, _snapshot_hash)) => {
+                    let slot = bank_forks.working_bank().slot();
+                    let bank = bank_forks.get(slot).unwrap_or_else(|| {
+                        eprintln!("Error: Slot {} is not available", slot);
+                        exit(1);
+                    });
+
+                    let delegations = bank.cloned_stake_delegations();
+                    let total_stake: u64 = delegations
+                        .values()
+                        .map(|delegation| delegation.stake)
+                        .sum();
+                    let minimum_delegation =
+                        solana_stake_program::get_minimum_delegation(|feature_id| {
+                            bank.feature_set.is_active(feature_id)
+                        });
+                    println!("Slot: {}", slot);
+                    println!(
+                        "Stake delegations: {} ({})",
+                        delegations.len(),
+                        Sol(total_stake)
+                    );
+                    println!("Current minimum delegation: {}", Sol(minimum_delegation));
+                    println!(
+                        "{:>20}  {:>20}  {:>24}",
+                        "Threshold", "Delegations Below", "Stake Below"
+                    );
+                    for threshold in thresholds {
+                        let (count, stake) = delegations
+                            .values()
+                            .filter(|delegation| delegation.stake < threshold)
+                            .fold((0, 0), |(count, stake), delegation| {
+                                (count + 1, stake + delegation.stake)
+                            });
+                        println!(
+                            "{:>20}  {:>11} ({:>5.1}%)  {:>24}",
+                            Sol(threshold).to_string(),
+                            count,
+                            count as f64 * 100. / delegations.len().max(1) as f64,
+                            Sol(stake).to_string(),
+                        );
+                    }
+                }
+                Err(err) => {
+                    eprintln!("Failed to load ledger: {:?}", err);
+                    exit(1);
+                }
+            }
+        }
         ("purge", Some(arg_matches)) => {
             let start_slot = value_t_or_exit!(arg_matches, "start_slot", Slot);
             let end_slot = value_t!(arg_matches, "end_slot", Slot).ok();
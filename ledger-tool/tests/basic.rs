@@ -46,4 +46,16 @@ fn nominal() {
     let output = run_ledger_tool(&["-l", &ledger_path, "print", "-vvv"]);
     assert!(output.status.success());
     assert_eq!(count_newlines(&output.stdout), ticks + meta_lines);
+
+    // Report dust stake delegations
+    let output = run_ledger_tool(&[
+        "-l",
+        &ledger_path,
+        "minimum-delegation-report",
+        "--threshold",
+        "1,10",
+    ]);
+    assert!(output.status.success());
+    let header_lines = 4;
+    assert_eq!(count_newlines(&output.stdout), header_lines + 2);
 }
@@ -3684,12 +3684,15 @@ pub mod tests {
         hash::{self, hash, Hash},
         instruction::CompiledInstruction,
         packet::PACKET_DATA_SIZE,
+        process_instruction::TransactionReturnData,
         pubkey::Pubkey,
         signature::Signature,
         transaction::TransactionError,
     };
     use solana_storage_proto::convert::generated;
-    use solana_transaction_status::{InnerInstructions, Reward, Rewards, TransactionTokenBalance};
+    use solana_transaction_status::{
+        InnerInstructions, Reward, Rewards, TransactionComputeUnits, TransactionTokenBalance,
+    };
     use std::time::Duration;
 
     // used for tests only
@@ -5879,6 +5882,8 @@ pub mod tests {
                     log_messages: Some(vec![]),
                     pre_token_balances: Some(vec![]),
                     post_token_balances: Some(vec![]),
+                    return_data: None,
+                    compute_units: None,
                 }
                 .into();
                 ledger
@@ -5894,6 +5899,8 @@ pub mod tests {
                     log_messages: Some(vec![]),
                     pre_token_balances: Some(vec![]),
                     post_token_balances: Some(vec![]),
+                    return_data: None,
+                    compute_units: None,
                 }
                 .into();
                 ledger
@@ -5909,6 +5916,8 @@ pub mod tests {
                     log_messages: Some(vec![]),
                     pre_token_balances: Some(vec![]),
                     post_token_balances: Some(vec![]),
+                    return_data: None,
+                    compute_units: None,
                 }
                 .into();
                 ledger
@@ -5926,6 +5935,8 @@ pub mod tests {
                         log_messages: Some(vec![]),
                         pre_token_balances: Some(vec![]),
                         post_token_balances: Some(vec![]),
+                        return_data: None,
+                        compute_units: None,
                     }),
                 }
             })
@@ -6020,6 +6031,11 @@ pub mod tests {
             let log_messages_vec = vec![String::from("Test message\n")];
             let pre_token_balances_vec = vec![];
             let post_token_balances_vec = vec![];
+            let return_data = TransactionReturnData {
+                program_id: Pubkey::new_unique(),
+                data: vec![1, 2, 3],
+            };
+            let compute_units = TransactionComputeUnits::new(vec![1_200, 0, 300]);
 
             // result not found
             assert!(transaction_status_cf
@@ -6043,6 +6059,8 @@ pub mod tests {
                 log_messages: Some(log_messages_vec.clone()),
                 pre_token_balances: Some(pre_token_balances_vec.clone()),
                 post_token_balances: Some(post_token_balances_vec.clone()),
+                return_data: Some(return_data.clone()),
+                compute_units: Some(compute_units.clone()),
             }
             .into();
             assert!(transaction_status_cf
@@ -6059,6 +6077,8 @@ pub mod tests {
                 log_messages,
                 pre_token_balances,
                 post_token_balances,
+                return_data: stored_return_data,
+                compute_units: stored_compute_units,
             } = transaction_status_cf
                 .get_protobuf_or_bincode::<StoredTransactionStatusMeta>((
                     0,
@@ -6077,6 +6097,8 @@ pub mod tests {
             assert_eq!(log_messages.unwrap(), log_messages_vec);
             assert_eq!(pre_token_balances.unwrap(), pre_token_balances_vec);
             assert_eq!(post_token_balances.unwrap(), post_token_balances_vec);
+            assert_eq!(stored_return_data, Some(return_data));
+            assert_eq!(stored_compute_units, Some(compute_units));
 
             // insert value
             let status = TransactionStatusMeta {
@@ -6088,6 +6110,8 @@ pub mod tests {
                 log_messages: Some(log_messages_vec.clone()),
                 pre_token_balances: Some(pre_token_balances_vec.clone()),
                 post_token_balances: Some(post_token_balances_vec.clone()),
+                return_data: None,
+                compute_units: None,
             }
             .into();
             assert!(transaction_status_cf
@@ -6104,6 +6128,8 @@ pub mod tests {
                 log_messages,
                 pre_token_balances,
                 post_token_balances,
+                return_data: stored_return_data,
+                compute_units: stored_compute_units,
             } = transaction_status_cf
                 .get_protobuf_or_bincode::<StoredTransactionStatusMeta>((
                     0,
@@ -6124,6 +6150,8 @@ pub mod tests {
             assert_eq!(log_messages.unwrap(), log_messages_vec);
             assert_eq!(pre_token_balances.unwrap(), pre_token_balances_vec);
             assert_eq!(post_token_balances.unwrap(), post_token_balances_vec);
+            assert_eq!(stored_return_data, None);
+            assert_eq!(stored_compute_units, None);
         }
         Blockstore::destroy(&blockstore_path).expect("Expected successful database destruction");
     }
@@ -6354,6 +6382,8 @@ pub mod tests {
                 log_messages: Some(vec![]),
                 pre_token_balances: Some(vec![]),
                 post_token_balances: Some(vec![]),
+                return_data: None,
+                compute_units: None,
             }
             .into();
 
@@ -6565,6 +6595,8 @@ pub mod tests {
                     log_messages: log_messages.clone(),
                     pre_token_balances: pre_token_balances.clone(),
                     post_token_balances: post_token_balances.clone(),
+                    return_data: None,
+                    compute_units: None,
                 }
                 .into();
                 blockstore
@@ -6582,6 +6614,8 @@ pub mod tests {
                         log_messages,
                         pre_token_balances,
                         post_token_balances,
+                        return_data: None,
+                        compute_units: None,
                     }),
                 }
             })
@@ -6661,6 +6695,8 @@ pub mod tests {
                     log_messages: log_messages.clone(),
                     pre_token_balances: pre_token_balances.clone(),
                     post_token_balances: post_token_balances.clone(),
+                    return_data: None,
+                    compute_units: None,
                 }
                 .into();
                 blockstore
@@ -6678,6 +6714,8 @@ pub mod tests {
                         log_messages,
                         pre_token_balances,
                         post_token_balances,
+                        return_data: None,
+                        compute_units: None,
                     }),
                 }
             })
@@ -7405,6 +7443,8 @@ pub mod tests {
                     log_messages: Some(vec![]),
                     pre_token_balances: Some(vec![]),
                     post_token_balances: Some(vec![]),
+                    return_data: None,
+                    compute_units: None,
                 }
                 .into();
                 transaction_status_cf
@@ -7949,6 +7989,11 @@ pub mod tests {
                         ui_amount_string: "1.1".to_string(),
                     },
                 }]),
+                return_data: Some(TransactionReturnData {
+                    program_id: Pubkey::new_unique(),
+                    data: vec![1, 2, 3],
+                }),
+                compute_units: Some(TransactionComputeUnits::new(vec![42])),
             };
             let deprecated_status: StoredTransactionStatusMeta = status.clone().into();
             let protobuf_status: generated::TransactionStatusMeta = status.into();
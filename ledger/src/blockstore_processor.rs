@@ -18,12 +18,14 @@ use solana_rayon_threadlimit::get_thread_count;
 use solana_runtime::{
     accounts_index::AccountIndex,
     bank::{
-        Bank, ExecuteTimings, InnerInstructionsList, TransactionBalancesSet,
-        TransactionExecutionResult, TransactionLogMessages, TransactionResults,
+        Bank, ExecuteTimings, InnerInstructionsList, InstructionComputeUnits,
+        TransactionBalancesSet, TransactionExecutionResult, TransactionLogMessages,
+        TransactionResults,
     },
     bank_forks::BankForks,
     bank_utils,
     commitment::VOTE_THRESHOLD_SIZE,
+    program_trace::ProgramTraceFilter,
     transaction_batch::TransactionBatch,
     vote_account::ArcVoteAccount,
     vote_sender_types::ReplayVoteSender,
@@ -32,6 +34,7 @@ use solana_sdk::{
     clock::{Slot, MAX_PROCESSING_AGE},
     genesis_config::GenesisConfig,
     hash::Hash,
+    process_instruction::TransactionReturnData,
     pubkey::Pubkey,
     signature::{Keypair, Signature},
     timing,
@@ -115,15 +118,21 @@ fn execute_batch(
         vec![]
     };
 
-    let (tx_results, balances, inner_instructions, transaction_logs) =
-        batch.bank().load_execute_and_commit_transactions(
-            batch,
-            MAX_PROCESSING_AGE,
-            transaction_status_sender.is_some(),
-            transaction_status_sender.is_some(),
-            transaction_status_sender.is_some(),
-            timings,
-        );
+    let (
+        tx_results,
+        balances,
+        inner_instructions,
+        transaction_logs,
+        transaction_return_data,
+        transaction_compute_units,
+    ) = batch.bank().load_execute_and_commit_transactions(
+        batch,
+        MAX_PROCESSING_AGE,
+        transaction_status_sender.is_some(),
+        transaction_status_sender.is_some(),
+        transaction_status_sender.is_some(),
+        timings,
+    );
 
     bank_utils::find_and_send_votes(batch.hashed_transactions(), &tx_results, replay_vote_sender);
 
@@ -152,6 +161,8 @@ fn execute_batch(
             token_balances,
             inner_instructions,
             transaction_logs,
+            transaction_return_data,
+            transaction_compute_units,
         );
     }
 
@@ -369,6 +380,7 @@ pub struct ProcessOptions {
     pub account_indexes: HashSet<AccountIndex>,
     pub accounts_db_caching_enabled: bool,
     pub allow_dead_slots: bool,
+    pub program_trace_filter: ProgramTraceFilter,
 }
 
 pub fn process_blockstore(
@@ -396,6 +408,7 @@ pub fn process_blockstore(
         opts.account_indexes.clone(),
         opts.accounts_db_caching_enabled,
     );
+    bank0.program_trace_collector.write().unwrap().filter = opts.program_trace_filter.clone();
     let bank0 = Arc::new(bank0);
     info!("processing ledger for slot 0...");
     let recyclers = VerifyRecyclers::default();
@@ -411,6 +424,7 @@ pub(crate) fn process_blockstore_from_root(
     recyclers: &VerifyRecyclers,
     transaction_status_sender: Option<TransactionStatusSender>,
 ) -> BlockstoreProcessorResult {
+    bank.program_trace_collector.write().unwrap().filter = opts.program_trace_filter.clone();
     do_process_blockstore_from_root(
         blockstore,
         Arc::new(bank),
@@ -730,8 +744,7 @@ pub fn confirm_slot(
     };
 
     let check_start = Instant::now();
-    let check_result =
-        entries.verify_and_hash_transactions(skip_verification, bank.secp256k1_program_enabled());
+    let check_result = entries.verify_and_hash_transactions(skip_verification, &bank.feature_set);
     if check_result.is_none() {
         warn!("Ledger proof of history failed at slot: {}", slot);
         return Err(BlockError::InvalidEntryHash.into());
@@ -1114,6 +1127,8 @@ pub struct TransactionStatusBatch {
     pub token_balances: TransactionTokenBalancesSet,
     pub inner_instructions: Option<Vec<Option<InnerInstructionsList>>>,
     pub transaction_logs: Option<Vec<TransactionLogMessages>>,
+    pub transaction_return_data: Vec<Option<TransactionReturnData>>,
+    pub transaction_compute_units: Vec<InstructionComputeUnits>,
 }
 
 #[derive(Clone)]
@@ -1123,6 +1138,7 @@ pub struct TransactionStatusSender {
 }
 
 impl TransactionStatusSender {
+    #[allow(clippy::too_many_arguments)]
     pub fn send_transaction_status_batch(
         &self,
         bank: Arc<Bank>,
@@ -1132,6 +1148,8 @@ impl TransactionStatusSender {
         token_balances: TransactionTokenBalancesSet,
         inner_instructions: Vec<Option<InnerInstructionsList>>,
         transaction_logs: Vec<TransactionLogMessages>,
+        transaction_return_data: Vec<Option<TransactionReturnData>>,
+        transaction_compute_units: Vec<InstructionComputeUnits>,
     ) {
         let slot = bank.slot();
         let (inner_instructions, transaction_logs) = if !self.enable_cpi_and_log_storage {
@@ -1149,6 +1167,8 @@ impl TransactionStatusSender {
                 token_balances,
                 inner_instructions,
                 transaction_logs,
+                transaction_return_data,
+                transaction_compute_units,
             }))
         {
             trace!(
@@ -3059,6 +3079,8 @@ pub mod tests {
             _balances,
             _inner_instructions,
             _log_messages,
+            _return_data,
+            _compute_units,
         ) = batch.bank().load_execute_and_commit_transactions(
             &batch,
             MAX_PROCESSING_AGE,
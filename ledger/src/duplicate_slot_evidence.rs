@@ -0,0 +1,230 @@
+//! Offline verification of duplicate-slot proofs.
+//!
+//! Blockstore stores a `DuplicateSlotProof` in the `DuplicateSlots` column when
+//! it sees two different shreds for the same (slot, shred index, shred type).
+//! Both shreds are signed by the slot leader, so once the signatures are
+//! checked against the leader schedule, the pair is standalone evidence that
+//! the leader produced conflicting blocks for that slot.
+
+use crate::{
+    blockstore_meta::DuplicateSlotProof,
+    shred::{Shred, ShredError, ShredType},
+};
+use serde::{Deserialize, Serialize};
+use solana_sdk::{clock::Slot, pubkey::Pubkey, signature::Signature};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum DuplicateSlotEvidenceError {
+    #[error("evidence does not match its shreds")]
+    EvidenceMismatch,
+    #[error("invalid duplicate shreds")]
+    InvalidDuplicateShreds,
+    #[error("invalid shred")]
+    InvalidShred(#[from] ShredError),
+    #[error("invalid signature")]
+    InvalidSignature,
+    #[error("shred index mismatch")]
+    ShredIndexMismatch,
+    #[error("shred type mismatch")]
+    ShredTypeMismatch,
+    #[error("slot mismatch")]
+    SlotMismatch,
+    #[error("unknown slot leader")]
+    UnknownSlotLeader,
+}
+
+pub type Result<T> = std::result::Result<T, DuplicateSlotEvidenceError>;
+
+/// Verified proof that `leader` signed two different shreds for the same
+/// (slot, shred index, shred type).
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct DuplicateSlotEvidence {
+    pub slot: Slot,
+    pub leader: Pubkey,
+    pub shred_index: u32,
+    pub shred_type: ShredType,
+    pub signature1: Signature,
+    pub signature2: Signature,
+    #[serde(with = "serde_bytes")]
+    pub shred1: Vec<u8>,
+    #[serde(with = "serde_bytes")]
+    pub shred2: Vec<u8>,
+}
+
+impl DuplicateSlotEvidence {
+    /// Verifies a duplicate-slot proof against the leader schedule, and
+    /// returns the evidence if both shreds are signed by the slot leader.
+    pub fn new<F>(proof: &DuplicateSlotProof, leader_schedule: F) -> Result<Self>
+    where
+        F: FnOnce(Slot) -> Option<Pubkey>,
+    {
+        let shred1 = Shred::new_from_serialized_shred(proof.shred1.clone())?;
+        let shred2 = Shred::new_from_serialized_shred(proof.shred2.clone())?;
+        let leader = verify_duplicate_shreds(&shred1, &shred2, leader_schedule)?;
+        Ok(Self {
+            slot: shred1.slot(),
+            leader,
+            shred_index: shred1.index(),
+            shred_type: shred1.common_header.shred_type,
+            signature1: shred1.signature(),
+            signature2: shred2.signature(),
+            shred1: shred1.payload,
+            shred2: shred2.payload,
+        })
+    }
+
+    /// Re-verifies previously exported evidence against an independently
+    /// obtained leader schedule.
+    pub fn verify<F>(&self, leader_schedule: F) -> Result<()>
+    where
+        F: FnOnce(Slot) -> Option<Pubkey>,
+    {
+        let proof = DuplicateSlotProof {
+            shred1: self.shred1.clone(),
+            shred2: self.shred2.clone(),
+        };
+        if Self::new(&proof, leader_schedule)? == *self {
+            Ok(())
+        } else {
+            Err(DuplicateSlotEvidenceError::EvidenceMismatch)
+        }
+    }
+}
+
+/// Checks that the two shreds are conflicting versions of the same
+/// (slot, shred index, shred type), and that both carry valid signatures
+/// from the slot leader. Returns the slot leader.
+pub fn verify_duplicate_shreds<F>(
+    shred1: &Shred,
+    shred2: &Shred,
+    leader_schedule: F,
+) -> Result<Pubkey>
+where
+    F: FnOnce(Slot) -> Option<Pubkey>,
+{
+    if shred1.slot() != shred2.slot() {
+        return Err(DuplicateSlotEvidenceError::SlotMismatch);
+    }
+    if shred1.index() != shred2.index() {
+        return Err(DuplicateSlotEvidenceError::ShredIndexMismatch);
+    }
+    if shred1.common_header.shred_type != shred2.common_header.shred_type {
+        return Err(DuplicateSlotEvidenceError::ShredTypeMismatch);
+    }
+    if shred1.payload == shred2.payload {
+        return Err(DuplicateSlotEvidenceError::InvalidDuplicateShreds);
+    }
+    let leader =
+        leader_schedule(shred1.slot()).ok_or(DuplicateSlotEvidenceError::UnknownSlotLeader)?;
+    if !shred1.verify(&leader) || !shred2.verify(&leader) {
+        return Err(DuplicateSlotEvidenceError::InvalidSignature);
+    }
+    Ok(leader)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{entry::Entry, shred::Shredder};
+    use solana_sdk::{
+        hash::Hash,
+        signature::{Keypair, Signer},
+        system_transaction,
+    };
+    use std::sync::Arc;
+
+    fn new_shred(shredder: &Shredder, next_shred_index: u32) -> Shred {
+        let entries = vec![Entry::new(
+            &Hash::new_unique(),
+            1,
+            vec![system_transaction::transfer(
+                &Keypair::new(),
+                &Pubkey::new_unique(),
+                1,
+                Hash::new_unique(),
+            )],
+        )];
+        let (mut data_shreds, _coding_shreds, _last_shred_index) =
+            shredder.entries_to_shreds(&entries, true, next_shred_index);
+        data_shreds.swap_remove(0)
+    }
+
+    fn new_duplicate_slot_proof(leader: &Arc<Keypair>, slot: Slot) -> DuplicateSlotProof {
+        let shredder = Shredder::new(slot, slot - 1, 0.0, leader.clone(), 0, 0).unwrap();
+        DuplicateSlotProof {
+            shred1: new_shred(&shredder, 5).payload,
+            shred2: new_shred(&shredder, 5).payload,
+        }
+    }
+
+    #[test]
+    fn test_duplicate_slot_evidence() {
+        let leader = Arc::new(Keypair::new());
+        let slot = 42;
+        let proof = new_duplicate_slot_proof(&leader, slot);
+        let leader_schedule = |s| {
+            if s == slot {
+                Some(leader.pubkey())
+            } else {
+                None
+            }
+        };
+
+        let evidence = DuplicateSlotEvidence::new(&proof, leader_schedule).unwrap();
+        assert_eq!(evidence.slot, slot);
+        assert_eq!(evidence.leader, leader.pubkey());
+        assert_eq!(evidence.shred_index, 5);
+        assert_ne!(evidence.signature1, evidence.signature2);
+        assert!(evidence.verify(leader_schedule).is_ok());
+
+        // Round trip through the serialized format.
+        let bytes = bincode::serialize(&evidence).unwrap();
+        let evidence: DuplicateSlotEvidence = bincode::deserialize(&bytes).unwrap();
+        assert!(evidence.verify(leader_schedule).is_ok());
+
+        // Wrong leader.
+        assert!(matches!(
+            evidence.verify(|_| Some(Pubkey::new_unique())),
+            Err(DuplicateSlotEvidenceError::InvalidSignature)
+        ));
+        assert!(matches!(
+            evidence.verify(|_| None),
+            Err(DuplicateSlotEvidenceError::UnknownSlotLeader)
+        ));
+
+        // Tampered metadata.
+        let mut tampered = evidence;
+        tampered.slot += 1;
+        assert!(matches!(
+            tampered.verify(|_| Some(leader.pubkey())),
+            Err(DuplicateSlotEvidenceError::EvidenceMismatch)
+        ));
+    }
+
+    #[test]
+    fn test_duplicate_slot_evidence_invalid() {
+        let leader = Arc::new(Keypair::new());
+        let slot = 42;
+        let proof = new_duplicate_slot_proof(&leader, slot);
+
+        let same = DuplicateSlotProof {
+            shred1: proof.shred1.clone(),
+            shred2: proof.shred1.clone(),
+        };
+        assert!(matches!(
+            DuplicateSlotEvidence::new(&same, |_| Some(leader.pubkey())),
+            Err(DuplicateSlotEvidenceError::InvalidDuplicateShreds)
+        ));
+
+        let other = new_duplicate_slot_proof(&leader, slot + 1);
+        let mismatch = DuplicateSlotProof {
+            shred1: proof.shred1,
+            shred2: other.shred1,
+        };
+        assert!(matches!(
+            DuplicateSlotEvidence::new(&mismatch, |_| Some(leader.pubkey())),
+            Err(DuplicateSlotEvidenceError::SlotMismatch)
+        ));
+    }
+}
@@ -13,6 +13,7 @@ pub mod blockstore_db;
 pub mod blockstore_meta;
 pub mod blockstore_processor;
 pub mod builtins;
+pub mod duplicate_slot_evidence;
 pub mod entry;
 pub mod erasure;
 pub mod genesis_utils;
@@ -47,9 +47,10 @@ use solana_sdk::{
     hash::Hash,
     poh_config::PohConfig,
     pubkey::Pubkey,
-    signature::{Keypair, Signer},
+    signature::{write_keypair_file, Keypair, Signer},
     system_program, system_transaction,
 };
+use solana_validator::admin_rpc_service;
 use solana_vote_program::vote_state::MAX_LOCKOUT_HISTORY;
 use std::{
     collections::{HashMap, HashSet},
@@ -58,9 +59,9 @@ use std::{
     iter,
     path::{Path, PathBuf},
     sync::atomic::{AtomicBool, Ordering},
-    sync::Arc,
+    sync::{Arc, RwLock},
     thread::{sleep, Builder, JoinHandle},
-    time::{Duration, Instant},
+    time::{Duration, Instant, SystemTime},
 };
 use tempfile::TempDir;
 
@@ -1419,6 +1420,88 @@ fn test_no_voting() {
     }
 }
 
+#[test]
+#[serial]
+fn test_set_identity() {
+    solana_logger::setup_with_default(RUST_LOG_FILTER);
+    error!("test_set_identity");
+    let cluster = LocalCluster::new_with_equal_stakes(1, 10_000, 100);
+    cluster.check_for_new_roots(16, "test_set_identity");
+
+    let leader_pubkey = cluster.entry_point_info.id;
+    let validator_info = &cluster.validators[&leader_pubkey];
+    let original_identity = validator_info.info.keypair.clone();
+    let ledger_path = validator_info.info.ledger_path.clone();
+    let cluster_info = validator_info
+        .validator
+        .as_ref()
+        .unwrap()
+        .cluster_info
+        .clone();
+    let rpc_client = RpcClient::new_socket(validator_info.info.contact_info.rpc);
+
+    // Serve the admin RPC from the node's ledger, as `solana-validator` does
+    admin_rpc_service::run(
+        &ledger_path,
+        admin_rpc_service::AdminRpcRequestMetadata {
+            rpc_addr: Some(validator_info.info.contact_info.rpc),
+            start_time: SystemTime::now(),
+            start_progress: Arc::default(),
+            validator_exit: validator_info.config.validator_exit.clone(),
+            authorized_voter_keypairs: Arc::new(RwLock::new(vec![validator_info
+                .info
+                .voting_keypair
+                .clone()])),
+            ledger_path: ledger_path.clone(),
+            post_init: Arc::new(RwLock::new(Some(
+                admin_rpc_service::AdminRpcRequestMetadataPostInit {
+                    cluster_info: cluster_info.clone(),
+                },
+            ))),
+        },
+    );
+    let set_identity = |identity: &Keypair| {
+        let identity_file = ledger_path.join(format!("{}.json", identity.pubkey()));
+        write_keypair_file(identity, &identity_file).unwrap();
+        let mut retries = 0;
+        loop {
+            let result = admin_rpc_service::runtime().block_on(async {
+                admin_rpc_service::connect(&ledger_path)
+                    .await?
+                    .set_identity(identity_file.display().to_string(), false)
+                    .await
+            });
+            match result {
+                Ok(()) => break,
+                // The admin RPC server starts in the background
+                Err(err) if retries < 10 => {
+                    info!("setIdentity failed, retrying: {}", err);
+                    retries += 1;
+                    sleep(Duration::from_millis(500));
+                }
+                Err(err) => panic!("setIdentity failed: {}", err),
+            }
+        }
+    };
+
+    // Hot-swap to an unstaked identity, the node should advertise it without a restart
+    let new_identity = Keypair::new();
+    set_identity(&new_identity);
+    assert_eq!(cluster_info.id(), new_identity.pubkey());
+    assert_eq!(cluster_info.my_contact_info().id, new_identity.pubkey());
+    assert_eq!(rpc_client.get_identity().unwrap(), new_identity.pubkey());
+    sleep(Duration::from_millis(clock::DEFAULT_MS_PER_SLOT * 8));
+
+    // Swap back to the staked identity, which must resume producing blocks and rooting them
+    set_identity(&original_identity);
+    assert_eq!(cluster_info.id(), original_identity.pubkey());
+    assert_eq!(
+        rpc_client.get_identity().unwrap(),
+        original_identity.pubkey()
+    );
+    cluster.check_for_new_roots(16, "test_set_identity");
+}
+
 #[test]
 #[serial]
 fn test_optimistic_confirmation_violation_detection() {
@@ -7,6 +7,7 @@ use {
     log::*,
     solana_banks_client::start_client,
     solana_banks_server::banks_server::start_local_server,
+    solana_bpf_loader_program::serialization::{deserialize_parameters, serialize_parameters},
     solana_runtime::{
         bank::{Bank, Builtin, ExecuteTimings},
         bank_forks::BankForks,
@@ -16,10 +17,14 @@ use {
     solana_sdk::{
         account::{Account, AccountSharedData, ReadableAccount},
         account_info::AccountInfo,
+        bpf_loader,
         clock::{Clock, Slot},
-        entrypoint::{ProgramResult, SUCCESS},
+        entrypoint::{deserialize, ProgramResult, SUCCESS},
         epoch_schedule::EpochSchedule,
-        feature_set::demote_sysvar_write_locks,
+        feature_set::{
+            demote_sysvar_write_locks, do_support_realloc, return_data_syscall_enabled,
+            skip_ro_deserialization,
+        },
         fee_calculator::{FeeCalculator, FeeRateGovernor},
         genesis_config::{ClusterType, GenesisConfig},
         hash::Hash,
@@ -31,6 +36,7 @@ use {
         process_instruction::{
             stable_log, BpfComputeBudget, InvokeContext, ProcessInstructionWithContext,
         },
+        program::MAX_RETURN_DATA,
         program_error::{ProgramError, ACCOUNT_BORROW_FAILED, UNSUPPORTED_SYSVAR},
         pubkey::Pubkey,
         rent::Rent,
@@ -44,7 +50,6 @@ use {
     solana_vote_program::vote_state::{VoteState, VoteStateVersions},
     std::{
         cell::RefCell,
-        collections::HashMap,
         convert::TryFrom,
         fs::File,
         io::{self, Read},
@@ -63,6 +68,7 @@ use {
 
 // Export types so test clients can limit their solana crate dependencies
 pub use solana_banks_client::BanksClient;
+pub use solana_runtime::program_trace::{ProgramTraceFilter, TransactionProgramTraces};
 
 // Export tokio for test clients
 pub use tokio;
@@ -103,66 +109,30 @@ pub fn builtin_process_instruction(
     input: &[u8],
     invoke_context: &mut dyn InvokeContext,
 ) -> Result<(), InstructionError> {
+    let skip_ro_deserialization = invoke_context.is_feature_active(&skip_ro_deserialization::id());
+    let do_support_realloc = invoke_context.is_feature_active(&do_support_realloc::id());
     set_invoke_context(invoke_context);
 
-    // Copy all the accounts into a HashMap to ensure there are no duplicates
-    let mut accounts: HashMap<Pubkey, Account> = keyed_accounts
-        .iter()
-        .map(|ka| {
-            (
-                *ka.unsigned_key(),
-                Account::from(ka.account.borrow().clone()),
-            )
-        })
-        .collect();
-
-    // Create shared references to each account's lamports/data/owner
-    let account_refs: HashMap<_, _> = accounts
-        .iter_mut()
-        .map(|(key, account)| {
-            (
-                *key,
-                (
-                    Rc::new(RefCell::new(&mut account.lamports)),
-                    Rc::new(RefCell::new(&mut account.data[..])),
-                    &account.owner,
-                ),
-            )
-        })
-        .collect();
-
-    // Create AccountInfos
-    let account_infos: Vec<AccountInfo> = keyed_accounts
-        .iter()
-        .map(|keyed_account| {
-            let key = keyed_account.unsigned_key();
-            let (lamports, data, owner) = &account_refs[key];
-            AccountInfo {
-                key,
-                is_signer: keyed_account.signer_key().is_some(),
-                is_writable: keyed_account.is_writable(),
-                lamports: lamports.clone(),
-                data: data.clone(),
-                owner,
-                executable: keyed_account.executable().unwrap(),
-                rent_epoch: keyed_account.rent_epoch().unwrap(),
-            }
-        })
-        .collect();
+    // Serialize the accounts into the same input buffer a BPF program gets, so
+    // that things like `AccountInfo::realloc` behave as they do on-chain
+    let loader_id = bpf_loader::id();
+    let mut parameter_bytes = serialize_parameters(&loader_id, program_id, keyed_accounts, input)?;
 
     // Execute the program
-    process_instruction(program_id, &account_infos, input).map_err(u64::from)?;
-
-    // Commit AccountInfo changes back into KeyedAccounts
-    for keyed_account in keyed_accounts {
-        let mut account = keyed_account.account.borrow_mut();
-        let key = keyed_account.unsigned_key();
-        let (lamports, data, _owner) = &account_refs[key];
-        account.lamports = **lamports.borrow();
-        account.set_data(data.borrow().to_vec());
+    {
+        let (program_id, account_infos, input) =
+            unsafe { deserialize(parameter_bytes.as_mut_ptr()) };
+        process_instruction(program_id, &account_infos, input).map_err(u64::from)?;
     }
 
-    Ok(())
+    // Commit AccountInfo changes back into KeyedAccounts
+    deserialize_parameters(
+        &loader_id,
+        keyed_accounts,
+        &parameter_bytes,
+        skip_ro_deserialization,
+        do_support_realloc,
+    )
 }
 
 /// Converts a `solana-program`-style entrypoint into the runtime's entrypoint style, for
@@ -392,6 +362,36 @@ impl solana_sdk::program_stubs::SyscallStubs for SyscallStubs {
     fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
         get_sysvar::<Rent>(&rent::id(), var_addr)
     }
+
+    fn sol_set_return_data(&self, data: &[u8]) {
+        let invoke_context = get_invoke_context();
+        if !invoke_context.is_feature_active(&return_data_syscall_enabled::id()) {
+            panic!("sol_set_return_data() not available");
+        }
+        if data.len() > MAX_RETURN_DATA {
+            panic!(
+                "Return data too large ({} > {})",
+                data.len(),
+                MAX_RETURN_DATA
+            );
+        }
+        invoke_context
+            .set_return_data(data.to_vec())
+            .expect("set_return_data");
+    }
+
+    fn sol_get_return_data(&self) -> Option<(Pubkey, Vec<u8>)> {
+        let invoke_context = get_invoke_context();
+        if !invoke_context.is_feature_active(&return_data_syscall_enabled::id()) {
+            panic!("sol_get_return_data() not available");
+        }
+        let (program_id, data) = invoke_context.get_return_data();
+        if data.is_empty() {
+            None
+        } else {
+            Some((program_id, data.to_vec()))
+        }
+    }
 }
 
 pub fn find_file(filename: &str) -> Option<PathBuf> {
@@ -468,6 +468,7 @@ pub struct ProgramTest {
     builtins: Vec<Builtin>,
     bpf_compute_max_units: Option<u64>,
     prefer_bpf: bool,
+    program_trace_filter: ProgramTraceFilter,
 }
 
 impl Default for ProgramTest {
@@ -497,6 +498,7 @@ impl Default for ProgramTest {
             builtins: vec![],
             bpf_compute_max_units: None,
             prefer_bpf,
+            program_trace_filter: ProgramTraceFilter::default(),
         }
     }
 }
@@ -522,6 +524,12 @@ impl ProgramTest {
         self.bpf_compute_max_units = Some(bpf_compute_max_units);
     }
 
+    /// Record an instruction trace of the BPF programs invoked by the transactions matching
+    /// `program_trace_filter`, see `ProgramTestContext::take_program_traces()`
+    pub fn set_program_trace_filter(&mut self, program_trace_filter: ProgramTraceFilter) {
+        self.program_trace_filter = program_trace_filter;
+    }
+
     /// Add an account to the test environment
     pub fn add_account(&mut self, address: Pubkey, account: Account) {
         self.accounts
@@ -693,6 +701,7 @@ impl ProgramTest {
         for loader in &[
             solana_bpf_loader_deprecated_program!(),
             solana_bpf_loader_program!(),
+            solana_bpf_loader_upgradeable_program!(),
         ] {
             bank.add_builtin(&loader.0, loader.1, loader.2);
         }
@@ -724,6 +733,7 @@ impl ProgramTest {
                 ..BpfComputeBudget::default()
             }));
         }
+        bank.program_trace_collector.write().unwrap().filter = self.program_trace_filter.clone();
         let bank = setup_fee_calculator(bank);
         let slot = bank.slot();
         let last_blockhash = bank.last_blockhash();
@@ -896,6 +906,15 @@ impl ProgramTestContext {
         &self.genesis_config
     }
 
+    /// Take the BPF program traces recorded since the last call, see
+    /// `ProgramTest::set_program_trace_filter()`
+    pub fn take_program_traces(&self) -> Vec<TransactionProgramTraces> {
+        let bank_forks = self.bank_forks.read().unwrap();
+        let bank = bank_forks.working_bank();
+        let mut program_trace_collector = bank.program_trace_collector.write().unwrap();
+        std::mem::take(&mut program_trace_collector.traces)
+    }
+
     /// Manually increment vote credits for the current epoch in the specified vote account to simulate validator voting activity
     pub fn increment_vote_account_credits(
         &mut self,
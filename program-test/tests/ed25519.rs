@@ -0,0 +1,122 @@
+use {
+    solana_program_test::{processor, ProgramTest},
+    solana_sdk::{
+        account_info::{next_account_info, AccountInfo},
+        ed25519_instruction::{
+            get_signature_offsets, new_ed25519_instruction, CURRENT_INSTRUCTION_INDEX,
+            PUBKEY_SERIALIZED_SIZE,
+        },
+        ed25519_program,
+        entrypoint::ProgramResult,
+        instruction::{AccountMeta, Instruction},
+        msg,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+        sysvar,
+        transaction::{Transaction, TransactionError},
+    },
+};
+
+const MESSAGE: &[u8] = b"oracle price: 42";
+
+// Process instruction checking that the previous instruction verified `MESSAGE`, signed by the
+// public key passed in as instruction data
+fn process_instruction(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    input: &[u8],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let instructions_info = next_account_info(account_info_iter)?;
+    let instructions_data = instructions_info.data.borrow();
+    let current_index = sysvar::instructions::load_current_index(&instructions_data);
+    if current_index == 0 {
+        return Err(ProgramError::InvalidArgument);
+    }
+    let instruction =
+        sysvar::instructions::load_instruction_at(current_index as usize - 1, &instructions_data)
+            .map_err(|_| ProgramError::InvalidArgument)?;
+    if instruction.program_id != ed25519_program::id() {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let offsets =
+        get_signature_offsets(&instruction.data).map_err(|_| ProgramError::InvalidArgument)?;
+    if offsets.len() != 1
+        || offsets[0].public_key_instruction_index != CURRENT_INSTRUCTION_INDEX
+        || offsets[0].message_instruction_index != CURRENT_INSTRUCTION_INDEX
+    {
+        return Err(ProgramError::InvalidArgument);
+    }
+    let public_key_offset = offsets[0].public_key_offset as usize;
+    let public_key =
+        &instruction.data[public_key_offset..public_key_offset + PUBKEY_SERIALIZED_SIZE];
+    let message_data_offset = offsets[0].message_data_offset as usize;
+    let message = &instruction.data
+        [message_data_offset..message_data_offset + offsets[0].message_data_size as usize];
+    if public_key != input || message != MESSAGE {
+        return Err(ProgramError::InvalidArgument);
+    }
+    msg!("Found verified ed25519 signature");
+    Ok(())
+}
+
+fn check_instruction(program_id: Pubkey, signer: &Pubkey) -> Instruction {
+    Instruction::new_with_bytes(
+        program_id,
+        signer.as_ref(),
+        vec![AccountMeta::new_readonly(sysvar::instructions::id(), false)],
+    )
+}
+
+#[tokio::test]
+async fn ed25519_introspection() {
+    let program_id = Pubkey::new_unique();
+    let program_test = ProgramTest::new(
+        "ed25519_introspection",
+        program_id,
+        processor!(process_instruction),
+    );
+    let mut context = program_test.start_with_context().await;
+    let oracle = Keypair::new();
+
+    // A valid signature is visible to the program through the instructions sysvar
+    let transaction = Transaction::new_signed_with_payer(
+        &[
+            new_ed25519_instruction(&oracle, MESSAGE),
+            check_instruction(program_id, &oracle.pubkey()),
+        ],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap();
+
+    // A bad signature is rejected before the program runs
+    let mut ed25519_instruction = new_ed25519_instruction(&oracle, MESSAGE);
+    let last = ed25519_instruction.data.len() - 1;
+    ed25519_instruction.data[last] ^= 1;
+    let transaction = Transaction::new_signed_with_payer(
+        &[
+            ed25519_instruction,
+            check_instruction(program_id, &oracle.pubkey()),
+        ],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    assert_eq!(
+        context
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InvalidAccountIndex
+    );
+}
@@ -0,0 +1,93 @@
+use {
+    solana_program_test::ProgramTest,
+    solana_sdk::{
+        account::Account,
+        bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+        instruction::InstructionError,
+        pubkey::Pubkey,
+        rent::Rent,
+        signature::{Keypair, Signer},
+        transaction::{Transaction, TransactionError},
+    },
+};
+
+#[tokio::test]
+async fn extend_program_data() {
+    let program_address = Pubkey::new_unique();
+    let (programdata_address, _) =
+        Pubkey::find_program_address(&[program_address.as_ref()], &bpf_loader_upgradeable::id());
+    let upgrade_authority = Keypair::new();
+
+    let programdata_len = UpgradeableLoaderState::programdata_len(64).unwrap();
+    let mut data = vec![0; programdata_len];
+    bincode::serialize_into(
+        &mut data[..],
+        &UpgradeableLoaderState::ProgramData {
+            slot: 0,
+            upgrade_authority_address: Some(upgrade_authority.pubkey()),
+        },
+    )
+    .unwrap();
+    let mut program_test = ProgramTest::default();
+    program_test.add_account(
+        programdata_address,
+        Account {
+            lamports: Rent::default().minimum_balance(programdata_len),
+            data,
+            owner: bpf_loader_upgradeable::id(),
+            ..Account::default()
+        },
+    );
+    let mut context = program_test.start_with_context().await;
+
+    // The upgrade authority must sign
+    let wrong_authority = Keypair::new();
+    let transaction = Transaction::new_signed_with_payer(
+        &[bpf_loader_upgradeable::extend_program_data(
+            &program_address,
+            &wrong_authority.pubkey(),
+            &context.payer.pubkey(),
+            1024,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &wrong_authority],
+        context.last_blockhash,
+    );
+    assert_eq!(
+        context
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(0, InstructionError::IncorrectAuthority)
+    );
+
+    // The ProgramData account grows and stays rent-exempt
+    let transaction = Transaction::new_signed_with_payer(
+        &[bpf_loader_upgradeable::extend_program_data(
+            &program_address,
+            &upgrade_authority.pubkey(),
+            &context.payer.pubkey(),
+            1024,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &upgrade_authority],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap();
+
+    let account = context
+        .banks_client
+        .get_account(programdata_address)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(account.data.len(), programdata_len + 1024);
+    let rent = context.banks_client.get_rent().await.unwrap();
+    assert!(rent.is_exempt(account.lamports, account.data.len()));
+}
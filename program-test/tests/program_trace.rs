@@ -0,0 +1,59 @@
+use {
+    solana_program_test::{ProgramTest, ProgramTraceFilter},
+    solana_sdk::{
+        account::Account, bpf_loader, instruction::Instruction, pubkey::Pubkey, signature::Signer,
+        transaction::Transaction,
+    },
+    std::{fs::File, io::Read},
+};
+
+#[tokio::test]
+async fn program_trace() {
+    let program_id = Pubkey::new_unique();
+    let mut file =
+        File::open("../programs/bpf_loader/test_elfs/noop_aligned.so").expect("file open failed");
+    let mut elf = Vec::new();
+    file.read_to_end(&mut elf).unwrap();
+
+    let mut program_test = ProgramTest::default();
+    program_test.add_account(
+        program_id,
+        Account {
+            lamports: 1,
+            data: elf,
+            owner: bpf_loader::id(),
+            executable: true,
+            rent_epoch: 0,
+        },
+    );
+    program_test.set_program_trace_filter(ProgramTraceFilter::All);
+
+    let mut context = program_test.start_with_context().await;
+    let transaction = Transaction::new_signed_with_payer(
+        &[Instruction::new_with_bytes(program_id, &[], vec![])],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    let signature = transaction.signatures[0];
+    context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap();
+
+    let traces = context.take_program_traces();
+    assert_eq!(traces.len(), 1);
+    assert_eq!(traces[0].signature, signature);
+    assert_eq!(traces[0].traces.len(), 1);
+    assert_eq!(traces[0].traces[0].program_id, program_id);
+    let root = format!("{};entrypoint", program_id);
+    let folded_stacks = traces[0].folded_stacks();
+    assert!(folded_stacks.contains_key(&root));
+    assert!(folded_stacks.keys().all(|stack| stack.starts_with(&root)));
+    assert_eq!(
+        folded_stacks.values().sum::<u64>(),
+        traces[0].traces[0].instructions.len() as u64
+    );
+    assert!(context.take_program_traces().is_empty());
+}
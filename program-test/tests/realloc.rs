@@ -0,0 +1,96 @@
+use {
+    solana_program_test::{processor, ProgramTest},
+    solana_sdk::{
+        account::Account,
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::{ProgramResult, MAX_PERMITTED_DATA_INCREASE},
+        instruction::{AccountMeta, Instruction, InstructionError},
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+        transaction::{Transaction, TransactionError},
+    },
+    std::convert::TryInto,
+};
+
+// Process instruction to resize the first account to the length in `input`
+fn realloc_process_instruction(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    input: &[u8],
+) -> ProgramResult {
+    let account_info = next_account_info(&mut accounts.iter())?;
+    let new_len = u64::from_le_bytes(input.try_into().unwrap()) as usize;
+    account_info.realloc(new_len, true)?;
+    assert_eq!(account_info.data_len(), new_len);
+    Ok(())
+}
+
+#[tokio::test]
+async fn realloc() {
+    let program_id = Pubkey::new_unique();
+    let account_pubkey = Pubkey::new_unique();
+    let mut program_test = ProgramTest::new(
+        "realloc",
+        program_id,
+        processor!(realloc_process_instruction),
+    );
+    program_test.add_account(
+        account_pubkey,
+        Account {
+            lamports: 1_000_000_000,
+            data: vec![1; 4],
+            owner: program_id,
+            ..Account::default()
+        },
+    );
+
+    let mut context = program_test.start_with_context().await;
+    let realloc_transaction = |new_len: usize, payer: &Keypair, recent_blockhash| {
+        Transaction::new_signed_with_payer(
+            &[Instruction::new_with_bytes(
+                program_id,
+                &(new_len as u64).to_le_bytes(),
+                vec![AccountMeta::new(account_pubkey, false)],
+            )],
+            Some(&payer.pubkey()),
+            &[payer],
+            recent_blockhash,
+        )
+    };
+
+    let transaction = realloc_transaction(
+        MAX_PERMITTED_DATA_INCREASE + 4,
+        &context.payer,
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap();
+    let account = context
+        .banks_client
+        .get_account(account_pubkey)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(account.data.len(), MAX_PERMITTED_DATA_INCREASE + 4);
+    assert_eq!(&account.data[..4], &[1; 4]);
+    assert!(account.data[4..].iter().all(|byte| *byte == 0));
+
+    // Each instruction may only grow the data by the permitted increase
+    let transaction = realloc_transaction(
+        2 * MAX_PERMITTED_DATA_INCREASE + 5,
+        &context.payer,
+        context.last_blockhash,
+    );
+    assert_eq!(
+        context
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidRealloc)
+    );
+}
@@ -0,0 +1,99 @@
+use {
+    solana_program_test::{processor, ProgramTest},
+    solana_sdk::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        instruction::{AccountMeta, Instruction},
+        msg,
+        process_instruction::TransactionReturnData,
+        program::{get_return_data, invoke, set_return_data},
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        signature::Signer,
+        transaction::Transaction,
+    },
+};
+
+// Process instruction to invoke into another program and read its return data
+fn invoker_process_instruction(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _input: &[u8],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let invoked_program_info = next_account_info(account_info_iter)?;
+    invoke(
+        &Instruction::new_with_bytes(*invoked_program_info.key, b"hello", vec![]),
+        &[invoked_program_info.clone()],
+    )?;
+    let (program_id, data) = get_return_data().ok_or(ProgramError::InvalidArgument)?;
+    if program_id != *invoked_program_info.key || data != b"hello world" {
+        return Err(ProgramError::InvalidArgument);
+    }
+    msg!("Read return data from invoked program");
+    set_return_data(b"ok");
+    Ok(())
+}
+
+// Process instruction to be invoked by another program, setting return data
+#[allow(clippy::unnecessary_wraps)]
+fn invoked_process_instruction(
+    _program_id: &Pubkey,
+    _accounts: &[AccountInfo],
+    input: &[u8],
+) -> ProgramResult {
+    let mut data = input.to_vec();
+    data.extend_from_slice(b" world");
+    set_return_data(&data);
+    Ok(())
+}
+
+#[tokio::test]
+async fn return_data() {
+    let invoker_program_id = Pubkey::new_unique();
+    let mut program_test = ProgramTest::new(
+        "invoker",
+        invoker_program_id,
+        processor!(invoker_process_instruction),
+    );
+    let invoked_program_id = Pubkey::new_unique();
+    program_test.add_program(
+        "invoked",
+        invoked_program_id,
+        processor!(invoked_process_instruction),
+    );
+
+    let mut context = program_test.start_with_context().await;
+    let instructions = vec![Instruction::new_with_bytes(
+        invoker_program_id,
+        &[],
+        vec![AccountMeta::new_readonly(invoked_program_id, false)],
+    )];
+
+    let transaction = Transaction::new_signed_with_payer(
+        &instructions,
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+
+    let simulation = context
+        .banks_client
+        .simulate_transaction(transaction.clone())
+        .await
+        .unwrap();
+    assert_eq!(simulation.result, Some(Ok(())));
+    assert_eq!(
+        simulation.simulation_details.unwrap().return_data,
+        Some(TransactionReturnData {
+            program_id: invoker_program_id,
+            data: b"ok".to_vec(),
+        })
+    );
+
+    context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap();
+}
@@ -1,6 +1,7 @@
 #![allow(clippy::integer_arithmetic)]
 use {
     solana_program_test::{processor, ProgramTest, ProgramTestError},
+    solana_runtime::bank::NUM_EPOCH_REWARD_PARTITIONS,
     solana_sdk::{
         account_info::{next_account_info, AccountInfo},
         clock::Clock,
@@ -235,10 +236,11 @@ async fn stake_rewards_from_warp() {
 
     context.increment_vote_account_credits(&vote_keypair.pubkey(), 100);
 
-    // go forward and see that rewards have been distributed
+    // go forward and see that rewards have been distributed, warping past the
+    // blocks that credit stake rewards after the epoch boundary
     let slots_per_epoch = context.genesis_config().epoch_schedule.slots_per_epoch;
     context
-        .warp_to_slot(first_normal_slot + slots_per_epoch)
+        .warp_to_slot(first_normal_slot + slots_per_epoch + NUM_EPOCH_REWARD_PARTITIONS)
         .unwrap();
 
     let account = context
@@ -0,0 +1,152 @@
+use crate::{id, state::LOOKUP_TABLE_MAX_SPACE};
+use serde_derive::{Deserialize, Serialize};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_instruction, sysvar,
+};
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+pub enum ProgramInstruction {
+    /// Initialize an address lookup table account. The account must be
+    /// allocated and assigned to this program beforehand, see
+    /// `create_lookup_table`.
+    ///
+    /// # Account references
+    ///   0. `[WRITE, SIGNER]` Uninitialized address lookup table account
+    ///   1. `[]` Address lookup table authority
+    InitializeLookupTable,
+
+    /// Permanently freeze an address lookup table, making it immutable.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Address lookup table account to freeze
+    ///   1. `[SIGNER]` Current authority
+    FreezeLookupTable,
+
+    /// Extend an address lookup table with new addresses. Appended addresses
+    /// may not be looked up until the next slot.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Address lookup table account to extend
+    ///   1. `[SIGNER]` Current authority
+    ///   2. `[]` Clock sysvar
+    ExtendLookupTable { new_addresses: Vec<Pubkey> },
+
+    /// Deactivate an address lookup table, making it unusable and
+    /// eligible for closure after a short period of time.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Address lookup table account to deactivate
+    ///   1. `[SIGNER]` Current authority
+    ///   2. `[]` Clock sysvar
+    DeactivateLookupTable,
+
+    /// Close an address lookup table account once its deactivation slot is
+    /// no longer recent
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Address lookup table account to close
+    ///   1. `[SIGNER]` Current authority
+    ///   2. `[WRITE]` Recipient of closed account lamports
+    ///   3. `[]` Clock sysvar
+    ///   4. `[]` Slot hashes sysvar
+    CloseLookupTable,
+}
+
+fn initialize_lookup_table(
+    lookup_table_address: &Pubkey,
+    authority_address: &Pubkey,
+) -> Instruction {
+    Instruction::new_with_bincode(
+        id(),
+        &ProgramInstruction::InitializeLookupTable,
+        vec![
+            AccountMeta::new(*lookup_table_address, true),
+            AccountMeta::new_readonly(*authority_address, false),
+        ],
+    )
+}
+
+/// Constructs instructions which allocate a lookup table account with
+/// space for the maximum number of addresses and initialize it.
+pub fn create_lookup_table(
+    payer_address: &Pubkey,
+    lookup_table_address: &Pubkey,
+    authority_address: &Pubkey,
+    lamports: u64,
+) -> Vec<Instruction> {
+    vec![
+        system_instruction::create_account(
+            payer_address,
+            lookup_table_address,
+            lamports,
+            LOOKUP_TABLE_MAX_SPACE as u64,
+            &id(),
+        ),
+        initialize_lookup_table(lookup_table_address, authority_address),
+    ]
+}
+
+pub fn freeze_lookup_table(
+    lookup_table_address: &Pubkey,
+    authority_address: &Pubkey,
+) -> Instruction {
+    Instruction::new_with_bincode(
+        id(),
+        &ProgramInstruction::FreezeLookupTable,
+        vec![
+            AccountMeta::new(*lookup_table_address, false),
+            AccountMeta::new_readonly(*authority_address, true),
+        ],
+    )
+}
+
+pub fn extend_lookup_table(
+    lookup_table_address: &Pubkey,
+    authority_address: &Pubkey,
+    new_addresses: Vec<Pubkey>,
+) -> Instruction {
+    Instruction::new_with_bincode(
+        id(),
+        &ProgramInstruction::ExtendLookupTable { new_addresses },
+        vec![
+            AccountMeta::new(*lookup_table_address, false),
+            AccountMeta::new_readonly(*authority_address, true),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+        ],
+    )
+}
+
+pub fn deactivate_lookup_table(
+    lookup_table_address: &Pubkey,
+    authority_address: &Pubkey,
+) -> Instruction {
+    Instruction::new_with_bincode(
+        id(),
+        &ProgramInstruction::DeactivateLookupTable,
+        vec![
+            AccountMeta::new(*lookup_table_address, false),
+            AccountMeta::new_readonly(*authority_address, true),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+        ],
+    )
+}
+
+pub fn close_lookup_table(
+    lookup_table_address: &Pubkey,
+    authority_address: &Pubkey,
+    recipient_address: &Pubkey,
+) -> Instruction {
+    Instruction::new_with_bincode(
+        id(),
+        &ProgramInstruction::CloseLookupTable,
+        vec![
+            AccountMeta::new(*lookup_table_address, false),
+            AccountMeta::new_readonly(*authority_address, true),
+            AccountMeta::new(*recipient_address, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(sysvar::slot_hashes::id(), false),
+        ],
+    )
+}
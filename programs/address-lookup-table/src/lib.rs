@@ -0,0 +1,6 @@
+#![allow(clippy::integer_arithmetic)]
+pub mod instruction;
+pub mod processor;
+pub mod state;
+
+solana_sdk::declare_id!("AddressLookupTab1e1111111111111111111111111");
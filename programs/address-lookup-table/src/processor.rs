@@ -0,0 +1,591 @@
+//! Address lookup table program
+
+use crate::{
+    id,
+    instruction::ProgramInstruction,
+    state::{AddressLookupTable, LookupTableMeta, ProgramState, LOOKUP_TABLE_META_SIZE},
+};
+use solana_sdk::{
+    account::{ReadableAccount, WritableAccount},
+    clock::Clock,
+    ic_msg,
+    instruction::InstructionError,
+    keyed_account::{from_keyed_account, next_keyed_account, KeyedAccount},
+    process_instruction::InvokeContext,
+    program_utils::limited_deserialize,
+    pubkey::Pubkey,
+    slot_hashes::SlotHashes,
+};
+use std::mem::size_of;
+
+pub fn process_instruction(
+    _program_id: &Pubkey,
+    keyed_accounts: &[KeyedAccount],
+    data: &[u8],
+    invoke_context: &mut dyn InvokeContext,
+) -> Result<(), InstructionError> {
+    let keyed_accounts_iter = &mut keyed_accounts.iter();
+    let lookup_table_account = next_keyed_account(keyed_accounts_iter)?;
+    if lookup_table_account.owner()? != id() {
+        return Err(InstructionError::InvalidAccountOwner);
+    }
+
+    match limited_deserialize(data)? {
+        ProgramInstruction::InitializeLookupTable => {
+            let authority_account = next_keyed_account(keyed_accounts_iter)?;
+            initialize_lookup_table(invoke_context, lookup_table_account, authority_account)
+        }
+        ProgramInstruction::FreezeLookupTable => {
+            let authority_account = next_keyed_account(keyed_accounts_iter)?;
+            freeze_lookup_table(invoke_context, lookup_table_account, authority_account)
+        }
+        ProgramInstruction::ExtendLookupTable { new_addresses } => {
+            let authority_account = next_keyed_account(keyed_accounts_iter)?;
+            let clock = from_keyed_account::<Clock>(next_keyed_account(keyed_accounts_iter)?)?;
+            extend_lookup_table(
+                invoke_context,
+                lookup_table_account,
+                authority_account,
+                &clock,
+                new_addresses,
+            )
+        }
+        ProgramInstruction::DeactivateLookupTable => {
+            let authority_account = next_keyed_account(keyed_accounts_iter)?;
+            let clock = from_keyed_account::<Clock>(next_keyed_account(keyed_accounts_iter)?)?;
+            deactivate_lookup_table(
+                invoke_context,
+                lookup_table_account,
+                authority_account,
+                &clock,
+            )
+        }
+        ProgramInstruction::CloseLookupTable => {
+            let authority_account = next_keyed_account(keyed_accounts_iter)?;
+            let recipient_account = next_keyed_account(keyed_accounts_iter)?;
+            let clock = from_keyed_account::<Clock>(next_keyed_account(keyed_accounts_iter)?)?;
+            let slot_hashes =
+                from_keyed_account::<SlotHashes>(next_keyed_account(keyed_accounts_iter)?)?;
+            close_lookup_table(
+                invoke_context,
+                lookup_table_account,
+                authority_account,
+                recipient_account,
+                &clock,
+                &slot_hashes,
+            )
+        }
+    }
+}
+
+fn initialize_lookup_table(
+    invoke_context: &dyn InvokeContext,
+    lookup_table_account: &KeyedAccount,
+    authority_account: &KeyedAccount,
+) -> Result<(), InstructionError> {
+    // The lookup table account must sign to prevent anyone else from
+    // initializing it with their own authority
+    if lookup_table_account.signer_key().is_none() {
+        ic_msg!(invoke_context, "Lookup table account must be a signer");
+        return Err(InstructionError::MissingRequiredSignature);
+    }
+
+    let mut account = lookup_table_account.try_account_ref_mut()?;
+    match AddressLookupTable::deserialize_state(account.data())? {
+        ProgramState::Uninitialized => {}
+        ProgramState::LookupTable(_) => return Err(InstructionError::AccountAlreadyInitialized),
+    }
+    if AddressLookupTable::capacity(account.data().len()) == 0 {
+        ic_msg!(
+            invoke_context,
+            "Lookup table account is too small to hold any addresses"
+        );
+        return Err(InstructionError::AccountDataTooSmall);
+    }
+
+    AddressLookupTable::overwrite_meta_data(
+        account.data_as_mut_slice(),
+        LookupTableMeta::new(*authority_account.unsigned_key()),
+    )
+}
+
+/// Deserializes a lookup table and checks that the authority account signed
+/// on behalf of the table's authority
+fn get_lookup_table_for_update(
+    invoke_context: &dyn InvokeContext,
+    lookup_table_account: &KeyedAccount,
+    authority_account: &KeyedAccount,
+) -> Result<AddressLookupTable, InstructionError> {
+    let lookup_table =
+        AddressLookupTable::deserialize(lookup_table_account.try_account_ref()?.data())?;
+    match lookup_table.meta.authority {
+        None => {
+            ic_msg!(invoke_context, "Lookup table is frozen");
+            Err(InstructionError::Immutable)
+        }
+        Some(authority) if authority != *authority_account.unsigned_key() => {
+            Err(InstructionError::IncorrectAuthority)
+        }
+        Some(_) if authority_account.signer_key().is_none() => {
+            ic_msg!(invoke_context, "Authority account must be a signer");
+            Err(InstructionError::MissingRequiredSignature)
+        }
+        Some(_) => Ok(lookup_table),
+    }
+}
+
+fn freeze_lookup_table(
+    invoke_context: &dyn InvokeContext,
+    lookup_table_account: &KeyedAccount,
+    authority_account: &KeyedAccount,
+) -> Result<(), InstructionError> {
+    let lookup_table =
+        get_lookup_table_for_update(invoke_context, lookup_table_account, authority_account)?;
+    if !lookup_table.meta.is_active() {
+        ic_msg!(invoke_context, "Deactivated tables cannot be frozen");
+        return Err(InstructionError::InvalidArgument);
+    }
+    if lookup_table.addresses.is_empty() {
+        ic_msg!(invoke_context, "Empty lookup tables cannot be frozen");
+        return Err(InstructionError::InvalidInstructionData);
+    }
+
+    let mut meta = lookup_table.meta;
+    meta.authority = None;
+    AddressLookupTable::overwrite_meta_data(
+        lookup_table_account
+            .try_account_ref_mut()?
+            .data_as_mut_slice(),
+        meta,
+    )
+}
+
+fn extend_lookup_table(
+    invoke_context: &dyn InvokeContext,
+    lookup_table_account: &KeyedAccount,
+    authority_account: &KeyedAccount,
+    clock: &Clock,
+    new_addresses: Vec<Pubkey>,
+) -> Result<(), InstructionError> {
+    let lookup_table =
+        get_lookup_table_for_update(invoke_context, lookup_table_account, authority_account)?;
+    if !lookup_table.meta.is_active() {
+        ic_msg!(invoke_context, "Deactivated tables cannot be extended");
+        return Err(InstructionError::InvalidArgument);
+    }
+    if new_addresses.is_empty() {
+        ic_msg!(invoke_context, "Must extend with at least one address");
+        return Err(InstructionError::InvalidInstructionData);
+    }
+
+    let mut account = lookup_table_account.try_account_ref_mut()?;
+    let old_num_addresses = lookup_table.addresses.len();
+    let new_num_addresses = old_num_addresses.saturating_add(new_addresses.len());
+    if new_num_addresses > AddressLookupTable::capacity(account.data().len()) {
+        ic_msg!(
+            invoke_context,
+            "Extended lookup table length {} would exceed its capacity of {}",
+            new_num_addresses,
+            AddressLookupTable::capacity(account.data().len()),
+        );
+        return Err(InstructionError::InvalidInstructionData);
+    }
+
+    let mut meta = lookup_table.meta;
+    if clock.slot != meta.last_extended_slot {
+        meta.last_extended_slot = clock.slot;
+        meta.last_extended_slot_start_index = old_num_addresses as u8;
+    }
+    meta.num_addresses = new_num_addresses as u16;
+
+    let data = account.data_as_mut_slice();
+    AddressLookupTable::overwrite_meta_data(data, meta)?;
+    let start = LOOKUP_TABLE_META_SIZE + old_num_addresses * size_of::<Pubkey>();
+    for (address_data, new_address) in data[start..]
+        .chunks_exact_mut(size_of::<Pubkey>())
+        .zip(new_addresses.iter())
+    {
+        address_data.copy_from_slice(new_address.as_ref());
+    }
+
+    Ok(())
+}
+
+fn deactivate_lookup_table(
+    invoke_context: &dyn InvokeContext,
+    lookup_table_account: &KeyedAccount,
+    authority_account: &KeyedAccount,
+    clock: &Clock,
+) -> Result<(), InstructionError> {
+    let lookup_table =
+        get_lookup_table_for_update(invoke_context, lookup_table_account, authority_account)?;
+    if !lookup_table.meta.is_active() {
+        ic_msg!(invoke_context, "Lookup table is already deactivated");
+        return Err(InstructionError::InvalidArgument);
+    }
+
+    let mut meta = lookup_table.meta;
+    meta.deactivation_slot = clock.slot;
+    AddressLookupTable::overwrite_meta_data(
+        lookup_table_account
+            .try_account_ref_mut()?
+            .data_as_mut_slice(),
+        meta,
+    )
+}
+
+fn close_lookup_table(
+    invoke_context: &dyn InvokeContext,
+    lookup_table_account: &KeyedAccount,
+    authority_account: &KeyedAccount,
+    recipient_account: &KeyedAccount,
+    clock: &Clock,
+    slot_hashes: &SlotHashes,
+) -> Result<(), InstructionError> {
+    if recipient_account.unsigned_key() == lookup_table_account.unsigned_key() {
+        ic_msg!(
+            invoke_context,
+            "Lookup table cannot be the recipient of reclaimed lamports"
+        );
+        return Err(InstructionError::InvalidArgument);
+    }
+
+    let lookup_table =
+        get_lookup_table_for_update(invoke_context, lookup_table_account, authority_account)?;
+    if lookup_table.meta.is_active() {
+        ic_msg!(invoke_context, "Lookup table is not deactivated");
+        return Err(InstructionError::InvalidArgument);
+    }
+    if lookup_table.meta.is_usable(clock.slot, slot_hashes) {
+        ic_msg!(
+            invoke_context,
+            "Lookup table deactivation slot {} is still recent",
+            lookup_table.meta.deactivation_slot,
+        );
+        return Err(InstructionError::InvalidArgument);
+    }
+
+    let mut account = lookup_table_account.try_account_ref_mut()?;
+    let mut recipient = recipient_account.try_account_ref_mut()?;
+    let lamports = recipient
+        .lamports()
+        .checked_add(account.lamports())
+        .ok_or(InstructionError::ArithmeticOverflow)?;
+    recipient.set_lamports(lamports);
+    account.set_lamports(0);
+    account
+        .data_as_mut_slice()
+        .iter_mut()
+        .for_each(|byte| *byte = 0);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        instruction::{
+            close_lookup_table, create_lookup_table, deactivate_lookup_table, extend_lookup_table,
+            freeze_lookup_table,
+        },
+        state::{LookupError, LOOKUP_TABLE_MAX_ADDRESSES, LOOKUP_TABLE_MAX_SPACE},
+    };
+    use solana_sdk::{
+        account::{self, AccountSharedData},
+        hash::Hash,
+        instruction::Instruction,
+        process_instruction::MockInvokeContext,
+        sysvar,
+    };
+    use std::{cell::RefCell, collections::HashMap};
+
+    struct TestContext {
+        accounts: HashMap<Pubkey, RefCell<AccountSharedData>>,
+        clock: Clock,
+        slot_hashes: SlotHashes,
+    }
+
+    impl TestContext {
+        fn new(lookup_table_address: &Pubkey) -> Self {
+            let mut accounts = HashMap::new();
+            accounts.insert(
+                *lookup_table_address,
+                RefCell::new(AccountSharedData::new(100, LOOKUP_TABLE_MAX_SPACE, &id())),
+            );
+            Self {
+                accounts,
+                clock: Clock::default(),
+                slot_hashes: SlotHashes::default(),
+            }
+        }
+
+        fn process(&self, instruction: &Instruction) -> Result<(), InstructionError> {
+            let accounts: Vec<_> = instruction
+                .accounts
+                .iter()
+                .map(|meta| {
+                    if sysvar::clock::check_id(&meta.pubkey) {
+                        RefCell::new(account::create_account_shared_data_for_test(&self.clock))
+                    } else if sysvar::slot_hashes::check_id(&meta.pubkey) {
+                        RefCell::new(account::create_account_shared_data_for_test(
+                            &self.slot_hashes,
+                        ))
+                    } else {
+                        self.accounts.get(&meta.pubkey).cloned().unwrap_or_default()
+                    }
+                })
+                .collect();
+            let keyed_accounts: Vec<_> = instruction
+                .accounts
+                .iter()
+                .zip(accounts.iter())
+                .map(|(meta, account)| KeyedAccount::new(&meta.pubkey, meta.is_signer, account))
+                .collect();
+            process_instruction(
+                &id(),
+                &keyed_accounts,
+                &instruction.data,
+                &mut MockInvokeContext::default(),
+            )?;
+
+            for (meta, account) in instruction.accounts.iter().zip(accounts.into_iter()) {
+                if let Some(stored) = self.accounts.get(&meta.pubkey) {
+                    *stored.borrow_mut() = account.into_inner();
+                }
+            }
+            Ok(())
+        }
+
+        fn lookup_table(&self, address: &Pubkey) -> AddressLookupTable {
+            AddressLookupTable::deserialize(self.accounts[address].borrow().data()).unwrap()
+        }
+    }
+
+    fn with_signer(mut instruction: Instruction, index: usize, is_signer: bool) -> Instruction {
+        instruction.accounts[index].is_signer = is_signer;
+        instruction
+    }
+
+    #[test]
+    fn test_initialize_lookup_table() {
+        let table = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let context = TestContext::new(&table);
+        let initialize_ix = create_lookup_table(&Pubkey::new_unique(), &table, &authority, 1)
+            .pop()
+            .unwrap();
+
+        assert_eq!(
+            context.process(&with_signer(initialize_ix.clone(), 0, false)),
+            Err(InstructionError::MissingRequiredSignature)
+        );
+        assert_eq!(context.process(&initialize_ix), Ok(()));
+        assert_eq!(
+            context.lookup_table(&table),
+            AddressLookupTable {
+                meta: LookupTableMeta::new(authority),
+                addresses: vec![],
+            }
+        );
+        assert_eq!(
+            context.process(&initialize_ix),
+            Err(InstructionError::AccountAlreadyInitialized)
+        );
+
+        context.accounts[&table]
+            .borrow_mut()
+            .set_owner(Pubkey::new_unique());
+        assert_eq!(
+            context.process(&initialize_ix),
+            Err(InstructionError::InvalidAccountOwner)
+        );
+
+        // accounts too small to hold any addresses cannot be initialized
+        let small_table = Pubkey::new_unique();
+        let mut context = TestContext::new(&small_table);
+        context.accounts.insert(
+            small_table,
+            RefCell::new(AccountSharedData::new(100, LOOKUP_TABLE_META_SIZE, &id())),
+        );
+        let initialize_ix = create_lookup_table(&authority, &small_table, &authority, 1)
+            .pop()
+            .unwrap();
+        assert_eq!(
+            context.process(&initialize_ix),
+            Err(InstructionError::AccountDataTooSmall)
+        );
+    }
+
+    #[test]
+    fn test_extend_lookup_table() {
+        let table = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let mut context = TestContext::new(&table);
+        let initialize_ix = create_lookup_table(&authority, &table, &authority, 1)
+            .pop()
+            .unwrap();
+        context.process(&initialize_ix).unwrap();
+
+        let addresses: Vec<_> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        context.clock.slot = 1;
+        assert_eq!(
+            context.process(&extend_lookup_table(&table, &authority, vec![])),
+            Err(InstructionError::InvalidInstructionData)
+        );
+        assert_eq!(
+            context.process(&extend_lookup_table(
+                &table,
+                &Pubkey::new_unique(),
+                addresses.clone()
+            )),
+            Err(InstructionError::IncorrectAuthority)
+        );
+        assert_eq!(
+            context.process(&with_signer(
+                extend_lookup_table(&table, &authority, addresses.clone()),
+                1,
+                false
+            )),
+            Err(InstructionError::MissingRequiredSignature)
+        );
+        assert_eq!(
+            context.process(&extend_lookup_table(
+                &table,
+                &authority,
+                addresses[..2].to_vec()
+            )),
+            Ok(())
+        );
+        context.clock.slot = 2;
+        assert_eq!(
+            context.process(&extend_lookup_table(
+                &table,
+                &authority,
+                addresses[2..].to_vec()
+            )),
+            Ok(())
+        );
+
+        let lookup_table = context.lookup_table(&table);
+        assert_eq!(lookup_table.addresses, addresses);
+        assert_eq!(lookup_table.meta.last_extended_slot, 2);
+        assert_eq!(lookup_table.meta.last_extended_slot_start_index, 2);
+        assert_eq!(
+            lookup_table.lookup(2, &[2], &context.slot_hashes),
+            Err(LookupError::InvalidLookupIndex)
+        );
+        assert_eq!(
+            lookup_table.lookup(3, &[2, 0], &context.slot_hashes),
+            Ok(vec![addresses[2], addresses[0]])
+        );
+
+        // the table cannot be extended past its capacity
+        let new_addresses = vec![Pubkey::new_unique(); LOOKUP_TABLE_MAX_ADDRESSES - 2];
+        assert_eq!(
+            context.process(&extend_lookup_table(&table, &authority, new_addresses)),
+            Err(InstructionError::InvalidInstructionData)
+        );
+    }
+
+    #[test]
+    fn test_freeze_lookup_table() {
+        let table = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let context = TestContext::new(&table);
+        let initialize_ix = create_lookup_table(&authority, &table, &authority, 1)
+            .pop()
+            .unwrap();
+        context.process(&initialize_ix).unwrap();
+
+        assert_eq!(
+            context.process(&freeze_lookup_table(&table, &authority)),
+            Err(InstructionError::InvalidInstructionData)
+        );
+        context
+            .process(&extend_lookup_table(
+                &table,
+                &authority,
+                vec![Pubkey::new_unique()],
+            ))
+            .unwrap();
+        assert_eq!(
+            context.process(&freeze_lookup_table(&table, &authority)),
+            Ok(())
+        );
+        assert_eq!(context.lookup_table(&table).meta.authority, None);
+        assert_eq!(
+            context.process(&extend_lookup_table(
+                &table,
+                &authority,
+                vec![Pubkey::new_unique()],
+            )),
+            Err(InstructionError::Immutable)
+        );
+        assert_eq!(
+            context.process(&deactivate_lookup_table(&table, &authority)),
+            Err(InstructionError::Immutable)
+        );
+    }
+
+    #[test]
+    fn test_deactivate_and_close_lookup_table() {
+        let table = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let recipient = Pubkey::new_unique();
+        let mut context = TestContext::new(&table);
+        context
+            .accounts
+            .insert(recipient, RefCell::new(AccountSharedData::default()));
+        let initialize_ix = create_lookup_table(&authority, &table, &authority, 1)
+            .pop()
+            .unwrap();
+        context.process(&initialize_ix).unwrap();
+
+        assert_eq!(
+            context.process(&close_lookup_table(&table, &authority, &recipient)),
+            Err(InstructionError::InvalidArgument)
+        );
+
+        context.clock.slot = 5;
+        assert_eq!(
+            context.process(&deactivate_lookup_table(&table, &authority)),
+            Ok(())
+        );
+        assert_eq!(context.lookup_table(&table).meta.deactivation_slot, 5);
+        assert_eq!(
+            context.process(&deactivate_lookup_table(&table, &authority)),
+            Err(InstructionError::InvalidArgument)
+        );
+        assert_eq!(
+            context.process(&extend_lookup_table(
+                &table,
+                &authority,
+                vec![Pubkey::new_unique()],
+            )),
+            Err(InstructionError::InvalidArgument)
+        );
+
+        // the deactivation slot is still recent
+        context.clock.slot = 6;
+        context.slot_hashes = SlotHashes::new(&[(5, Hash::default())]);
+        assert_eq!(
+            context.process(&close_lookup_table(&table, &authority, &recipient)),
+            Err(InstructionError::InvalidArgument)
+        );
+
+        context.slot_hashes = SlotHashes::default();
+        assert_eq!(
+            context.process(&close_lookup_table(&table, &table, &table)),
+            Err(InstructionError::InvalidArgument)
+        );
+        assert_eq!(
+            context.process(&close_lookup_table(&table, &authority, &recipient)),
+            Ok(())
+        );
+        assert_eq!(context.accounts[&recipient].borrow().lamports(), 100);
+        let closed_account = context.accounts[&table].borrow();
+        assert_eq!(closed_account.lamports(), 0);
+        assert!(closed_account.data().iter().all(|byte| *byte == 0));
+    }
+}
@@ -0,0 +1,285 @@
+//! Address lookup table account state
+
+use serde_derive::{Deserialize, Serialize};
+use solana_sdk::{
+    clock::Slot, instruction::InstructionError, pubkey::Pubkey, slot_hashes::SlotHashes,
+};
+use std::mem::size_of;
+
+/// The maximum number of addresses that a lookup table can hold
+pub const LOOKUP_TABLE_MAX_ADDRESSES: usize = 256;
+
+/// The serialized size of lookup table metadata
+pub const LOOKUP_TABLE_META_SIZE: usize = 56;
+
+/// The account data size of a lookup table which can hold the maximum number
+/// of addresses
+pub const LOOKUP_TABLE_MAX_SPACE: usize =
+    LOOKUP_TABLE_META_SIZE + LOOKUP_TABLE_MAX_ADDRESSES * size_of::<Pubkey>();
+
+/// Program account states
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub enum ProgramState {
+    /// Account is not initialized.
+    Uninitialized,
+    /// Initialized `LookupTable` account.
+    LookupTable(LookupTableMeta),
+}
+
+/// Address lookup table metadata
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct LookupTableMeta {
+    /// Lookup tables cannot be closed until the deactivation slot is no longer
+    /// "recent" (not accessible in the `SlotHashes` sysvar).
+    pub deactivation_slot: Slot,
+    /// The slot that the table was last extended. Address tables may only be
+    /// used to lookup addresses that were extended before the current slot.
+    pub last_extended_slot: Slot,
+    /// The start index where the table was last extended from during the
+    /// `last_extended_slot`.
+    pub last_extended_slot_start_index: u8,
+    /// Authority address which must sign for each modification. Frozen tables
+    /// have no authority.
+    pub authority: Option<Pubkey>,
+    /// Number of addresses stored in the table
+    pub num_addresses: u16,
+}
+
+impl LookupTableMeta {
+    pub fn new(authority: Pubkey) -> Self {
+        Self {
+            deactivation_slot: Slot::MAX,
+            last_extended_slot: 0,
+            last_extended_slot_start_index: 0,
+            authority: Some(authority),
+            num_addresses: 0,
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.deactivation_slot == Slot::MAX
+    }
+
+    /// Deactivated tables may still be used to look up addresses until the
+    /// deactivation slot is no longer recent, so that transactions which were
+    /// signed against the table before deactivation can still be processed.
+    pub fn is_usable(&self, current_slot: Slot, slot_hashes: &SlotHashes) -> bool {
+        self.is_active()
+            || self.deactivation_slot == current_slot
+            || slot_hashes.get(&self.deactivation_slot).is_some()
+    }
+
+    /// Number of addresses which can be looked up in the current slot.
+    /// Addresses appended during the current slot are not usable until the
+    /// next slot.
+    pub fn active_addresses_len(&self, current_slot: Slot) -> usize {
+        if current_slot > self.last_extended_slot {
+            usize::from(self.num_addresses)
+        } else {
+            usize::from(self.last_extended_slot_start_index)
+        }
+    }
+}
+
+/// Reasons an address lookup can fail
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum LookupError {
+    /// The lookup table is deactivated and no longer usable
+    LookupTableDeactivated,
+    /// An index is out of range of the active addresses
+    InvalidLookupIndex,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct AddressLookupTable {
+    pub meta: LookupTableMeta,
+    pub addresses: Vec<Pubkey>,
+}
+
+impl AddressLookupTable {
+    /// Maximum number of addresses that a lookup table account of the given
+    /// data length can hold
+    pub fn capacity(data_len: usize) -> usize {
+        (data_len.saturating_sub(LOOKUP_TABLE_META_SIZE) / size_of::<Pubkey>())
+            .min(LOOKUP_TABLE_MAX_ADDRESSES)
+    }
+
+    /// Deserialize the state of an initialized lookup table account
+    pub fn deserialize(data: &[u8]) -> Result<Self, InstructionError> {
+        let meta = match Self::deserialize_state(data)? {
+            ProgramState::LookupTable(meta) => meta,
+            ProgramState::Uninitialized => return Err(InstructionError::UninitializedAccount),
+        };
+
+        let num_addresses = usize::from(meta.num_addresses);
+        if num_addresses > Self::capacity(data.len()) {
+            return Err(InstructionError::InvalidAccountData);
+        }
+
+        let addresses = data[LOOKUP_TABLE_META_SIZE..]
+            .chunks_exact(size_of::<Pubkey>())
+            .take(num_addresses)
+            .map(Pubkey::new)
+            .collect();
+
+        Ok(Self { meta, addresses })
+    }
+
+    /// Deserialize the program state stored in the metadata section of a
+    /// lookup table account
+    pub fn deserialize_state(data: &[u8]) -> Result<ProgramState, InstructionError> {
+        if data.len() < LOOKUP_TABLE_META_SIZE {
+            return Err(InstructionError::InvalidAccountData);
+        }
+        bincode::deserialize(&data[..LOOKUP_TABLE_META_SIZE])
+            .map_err(|_| InstructionError::InvalidAccountData)
+    }
+
+    /// Overwrite the metadata section of a lookup table account
+    pub fn overwrite_meta_data(
+        data: &mut [u8],
+        meta: LookupTableMeta,
+    ) -> Result<(), InstructionError> {
+        if data.len() < LOOKUP_TABLE_META_SIZE {
+            return Err(InstructionError::InvalidAccountData);
+        }
+        let meta_data = &mut data[..LOOKUP_TABLE_META_SIZE];
+        meta_data.iter_mut().for_each(|byte| *byte = 0);
+        bincode::serialize_into(meta_data, &ProgramState::LookupTable(meta))
+            .map_err(|_| InstructionError::GenericError)
+    }
+
+    /// Look up the addresses at the given indexes
+    pub fn lookup(
+        &self,
+        current_slot: Slot,
+        indexes: &[u8],
+        slot_hashes: &SlotHashes,
+    ) -> Result<Vec<Pubkey>, LookupError> {
+        if !self.meta.is_usable(current_slot, slot_hashes) {
+            return Err(LookupError::LookupTableDeactivated);
+        }
+
+        let active_addresses_len = self
+            .meta
+            .active_addresses_len(current_slot)
+            .min(self.addresses.len());
+        let active_addresses = &self.addresses[..active_addresses_len];
+        indexes
+            .iter()
+            .map(|index| {
+                active_addresses
+                    .get(usize::from(*index))
+                    .copied()
+                    .ok_or(LookupError::InvalidLookupIndex)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::hash::Hash;
+
+    fn new_table(meta: LookupTableMeta, addresses: &[Pubkey]) -> Vec<u8> {
+        let mut data = vec![0; LOOKUP_TABLE_MAX_SPACE];
+        AddressLookupTable::overwrite_meta_data(&mut data, meta).unwrap();
+        for (i, address) in addresses.iter().enumerate() {
+            let offset = LOOKUP_TABLE_META_SIZE + i * size_of::<Pubkey>();
+            data[offset..offset + size_of::<Pubkey>()].copy_from_slice(address.as_ref());
+        }
+        data
+    }
+
+    #[test]
+    fn test_meta_size() {
+        let max_meta = ProgramState::LookupTable(LookupTableMeta {
+            deactivation_slot: Slot::MAX,
+            last_extended_slot: Slot::MAX,
+            last_extended_slot_start_index: u8::MAX,
+            authority: Some(Pubkey::new_unique()),
+            num_addresses: u16::MAX,
+        });
+        assert_eq!(
+            bincode::serialized_size(&max_meta).unwrap() as usize,
+            LOOKUP_TABLE_META_SIZE
+        );
+    }
+
+    #[test]
+    fn test_deserialize() {
+        assert_eq!(
+            AddressLookupTable::deserialize(&[0; LOOKUP_TABLE_META_SIZE - 1]),
+            Err(InstructionError::InvalidAccountData)
+        );
+        assert_eq!(
+            AddressLookupTable::deserialize(&[0; LOOKUP_TABLE_MAX_SPACE]),
+            Err(InstructionError::UninitializedAccount)
+        );
+
+        let addresses = vec![Pubkey::new_unique(), Pubkey::new_unique()];
+        let mut meta = LookupTableMeta::new(Pubkey::new_unique());
+        meta.num_addresses = addresses.len() as u16;
+        let data = new_table(meta.clone(), &addresses);
+        assert_eq!(
+            AddressLookupTable::deserialize(&data),
+            Ok(AddressLookupTable { meta, addresses })
+        );
+
+        // more addresses than the account can hold
+        assert_eq!(
+            AddressLookupTable::deserialize(&data[..LOOKUP_TABLE_META_SIZE]),
+            Err(InstructionError::InvalidAccountData)
+        );
+    }
+
+    #[test]
+    fn test_lookup() {
+        let addresses: Vec<_> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        let mut meta = LookupTableMeta::new(Pubkey::new_unique());
+        meta.num_addresses = 3;
+        meta.last_extended_slot = 10;
+        meta.last_extended_slot_start_index = 2;
+        let table = AddressLookupTable {
+            meta,
+            addresses: addresses.clone(),
+        };
+        let slot_hashes = SlotHashes::default();
+
+        // addresses extended in the current slot are not yet usable
+        assert_eq!(
+            table.lookup(10, &[1, 0], &slot_hashes),
+            Ok(vec![addresses[1], addresses[0]])
+        );
+        assert_eq!(
+            table.lookup(10, &[2], &slot_hashes),
+            Err(LookupError::InvalidLookupIndex)
+        );
+        assert_eq!(table.lookup(11, &[2], &slot_hashes), Ok(vec![addresses[2]]));
+        assert_eq!(
+            table.lookup(11, &[3], &slot_hashes),
+            Err(LookupError::InvalidLookupIndex)
+        );
+    }
+
+    #[test]
+    fn test_lookup_deactivated() {
+        let mut meta = LookupTableMeta::new(Pubkey::new_unique());
+        meta.num_addresses = 1;
+        meta.deactivation_slot = 5;
+        let table = AddressLookupTable {
+            meta,
+            addresses: vec![Pubkey::new_unique()],
+        };
+
+        assert!(table.lookup(5, &[0], &SlotHashes::default()).is_ok());
+        assert!(table
+            .lookup(6, &[0], &SlotHashes::new(&[(5, Hash::default())]))
+            .is_ok());
+        assert_eq!(
+            table.lookup(6, &[0], &SlotHashes::default()),
+            Err(LookupError::LookupTableDeactivated)
+        );
+    }
+}
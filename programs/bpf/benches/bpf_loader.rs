@@ -191,6 +191,64 @@ fn bench_program_execute_noop(bencher: &mut Bencher) {
     });
 }
 
+#[bench]
+fn bench_program_mem_syscalls(bencher: &mut Bencher) {
+    const DATA_LEN: usize = 1024 * 1024;
+    let loader_id = bpf_loader::id();
+    let mut invoke_context = MockInvokeContext::default();
+
+    let accounts = [RefCell::new(AccountSharedData::new(
+        1,
+        DATA_LEN,
+        &solana_sdk::pubkey::new_rand(),
+    ))];
+    let keys = [solana_sdk::pubkey::new_rand()];
+    let keyed_accounts: Vec<_> = keys
+        .iter()
+        .zip(&accounts)
+        .map(|(key, account)| solana_sdk::keyed_account::KeyedAccount::new(&key, false, &account))
+        .collect();
+    let mut serialized = serialize_parameters(
+        &bpf_loader::id(),
+        &solana_sdk::pubkey::new_rand(),
+        &keyed_accounts,
+        &[],
+    )
+    .unwrap();
+
+    let elf = load_elf("bench_mem").unwrap();
+    let mut executable =
+        Executable::<BpfError, ThisInstructionMeter>::from_elf(&elf, None, Config::default())
+            .unwrap();
+    executable.set_syscall_registry(register_syscalls(&mut invoke_context).unwrap());
+    let compute_meter = invoke_context.get_compute_meter();
+    let mut instruction_meter = ThisInstructionMeter { compute_meter };
+    let mut vm = create_vm(
+        &loader_id,
+        executable.as_ref(),
+        &mut serialized,
+        &[],
+        &mut invoke_context,
+    )
+    .unwrap();
+
+    assert_eq!(
+        SUCCESS,
+        vm.execute_program_interpreted(&mut instruction_meter)
+            .unwrap()
+    );
+    println!(
+        "Filled, copied, moved and compared {:?} bytes in {:?} instructions",
+        DATA_LEN,
+        vm.get_total_instruction_count(),
+    );
+
+    bencher.iter(|| {
+        vm.execute_program_interpreted(&mut instruction_meter)
+            .unwrap();
+    });
+}
+
 #[bench]
 fn bench_instruction_count_tuner(_bencher: &mut Bencher) {
     const BUDGET: u64 = 200_000;
@@ -60,6 +60,7 @@ fn main() {
         let rust_programs = [
             "128bit",
             "alloc",
+            "blake3",
             "call_depth",
             "caller_access",
             "custom_heap",
@@ -76,15 +77,19 @@ fn main() {
             "invoke_and_return",
             "invoked",
             "iter",
+            "keccak256",
             "many_args",
             "mem",
             "noop",
             "panic",
             "param_passing",
             "rand",
+            "realloc",
+            "realloc_invoke",
             "ristretto",
             "ro_modify",
             "sanity",
+            "secp256k1_recover",
             "sha256",
             "spoof1",
             "spoof1_system",
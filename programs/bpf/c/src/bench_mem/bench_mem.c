@@ -0,0 +1,28 @@
+/**
+ * @brief Memory operation benchmark program.  Fills, copies, moves and
+ * compares the data of the first account using the runtime's memory
+ * operation syscalls, used by the mem syscalls bench test.
+ */
+
+#define SOL_MEM_SYSCALLS
+#include <solana_sdk.h>
+
+#define NUM_KA 1
+
+extern uint64_t entrypoint(const uint8_t *input) {
+  SolAccountInfo ka[NUM_KA];
+  SolParameters params = (SolParameters){.ka = ka};
+  if (!sol_deserialize(input, &params, SOL_ARRAY_SIZE(ka))) {
+    return ERROR_INVALID_ARGUMENT;
+  }
+  uint8_t *data = ka[0].data;
+  int half = ka[0].data_len / 2;
+
+  sol_memset(data, 42, half);
+  sol_memcpy(data + half, data, half);
+  sol_memmove(data + 1, data, half);
+  if (0 != sol_memcmp(data, data + half, half)) {
+    return ERROR_INVALID_ARGUMENT;
+  }
+  return SUCCESS;
+}
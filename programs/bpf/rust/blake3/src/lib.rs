@@ -0,0 +1,38 @@
+//! @brief Blake3 Syscall test
+
+extern crate solana_program;
+use solana_program::{blake3, custom_panic_default, hash::Hash, msg};
+
+fn test_blake3() {
+    // Test vector from the BLAKE3 reference implementation
+    let empty = [
+        0xaf, 0x13, 0x49, 0xb9, 0xf5, 0xf9, 0xa1, 0xa6, 0xa0, 0x40, 0x4d, 0xea, 0x36, 0xdc, 0xc9,
+        0x49, 0x9b, 0xcb, 0x25, 0xc9, 0xad, 0xc1, 0x12, 0xb7, 0xcc, 0x9a, 0x93, 0xca, 0xe4, 0x1f,
+        0x32, 0x62,
+    ];
+    assert_eq!(blake3::hash(&[]), Hash::new_from_array(empty));
+
+    let vals = &["Gaggablaghblagh!".as_ref(), "flurbos".as_ref()];
+    assert_eq!(blake3::hashv(vals), blake3::hash(b"Gaggablaghblagh!flurbos"));
+}
+
+#[no_mangle]
+pub extern "C" fn entrypoint(_input: *mut u8) -> u64 {
+    msg!("blake3");
+
+    test_blake3();
+
+    0
+}
+
+custom_panic_default!();
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_blake3_native() {
+        test_blake3();
+    }
+}
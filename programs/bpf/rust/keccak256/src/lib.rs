@@ -0,0 +1,38 @@
+//! @brief Keccak256 Syscall test
+
+extern crate solana_program;
+use solana_program::{custom_panic_default, hash::Hash, keccak, msg};
+
+fn test_keccak256() {
+    // Ethereum's empty code hash
+    let empty = [
+        0xc5, 0xd2, 0x46, 0x01, 0x86, 0xf7, 0x23, 0x3c, 0x92, 0x7e, 0x7d, 0xb2, 0xdc, 0xc7, 0x03,
+        0xc0, 0xe5, 0x00, 0xb6, 0x53, 0xca, 0x82, 0x27, 0x3b, 0x7b, 0xfa, 0xd8, 0x04, 0x5d, 0x85,
+        0xa4, 0x70,
+    ];
+    assert_eq!(keccak::hash(&[]), Hash::new_from_array(empty));
+
+    let vals = &["Gaggablaghblagh!".as_ref(), "flurbos".as_ref()];
+    assert_eq!(keccak::hashv(vals), keccak::hash(b"Gaggablaghblagh!flurbos"));
+}
+
+#[no_mangle]
+pub extern "C" fn entrypoint(_input: *mut u8) -> u64 {
+    msg!("keccak256");
+
+    test_keccak256();
+
+    0
+}
+
+custom_panic_default!();
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_keccak256_native() {
+        test_keccak256();
+    }
+}
@@ -0,0 +1,47 @@
+//! @brief Example Rust-based BPF realloc test program
+
+use solana_program::{
+    account_info::AccountInfo, entrypoint, entrypoint::ProgramResult, msg,
+    program_error::ProgramError, pubkey::Pubkey,
+};
+use std::convert::TryInto;
+
+pub const REALLOC: u8 = 1;
+pub const REALLOC_EXTEND_AND_FILL: u8 = 2;
+
+entrypoint!(process_instruction);
+fn process_instruction(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let account = &accounts[0];
+    let new_len = instruction_data
+        .get(1..9)
+        .and_then(|bytes| bytes.try_into().ok())
+        .map(u64::from_le_bytes)
+        .ok_or(ProgramError::InvalidInstructionData)? as usize;
+
+    match instruction_data[0] {
+        REALLOC => {
+            msg!("realloc to {}", new_len);
+            account.realloc(new_len, false)?;
+            assert_eq!(new_len, account.data_len());
+        }
+        REALLOC_EXTEND_AND_FILL => {
+            let fill = instruction_data[9];
+            let pre_len = account.data_len();
+            msg!("realloc extend from {} to {} and fill", pre_len, new_len);
+            account.realloc(new_len, true)?;
+            assert_eq!(new_len, account.data_len());
+            let mut data = account.try_borrow_mut_data()?;
+            for byte in data[pre_len..].iter_mut() {
+                assert_eq!(*byte, 0);
+                *byte = fill;
+            }
+        }
+        _ => panic!(),
+    }
+
+    Ok(())
+}
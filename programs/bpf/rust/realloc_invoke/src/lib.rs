@@ -0,0 +1,107 @@
+//! @brief Example Rust-based BPF program that reallocs an account via CPI
+
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint,
+    entrypoint::{ProgramResult, MAX_PERMITTED_DATA_INCREASE},
+    instruction::{AccountMeta, Instruction},
+    msg,
+    program::invoke,
+    pubkey::Pubkey,
+};
+
+pub const INVOKE_REALLOC_EXTEND_AND_FILL: u8 = 1;
+pub const INVOKE_REALLOC_MAX_TWICE: u8 = 2;
+pub const INVOKE_REALLOC_AND_SHRINK: u8 = 3;
+
+// Instruction tags understood by the realloc program
+const REALLOC: u8 = 1;
+const REALLOC_EXTEND_AND_FILL: u8 = 2;
+
+fn realloc_instruction(
+    program_id: &Pubkey,
+    account: &Pubkey,
+    tag: u8,
+    new_len: usize,
+) -> Instruction {
+    let mut data = vec![tag];
+    data.extend_from_slice(&(new_len as u64).to_le_bytes());
+    data.push(1); // fill value
+    Instruction::new_with_bytes(*program_id, &data, vec![AccountMeta::new(*account, false)])
+}
+
+entrypoint!(process_instruction);
+fn process_instruction(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let account = &accounts[0];
+    let realloc_program_id = accounts[1].key;
+    let pre_len = account.data_len();
+
+    match instruction_data[0] {
+        INVOKE_REALLOC_EXTEND_AND_FILL => {
+            msg!("invoke realloc extend and fill");
+            let new_len = pre_len + MAX_PERMITTED_DATA_INCREASE;
+            invoke(
+                &realloc_instruction(
+                    realloc_program_id,
+                    account.key,
+                    REALLOC_EXTEND_AND_FILL,
+                    new_len,
+                ),
+                accounts,
+            )?;
+            // The callee's length change must be visible to the caller
+            assert_eq!(account.data_len(), new_len);
+            let data = account.try_borrow_data()?;
+            for byte in data[pre_len..].iter() {
+                assert_eq!(*byte, 1);
+            }
+        }
+        INVOKE_REALLOC_MAX_TWICE => {
+            msg!("invoke realloc max twice");
+            invoke(
+                &realloc_instruction(
+                    realloc_program_id,
+                    account.key,
+                    REALLOC,
+                    pre_len + MAX_PERMITTED_DATA_INCREASE,
+                ),
+                accounts,
+            )?;
+            // Growing past the limit across multiple invocations must fail
+            invoke(
+                &realloc_instruction(
+                    realloc_program_id,
+                    account.key,
+                    REALLOC,
+                    pre_len + MAX_PERMITTED_DATA_INCREASE + 1,
+                ),
+                accounts,
+            )?;
+            panic!("last invoke should fail");
+        }
+        INVOKE_REALLOC_AND_SHRINK => {
+            msg!("invoke realloc extend and shrink");
+            invoke(
+                &realloc_instruction(
+                    realloc_program_id,
+                    account.key,
+                    REALLOC_EXTEND_AND_FILL,
+                    pre_len + 1,
+                ),
+                accounts,
+            )?;
+            invoke(
+                &realloc_instruction(realloc_program_id, account.key, REALLOC, 0),
+                accounts,
+            )?;
+            assert_eq!(account.data_len(), 0);
+        }
+        _ => panic!(),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,53 @@
+//! @brief Secp256k1Recover Syscall test
+
+extern crate solana_program;
+use solana_program::{
+    custom_panic_default, keccak, msg,
+    secp256k1_recover::{secp256k1_recover, Secp256k1RecoverError},
+};
+
+fn test_secp256k1_recover() {
+    let expected: [u8; 64] = [
+        152, 156, 11, 118, 203, 86, 57, 113, 253, 201, 190, 243, 30, 192, 108, 53, 96, 243, 36,
+        157, 110, 233, 229, 216, 60, 87, 98, 85, 150, 224, 95, 111, 99, 31, 77, 5, 179, 174, 81,
+        135, 118, 238, 8, 117, 90, 119, 3, 230, 75, 46, 188, 50, 84, 117, 4, 222, 11, 85, 161, 66,
+        212, 236, 223, 128,
+    ];
+    let hash = keccak::hash(b"hello world");
+    let recovery_id: u8 = 1;
+    let signature: [u8; 64] = [
+        211, 241, 111, 34, 110, 132, 250, 162, 190, 188, 244, 49, 79, 9, 166, 248, 90, 15, 134,
+        16, 56, 198, 66, 66, 78, 117, 199, 0, 29, 104, 56, 129, 96, 156, 175, 181, 179, 52, 49,
+        239, 8, 3, 144, 59, 239, 241, 225, 18, 155, 225, 48, 59, 101, 254, 183, 49, 145, 86, 138,
+        74, 254, 201, 244, 129,
+    ];
+
+    let public_key = secp256k1_recover(hash.as_ref(), recovery_id, &signature).unwrap();
+    assert_eq!(public_key.to_bytes(), expected);
+
+    assert_eq!(
+        secp256k1_recover(hash.as_ref(), 4, &signature),
+        Err(Secp256k1RecoverError::InvalidRecoveryId)
+    );
+}
+
+#[no_mangle]
+pub extern "C" fn entrypoint(_input: *mut u8) -> u64 {
+    msg!("secp256k1_recover");
+
+    test_secp256k1_recover();
+
+    0
+}
+
+custom_panic_default!();
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_secp256k1_recover_native() {
+        test_secp256k1_recover();
+    }
+}
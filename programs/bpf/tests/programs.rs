@@ -27,7 +27,7 @@ use solana_sdk::{
     client::SyncClient,
     clock::MAX_PROCESSING_AGE,
     entrypoint::{MAX_PERMITTED_DATA_INCREASE, SUCCESS},
-    feature_set::ristretto_mul_syscall_enabled,
+    feature_set::{do_support_realloc, ristretto_mul_syscall_enabled},
     instruction::{AccountMeta, CompiledInstruction, Instruction, InstructionError},
     keyed_account::KeyedAccount,
     message::Message,
@@ -40,7 +40,8 @@ use solana_sdk::{
 };
 use solana_transaction_status::{
     token_balances::collect_token_balances, ConfirmedTransaction, InnerInstructions,
-    TransactionStatusMeta, TransactionWithStatusMeta, UiTransactionEncoding,
+    TransactionComputeUnits, TransactionStatusMeta, TransactionWithStatusMeta,
+    UiTransactionEncoding,
 };
 use std::{cell::RefCell, collections::HashMap, env, fs::File, io::Read, path::PathBuf, sync::Arc};
 
@@ -232,6 +233,7 @@ fn run_program(
             parameter_accounts,
             &parameter_bytes,
             true,
+            true,
         )
         .unwrap();
         if i == 1 {
@@ -270,14 +272,15 @@ fn process_transaction_and_record_inner(
     let signature = tx.signatures.get(0).unwrap().clone();
     let txs = vec![tx];
     let tx_batch = bank.prepare_batch(txs.iter());
-    let (mut results, _, mut inner, _transaction_logs) = bank.load_execute_and_commit_transactions(
-        &tx_batch,
-        MAX_PROCESSING_AGE,
-        false,
-        true,
-        false,
-        &mut ExecuteTimings::default(),
-    );
+    let (mut results, _, mut inner, _transaction_logs, _return_data, _compute_units) = bank
+        .load_execute_and_commit_transactions(
+            &tx_batch,
+            MAX_PROCESSING_AGE,
+            false,
+            true,
+            false,
+            &mut ExecuteTimings::default(),
+        );
     let inner_instructions = if inner.is_empty() {
         Some(vec![vec![]])
     } else {
@@ -309,6 +312,8 @@ fn execute_transactions(bank: &Bank, txs: &[Transaction]) -> Vec<ConfirmedTransa
         },
         mut inner_instructions,
         mut transaction_logs,
+        transaction_return_data,
+        transaction_compute_units,
     ) = bank.load_execute_and_commit_transactions(
         &batch,
         std::usize::MAX,
@@ -335,6 +340,8 @@ fn execute_transactions(bank: &Bank, txs: &[Transaction]) -> Vec<ConfirmedTransa
         tx_pre_token_balances.into_iter(),
         tx_post_token_balances.into_iter(),
         transaction_logs.into_iter(),
+        transaction_return_data.into_iter(),
+        transaction_compute_units.into_iter(),
     )
     .map(
         |(
@@ -346,6 +353,8 @@ fn execute_transactions(bank: &Bank, txs: &[Transaction]) -> Vec<ConfirmedTransa
             pre_token_balances,
             post_token_balances,
             log_messages,
+            return_data,
+            instruction_compute_units,
         )| {
             let fee_calculator = nonce_rollback
                 .map(|nonce_rollback| nonce_rollback.fee_calculator())
@@ -374,6 +383,8 @@ fn execute_transactions(bank: &Bank, txs: &[Transaction]) -> Vec<ConfirmedTransa
                 post_token_balances: Some(post_token_balances),
                 inner_instructions,
                 log_messages: Some(log_messages),
+                return_data,
+                compute_units: Some(TransactionComputeUnits::new(instruction_compute_units)),
             };
 
             ConfirmedTransaction {
@@ -425,10 +436,12 @@ fn test_program_bpf_sanity() {
         programs.extend_from_slice(&[
             ("solana_bpf_rust_128bit", true),
             ("solana_bpf_rust_alloc", true),
+            ("solana_bpf_rust_blake3", true),
             ("solana_bpf_rust_custom_heap", true),
             ("solana_bpf_rust_dep_crate", true),
             ("solana_bpf_rust_external_spend", false),
             ("solana_bpf_rust_iter", true),
+            ("solana_bpf_rust_keccak256", true),
             ("solana_bpf_rust_many_args", true),
             ("solana_bpf_rust_mem", true),
             ("solana_bpf_rust_noop", true),
@@ -437,6 +450,7 @@ fn test_program_bpf_sanity() {
             ("solana_bpf_rust_rand", true),
             ("solana_bpf_rust_ristretto", true),
             ("solana_bpf_rust_sanity", true),
+            ("solana_bpf_rust_secp256k1_recover", true),
             ("solana_bpf_rust_sha256", true),
         ]);
     }
@@ -2322,3 +2336,165 @@ fn test_program_bpf_finalize() {
         TransactionError::InstructionError(0, InstructionError::ProgramFailedToComplete)
     );
 }
+
+#[cfg(feature = "bpf_rust")]
+#[test]
+fn test_program_bpf_realloc() {
+    solana_logger::setup();
+
+    const REALLOC: u8 = 1;
+    const REALLOC_EXTEND_AND_FILL: u8 = 2;
+    const INVOKE_REALLOC_EXTEND_AND_FILL: u8 = 1;
+    const INVOKE_REALLOC_MAX_TWICE: u8 = 2;
+    const INVOKE_REALLOC_AND_SHRINK: u8 = 3;
+
+    fn realloc_data(tag: u8, new_len: usize) -> Vec<u8> {
+        let mut data = vec![tag];
+        data.extend_from_slice(&(new_len as u64).to_le_bytes());
+        data.push(1); // fill value
+        data
+    }
+
+    let GenesisConfigInfo {
+        genesis_config,
+        mint_keypair,
+        ..
+    } = create_genesis_config(50);
+    let mut bank = Bank::new(&genesis_config);
+    bank.deactivate_feature(&do_support_realloc::id());
+    let (name, id, entrypoint) = solana_bpf_loader_program!();
+    bank.add_builtin(&name, id, entrypoint);
+    let bank = Arc::new(bank);
+    let bank_client = BankClient::new_shared(&bank);
+
+    let realloc_program_id = load_bpf_program(
+        &bank_client,
+        &bpf_loader::id(),
+        &mint_keypair,
+        "solana_bpf_rust_realloc",
+    );
+    let realloc_invoke_program_id = load_bpf_program(
+        &bank_client,
+        &bpf_loader::id(),
+        &mint_keypair,
+        "solana_bpf_rust_realloc_invoke",
+    );
+
+    let pubkey = solana_sdk::pubkey::new_rand();
+    let account = AccountSharedData::new(42, 100, &realloc_program_id);
+    bank.store_account(&pubkey, &account);
+
+    // Realloc is rejected before the feature is activated
+    let instruction = Instruction::new_with_bytes(
+        realloc_program_id,
+        &realloc_data(REALLOC, 200),
+        vec![AccountMeta::new(pubkey, false)],
+    );
+    assert_eq!(
+        bank_client
+            .send_and_confirm_instruction(&mint_keypair, instruction)
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(0, InstructionError::AccountDataSizeChanged)
+    );
+
+    let mut bank = Bank::new_from_parent(&bank, &Pubkey::default(), 1);
+    bank.activate_feature(&do_support_realloc::id());
+    let bank = Arc::new(bank);
+    let bank_client = BankClient::new_shared(&bank);
+
+    // Grow
+    let instruction = Instruction::new_with_bytes(
+        realloc_program_id,
+        &realloc_data(REALLOC_EXTEND_AND_FILL, 100 + MAX_PERMITTED_DATA_INCREASE),
+        vec![AccountMeta::new(pubkey, false)],
+    );
+    assert!(bank_client
+        .send_and_confirm_instruction(&mint_keypair, instruction)
+        .is_ok());
+    let data = bank_client.get_account_data(&pubkey).unwrap().unwrap();
+    assert_eq!(data.len(), 100 + MAX_PERMITTED_DATA_INCREASE);
+    assert!(data[..100].iter().all(|byte| *byte == 0));
+    assert!(data[100..].iter().all(|byte| *byte == 1));
+
+    // Grow past the per-instruction limit
+    let instruction = Instruction::new_with_bytes(
+        realloc_program_id,
+        &realloc_data(REALLOC, 100 + 2 * MAX_PERMITTED_DATA_INCREASE + 1),
+        vec![AccountMeta::new(pubkey, false)],
+    );
+    assert_eq!(
+        bank_client
+            .send_and_confirm_instruction(&mint_keypair, instruction)
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidRealloc)
+    );
+
+    // Shrink
+    let instruction = Instruction::new_with_bytes(
+        realloc_program_id,
+        &realloc_data(REALLOC, 0),
+        vec![AccountMeta::new(pubkey, false)],
+    );
+    assert!(bank_client
+        .send_and_confirm_instruction(&mint_keypair, instruction)
+        .is_ok());
+    assert_eq!(
+        bank_client
+            .get_account_data(&pubkey)
+            .unwrap()
+            .unwrap()
+            .len(),
+        0
+    );
+
+    // Grow via CPI, the caller sees the new length
+    let account_metas = vec![
+        AccountMeta::new(pubkey, false),
+        AccountMeta::new_readonly(realloc_program_id, false),
+    ];
+    let instruction = Instruction::new_with_bytes(
+        realloc_invoke_program_id,
+        &[INVOKE_REALLOC_EXTEND_AND_FILL],
+        account_metas.clone(),
+    );
+    assert!(bank_client
+        .send_and_confirm_instruction(&mint_keypair, instruction)
+        .is_ok());
+    let data = bank_client.get_account_data(&pubkey).unwrap().unwrap();
+    assert_eq!(data.len(), MAX_PERMITTED_DATA_INCREASE);
+    assert!(data.iter().all(|byte| *byte == 1));
+
+    // Grow and then shrink via CPI
+    let instruction = Instruction::new_with_bytes(
+        realloc_invoke_program_id,
+        &[INVOKE_REALLOC_AND_SHRINK],
+        account_metas.clone(),
+    );
+    assert!(bank_client
+        .send_and_confirm_instruction(&mint_keypair, instruction)
+        .is_ok());
+    assert_eq!(
+        bank_client
+            .get_account_data(&pubkey)
+            .unwrap()
+            .unwrap()
+            .len(),
+        0
+    );
+
+    // The limit spans all invocations within an instruction
+    let instruction = Instruction::new_with_bytes(
+        realloc_invoke_program_id,
+        &[INVOKE_REALLOC_MAX_TWICE],
+        account_metas,
+    );
+    assert_eq!(
+        bank_client
+            .send_and_confirm_instruction(&mint_keypair, instruction)
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidRealloc)
+    );
+}
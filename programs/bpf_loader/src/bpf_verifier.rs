@@ -146,149 +146,177 @@ fn check_imm_register(insn: &ebpf::Insn, insn_ptr: usize) -> Result<(), Verifier
     Ok(())
 }
 
-#[rustfmt::skip]
 pub fn check(prog: &[u8]) -> Result<(), BpfError> {
     check_prog_len(prog)?;
 
     let mut insn_ptr: usize = 0;
     while insn_ptr * ebpf::INSN_SIZE < prog.len() {
-        let insn = ebpf::get_insn(prog, insn_ptr);
-        let mut store = false;
+        insn_ptr += check_insn(prog, insn_ptr)?;
+    }
+
+    // insn_ptr should now be equal to number of instructions.
+    if insn_ptr != prog.len() / ebpf::INSN_SIZE {
+        return Err(
+            VerifierError::JumpOutOfCode(adj_insn_ptr(insn_ptr), adj_insn_ptr(insn_ptr)).into(),
+        );
+    }
 
-        match insn.opc {
+    Ok(())
+}
 
-            // BPF_LD class
-            ebpf::LD_ABS_B   => {},
-            ebpf::LD_ABS_H   => {},
-            ebpf::LD_ABS_W   => {},
-            ebpf::LD_ABS_DW  => {},
-            ebpf::LD_IND_B   => {},
-            ebpf::LD_IND_H   => {},
-            ebpf::LD_IND_W   => {},
-            ebpf::LD_IND_DW  => {},
+/// Like `check()`, but continues past violations and returns all of them
+pub fn check_all(prog: &[u8]) -> Vec<BpfError> {
+    if let Err(err) = check_prog_len(prog) {
+        return vec![err];
+    }
 
-            ebpf::LD_DW_IMM  => {
-                store = true;
-                check_load_dw(prog, insn_ptr)?;
+    let mut errors = vec![];
+    let mut insn_ptr: usize = 0;
+    while insn_ptr * ebpf::INSN_SIZE < prog.len() {
+        match check_insn(prog, insn_ptr) {
+            Ok(insn_count) => insn_ptr += insn_count,
+            Err(err) => {
+                errors.push(err);
                 insn_ptr += 1;
-            },
+            }
+        }
+    }
+    errors
+}
 
-            // BPF_LDX class
-            ebpf::LD_B_REG   => {},
-            ebpf::LD_H_REG   => {},
-            ebpf::LD_W_REG   => {},
-            ebpf::LD_DW_REG  => {},
+/// Check the instruction at `insn_ptr`, returns the number of instruction slots it occupies
+#[rustfmt::skip]
+fn check_insn(prog: &[u8], insn_ptr: usize) -> Result<usize, BpfError> {
+    let insn = ebpf::get_insn(prog, insn_ptr);
+    let mut store = false;
+    let mut insn_count = 1;
 
-            // BPF_ST class
-            ebpf::ST_B_IMM   => store = true,
-            ebpf::ST_H_IMM   => store = true,
-            ebpf::ST_W_IMM   => store = true,
-            ebpf::ST_DW_IMM  => store = true,
+    match insn.opc {
 
-            // BPF_STX class
-            ebpf::ST_B_REG   => store = true,
-            ebpf::ST_H_REG   => store = true,
-            ebpf::ST_W_REG   => store = true,
-            ebpf::ST_DW_REG  => store = true,
+        // BPF_LD class
+        ebpf::LD_ABS_B   => {},
+        ebpf::LD_ABS_H   => {},
+        ebpf::LD_ABS_W   => {},
+        ebpf::LD_ABS_DW  => {},
+        ebpf::LD_IND_B   => {},
+        ebpf::LD_IND_H   => {},
+        ebpf::LD_IND_W   => {},
+        ebpf::LD_IND_DW  => {},
 
-            // BPF_ALU class
-            ebpf::ADD32_IMM  => {},
-            ebpf::ADD32_REG  => {},
-            ebpf::SUB32_IMM  => {},
-            ebpf::SUB32_REG  => {},
-            ebpf::MUL32_IMM  => {},
-            ebpf::MUL32_REG  => {},
-            ebpf::DIV32_IMM  => { check_imm_nonzero(&insn, insn_ptr)?; },
-            ebpf::DIV32_REG  => {},
-            ebpf::OR32_IMM   => {},
-            ebpf::OR32_REG   => {},
-            ebpf::AND32_IMM  => {},
-            ebpf::AND32_REG  => {},
-            ebpf::LSH32_IMM  => { check_imm_shift(&insn, insn_ptr)?; },
-            ebpf::LSH32_REG  => {},
-            ebpf::RSH32_IMM  => { check_imm_shift(&insn, insn_ptr)?; },
-            ebpf::RSH32_REG  => {},
-            ebpf::NEG32      => {},
-            ebpf::MOD32_IMM  => { check_imm_nonzero(&insn, insn_ptr)?; },
-            ebpf::MOD32_REG  => {},
-            ebpf::XOR32_IMM  => {},
-            ebpf::XOR32_REG  => {},
-            ebpf::MOV32_IMM  => {},
-            ebpf::MOV32_REG  => {},
-            ebpf::ARSH32_IMM => { check_imm_shift(&insn, insn_ptr)?; },
-            ebpf::ARSH32_REG => {},
-            ebpf::LE         => { check_imm_endian(&insn, insn_ptr)?; },
-            ebpf::BE         => { check_imm_endian(&insn, insn_ptr)?; },
+        ebpf::LD_DW_IMM  => {
+            store = true;
+            check_load_dw(prog, insn_ptr)?;
+            insn_count = 2;
+        },
 
-            // BPF_ALU64 class
-            ebpf::ADD64_IMM  => {},
-            ebpf::ADD64_REG  => {},
-            ebpf::SUB64_IMM  => {},
-            ebpf::SUB64_REG  => {},
-            ebpf::MUL64_IMM  => { check_imm_nonzero(&insn, insn_ptr)?; },
-            ebpf::MUL64_REG  => {},
-            ebpf::DIV64_IMM  => { check_imm_nonzero(&insn, insn_ptr)?; },
-            ebpf::DIV64_REG  => {},
-            ebpf::OR64_IMM   => {},
-            ebpf::OR64_REG   => {},
-            ebpf::AND64_IMM  => {},
-            ebpf::AND64_REG  => {},
-            ebpf::LSH64_IMM  => { check_imm_shift(&insn, insn_ptr)?; },
-            ebpf::LSH64_REG  => {},
-            ebpf::RSH64_IMM  => { check_imm_shift(&insn, insn_ptr)?; },
-            ebpf::RSH64_REG  => {},
-            ebpf::NEG64      => {},
-            ebpf::MOD64_IMM  => { check_imm_nonzero(&insn, insn_ptr)?; },
-            ebpf::MOD64_REG  => {},
-            ebpf::XOR64_IMM  => {},
-            ebpf::XOR64_REG  => {},
-            ebpf::MOV64_IMM  => {},
-            ebpf::MOV64_REG  => {},
-            ebpf::ARSH64_IMM => { check_imm_shift(&insn, insn_ptr)?; },
-            ebpf::ARSH64_REG => {},
+        // BPF_LDX class
+        ebpf::LD_B_REG   => {},
+        ebpf::LD_H_REG   => {},
+        ebpf::LD_W_REG   => {},
+        ebpf::LD_DW_REG  => {},
 
-            // BPF_JMP class
-            ebpf::JA         => { check_jmp_offset(prog, insn_ptr)?; },
-            ebpf::JEQ_IMM    => { check_jmp_offset(prog, insn_ptr)?; },
-            ebpf::JEQ_REG    => { check_jmp_offset(prog, insn_ptr)?; },
-            ebpf::JGT_IMM    => { check_jmp_offset(prog, insn_ptr)?; },
-            ebpf::JGT_REG    => { check_jmp_offset(prog, insn_ptr)?; },
-            ebpf::JGE_IMM    => { check_jmp_offset(prog, insn_ptr)?; },
-            ebpf::JGE_REG    => { check_jmp_offset(prog, insn_ptr)?; },
-            ebpf::JLT_IMM    => { check_jmp_offset(prog, insn_ptr)?; },
-            ebpf::JLT_REG    => { check_jmp_offset(prog, insn_ptr)?; },
-            ebpf::JLE_IMM    => { check_jmp_offset(prog, insn_ptr)?; },
-            ebpf::JLE_REG    => { check_jmp_offset(prog, insn_ptr)?; },
-            ebpf::JSET_IMM   => { check_jmp_offset(prog, insn_ptr)?; },
-            ebpf::JSET_REG   => { check_jmp_offset(prog, insn_ptr)?; },
-            ebpf::JNE_IMM    => { check_jmp_offset(prog, insn_ptr)?; },
-            ebpf::JNE_REG    => { check_jmp_offset(prog, insn_ptr)?; },
-            ebpf::JSGT_IMM   => { check_jmp_offset(prog, insn_ptr)?; },
-            ebpf::JSGT_REG   => { check_jmp_offset(prog, insn_ptr)?; },
-            ebpf::JSGE_IMM   => { check_jmp_offset(prog, insn_ptr)?; },
-            ebpf::JSGE_REG   => { check_jmp_offset(prog, insn_ptr)?; },
-            ebpf::JSLT_IMM   => { check_jmp_offset(prog, insn_ptr)?; },
-            ebpf::JSLT_REG   => { check_jmp_offset(prog, insn_ptr)?; },
-            ebpf::JSLE_IMM   => { check_jmp_offset(prog, insn_ptr)?; },
-            ebpf::JSLE_REG   => { check_jmp_offset(prog, insn_ptr)?; },
-            ebpf::CALL_IMM   => {},
-            ebpf::CALL_REG   => { check_imm_register(&insn, insn_ptr)?; },
-            ebpf::EXIT       => {},
+        // BPF_ST class
+        ebpf::ST_B_IMM   => store = true,
+        ebpf::ST_H_IMM   => store = true,
+        ebpf::ST_W_IMM   => store = true,
+        ebpf::ST_DW_IMM  => store = true,
 
-            _                => {
-                return Err(VerifierError::UnknownOpCode(insn.opc, adj_insn_ptr(insn_ptr)).into());
-            }
-        }
+        // BPF_STX class
+        ebpf::ST_B_REG   => store = true,
+        ebpf::ST_H_REG   => store = true,
+        ebpf::ST_W_REG   => store = true,
+        ebpf::ST_DW_REG  => store = true,
 
-        check_registers(&insn, store, insn_ptr)?;
+        // BPF_ALU class
+        ebpf::ADD32_IMM  => {},
+        ebpf::ADD32_REG  => {},
+        ebpf::SUB32_IMM  => {},
+        ebpf::SUB32_REG  => {},
+        ebpf::MUL32_IMM  => {},
+        ebpf::MUL32_REG  => {},
+        ebpf::DIV32_IMM  => { check_imm_nonzero(&insn, insn_ptr)?; },
+        ebpf::DIV32_REG  => {},
+        ebpf::OR32_IMM   => {},
+        ebpf::OR32_REG   => {},
+        ebpf::AND32_IMM  => {},
+        ebpf::AND32_REG  => {},
+        ebpf::LSH32_IMM  => { check_imm_shift(&insn, insn_ptr)?; },
+        ebpf::LSH32_REG  => {},
+        ebpf::RSH32_IMM  => { check_imm_shift(&insn, insn_ptr)?; },
+        ebpf::RSH32_REG  => {},
+        ebpf::NEG32      => {},
+        ebpf::MOD32_IMM  => { check_imm_nonzero(&insn, insn_ptr)?; },
+        ebpf::MOD32_REG  => {},
+        ebpf::XOR32_IMM  => {},
+        ebpf::XOR32_REG  => {},
+        ebpf::MOV32_IMM  => {},
+        ebpf::MOV32_REG  => {},
+        ebpf::ARSH32_IMM => { check_imm_shift(&insn, insn_ptr)?; },
+        ebpf::ARSH32_REG => {},
+        ebpf::LE         => { check_imm_endian(&insn, insn_ptr)?; },
+        ebpf::BE         => { check_imm_endian(&insn, insn_ptr)?; },
 
-        insn_ptr += 1;
-    }
+        // BPF_ALU64 class
+        ebpf::ADD64_IMM  => {},
+        ebpf::ADD64_REG  => {},
+        ebpf::SUB64_IMM  => {},
+        ebpf::SUB64_REG  => {},
+        ebpf::MUL64_IMM  => { check_imm_nonzero(&insn, insn_ptr)?; },
+        ebpf::MUL64_REG  => {},
+        ebpf::DIV64_IMM  => { check_imm_nonzero(&insn, insn_ptr)?; },
+        ebpf::DIV64_REG  => {},
+        ebpf::OR64_IMM   => {},
+        ebpf::OR64_REG   => {},
+        ebpf::AND64_IMM  => {},
+        ebpf::AND64_REG  => {},
+        ebpf::LSH64_IMM  => { check_imm_shift(&insn, insn_ptr)?; },
+        ebpf::LSH64_REG  => {},
+        ebpf::RSH64_IMM  => { check_imm_shift(&insn, insn_ptr)?; },
+        ebpf::RSH64_REG  => {},
+        ebpf::NEG64      => {},
+        ebpf::MOD64_IMM  => { check_imm_nonzero(&insn, insn_ptr)?; },
+        ebpf::MOD64_REG  => {},
+        ebpf::XOR64_IMM  => {},
+        ebpf::XOR64_REG  => {},
+        ebpf::MOV64_IMM  => {},
+        ebpf::MOV64_REG  => {},
+        ebpf::ARSH64_IMM => { check_imm_shift(&insn, insn_ptr)?; },
+        ebpf::ARSH64_REG => {},
 
-    // insn_ptr should now be equal to number of instructions.
-    if insn_ptr != prog.len() / ebpf::INSN_SIZE {
-        return Err(VerifierError::JumpOutOfCode(adj_insn_ptr(insn_ptr), adj_insn_ptr(insn_ptr)).into());
+        // BPF_JMP class
+        ebpf::JA         => { check_jmp_offset(prog, insn_ptr)?; },
+        ebpf::JEQ_IMM    => { check_jmp_offset(prog, insn_ptr)?; },
+        ebpf::JEQ_REG    => { check_jmp_offset(prog, insn_ptr)?; },
+        ebpf::JGT_IMM    => { check_jmp_offset(prog, insn_ptr)?; },
+        ebpf::JGT_REG    => { check_jmp_offset(prog, insn_ptr)?; },
+        ebpf::JGE_IMM    => { check_jmp_offset(prog, insn_ptr)?; },
+        ebpf::JGE_REG    => { check_jmp_offset(prog, insn_ptr)?; },
+        ebpf::JLT_IMM    => { check_jmp_offset(prog, insn_ptr)?; },
+        ebpf::JLT_REG    => { check_jmp_offset(prog, insn_ptr)?; },
+        ebpf::JLE_IMM    => { check_jmp_offset(prog, insn_ptr)?; },
+        ebpf::JLE_REG    => { check_jmp_offset(prog, insn_ptr)?; },
+        ebpf::JSET_IMM   => { check_jmp_offset(prog, insn_ptr)?; },
+        ebpf::JSET_REG   => { check_jmp_offset(prog, insn_ptr)?; },
+        ebpf::JNE_IMM    => { check_jmp_offset(prog, insn_ptr)?; },
+        ebpf::JNE_REG    => { check_jmp_offset(prog, insn_ptr)?; },
+        ebpf::JSGT_IMM   => { check_jmp_offset(prog, insn_ptr)?; },
+        ebpf::JSGT_REG   => { check_jmp_offset(prog, insn_ptr)?; },
+        ebpf::JSGE_IMM   => { check_jmp_offset(prog, insn_ptr)?; },
+        ebpf::JSGE_REG   => { check_jmp_offset(prog, insn_ptr)?; },
+        ebpf::JSLT_IMM   => { check_jmp_offset(prog, insn_ptr)?; },
+        ebpf::JSLT_REG   => { check_jmp_offset(prog, insn_ptr)?; },
+        ebpf::JSLE_IMM   => { check_jmp_offset(prog, insn_ptr)?; },
+        ebpf::JSLE_REG   => { check_jmp_offset(prog, insn_ptr)?; },
+        ebpf::CALL_IMM   => {},
+        ebpf::CALL_REG   => { check_imm_register(&insn, insn_ptr)?; },
+        ebpf::EXIT       => {},
+
+        _                => {
+            return Err(VerifierError::UnknownOpCode(insn.opc, adj_insn_ptr(insn_ptr)).into());
+        }
     }
 
-    Ok(())
+    check_registers(&insn, store, insn_ptr)?;
+
+    Ok(insn_count)
 }
@@ -0,0 +1,384 @@
+//! Offline verification of program ELFs, reporting every problem which would
+//! make deploying or executing the program fail
+
+use crate::{
+    bpf_verifier, profiler::FunctionSymbols, profiler::UNKNOWN_FUNCTION,
+    syscalls::register_syscalls_with_features, BpfError, ThisInstructionMeter,
+};
+use goblin::elf::{
+    reloc::R_X86_64_32,
+    section_header::{SectionHeader, SHT_NOBITS},
+    Elf,
+};
+use solana_rbpf::{
+    ebpf,
+    vm::{Config, Executable, SyscallRegistry},
+};
+use solana_sdk::{
+    bpf_loader_upgradeable::UpgradeableLoaderState, feature_set::FeatureSet,
+    process_instruction::BpfComputeBudget, system_instruction::MAX_PERMITTED_DATA_LENGTH,
+};
+use std::collections::BTreeMap;
+use thiserror::Error;
+
+/// Problems found in a program ELF
+#[derive(Debug, Error, PartialEq)]
+pub enum ElfVerificationError {
+    #[error("failed to parse ELF: {0}")]
+    InvalidElf(String),
+    #[error("program is {0} bytes, the largest deployable program is {1} bytes")]
+    ProgramTooLarge(usize, usize),
+    #[error("section {name} (ELF file offset {offset:#x}) of {size} bytes extends past the end of the file")]
+    SectionOutOfBounds {
+        name: String,
+        offset: usize,
+        size: usize,
+    },
+    #[error("section {name} (ELF file offset {offset:#x}) is not supported")]
+    UnsupportedSection { name: String, offset: usize },
+    #[error("failed to load ELF: {0}")]
+    LoadFailed(String),
+    #[error("{0}")]
+    Verifier(BpfError),
+    #[error("unresolved syscall {name} at instruction #{instruction} in {function}")]
+    UnresolvedSyscall {
+        name: String,
+        instruction: usize,
+        function: String,
+    },
+    #[error("stack frame of {function} overflows by {overflow} bytes at instruction #{instruction}, max {max}")]
+    StackFrameOverflow {
+        function: String,
+        instruction: usize,
+        overflow: usize,
+        max: usize,
+    },
+}
+
+/// Check a program ELF the way the BPF loader would on deployment and
+/// execution, with the syscalls available under `feature_set`.  Unlike the
+/// loader, every problem found is reported instead of only the first one
+pub fn verify_elf(elf_bytes: &[u8], feature_set: &FeatureSet) -> Vec<ElfVerificationError> {
+    let mut errors = vec![];
+
+    let max_program_len = MAX_PERMITTED_DATA_LENGTH as usize
+        - UpgradeableLoaderState::programdata_data_offset().unwrap();
+    if elf_bytes.len() > max_program_len {
+        errors.push(ElfVerificationError::ProgramTooLarge(
+            elf_bytes.len(),
+            max_program_len,
+        ));
+    }
+
+    let elf = match Elf::parse(elf_bytes) {
+        Ok(elf) => elf,
+        Err(err) => {
+            errors.push(ElfVerificationError::InvalidElf(err.to_string()));
+            return errors;
+        }
+    };
+    let section_errors = check_sections(elf_bytes, &elf);
+    let section_errors_found = !section_errors.is_empty();
+    errors.extend(section_errors);
+
+    let bpf_compute_budget = BpfComputeBudget::default();
+    let config = Config {
+        max_call_depth: bpf_compute_budget.max_call_depth,
+        stack_frame_size: bpf_compute_budget.stack_frame_size,
+        ..Config::default()
+    };
+    // The loader would already reject the section errors, only load if there
+    // are none to not report the first of them twice
+    let executable = if section_errors_found {
+        None
+    } else {
+        match Executable::<BpfError, ThisInstructionMeter>::from_elf(elf_bytes, None, config) {
+            Ok(executable) => Some(executable),
+            Err(err) => {
+                errors.push(ElfVerificationError::LoadFailed(err.to_string()));
+                None
+            }
+        }
+    };
+    let text_section = match find_section(&elf, ".text") {
+        Some(text_section) => text_section,
+        None => return errors,
+    };
+    // Relocations only patch immediates, so without a loaded executable the
+    // unrelocated text is just as good for checking instructions
+    let text_bytes = match executable
+        .as_ref()
+        .map(|executable| executable.get_text_bytes())
+    {
+        Some(Ok((_, text_bytes))) => text_bytes,
+        _ => match elf_bytes.get(text_section.file_range()) {
+            Some(text_bytes) => text_bytes,
+            None => return errors,
+        },
+    };
+
+    errors.extend(
+        bpf_verifier::check_all(text_bytes)
+            .into_iter()
+            .map(ElfVerificationError::Verifier),
+    );
+
+    let symbols = FunctionSymbols::from_elf(elf_bytes);
+    match register_syscalls_with_features(|feature_id| feature_set.is_active(feature_id)) {
+        Ok(syscall_registry) => errors.extend(check_syscalls(
+            &elf,
+            text_section,
+            &syscall_registry,
+            &symbols,
+        )),
+        Err(err) => errors.push(ElfVerificationError::LoadFailed(err.to_string())),
+    }
+    errors.extend(check_stack_frames(
+        text_bytes,
+        config.stack_frame_size,
+        &symbols,
+    ));
+    errors
+}
+
+fn find_section<'a>(elf: &'a Elf, name: &str) -> Option<&'a SectionHeader> {
+    elf.section_headers
+        .iter()
+        .find(|header| match elf.shdr_strtab.get(header.sh_name) {
+            Some(Ok(this_name)) => this_name == name,
+            _ => false,
+        })
+}
+
+fn section_name(elf: &Elf, header: &SectionHeader) -> String {
+    match elf.shdr_strtab.get(header.sh_name) {
+        Some(Ok(name)) => name.to_string(),
+        _ => format!("#{}", header.sh_name),
+    }
+}
+
+fn check_sections(elf_bytes: &[u8], elf: &Elf) -> Vec<ElfVerificationError> {
+    let mut errors = vec![];
+    for header in &elf.section_headers {
+        let name = section_name(elf, header);
+        let offset = header.sh_offset as usize;
+        if name == ".bss" {
+            errors.push(ElfVerificationError::UnsupportedSection { name, offset });
+        } else if header.sh_type != SHT_NOBITS
+            && header
+                .sh_offset
+                .checked_add(header.sh_size)
+                .map(|end| end > elf_bytes.len() as u64)
+                .unwrap_or(true)
+        {
+            errors.push(ElfVerificationError::SectionOutOfBounds {
+                name,
+                offset,
+                size: header.sh_size as usize,
+            });
+        }
+    }
+    errors
+}
+
+/// Report calls to symbols which are neither functions of the program nor
+/// registered syscalls
+fn check_syscalls(
+    elf: &Elf,
+    text_section: &SectionHeader,
+    syscall_registry: &SyscallRegistry,
+    symbols: &FunctionSymbols,
+) -> Vec<ElfVerificationError> {
+    let mut errors = vec![];
+    for relocation in elf.dynrels.iter() {
+        if relocation.r_type != R_X86_64_32 {
+            continue;
+        }
+        let symbol = match elf.dynsyms.get(relocation.r_sym) {
+            Some(symbol) => symbol,
+            None => continue,
+        };
+        if symbol.is_function() && symbol.st_value != 0 {
+            continue;
+        }
+        let name = match elf.dynstrtab.get(symbol.st_name) {
+            Some(Ok(name)) => name,
+            _ => continue,
+        };
+        if syscall_registry
+            .lookup_syscall(ebpf::hash_symbol_name(name.as_bytes()))
+            .is_some()
+        {
+            continue;
+        }
+        let insn_ptr =
+            (relocation.r_offset.saturating_sub(text_section.sh_offset)) as usize / ebpf::INSN_SIZE;
+        errors.push(ElfVerificationError::UnresolvedSyscall {
+            name: name.to_string(),
+            instruction: insn_ptr + ebpf::ELF_INSN_DUMP_OFFSET,
+            function: symbols
+                .lookup(insn_ptr)
+                .unwrap_or(UNKNOWN_FUNCTION)
+                .to_string(),
+        });
+    }
+    errors
+}
+
+/// Report the deepest stack access past the stack frame of each function
+fn check_stack_frames(
+    text_bytes: &[u8],
+    stack_frame_size: usize,
+    symbols: &FunctionSymbols,
+) -> Vec<ElfVerificationError> {
+    let mut overflows = BTreeMap::<&str, (usize, usize)>::new();
+    let mut insn_ptr = 0;
+    while (insn_ptr + 1) * ebpf::INSN_SIZE <= text_bytes.len() {
+        let insn = ebpf::get_insn(text_bytes, insn_ptr);
+        let base_register = match insn.opc {
+            ebpf::LD_B_REG | ebpf::LD_H_REG | ebpf::LD_W_REG | ebpf::LD_DW_REG => insn.src,
+            ebpf::ST_B_IMM
+            | ebpf::ST_H_IMM
+            | ebpf::ST_W_IMM
+            | ebpf::ST_DW_IMM
+            | ebpf::ST_B_REG
+            | ebpf::ST_H_REG
+            | ebpf::ST_W_REG
+            | ebpf::ST_DW_REG => insn.dst,
+            _ => 0,
+        };
+        let depth = -(insn.off as isize) as usize;
+        if base_register as usize == ebpf::STACK_REG && insn.off < 0 && depth > stack_frame_size {
+            let function = symbols.lookup(insn_ptr).unwrap_or(UNKNOWN_FUNCTION);
+            let overflow = depth - stack_frame_size;
+            let deepest = overflows.entry(function).or_insert((insn_ptr, overflow));
+            if overflow > deepest.1 {
+                *deepest = (insn_ptr, overflow);
+            }
+        }
+        insn_ptr += if insn.opc == ebpf::LD_DW_IMM { 2 } else { 1 };
+    }
+    overflows
+        .into_iter()
+        .map(
+            |(function, (insn_ptr, overflow))| ElfVerificationError::StackFrameOverflow {
+                function: function.to_string(),
+                instruction: insn_ptr + ebpf::ELF_INSN_DUMP_OFFSET,
+                overflow,
+                max: stack_frame_size,
+            },
+        )
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bpf_verifier::VerifierError;
+    use solana_rbpf::assembler::assemble;
+    use std::{fs::File, io::Read};
+
+    fn read_test_elf() -> Vec<u8> {
+        let mut file = File::open("test_elfs/noop_aligned.so").expect("file open failed");
+        let mut elf = Vec::new();
+        file.read_to_end(&mut elf).unwrap();
+        elf
+    }
+
+    #[test]
+    fn test_verify_elf() {
+        let elf = read_test_elf();
+        assert_eq!(verify_elf(&elf, &FeatureSet::all_enabled()), vec![]);
+
+        assert_eq!(verify_elf(&elf[..16], &FeatureSet::all_enabled()).len(), 1);
+
+        // Enlarge .text past the end of the file
+        let parsed = Elf::parse(&elf).unwrap();
+        let text_section_index = parsed
+            .section_headers
+            .iter()
+            .position(|header| matches!(parsed.shdr_strtab.get(header.sh_name), Some(Ok(".text"))))
+            .unwrap();
+        let text_offset = parsed.section_headers[text_section_index].sh_offset as usize;
+        let sh_size_offset = parsed.header.e_shoff as usize
+            + text_section_index * parsed.header.e_shentsize as usize
+            + 32;
+        let mut oversized_elf = elf.clone();
+        oversized_elf[sh_size_offset..sh_size_offset + 8]
+            .copy_from_slice(&(elf.len() as u64).to_le_bytes());
+        assert_eq!(
+            verify_elf(&oversized_elf, &FeatureSet::all_enabled())[0],
+            ElfVerificationError::SectionOutOfBounds {
+                name: ".text".to_string(),
+                offset: text_offset,
+                size: elf.len(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_verify_elf_unresolved_syscall() {
+        let elf = read_test_elf();
+        let parsed = Elf::parse(&elf).unwrap();
+        let text_section = find_section(&parsed, ".text").unwrap();
+        let symbols = FunctionSymbols::from_elf(&elf);
+
+        let syscall_registry = register_syscalls_with_features(|_| true).unwrap();
+        assert_eq!(
+            check_syscalls(&parsed, text_section, &syscall_registry, &symbols),
+            vec![]
+        );
+
+        let errors = check_syscalls(&parsed, text_section, &SyscallRegistry::default(), &symbols);
+        assert!(!errors.is_empty());
+        assert!(errors.iter().any(|error| matches!(
+            error,
+            ElfVerificationError::UnresolvedSyscall { name, function, .. }
+                if name == "sol_log_" && function == "entrypoint"
+        )));
+    }
+
+    #[test]
+    fn test_check_all_instructions() {
+        let text_bytes = assemble(
+            "
+            div64 r0, 0
+            mov64 r10, 1
+            exit",
+        )
+        .unwrap();
+        assert_eq!(
+            bpf_verifier::check_all(&text_bytes),
+            vec![
+                VerifierError::DivisionByZero(ebpf::ELF_INSN_DUMP_OFFSET).into(),
+                VerifierError::CannotWriteR10(ebpf::ELF_INSN_DUMP_OFFSET + 1).into(),
+            ]
+        );
+        assert_eq!(
+            bpf_verifier::check(&text_bytes),
+            Err(VerifierError::DivisionByZero(ebpf::ELF_INSN_DUMP_OFFSET).into())
+        );
+    }
+
+    #[test]
+    fn test_check_stack_frames() {
+        let text_bytes = assemble(
+            "
+            stxdw [r10-0x8], r1
+            stxdw [r10-0x1008], r1
+            ldxdw r2, [r10-0x1010]
+            stxdw [r1-0x2000], r1
+            exit",
+        )
+        .unwrap();
+        assert_eq!(
+            check_stack_frames(&text_bytes, 4096, &FunctionSymbols::default()),
+            vec![ElfVerificationError::StackFrameOverflow {
+                function: UNKNOWN_FUNCTION.to_string(),
+                instruction: ebpf::ELF_INSN_DUMP_OFFSET + 2,
+                overflow: 16,
+                max: 4096,
+            }]
+        );
+    }
+}
@@ -3,6 +3,8 @@ pub mod alloc;
 pub mod allocator_bump;
 pub mod bpf_verifier;
 pub mod deprecated;
+pub mod elf_verifier;
+pub mod profiler;
 pub mod serialization;
 pub mod syscalls;
 pub mod upgradeable;
@@ -11,6 +13,7 @@ pub mod with_jit;
 
 use crate::{
     bpf_verifier::VerifierError,
+    profiler::FunctionSymbols,
     serialization::{deserialize_parameters, serialize_parameters},
     syscalls::SyscallError,
 };
@@ -30,7 +33,10 @@ use solana_sdk::{
     bpf_loader_upgradeable::{self, UpgradeableLoaderState},
     clock::Clock,
     entrypoint::SUCCESS,
-    feature_set::{skip_ro_deserialization, upgradeable_close_instruction},
+    feature_set::{
+        do_support_realloc, skip_ro_deserialization, upgradeable_close_instruction,
+        upgradeable_extend_program_data,
+    },
     ic_logger_msg, ic_msg,
     instruction::InstructionError,
     keyed_account::{from_keyed_account, next_keyed_account, KeyedAccount},
@@ -74,6 +80,19 @@ pub fn create_and_cache_executor(
     data: &[u8],
     invoke_context: &mut dyn InvokeContext,
     use_jit: bool,
+) -> Result<Arc<BpfExecutor>, InstructionError> {
+    let executor = create_executor(data, invoke_context, use_jit, false)?;
+    invoke_context.add_executor(key, executor.clone());
+    Ok(executor)
+}
+
+/// Create an executor for the program ELF in `data`, which records an
+/// instruction trace of every invocation if `enable_tracing` is set
+pub fn create_executor(
+    data: &[u8],
+    invoke_context: &mut dyn InvokeContext,
+    use_jit: bool,
+    enable_tracing: bool,
 ) -> Result<Arc<BpfExecutor>, InstructionError> {
     let bpf_compute_budget = invoke_context.get_bpf_compute_budget();
     let mut program = Executable::<BpfError, ThisInstructionMeter>::from_elf(
@@ -83,7 +102,7 @@ pub fn create_and_cache_executor(
             max_call_depth: bpf_compute_budget.max_call_depth,
             stack_frame_size: bpf_compute_budget.stack_frame_size,
             enable_instruction_meter: true,
-            enable_instruction_tracing: log_enabled!(Trace),
+            enable_instruction_tracing: enable_tracing || log_enabled!(Trace),
         },
     )
     .map_err(|e| map_ebpf_error(invoke_context, e))?;
@@ -103,9 +122,12 @@ pub fn create_and_cache_executor(
             return Err(InstructionError::ProgramFailedToCompile);
         }
     }
-    let executor = Arc::new(BpfExecutor { program });
-    invoke_context.add_executor(key, executor.clone());
-    Ok(executor)
+    let symbols = if enable_tracing {
+        Some(FunctionSymbols::from_elf(data))
+    } else {
+        None
+    };
+    Ok(Arc::new(BpfExecutor { program, symbols }))
 }
 
 fn write_program_data(
@@ -240,14 +262,25 @@ fn process_instruction_common(
             return Err(InstructionError::IncorrectProgramId);
         }
 
-        let executor = match invoke_context.get_executor(program_id) {
-            Some(executor) => executor,
-            None => create_and_cache_executor(
-                program_id,
+        let enable_tracing = invoke_context.is_program_tracing_enabled();
+        let executor: Arc<dyn Executor> = if enable_tracing {
+            // Traced programs run in the interpreter and bypass the executor cache
+            create_executor(
                 &program.try_account_ref()?.data()[offset..],
                 invoke_context,
-                use_jit,
-            )?,
+                false,
+                true,
+            )?
+        } else {
+            match invoke_context.get_executor(program_id) {
+                Some(executor) => executor,
+                None => create_and_cache_executor(
+                    program_id,
+                    &program.try_account_ref()?.data()[offset..],
+                    invoke_context,
+                    use_jit,
+                )?,
+            }
         };
         executor.execute(
             loader_id,
@@ -255,7 +288,7 @@ fn process_instruction_common(
             keyed_accounts,
             instruction_data,
             invoke_context,
-            use_jit,
+            use_jit && !enable_tracing,
         )?
     } else {
         if !check_loader_id(program_id) {
@@ -677,6 +710,99 @@ fn process_loader_upgradeable_instruction(
 
             ic_logger_msg!(logger, "Closed {}", close_account.unsigned_key());
         }
+        UpgradeableLoaderInstruction::ExtendProgramData { additional_bytes } => {
+            if !invoke_context.is_feature_active(&upgradeable_extend_program_data::id()) {
+                return Err(InstructionError::InvalidInstructionData);
+            }
+            let programdata = next_keyed_account(account_iter)?;
+            let authority = next_keyed_account(account_iter)?;
+            let payer = next_keyed_account(account_iter)?;
+            let rent = from_keyed_account::<Rent>(next_keyed_account(account_iter)?)?;
+            let system = next_keyed_account(account_iter)?;
+
+            if additional_bytes == 0 {
+                ic_logger_msg!(logger, "Additional bytes must be greater than 0");
+                return Err(InstructionError::InvalidInstructionData);
+            }
+
+            // Verify ProgramData account
+
+            if &programdata.owner()? != program_id {
+                ic_logger_msg!(logger, "ProgramData account not owned by loader");
+                return Err(InstructionError::InvalidAccountOwner);
+            }
+            if !programdata.is_writable() {
+                ic_logger_msg!(logger, "ProgramData account not writeable");
+                return Err(InstructionError::InvalidArgument);
+            }
+            if let UpgradeableLoaderState::ProgramData {
+                slot: _,
+                upgrade_authority_address,
+            } = programdata.state()?
+            {
+                if upgrade_authority_address.is_none() {
+                    ic_logger_msg!(logger, "Program not upgradeable");
+                    return Err(InstructionError::Immutable);
+                }
+                if upgrade_authority_address != Some(*authority.unsigned_key()) {
+                    ic_logger_msg!(logger, "Incorrect upgrade authority provided");
+                    return Err(InstructionError::IncorrectAuthority);
+                }
+                if authority.signer_key().is_none() {
+                    ic_logger_msg!(logger, "Upgrade authority did not sign");
+                    return Err(InstructionError::MissingRequiredSignature);
+                }
+            } else {
+                ic_logger_msg!(logger, "Invalid ProgramData account");
+                return Err(InstructionError::InvalidAccountData);
+            }
+
+            let new_len = programdata
+                .data_len()?
+                .saturating_add(additional_bytes as usize);
+            if new_len > MAX_PERMITTED_DATA_LENGTH as usize {
+                ic_logger_msg!(
+                    logger,
+                    "Extended ProgramData length of {} bytes exceeds max permitted length of {} bytes",
+                    new_len,
+                    MAX_PERMITTED_DATA_LENGTH
+                );
+                return Err(InstructionError::InvalidRealloc);
+            }
+
+            // Fund the ProgramData account to rent-exemption at its new size
+
+            let required_lamports = rent
+                .minimum_balance(new_len)
+                .max(1)
+                .saturating_sub(programdata.lamports()?);
+            if required_lamports > 0 {
+                MessageProcessor::native_invoke(
+                    invoke_context,
+                    system_instruction::transfer(
+                        payer.unsigned_key(),
+                        programdata.unsigned_key(),
+                        required_lamports,
+                    ),
+                    &[payer, programdata, system],
+                    &[],
+                )?;
+            }
+
+            // Grow the ProgramData account, the new bytes are zeroed
+
+            let mut account = programdata.try_account_ref_mut()?;
+            let mut data = account.data().to_vec();
+            data.resize(new_len, 0);
+            account.set_data(data);
+
+            ic_logger_msg!(
+                logger,
+                "Extended ProgramData account {} by {} bytes",
+                programdata.unsigned_key(),
+                additional_bytes
+            );
+        }
     }
 
     Ok(())
@@ -759,6 +885,9 @@ impl InstructionMeter for ThisInstructionMeter {
 /// BPF Loader's Executor implementation
 pub struct BpfExecutor {
     program: Box<dyn Executable<BpfError, ThisInstructionMeter>>,
+    /// Function symbols of the program, only loaded by executors which record
+    /// instruction traces
+    symbols: Option<FunctionSymbols>,
 }
 
 // Well, implement Debug for solana_rbpf::vm::Executable in solana-rbpf...
@@ -831,6 +960,25 @@ impl Executor for BpfExecutor {
                     .unwrap();
                 trace!("BPF Program Instruction Trace:\n{}", trace_buffer);
             }
+            let program_trace = self.symbols.as_ref().map(|symbols| {
+                let text_bytes = self
+                    .program
+                    .get_text_bytes()
+                    .map(|(_, text_bytes)| text_bytes)
+                    .unwrap_or_default();
+                profiler::trace_program(
+                    program_id,
+                    invoke_depth,
+                    text_bytes,
+                    self.program.get_syscall_registry(),
+                    symbols,
+                    vm.get_tracer(),
+                )
+            });
+            drop(vm);
+            if let Some(program_trace) = program_trace {
+                invoke_context.record_program_trace(program_trace);
+            }
             match result {
                 Ok(status) => {
                     if status != SUCCESS {
@@ -861,6 +1009,7 @@ impl Executor for BpfExecutor {
             parameter_accounts,
             &parameter_bytes,
             invoke_context.is_feature_active(&skip_ro_deserialization::id()),
+            invoke_context.is_feature_active(&do_support_realloc::id()),
         )?;
         deserialize_time.stop();
         invoke_context.update_timing(
@@ -878,7 +1027,7 @@ impl Executor for BpfExecutor {
 mod tests {
     use super::*;
     use rand::Rng;
-    use solana_runtime::{bank::Bank, bank_client::BankClient};
+    use solana_runtime::{bank::Bank, bank_client::BankClient, program_trace::ProgramTraceFilter};
     use solana_sdk::{
         account::{
             create_account_shared_data_for_test as create_account_for_test, AccountSharedData,
@@ -1151,6 +1300,63 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_bpf_loader_program_trace() {
+        let (genesis_config, mint_keypair) = create_genesis_config(1_000_000_000);
+        let mut bank = Bank::new(&genesis_config);
+        bank.add_builtin(
+            "solana_bpf_loader_program",
+            bpf_loader::id(),
+            process_instruction,
+        );
+        let program_id = solana_sdk::pubkey::new_rand();
+        let mut file = File::open("test_elfs/noop_aligned.so").expect("file open failed");
+        let mut elf = Vec::new();
+        file.read_to_end(&mut elf).unwrap();
+        let mut program_account = AccountSharedData::new(1, 0, &bpf_loader::id());
+        program_account.set_data(elf);
+        program_account.executable = true;
+        bank.store_account(&program_id, &program_account);
+        let bank = Arc::new(bank);
+        let bank_client = BankClient::new_shared(&bank);
+
+        // Tracing disabled
+        let instruction = Instruction::new_with_bytes(program_id, &[0], vec![]);
+        bank_client
+            .send_and_confirm_instruction(&mint_keypair, instruction)
+            .unwrap();
+        assert!(bank
+            .program_trace_collector
+            .read()
+            .unwrap()
+            .traces
+            .is_empty());
+
+        // Tracing enabled
+        bank.program_trace_collector.write().unwrap().filter = ProgramTraceFilter::All;
+        let instruction = Instruction::new_with_bytes(program_id, &[1], vec![]);
+        let signature = bank_client
+            .send_and_confirm_instruction(&mint_keypair, instruction)
+            .unwrap();
+        let traces = std::mem::take(&mut bank.program_trace_collector.write().unwrap().traces);
+        assert_eq!(traces.len(), 1);
+        assert_eq!(traces[0].signature, signature);
+        assert_eq!(traces[0].traces.len(), 1);
+        let trace = &traces[0].traces[0];
+        assert_eq!(trace.program_id, program_id);
+        assert_eq!(trace.invoke_depth, 1);
+        assert!(!trace.instructions.is_empty());
+        assert!(trace.instructions[0].contains(" entrypoint: "));
+        assert_eq!(
+            trace
+                .folded_stacks
+                .iter()
+                .map(|(_, units)| units)
+                .sum::<u64>(),
+            trace.instructions.len() as u64
+        );
+    }
+
     #[test]
     fn test_bpf_loader_serialize_unaligned() {
         let program_id = bpf_loader_deprecated::id();
@@ -3313,6 +3519,165 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_bpf_loader_upgradeable_extend_program_data() {
+        let (genesis_config, mint_keypair) = create_genesis_config(1_000_000_000);
+        let mut bank = Bank::new(&genesis_config);
+        bank.feature_set = Arc::new(FeatureSet::all_enabled());
+        bank.add_builtin(
+            "solana_bpf_loader_upgradeable_program",
+            bpf_loader_upgradeable::id(),
+            process_instruction,
+        );
+        let bank = Arc::new(bank);
+        let bank_client = BankClient::new_shared(&bank);
+
+        let program_address = Pubkey::new_unique();
+        let (programdata_address, _) = Pubkey::find_program_address(
+            &[program_address.as_ref()],
+            &bpf_loader_upgradeable::id(),
+        );
+        let upgrade_authority_keypair = Keypair::new();
+        let programdata_len = UpgradeableLoaderState::programdata_len(100).unwrap();
+        let mut programdata_account = AccountSharedData::new(
+            bank.get_minimum_balance_for_rent_exemption(programdata_len),
+            programdata_len,
+            &bpf_loader_upgradeable::id(),
+        );
+        programdata_account
+            .set_state(&UpgradeableLoaderState::ProgramData {
+                slot: 0,
+                upgrade_authority_address: Some(upgrade_authority_keypair.pubkey()),
+            })
+            .unwrap();
+        let programdata_data_offset = UpgradeableLoaderState::programdata_data_offset().unwrap();
+        programdata_account.data_as_mut_slice()[programdata_data_offset..].fill(42);
+        bank.store_account(&programdata_address, &programdata_account);
+
+        // Case: extend, the payer funds rent-exemption at the new size
+        let before = bank.get_balance(&mint_keypair.pubkey());
+        let message = Message::new(
+            &[bpf_loader_upgradeable::extend_program_data(
+                &program_address,
+                &upgrade_authority_keypair.pubkey(),
+                &mint_keypair.pubkey(),
+                1024,
+            )],
+            Some(&mint_keypair.pubkey()),
+        );
+        bank_client
+            .send_and_confirm_message(&[&mint_keypair, &upgrade_authority_keypair], message)
+            .unwrap();
+        let account = bank.get_account(&programdata_address).unwrap();
+        assert_eq!(account.data().len(), programdata_len + 1024);
+        assert_eq!(
+            account.lamports(),
+            bank.get_minimum_balance_for_rent_exemption(programdata_len + 1024)
+        );
+        assert_eq!(
+            bank.get_balance(&mint_keypair.pubkey()),
+            before - (account.lamports() - programdata_account.lamports())
+        );
+        assert_eq!(
+            account.state(),
+            Ok(UpgradeableLoaderState::ProgramData {
+                slot: 0,
+                upgrade_authority_address: Some(upgrade_authority_keypair.pubkey()),
+            })
+        );
+        assert!(account.data()[programdata_data_offset..programdata_len]
+            .iter()
+            .all(|&value| value == 42));
+        assert!(account.data()[programdata_len..]
+            .iter()
+            .all(|&value| value == 0));
+
+        // Case: wrong authority
+        bank.clear_signatures();
+        let wrong_authority_keypair = Keypair::new();
+        let message = Message::new(
+            &[bpf_loader_upgradeable::extend_program_data(
+                &program_address,
+                &wrong_authority_keypair.pubkey(),
+                &mint_keypair.pubkey(),
+                1024,
+            )],
+            Some(&mint_keypair.pubkey()),
+        );
+        assert_eq!(
+            TransactionError::InstructionError(0, InstructionError::IncorrectAuthority),
+            bank_client
+                .send_and_confirm_message(&[&mint_keypair, &wrong_authority_keypair], message)
+                .unwrap_err()
+                .unwrap()
+        );
+
+        // Case: zero additional bytes
+        bank.clear_signatures();
+        let message = Message::new(
+            &[bpf_loader_upgradeable::extend_program_data(
+                &program_address,
+                &upgrade_authority_keypair.pubkey(),
+                &mint_keypair.pubkey(),
+                0,
+            )],
+            Some(&mint_keypair.pubkey()),
+        );
+        assert_eq!(
+            TransactionError::InstructionError(0, InstructionError::InvalidInstructionData),
+            bank_client
+                .send_and_confirm_message(&[&mint_keypair, &upgrade_authority_keypair], message)
+                .unwrap_err()
+                .unwrap()
+        );
+
+        // Case: too large
+        bank.clear_signatures();
+        let message = Message::new(
+            &[bpf_loader_upgradeable::extend_program_data(
+                &program_address,
+                &upgrade_authority_keypair.pubkey(),
+                &mint_keypair.pubkey(),
+                MAX_PERMITTED_DATA_LENGTH as u32,
+            )],
+            Some(&mint_keypair.pubkey()),
+        );
+        assert_eq!(
+            TransactionError::InstructionError(0, InstructionError::InvalidRealloc),
+            bank_client
+                .send_and_confirm_message(&[&mint_keypair, &upgrade_authority_keypair], message)
+                .unwrap_err()
+                .unwrap()
+        );
+
+        // Case: program is not upgradeable
+        let mut account = bank.get_account(&programdata_address).unwrap();
+        account
+            .set_state(&UpgradeableLoaderState::ProgramData {
+                slot: 0,
+                upgrade_authority_address: None,
+            })
+            .unwrap();
+        bank.store_account(&programdata_address, &account);
+        bank.clear_signatures();
+        let message = Message::new(
+            &[bpf_loader_upgradeable::extend_program_data(
+                &program_address,
+                &upgrade_authority_keypair.pubkey(),
+                &mint_keypair.pubkey(),
+                1024,
+            )],
+            Some(&mint_keypair.pubkey()),
+        );
+        assert_eq!(
+            TransactionError::InstructionError(0, InstructionError::Immutable),
+            bank_client
+                .send_and_confirm_message(&[&mint_keypair, &upgrade_authority_keypair], message)
+                .unwrap_err()
+                .unwrap()
+        );
+    }
+
     /// fuzzing utility function
     fn fuzz<F>(
         bytes: &[u8],
@@ -0,0 +1,227 @@
+//! Symbolization of BPF instruction traces and per-function compute unit profiles
+
+use goblin::elf::{sym::STT_FUNC, Elf};
+use solana_rbpf::{
+    disassembler, ebpf,
+    vm::{SyscallRegistry, Tracer},
+};
+use solana_sdk::{process_instruction::ProgramTrace, pubkey::Pubkey};
+use std::collections::BTreeMap;
+
+/// Frame name of instructions outside of any known function
+pub const UNKNOWN_FUNCTION: &str = "[unknown]";
+
+/// The functions of a program's text section
+#[derive(Debug, Default, PartialEq)]
+pub struct FunctionSymbols {
+    /// First instruction, instruction count and demangled name of each
+    /// function, sorted by first instruction
+    functions: Vec<(usize, usize, String)>,
+}
+
+impl FunctionSymbols {
+    /// Read the function symbols of an ELF from its symbol table, or only the
+    /// exported ones from its dynamic symbol table if it was stripped
+    pub fn from_elf(elf_bytes: &[u8]) -> Self {
+        let mut functions = vec![];
+        if let Ok(elf) = Elf::parse(elf_bytes) {
+            let text_section = elf
+                .section_headers
+                .iter()
+                .find(|header| matches!(elf.shdr_strtab.get(header.sh_name), Some(Ok(".text"))));
+            if let Some(text_section) = text_section {
+                let text_range = text_section.vm_range();
+                let symbols = elf
+                    .syms
+                    .iter()
+                    .map(|symbol| (symbol, &elf.strtab))
+                    .chain(elf.dynsyms.iter().map(|symbol| (symbol, &elf.dynstrtab)));
+                for (symbol, strtab) in symbols {
+                    if symbol.st_type() != STT_FUNC
+                        || !text_range.contains(&(symbol.st_value as usize))
+                    {
+                        continue;
+                    }
+                    if let Some(Ok(name)) = strtab.get(symbol.st_name) {
+                        functions.push((
+                            (symbol.st_value as usize - text_range.start) / ebpf::INSN_SIZE,
+                            (symbol.st_size as usize / ebpf::INSN_SIZE).max(1),
+                            format!("{:#}", rustc_demangle::demangle(name)),
+                        ));
+                    }
+                }
+            }
+        }
+        functions.sort();
+        functions.dedup();
+        Self { functions }
+    }
+
+    /// Name of the function containing the instruction at `pc`
+    pub fn lookup(&self, pc: usize) -> Option<&str> {
+        let index = match self
+            .functions
+            .binary_search_by_key(&pc, |(start, _, _)| *start)
+        {
+            Ok(index) => index,
+            Err(0) => return None,
+            Err(index) => index - 1,
+        };
+        let (start, len, name) = &self.functions[index];
+        if pc < start + len {
+            Some(name)
+        } else {
+            None
+        }
+    }
+}
+
+/// Symbolize the instruction trace of a program invocation and fold it into
+/// the compute units consumed per call stack.
+///
+/// Every executed BPF instruction is counted as one compute unit, the cost of
+/// syscalls is not attributed to any stack.
+pub fn trace_program(
+    program_id: &Pubkey,
+    invoke_depth: usize,
+    text_bytes: &[u8],
+    syscall_registry: &SyscallRegistry,
+    symbols: &FunctionSymbols,
+    tracer: &Tracer,
+) -> ProgramTrace {
+    let disassembled = disassembler::to_insn_vec(text_bytes);
+    let mut pc_to_instruction_index = vec![0usize; text_bytes.len() / ebpf::INSN_SIZE];
+    for (index, insn) in disassembled.iter().enumerate() {
+        pc_to_instruction_index[insn.ptr] = index;
+        // The second half of lddw belongs to the same instruction
+        if let Some(next) = pc_to_instruction_index.get_mut(insn.ptr + 1) {
+            *next = index;
+        }
+    }
+
+    let mut instructions = Vec::with_capacity(tracer.log.len());
+    let mut folded_stacks = BTreeMap::<String, u64>::new();
+    let mut stack: Vec<&str> = vec![];
+    let mut folded_stack = String::new();
+    let mut enter_function = true;
+    for (index, entry) in tracer.log.iter().enumerate() {
+        let pc = entry[11] as usize;
+        let function = symbols.lookup(pc).unwrap_or(UNKNOWN_FUNCTION);
+        let insn = match pc_to_instruction_index.get(pc) {
+            Some(instruction_index) => &disassembled[*instruction_index],
+            None => continue,
+        };
+        instructions.push(format!(
+            "{:5?} {:016X?} {:5?} {}: {}",
+            index,
+            &entry[0..11],
+            pc + ebpf::ELF_INSN_DUMP_OFFSET,
+            function,
+            insn.desc,
+        ));
+
+        if enter_function || stack.is_empty() {
+            stack.push(function);
+            folded_stack = stack.join(";");
+            enter_function = false;
+        }
+        *folded_stacks.entry(folded_stack.clone()).or_default() += 1;
+        match insn.opc {
+            ebpf::CALL_IMM => {
+                enter_function = syscall_registry.lookup_syscall(insn.imm as u32).is_none()
+            }
+            ebpf::CALL_REG => enter_function = true,
+            ebpf::EXIT => {
+                stack.pop();
+                folded_stack = stack.join(";");
+            }
+            _ => {}
+        }
+    }
+
+    ProgramTrace {
+        program_id: *program_id,
+        invoke_depth,
+        instructions,
+        folded_stacks: folded_stacks.into_iter().collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_rbpf::assembler::assemble;
+
+    #[test]
+    fn test_function_symbols_lookup() {
+        let symbols = FunctionSymbols {
+            functions: vec![
+                (2, 3, "entrypoint".to_string()),
+                (8, 1, "helper".to_string()),
+            ],
+        };
+        assert_eq!(symbols.lookup(0), None);
+        assert_eq!(symbols.lookup(2), Some("entrypoint"));
+        assert_eq!(symbols.lookup(4), Some("entrypoint"));
+        assert_eq!(symbols.lookup(5), None);
+        assert_eq!(symbols.lookup(8), Some("helper"));
+        assert_eq!(symbols.lookup(9), None);
+
+        assert_eq!(
+            FunctionSymbols::from_elf(&[0; 16]),
+            FunctionSymbols::default()
+        );
+    }
+
+    #[test]
+    fn test_trace_program() {
+        let text_bytes = assemble(
+            "
+            mov64 r0, 0
+            call 0x10
+            exit
+            mov64 r0, 1
+            exit
+            mov64 r0, 2",
+        )
+        .unwrap();
+        let symbols = FunctionSymbols {
+            functions: vec![
+                (0, 3, "entrypoint".to_string()),
+                (3, 2, "helper".to_string()),
+            ],
+        };
+        let tracer = Tracer {
+            log: [0, 1, 3, 4, 2, 5]
+                .iter()
+                .map(|pc| {
+                    let mut entry = [0; 12];
+                    entry[11] = *pc;
+                    entry
+                })
+                .collect(),
+        };
+        let program_id = Pubkey::new_unique();
+        let trace = trace_program(
+            &program_id,
+            1,
+            &text_bytes,
+            &SyscallRegistry::default(),
+            &symbols,
+            &tracer,
+        );
+
+        assert_eq!(trace.program_id, program_id);
+        assert_eq!(trace.invoke_depth, 1);
+        assert_eq!(trace.instructions.len(), 6);
+        assert!(trace.instructions[2].ends_with("   32 helper: mov64 r0, 0x1"));
+        assert_eq!(
+            trace.folded_stacks,
+            vec![
+                ("[unknown]".to_string(), 1),
+                ("entrypoint".to_string(), 3),
+                ("entrypoint;helper".to_string(), 2),
+            ]
+        );
+    }
+}
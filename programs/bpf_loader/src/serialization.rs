@@ -2,6 +2,7 @@ use byteorder::{ByteOrder, LittleEndian, WriteBytesExt};
 use solana_sdk::{
     account::ReadableAccount, bpf_loader_deprecated, entrypoint::MAX_PERMITTED_DATA_INCREASE,
     instruction::InstructionError, keyed_account::KeyedAccount, pubkey::Pubkey,
+    system_instruction::MAX_PERMITTED_DATA_LENGTH,
 };
 use std::{
     io::prelude::*,
@@ -36,11 +37,17 @@ pub fn deserialize_parameters(
     keyed_accounts: &[KeyedAccount],
     buffer: &[u8],
     skip_ro_deserialization: bool,
+    do_support_realloc: bool,
 ) -> Result<(), InstructionError> {
     if *loader_id == bpf_loader_deprecated::id() {
         deserialize_parameters_unaligned(keyed_accounts, buffer, skip_ro_deserialization)
     } else {
-        deserialize_parameters_aligned(keyed_accounts, buffer, skip_ro_deserialization)
+        deserialize_parameters_aligned(
+            keyed_accounts,
+            buffer,
+            skip_ro_deserialization,
+            do_support_realloc,
+        )
     }
 }
 
@@ -233,6 +240,7 @@ pub fn deserialize_parameters_aligned(
     keyed_accounts: &[KeyedAccount],
     buffer: &[u8],
     skip_ro_deserialization: bool,
+    do_support_realloc: bool,
 ) -> Result<(), InstructionError> {
     let mut start = size_of::<u64>(); // number of accounts
     for (i, keyed_account) in keyed_accounts.iter().enumerate() {
@@ -258,7 +266,14 @@ pub fn deserialize_parameters_aligned(
             let post_len = LittleEndian::read_u64(&buffer[start..]) as usize;
             start += size_of::<u64>(); // data length
             let mut data_end = start + pre_len;
-            if post_len != pre_len
+            if do_support_realloc {
+                if post_len.saturating_sub(pre_len) > MAX_PERMITTED_DATA_INCREASE
+                    || post_len > MAX_PERMITTED_DATA_LENGTH as usize
+                {
+                    return Err(InstructionError::InvalidRealloc);
+                }
+                data_end = start + post_len;
+            } else if post_len != pre_len
                 && (post_len.saturating_sub(pre_len)) <= MAX_PERMITTED_DATA_INCREASE
             {
                 data_end = start + post_len;
@@ -435,7 +450,14 @@ mod tests {
                 }
             })
             .collect();
-        deserialize_parameters(&bpf_loader::id(), &de_keyed_accounts, &serialized, true).unwrap();
+        deserialize_parameters(
+            &bpf_loader::id(),
+            &de_keyed_accounts,
+            &serialized,
+            true,
+            true,
+        )
+        .unwrap();
         for ((account, de_keyed_account), key) in
             accounts.iter().zip(de_keyed_accounts).zip(keys.clone())
         {
@@ -493,6 +515,7 @@ mod tests {
             &de_keyed_accounts,
             &serialized,
             true,
+            true,
         )
         .unwrap();
         for ((account, de_keyed_account), key) in
@@ -511,6 +534,69 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_deserialize_parameters_realloc() {
+        let program_id = solana_sdk::pubkey::new_rand();
+        let key = solana_sdk::pubkey::new_rand();
+        let account = RefCell::new(AccountSharedData::from(Account {
+            lamports: 1,
+            data: vec![1u8, 2, 3, 4, 5],
+            owner: program_id,
+            executable: false,
+            rent_epoch: 100,
+        }));
+        let keyed_accounts = [KeyedAccount::new(&key, false, &account)];
+        let data_len_offset = size_of::<u64>() // number of accounts
+            + size_of::<u8>() // dup
+            + size_of::<u8>() // is_signer
+            + size_of::<u8>() // is_writable
+            + size_of::<u8>() // executable
+            + 4 // padding to 128-bit aligned
+            + size_of::<Pubkey>() // key
+            + size_of::<Pubkey>() // owner
+            + size_of::<u64>(); // lamports
+        let data_offset = data_len_offset + size_of::<u64>();
+
+        let serialize_with_len = |new_len: usize| {
+            let mut serialized =
+                serialize_parameters(&bpf_loader::id(), &program_id, &keyed_accounts, &[]).unwrap();
+            serialized[data_len_offset..data_offset]
+                .copy_from_slice(&(new_len as u64).to_le_bytes());
+            serialized[data_offset + 5..data_offset + new_len.max(5)]
+                .iter_mut()
+                .for_each(|byte| *byte = 42);
+            serialized
+        };
+
+        // grow
+        let serialized = serialize_with_len(5 + MAX_PERMITTED_DATA_INCREASE);
+        deserialize_parameters(&bpf_loader::id(), &keyed_accounts, &serialized, true, true)
+            .unwrap();
+        let mut expected = vec![1u8, 2, 3, 4, 5];
+        expected.resize(5 + MAX_PERMITTED_DATA_INCREASE, 42);
+        assert_eq!(account.borrow().data(), &expected[..]);
+
+        // grow past the permitted increase
+        let serialized =
+            serialize_with_len(account.borrow().data().len() + MAX_PERMITTED_DATA_INCREASE + 1);
+        assert_eq!(
+            deserialize_parameters(&bpf_loader::id(), &keyed_accounts, &serialized, true, true),
+            Err(InstructionError::InvalidRealloc)
+        );
+
+        // shrink
+        let serialized = serialize_with_len(3);
+        deserialize_parameters(&bpf_loader::id(), &keyed_accounts, &serialized, true, true)
+            .unwrap();
+        assert_eq!(account.borrow().data(), &[1u8, 2, 3]);
+
+        // growth past the permitted increase is ignored when realloc is not supported
+        let serialized = serialize_with_len(3 + MAX_PERMITTED_DATA_INCREASE + 1);
+        deserialize_parameters(&bpf_loader::id(), &keyed_accounts, &serialized, true, false)
+            .unwrap();
+        assert_eq!(account.borrow().data(), &[1u8, 2, 3]);
+    }
+
     // the old bpf_loader in-program deserializer bpf_loader::id()
     #[allow(clippy::type_complexity)]
     pub unsafe fn deserialize_unaligned<'a>(
@@ -1,6 +1,7 @@
 use crate::{alloc, BpfError};
 use alloc::Alloc;
 use curve25519_dalek::{ristretto::RistrettoPoint, scalar::Scalar};
+use sha3::{Digest, Keccak256};
 use solana_rbpf::{
     ebpf::MM_HEAP_START,
     error::EbpfError,
@@ -19,8 +20,10 @@ use solana_sdk::{
     entrypoint::{MAX_PERMITTED_DATA_INCREASE, SUCCESS},
     epoch_schedule::EpochSchedule,
     feature_set::{
-        cpi_data_cost, cpi_share_ro_and_exec_accounts, demote_sysvar_write_locks,
-        ristretto_mul_syscall_enabled, sysvar_via_syscall,
+        blake3_syscall_enabled, cpi_data_cost, cpi_share_ro_and_exec_accounts,
+        demote_sysvar_write_locks, do_support_realloc, keccak256_syscall_enabled,
+        mem_syscalls_enabled, return_data_syscall_enabled, ristretto_mul_syscall_enabled,
+        secp256k1_recover_syscall_enabled, sysvar_via_syscall,
     },
     hash::{Hasher, HASH_BYTES},
     ic_msg,
@@ -28,13 +31,19 @@ use solana_sdk::{
     keyed_account::KeyedAccount,
     native_loader,
     process_instruction::{stable_log, ComputeMeter, InvokeContext, Logger},
+    program::MAX_RETURN_DATA,
     pubkey::{Pubkey, PubkeyError, MAX_SEEDS},
     rent::Rent,
+    secp256k1_recover::{
+        Secp256k1RecoverError, SECP256K1_HASH_LENGTH, SECP256K1_PUBLIC_KEY_LENGTH,
+        SECP256K1_SIGNATURE_LENGTH,
+    },
     sysvar::{self, fees::Fees, Sysvar, SysvarId},
 };
 use std::{
     alloc::Layout,
     cell::{Ref, RefCell, RefMut},
+    convert::TryFrom,
     mem::{align_of, size_of},
     rc::Rc,
     slice::from_raw_parts_mut,
@@ -72,6 +81,10 @@ pub enum SyscallError {
     InstructionTooLarge(usize, usize),
     #[error("Too many accounts passed to inner instruction")]
     TooManyAccounts,
+    #[error("Return data too large ({0} > {1})")]
+    ReturnDataTooLarge(u64, u64),
+    #[error("Overlapping copy")]
+    CopyOverlapping,
 }
 impl From<SyscallError> for EbpfError<BpfError> {
     fn from(error: SyscallError) -> Self {
@@ -102,6 +115,14 @@ use crate::allocator_bump::BpfAllocator;
 
 pub fn register_syscalls(
     invoke_context: &mut dyn InvokeContext,
+) -> Result<SyscallRegistry, EbpfError<BpfError>> {
+    register_syscalls_with_features(|feature_id| invoke_context.is_feature_active(feature_id))
+}
+
+/// Register the syscalls available when the features accepted by
+/// `is_feature_active` are active
+pub fn register_syscalls_with_features(
+    is_feature_active: impl Fn(&Pubkey) -> bool,
 ) -> Result<SyscallRegistry, EbpfError<BpfError>> {
     let mut syscall_registry = SyscallRegistry::default();
 
@@ -126,12 +147,32 @@ pub fn register_syscalls(
 
     syscall_registry.register_syscall_by_name(b"sol_sha256", SyscallSha256::call)?;
 
-    if invoke_context.is_feature_active(&ristretto_mul_syscall_enabled::id()) {
+    if is_feature_active(&keccak256_syscall_enabled::id()) {
+        syscall_registry.register_syscall_by_name(b"sol_keccak256", SyscallKeccak256::call)?;
+    }
+
+    if is_feature_active(&blake3_syscall_enabled::id()) {
+        syscall_registry.register_syscall_by_name(b"sol_blake3", SyscallBlake3::call)?;
+    }
+
+    if is_feature_active(&secp256k1_recover_syscall_enabled::id()) {
+        syscall_registry
+            .register_syscall_by_name(b"sol_secp256k1_recover", SyscallSecp256k1Recover::call)?;
+    }
+
+    if is_feature_active(&mem_syscalls_enabled::id()) {
+        syscall_registry.register_syscall_by_name(b"sol_memcpy_", SyscallMemcpy::call)?;
+        syscall_registry.register_syscall_by_name(b"sol_memmove_", SyscallMemmove::call)?;
+        syscall_registry.register_syscall_by_name(b"sol_memcmp_", SyscallMemcmp::call)?;
+        syscall_registry.register_syscall_by_name(b"sol_memset_", SyscallMemset::call)?;
+    }
+
+    if is_feature_active(&ristretto_mul_syscall_enabled::id()) {
         syscall_registry
             .register_syscall_by_name(b"sol_ristretto_mul", SyscallRistrettoMul::call)?;
     }
 
-    if invoke_context.is_feature_active(&sysvar_via_syscall::id()) {
+    if is_feature_active(&sysvar_via_syscall::id()) {
         syscall_registry
             .register_syscall_by_name(b"sol_get_clock_sysvar", SyscallGetClockSysvar::call)?;
         syscall_registry.register_syscall_by_name(
@@ -150,6 +191,13 @@ pub fn register_syscalls(
         .register_syscall_by_name(b"sol_invoke_signed_rust", SyscallInvokeSignedRust::call)?;
     syscall_registry.register_syscall_by_name(b"sol_alloc_free_", SyscallAllocFree::call)?;
 
+    if is_feature_active(&return_data_syscall_enabled::id()) {
+        syscall_registry
+            .register_syscall_by_name(b"sol_set_return_data", SyscallSetReturnData::call)?;
+        syscall_registry
+            .register_syscall_by_name(b"sol_get_return_data", SyscallGetReturnData::call)?;
+    }
+
     Ok(syscall_registry)
 }
 
@@ -249,6 +297,79 @@ pub fn bind_syscall_context_objects<'a>(
         None,
     )?;
 
+    bind_feature_gated_syscall_context_object!(
+        vm,
+        invoke_context.is_feature_active(&keccak256_syscall_enabled::id()),
+        Box::new(SyscallKeccak256 {
+            base_cost: bpf_compute_budget.keccak256_base_cost,
+            byte_cost: bpf_compute_budget.keccak256_byte_cost,
+            compute_meter: invoke_context.get_compute_meter(),
+            loader_id,
+        }),
+    );
+
+    bind_feature_gated_syscall_context_object!(
+        vm,
+        invoke_context.is_feature_active(&blake3_syscall_enabled::id()),
+        Box::new(SyscallBlake3 {
+            base_cost: bpf_compute_budget.blake3_base_cost,
+            byte_cost: bpf_compute_budget.blake3_byte_cost,
+            compute_meter: invoke_context.get_compute_meter(),
+            loader_id,
+        }),
+    );
+
+    bind_feature_gated_syscall_context_object!(
+        vm,
+        invoke_context.is_feature_active(&secp256k1_recover_syscall_enabled::id()),
+        Box::new(SyscallSecp256k1Recover {
+            cost: bpf_compute_budget.secp256k1_recover_cost,
+            compute_meter: invoke_context.get_compute_meter(),
+            loader_id,
+        }),
+    );
+
+    let is_mem_syscalls_active = invoke_context.is_feature_active(&mem_syscalls_enabled::id());
+    bind_feature_gated_syscall_context_object!(
+        vm,
+        is_mem_syscalls_active,
+        Box::new(SyscallMemcpy {
+            base_cost: bpf_compute_budget.mem_op_base_cost,
+            bytes_per_unit: bpf_compute_budget.mem_op_bytes_per_unit,
+            compute_meter: invoke_context.get_compute_meter(),
+            loader_id,
+        }),
+    );
+    bind_feature_gated_syscall_context_object!(
+        vm,
+        is_mem_syscalls_active,
+        Box::new(SyscallMemmove {
+            base_cost: bpf_compute_budget.mem_op_base_cost,
+            bytes_per_unit: bpf_compute_budget.mem_op_bytes_per_unit,
+            compute_meter: invoke_context.get_compute_meter(),
+        }),
+    );
+    bind_feature_gated_syscall_context_object!(
+        vm,
+        is_mem_syscalls_active,
+        Box::new(SyscallMemcmp {
+            base_cost: bpf_compute_budget.mem_op_base_cost,
+            bytes_per_unit: bpf_compute_budget.mem_op_bytes_per_unit,
+            compute_meter: invoke_context.get_compute_meter(),
+            loader_id,
+        }),
+    );
+    bind_feature_gated_syscall_context_object!(
+        vm,
+        is_mem_syscalls_active,
+        Box::new(SyscallMemset {
+            base_cost: bpf_compute_budget.mem_op_base_cost,
+            bytes_per_unit: bpf_compute_budget.mem_op_bytes_per_unit,
+            compute_meter: invoke_context.get_compute_meter(),
+            loader_id,
+        }),
+    );
+
     bind_feature_gated_syscall_context_object!(
         vm,
         invoke_context.is_feature_active(&ristretto_mul_syscall_enabled::id()),
@@ -260,6 +381,8 @@ pub fn bind_syscall_context_objects<'a>(
     );
 
     let is_sysvar_via_syscall_active = invoke_context.is_feature_active(&sysvar_via_syscall::id());
+    let is_return_data_syscall_active =
+        invoke_context.is_feature_active(&return_data_syscall_enabled::id());
 
     let invoke_context = Rc::new(RefCell::new(invoke_context));
 
@@ -314,6 +437,24 @@ pub fn bind_syscall_context_objects<'a>(
         None,
     )?;
 
+    // Return data syscalls
+    bind_feature_gated_syscall_context_object!(
+        vm,
+        is_return_data_syscall_active,
+        Box::new(SyscallSetReturnData {
+            invoke_context: invoke_context.clone(),
+            loader_id,
+        }),
+    );
+    bind_feature_gated_syscall_context_object!(
+        vm,
+        is_return_data_syscall_active,
+        Box::new(SyscallGetReturnData {
+            invoke_context: invoke_context.clone(),
+            loader_id,
+        }),
+    );
+
     // Memory allocator
 
     vm.bind_syscall_context_object(
@@ -839,123 +980,512 @@ impl<'a> SyscallObject<BpfError> for SyscallSha256<'a> {
     }
 }
 
-/// Ristretto point multiply
-pub struct SyscallRistrettoMul<'a> {
-    cost: u64,
+/// Keccak256
+pub struct SyscallKeccak256<'a> {
+    base_cost: u64,
+    byte_cost: u64,
     compute_meter: Rc<RefCell<dyn ComputeMeter>>,
     loader_id: &'a Pubkey,
 }
-impl<'a> SyscallObject<BpfError> for SyscallRistrettoMul<'a> {
+impl<'a> SyscallObject<BpfError> for SyscallKeccak256<'a> {
     fn call(
         &mut self,
-        point_addr: u64,
-        scalar_addr: u64,
+        vals_addr: u64,
+        vals_len: u64,
         result_addr: u64,
         _arg4: u64,
         _arg5: u64,
         memory_mapping: &MemoryMapping,
         result: &mut Result<u64, EbpfError<BpfError>>,
     ) {
-        question_mark!(self.compute_meter.consume(self.cost), result);
-
-        let point = question_mark!(
-            translate_type::<RistrettoPoint>(memory_mapping, point_addr, self.loader_id),
-            result
-        );
-        let scalar = question_mark!(
-            translate_type::<Scalar>(memory_mapping, scalar_addr, self.loader_id),
-            result
-        );
-        let output = question_mark!(
-            translate_type_mut::<RistrettoPoint>(memory_mapping, result_addr, self.loader_id),
+        question_mark!(self.compute_meter.consume(self.base_cost), result);
+        let hash_result = question_mark!(
+            translate_slice_mut::<u8>(
+                memory_mapping,
+                result_addr,
+                HASH_BYTES as u64,
+                self.loader_id
+            ),
             result
         );
-        *output = point * scalar;
-
+        let mut hasher = Keccak256::new();
+        if vals_len > 0 {
+            let vals = question_mark!(
+                translate_slice::<&[u8]>(memory_mapping, vals_addr, vals_len, self.loader_id),
+                result
+            );
+            for val in vals.iter() {
+                let bytes = question_mark!(
+                    translate_slice::<u8>(
+                        memory_mapping,
+                        val.as_ptr() as u64,
+                        val.len() as u64,
+                        self.loader_id
+                    ),
+                    result
+                );
+                question_mark!(
+                    self.compute_meter
+                        .consume(self.byte_cost * (val.len() as u64 / 2)),
+                    result
+                );
+                hasher.update(bytes);
+            }
+        }
+        hash_result.copy_from_slice(&hasher.finalize());
         *result = Ok(0);
     }
 }
 
-fn get_sysvar<T: std::fmt::Debug + Sysvar + SysvarId>(
-    id: &Pubkey,
-    var_addr: u64,
-    loader_id: &Pubkey,
-    memory_mapping: &MemoryMapping,
-    invoke_context: Rc<RefCell<&mut dyn InvokeContext>>,
-) -> Result<u64, EbpfError<BpfError>> {
-    let mut invoke_context = invoke_context
-        .try_borrow_mut()
-        .map_err(|_| SyscallError::InvokeContextBorrowFailed)?;
-
-    invoke_context.get_compute_meter().consume(
-        invoke_context.get_bpf_compute_budget().sysvar_base_cost + size_of::<T>() as u64,
-    )?;
-    let var = translate_type_mut::<T>(memory_mapping, var_addr, loader_id)?;
-
-    let sysvar_data = invoke_context.get_sysvar_data(id).ok_or_else(|| {
-        ic_msg!(invoke_context, "Unable to get Sysvar {}", id);
-        SyscallError::InstructionError(InstructionError::UnsupportedSysvar)
-    })?;
-
-    *var = bincode::deserialize(&sysvar_data).map_err(|e| {
-        ic_msg!(invoke_context, "Unable to get Sysvar {}: {:?}", id, e);
-        SyscallError::InstructionError(InstructionError::UnsupportedSysvar)
-    })?;
-
-    Ok(SUCCESS)
-}
-
-/// Get a Clock sysvar
-struct SyscallGetClockSysvar<'a> {
-    invoke_context: Rc<RefCell<&'a mut dyn InvokeContext>>,
+/// Blake3
+pub struct SyscallBlake3<'a> {
+    base_cost: u64,
+    byte_cost: u64,
+    compute_meter: Rc<RefCell<dyn ComputeMeter>>,
     loader_id: &'a Pubkey,
 }
-impl<'a> SyscallObject<BpfError> for SyscallGetClockSysvar<'a> {
+impl<'a> SyscallObject<BpfError> for SyscallBlake3<'a> {
     fn call(
         &mut self,
-        var_addr: u64,
-        _arg2: u64,
-        _arg3: u64,
+        vals_addr: u64,
+        vals_len: u64,
+        result_addr: u64,
         _arg4: u64,
         _arg5: u64,
         memory_mapping: &MemoryMapping,
         result: &mut Result<u64, EbpfError<BpfError>>,
     ) {
-        *result = get_sysvar::<Clock>(
-            &sysvar::clock::id(),
-            var_addr,
-            self.loader_id,
-            memory_mapping,
-            self.invoke_context.clone(),
+        question_mark!(self.compute_meter.consume(self.base_cost), result);
+        let hash_result = question_mark!(
+            translate_slice_mut::<u8>(
+                memory_mapping,
+                result_addr,
+                HASH_BYTES as u64,
+                self.loader_id
+            ),
+            result
         );
+        let mut hasher = blake3::Hasher::new();
+        if vals_len > 0 {
+            let vals = question_mark!(
+                translate_slice::<&[u8]>(memory_mapping, vals_addr, vals_len, self.loader_id),
+                result
+            );
+            for val in vals.iter() {
+                let bytes = question_mark!(
+                    translate_slice::<u8>(
+                        memory_mapping,
+                        val.as_ptr() as u64,
+                        val.len() as u64,
+                        self.loader_id
+                    ),
+                    result
+                );
+                question_mark!(
+                    self.compute_meter
+                        .consume(self.byte_cost * (val.len() as u64 / 2)),
+                    result
+                );
+                hasher.update(bytes);
+            }
+        }
+        hash_result.copy_from_slice(blake3::Hasher::finalize(&hasher).as_bytes());
+        *result = Ok(0);
     }
 }
-/// Get a EpochSchedule sysvar
-struct SyscallGetEpochScheduleSysvar<'a> {
-    invoke_context: Rc<RefCell<&'a mut dyn InvokeContext>>,
+
+/// Secp256k1 public key recovery
+///
+/// Invalid inputs are reported through the return value, as a
+/// `Secp256k1RecoverError`, rather than by aborting the program
+pub struct SyscallSecp256k1Recover<'a> {
+    cost: u64,
+    compute_meter: Rc<RefCell<dyn ComputeMeter>>,
     loader_id: &'a Pubkey,
 }
-impl<'a> SyscallObject<BpfError> for SyscallGetEpochScheduleSysvar<'a> {
+impl<'a> SyscallObject<BpfError> for SyscallSecp256k1Recover<'a> {
     fn call(
         &mut self,
-        var_addr: u64,
-        _arg2: u64,
-        _arg3: u64,
-        _arg4: u64,
+        hash_addr: u64,
+        recovery_id_val: u64,
+        signature_addr: u64,
+        result_addr: u64,
         _arg5: u64,
         memory_mapping: &MemoryMapping,
         result: &mut Result<u64, EbpfError<BpfError>>,
     ) {
-        *result = get_sysvar::<EpochSchedule>(
-            &sysvar::epoch_schedule::id(),
-            var_addr,
-            self.loader_id,
-            memory_mapping,
-            self.invoke_context.clone(),
+        question_mark!(self.compute_meter.consume(self.cost), result);
+
+        let hash = question_mark!(
+            translate_slice::<u8>(
+                memory_mapping,
+                hash_addr,
+                SECP256K1_HASH_LENGTH as u64,
+                self.loader_id
+            ),
+            result
+        );
+        let signature = question_mark!(
+            translate_slice::<u8>(
+                memory_mapping,
+                signature_addr,
+                SECP256K1_SIGNATURE_LENGTH as u64,
+                self.loader_id
+            ),
+            result
         );
+        let secp256k1_recover_result = question_mark!(
+            translate_slice_mut::<u8>(
+                memory_mapping,
+                result_addr,
+                SECP256K1_PUBLIC_KEY_LENGTH as u64,
+                self.loader_id
+            ),
+            result
+        );
+
+        let message = match secp256k1::Message::parse_slice(hash) {
+            Ok(message) => message,
+            Err(_) => {
+                *result = Ok(Secp256k1RecoverError::InvalidHash.into());
+                return;
+            }
+        };
+        let recovery_id = match u8::try_from(recovery_id_val)
+            .ok()
+            .and_then(|recovery_id| secp256k1::RecoveryId::parse(recovery_id).ok())
+        {
+            Some(recovery_id) => recovery_id,
+            None => {
+                *result = Ok(Secp256k1RecoverError::InvalidRecoveryId.into());
+                return;
+            }
+        };
+        let public_key = match secp256k1::Signature::parse_slice(signature)
+            .and_then(|signature| secp256k1::recover(&message, &signature, &recovery_id))
+        {
+            Ok(public_key) => public_key.serialize(),
+            Err(_) => {
+                *result = Ok(Secp256k1RecoverError::InvalidSignature.into());
+                return;
+            }
+        };
+
+        secp256k1_recover_result.copy_from_slice(&public_key[1..]);
+        *result = Ok(0);
     }
 }
-/// Get a Fees sysvar
+
+/// Check whether two `n` byte regions starting at `a` and `b` overlap
+fn is_overlapping(a: u64, b: u64, n: u64) -> bool {
+    n > 0 && a < b.saturating_add(n) && b < a.saturating_add(n)
+}
+
+/// Charge for a memory operation over `n` bytes
+fn consume_mem_op(
+    compute_meter: &mut Rc<RefCell<dyn ComputeMeter>>,
+    base_cost: u64,
+    bytes_per_unit: u64,
+    n: u64,
+) -> Result<(), EbpfError<BpfError>> {
+    compute_meter.consume(base_cost.saturating_add(n / bytes_per_unit))
+}
+
+/// Memcpy, the source and destination must not overlap
+pub struct SyscallMemcpy<'a> {
+    base_cost: u64,
+    bytes_per_unit: u64,
+    compute_meter: Rc<RefCell<dyn ComputeMeter>>,
+    loader_id: &'a Pubkey,
+}
+impl<'a> SyscallObject<BpfError> for SyscallMemcpy<'a> {
+    fn call(
+        &mut self,
+        dst_addr: u64,
+        src_addr: u64,
+        n: u64,
+        _arg4: u64,
+        _arg5: u64,
+        memory_mapping: &MemoryMapping,
+        result: &mut Result<u64, EbpfError<BpfError>>,
+    ) {
+        question_mark!(
+            consume_mem_op(
+                &mut self.compute_meter,
+                self.base_cost,
+                self.bytes_per_unit,
+                n
+            ),
+            result
+        );
+        if is_overlapping(dst_addr, src_addr, n) {
+            *result = Err(SyscallError::CopyOverlapping.into());
+            return;
+        }
+        let dst = question_mark!(
+            translate_slice_mut::<u8>(memory_mapping, dst_addr, n, self.loader_id),
+            result
+        );
+        let src = question_mark!(
+            translate_slice::<u8>(memory_mapping, src_addr, n, self.loader_id),
+            result
+        );
+        dst.copy_from_slice(src);
+        *result = Ok(0);
+    }
+}
+
+/// Memmove, the source and destination may overlap
+pub struct SyscallMemmove {
+    base_cost: u64,
+    bytes_per_unit: u64,
+    compute_meter: Rc<RefCell<dyn ComputeMeter>>,
+}
+impl SyscallObject<BpfError> for SyscallMemmove {
+    fn call(
+        &mut self,
+        dst_addr: u64,
+        src_addr: u64,
+        n: u64,
+        _arg4: u64,
+        _arg5: u64,
+        memory_mapping: &MemoryMapping,
+        result: &mut Result<u64, EbpfError<BpfError>>,
+    ) {
+        question_mark!(
+            consume_mem_op(
+                &mut self.compute_meter,
+                self.base_cost,
+                self.bytes_per_unit,
+                n
+            ),
+            result
+        );
+        if n == 0 {
+            *result = Ok(0);
+            return;
+        }
+        // Translate to host addresses rather than slices, the regions may alias
+        let dst = question_mark!(
+            translate(memory_mapping, AccessType::Store, dst_addr, n),
+            result
+        );
+        let src = question_mark!(
+            translate(memory_mapping, AccessType::Load, src_addr, n),
+            result
+        );
+        unsafe {
+            std::ptr::copy(src as *const u8, dst as *mut u8, n as usize);
+        }
+        *result = Ok(0);
+    }
+}
+
+/// Memcmp, writes the difference of the first differing bytes (or 0) to
+/// the result address as an `i32`
+pub struct SyscallMemcmp<'a> {
+    base_cost: u64,
+    bytes_per_unit: u64,
+    compute_meter: Rc<RefCell<dyn ComputeMeter>>,
+    loader_id: &'a Pubkey,
+}
+impl<'a> SyscallObject<BpfError> for SyscallMemcmp<'a> {
+    fn call(
+        &mut self,
+        s1_addr: u64,
+        s2_addr: u64,
+        n: u64,
+        cmp_result_addr: u64,
+        _arg5: u64,
+        memory_mapping: &MemoryMapping,
+        result: &mut Result<u64, EbpfError<BpfError>>,
+    ) {
+        question_mark!(
+            consume_mem_op(
+                &mut self.compute_meter,
+                self.base_cost,
+                self.bytes_per_unit,
+                n
+            ),
+            result
+        );
+        let s1 = question_mark!(
+            translate_slice::<u8>(memory_mapping, s1_addr, n, self.loader_id),
+            result
+        );
+        let s2 = question_mark!(
+            translate_slice::<u8>(memory_mapping, s2_addr, n, self.loader_id),
+            result
+        );
+        let cmp_result = question_mark!(
+            translate_type_mut::<i32>(memory_mapping, cmp_result_addr, self.loader_id),
+            result
+        );
+        *cmp_result = s1
+            .iter()
+            .zip(s2.iter())
+            .find(|(a, b)| a != b)
+            .map(|(a, b)| *a as i32 - *b as i32)
+            .unwrap_or(0);
+        *result = Ok(0);
+    }
+}
+
+/// Memset
+pub struct SyscallMemset<'a> {
+    base_cost: u64,
+    bytes_per_unit: u64,
+    compute_meter: Rc<RefCell<dyn ComputeMeter>>,
+    loader_id: &'a Pubkey,
+}
+impl<'a> SyscallObject<BpfError> for SyscallMemset<'a> {
+    fn call(
+        &mut self,
+        s_addr: u64,
+        c: u64,
+        n: u64,
+        _arg4: u64,
+        _arg5: u64,
+        memory_mapping: &MemoryMapping,
+        result: &mut Result<u64, EbpfError<BpfError>>,
+    ) {
+        question_mark!(
+            consume_mem_op(
+                &mut self.compute_meter,
+                self.base_cost,
+                self.bytes_per_unit,
+                n
+            ),
+            result
+        );
+        let s = question_mark!(
+            translate_slice_mut::<u8>(memory_mapping, s_addr, n, self.loader_id),
+            result
+        );
+        for val in s.iter_mut() {
+            *val = c as u8;
+        }
+        *result = Ok(0);
+    }
+}
+
+/// Ristretto point multiply
+pub struct SyscallRistrettoMul<'a> {
+    cost: u64,
+    compute_meter: Rc<RefCell<dyn ComputeMeter>>,
+    loader_id: &'a Pubkey,
+}
+impl<'a> SyscallObject<BpfError> for SyscallRistrettoMul<'a> {
+    fn call(
+        &mut self,
+        point_addr: u64,
+        scalar_addr: u64,
+        result_addr: u64,
+        _arg4: u64,
+        _arg5: u64,
+        memory_mapping: &MemoryMapping,
+        result: &mut Result<u64, EbpfError<BpfError>>,
+    ) {
+        question_mark!(self.compute_meter.consume(self.cost), result);
+
+        let point = question_mark!(
+            translate_type::<RistrettoPoint>(memory_mapping, point_addr, self.loader_id),
+            result
+        );
+        let scalar = question_mark!(
+            translate_type::<Scalar>(memory_mapping, scalar_addr, self.loader_id),
+            result
+        );
+        let output = question_mark!(
+            translate_type_mut::<RistrettoPoint>(memory_mapping, result_addr, self.loader_id),
+            result
+        );
+        *output = point * scalar;
+
+        *result = Ok(0);
+    }
+}
+
+fn get_sysvar<T: std::fmt::Debug + Sysvar + SysvarId>(
+    id: &Pubkey,
+    var_addr: u64,
+    loader_id: &Pubkey,
+    memory_mapping: &MemoryMapping,
+    invoke_context: Rc<RefCell<&mut dyn InvokeContext>>,
+) -> Result<u64, EbpfError<BpfError>> {
+    let mut invoke_context = invoke_context
+        .try_borrow_mut()
+        .map_err(|_| SyscallError::InvokeContextBorrowFailed)?;
+
+    invoke_context.get_compute_meter().consume(
+        invoke_context.get_bpf_compute_budget().sysvar_base_cost + size_of::<T>() as u64,
+    )?;
+    let var = translate_type_mut::<T>(memory_mapping, var_addr, loader_id)?;
+
+    let sysvar_data = invoke_context.get_sysvar_data(id).ok_or_else(|| {
+        ic_msg!(invoke_context, "Unable to get Sysvar {}", id);
+        SyscallError::InstructionError(InstructionError::UnsupportedSysvar)
+    })?;
+
+    *var = bincode::deserialize(&sysvar_data).map_err(|e| {
+        ic_msg!(invoke_context, "Unable to get Sysvar {}: {:?}", id, e);
+        SyscallError::InstructionError(InstructionError::UnsupportedSysvar)
+    })?;
+
+    Ok(SUCCESS)
+}
+
+/// Get a Clock sysvar
+struct SyscallGetClockSysvar<'a> {
+    invoke_context: Rc<RefCell<&'a mut dyn InvokeContext>>,
+    loader_id: &'a Pubkey,
+}
+impl<'a> SyscallObject<BpfError> for SyscallGetClockSysvar<'a> {
+    fn call(
+        &mut self,
+        var_addr: u64,
+        _arg2: u64,
+        _arg3: u64,
+        _arg4: u64,
+        _arg5: u64,
+        memory_mapping: &MemoryMapping,
+        result: &mut Result<u64, EbpfError<BpfError>>,
+    ) {
+        *result = get_sysvar::<Clock>(
+            &sysvar::clock::id(),
+            var_addr,
+            self.loader_id,
+            memory_mapping,
+            self.invoke_context.clone(),
+        );
+    }
+}
+/// Get a EpochSchedule sysvar
+struct SyscallGetEpochScheduleSysvar<'a> {
+    invoke_context: Rc<RefCell<&'a mut dyn InvokeContext>>,
+    loader_id: &'a Pubkey,
+}
+impl<'a> SyscallObject<BpfError> for SyscallGetEpochScheduleSysvar<'a> {
+    fn call(
+        &mut self,
+        var_addr: u64,
+        _arg2: u64,
+        _arg3: u64,
+        _arg4: u64,
+        _arg5: u64,
+        memory_mapping: &MemoryMapping,
+        result: &mut Result<u64, EbpfError<BpfError>>,
+    ) {
+        *result = get_sysvar::<EpochSchedule>(
+            &sysvar::epoch_schedule::id(),
+            var_addr,
+            self.loader_id,
+            memory_mapping,
+            self.invoke_context.clone(),
+        );
+    }
+}
+/// Get a Fees sysvar
 struct SyscallGetFeesSysvar<'a> {
     invoke_context: Rc<RefCell<&'a mut dyn InvokeContext>>,
     loader_id: &'a Pubkey,
@@ -1030,6 +1560,7 @@ trait SyscallInvokeSigned<'a> {
     fn get_context_mut(&self) -> Result<RefMut<&'a mut dyn InvokeContext>, EbpfError<BpfError>>;
     fn get_context(&self) -> Result<Ref<&'a mut dyn InvokeContext>, EbpfError<BpfError>>;
     fn get_callers_keyed_accounts(&self) -> &'a [KeyedAccount<'a>];
+    fn get_loader_id(&self) -> &'a Pubkey;
     fn translate_instruction(
         &self,
         addr: u64,
@@ -1073,6 +1604,9 @@ impl<'a> SyscallInvokeSigned<'a> for SyscallInvokeSignedRust<'a> {
     fn get_callers_keyed_accounts(&self) -> &'a [KeyedAccount<'a>] {
         self.callers_keyed_accounts
     }
+    fn get_loader_id(&self) -> &'a Pubkey {
+        self.loader_id
+    }
     fn translate_instruction(
         &self,
         addr: u64,
@@ -1369,6 +1903,9 @@ impl<'a> SyscallInvokeSigned<'a> for SyscallInvokeSignedC<'a> {
     fn get_callers_keyed_accounts(&self) -> &'a [KeyedAccount<'a>] {
         self.callers_keyed_accounts
     }
+    fn get_loader_id(&self) -> &'a Pubkey {
+        self.loader_id
+    }
 
     fn translate_instruction(
         &self,
@@ -1740,29 +2277,137 @@ fn get_upgradeable_executable(
     }
 }
 
-/// Call process instruction, common to both Rust and C
-fn call<'a>(
-    syscall: &mut dyn SyscallInvokeSigned<'a>,
-    instruction_addr: u64,
-    account_infos_addr: u64,
-    account_infos_len: u64,
-    signers_seeds_addr: u64,
-    signers_seeds_len: u64,
-    memory_mapping: &MemoryMapping,
-) -> Result<u64, EbpfError<BpfError>> {
-    let (
-        message,
-        executables,
-        accounts,
-        account_refs,
-        caller_write_privileges,
-        demote_sysvar_write_locks,
-    ) = {
-        let invoke_context = syscall.get_context()?;
+/// Set the return data of the running program
+pub struct SyscallSetReturnData<'a> {
+    invoke_context: Rc<RefCell<&'a mut dyn InvokeContext>>,
+    loader_id: &'a Pubkey,
+}
+impl<'a> SyscallObject<BpfError> for SyscallSetReturnData<'a> {
+    fn call(
+        &mut self,
+        addr: u64,
+        len: u64,
+        _arg3: u64,
+        _arg4: u64,
+        _arg5: u64,
+        memory_mapping: &MemoryMapping,
+        result: &mut Result<u64, EbpfError<BpfError>>,
+    ) {
+        let mut invoke_context = question_mark!(
+            self.invoke_context
+                .try_borrow_mut()
+                .map_err(|_| SyscallError::InvokeContextBorrowFailed),
+            result
+        );
+        let budget = invoke_context.get_bpf_compute_budget();
+        let cost = len / budget.cpi_bytes_per_unit + budget.syscall_base_cost;
+        question_mark!(invoke_context.get_compute_meter().consume(cost), result);
 
-        invoke_context
-            .get_compute_meter()
-            .consume(invoke_context.get_bpf_compute_budget().invoke_units)?;
+        if len > MAX_RETURN_DATA as u64 {
+            *result = Err(SyscallError::ReturnDataTooLarge(len, MAX_RETURN_DATA as u64).into());
+            return;
+        }
+
+        let return_data = if len == 0 {
+            Vec::new()
+        } else {
+            question_mark!(
+                translate_slice::<u8>(memory_mapping, addr, len, self.loader_id),
+                result
+            )
+            .to_vec()
+        };
+        question_mark!(
+            invoke_context
+                .set_return_data(return_data)
+                .map_err(SyscallError::InstructionError),
+            result
+        );
+
+        *result = Ok(0);
+    }
+}
+
+/// Get the return data set by the last invoked program
+pub struct SyscallGetReturnData<'a> {
+    invoke_context: Rc<RefCell<&'a mut dyn InvokeContext>>,
+    loader_id: &'a Pubkey,
+}
+impl<'a> SyscallObject<BpfError> for SyscallGetReturnData<'a> {
+    fn call(
+        &mut self,
+        return_data_addr: u64,
+        len: u64,
+        program_id_addr: u64,
+        _arg4: u64,
+        _arg5: u64,
+        memory_mapping: &MemoryMapping,
+        result: &mut Result<u64, EbpfError<BpfError>>,
+    ) {
+        let invoke_context = question_mark!(
+            self.invoke_context
+                .try_borrow()
+                .map_err(|_| SyscallError::InvokeContextBorrowFailed),
+            result
+        );
+        let budget = invoke_context.get_bpf_compute_budget();
+        question_mark!(
+            invoke_context
+                .get_compute_meter()
+                .consume(budget.syscall_base_cost),
+            result
+        );
+
+        let (program_id, return_data) = invoke_context.get_return_data();
+        let length = len.min(return_data.len() as u64);
+        if length != 0 {
+            let cost = (length + size_of::<Pubkey>() as u64) / budget.cpi_bytes_per_unit;
+            question_mark!(invoke_context.get_compute_meter().consume(cost), result);
+
+            let return_data_result = question_mark!(
+                translate_slice_mut::<u8>(memory_mapping, return_data_addr, length, self.loader_id),
+                result
+            );
+            return_data_result.copy_from_slice(&return_data[..length as usize]);
+
+            let program_id_result = question_mark!(
+                translate_type_mut::<Pubkey>(memory_mapping, program_id_addr, self.loader_id),
+                result
+            );
+            *program_id_result = program_id;
+        }
+
+        // Return the actual length rather than the length copied
+        *result = Ok(return_data.len() as u64);
+    }
+}
+
+/// Call process instruction, common to both Rust and C
+fn call<'a>(
+    syscall: &mut dyn SyscallInvokeSigned<'a>,
+    instruction_addr: u64,
+    account_infos_addr: u64,
+    account_infos_len: u64,
+    signers_seeds_addr: u64,
+    signers_seeds_len: u64,
+    memory_mapping: &MemoryMapping,
+) -> Result<u64, EbpfError<BpfError>> {
+    let loader_id = syscall.get_loader_id();
+    let (
+        message,
+        executables,
+        accounts,
+        account_refs,
+        caller_write_privileges,
+        orig_data_lens,
+        demote_sysvar_write_locks,
+        do_support_realloc,
+    ) = {
+        let invoke_context = syscall.get_context()?;
+
+        invoke_context
+            .get_compute_meter()
+            .consume(invoke_context.get_bpf_compute_budget().invoke_units)?;
 
         let caller_program_id = invoke_context
             .get_caller()
@@ -1803,6 +2448,19 @@ fn call<'a>(
                 }
             })
             .collect::<Vec<bool>>();
+        // Length of each account's data as serialized into the caller's
+        // input buffer, bounds any realloc made by the caller or callee
+        let orig_data_lens = message
+            .account_keys
+            .iter()
+            .map(|key| {
+                keyed_account_refs
+                    .iter()
+                    .find(|keyed_account| key == keyed_account.unsigned_key())
+                    .map_or(Ok(0), |keyed_account| keyed_account.data_len())
+            })
+            .collect::<Result<Vec<usize>, InstructionError>>()
+            .map_err(SyscallError::InstructionError)?;
         check_authorized_program(&callee_program_id, &instruction.data)?;
         let (accounts, account_refs) = syscall.translate_accounts(
             &message.account_keys,
@@ -1812,6 +2470,29 @@ fn call<'a>(
             account_infos_len,
             memory_mapping,
         )?;
+        let do_support_realloc = invoke_context.is_feature_active(&do_support_realloc::id());
+        if do_support_realloc {
+            for (account_ref, orig_data_len) in account_refs.iter().zip(&orig_data_lens) {
+                if let Some(account_ref) = account_ref {
+                    let max_data_len = if bpf_loader_deprecated::check_id(loader_id) {
+                        *orig_data_len
+                    } else {
+                        orig_data_len.saturating_add(MAX_PERMITTED_DATA_INCREASE)
+                    };
+                    if account_ref.data.len() > max_data_len {
+                        ic_msg!(
+                            invoke_context,
+                            "Account data size realloc limited to {} in inner instructions",
+                            max_data_len.saturating_sub(*orig_data_len)
+                        );
+                        return Err(SyscallError::InstructionError(
+                            InstructionError::InvalidRealloc,
+                        )
+                        .into());
+                    }
+                }
+            }
+        }
 
         // Construct executables
 
@@ -1839,7 +2520,9 @@ fn call<'a>(
             accounts,
             account_refs,
             caller_write_privileges,
+            orig_data_lens,
             invoke_context.is_feature_active(&demote_sysvar_write_locks::id()),
+            do_support_realloc,
         )
     };
 
@@ -1864,11 +2547,39 @@ fn call<'a>(
         let invoke_context = syscall.get_context()?;
         for (i, (account, account_ref)) in accounts.iter().zip(account_refs).enumerate() {
             let account = account.borrow();
-            if let Some(account_ref) = account_ref {
+            if let Some(mut account_ref) = account_ref {
                 if message.is_writable(i, demote_sysvar_write_locks) && !account.executable {
                     *account_ref.lamports = account.lamports;
                     *account_ref.owner = account.owner;
-                    if account_ref.data.len() != account.data().len() {
+                    let new_len = account.data().len();
+                    if do_support_realloc
+                        && !bpf_loader_deprecated::check_id(loader_id)
+                        && account_ref.data.len() != new_len
+                    {
+                        if new_len > orig_data_lens[i].saturating_add(MAX_PERMITTED_DATA_INCREASE) {
+                            ic_msg!(
+                                invoke_context,
+                                "Account data size realloc limited to {} in inner instructions",
+                                MAX_PERMITTED_DATA_INCREASE
+                            );
+                            return Err(SyscallError::InstructionError(
+                                InstructionError::InvalidRealloc,
+                            )
+                            .into());
+                        }
+                        if new_len < account_ref.data.len() {
+                            // Zero the truncated bytes so they read back as zeroes if regrown
+                            account_ref.data[new_len..].fill(0);
+                        }
+                        account_ref.data = translate_slice_mut::<u8>(
+                            memory_mapping,
+                            account_ref.vm_data_addr,
+                            new_len as u64,
+                            loader_id,
+                        )?;
+                        *account_ref.ref_to_len_in_vm = new_len as u64;
+                        *account_ref.serialized_len_ptr = new_len as u64;
+                    } else if account_ref.data.len() != account.data().len() {
                         if !account_ref.data.is_empty() {
                             // Only support for `CreateAccount` at this time.
                             // Need a way to limit total realloc size across multiple CPI calls
@@ -2657,6 +3368,362 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_syscall_mem_ops() {
+        let mut buf = vec![0u8; 64];
+        let mut cmp_result = vec![0i32; 1];
+        let buf_va = 4096;
+        let cmp_result_va = 8192;
+        let memory_mapping = MemoryMapping::new::<UserError>(
+            vec![
+                MemoryRegion {
+                    host_addr: buf.as_mut_ptr() as u64,
+                    vm_addr: buf_va,
+                    len: buf.len() as u64,
+                    vm_gap_shift: 63,
+                    is_writable: true,
+                },
+                MemoryRegion {
+                    host_addr: cmp_result.as_mut_ptr() as u64,
+                    vm_addr: cmp_result_va,
+                    len: size_of::<i32>() as u64,
+                    vm_gap_shift: 63,
+                    is_writable: true,
+                },
+            ],
+            &DEFAULT_CONFIG,
+        )
+        .unwrap();
+        let compute_meter: Rc<RefCell<dyn ComputeMeter>> =
+            Rc::new(RefCell::new(MockComputeMeter { remaining: 1_000 }));
+        let loader_id = bpf_loader::id();
+
+        // memset the first half
+        let mut syscall = SyscallMemset {
+            base_cost: 10,
+            bytes_per_unit: 4,
+            compute_meter: compute_meter.clone(),
+            loader_id: &loader_id,
+        };
+        let mut result: Result<u64, EbpfError<BpfError>> = Ok(0);
+        syscall.call(buf_va, 0x1ab, 32, 0, 0, &memory_mapping, &mut result);
+        result.unwrap();
+        assert_eq!(buf[..32], [0xab; 32]);
+        assert_eq!(buf[32..], [0; 32]);
+        assert_eq!(compute_meter.borrow().get_remaining(), 1_000 - 10 - 8);
+
+        // memcpy into the second half, overlapping copies are rejected
+        let mut syscall = SyscallMemcpy {
+            base_cost: 10,
+            bytes_per_unit: 4,
+            compute_meter: compute_meter.clone(),
+            loader_id: &loader_id,
+        };
+        let mut result: Result<u64, EbpfError<BpfError>> = Ok(0);
+        syscall.call(
+            buf_va + 40,
+            buf_va + 8,
+            16,
+            0,
+            0,
+            &memory_mapping,
+            &mut result,
+        );
+        result.unwrap();
+        assert_eq!(buf[32..40], [0; 8]);
+        assert_eq!(buf[40..56], [0xab; 16]);
+        assert_eq!(buf[56..], [0; 8]);
+        let mut result: Result<u64, EbpfError<BpfError>> = Ok(0);
+        syscall.call(buf_va + 4, buf_va, 8, 0, 0, &memory_mapping, &mut result);
+        assert_eq!(
+            Err(EbpfError::UserError(BpfError::SyscallError(
+                SyscallError::CopyOverlapping
+            ))),
+            result
+        );
+        let mut result: Result<u64, EbpfError<BpfError>> = Ok(0);
+        syscall.call(buf_va, cmp_result_va, 8, 0, 0, &memory_mapping, &mut result);
+        assert_access_violation!(result, cmp_result_va, 8);
+
+        // memmove handles overlapping copies in either direction
+        for (i, byte) in buf.iter_mut().enumerate() {
+            *byte = i as u8;
+        }
+        let mut syscall = SyscallMemmove {
+            base_cost: 10,
+            bytes_per_unit: 4,
+            compute_meter: compute_meter.clone(),
+        };
+        let mut result: Result<u64, EbpfError<BpfError>> = Ok(0);
+        syscall.call(buf_va + 1, buf_va, 4, 0, 0, &memory_mapping, &mut result);
+        result.unwrap();
+        assert_eq!(buf[..6], [0, 0, 1, 2, 3, 5]);
+        let mut result: Result<u64, EbpfError<BpfError>> = Ok(0);
+        syscall.call(buf_va, buf_va + 1, 4, 0, 0, &memory_mapping, &mut result);
+        result.unwrap();
+        assert_eq!(buf[..6], [0, 1, 2, 3, 3, 5]);
+
+        // memcmp reports the difference of the first differing bytes
+        let mut syscall = SyscallMemcmp {
+            base_cost: 10,
+            bytes_per_unit: 4,
+            compute_meter: compute_meter.clone(),
+            loader_id: &loader_id,
+        };
+        let mut result: Result<u64, EbpfError<BpfError>> = Ok(0);
+        syscall.call(
+            buf_va,
+            buf_va,
+            64,
+            cmp_result_va,
+            0,
+            &memory_mapping,
+            &mut result,
+        );
+        result.unwrap();
+        assert_eq!(cmp_result[0], 0);
+        let mut result: Result<u64, EbpfError<BpfError>> = Ok(0);
+        syscall.call(
+            buf_va,
+            buf_va + 8,
+            8,
+            cmp_result_va,
+            0,
+            &memory_mapping,
+            &mut result,
+        );
+        result.unwrap();
+        assert_eq!(cmp_result[0], -8);
+        let mut result: Result<u64, EbpfError<BpfError>> = Ok(0);
+        syscall.call(
+            buf_va + 8,
+            buf_va,
+            8,
+            cmp_result_va,
+            0,
+            &memory_mapping,
+            &mut result,
+        );
+        result.unwrap();
+        assert_eq!(cmp_result[0], 8);
+
+        // Costs scale with the number of bytes
+        let remaining = compute_meter.borrow().get_remaining();
+        let mut syscall = SyscallMemset {
+            base_cost: 10,
+            bytes_per_unit: 4,
+            compute_meter: compute_meter.clone(),
+            loader_id: &loader_id,
+        };
+        let mut result: Result<u64, EbpfError<BpfError>> = Ok(0);
+        syscall.call(buf_va, 0, 64, 0, 0, &memory_mapping, &mut result);
+        result.unwrap();
+        assert_eq!(compute_meter.borrow().get_remaining(), remaining - 10 - 16);
+        compute_meter
+            .borrow_mut()
+            .consume(remaining - 10 - 16 - 5)
+            .unwrap();
+        let mut result: Result<u64, EbpfError<BpfError>> = Ok(0);
+        syscall.call(buf_va, 0, 64, 0, 0, &memory_mapping, &mut result);
+        assert_eq!(
+            Err(EbpfError::UserError(BpfError::SyscallError(
+                SyscallError::InstructionError(InstructionError::ComputationalBudgetExceeded)
+            ))),
+            result
+        );
+    }
+
+    #[test]
+    fn test_syscall_keccak256_and_blake3() {
+        let bytes1 = "Gaggablaghblagh!";
+        let bytes2 = "flurbos";
+        let bytes_to_hash = [bytes1.as_bytes(), bytes2.as_bytes()];
+        // (vm address, length) of each slice to hash
+        let vm_slices = [4096u64, bytes1.len() as u64, 8192, bytes2.len() as u64];
+        let hash_result = [0; HASH_BYTES];
+        let vals_va = 96;
+        let rw_va = 192;
+        let memory_mapping = MemoryMapping::new::<UserError>(
+            vec![
+                MemoryRegion {
+                    host_addr: bytes1.as_ptr() as *const _ as u64,
+                    vm_addr: 4096,
+                    len: bytes1.len() as u64,
+                    vm_gap_shift: 63,
+                    is_writable: false,
+                },
+                MemoryRegion {
+                    host_addr: bytes2.as_ptr() as *const _ as u64,
+                    vm_addr: 8192,
+                    len: bytes2.len() as u64,
+                    vm_gap_shift: 63,
+                    is_writable: false,
+                },
+                MemoryRegion {
+                    host_addr: vm_slices.as_ptr() as *const _ as u64,
+                    vm_addr: vals_va,
+                    len: 32,
+                    vm_gap_shift: 63,
+                    is_writable: false,
+                },
+                MemoryRegion {
+                    host_addr: hash_result.as_ptr() as *const _ as u64,
+                    vm_addr: rw_va,
+                    len: HASH_BYTES as u64,
+                    vm_gap_shift: 63,
+                    is_writable: true,
+                },
+            ],
+            &DEFAULT_CONFIG,
+        )
+        .unwrap();
+        let vals_len = bytes_to_hash.len() as u64;
+
+        let compute_meter: Rc<RefCell<dyn ComputeMeter>> =
+            Rc::new(RefCell::new(MockComputeMeter {
+                remaining: (bytes1.len() + bytes2.len()) as u64,
+            }));
+        let mut syscall = SyscallKeccak256 {
+            base_cost: 0,
+            byte_cost: 2,
+            compute_meter,
+            loader_id: &bpf_loader::id(),
+        };
+        let mut result: Result<u64, EbpfError<BpfError>> = Ok(0);
+        syscall.call(vals_va, vals_len, rw_va, 0, 0, &memory_mapping, &mut result);
+        result.unwrap();
+        assert_eq!(
+            hash_result,
+            solana_sdk::keccak::hashv(&bytes_to_hash).to_bytes()
+        );
+        let mut result: Result<u64, EbpfError<BpfError>> = Ok(0);
+        syscall.call(vals_va, vals_len, rw_va, 0, 0, &memory_mapping, &mut result);
+        assert_eq!(
+            Err(EbpfError::UserError(BpfError::SyscallError(
+                SyscallError::InstructionError(InstructionError::ComputationalBudgetExceeded)
+            ))),
+            result
+        );
+
+        let compute_meter: Rc<RefCell<dyn ComputeMeter>> =
+            Rc::new(RefCell::new(MockComputeMeter {
+                remaining: (bytes1.len() + bytes2.len()) as u64,
+            }));
+        let mut syscall = SyscallBlake3 {
+            base_cost: 0,
+            byte_cost: 2,
+            compute_meter,
+            loader_id: &bpf_loader::id(),
+        };
+        let mut result: Result<u64, EbpfError<BpfError>> = Ok(0);
+        syscall.call(vals_va, vals_len, rw_va, 0, 0, &memory_mapping, &mut result);
+        result.unwrap();
+        assert_eq!(
+            hash_result,
+            solana_sdk::blake3::hashv(&bytes_to_hash).to_bytes()
+        );
+        let mut result: Result<u64, EbpfError<BpfError>> = Ok(0);
+        syscall.call(
+            vals_va,
+            vals_len,
+            rw_va - 1, // AccessViolation
+            0,
+            0,
+            &memory_mapping,
+            &mut result,
+        );
+        assert_access_violation!(result, rw_va - 1, HASH_BYTES as u64);
+    }
+
+    #[test]
+    fn test_syscall_secp256k1_recover() {
+        let secret_key = secp256k1::SecretKey::parse(&[7; 32]).unwrap();
+        let public_key = secp256k1::PublicKey::from_secret_key(&secret_key);
+        let hash = solana_sdk::keccak::hash(b"hello").to_bytes();
+        let (signature, recovery_id) =
+            secp256k1::sign(&secp256k1::Message::parse(&hash), &secret_key);
+        let signature = signature.serialize();
+        let recovered = [0u8; SECP256K1_PUBLIC_KEY_LENGTH];
+
+        let hash_va = 4096;
+        let signature_va = 8192;
+        let result_va = 12288;
+        let memory_mapping = MemoryMapping::new::<UserError>(
+            vec![
+                MemoryRegion::new_from_slice(&hash, hash_va, 0, false),
+                MemoryRegion::new_from_slice(&signature, signature_va, 0, false),
+                MemoryRegion::new_from_slice(&recovered, result_va, 0, true),
+            ],
+            &DEFAULT_CONFIG,
+        )
+        .unwrap();
+        let compute_meter: Rc<RefCell<dyn ComputeMeter>> =
+            Rc::new(RefCell::new(MockComputeMeter { remaining: 3 }));
+        let mut syscall = SyscallSecp256k1Recover {
+            cost: 1,
+            compute_meter,
+            loader_id: &bpf_loader::id(),
+        };
+
+        let mut result: Result<u64, EbpfError<BpfError>> = Ok(0);
+        syscall.call(
+            hash_va,
+            recovery_id.serialize() as u64,
+            signature_va,
+            result_va,
+            0,
+            &memory_mapping,
+            &mut result,
+        );
+        assert_eq!(result.unwrap(), 0);
+        assert_eq!(recovered[..], public_key.serialize()[1..]);
+
+        // Invalid inputs are reported to the program rather than aborting it
+        let mut result: Result<u64, EbpfError<BpfError>> = Ok(0);
+        syscall.call(
+            hash_va,
+            256,
+            signature_va,
+            result_va,
+            0,
+            &memory_mapping,
+            &mut result,
+        );
+        assert_eq!(
+            result.unwrap(),
+            u64::from(Secp256k1RecoverError::InvalidRecoveryId)
+        );
+
+        let mut result: Result<u64, EbpfError<BpfError>> = Ok(0);
+        syscall.call(
+            hash_va,
+            recovery_id.serialize() as u64,
+            signature_va - 1, // AccessViolation
+            result_va,
+            0,
+            &memory_mapping,
+            &mut result,
+        );
+        assert_access_violation!(result, signature_va - 1, SECP256K1_SIGNATURE_LENGTH as u64);
+
+        let mut result: Result<u64, EbpfError<BpfError>> = Ok(0);
+        syscall.call(
+            hash_va,
+            recovery_id.serialize() as u64,
+            signature_va,
+            result_va,
+            0,
+            &memory_mapping,
+            &mut result,
+        );
+        assert_eq!(
+            Err(EbpfError::UserError(BpfError::SyscallError(
+                SyscallError::InstructionError(InstructionError::ComputationalBudgetExceeded)
+            ))),
+            result
+        );
+    }
+
     #[test]
     fn test_syscall_get_sysvar() {
         // Test clock sysvar
@@ -2831,4 +3898,107 @@ mod tests {
             assert_eq!(got_rent, src_rent);
         }
     }
+
+    #[test]
+    fn test_syscall_return_data() {
+        let program_id = Pubkey::new_unique();
+        let mut invoke_context = MockInvokeContext {
+            key: program_id,
+            ..MockInvokeContext::default()
+        };
+        let invoke_context: Rc<RefCell<&mut dyn InvokeContext>> =
+            Rc::new(RefCell::new(&mut invoke_context));
+
+        let set_data = b"Hello, return data";
+        let set_va = 4096;
+        let get_data = [0u8; 32];
+        let get_va = 8192;
+        let got_program_id = Pubkey::default();
+        let got_program_id_va = 12288;
+        let memory_mapping = MemoryMapping::new::<UserError>(
+            vec![
+                MemoryRegion::new_from_slice(set_data, set_va, 0, false),
+                MemoryRegion::new_from_slice(&get_data, get_va, 0, true),
+                MemoryRegion {
+                    host_addr: &got_program_id as *const _ as u64,
+                    vm_addr: got_program_id_va,
+                    len: size_of::<Pubkey>() as u64,
+                    vm_gap_shift: 63,
+                    is_writable: true,
+                },
+            ],
+            &DEFAULT_CONFIG,
+        )
+        .unwrap();
+
+        // Nothing has been set yet
+        let mut get_syscall = SyscallGetReturnData {
+            invoke_context: invoke_context.clone(),
+            loader_id: &bpf_loader::id(),
+        };
+        let mut result: Result<u64, EbpfError<BpfError>> = Ok(0);
+        get_syscall.call(
+            get_va,
+            get_data.len() as u64,
+            got_program_id_va,
+            0,
+            0,
+            &memory_mapping,
+            &mut result,
+        );
+        assert_eq!(result.unwrap(), 0);
+
+        let mut set_syscall = SyscallSetReturnData {
+            invoke_context: invoke_context.clone(),
+            loader_id: &bpf_loader::id(),
+        };
+        let mut result: Result<u64, EbpfError<BpfError>> = Ok(0);
+        set_syscall.call(
+            set_va,
+            set_data.len() as u64,
+            0,
+            0,
+            0,
+            &memory_mapping,
+            &mut result,
+        );
+        result.unwrap();
+
+        // Only the requested length is copied, but the full length is returned
+        let mut result: Result<u64, EbpfError<BpfError>> = Ok(0);
+        get_syscall.call(
+            get_va,
+            5,
+            got_program_id_va,
+            0,
+            0,
+            &memory_mapping,
+            &mut result,
+        );
+        assert_eq!(result.unwrap(), set_data.len() as u64);
+        assert_eq!(&get_data[..5], b"Hello");
+        assert_eq!(get_data[5], 0);
+        assert_eq!(got_program_id, program_id);
+
+        // Return data larger than MAX_RETURN_DATA is rejected
+        let mut result: Result<u64, EbpfError<BpfError>> = Ok(0);
+        set_syscall.call(
+            set_va,
+            MAX_RETURN_DATA as u64 + 1,
+            0,
+            0,
+            0,
+            &memory_mapping,
+            &mut result,
+        );
+        assert_eq!(
+            result,
+            Err(EbpfError::UserError(BpfError::SyscallError(
+                SyscallError::ReturnDataTooLarge(
+                    MAX_RETURN_DATA as u64 + 1,
+                    MAX_RETURN_DATA as u64
+                )
+            )))
+        );
+    }
 }
@@ -82,6 +82,15 @@ impl Condition {
     }
 }
 
+fn is_valid_threshold(threshold: u8, conditions: &[Condition]) -> bool {
+    threshold > 0
+        && threshold as usize <= conditions.len()
+        && conditions
+            .iter()
+            .enumerate()
+            .all(|(i, cond)| !conditions[..i].contains(cond))
+}
+
 /// A data type representing a payment plan.
 #[repr(C)]
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
@@ -98,6 +107,14 @@ pub enum BudgetExpr {
 
     /// Make a payment after both of two conditions are satisfied
     And(Condition, Condition, Box<BudgetExpr>),
+
+    /// Make a payment after the given number of conditions are satisfied. Each
+    /// condition is satisfied at most once.
+    Threshold(u8, Vec<Condition>, Box<BudgetExpr>),
+
+    /// Reduce a payment plan, unless the condition is satisfied first, in which
+    /// case make the refund payment instead.
+    RefundAfter(Condition, Payment, Box<BudgetExpr>),
 }
 
 impl BudgetExpr {
@@ -170,6 +187,43 @@ impl BudgetExpr {
         )
     }
 
+    /// Create a budget that pays `lamports` to `to` after being witnessed by
+    /// `threshold` of the `witnesses`.
+    pub fn new_threshold_payment(
+        threshold: u8,
+        witnesses: &[Pubkey],
+        lamports: u64,
+        to: &Pubkey,
+    ) -> Self {
+        BudgetExpr::Threshold(
+            threshold,
+            witnesses
+                .iter()
+                .map(|witness| Condition::Signature(*witness))
+                .collect(),
+            Box::new(Self::new_payment(lamports, to)),
+        )
+    }
+
+    /// Create a budget that reduces `expr`, unless the given DateTime is signed by
+    /// `dt_pubkey` first, in which case it refunds `lamports` to `from`.
+    pub fn new_refundable_payment(
+        expr: BudgetExpr,
+        dt: DateTime<Utc>,
+        dt_pubkey: &Pubkey,
+        lamports: u64,
+        from: &Pubkey,
+    ) -> Self {
+        BudgetExpr::RefundAfter(
+            Condition::Timestamp(dt, *dt_pubkey),
+            Payment {
+                lamports,
+                to: *from,
+            },
+            Box::new(expr),
+        )
+    }
+
     /// Create a budget that pays `lamports` to `to` after the given DateTime signed
     /// by `dt_pubkey`.
     pub fn new_future_payment(
@@ -227,6 +281,27 @@ impl BudgetExpr {
             BudgetExpr::Or(a, b) => {
                 a.1.verify(spendable_lamports) && b.1.verify(spendable_lamports)
             }
+            BudgetExpr::Threshold(threshold, conditions, sub_expr) => {
+                is_valid_threshold(*threshold, conditions) && sub_expr.verify(spendable_lamports)
+            }
+            BudgetExpr::RefundAfter(_, refund, sub_expr) => {
+                refund.lamports == spendable_lamports && sub_expr.verify(spendable_lamports)
+            }
+        }
+    }
+
+    /// Return false if any threshold can never be met, is met without any
+    /// witnesses, or could count one witness twice.
+    pub fn is_well_formed(&self) -> bool {
+        match self {
+            BudgetExpr::Pay(_) => true,
+            BudgetExpr::After(_, sub_expr)
+            | BudgetExpr::And(_, _, sub_expr)
+            | BudgetExpr::RefundAfter(_, _, sub_expr) => sub_expr.is_well_formed(),
+            BudgetExpr::Or(a, b) => a.1.is_well_formed() && b.1.is_well_formed(),
+            BudgetExpr::Threshold(threshold, conditions, sub_expr) => {
+                is_valid_threshold(*threshold, conditions) && sub_expr.is_well_formed()
+            }
         }
     }
 
@@ -252,6 +327,31 @@ impl BudgetExpr {
                     None
                 }
             }
+            BudgetExpr::Threshold(threshold, conditions, sub_expr) => {
+                if let Some(i) = conditions
+                    .iter()
+                    .position(|cond| cond.is_satisfied(witness, from))
+                {
+                    conditions.remove(i);
+                    *threshold -= 1;
+                }
+                if *threshold == 0 {
+                    Some(sub_expr.clone())
+                } else {
+                    None
+                }
+            }
+            BudgetExpr::RefundAfter(cond, refund, _) if cond.is_satisfied(witness, from) => {
+                Some(Box::new(BudgetExpr::Pay(refund.clone())))
+            }
+            BudgetExpr::RefundAfter(_, _, sub_expr) => {
+                sub_expr.apply_witness(witness, from);
+                if sub_expr.final_payment().is_some() {
+                    Some(sub_expr.clone())
+                } else {
+                    None
+                }
+            }
             _ => None,
         };
         if let Some(expr) = new_expr {
@@ -368,6 +468,100 @@ mod tests {
         assert_eq!(expr, BudgetExpr::new_authorized_payment(&from1, 42, &to));
     }
 
+    #[test]
+    fn test_verify_threshold() {
+        let from0 = solana_sdk::pubkey::new_rand();
+        let from1 = solana_sdk::pubkey::new_rand();
+        let to = Pubkey::default();
+        assert!(BudgetExpr::new_threshold_payment(1, &[from0, from1], 42, &to).verify(42));
+        assert!(BudgetExpr::new_threshold_payment(2, &[from0, from1], 42, &to).verify(42));
+        assert!(!BudgetExpr::new_threshold_payment(0, &[from0, from1], 42, &to).verify(42));
+        assert!(!BudgetExpr::new_threshold_payment(3, &[from0, from1], 42, &to).verify(42));
+        assert!(!BudgetExpr::new_threshold_payment(2, &[from0, from0], 42, &to).verify(42));
+
+        // Ensure malformed thresholds are found in nested expressions.
+        let expr = BudgetExpr::new_threshold_payment(3, &[from0, from1], 42, &to);
+        let expr = BudgetExpr::After(Condition::Signature(from0), Box::new(expr));
+        assert!(!expr.verify(42));
+    }
+
+    #[test]
+    fn test_threshold_payment() {
+        let from0 = solana_sdk::pubkey::new_rand();
+        let from1 = solana_sdk::pubkey::new_rand();
+        let from2 = solana_sdk::pubkey::new_rand();
+        let to = Pubkey::default();
+
+        let mut expr = BudgetExpr::new_threshold_payment(2, &[from0, from1, from2], 42, &to);
+        expr.apply_witness(&Witness::Signature, &from1);
+        assert_eq!(
+            expr,
+            BudgetExpr::new_threshold_payment(1, &[from0, from2], 42, &to)
+        );
+
+        // Ensure the same witness can't be counted twice.
+        expr.apply_witness(&Witness::Signature, &from1);
+        assert_eq!(
+            expr,
+            BudgetExpr::new_threshold_payment(1, &[from0, from2], 42, &to)
+        );
+
+        expr.apply_witness(&Witness::Signature, &from2);
+        assert_eq!(expr, BudgetExpr::new_payment(42, &to));
+    }
+
+    #[test]
+    fn test_unauthorized_threshold_payment() {
+        let from0 = solana_sdk::pubkey::new_rand();
+        let from1 = solana_sdk::pubkey::new_rand();
+        let to = solana_sdk::pubkey::new_rand();
+
+        let mut expr = BudgetExpr::new_threshold_payment(1, &[from0, from1], 42, &to);
+        let orig_expr = expr.clone();
+        expr.apply_witness(&Witness::Signature, &to); // <-- Attack!
+        assert_eq!(expr, orig_expr);
+    }
+
+    #[test]
+    fn test_refundable_payment() {
+        let dt = Utc.ymd(2014, 11, 14).and_hms(8, 9, 10);
+        let from = solana_sdk::pubkey::new_rand();
+        let from0 = solana_sdk::pubkey::new_rand();
+        let from1 = solana_sdk::pubkey::new_rand();
+        let to = solana_sdk::pubkey::new_rand();
+
+        let multisig = BudgetExpr::new_2_2_multisig_payment(&from0, &from1, 42, &to);
+        let expr = BudgetExpr::new_refundable_payment(multisig, dt, &from, 42, &from);
+        assert!(expr.verify(42));
+        assert!(!expr.verify(41));
+
+        // Witnesses reduce the payment plan until it pays out.
+        let mut paid_expr = expr.clone();
+        paid_expr.apply_witness(&Witness::Signature, &from0);
+        assert_eq!(
+            paid_expr,
+            BudgetExpr::new_refundable_payment(
+                BudgetExpr::new_authorized_payment(&from1, 42, &to),
+                dt,
+                &from,
+                42,
+                &from
+            )
+        );
+        paid_expr.apply_witness(&Witness::Signature, &from1);
+        assert_eq!(paid_expr, BudgetExpr::new_payment(42, &to));
+
+        // Nothing is refunded before the expiry.
+        let mut refunded_expr = expr.clone();
+        let early_dt = Utc.ymd(2014, 11, 13).and_hms(8, 9, 10);
+        refunded_expr.apply_witness(&Witness::Timestamp(early_dt), &from);
+        assert_eq!(refunded_expr, expr);
+
+        refunded_expr.apply_witness(&Witness::Signature, &from0);
+        refunded_expr.apply_witness(&Witness::Timestamp(dt), &from);
+        assert_eq!(refunded_expr, BudgetExpr::new_payment(42, &from));
+    }
+
     #[test]
     fn test_multisig_after_ts() {
         let from0 = solana_sdk::pubkey::new_rand();
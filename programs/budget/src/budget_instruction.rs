@@ -16,6 +16,9 @@ use thiserror::Error;
 pub enum BudgetError {
     #[error("destination missing")]
     DestinationMissing,
+
+    #[error("invalid threshold")]
+    InvalidThreshold,
 }
 
 impl<T> DecodeError<T> for BudgetError {
@@ -103,6 +106,26 @@ pub fn when_signed(
     create_account(from, contract, lamports, expr)
 }
 
+/// Create a payment script that pays out after `threshold` of the `witnesses`
+/// sign, or, if given an `expiry`, refunds `from` once the expiry's DateTime is
+/// signed by its `Pubkey` first.
+#[allow(clippy::too_many_arguments)]
+pub fn when_signed_by_threshold(
+    from: &Pubkey,
+    to: &Pubkey,
+    contract: &Pubkey,
+    threshold: u8,
+    witnesses: &[Pubkey],
+    expiry: Option<(DateTime<Utc>, Pubkey)>,
+    lamports: u64,
+) -> Vec<Instruction> {
+    let mut expr = BudgetExpr::new_threshold_payment(threshold, witnesses, lamports, to);
+    if let Some((dt, dt_pubkey)) = expiry {
+        expr = BudgetExpr::new_refundable_payment(expr, dt, &dt_pubkey, lamports, from);
+    }
+    create_account(from, contract, lamports, expr)
+}
+
 /// Make a payment when an account has the given data
 pub fn when_account_data(
     from: &Pubkey,
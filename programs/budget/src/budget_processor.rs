@@ -1,6 +1,6 @@
 //! budget program
 use crate::{
-    budget_expr::Witness,
+    budget_expr::{Payment, Witness},
     budget_instruction::{BudgetError, BudgetInstruction},
     budget_state::BudgetState,
 };
@@ -16,6 +16,34 @@ use solana_sdk::{
     pubkey::Pubkey,
 };
 
+/// Make the final payment of a payment plan. The payment is made to the
+/// witness if it signed and is the payee, else to the `to` account.
+fn apply_payment(
+    budget_state: &mut BudgetState,
+    payment: Payment,
+    witness_keyed_account: &KeyedAccount,
+    contract_keyed_account: &KeyedAccount,
+    to_keyed_account: Result<&KeyedAccount, InstructionError>,
+) -> Result<(), InstructionError> {
+    if let Some(key) = witness_keyed_account.signer_key() {
+        if &payment.to == key {
+            budget_state.pending_budget = None;
+            contract_keyed_account.try_account_ref_mut()?.lamports -= payment.lamports;
+            witness_keyed_account.try_account_ref_mut()?.lamports += payment.lamports;
+            return Ok(());
+        }
+    }
+    let to_keyed_account = to_keyed_account?;
+    if &payment.to != to_keyed_account.unsigned_key() {
+        trace!("destination missing");
+        return Err(BudgetError::DestinationMissing.into());
+    }
+    budget_state.pending_budget = None;
+    contract_keyed_account.try_account_ref_mut()?.lamports -= payment.lamports;
+    to_keyed_account.try_account_ref_mut()?.lamports += payment.lamports;
+    Ok(())
+}
+
 /// Process a Witness Signature. Any payment plans waiting on this signature
 /// will progress one step.
 fn apply_signature(
@@ -32,22 +60,13 @@ fn apply_signature(
     }
 
     if let Some(payment) = final_payment {
-        if let Some(key) = witness_keyed_account.signer_key() {
-            if &payment.to == key {
-                budget_state.pending_budget = None;
-                contract_keyed_account.try_account_ref_mut()?.lamports -= payment.lamports;
-                witness_keyed_account.try_account_ref_mut()?.lamports += payment.lamports;
-                return Ok(());
-            }
-        }
-        let to_keyed_account = to_keyed_account?;
-        if &payment.to != to_keyed_account.unsigned_key() {
-            trace!("destination missing");
-            return Err(BudgetError::DestinationMissing.into());
-        }
-        budget_state.pending_budget = None;
-        contract_keyed_account.try_account_ref_mut()?.lamports -= payment.lamports;
-        to_keyed_account.try_account_ref_mut()?.lamports += payment.lamports;
+        apply_payment(
+            budget_state,
+            payment,
+            witness_keyed_account,
+            contract_keyed_account,
+            to_keyed_account,
+        )?;
     }
     Ok(())
 }
@@ -71,14 +90,13 @@ fn apply_timestamp(
     }
 
     if let Some(payment) = final_payment {
-        let to_keyed_account = to_keyed_account?;
-        if &payment.to != to_keyed_account.unsigned_key() {
-            trace!("destination missing");
-            return Err(BudgetError::DestinationMissing.into());
-        }
-        budget_state.pending_budget = None;
-        contract_keyed_account.try_account_ref_mut()?.lamports -= payment.lamports;
-        to_keyed_account.try_account_ref_mut()?.lamports += payment.lamports;
+        apply_payment(
+            budget_state,
+            payment,
+            witness_keyed_account,
+            contract_keyed_account,
+            to_keyed_account,
+        )?;
     }
     Ok(())
 }
@@ -102,14 +120,13 @@ fn apply_account_data(
     }
 
     if let Some(payment) = final_payment {
-        let to_keyed_account = to_keyed_account?;
-        if &payment.to != to_keyed_account.unsigned_key() {
-            trace!("destination missing");
-            return Err(BudgetError::DestinationMissing.into());
-        }
-        budget_state.pending_budget = None;
-        contract_keyed_account.try_account_ref_mut()?.lamports -= payment.lamports;
-        to_keyed_account.try_account_ref_mut()?.lamports += payment.lamports;
+        apply_payment(
+            budget_state,
+            payment,
+            witness_keyed_account,
+            contract_keyed_account,
+            to_keyed_account,
+        )?;
     }
     Ok(())
 }
@@ -136,6 +153,10 @@ pub fn process_instruction(
                 to_keyed_account.try_account_ref_mut()?.lamports += payment.lamports;
                 return Ok(());
             }
+            if !expr.is_well_formed() {
+                trace!("invalid threshold");
+                return Err(BudgetError::InvalidThreshold.into());
+            }
             let existing =
                 BudgetState::deserialize(&contract_keyed_account.try_account_ref_mut()?.data())
                     .ok();
@@ -243,6 +264,7 @@ pub fn process_instruction(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::budget_expr::BudgetExpr;
     use crate::budget_instruction;
     use crate::id;
     use solana_runtime::bank::Bank;
@@ -251,7 +273,7 @@ mod tests {
     use solana_sdk::client::SyncClient;
     use solana_sdk::genesis_config::create_genesis_config;
     use solana_sdk::hash::hash;
-    use solana_sdk::instruction::InstructionError;
+    use solana_sdk::instruction::{AccountMeta, Instruction, InstructionError};
     use solana_sdk::message::Message;
     use solana_sdk::signature::{Keypair, Signer};
     use solana_sdk::transaction::TransactionError;
@@ -604,4 +626,168 @@ mod tests {
         assert_eq!(bank_client.get_balance(&bob_pubkey).unwrap(), 42);
         assert_eq!(bank_client.get_account_data(&budget_pubkey).unwrap(), None);
     }
+
+    // Enough to keep contracts rent-exempt
+    const LAMPORTS: u64 = 1_000_000_000;
+
+    fn send_witness_instruction(
+        bank_client: &BankClient,
+        payer_keypair: &Keypair,
+        witness_keypair: &Keypair,
+        instruction: Instruction,
+    ) {
+        let message = Message::new(&[instruction], Some(&payer_keypair.pubkey()));
+        bank_client
+            .send_and_confirm_message(&[payer_keypair, witness_keypair], message)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_initialize_invalid_threshold() {
+        let (bank, alice_keypair) = create_bank(10_000);
+        let bank_client = BankClient::new(bank);
+        let alice_pubkey = alice_keypair.pubkey();
+        let budget_keypair = Keypair::new();
+        let budget_pubkey = budget_keypair.pubkey();
+        let bob_pubkey = solana_sdk::pubkey::new_rand();
+        let witness = solana_sdk::pubkey::new_rand();
+
+        let mut instructions = budget_instruction::when_signed_by_threshold(
+            &alice_pubkey,
+            &bob_pubkey,
+            &budget_pubkey,
+            1,
+            &[witness],
+            None,
+            1,
+        );
+        // Attack! A threshold met without any witnesses.
+        let expr = BudgetExpr::new_threshold_payment(0, &[witness], 1, &bob_pubkey);
+        instructions[1] = Instruction::new_with_bincode(
+            id(),
+            &BudgetInstruction::InitializeAccount(Box::new(expr)),
+            vec![AccountMeta::new(budget_pubkey, false)],
+        );
+        let message = Message::new(&instructions, Some(&alice_pubkey));
+        assert_eq!(
+            bank_client
+                .send_and_confirm_message(&[&alice_keypair, &budget_keypair], message)
+                .unwrap_err()
+                .unwrap(),
+            TransactionError::InstructionError(
+                1,
+                InstructionError::Custom(BudgetError::InvalidThreshold as u32)
+            )
+        );
+    }
+
+    #[test]
+    fn test_pay_on_threshold() {
+        let (bank, alice_keypair) = create_bank(10_000_000_000);
+        let bank_client = BankClient::new(bank);
+        let alice_pubkey = alice_keypair.pubkey();
+        let budget_keypair = Keypair::new();
+        let budget_pubkey = budget_keypair.pubkey();
+        let bob_pubkey = solana_sdk::pubkey::new_rand();
+        let witness_keypairs = vec![Keypair::new(), Keypair::new(), Keypair::new()];
+        let witnesses: Vec<_> = witness_keypairs.iter().map(|k| k.pubkey()).collect();
+
+        let instructions = budget_instruction::when_signed_by_threshold(
+            &alice_pubkey,
+            &bob_pubkey,
+            &budget_pubkey,
+            2,
+            &witnesses,
+            None,
+            LAMPORTS,
+        );
+        let message = Message::new(&instructions, Some(&alice_pubkey));
+        bank_client
+            .send_and_confirm_message(&[&alice_keypair, &budget_keypair], message)
+            .unwrap();
+
+        let instruction =
+            budget_instruction::apply_signature(&witnesses[1], &budget_pubkey, &bob_pubkey);
+        send_witness_instruction(
+            &bank_client,
+            &alice_keypair,
+            &witness_keypairs[1],
+            instruction,
+        );
+        assert_eq!(bank_client.get_balance(&budget_pubkey).unwrap(), LAMPORTS);
+
+        // Ensure a second signature from the same witness isn't counted.
+        let instruction =
+            budget_instruction::apply_signature(&witnesses[1], &budget_pubkey, &alice_pubkey);
+        send_witness_instruction(
+            &bank_client,
+            &alice_keypair,
+            &witness_keypairs[1],
+            instruction,
+        );
+        assert_eq!(bank_client.get_balance(&budget_pubkey).unwrap(), LAMPORTS);
+        assert_eq!(bank_client.get_balance(&bob_pubkey).unwrap(), 0);
+
+        let instruction =
+            budget_instruction::apply_signature(&witnesses[2], &budget_pubkey, &bob_pubkey);
+        send_witness_instruction(
+            &bank_client,
+            &alice_keypair,
+            &witness_keypairs[2],
+            instruction,
+        );
+        assert_eq!(bank_client.get_balance(&bob_pubkey).unwrap(), LAMPORTS);
+        assert_eq!(bank_client.get_account_data(&budget_pubkey).unwrap(), None);
+    }
+
+    #[test]
+    fn test_refund_after_expiry() {
+        let (bank, alice_keypair) = create_bank(10_000_000_000);
+        let bank_client = BankClient::new(bank);
+        let alice_pubkey = alice_keypair.pubkey();
+        let budget_keypair = Keypair::new();
+        let budget_pubkey = budget_keypair.pubkey();
+        let bob_pubkey = solana_sdk::pubkey::new_rand();
+        let witness_keypairs = vec![Keypair::new(), Keypair::new()];
+        let witnesses: Vec<_> = witness_keypairs.iter().map(|k| k.pubkey()).collect();
+        let dt = Utc::now();
+
+        let instructions = budget_instruction::when_signed_by_threshold(
+            &alice_pubkey,
+            &bob_pubkey,
+            &budget_pubkey,
+            2,
+            &witnesses,
+            Some((dt, alice_pubkey)),
+            LAMPORTS,
+        );
+        let message = Message::new(&instructions, Some(&alice_pubkey));
+        bank_client
+            .send_and_confirm_message(&[&alice_keypair, &budget_keypair], message)
+            .unwrap();
+        let alice_balance = bank_client.get_balance(&alice_pubkey).unwrap();
+
+        let instruction =
+            budget_instruction::apply_signature(&witnesses[0], &budget_pubkey, &bob_pubkey);
+        send_witness_instruction(
+            &bank_client,
+            &alice_keypair,
+            &witness_keypairs[0],
+            instruction,
+        );
+        assert_eq!(bank_client.get_balance(&budget_pubkey).unwrap(), LAMPORTS);
+
+        // The threshold wasn't met before the expiry, so Alice gets her funds back.
+        let instruction =
+            budget_instruction::apply_timestamp(&alice_pubkey, &budget_pubkey, &alice_pubkey, dt);
+        bank_client
+            .send_and_confirm_instruction(&alice_keypair, instruction)
+            .unwrap();
+        assert_eq!(
+            bank_client.get_balance(&alice_pubkey).unwrap(),
+            alice_balance + LAMPORTS
+        );
+        assert_eq!(bank_client.get_balance(&bob_pubkey).unwrap(), 0);
+        assert_eq!(bank_client.get_account_data(&budget_pubkey).unwrap(), None);
+    }
 }
@@ -0,0 +1,61 @@
+use solana_sdk::{
+    instruction::InstructionError, keyed_account::KeyedAccount, process_instruction::InvokeContext,
+    pubkey::Pubkey,
+};
+
+pub fn process_instruction(
+    _program_id: &Pubkey,
+    _keyed_accounts: &[KeyedAccount],
+    _data: &[u8],
+    _invoke_context: &mut dyn InvokeContext,
+) -> Result<(), InstructionError> {
+    // Should be already checked by now.
+    Ok(())
+}
+
+#[cfg(test)]
+pub mod test {
+    use rand::{thread_rng, Rng};
+    use solana_sdk::{
+        ed25519_instruction::{new_ed25519_instruction, SIGNATURE_OFFSETS_START},
+        feature_set::FeatureSet,
+        hash::Hash,
+        signature::{Keypair, Signer},
+        transaction::Transaction,
+    };
+
+    #[test]
+    fn test_ed25519() {
+        let privkey = Keypair::new();
+        let message_arr = b"hello";
+        let mut instruction = new_ed25519_instruction(&privkey, message_arr);
+        let mint_keypair = Keypair::new();
+        let feature_set = FeatureSet::all_enabled();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction.clone()],
+            Some(&mint_keypair.pubkey()),
+            &[&mint_keypair],
+            Hash::default(),
+        );
+
+        assert!(tx.verify_precompiles_with_features(&feature_set).is_ok());
+
+        // Skip the signature count and the padding byte, which isn't checked
+        let index = thread_rng().gen_range(SIGNATURE_OFFSETS_START, instruction.data.len());
+        instruction.data[index] = instruction.data[index].wrapping_add(12);
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&mint_keypair.pubkey()),
+            &[&mint_keypair],
+            Hash::default(),
+        );
+        assert!(tx.verify_precompiles_with_features(&feature_set).is_err());
+
+        // Precompile instructions aren't verified until the program is enabled
+        assert!(tx
+            .verify_precompiles_with_features(&FeatureSet::default())
+            .is_ok());
+        assert!(tx.verify_precompiles().is_ok());
+    }
+}
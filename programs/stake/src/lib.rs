@@ -1,6 +1,8 @@
 #![cfg_attr(RUSTC_WITH_SPECIALIZATION, feature(specialization))]
 #![allow(clippy::integer_arithmetic)]
-use solana_sdk::genesis_config::GenesisConfig;
+use solana_sdk::{
+    feature_set, genesis_config::GenesisConfig, native_token::LAMPORTS_PER_SOL, pubkey::Pubkey,
+};
 
 pub mod config;
 pub mod stake_instruction;
@@ -8,9 +10,24 @@ pub mod stake_state;
 
 solana_sdk::declare_id!("Stake11111111111111111111111111111111111111");
 
+/// The minimum stake delegation, in SOL, once
+/// `stake_raise_minimum_delegation_to_1_sol` is active
+pub const MINIMUM_DELEGATION_SOL: u64 = 1;
+
 pub fn add_genesis_accounts(genesis_config: &mut GenesisConfig) -> u64 {
     config::add_genesis_account(genesis_config)
 }
 
+/// The minimum number of lamports a stake account may delegate, or hold
+/// delegated after a split or merge.  There is no minimum until
+/// `stake_raise_minimum_delegation_to_1_sol` is activated.
+pub fn get_minimum_delegation(is_feature_active: impl Fn(&Pubkey) -> bool) -> u64 {
+    if is_feature_active(&feature_set::stake_raise_minimum_delegation_to_1_sol::id()) {
+        MINIMUM_DELEGATION_SOL * LAMPORTS_PER_SOL
+    } else {
+        0
+    }
+}
+
 #[macro_use]
 extern crate solana_frozen_abi_macro;
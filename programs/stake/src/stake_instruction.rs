@@ -15,7 +15,9 @@ use solana_sdk::{
     program_utils::limited_deserialize,
     pubkey::Pubkey,
     system_instruction,
-    sysvar::{self, clock::Clock, rent::Rent, stake_history::StakeHistory},
+    sysvar::{
+        self, clock::Clock, epoch_rewards::EpochRewards, rent::Rent, stake_history::StakeHistory,
+    },
 };
 use thiserror::Error;
 
@@ -48,6 +50,29 @@ pub enum StakeError {
 
     #[error("custodian signature not present")]
     CustodianSignatureMissing,
+
+    #[error("only fully active stake can be redelegated")]
+    RedelegateTransientOrInactiveStake,
+
+    #[error("stake is already delegated to this vote account")]
+    RedelegateToSameVoteAccount,
+
+    #[error("insufficient voting activity in the reference vote account")]
+    InsufficientReferenceVotes,
+
+    #[error("stake account is not delegated to the provided vote account")]
+    VoteAddressMismatch,
+
+    #[error(
+        "stake account has not been delinquent for the minimum epochs required for deactivation"
+    )]
+    MinimumDelinquentEpochsForDeactivationNotMet,
+
+    #[error("delegation amount is less than the minimum")]
+    InsufficientDelegation,
+
+    #[error("stake accounts cannot be modified while epoch rewards are being distributed")]
+    EpochRewardsActive,
 }
 
 impl<E> DecodeError<E> for StakeError {
@@ -166,6 +191,53 @@ pub enum StakeInstruction {
     ///   3. Optional: [SIGNER] Lockup authority, if updating StakeAuthorize::Withdrawer before
     ///      lockup expiration
     AuthorizeWithSeed(AuthorizeWithSeedArgs),
+
+    /// Redelegate activated stake to another vote account without waiting for
+    /// it to cool down.
+    ///
+    /// The effective stake is moved to the uninitialized stake account, which
+    /// takes on the authorities and lockup of the source and is delegated to
+    /// the new vote account, activating this epoch.  Its rent-exempt reserve
+    /// is paid out of the moved stake unless it was funded beforehand.
+    ///
+    /// The source stake account is deactivated and keeps its rent-exempt
+    /// reserve and any lamports that were not part of the effective stake; it
+    /// can be withdrawn once its stake has cooled down.
+    ///
+    /// Only fully active stake can be redelegated, and not to the vote account
+    /// it is already delegated to.
+    ///
+    /// # Account references
+    ///   0. [WRITE] Delegated stake account to be redelegated
+    ///   1. [WRITE] Uninitialized stake account that will hold the redelegated stake
+    ///   2. [] Vote account to which the stake will be delegated
+    ///   3. [] Clock sysvar
+    ///   4. [] Stake history sysvar that carries stake warmup/cooldown history
+    ///   5. [] Address of config account that carries stake config
+    ///   6. [SIGNER] Stake authority
+    Redelegate,
+
+    /// Deactivate stake delegated to a vote account that has been delinquent
+    /// for at least `MINIMUM_DELINQUENT_EPOCHS_FOR_DEACTIVATION` epochs.
+    ///
+    /// No signer is required.  The delinquent vote account must have earned no
+    /// credits in the last `MINIMUM_DELINQUENT_EPOCHS_FOR_DEACTIVATION` epochs,
+    /// while the reference vote account must have earned credits in each of
+    /// them, including the current epoch.
+    ///
+    /// # Account references
+    ///   0. [WRITE] Delegated stake account
+    ///   1. [] Delinquent vote account for the delegated stake account
+    ///   2. [] Reference vote account that has voted in each of the last
+    ///      `MINIMUM_DELINQUENT_EPOCHS_FOR_DEACTIVATION` epochs
+    ///   3. [] Clock sysvar
+    DeactivateDelinquent,
+
+    /// Get the minimum stake delegation, in lamports
+    ///
+    /// The minimum is set as little-endian `u64` return data.  No accounts
+    /// are required.
+    GetMinimumDelegation,
 }
 
 #[derive(Default, Debug, Serialize, Deserialize, PartialEq, Clone, Copy)]
@@ -438,6 +510,74 @@ pub fn delegate_stake(
     Instruction::new_with_bincode(id(), &StakeInstruction::DelegateStake, account_metas)
 }
 
+fn _redelegate(
+    stake_pubkey: &Pubkey,
+    authorized_pubkey: &Pubkey,
+    vote_pubkey: &Pubkey,
+    uninitialized_stake_pubkey: &Pubkey,
+) -> Instruction {
+    let account_metas = vec![
+        AccountMeta::new(*stake_pubkey, false),
+        AccountMeta::new(*uninitialized_stake_pubkey, false),
+        AccountMeta::new_readonly(*vote_pubkey, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(sysvar::stake_history::id(), false),
+        AccountMeta::new_readonly(crate::config::id(), false),
+        AccountMeta::new_readonly(*authorized_pubkey, true),
+    ];
+    Instruction::new_with_bincode(id(), &StakeInstruction::Redelegate, account_metas)
+}
+
+pub fn redelegate(
+    stake_pubkey: &Pubkey,
+    authorized_pubkey: &Pubkey,
+    vote_pubkey: &Pubkey,
+    uninitialized_stake_pubkey: &Pubkey,
+) -> Vec<Instruction> {
+    vec![
+        system_instruction::create_account(
+            authorized_pubkey, // Sending 0, so any signer will suffice
+            uninitialized_stake_pubkey,
+            0,
+            std::mem::size_of::<StakeState>() as u64,
+            &id(),
+        ),
+        _redelegate(
+            stake_pubkey,
+            authorized_pubkey,
+            vote_pubkey,
+            uninitialized_stake_pubkey,
+        ),
+    ]
+}
+
+pub fn redelegate_with_seed(
+    stake_pubkey: &Pubkey,
+    authorized_pubkey: &Pubkey,
+    vote_pubkey: &Pubkey,
+    uninitialized_stake_pubkey: &Pubkey, // derived using create_with_seed()
+    base: &Pubkey,                       // base
+    seed: &str,                          // seed
+) -> Vec<Instruction> {
+    vec![
+        system_instruction::create_account_with_seed(
+            authorized_pubkey, // Sending 0, so any signer will suffice
+            uninitialized_stake_pubkey,
+            base,
+            seed,
+            0,
+            std::mem::size_of::<StakeState>() as u64,
+            &id(),
+        ),
+        _redelegate(
+            stake_pubkey,
+            authorized_pubkey,
+            vote_pubkey,
+            uninitialized_stake_pubkey,
+        ),
+    ]
+}
+
 pub fn withdraw(
     stake_pubkey: &Pubkey,
     withdrawer_pubkey: &Pubkey,
@@ -469,6 +609,24 @@ pub fn deactivate_stake(stake_pubkey: &Pubkey, authorized_pubkey: &Pubkey) -> In
     Instruction::new_with_bincode(id(), &StakeInstruction::Deactivate, account_metas)
 }
 
+pub fn deactivate_delinquent_stake(
+    stake_pubkey: &Pubkey,
+    delinquent_vote_pubkey: &Pubkey,
+    reference_vote_pubkey: &Pubkey,
+) -> Instruction {
+    let account_metas = vec![
+        AccountMeta::new(*stake_pubkey, false),
+        AccountMeta::new_readonly(*delinquent_vote_pubkey, false),
+        AccountMeta::new_readonly(*reference_vote_pubkey, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+    ];
+    Instruction::new_with_bincode(id(), &StakeInstruction::DeactivateDelinquent, account_metas)
+}
+
+pub fn get_minimum_delegation() -> Instruction {
+    Instruction::new_with_bincode(id(), &StakeInstruction::GetMinimumDelegation, vec![])
+}
+
 pub fn set_lockup(
     stake_pubkey: &Pubkey,
     lockup: &LockupArgs,
@@ -491,25 +649,53 @@ pub fn process_instruction(
     trace!("keyed_accounts: {:?}", keyed_accounts);
 
     let signers = get_signers(keyed_accounts);
-
-    let keyed_accounts = &mut keyed_accounts.iter();
-    let me = &next_keyed_account(keyed_accounts)?;
-
-    if me.owner()? != id() {
-        if invoke_context.is_feature_active(&feature_set::check_program_owner::id()) {
-            return Err(InstructionError::InvalidAccountOwner);
-        } else {
-            return Err(InstructionError::IncorrectProgramId);
+    let minimum_delegation =
+        crate::get_minimum_delegation(|feature_id| invoke_context.is_feature_active(feature_id));
+
+    let instruction = limited_deserialize(data)?;
+
+    // stake accounts are locked until every partition of the epoch rewards is credited
+    if invoke_context.is_feature_active(&feature_set::partitioned_epoch_rewards::id())
+        && !matches!(instruction, StakeInstruction::GetMinimumDelegation)
+    {
+        let epoch_rewards_active = invoke_context
+            .get_sysvar_data(&sysvar::epoch_rewards::id())
+            .and_then(|data| bincode::deserialize::<EpochRewards>(&data).ok())
+            .map(|epoch_rewards| epoch_rewards.active)
+            .unwrap_or(false);
+        if epoch_rewards_active {
+            return Err(StakeError::EpochRewardsActive.into());
         }
     }
 
-    match limited_deserialize(data)? {
-        StakeInstruction::Initialize(authorized, lockup) => me.initialize(
-            &authorized,
-            &lockup,
-            &from_keyed_account::<Rent>(next_keyed_account(keyed_accounts)?)?,
-        ),
+    // every instruction but GetMinimumDelegation operates on the stake account
+    let check_program_owner =
+        invoke_context.is_feature_active(&feature_set::check_program_owner::id());
+    let stake_account = keyed_accounts.first();
+    let get_stake_account = || {
+        let me = stake_account.ok_or(InstructionError::NotEnoughAccountKeys)?;
+        if me.owner()? != id() {
+            if check_program_owner {
+                return Err(InstructionError::InvalidAccountOwner);
+            } else {
+                return Err(InstructionError::IncorrectProgramId);
+            }
+        }
+        Ok(me)
+    };
+    let keyed_accounts = &mut keyed_accounts.iter().skip(1);
+
+    match instruction {
+        StakeInstruction::Initialize(authorized, lockup) => {
+            let me = get_stake_account()?;
+            me.initialize(
+                &authorized,
+                &lockup,
+                &from_keyed_account::<Rent>(next_keyed_account(keyed_accounts)?)?,
+            )
+        }
         StakeInstruction::Authorize(authorized_pubkey, stake_authorize) => {
+            let me = get_stake_account()?;
             let require_custodian_for_locked_stake_authorize = invoke_context.is_feature_active(
                 &feature_set::require_custodian_for_locked_stake_authorize::id(),
             );
@@ -539,6 +725,7 @@ pub fn process_instruction(
             }
         }
         StakeInstruction::AuthorizeWithSeed(args) => {
+            let me = get_stake_account()?;
             let authority_base = next_keyed_account(keyed_accounts)?;
             let require_custodian_for_locked_stake_authorize = invoke_context.is_feature_active(
                 &feature_set::require_custodian_for_locked_stake_authorize::id(),
@@ -572,6 +759,7 @@ pub fn process_instruction(
             }
         }
         StakeInstruction::DelegateStake => {
+            let me = get_stake_account()?;
             let vote = next_keyed_account(keyed_accounts)?;
 
             me.delegate(
@@ -579,25 +767,30 @@ pub fn process_instruction(
                 &from_keyed_account::<Clock>(next_keyed_account(keyed_accounts)?)?,
                 &from_keyed_account::<StakeHistory>(next_keyed_account(keyed_accounts)?)?,
                 &config::from_keyed_account(next_keyed_account(keyed_accounts)?)?,
+                minimum_delegation,
                 &signers,
             )
         }
         StakeInstruction::Split(lamports) => {
+            let me = get_stake_account()?;
             let split_stake = &next_keyed_account(keyed_accounts)?;
-            me.split(lamports, split_stake, &signers)
+            me.split(lamports, split_stake, minimum_delegation, &signers)
         }
         StakeInstruction::Merge => {
+            let me = get_stake_account()?;
             let source_stake = &next_keyed_account(keyed_accounts)?;
             me.merge(
                 invoke_context,
                 source_stake,
                 &from_keyed_account::<Clock>(next_keyed_account(keyed_accounts)?)?,
                 &from_keyed_account::<StakeHistory>(next_keyed_account(keyed_accounts)?)?,
+                minimum_delegation,
                 &signers,
             )
         }
 
         StakeInstruction::Withdraw(lamports) => {
+            let me = get_stake_account()?;
             let to = &next_keyed_account(keyed_accounts)?;
             me.withdraw(
                 lamports,
@@ -608,12 +801,55 @@ pub fn process_instruction(
                 keyed_accounts.next(),
             )
         }
-        StakeInstruction::Deactivate => me.deactivate(
-            &from_keyed_account::<Clock>(next_keyed_account(keyed_accounts)?)?,
-            &signers,
-        ),
+        StakeInstruction::Deactivate => {
+            let me = get_stake_account()?;
+            me.deactivate(
+                &from_keyed_account::<Clock>(next_keyed_account(keyed_accounts)?)?,
+                &signers,
+            )
+        }
 
-        StakeInstruction::SetLockup(lockup) => me.set_lockup(&lockup, &signers),
+        StakeInstruction::SetLockup(lockup) => {
+            let me = get_stake_account()?;
+            me.set_lockup(&lockup, &signers)
+        }
+        StakeInstruction::Redelegate => {
+            let me = get_stake_account()?;
+            if !invoke_context.is_feature_active(&feature_set::stake_redelegate_instruction::id()) {
+                return Err(InstructionError::InvalidInstructionData);
+            }
+            let uninitialized_stake = &next_keyed_account(keyed_accounts)?;
+            let vote = next_keyed_account(keyed_accounts)?;
+
+            me.redelegate(
+                uninitialized_stake,
+                &vote,
+                &from_keyed_account::<Clock>(next_keyed_account(keyed_accounts)?)?,
+                &from_keyed_account::<StakeHistory>(next_keyed_account(keyed_accounts)?)?,
+                &config::from_keyed_account(next_keyed_account(keyed_accounts)?)?,
+                minimum_delegation,
+                &signers,
+            )
+        }
+        StakeInstruction::DeactivateDelinquent => {
+            let me = get_stake_account()?;
+            if !invoke_context
+                .is_feature_active(&feature_set::stake_deactivate_delinquent_instruction::id())
+            {
+                return Err(InstructionError::InvalidInstructionData);
+            }
+            let delinquent_vote = next_keyed_account(keyed_accounts)?;
+            let reference_vote = next_keyed_account(keyed_accounts)?;
+
+            me.deactivate_delinquent(
+                delinquent_vote,
+                reference_vote,
+                &from_keyed_account::<Clock>(next_keyed_account(keyed_accounts)?)?,
+            )
+        }
+        StakeInstruction::GetMinimumDelegation => {
+            invoke_context.set_return_data(minimum_delegation.to_le_bytes().to_vec())
+        }
     }
 }
 
@@ -798,6 +1034,25 @@ mod tests {
             )),
             Err(InstructionError::InvalidAccountData),
         );
+        assert_eq!(
+            process_instruction(
+                &redelegate(
+                    &Pubkey::default(),
+                    &Pubkey::default(),
+                    &invalid_vote_state_pubkey(),
+                    &invalid_stake_state_pubkey(),
+                )[1]
+            ),
+            Err(InstructionError::InvalidAccountData),
+        );
+        assert_eq!(
+            process_instruction(&deactivate_delinquent_stake(
+                &Pubkey::default(),
+                &invalid_vote_state_pubkey(),
+                &invalid_vote_state_pubkey(),
+            )),
+            Err(InstructionError::InvalidAccountData),
+        );
     }
 
     #[test]
@@ -908,6 +1163,36 @@ mod tests {
             )),
             Err(InstructionError::InvalidAccountOwner),
         );
+        assert_eq!(
+            process_instruction(
+                &redelegate(
+                    &spoofed_stake_state_pubkey(),
+                    &Pubkey::default(),
+                    &Pubkey::default(),
+                    &Pubkey::default(),
+                )[1]
+            ),
+            Err(InstructionError::InvalidAccountOwner),
+        );
+        assert_eq!(
+            process_instruction(
+                &redelegate(
+                    &Pubkey::default(),
+                    &Pubkey::default(),
+                    &Pubkey::default(),
+                    &spoofed_stake_state_pubkey(),
+                )[1]
+            ),
+            Err(InstructionError::IncorrectProgramId),
+        );
+        assert_eq!(
+            process_instruction(&deactivate_delinquent_stake(
+                &spoofed_stake_state_pubkey(),
+                &Pubkey::default(),
+                &Pubkey::default(),
+            )),
+            Err(InstructionError::InvalidAccountOwner),
+        );
     }
 
     #[test]
@@ -1130,6 +1415,91 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_get_minimum_delegation() {
+        let instruction = get_minimum_delegation();
+        assert!(instruction.accounts.is_empty());
+
+        let mut invoke_context = MockInvokeContext::default();
+        assert_eq!(
+            super::process_instruction(
+                &Pubkey::default(),
+                &[],
+                &instruction.data,
+                &mut invoke_context,
+            ),
+            Ok(())
+        );
+        let (_, return_data) = invoke_context.get_return_data();
+        assert_eq!(
+            return_data,
+            crate::get_minimum_delegation(|_| true).to_le_bytes()
+        );
+        assert_eq!(
+            crate::get_minimum_delegation(|_| true),
+            crate::MINIMUM_DELEGATION_SOL * solana_sdk::native_token::LAMPORTS_PER_SOL
+        );
+        assert_eq!(crate::get_minimum_delegation(|_| false), 0);
+    }
+
+    #[test]
+    fn test_epoch_rewards_active() {
+        let stake_pubkey = Pubkey::new_unique();
+        let stake_account = create_default_stake_account();
+        let keyed_accounts = [KeyedAccount::new(&stake_pubkey, true, &stake_account)];
+        let instruction_data = serialize(&StakeInstruction::Initialize(
+            Authorized::default(),
+            Lockup::default(),
+        ))
+        .unwrap();
+
+        let mut invoke_context = MockInvokeContext::default();
+        let epoch_rewards = sysvar::epoch_rewards::EpochRewards {
+            active: true,
+            ..sysvar::epoch_rewards::EpochRewards::default()
+        };
+        invoke_context.sysvars = vec![(
+            sysvar::epoch_rewards::id(),
+            Some(std::rc::Rc::new(serialize(&epoch_rewards).unwrap())),
+        )];
+        assert_eq!(
+            super::process_instruction(
+                &Pubkey::default(),
+                &keyed_accounts,
+                &instruction_data,
+                &mut invoke_context,
+            ),
+            Err(StakeError::EpochRewardsActive.into())
+        );
+
+        // the minimum delegation can still be queried
+        assert_eq!(
+            super::process_instruction(
+                &Pubkey::default(),
+                &[],
+                &get_minimum_delegation().data,
+                &mut invoke_context,
+            ),
+            Ok(())
+        );
+
+        // once the distribution completes, stake accounts are unlocked again
+        let epoch_rewards = sysvar::epoch_rewards::EpochRewards::default();
+        invoke_context.sysvars = vec![(
+            sysvar::epoch_rewards::id(),
+            Some(std::rc::Rc::new(serialize(&epoch_rewards).unwrap())),
+        )];
+        assert_ne!(
+            super::process_instruction(
+                &Pubkey::default(),
+                &keyed_accounts,
+                &instruction_data,
+                &mut invoke_context,
+            ),
+            Err(StakeError::EpochRewardsActive.into())
+        );
+    }
+
     #[test]
     fn test_custom_error_decode() {
         use num_traits::FromPrimitive;
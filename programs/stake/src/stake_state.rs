@@ -24,6 +24,10 @@ use solana_sdk::{
 use solana_vote_program::vote_state::{VoteState, VoteStateVersions};
 use std::{collections::HashSet, convert::TryFrom};
 
+/// Number of consecutive epochs a vote account must have gone without earning
+/// credits before the stake delegated to it can be deactivated by anyone
+pub const MINIMUM_DELINQUENT_EPOCHS_FOR_DEACTIVATION: usize = 5;
+
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Copy, AbiExample)]
 #[allow(clippy::large_enum_variant)]
 pub enum StakeState {
@@ -851,9 +855,26 @@ pub trait StakeAccount {
         clock: &Clock,
         stake_history: &StakeHistory,
         config: &Config,
+        minimum_delegation: u64,
         signers: &HashSet<Pubkey>,
     ) -> Result<(), InstructionError>;
     fn deactivate(&self, clock: &Clock, signers: &HashSet<Pubkey>) -> Result<(), InstructionError>;
+    fn redelegate(
+        &self,
+        uninitialized_stake: &KeyedAccount,
+        vote_account: &KeyedAccount,
+        clock: &Clock,
+        stake_history: &StakeHistory,
+        config: &Config,
+        minimum_delegation: u64,
+        signers: &HashSet<Pubkey>,
+    ) -> Result<(), InstructionError>;
+    fn deactivate_delinquent(
+        &self,
+        delinquent_vote_account: &KeyedAccount,
+        reference_vote_account: &KeyedAccount,
+        clock: &Clock,
+    ) -> Result<(), InstructionError>;
     fn set_lockup(
         &self,
         lockup: &LockupArgs,
@@ -863,6 +884,7 @@ pub trait StakeAccount {
         &self,
         lamports: u64,
         split_stake: &KeyedAccount,
+        minimum_delegation: u64,
         signers: &HashSet<Pubkey>,
     ) -> Result<(), InstructionError>;
     fn merge(
@@ -871,6 +893,7 @@ pub trait StakeAccount {
         source_stake: &KeyedAccount,
         clock: &Clock,
         stake_history: &StakeHistory,
+        minimum_delegation: u64,
         signers: &HashSet<Pubkey>,
     ) -> Result<(), InstructionError>;
     fn withdraw(
@@ -987,6 +1010,7 @@ impl<'a> StakeAccount for KeyedAccount<'a> {
         clock: &Clock,
         stake_history: &StakeHistory,
         config: &Config,
+        minimum_delegation: u64,
         signers: &HashSet<Pubkey>,
     ) -> Result<(), InstructionError> {
         if vote_account.owner()? != solana_vote_program::id() {
@@ -996,8 +1020,10 @@ impl<'a> StakeAccount for KeyedAccount<'a> {
         match self.state()? {
             StakeState::Initialized(meta) => {
                 meta.authorized.check(signers, StakeAuthorize::Staker)?;
+                let stake_amount = self.lamports()?.saturating_sub(meta.rent_exempt_reserve); // can't stake the rent ;)
+                validate_delegated_amount(stake_amount, minimum_delegation)?;
                 let stake = Stake::new(
-                    self.lamports()?.saturating_sub(meta.rent_exempt_reserve), // can't stake the rent ;)
+                    stake_amount,
                     vote_account.unsigned_key(),
                     &State::<VoteStateVersions>::state(vote_account)?.convert_to_current(),
                     clock.epoch,
@@ -1007,8 +1033,10 @@ impl<'a> StakeAccount for KeyedAccount<'a> {
             }
             StakeState::Stake(meta, mut stake) => {
                 meta.authorized.check(signers, StakeAuthorize::Staker)?;
+                let stake_amount = self.lamports()?.saturating_sub(meta.rent_exempt_reserve); // can't stake the rent ;)
+                validate_delegated_amount(stake_amount, minimum_delegation)?;
                 stake.redelegate(
-                    self.lamports()?.saturating_sub(meta.rent_exempt_reserve), // can't stake the rent ;)
+                    stake_amount,
                     vote_account.unsigned_key(),
                     &State::<VoteStateVersions>::state(vote_account)?.convert_to_current(),
                     clock,
@@ -1030,6 +1058,127 @@ impl<'a> StakeAccount for KeyedAccount<'a> {
             Err(InstructionError::InvalidAccountData)
         }
     }
+    fn redelegate(
+        &self,
+        uninitialized_stake: &KeyedAccount,
+        vote_account: &KeyedAccount,
+        clock: &Clock,
+        stake_history: &StakeHistory,
+        config: &Config,
+        minimum_delegation: u64,
+        signers: &HashSet<Pubkey>,
+    ) -> Result<(), InstructionError> {
+        if uninitialized_stake.owner()? != id() {
+            return Err(InstructionError::IncorrectProgramId);
+        }
+        if uninitialized_stake.data_len()? != std::mem::size_of::<StakeState>() {
+            return Err(InstructionError::InvalidAccountData);
+        }
+        if uninitialized_stake.unsigned_key() == self.unsigned_key() {
+            return Err(InstructionError::InvalidArgument);
+        }
+        if !matches!(uninitialized_stake.state()?, StakeState::Uninitialized) {
+            return Err(InstructionError::AccountAlreadyInitialized);
+        }
+        if vote_account.owner()? != solana_vote_program::id() {
+            return Err(InstructionError::IncorrectProgramId);
+        }
+
+        let (meta, mut stake) = match self.state()? {
+            StakeState::Stake(meta, stake) => (meta, stake),
+            _ => return Err(InstructionError::InvalidAccountData),
+        };
+        meta.authorized.check(signers, StakeAuthorize::Staker)?;
+
+        // only fully active stake can be moved, so that the source's
+        //  deactivation and the new account's activation are both
+        //  accounted for in the stake history
+        let (effective_stake, activating_stake, deactivating_stake) = stake
+            .delegation
+            .stake_activating_and_deactivating(clock.epoch, Some(stake_history), true);
+        if effective_stake == 0 || activating_stake != 0 || deactivating_stake != 0 {
+            return Err(StakeError::RedelegateTransientOrInactiveStake.into());
+        }
+        // redelegating to the same voter would only churn the cluster's
+        //  warmup/cooldown allowance
+        if stake.delegation.voter_pubkey == *vote_account.unsigned_key() {
+            return Err(StakeError::RedelegateToSameVoteAccount.into());
+        }
+
+        let uninitialized_rent_exempt_reserve = calculate_split_rent_exempt_reserve(
+            meta.rent_exempt_reserve,
+            self.data_len()? as u64,
+            uninitialized_stake.data_len()? as u64,
+        );
+        // the new account's rent-exempt reserve comes out of the moved stake
+        //  unless it was funded beforehand
+        let new_stake_amount = checked_add(uninitialized_stake.lamports()?, effective_stake)?
+            .saturating_sub(uninitialized_rent_exempt_reserve);
+        if new_stake_amount == 0 || effective_stake > self.lamports()? {
+            return Err(InstructionError::InsufficientFunds);
+        }
+        validate_delegated_amount(new_stake_amount, minimum_delegation)?;
+
+        // the source stays delegated to its old voter while it cools down,
+        //  earning rewards for the rest of this epoch
+        stake.deactivate(clock.epoch)?;
+        let new_stake = Stake::new(
+            new_stake_amount,
+            vote_account.unsigned_key(),
+            &State::<VoteStateVersions>::state(vote_account)?.convert_to_current(),
+            clock.epoch,
+            config,
+        );
+        let mut new_meta = meta;
+        new_meta.rent_exempt_reserve = uninitialized_rent_exempt_reserve;
+
+        self.set_state(&StakeState::Stake(meta, stake))?;
+        uninitialized_stake.set_state(&StakeState::Stake(new_meta, new_stake))?;
+
+        self.try_account_ref_mut()?.lamports -= effective_stake;
+        uninitialized_stake.try_account_ref_mut()?.lamports += effective_stake;
+        Ok(())
+    }
+    fn deactivate_delinquent(
+        &self,
+        delinquent_vote_account: &KeyedAccount,
+        reference_vote_account: &KeyedAccount,
+        clock: &Clock,
+    ) -> Result<(), InstructionError> {
+        if delinquent_vote_account.owner()? != solana_vote_program::id()
+            || reference_vote_account.owner()? != solana_vote_program::id()
+        {
+            return Err(InstructionError::IncorrectProgramId);
+        }
+
+        // the reference vote account proves that the cluster has been making
+        //  progress, so that a halt doesn't make every validator look delinquent
+        let reference_vote_state =
+            State::<VoteStateVersions>::state(reference_vote_account)?.convert_to_current();
+        if !acceptable_reference_epoch_credits(reference_vote_state.epoch_credits(), clock.epoch) {
+            return Err(StakeError::InsufficientReferenceVotes.into());
+        }
+
+        if let StakeState::Stake(meta, mut stake) = self.state()? {
+            if stake.delegation.voter_pubkey != *delinquent_vote_account.unsigned_key() {
+                return Err(StakeError::VoteAddressMismatch.into());
+            }
+
+            let delinquent_vote_state =
+                State::<VoteStateVersions>::state(delinquent_vote_account)?.convert_to_current();
+            if !eligible_for_deactivate_delinquent(
+                delinquent_vote_state.epoch_credits(),
+                clock.epoch,
+            ) {
+                return Err(StakeError::MinimumDelinquentEpochsForDeactivationNotMet.into());
+            }
+
+            stake.deactivate(clock.epoch)?;
+            self.set_state(&StakeState::Stake(meta, stake))
+        } else {
+            Err(InstructionError::InvalidAccountData)
+        }
+    }
     fn set_lockup(
         &self,
         lockup: &LockupArgs,
@@ -1052,6 +1201,7 @@ impl<'a> StakeAccount for KeyedAccount<'a> {
         &self,
         lamports: u64,
         split: &KeyedAccount,
+        minimum_delegation: u64,
         signers: &HashSet<Pubkey>,
     ) -> Result<(), InstructionError> {
         if split.owner()? != id() {
@@ -1114,6 +1264,15 @@ impl<'a> StakeAccount for KeyedAccount<'a> {
                         )
                     };
                     let split_stake = stake.split(remaining_stake_delta, split_stake_amount)?;
+                    // a partial split must leave both accounts delegating at
+                    //  least the minimum
+                    if lamports != self.lamports()? {
+                        validate_delegated_amount(stake.delegation.stake, minimum_delegation)?;
+                        validate_delegated_amount(
+                            split_stake.delegation.stake,
+                            minimum_delegation,
+                        )?;
+                    }
                     let mut split_meta = meta;
                     split_meta.rent_exempt_reserve = split_rent_exempt_reserve;
 
@@ -1169,6 +1328,7 @@ impl<'a> StakeAccount for KeyedAccount<'a> {
         source_account: &KeyedAccount,
         clock: &Clock,
         stake_history: &StakeHistory,
+        minimum_delegation: u64,
         signers: &HashSet<Pubkey>,
     ) -> Result<(), InstructionError> {
         // Ensure source isn't spoofed
@@ -1194,6 +1354,9 @@ impl<'a> StakeAccount for KeyedAccount<'a> {
 
         ic_msg!(invoke_context, "Merging stake accounts");
         if let Some(merged_state) = stake_merge_kind.merge(invoke_context, source_merge_kind)? {
+            if let StakeState::Stake(_, stake) = &merged_state {
+                validate_delegated_amount(stake.delegation.stake, minimum_delegation)?;
+            }
             self.set_state(&merged_state)?;
         }
 
@@ -1522,6 +1685,51 @@ pub fn calculate_points(
     }
 }
 
+/// Whether a vote account earned credits in each of the last
+/// `MINIMUM_DELINQUENT_EPOCHS_FOR_DEACTIVATION` epochs, up to and including
+/// `current_epoch`
+pub fn acceptable_reference_epoch_credits(
+    epoch_credits: &[(Epoch, u64, u64)],
+    current_epoch: Epoch,
+) -> bool {
+    if let Some(epoch_index) = epoch_credits
+        .len()
+        .checked_sub(MINIMUM_DELINQUENT_EPOCHS_FOR_DEACTIVATION)
+    {
+        let mut epoch = current_epoch;
+        for (vote_epoch, ..) in epoch_credits[epoch_index..].iter().rev() {
+            if *vote_epoch != epoch {
+                return false;
+            }
+            epoch = epoch.saturating_sub(1);
+        }
+        true
+    } else {
+        false
+    }
+}
+
+/// Whether a vote account has earned no credits in the last
+/// `MINIMUM_DELINQUENT_EPOCHS_FOR_DEACTIVATION` epochs, up to and including
+/// `current_epoch`
+pub fn eligible_for_deactivate_delinquent(
+    epoch_credits: &[(Epoch, u64, u64)],
+    current_epoch: Epoch,
+) -> bool {
+    match epoch_credits.last() {
+        None => true,
+        Some((epoch, ..)) => {
+            if let Some(minimum_epoch) =
+                current_epoch.checked_sub(MINIMUM_DELINQUENT_EPOCHS_FOR_DEACTIVATION as Epoch)
+            {
+                *epoch <= minimum_epoch
+            } else {
+                false
+            }
+        }
+    }
+}
+
 // utility function, used by Split
 //This emulates current Rent math in order to preserve backward compatibility. In the future, and
 //to support variable rent, the Split instruction should pass in the Rent sysvar instead.
@@ -1535,6 +1743,18 @@ fn calculate_split_rent_exempt_reserve(
     lamports_per_byte_year * (split_data_len + ACCOUNT_STORAGE_OVERHEAD)
 }
 
+// utility function, used by Delegate, Split, Merge and Redelegate
+fn validate_delegated_amount(
+    stake_amount: u64,
+    minimum_delegation: u64,
+) -> Result<(), InstructionError> {
+    if stake_amount < minimum_delegation {
+        Err(StakeError::InsufficientDelegation.into())
+    } else {
+        Ok(())
+    }
+}
+
 pub type RewriteStakeStatus = (&'static str, (u64, u64), (u64, u64));
 
 pub fn rewrite_stakes(
@@ -1943,6 +2163,7 @@ mod tests {
                 &clock,
                 &StakeHistory::default(),
                 &Config::default(),
+                0,
                 &signers,
             ),
             Err(InstructionError::MissingRequiredSignature)
@@ -1957,6 +2178,7 @@ mod tests {
                 &clock,
                 &StakeHistory::default(),
                 &Config::default(),
+                0,
                 &signers,
             )
             .is_ok());
@@ -1986,6 +2208,7 @@ mod tests {
                 &clock,
                 &StakeHistory::default(),
                 &Config::default(),
+                0,
                 &signers
             ),
             Err(StakeError::TooSoonToRedelegate.into())
@@ -2004,6 +2227,7 @@ mod tests {
                 &clock,
                 &StakeHistory::default(),
                 &Config::default(),
+                0,
                 &signers
             )
             .is_ok());
@@ -2018,6 +2242,7 @@ mod tests {
                 &clock,
                 &StakeHistory::default(),
                 &Config::default(),
+                0,
                 &signers,
             ),
             Err(solana_sdk::instruction::InstructionError::IncorrectProgramId)
@@ -2049,6 +2274,7 @@ mod tests {
                 &clock,
                 &StakeHistory::default(),
                 &Config::default(),
+                0,
                 &signers
             )
             .is_err());
@@ -2826,6 +3052,7 @@ mod tests {
                 &clock,
                 &StakeHistory::default(),
                 &Config::default(),
+                0,
                 &signers
             ),
             Ok(())
@@ -2918,6 +3145,7 @@ mod tests {
                 &Clock::default(),
                 &StakeHistory::default(),
                 &Config::default(),
+                0,
                 &vec![stake_pubkey].into_iter().collect(),
             )
             .unwrap();
@@ -3161,6 +3389,7 @@ mod tests {
                 &clock,
                 &StakeHistory::default(),
                 &Config::default(),
+                0,
                 &signers,
             ),
             Ok(())
@@ -3332,6 +3561,7 @@ mod tests {
                 &future,
                 &StakeHistory::default(),
                 &Config::default(),
+                0,
                 &signers,
             ),
             Ok(())
@@ -4196,6 +4426,7 @@ mod tests {
             stake_keyed_account.split(
                 stake_lamports / 2,
                 &split_stake_keyed_account,
+                0,
                 &HashSet::default() // no signers
             ),
             Err(InstructionError::MissingRequiredSignature)
@@ -4204,7 +4435,7 @@ mod tests {
         // this should work
         let signers = vec![stake_pubkey].into_iter().collect();
         assert_eq!(
-            stake_keyed_account.split(stake_lamports / 2, &split_stake_keyed_account, &signers),
+            stake_keyed_account.split(stake_lamports / 2, &split_stake_keyed_account, 0, &signers),
             Ok(())
         );
         assert_eq!(
@@ -4239,7 +4470,7 @@ mod tests {
         let split_stake_keyed_account =
             KeyedAccount::new(&split_stake_pubkey, true, &split_stake_account);
         assert_eq!(
-            stake_keyed_account.split(stake_lamports / 2, &split_stake_keyed_account, &signers),
+            stake_keyed_account.split(stake_lamports / 2, &split_stake_keyed_account, 0, &signers),
             Err(InstructionError::InvalidAccountData)
         );
     }
@@ -4284,7 +4515,7 @@ mod tests {
         let split_stake_keyed_account =
             KeyedAccount::new(&split_stake_pubkey, true, &split_stake_account);
         assert_eq!(
-            stake_keyed_account.split(stake_lamports / 2, &split_stake_keyed_account, &signers),
+            stake_keyed_account.split(stake_lamports / 2, &split_stake_keyed_account, 0, &signers),
             Err(StakeError::InsufficientStake.into())
         );
     }
@@ -4337,6 +4568,7 @@ mod tests {
                 stake_keyed_account.split(
                     rent_exempt_reserve,
                     &split_stake_keyed_account,
+                    0,
                     &signers
                 ),
                 Err(InstructionError::InsufficientFunds)
@@ -4347,6 +4579,7 @@ mod tests {
                 stake_keyed_account.split(
                     stake_lamports - rent_exempt_reserve,
                     &split_stake_keyed_account,
+                    0,
                     &signers
                 ),
                 Err(InstructionError::InsufficientFunds)
@@ -4358,6 +4591,7 @@ mod tests {
                 stake_keyed_account.split(
                     stake_lamports - (rent_exempt_reserve + 1), // leave rent_exempt_reserve + 1 in original account
                     &split_stake_keyed_account,
+                    0,
                     &signers
                 ),
                 Ok(())
@@ -4425,13 +4659,23 @@ mod tests {
 
             // split more than available fails
             assert_eq!(
-                stake_keyed_account.split(stake_lamports + 1, &split_stake_keyed_account, &signers),
+                stake_keyed_account.split(
+                    stake_lamports + 1,
+                    &split_stake_keyed_account,
+                    0,
+                    &signers
+                ),
                 Err(InstructionError::InsufficientFunds)
             );
 
             // should work
             assert_eq!(
-                stake_keyed_account.split(stake_lamports / 2, &split_stake_keyed_account, &signers),
+                stake_keyed_account.split(
+                    stake_lamports / 2,
+                    &split_stake_keyed_account,
+                    0,
+                    &signers
+                ),
                 Ok(())
             );
             // no lamport leakage
@@ -4511,7 +4755,7 @@ mod tests {
         let stake_keyed_account = KeyedAccount::new(&stake_pubkey, true, &stake_account);
 
         assert_eq!(
-            stake_keyed_account.split(stake_lamports / 2, &split_stake_keyed_account, &signers),
+            stake_keyed_account.split(stake_lamports / 2, &split_stake_keyed_account, 0, &signers),
             Err(InstructionError::IncorrectProgramId),
         );
     }
@@ -4563,13 +4807,23 @@ mod tests {
 
             // split more than available fails
             assert_eq!(
-                stake_keyed_account.split(stake_lamports + 1, &split_stake_keyed_account, &signers),
+                stake_keyed_account.split(
+                    stake_lamports + 1,
+                    &split_stake_keyed_account,
+                    0,
+                    &signers
+                ),
                 Err(InstructionError::InsufficientFunds)
             );
 
             // should work
             assert_eq!(
-                stake_keyed_account.split(stake_lamports / 2, &split_stake_keyed_account, &signers),
+                stake_keyed_account.split(
+                    stake_lamports / 2,
+                    &split_stake_keyed_account,
+                    0,
+                    &signers
+                ),
                 Ok(())
             );
             // no lamport leakage
@@ -4678,13 +4932,23 @@ mod tests {
 
             // split more than available fails
             assert_eq!(
-                stake_keyed_account.split(stake_lamports + 1, &split_stake_keyed_account, &signers),
+                stake_keyed_account.split(
+                    stake_lamports + 1,
+                    &split_stake_keyed_account,
+                    0,
+                    &signers
+                ),
                 Err(InstructionError::InsufficientFunds)
             );
 
             // should work
             assert_eq!(
-                stake_keyed_account.split(stake_lamports / 2, &split_stake_keyed_account, &signers),
+                stake_keyed_account.split(
+                    stake_lamports / 2,
+                    &split_stake_keyed_account,
+                    0,
+                    &signers
+                ),
                 Ok(())
             );
             // no lamport leakage
@@ -4796,12 +5060,12 @@ mod tests {
 
             // should always return error when splitting to larger account
             let split_result =
-                stake_keyed_account.split(split_amount, &split_stake_keyed_account, &signers);
+                stake_keyed_account.split(split_amount, &split_stake_keyed_account, 0, &signers);
             assert_eq!(split_result, Err(InstructionError::InvalidAccountData));
 
             // Splitting 100% of source should not make a difference
             let split_result =
-                stake_keyed_account.split(stake_lamports, &split_stake_keyed_account, &signers);
+                stake_keyed_account.split(stake_lamports, &split_stake_keyed_account, 0, &signers);
             assert_eq!(split_result, Err(InstructionError::InvalidAccountData));
         }
     }
@@ -4852,7 +5116,7 @@ mod tests {
 
             // split 100% over to dest
             assert_eq!(
-                stake_keyed_account.split(stake_lamports, &split_stake_keyed_account, &signers),
+                stake_keyed_account.split(stake_lamports, &split_stake_keyed_account, 0, &signers),
                 Ok(())
             );
 
@@ -4939,7 +5203,7 @@ mod tests {
 
             // split 100% over to dest
             assert_eq!(
-                stake_keyed_account.split(stake_lamports, &split_stake_keyed_account, &signers),
+                stake_keyed_account.split(stake_lamports, &split_stake_keyed_account, 0, &signers),
                 Ok(())
             );
 
@@ -5013,7 +5277,7 @@ mod tests {
             let stake_keyed_account = KeyedAccount::new(&stake_pubkey, true, &stake_account);
 
             assert_eq!(
-                stake_keyed_account.split(stake_lamports, &split_stake_keyed_account, &signers),
+                stake_keyed_account.split(stake_lamports, &split_stake_keyed_account, 0, &signers),
                 Err(InstructionError::InvalidAccountData)
             );
 
@@ -5039,7 +5303,7 @@ mod tests {
             let stake_keyed_account = KeyedAccount::new(&stake_pubkey, true, &stake_account);
 
             assert_eq!(
-                stake_keyed_account.split(stake_lamports, &split_stake_keyed_account, &signers),
+                stake_keyed_account.split(stake_lamports, &split_stake_keyed_account, 0, &signers),
                 Ok(())
             );
 
@@ -5098,6 +5362,460 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_redelegate() {
+        let clock = Clock {
+            epoch: 1,
+            ..Clock::default()
+        };
+        let stake_history = StakeHistory::default();
+        let rent_exempt_reserve =
+            Rent::default().minimum_balance(std::mem::size_of::<StakeState>());
+        let stake_lamports = 2 * rent_exempt_reserve;
+        let extra_lamports = 5;
+
+        let create_vote_account = || {
+            let vote_pubkey = solana_sdk::pubkey::new_rand();
+            let vote_account = RefCell::new(vote_state::create_account(
+                &vote_pubkey,
+                &solana_sdk::pubkey::new_rand(),
+                0,
+                100,
+            ));
+            (vote_pubkey, vote_account)
+        };
+        let (vote_pubkey, vote_account) = create_vote_account();
+        let vote_keyed_account = KeyedAccount::new(&vote_pubkey, false, &vote_account);
+        let (new_vote_pubkey, new_vote_account) = create_vote_account();
+        let new_vote_keyed_account = KeyedAccount::new(&new_vote_pubkey, false, &new_vote_account);
+
+        let stake_pubkey = solana_sdk::pubkey::new_rand();
+        let meta = Meta {
+            rent_exempt_reserve,
+            ..Meta::auto(&stake_pubkey)
+        };
+        let stake = Stake {
+            delegation: Delegation {
+                voter_pubkey: vote_pubkey,
+                stake: stake_lamports,
+                ..Delegation::default()
+            },
+            ..Stake::default()
+        };
+        let stake_account = AccountSharedData::new_ref_data_with_space(
+            rent_exempt_reserve + stake_lamports + extra_lamports,
+            &StakeState::Stake(meta, stake),
+            std::mem::size_of::<StakeState>(),
+            &id(),
+        )
+        .expect("stake_account");
+        let stake_keyed_account = KeyedAccount::new(&stake_pubkey, true, &stake_account);
+
+        let new_stake_pubkey = solana_sdk::pubkey::new_rand();
+        let new_stake_account = AccountSharedData::new_ref_data_with_space(
+            0,
+            &StakeState::Uninitialized,
+            std::mem::size_of::<StakeState>(),
+            &id(),
+        )
+        .expect("new_stake_account");
+        let new_stake_keyed_account =
+            KeyedAccount::new(&new_stake_pubkey, true, &new_stake_account);
+
+        let mut signers = HashSet::default();
+        assert_eq!(
+            stake_keyed_account.redelegate(
+                &new_stake_keyed_account,
+                &new_vote_keyed_account,
+                &clock,
+                &stake_history,
+                &Config::default(),
+                0,
+                &signers,
+            ),
+            Err(InstructionError::MissingRequiredSignature)
+        );
+        signers.insert(stake_pubkey);
+
+        // the new stake account must be an uninitialized stake account
+        let wrong_owner_account = AccountSharedData::new_ref_data_with_space(
+            0,
+            &StakeState::Uninitialized,
+            std::mem::size_of::<StakeState>(),
+            &solana_sdk::pubkey::new_rand(),
+        )
+        .expect("wrong_owner_account");
+        assert_eq!(
+            stake_keyed_account.redelegate(
+                &KeyedAccount::new(&new_stake_pubkey, true, &wrong_owner_account),
+                &new_vote_keyed_account,
+                &clock,
+                &stake_history,
+                &Config::default(),
+                0,
+                &signers,
+            ),
+            Err(InstructionError::IncorrectProgramId)
+        );
+        assert_eq!(
+            stake_keyed_account.redelegate(
+                &stake_keyed_account,
+                &new_vote_keyed_account,
+                &clock,
+                &stake_history,
+                &Config::default(),
+                0,
+                &signers,
+            ),
+            Err(InstructionError::InvalidArgument)
+        );
+
+        // the vote account must be owned by the vote program
+        assert_eq!(
+            stake_keyed_account.redelegate(
+                &new_stake_keyed_account,
+                &new_stake_keyed_account,
+                &clock,
+                &stake_history,
+                &Config::default(),
+                0,
+                &signers,
+            ),
+            Err(InstructionError::IncorrectProgramId)
+        );
+
+        // activating stake can't be redelegated
+        assert_eq!(
+            stake_keyed_account.redelegate(
+                &new_stake_keyed_account,
+                &new_vote_keyed_account,
+                &Clock::default(),
+                &stake_history,
+                &Config::default(),
+                0,
+                &signers,
+            ),
+            Err(StakeError::RedelegateTransientOrInactiveStake.into())
+        );
+
+        assert_eq!(
+            stake_keyed_account.redelegate(
+                &new_stake_keyed_account,
+                &vote_keyed_account,
+                &clock,
+                &stake_history,
+                &Config::default(),
+                0,
+                &signers,
+            ),
+            Err(StakeError::RedelegateToSameVoteAccount.into())
+        );
+
+        assert_eq!(
+            stake_keyed_account.redelegate(
+                &new_stake_keyed_account,
+                &new_vote_keyed_account,
+                &clock,
+                &stake_history,
+                &Config::default(),
+                0,
+                &signers,
+            ),
+            Ok(())
+        );
+
+        // the source keeps its reserve and the undelegated lamports, and cools down
+        assert_eq!(
+            stake_keyed_account.lamports().unwrap(),
+            rent_exempt_reserve + extra_lamports
+        );
+        assert_eq!(
+            stake_keyed_account.state(),
+            Ok(StakeState::Stake(
+                meta,
+                Stake {
+                    delegation: Delegation {
+                        deactivation_epoch: clock.epoch,
+                        ..stake.delegation
+                    },
+                    ..stake
+                }
+            ))
+        );
+
+        // the new account pays its reserve out of the moved stake, and warms up
+        assert_eq!(new_stake_keyed_account.lamports().unwrap(), stake_lamports);
+        assert_eq!(
+            new_stake_keyed_account.state(),
+            Ok(StakeState::Stake(
+                meta,
+                Stake {
+                    delegation: Delegation {
+                        voter_pubkey: new_vote_pubkey,
+                        stake: stake_lamports - rent_exempt_reserve,
+                        activation_epoch: clock.epoch,
+                        ..Delegation::default()
+                    },
+                    ..Stake::default()
+                }
+            ))
+        );
+
+        // a deactivating stake can't be redelegated
+        let other_stake_account = AccountSharedData::new_ref_data_with_space(
+            0,
+            &StakeState::Uninitialized,
+            std::mem::size_of::<StakeState>(),
+            &id(),
+        )
+        .expect("other_stake_account");
+        assert_eq!(
+            stake_keyed_account.redelegate(
+                &KeyedAccount::new(&solana_sdk::pubkey::new_rand(), true, &other_stake_account),
+                &vote_keyed_account,
+                &clock,
+                &stake_history,
+                &Config::default(),
+                0,
+                &signers,
+            ),
+            Err(StakeError::RedelegateTransientOrInactiveStake.into())
+        );
+
+        // nor can an initialized account be redelegated into
+        assert_eq!(
+            stake_keyed_account.redelegate(
+                &new_stake_keyed_account,
+                &vote_keyed_account,
+                &clock,
+                &stake_history,
+                &Config::default(),
+                0,
+                &signers,
+            ),
+            Err(InstructionError::AccountAlreadyInitialized)
+        );
+    }
+
+    #[test]
+    fn test_acceptable_reference_epoch_credits() {
+        let epoch_credits = [];
+        assert!(!acceptable_reference_epoch_credits(&epoch_credits, 0));
+
+        let epoch_credits = [(0, 42, 42), (1, 42, 42), (2, 42, 42), (3, 42, 42)];
+        assert!(!acceptable_reference_epoch_credits(&epoch_credits, 3));
+
+        let epoch_credits = [
+            (0, 42, 42),
+            (1, 42, 42),
+            (2, 42, 42),
+            (3, 42, 42),
+            (4, 42, 42),
+        ];
+        assert!(acceptable_reference_epoch_credits(&epoch_credits, 4));
+        // no credits in the current epoch
+        assert!(!acceptable_reference_epoch_credits(&epoch_credits, 5));
+
+        // a gap in the last five epochs
+        let epoch_credits = [
+            (1, 42, 42),
+            (2, 42, 42),
+            (3, 42, 42),
+            (5, 42, 42),
+            (6, 42, 42),
+            (7, 42, 42),
+        ];
+        assert!(!acceptable_reference_epoch_credits(&epoch_credits, 7));
+
+        let epoch_credits = [
+            (1, 42, 42),
+            (3, 42, 42),
+            (4, 42, 42),
+            (5, 42, 42),
+            (6, 42, 42),
+            (7, 42, 42),
+        ];
+        assert!(acceptable_reference_epoch_credits(&epoch_credits, 7));
+    }
+
+    #[test]
+    fn test_eligible_for_deactivate_delinquent() {
+        // a vote account that never earned credits is always delinquent
+        assert!(eligible_for_deactivate_delinquent(&[], 42));
+
+        let epoch_credits = [(0, 42, 42)];
+        assert!(!eligible_for_deactivate_delinquent(&epoch_credits, 0));
+        assert!(!eligible_for_deactivate_delinquent(
+            &epoch_credits,
+            MINIMUM_DELINQUENT_EPOCHS_FOR_DEACTIVATION as Epoch - 1
+        ));
+        assert!(eligible_for_deactivate_delinquent(
+            &epoch_credits,
+            MINIMUM_DELINQUENT_EPOCHS_FOR_DEACTIVATION as Epoch
+        ));
+
+        let epoch_credits = [(100, 42, 42)];
+        assert!(!eligible_for_deactivate_delinquent(
+            &epoch_credits,
+            100 + MINIMUM_DELINQUENT_EPOCHS_FOR_DEACTIVATION as Epoch - 1
+        ));
+        assert!(eligible_for_deactivate_delinquent(
+            &epoch_credits,
+            100 + MINIMUM_DELINQUENT_EPOCHS_FOR_DEACTIVATION as Epoch
+        ));
+    }
+
+    #[test]
+    fn test_deactivate_delinquent() {
+        let current_epoch = 20;
+        let clock = Clock {
+            epoch: current_epoch,
+            ..Clock::default()
+        };
+
+        let create_vote_account = |credit_epochs: &[Epoch]| {
+            let mut vote_state = VoteState::default();
+            for epoch in credit_epochs {
+                vote_state.increment_credits(*epoch);
+            }
+            let vote_account = AccountSharedData::new_ref_data_with_space(
+                100,
+                &VoteStateVersions::new_current(vote_state),
+                VoteState::size_of(),
+                &solana_vote_program::id(),
+            )
+            .expect("vote_account");
+            (solana_sdk::pubkey::new_rand(), vote_account)
+        };
+        let (reference_vote_pubkey, reference_vote_account) =
+            create_vote_account(&[16, 17, 18, 19, 20]);
+        let reference_vote_keyed_account =
+            KeyedAccount::new(&reference_vote_pubkey, false, &reference_vote_account);
+        let (vote_pubkey, vote_account) = create_vote_account(&[14]);
+        let vote_keyed_account = KeyedAccount::new(&vote_pubkey, false, &vote_account);
+
+        let stake_pubkey = solana_sdk::pubkey::new_rand();
+        let stake = Stake {
+            delegation: Delegation {
+                voter_pubkey: vote_pubkey,
+                stake: 42,
+                ..Delegation::default()
+            },
+            ..Stake::default()
+        };
+        let stake_account = AccountSharedData::new_ref_data_with_space(
+            42,
+            &StakeState::Stake(Meta::auto(&stake_pubkey), stake),
+            std::mem::size_of::<StakeState>(),
+            &id(),
+        )
+        .expect("stake_account");
+        // no signature is required
+        let stake_keyed_account = KeyedAccount::new(&stake_pubkey, false, &stake_account);
+
+        // the reference vote account must have voted in each of the last epochs
+        let (unhealthy_vote_pubkey, unhealthy_vote_account) =
+            create_vote_account(&[15, 16, 17, 18, 20]);
+        assert_eq!(
+            stake_keyed_account.deactivate_delinquent(
+                &vote_keyed_account,
+                &KeyedAccount::new(&unhealthy_vote_pubkey, false, &unhealthy_vote_account),
+                &clock,
+            ),
+            Err(StakeError::InsufficientReferenceVotes.into())
+        );
+
+        // the stake must be delegated to the delinquent vote account
+        assert_eq!(
+            stake_keyed_account.deactivate_delinquent(
+                &reference_vote_keyed_account,
+                &reference_vote_keyed_account,
+                &clock,
+            ),
+            Err(StakeError::VoteAddressMismatch.into())
+        );
+
+        // vote accounts must be owned by the vote program
+        assert_eq!(
+            stake_keyed_account.deactivate_delinquent(
+                &stake_keyed_account,
+                &reference_vote_keyed_account,
+                &clock,
+            ),
+            Err(InstructionError::IncorrectProgramId)
+        );
+
+        // the vote account earned credits too recently
+        vote_keyed_account
+            .set_state(&VoteStateVersions::new_current({
+                let mut vote_state = VoteState::default();
+                vote_state.increment_credits(16);
+                vote_state
+            }))
+            .unwrap();
+        assert_eq!(
+            stake_keyed_account.deactivate_delinquent(
+                &vote_keyed_account,
+                &reference_vote_keyed_account,
+                &clock,
+            ),
+            Err(StakeError::MinimumDelinquentEpochsForDeactivationNotMet.into())
+        );
+
+        vote_keyed_account
+            .set_state(&VoteStateVersions::new_current({
+                let mut vote_state = VoteState::default();
+                vote_state.increment_credits(15);
+                vote_state
+            }))
+            .unwrap();
+        assert_eq!(
+            stake_keyed_account.deactivate_delinquent(
+                &vote_keyed_account,
+                &reference_vote_keyed_account,
+                &clock,
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            stake_keyed_account.state(),
+            Ok(StakeState::Stake(
+                Meta::auto(&stake_pubkey),
+                Stake {
+                    delegation: Delegation {
+                        deactivation_epoch: current_epoch,
+                        ..stake.delegation
+                    },
+                    ..stake
+                }
+            ))
+        );
+
+        // can't be deactivated twice
+        assert_eq!(
+            stake_keyed_account.deactivate_delinquent(
+                &vote_keyed_account,
+                &reference_vote_keyed_account,
+                &clock,
+            ),
+            Err(StakeError::AlreadyDeactivated.into())
+        );
+
+        // nor can an account that isn't delegated
+        let initialized_stake_account = AccountSharedData::new_ref_data_with_space(
+            42,
+            &StakeState::Initialized(Meta::auto(&stake_pubkey)),
+            std::mem::size_of::<StakeState>(),
+            &id(),
+        )
+        .expect("initialized_stake_account");
+        assert_eq!(
+            KeyedAccount::new(&stake_pubkey, false, &initialized_stake_account)
+                .deactivate_delinquent(&vote_keyed_account, &reference_vote_keyed_account, &clock),
+            Err(InstructionError::InvalidAccountData)
+        );
+    }
+
     #[test]
     fn test_merge() {
         let stake_pubkey = solana_sdk::pubkey::new_rand();
@@ -5148,6 +5866,7 @@ mod tests {
                         &source_stake_keyed_account,
                         &Clock::default(),
                         &StakeHistory::default(),
+                        0,
                         &HashSet::new()
                     ),
                     Err(InstructionError::MissingRequiredSignature)
@@ -5159,6 +5878,7 @@ mod tests {
                         &source_stake_keyed_account,
                         &Clock::default(),
                         &StakeHistory::default(),
+                        0,
                         &signers
                     ),
                     Ok(())
@@ -5250,6 +5970,7 @@ mod tests {
                 &stake_keyed_account,
                 &Clock::default(),
                 &StakeHistory::default(),
+                0,
                 &signers,
             ),
             Err(InstructionError::InvalidArgument),
@@ -5307,6 +6028,7 @@ mod tests {
                         &source_stake_keyed_account,
                         &Clock::default(),
                         &StakeHistory::default(),
+                        0,
                         &wrong_signers,
                     ),
                     Err(InstructionError::MissingRequiredSignature)
@@ -5318,6 +6040,7 @@ mod tests {
                         &source_stake_keyed_account,
                         &Clock::default(),
                         &StakeHistory::default(),
+                        0,
                         &signers,
                     ),
                     Err(StakeError::MergeMismatch.into())
@@ -5370,6 +6093,7 @@ mod tests {
                         &source_stake_keyed_account,
                         &Clock::default(),
                         &StakeHistory::default(),
+                        0,
                         &signers,
                     ),
                     Err(InstructionError::InvalidAccountData)
@@ -5419,6 +6143,7 @@ mod tests {
                 &source_stake_keyed_account,
                 &Clock::default(),
                 &StakeHistory::default(),
+                0,
                 &signers
             ),
             Err(InstructionError::IncorrectProgramId)
@@ -5515,6 +6240,7 @@ mod tests {
                 &test_source_keyed,
                 clock,
                 stake_history,
+                0,
                 signers,
             );
             if result.is_ok() {
@@ -5803,6 +6529,7 @@ mod tests {
                 &clock,
                 &StakeHistory::default(),
                 &Config::default(),
+                0,
                 &signers,
             )
             .unwrap();
@@ -5850,6 +6577,7 @@ mod tests {
                 &clock,
                 &StakeHistory::default(),
                 &Config::default(),
+                0,
                 &other_signers,
             ),
             Err(InstructionError::MissingRequiredSignature)
@@ -5863,6 +6591,7 @@ mod tests {
                 &clock,
                 &StakeHistory::default(),
                 &Config::default(),
+                0,
                 &new_signers
             ),
             Ok(())
@@ -5915,6 +6644,7 @@ mod tests {
                 &clock,
                 &stake_history,
                 &config,
+                0,
                 &signers,
             )
             .unwrap();
@@ -5947,6 +6677,7 @@ mod tests {
                 &clock,
                 &stake_history,
                 &config,
+                0,
                 &signers,
             )
             .unwrap();
@@ -5969,6 +6700,7 @@ mod tests {
                 &clock,
                 &stake_history,
                 &config,
+                0,
                 &signers,
             )
             .unwrap();
@@ -6623,4 +7355,185 @@ mod tests {
         let delegation = new_state.delegation().unwrap();
         assert_eq!(delegation.stake, 2 * stake.delegation.stake);
     }
+
+    #[test]
+    fn test_minimum_delegation() {
+        let minimum_delegation = crate::get_minimum_delegation(|_| true);
+        let rent_exempt_reserve =
+            Rent::default().minimum_balance(std::mem::size_of::<StakeState>());
+        let clock = Clock {
+            epoch: 1,
+            ..Clock::default()
+        };
+        let invoke_context = MockInvokeContext::default();
+
+        let vote_pubkey = solana_sdk::pubkey::new_rand();
+        let vote_account = RefCell::new(vote_state::create_account(
+            &vote_pubkey,
+            &solana_sdk::pubkey::new_rand(),
+            0,
+            100,
+        ));
+        let vote_keyed_account = KeyedAccount::new(&vote_pubkey, false, &vote_account);
+        let new_vote_pubkey = solana_sdk::pubkey::new_rand();
+        let new_vote_account = RefCell::new(vote_state::create_account(
+            &new_vote_pubkey,
+            &solana_sdk::pubkey::new_rand(),
+            0,
+            100,
+        ));
+        let new_vote_keyed_account = KeyedAccount::new(&new_vote_pubkey, false, &new_vote_account);
+
+        let stake_pubkey = solana_sdk::pubkey::new_rand();
+        let signers = vec![stake_pubkey].into_iter().collect();
+        let meta = Meta {
+            rent_exempt_reserve,
+            ..Meta::auto(&stake_pubkey)
+        };
+        let new_stake_account = |lamports, state: &StakeState| {
+            AccountSharedData::new_ref_data_with_space(
+                lamports,
+                state,
+                std::mem::size_of::<StakeState>(),
+                &id(),
+            )
+            .expect("stake_account")
+        };
+        let new_stake = |stake| Stake {
+            delegation: Delegation {
+                voter_pubkey: vote_pubkey,
+                stake,
+                ..Delegation::default()
+            },
+            ..Stake::default()
+        };
+
+        // delegate
+        for (stake_lamports, expected_result) in &[
+            (
+                minimum_delegation - 1,
+                Err(StakeError::InsufficientDelegation.into()),
+            ),
+            (minimum_delegation, Ok(())),
+        ] {
+            let stake_account = new_stake_account(
+                rent_exempt_reserve + stake_lamports,
+                &StakeState::Initialized(meta),
+            );
+            let stake_keyed_account = KeyedAccount::new(&stake_pubkey, true, &stake_account);
+            assert_eq!(
+                stake_keyed_account.delegate(
+                    &vote_keyed_account,
+                    &clock,
+                    &StakeHistory::default(),
+                    &Config::default(),
+                    minimum_delegation,
+                    &signers,
+                ),
+                *expected_result,
+            );
+        }
+
+        // split: a partial split must leave both sides with the minimum, but
+        //  a full split moves the delegation as is
+        let split_pubkey = solana_sdk::pubkey::new_rand();
+        for (stake_lamports, split_lamports, expected_result) in &[
+            (
+                3 * minimum_delegation,
+                rent_exempt_reserve + minimum_delegation - 1,
+                Err(StakeError::InsufficientDelegation.into()),
+            ),
+            (
+                2 * minimum_delegation,
+                rent_exempt_reserve + minimum_delegation,
+                Err(StakeError::InsufficientDelegation.into()),
+            ),
+            (
+                3 * minimum_delegation,
+                rent_exempt_reserve + minimum_delegation,
+                Ok(()),
+            ),
+            (
+                minimum_delegation - 1,
+                rent_exempt_reserve + minimum_delegation - 1,
+                Ok(()),
+            ),
+        ] {
+            let stake_account = new_stake_account(
+                rent_exempt_reserve + stake_lamports,
+                &StakeState::Stake(meta, new_stake(*stake_lamports)),
+            );
+            let stake_keyed_account = KeyedAccount::new(&stake_pubkey, true, &stake_account);
+            let split_account = new_stake_account(0, &StakeState::Uninitialized);
+            let split_keyed_account = KeyedAccount::new(&split_pubkey, true, &split_account);
+            assert_eq!(
+                stake_keyed_account.split(
+                    *split_lamports,
+                    &split_keyed_account,
+                    minimum_delegation,
+                    &signers,
+                ),
+                *expected_result,
+            );
+        }
+
+        // merge fully active stakes, which don't move the source's reserve
+        //  into the delegation
+        let source_pubkey = solana_sdk::pubkey::new_rand();
+        for (stake_lamports, expected_result) in &[
+            (
+                minimum_delegation / 2 - 1,
+                Err(StakeError::InsufficientDelegation.into()),
+            ),
+            (minimum_delegation / 2, Ok(())),
+        ] {
+            let state = StakeState::Stake(meta, new_stake(*stake_lamports));
+            let stake_account = new_stake_account(rent_exempt_reserve + stake_lamports, &state);
+            let stake_keyed_account = KeyedAccount::new(&stake_pubkey, true, &stake_account);
+            let source_account = new_stake_account(rent_exempt_reserve + stake_lamports, &state);
+            let source_keyed_account = KeyedAccount::new(&source_pubkey, true, &source_account);
+            assert_eq!(
+                stake_keyed_account.merge(
+                    &invoke_context,
+                    &source_keyed_account,
+                    &clock,
+                    &StakeHistory::default(),
+                    minimum_delegation,
+                    &signers,
+                ),
+                *expected_result,
+            );
+        }
+
+        // redelegate: the new account's reserve comes out of the moved stake
+        let redelegated_pubkey = solana_sdk::pubkey::new_rand();
+        for (stake_lamports, expected_result) in &[
+            (
+                rent_exempt_reserve + minimum_delegation - 1,
+                Err(StakeError::InsufficientDelegation.into()),
+            ),
+            (rent_exempt_reserve + minimum_delegation, Ok(())),
+        ] {
+            let stake_account = new_stake_account(
+                rent_exempt_reserve + stake_lamports,
+                &StakeState::Stake(meta, new_stake(*stake_lamports)),
+            );
+            let stake_keyed_account = KeyedAccount::new(&stake_pubkey, true, &stake_account);
+            let redelegated_account = new_stake_account(0, &StakeState::Uninitialized);
+            let redelegated_keyed_account =
+                KeyedAccount::new(&redelegated_pubkey, true, &redelegated_account);
+            assert_eq!(
+                stake_keyed_account.redelegate(
+                    &redelegated_keyed_account,
+                    &new_vote_keyed_account,
+                    &clock,
+                    &StakeHistory::default(),
+                    &Config::default(),
+                    minimum_delegation,
+                    &signers,
+                ),
+                *expected_result,
+            );
+        }
+    }
 }
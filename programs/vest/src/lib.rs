@@ -1,4 +1,6 @@
 #![allow(clippy::integer_arithmetic)]
+pub mod token_grant_processor;
+pub mod token_grant_state;
 pub mod vest_instruction;
 pub mod vest_processor;
 pub mod vest_schedule;
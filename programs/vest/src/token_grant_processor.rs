@@ -0,0 +1,621 @@
+//! token grant processor
+use crate::{
+    token_grant_state::TokenGrantState,
+    vest_instruction::{get_escrow_authority, VestError, VestInstruction},
+    vest_processor::verify_signed_account,
+};
+use solana_sdk::{
+    account::{ReadableAccount, WritableAccount},
+    clock::Clock,
+    ic_msg, inline_spl_token_v2_0,
+    instruction::InstructionError,
+    keyed_account::{from_keyed_account, next_keyed_account, KeyedAccount},
+    process_instruction::InvokeContext,
+    pubkey::Pubkey,
+};
+use std::convert::TryInto;
+
+/// Return the mint, owner and amount of an SPL token account.
+fn verify_token_account(
+    keyed_account: &KeyedAccount,
+) -> Result<(Pubkey, Pubkey, u64), InstructionError> {
+    if keyed_account.owner()? != inline_spl_token_v2_0::id() {
+        return Err(InstructionError::IncorrectProgramId);
+    }
+
+    let account = keyed_account.try_account_ref()?;
+    let data = account.data();
+    if data.len() != inline_spl_token_v2_0::state::Account::get_packed_len() {
+        return Err(VestError::InvalidTokenAccount.into());
+    }
+    let read_pubkey = |offset: usize| Pubkey::new(&data[offset..offset + 32]);
+    let amount_offset = inline_spl_token_v2_0::SPL_TOKEN_ACCOUNT_AMOUNT_OFFSET;
+    let amount = u64::from_le_bytes(data[amount_offset..amount_offset + 8].try_into().unwrap());
+
+    Ok((
+        read_pubkey(inline_spl_token_v2_0::SPL_TOKEN_ACCOUNT_MINT_OFFSET),
+        read_pubkey(inline_spl_token_v2_0::SPL_TOKEN_ACCOUNT_OWNER_OFFSET),
+        amount,
+    ))
+}
+
+/// Accounts shared by the instructions that pay out of a grant's escrow
+struct EscrowAccounts<'a> {
+    escrow: &'a KeyedAccount<'a>,
+    destination: &'a KeyedAccount<'a>,
+    escrow_authority: &'a KeyedAccount<'a>,
+    clock: Clock,
+    token_program: &'a KeyedAccount<'a>,
+}
+
+fn next_escrow_accounts<'a, I: Iterator<Item = &'a KeyedAccount<'a>>>(
+    keyed_accounts_iter: &mut I,
+) -> Result<EscrowAccounts<'a>, InstructionError> {
+    Ok(EscrowAccounts {
+        escrow: next_keyed_account(keyed_accounts_iter)?,
+        destination: next_keyed_account(keyed_accounts_iter)?,
+        escrow_authority: next_keyed_account(keyed_accounts_iter)?,
+        clock: from_keyed_account::<Clock>(next_keyed_account(keyed_accounts_iter)?)?,
+        token_program: next_keyed_account(keyed_accounts_iter)?,
+    })
+}
+
+/// Transfer `amount` tokens out of the grant's escrow, signing as the escrow authority.
+fn transfer_from_escrow(
+    invoke_context: &mut dyn InvokeContext,
+    grant_pubkey: &Pubkey,
+    grant_state: &TokenGrantState,
+    accounts: &EscrowAccounts,
+    amount: u64,
+) -> Result<(), InstructionError> {
+    if accounts.escrow.unsigned_key() != &grant_state.escrow_pubkey {
+        return Err(VestError::Unauthorized.into());
+    }
+    let (mint_pubkey, _, _) = verify_token_account(accounts.destination)?;
+    if mint_pubkey != grant_state.mint_pubkey {
+        ic_msg!(
+            invoke_context,
+            "Destination token account has the wrong mint"
+        );
+        return Err(VestError::InvalidTokenAccount.into());
+    }
+    let (escrow_authority_pubkey, bump_seed) = get_escrow_authority(grant_pubkey);
+    if accounts.escrow_authority.unsigned_key() != &escrow_authority_pubkey {
+        return Err(VestError::Unauthorized.into());
+    }
+    if accounts.token_program.unsigned_key() != &inline_spl_token_v2_0::id() {
+        return Err(InstructionError::IncorrectProgramId);
+    }
+
+    invoke_context.native_invoke(
+        inline_spl_token_v2_0::instruction::transfer(
+            accounts.escrow.unsigned_key(),
+            accounts.destination.unsigned_key(),
+            &escrow_authority_pubkey,
+            amount,
+        ),
+        &[
+            accounts.escrow,
+            accounts.destination,
+            accounts.escrow_authority,
+            accounts.token_program,
+        ],
+        &[&[grant_pubkey.as_ref(), &[bump_seed]]],
+    )
+}
+
+pub fn process_token_grant_instruction(
+    keyed_accounts: &[KeyedAccount],
+    instruction: VestInstruction,
+    invoke_context: &mut dyn InvokeContext,
+) -> Result<(), InstructionError> {
+    let keyed_accounts_iter = &mut keyed_accounts.iter();
+    let grant_keyed_account = next_keyed_account(keyed_accounts_iter)?;
+    if grant_keyed_account.owner()? != crate::id() {
+        return Err(InstructionError::InvalidAccountOwner);
+    }
+    let grant_pubkey = grant_keyed_account.unsigned_key();
+    let mut grant_state =
+        TokenGrantState::deserialize(&grant_keyed_account.try_account_ref()?.data())?;
+
+    match instruction {
+        VestInstruction::InitializeTokenGrant {
+            terminator_pubkey,
+            payee_pubkey,
+            schedule,
+            total_amount,
+        } => {
+            if grant_state != TokenGrantState::default() {
+                return Err(InstructionError::AccountAlreadyInitialized);
+            }
+            if !schedule.is_valid() {
+                return Err(VestError::InvalidSchedule.into());
+            }
+            let escrow = next_keyed_account(keyed_accounts_iter)?;
+            let (mint_pubkey, owner_pubkey, amount) = verify_token_account(escrow)?;
+            if owner_pubkey != get_escrow_authority(grant_pubkey).0 {
+                ic_msg!(
+                    invoke_context,
+                    "Escrow token account is not owned by the escrow authority"
+                );
+                return Err(VestError::InvalidTokenAccount.into());
+            }
+            if amount < total_amount {
+                return Err(InstructionError::InsufficientFunds);
+            }
+            grant_state = TokenGrantState {
+                terminator_pubkey,
+                payee_pubkey,
+                mint_pubkey,
+                escrow_pubkey: *escrow.unsigned_key(),
+                schedule,
+                total_amount,
+                ..TokenGrantState::default()
+            };
+        }
+        VestInstruction::WithdrawTokens(amount) => {
+            verify_signed_account(
+                next_keyed_account(keyed_accounts_iter)?,
+                &grant_state.payee_pubkey,
+            )?;
+            let accounts = next_escrow_accounts(keyed_accounts_iter)?;
+            if amount > grant_state.available_amount(accounts.clock.unix_timestamp) {
+                return Err(VestError::InsufficientVestedTokens.into());
+            }
+            transfer_from_escrow(
+                invoke_context,
+                grant_pubkey,
+                &grant_state,
+                &accounts,
+                amount,
+            )?;
+            grant_state.withdrawn_amount += amount;
+        }
+        VestInstruction::RevokeTokenGrant => {
+            verify_signed_account(
+                next_keyed_account(keyed_accounts_iter)?,
+                &grant_state.terminator_pubkey,
+            )?;
+            if grant_state.is_revoked {
+                return Err(VestError::GrantRevoked.into());
+            }
+            let accounts = next_escrow_accounts(keyed_accounts_iter)?;
+            let unvested_amount = grant_state.revoke(accounts.clock.unix_timestamp);
+            transfer_from_escrow(
+                invoke_context,
+                grant_pubkey,
+                &grant_state,
+                &accounts,
+                unvested_amount,
+            )?;
+        }
+        _ => return Err(InstructionError::InvalidInstructionData),
+    }
+
+    grant_state.serialize(
+        grant_keyed_account
+            .try_account_ref_mut()?
+            .data_as_mut_slice(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{id, token_grant_state::TokenVestSchedule, vest_instruction, vest_processor};
+    use solana_runtime::{bank::Bank, bank_client::BankClient, genesis_utils::activate_feature};
+    use solana_sdk::{
+        account::{create_account_shared_data_for_test, AccountSharedData},
+        client::SyncClient,
+        feature_set,
+        genesis_config::create_genesis_config,
+        instruction::{AccountMeta, Instruction},
+        message::Message,
+        signature::{Keypair, Signature, Signer},
+        sysvar,
+        transaction::TransactionError,
+        transport,
+    };
+    use std::sync::Arc;
+
+    const TRANSFER_TAG: u8 = 3;
+
+    /// A stand-in for the SPL Token program that only supports `Transfer`.
+    fn process_token_instruction(
+        _program_id: &Pubkey,
+        keyed_accounts: &[KeyedAccount],
+        data: &[u8],
+        _invoke_context: &mut dyn InvokeContext,
+    ) -> Result<(), InstructionError> {
+        if data.len() != 9 || data[0] != TRANSFER_TAG {
+            return Err(InstructionError::InvalidInstructionData);
+        }
+        let amount = u64::from_le_bytes(data[1..].try_into().unwrap());
+        let keyed_accounts_iter = &mut keyed_accounts.iter();
+        let source = next_keyed_account(keyed_accounts_iter)?;
+        let destination = next_keyed_account(keyed_accounts_iter)?;
+        let authority = next_keyed_account(keyed_accounts_iter)?;
+
+        let (source_mint, source_owner, source_amount) = verify_token_account(source)?;
+        let (destination_mint, _, destination_amount) = verify_token_account(destination)?;
+        if authority.signer_key() != Some(&source_owner) {
+            return Err(InstructionError::MissingRequiredSignature);
+        }
+        if source_mint != destination_mint || source_amount < amount {
+            return Err(InstructionError::InvalidArgument);
+        }
+        set_token_amount(source, source_amount - amount);
+        set_token_amount(destination, destination_amount + amount);
+        Ok(())
+    }
+
+    fn set_token_amount(keyed_account: &KeyedAccount, amount: u64) {
+        let offset = inline_spl_token_v2_0::SPL_TOKEN_ACCOUNT_AMOUNT_OFFSET;
+        keyed_account
+            .try_account_ref_mut()
+            .unwrap()
+            .data_as_mut_slice()[offset..offset + 8]
+            .copy_from_slice(&amount.to_le_bytes());
+    }
+
+    fn create_token_account(mint: &Pubkey, owner: &Pubkey, amount: u64) -> AccountSharedData {
+        let mut account = AccountSharedData::new(
+            1,
+            inline_spl_token_v2_0::state::Account::get_packed_len(),
+            &inline_spl_token_v2_0::id(),
+        );
+        let data = account.data_as_mut_slice();
+        data[..32].copy_from_slice(mint.as_ref());
+        data[32..64].copy_from_slice(owner.as_ref());
+        data[64..72].copy_from_slice(&amount.to_le_bytes());
+        account
+    }
+
+    fn get_token_amount(bank: &Bank, pubkey: &Pubkey) -> u64 {
+        let account = bank.get_account(pubkey).unwrap();
+        u64::from_le_bytes(account.data()[64..72].try_into().unwrap())
+    }
+
+    fn set_unix_timestamp(bank: &Bank, unix_timestamp: i64) {
+        let clock = Clock {
+            unix_timestamp,
+            ..Clock::default()
+        };
+        bank.store_account(
+            &sysvar::clock::id(),
+            &create_account_shared_data_for_test(&clock),
+        );
+    }
+
+    fn create_bank(lamports: u64) -> (Arc<Bank>, Keypair) {
+        create_bank_with_token_grants(lamports, true)
+    }
+
+    fn create_bank_with_token_grants(
+        lamports: u64,
+        token_grants_enabled: bool,
+    ) -> (Arc<Bank>, Keypair) {
+        let (mut genesis_config, mint_keypair) = create_genesis_config(lamports);
+        if token_grants_enabled {
+            activate_feature(&mut genesis_config, feature_set::vest_token_grants::id());
+        }
+        let mut bank = Bank::new(&genesis_config);
+        bank.add_builtin("vest_program", id(), vest_processor::process_instruction);
+        bank.add_builtin(
+            "spl_token",
+            inline_spl_token_v2_0::id(),
+            process_token_instruction,
+        );
+        (Arc::new(bank), mint_keypair)
+    }
+
+    fn create_schedule() -> TokenVestSchedule {
+        TokenVestSchedule {
+            start_unix_timestamp: 100,
+            cliff_unix_timestamp: 125,
+            end_unix_timestamp: 200,
+        }
+    }
+
+    fn create_token_grant(
+        bank_client: &BankClient,
+        payer_keypair: &Keypair,
+        grant_keypair: &Keypair,
+        escrow_pubkey: &Pubkey,
+        terminator_pubkey: &Pubkey,
+        payee_pubkey: &Pubkey,
+        total_amount: u64,
+    ) -> transport::Result<Signature> {
+        let instructions = vest_instruction::create_token_grant(
+            &payer_keypair.pubkey(),
+            &grant_keypair.pubkey(),
+            escrow_pubkey,
+            terminator_pubkey,
+            payee_pubkey,
+            create_schedule(),
+            total_amount,
+            1,
+        );
+        let message = Message::new(&instructions, Some(&payer_keypair.pubkey()));
+        bank_client.send_and_confirm_message(&[payer_keypair, grant_keypair], message)
+    }
+
+    fn send_instruction(
+        bank_client: &BankClient,
+        payer_keypair: &Keypair,
+        signer_keypair: &Keypair,
+        instruction: Instruction,
+    ) -> transport::Result<Signature> {
+        let message = Message::new(&[instruction], Some(&payer_keypair.pubkey()));
+        bank_client.send_and_confirm_message(&[payer_keypair, signer_keypair], message)
+    }
+
+    fn instruction_error(index: u8, error: VestError) -> TransactionError {
+        TransactionError::InstructionError(index, error.into())
+    }
+
+    #[test]
+    fn test_token_grants_disabled() {
+        let (bank, mint_keypair) = create_bank_with_token_grants(10_000, false);
+        let bank_client = BankClient::new_shared(&bank);
+        let grant_keypair = Keypair::new();
+        let token_mint = solana_sdk::pubkey::new_rand();
+        let escrow_authority = vest_instruction::get_escrow_authority(&grant_keypair.pubkey()).0;
+        let escrow_pubkey = solana_sdk::pubkey::new_rand();
+        bank.store_account(
+            &escrow_pubkey,
+            &create_token_account(&token_mint, &escrow_authority, 1_000),
+        );
+        assert_eq!(
+            create_token_grant(
+                &bank_client,
+                &mint_keypair,
+                &grant_keypair,
+                &escrow_pubkey,
+                &mint_keypair.pubkey(),
+                &mint_keypair.pubkey(),
+                1_000,
+            )
+            .unwrap_err()
+            .unwrap(),
+            TransactionError::InstructionError(1, InstructionError::InvalidInstructionData)
+        );
+    }
+
+    #[test]
+    fn test_initialize_token_grant_invalid_escrow() {
+        let (bank, mint_keypair) = create_bank(10_000);
+        let bank_client = BankClient::new_shared(&bank);
+        let grant_keypair = Keypair::new();
+        let token_mint = solana_sdk::pubkey::new_rand();
+
+        // Attack! Escrow isn't controlled by the grant.
+        let escrow_pubkey = solana_sdk::pubkey::new_rand();
+        bank.store_account(
+            &escrow_pubkey,
+            &create_token_account(&token_mint, &mint_keypair.pubkey(), 1_000),
+        );
+        assert_eq!(
+            create_token_grant(
+                &bank_client,
+                &mint_keypair,
+                &grant_keypair,
+                &escrow_pubkey,
+                &mint_keypair.pubkey(),
+                &mint_keypair.pubkey(),
+                1_000,
+            )
+            .unwrap_err()
+            .unwrap(),
+            instruction_error(1, VestError::InvalidTokenAccount)
+        );
+    }
+
+    #[test]
+    fn test_initialize_token_grant_insufficient_escrow() {
+        let (bank, mint_keypair) = create_bank(10_000);
+        let bank_client = BankClient::new_shared(&bank);
+        let grant_keypair = Keypair::new();
+        let token_mint = solana_sdk::pubkey::new_rand();
+        let escrow_authority = vest_instruction::get_escrow_authority(&grant_keypair.pubkey()).0;
+        let escrow_pubkey = solana_sdk::pubkey::new_rand();
+        bank.store_account(
+            &escrow_pubkey,
+            &create_token_account(&token_mint, &escrow_authority, 999),
+        );
+        assert_eq!(
+            create_token_grant(
+                &bank_client,
+                &mint_keypair,
+                &grant_keypair,
+                &escrow_pubkey,
+                &mint_keypair.pubkey(),
+                &mint_keypair.pubkey(),
+                1_000,
+            )
+            .unwrap_err()
+            .unwrap(),
+            TransactionError::InstructionError(1, InstructionError::InsufficientFunds)
+        );
+    }
+
+    #[test]
+    fn test_withdraw_and_revoke_token_grant() {
+        let (bank, mint_keypair) = create_bank(10_000);
+        let bank_client = BankClient::new_shared(&bank);
+        let grant_keypair = Keypair::new();
+        let grant_pubkey = grant_keypair.pubkey();
+        let terminator_keypair = Keypair::new();
+        let payee_keypair = Keypair::new();
+        let token_mint = solana_sdk::pubkey::new_rand();
+        let escrow_authority = vest_instruction::get_escrow_authority(&grant_pubkey).0;
+
+        let escrow_pubkey = solana_sdk::pubkey::new_rand();
+        bank.store_account(
+            &escrow_pubkey,
+            &create_token_account(&token_mint, &escrow_authority, 1_000),
+        );
+        let payee_token_pubkey = solana_sdk::pubkey::new_rand();
+        bank.store_account(
+            &payee_token_pubkey,
+            &create_token_account(&token_mint, &payee_keypair.pubkey(), 0),
+        );
+        let refund_token_pubkey = solana_sdk::pubkey::new_rand();
+        bank.store_account(
+            &refund_token_pubkey,
+            &create_token_account(&token_mint, &terminator_keypair.pubkey(), 0),
+        );
+
+        set_unix_timestamp(&bank, 0);
+        create_token_grant(
+            &bank_client,
+            &mint_keypair,
+            &grant_keypair,
+            &escrow_pubkey,
+            &terminator_keypair.pubkey(),
+            &payee_keypair.pubkey(),
+            1_000,
+        )
+        .unwrap();
+
+        // Ensure the grant can't be reinitialized with a new payee.
+        let instruction = Instruction::new_with_bincode(
+            id(),
+            &VestInstruction::InitializeTokenGrant {
+                terminator_pubkey: mint_keypair.pubkey(),
+                payee_pubkey: mint_keypair.pubkey(),
+                schedule: create_schedule(),
+                total_amount: 1_000,
+            },
+            vec![
+                AccountMeta::new(grant_pubkey, false),
+                AccountMeta::new_readonly(escrow_pubkey, false),
+            ],
+        );
+        assert_eq!(
+            bank_client
+                .send_and_confirm_instruction(&mint_keypair, instruction)
+                .unwrap_err()
+                .unwrap(),
+            TransactionError::InstructionError(0, InstructionError::AccountAlreadyInitialized)
+        );
+
+        // Nothing is withdrawable before the cliff.
+        set_unix_timestamp(&bank, 110);
+        let instruction = vest_instruction::withdraw_tokens(
+            &grant_pubkey,
+            &payee_keypair.pubkey(),
+            &escrow_pubkey,
+            &payee_token_pubkey,
+            1,
+        );
+        assert_eq!(
+            send_instruction(&bank_client, &mint_keypair, &payee_keypair, instruction)
+                .unwrap_err()
+                .unwrap(),
+            instruction_error(0, VestError::InsufficientVestedTokens)
+        );
+
+        // Only the payee may withdraw.
+        set_unix_timestamp(&bank, 150);
+        let instruction = vest_instruction::withdraw_tokens(
+            &grant_pubkey,
+            &terminator_keypair.pubkey(),
+            &escrow_pubkey,
+            &refund_token_pubkey,
+            100,
+        );
+        assert_eq!(
+            send_instruction(
+                &bank_client,
+                &mint_keypair,
+                &terminator_keypair,
+                instruction
+            )
+            .unwrap_err()
+            .unwrap(),
+            instruction_error(0, VestError::Unauthorized)
+        );
+
+        // Withdraw part of the vested tokens.
+        let instruction = vest_instruction::withdraw_tokens(
+            &grant_pubkey,
+            &payee_keypair.pubkey(),
+            &escrow_pubkey,
+            &payee_token_pubkey,
+            300,
+        );
+        send_instruction(&bank_client, &mint_keypair, &payee_keypair, instruction).unwrap();
+        assert_eq!(get_token_amount(&bank, &payee_token_pubkey), 300);
+        assert_eq!(get_token_amount(&bank, &escrow_pubkey), 700);
+
+        let instruction = vest_instruction::withdraw_tokens(
+            &grant_pubkey,
+            &payee_keypair.pubkey(),
+            &escrow_pubkey,
+            &payee_token_pubkey,
+            201,
+        );
+        assert_eq!(
+            send_instruction(&bank_client, &mint_keypair, &payee_keypair, instruction)
+                .unwrap_err()
+                .unwrap(),
+            instruction_error(0, VestError::InsufficientVestedTokens)
+        );
+
+        // Revoke the unvested half of the grant.
+        let instruction = vest_instruction::revoke_token_grant(
+            &grant_pubkey,
+            &terminator_keypair.pubkey(),
+            &escrow_pubkey,
+            &refund_token_pubkey,
+        );
+        send_instruction(
+            &bank_client,
+            &mint_keypair,
+            &terminator_keypair,
+            instruction,
+        )
+        .unwrap();
+        assert_eq!(get_token_amount(&bank, &refund_token_pubkey), 500);
+        assert_eq!(get_token_amount(&bank, &escrow_pubkey), 200);
+
+        set_unix_timestamp(&bank, 175);
+        let instruction = vest_instruction::revoke_token_grant(
+            &grant_pubkey,
+            &terminator_keypair.pubkey(),
+            &escrow_pubkey,
+            &payee_token_pubkey,
+        );
+        assert_eq!(
+            send_instruction(
+                &bank_client,
+                &mint_keypair,
+                &terminator_keypair,
+                instruction
+            )
+            .unwrap_err()
+            .unwrap(),
+            instruction_error(0, VestError::GrantRevoked)
+        );
+
+        // Tokens vested before revocation remain withdrawable, but no more.
+        set_unix_timestamp(&bank, 200);
+        let instruction = vest_instruction::withdraw_tokens(
+            &grant_pubkey,
+            &payee_keypair.pubkey(),
+            &escrow_pubkey,
+            &payee_token_pubkey,
+            200,
+        );
+        send_instruction(&bank_client, &mint_keypair, &payee_keypair, instruction).unwrap();
+        assert_eq!(get_token_amount(&bank, &payee_token_pubkey), 500);
+        assert_eq!(get_token_amount(&bank, &escrow_pubkey), 0);
+
+        let grant_account = bank.get_account(&grant_pubkey).unwrap();
+        let grant_state = TokenGrantState::deserialize(&grant_account.data()).unwrap();
+        assert_eq!(grant_state.total_amount, 500);
+        assert_eq!(grant_state.withdrawn_amount, 500);
+        assert!(grant_state.is_revoked);
+    }
+}
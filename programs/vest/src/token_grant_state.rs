@@ -0,0 +1,247 @@
+//! token grant state
+use bincode::{self, deserialize, serialize_into, serialized_size};
+use serde_derive::{Deserialize, Serialize};
+use solana_sdk::{clock::UnixTimestamp, instruction::InstructionError, pubkey::Pubkey};
+
+/// A cliff plus linear unlock schedule, evaluated against the `Clock` sysvar.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TokenVestSchedule {
+    /// The time from which tokens begin to vest linearly
+    pub start_unix_timestamp: UnixTimestamp,
+
+    /// No tokens are released before this time
+    pub cliff_unix_timestamp: UnixTimestamp,
+
+    /// The time at which all tokens are vested
+    pub end_unix_timestamp: UnixTimestamp,
+}
+
+impl TokenVestSchedule {
+    pub fn is_valid(&self) -> bool {
+        self.start_unix_timestamp <= self.cliff_unix_timestamp
+            && self.cliff_unix_timestamp <= self.end_unix_timestamp
+    }
+
+    /// Return the number of `total_amount` tokens vested at `unix_timestamp`.
+    pub fn vested_amount(&self, total_amount: u64, unix_timestamp: UnixTimestamp) -> u64 {
+        if unix_timestamp < self.cliff_unix_timestamp {
+            0
+        } else if unix_timestamp >= self.end_unix_timestamp {
+            total_amount
+        } else {
+            // Timestamps are widened first, so that schedules spanning most of
+            // the i64 range can't overflow
+            let start = i128::from(self.start_unix_timestamp);
+            let elapsed = (i128::from(unix_timestamp) - start) as u128;
+            let duration = (i128::from(self.end_unix_timestamp) - start) as u128;
+            (total_amount as u128 * elapsed / duration) as u64
+        }
+    }
+}
+
+/// Leading byte of an initialized token grant account, which keeps its data
+/// from being mistaken for a vesting contract owned by the same program
+const TOKEN_GRANT_STATE_TAG: u8 = 1;
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+pub struct TokenGrantState {
+    /// The address authorized to revoke unvested tokens with a signed RevokeTokenGrant instruction
+    pub terminator_pubkey: Pubkey,
+
+    /// The address authorized to withdraw vested tokens
+    pub payee_pubkey: Pubkey,
+
+    /// The mint of the granted tokens
+    pub mint_pubkey: Pubkey,
+
+    /// The token account holding the granted tokens, owned by the grant's escrow authority
+    pub escrow_pubkey: Pubkey,
+
+    /// The schedule on which `total_amount` vests
+    pub schedule: TokenVestSchedule,
+
+    /// The number of tokens to pay the payee if the schedule completes
+    pub total_amount: u64,
+
+    /// The number of tokens the payee has already withdrawn
+    pub withdrawn_amount: u64,
+
+    /// True if the terminator has revoked the unvested tokens
+    pub is_revoked: bool,
+}
+
+impl TokenGrantState {
+    pub fn size_of() -> usize {
+        1 + serialized_size(&Self::default()).unwrap() as usize
+    }
+
+    /// Return true if `data` holds an initialized token grant.
+    pub fn is_token_grant(data: &[u8]) -> bool {
+        data.len() == Self::size_of() && data[0] == TOKEN_GRANT_STATE_TAG
+    }
+
+    pub fn serialize(&self, output: &mut [u8]) -> Result<(), InstructionError> {
+        let (tag, output) = output
+            .split_first_mut()
+            .ok_or(InstructionError::AccountDataTooSmall)?;
+        *tag = TOKEN_GRANT_STATE_TAG;
+        serialize_into(output, self).map_err(|_| InstructionError::AccountDataTooSmall)
+    }
+
+    /// Deserialize a token grant, or return the default state if `input` is
+    /// an uninitialized account of the right size.
+    pub fn deserialize(input: &[u8]) -> Result<Self, InstructionError> {
+        if Self::is_token_grant(input) {
+            deserialize(&input[1..]).map_err(|_| InstructionError::InvalidAccountData)
+        } else if input.len() == Self::size_of() && input.iter().all(|byte| *byte == 0) {
+            Ok(Self::default())
+        } else {
+            Err(InstructionError::InvalidAccountData)
+        }
+    }
+
+    /// Return the number of tokens vested at `unix_timestamp`. Once revoked,
+    /// `total_amount` only covers tokens that had vested at revocation.
+    pub fn vested_amount(&self, unix_timestamp: UnixTimestamp) -> u64 {
+        if self.is_revoked {
+            return self.total_amount;
+        }
+        self.schedule
+            .vested_amount(self.total_amount, unix_timestamp)
+    }
+
+    /// Return the number of vested tokens not yet withdrawn.
+    pub fn available_amount(&self, unix_timestamp: UnixTimestamp) -> u64 {
+        self.vested_amount(unix_timestamp)
+            .saturating_sub(self.withdrawn_amount)
+    }
+
+    /// Stop vesting at `unix_timestamp` and return the number of unvested tokens.
+    pub fn revoke(&mut self, unix_timestamp: UnixTimestamp) -> u64 {
+        let vested_amount = self.vested_amount(unix_timestamp);
+        let unvested_amount = self.total_amount - vested_amount;
+        self.total_amount = vested_amount;
+        self.is_revoked = true;
+        unvested_amount
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::id;
+    use solana_sdk::account::{AccountSharedData, ReadableAccount, WritableAccount};
+
+    fn create_schedule() -> TokenVestSchedule {
+        TokenVestSchedule {
+            start_unix_timestamp: 100,
+            cliff_unix_timestamp: 125,
+            end_unix_timestamp: 200,
+        }
+    }
+
+    #[test]
+    fn test_serializer() {
+        let mut a = AccountSharedData::new(0, TokenGrantState::size_of(), &id());
+        assert_eq!(
+            TokenGrantState::deserialize(&a.data()).unwrap(),
+            TokenGrantState::default()
+        );
+        assert!(!TokenGrantState::is_token_grant(&a.data()));
+
+        let b = TokenGrantState {
+            schedule: create_schedule(),
+            total_amount: 42,
+            ..TokenGrantState::default()
+        };
+        b.serialize(a.data_as_mut_slice()).unwrap();
+        assert!(TokenGrantState::is_token_grant(&a.data()));
+        let c = TokenGrantState::deserialize(&a.data()).unwrap();
+        assert_eq!(b, c);
+
+        // Untagged or wrongly sized data isn't a token grant
+        let mut data = a.data().to_vec();
+        data[0] = 0;
+        assert_eq!(
+            TokenGrantState::deserialize(&data),
+            Err(InstructionError::InvalidAccountData)
+        );
+        let mut data = a.data().to_vec();
+        data.push(0);
+        assert_eq!(
+            TokenGrantState::deserialize(&data),
+            Err(InstructionError::InvalidAccountData)
+        );
+    }
+
+    #[test]
+    fn test_schedule_is_valid() {
+        assert!(create_schedule().is_valid());
+        assert!(TokenVestSchedule::default().is_valid());
+        assert!(!TokenVestSchedule {
+            cliff_unix_timestamp: 99,
+            ..create_schedule()
+        }
+        .is_valid());
+        assert!(!TokenVestSchedule {
+            end_unix_timestamp: 124,
+            ..create_schedule()
+        }
+        .is_valid());
+    }
+
+    #[test]
+    fn test_vested_amount() {
+        let schedule = create_schedule();
+        assert_eq!(schedule.vested_amount(1_000, 0), 0);
+        assert_eq!(schedule.vested_amount(1_000, 124), 0);
+
+        // Tokens vested linearly before the cliff are released at the cliff.
+        assert_eq!(schedule.vested_amount(1_000, 125), 250);
+        assert_eq!(schedule.vested_amount(1_000, 150), 500);
+        assert_eq!(schedule.vested_amount(1_000, 199), 990);
+        assert_eq!(schedule.vested_amount(1_000, 200), 1_000);
+        assert_eq!(schedule.vested_amount(1_000, i64::MAX), 1_000);
+
+        // Ensure no overflow on large grants.
+        assert_eq!(schedule.vested_amount(u64::MAX, 150), u64::MAX / 2);
+
+        // Ensure no overflow on schedules spanning the whole timestamp range.
+        let schedule = TokenVestSchedule {
+            start_unix_timestamp: i64::MIN,
+            cliff_unix_timestamp: i64::MIN,
+            end_unix_timestamp: i64::MAX,
+        };
+        assert_eq!(schedule.vested_amount(1_000, i64::MIN), 0);
+        assert_eq!(schedule.vested_amount(1_000, 0), 500);
+        assert_eq!(schedule.vested_amount(1_000, i64::MAX), 1_000);
+
+        // Everything vests at the cliff when there is no linear period.
+        let schedule = TokenVestSchedule {
+            start_unix_timestamp: 100,
+            cliff_unix_timestamp: 100,
+            end_unix_timestamp: 100,
+        };
+        assert_eq!(schedule.vested_amount(1_000, 99), 0);
+        assert_eq!(schedule.vested_amount(1_000, 100), 1_000);
+    }
+
+    #[test]
+    fn test_revoke() {
+        let mut grant_state = TokenGrantState {
+            schedule: create_schedule(),
+            total_amount: 1_000,
+            withdrawn_amount: 100,
+            ..TokenGrantState::default()
+        };
+        assert_eq!(grant_state.available_amount(150), 400);
+
+        assert_eq!(grant_state.revoke(150), 500);
+        assert_eq!(grant_state.total_amount, 500);
+        assert!(grant_state.is_revoked);
+
+        // Verify vesting stops at revocation.
+        assert_eq!(grant_state.vested_amount(200), 500);
+        assert_eq!(grant_state.available_amount(200), 400);
+    }
+}
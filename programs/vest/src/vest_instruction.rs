@@ -1,13 +1,18 @@
-use crate::{id, vest_state::VestState};
+use crate::{
+    id,
+    token_grant_state::{TokenGrantState, TokenVestSchedule},
+    vest_state::VestState,
+};
 use bincode::serialized_size;
 use chrono::prelude::{Date, DateTime, Utc};
 use num_derive::FromPrimitive;
 use serde_derive::{Deserialize, Serialize};
 use solana_sdk::{
     decode_error::DecodeError,
+    inline_spl_token_v2_0,
     instruction::{AccountMeta, Instruction, InstructionError},
     pubkey::Pubkey,
-    system_instruction,
+    system_instruction, sysvar,
 };
 use thiserror::Error;
 
@@ -18,6 +23,18 @@ pub enum VestError {
 
     #[error("unauthorized")]
     Unauthorized,
+
+    #[error("invalid vesting schedule")]
+    InvalidSchedule,
+
+    #[error("invalid token account")]
+    InvalidTokenAccount,
+
+    #[error("insufficient vested tokens")]
+    InsufficientVestedTokens,
+
+    #[error("grant already revoked")]
+    GrantRevoked,
 }
 
 impl From<VestError> for InstructionError {
@@ -64,6 +81,55 @@ pub enum VestInstruction {
 
     /// Mark all available tokens as redeemable, regardless of the date.
     VestAll,
+
+    /// Declare a grant of SPL tokens vesting on a cliff plus linear schedule
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Uninitialized grant account
+    ///   1. `[]` Escrow token account, owned by the grant's escrow authority and
+    ///      holding at least `total_amount` tokens
+    InitializeTokenGrant {
+        terminator_pubkey: Pubkey, // The address authorized to revoke unvested tokens with a signed RevokeTokenGrant instruction
+        payee_pubkey: Pubkey,      // The address authorized to withdraw vested tokens
+        schedule: TokenVestSchedule, // The schedule on which the grant vests
+        total_amount: u64, // The number of tokens to pay the payee if the schedule completes
+    },
+
+    /// Transfer vested tokens from the escrow to a token account
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Grant account
+    ///   1. `[SIGNER]` Payee
+    ///   2. `[WRITE]` Escrow token account
+    ///   3. `[WRITE]` Destination token account
+    ///   4. `[]` Escrow authority
+    ///   5. `[]` Clock sysvar
+    ///   6. `[]` SPL Token program
+    WithdrawTokens(u64),
+
+    /// Stop vesting and return the unvested tokens to a token account. Tokens
+    /// that have already vested remain withdrawable by the payee.
+    ///
+    /// # Account references
+    ///   0. `[WRITE]` Grant account
+    ///   1. `[SIGNER]` Terminator
+    ///   2. `[WRITE]` Escrow token account
+    ///   3. `[WRITE]` Refund token account
+    ///   4. `[]` Escrow authority
+    ///   5. `[]` Clock sysvar
+    ///   6. `[]` SPL Token program
+    RevokeTokenGrant,
+}
+
+impl VestInstruction {
+    pub fn is_token_grant_instruction(&self) -> bool {
+        matches!(
+            self,
+            VestInstruction::InitializeTokenGrant { .. }
+                | VestInstruction::WithdrawTokens(_)
+                | VestInstruction::RevokeTokenGrant
+        )
+    }
 }
 
 fn initialize_account(
@@ -169,3 +235,102 @@ pub fn vest_all(contract: &Pubkey, from: &Pubkey) -> Instruction {
     ];
     Instruction::new_with_bincode(id(), &VestInstruction::VestAll, account_metas)
 }
+
+/// Return the address that owns a grant's escrow token account, and its bump seed.
+pub fn get_escrow_authority(grant: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[grant.as_ref()], &id())
+}
+
+fn initialize_token_grant(
+    grant_pubkey: &Pubkey,
+    escrow_pubkey: &Pubkey,
+    terminator_pubkey: &Pubkey,
+    payee_pubkey: &Pubkey,
+    schedule: TokenVestSchedule,
+    total_amount: u64,
+) -> Instruction {
+    let account_metas = vec![
+        AccountMeta::new(*grant_pubkey, false),
+        AccountMeta::new_readonly(*escrow_pubkey, false),
+    ];
+    Instruction::new_with_bincode(
+        id(),
+        &VestInstruction::InitializeTokenGrant {
+            terminator_pubkey: *terminator_pubkey,
+            payee_pubkey: *payee_pubkey,
+            schedule,
+            total_amount,
+        },
+        account_metas,
+    )
+}
+
+/// Create a grant account and declare a grant of the tokens held by
+/// `escrow_pubkey`. The escrow must already be a token account owned by the
+/// grant's escrow authority; see `get_escrow_authority()`.
+#[allow(clippy::too_many_arguments)]
+pub fn create_token_grant(
+    payer_pubkey: &Pubkey,
+    grant_pubkey: &Pubkey,
+    escrow_pubkey: &Pubkey,
+    terminator_pubkey: &Pubkey,
+    payee_pubkey: &Pubkey,
+    schedule: TokenVestSchedule,
+    total_amount: u64,
+    lamports: u64,
+) -> Vec<Instruction> {
+    let space = TokenGrantState::size_of() as u64;
+    vec![
+        system_instruction::create_account(&payer_pubkey, grant_pubkey, lamports, space, &id()),
+        initialize_token_grant(
+            grant_pubkey,
+            escrow_pubkey,
+            terminator_pubkey,
+            payee_pubkey,
+            schedule,
+            total_amount,
+        ),
+    ]
+}
+
+fn token_grant_account_metas(
+    grant: &Pubkey,
+    authority: &Pubkey,
+    escrow: &Pubkey,
+    to: &Pubkey,
+) -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new(*grant, false),
+        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new(*escrow, false),
+        AccountMeta::new(*to, false),
+        AccountMeta::new_readonly(get_escrow_authority(grant).0, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(inline_spl_token_v2_0::id(), false),
+    ]
+}
+
+pub fn withdraw_tokens(
+    grant: &Pubkey,
+    payee: &Pubkey,
+    escrow: &Pubkey,
+    to: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let account_metas = token_grant_account_metas(grant, payee, escrow, to);
+    Instruction::new_with_bincode(
+        id(),
+        &VestInstruction::WithdrawTokens(amount),
+        account_metas,
+    )
+}
+
+pub fn revoke_token_grant(
+    grant: &Pubkey,
+    terminator: &Pubkey,
+    escrow: &Pubkey,
+    to: &Pubkey,
+) -> Instruction {
+    let account_metas = token_grant_account_metas(grant, terminator, escrow, to);
+    Instruction::new_with_bincode(id(), &VestInstruction::RevokeTokenGrant, account_metas)
+}
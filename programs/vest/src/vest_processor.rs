@@ -1,5 +1,7 @@
 //! vest program
 use crate::{
+    token_grant_processor::process_token_grant_instruction,
+    token_grant_state::TokenGrantState,
     vest_instruction::{VestError, VestInstruction},
     vest_state::VestState,
 };
@@ -46,7 +48,7 @@ fn verify_account<'a>(
     keyed_account.try_account_ref_mut()
 }
 
-fn verify_signed_account<'a>(
+pub(crate) fn verify_signed_account<'a>(
     keyed_account: &'a KeyedAccount,
     expected_pubkey: &Pubkey,
 ) -> Result<RefMut<'a, AccountSharedData>, InstructionError> {
@@ -63,6 +65,14 @@ pub fn process_instruction(
     data: &[u8],
     invoke_context: &mut dyn InvokeContext,
 ) -> Result<(), InstructionError> {
+    let instruction: VestInstruction = limited_deserialize(data)?;
+    if instruction.is_token_grant_instruction() {
+        if !invoke_context.is_feature_active(&feature_set::vest_token_grants::id()) {
+            return Err(InstructionError::InvalidInstructionData);
+        }
+        return process_token_grant_instruction(keyed_accounts, instruction, invoke_context);
+    }
+
     let keyed_accounts_iter = &mut keyed_accounts.iter();
     let contract_account = &mut next_keyed_account(keyed_accounts_iter)?.try_account_ref_mut()?;
     if invoke_context.is_feature_active(&feature_set::check_program_owner::id())
@@ -70,8 +80,9 @@ pub fn process_instruction(
     {
         return Err(InstructionError::InvalidAccountOwner);
     }
-
-    let instruction = limited_deserialize(data)?;
+    if TokenGrantState::is_token_grant(&contract_account.data()) {
+        return Err(InstructionError::InvalidAccountData);
+    }
 
     let mut vest_state = if let VestInstruction::InitializeAccount {
         terminator_pubkey,
@@ -145,6 +156,11 @@ pub fn process_instruction(
             )?;
             vest_state.vest_all();
         }
+        VestInstruction::InitializeTokenGrant { .. }
+        | VestInstruction::WithdrawTokens(_)
+        | VestInstruction::RevokeTokenGrant => {
+            return Err(InstructionError::InvalidInstructionData);
+        }
     }
 
     vest_state.serialize(contract_account.data_as_mut_slice())
@@ -3,7 +3,10 @@
 
 use crate::{
     id,
-    vote_state::{self, Vote, VoteAuthorize, VoteInit, VoteState},
+    vote_state::{
+        self, serde_compact_vote_state_update, Vote, VoteAuthorize, VoteInit, VoteState,
+        VoteStateUpdate,
+    },
 };
 use log::*;
 use num_derive::{FromPrimitive, ToPrimitive};
@@ -19,7 +22,7 @@ use solana_sdk::{
     program_utils::limited_deserialize,
     pubkey::Pubkey,
     system_instruction,
-    sysvar::{self, clock::Clock, slot_hashes::SlotHashes},
+    sysvar::{self, clock::Clock, epoch_schedule::EpochSchedule, slot_hashes::SlotHashes, Sysvar},
 };
 use std::collections::HashSet;
 use thiserror::Error;
@@ -44,6 +47,39 @@ pub enum VoteError {
 
     #[error("authorized voter has already been changed this epoch")]
     TooSoonToReauthorize,
+
+    #[error("vote state update has more lockouts than the lockout history allows")]
+    TooManyVotes,
+
+    #[error("vote state update slots are not ordered")]
+    SlotsNotOrdered,
+
+    #[error("vote state update confirmations are not ordered")]
+    ConfirmationsNotOrdered,
+
+    #[error("vote state update contains a lockout with zero confirmations")]
+    ZeroConfirmations,
+
+    #[error("vote state update contains a confirmation count above the limit")]
+    ConfirmationTooLarge,
+
+    #[error("vote state update root rolls back the current root")]
+    RootRollBack,
+
+    #[error("vote state update rolls back the confirmations of a current vote")]
+    ConfirmationRollBack,
+
+    #[error("vote state update contains a slot at or below its root")]
+    SlotSmallerThanRoot,
+
+    #[error("vote state update drops a current vote that is still locked out")]
+    LockoutConflict,
+
+    #[error("vote state update contains a lockout that expired before a later vote")]
+    NewVoteStateLockoutMismatch,
+
+    #[error("commission increases are only allowed in the first half of an epoch")]
+    CommissionUpdateTooLate,
 }
 
 impl<E> DecodeError<E> for VoteError {
@@ -98,6 +134,9 @@ pub enum VoteInstruction {
 
     /// Update the commission for the vote account
     ///
+    /// Once commission updates are restricted, increases are only allowed in
+    /// the first half of an epoch
+    ///
     /// # Account references
     ///   0. [WRITE] Vote account to be updated
     ///   1. [SIGNER] Withdraw authority
@@ -111,6 +150,29 @@ pub enum VoteInstruction {
     ///   2. [] Clock sysvar
     ///   3. [SIGNER] Vote authority
     VoteSwitch(Vote, Hash),
+
+    /// Replace the vote account's lockouts and root with the validator's full
+    /// tower, sent in a compact encoding
+    ///
+    /// # Account references
+    ///   0. [WRITE] Vote account to vote with
+    ///   1. [] Slot hashes sysvar
+    ///   2. [] Clock sysvar
+    ///   3. [SIGNER] Vote authority
+    CompactUpdateVoteState(#[serde(with = "serde_compact_vote_state_update")] VoteStateUpdate),
+
+    /// Replace the vote account's lockouts and root with the validator's full
+    /// tower, sent in a compact encoding, with a switching proof
+    ///
+    /// # Account references
+    ///   0. [WRITE] Vote account to vote with
+    ///   1. [] Slot hashes sysvar
+    ///   2. [] Clock sysvar
+    ///   3. [SIGNER] Vote authority
+    CompactUpdateVoteStateSwitch(
+        #[serde(with = "serde_compact_vote_state_update")] VoteStateUpdate,
+        Hash,
+    ),
 }
 
 fn initialize_account(vote_pubkey: &Pubkey, vote_init: &VoteInit) -> Instruction {
@@ -248,6 +310,45 @@ pub fn vote_switch(
     )
 }
 
+pub fn compact_update_vote_state(
+    vote_pubkey: &Pubkey,
+    authorized_voter_pubkey: &Pubkey,
+    vote_state_update: VoteStateUpdate,
+) -> Instruction {
+    let account_metas = vec![
+        AccountMeta::new(*vote_pubkey, false),
+        AccountMeta::new_readonly(sysvar::slot_hashes::id(), false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(*authorized_voter_pubkey, true),
+    ];
+
+    Instruction::new_with_bincode(
+        id(),
+        &VoteInstruction::CompactUpdateVoteState(vote_state_update),
+        account_metas,
+    )
+}
+
+pub fn compact_update_vote_state_switch(
+    vote_pubkey: &Pubkey,
+    authorized_voter_pubkey: &Pubkey,
+    vote_state_update: VoteStateUpdate,
+    proof_hash: Hash,
+) -> Instruction {
+    let account_metas = vec![
+        AccountMeta::new(*vote_pubkey, false),
+        AccountMeta::new_readonly(sysvar::slot_hashes::id(), false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(*authorized_voter_pubkey, true),
+    ];
+
+    Instruction::new_with_bincode(
+        id(),
+        &VoteInstruction::CompactUpdateVoteStateSwitch(vote_state_update, proof_hash),
+        account_metas,
+    )
+}
+
 pub fn withdraw(
     vote_pubkey: &Pubkey,
     authorized_withdrawer_pubkey: &Pubkey,
@@ -275,6 +376,16 @@ fn verify_rent_exemption(
     }
 }
 
+fn get_sysvar<T: Sysvar>(
+    invoke_context: &mut dyn InvokeContext,
+    id: &Pubkey,
+) -> Result<T, InstructionError> {
+    invoke_context
+        .get_sysvar_data(id)
+        .and_then(|data| bincode::deserialize(&data).ok())
+        .ok_or(InstructionError::UnsupportedSysvar)
+}
+
 pub fn process_instruction(
     _program_id: &Pubkey,
     keyed_accounts: &[KeyedAccount],
@@ -319,7 +430,21 @@ pub fn process_instruction(
             &signers,
         ),
         VoteInstruction::UpdateCommission(commission) => {
-            vote_state::update_commission(me, commission, &signers)
+            let restrict_commission_increases = invoke_context.is_feature_active(
+                &feature_set::commission_updates_only_allowed_in_first_half_of_epoch::id(),
+            );
+            vote_state::update_commission(me, commission, &signers, || {
+                if !restrict_commission_increases {
+                    return Ok(true);
+                }
+                let clock = get_sysvar::<Clock>(invoke_context, &sysvar::clock::id())?;
+                let epoch_schedule =
+                    get_sysvar::<EpochSchedule>(invoke_context, &sysvar::epoch_schedule::id())?;
+                Ok(vote_state::is_commission_increase_allowed(
+                    clock.slot,
+                    &epoch_schedule,
+                ))
+            })
         }
         VoteInstruction::Vote(vote) | VoteInstruction::VoteSwitch(vote, _) => {
             inc_new_counter_info!("vote-native", 1);
@@ -331,6 +456,22 @@ pub fn process_instruction(
                 &signers,
             )
         }
+        VoteInstruction::CompactUpdateVoteState(vote_state_update)
+        | VoteInstruction::CompactUpdateVoteStateSwitch(vote_state_update, _) => {
+            if !invoke_context
+                .is_feature_active(&feature_set::allow_votes_to_directly_update_vote_state::id())
+            {
+                return Err(InstructionError::InvalidInstructionData);
+            }
+            inc_new_counter_info!("vote-state-native", 1);
+            vote_state::process_vote_state_update(
+                me,
+                &from_keyed_account::<SlotHashes>(next_keyed_account(keyed_accounts)?)?,
+                &from_keyed_account::<Clock>(next_keyed_account(keyed_accounts)?)?,
+                &vote_state_update,
+                &signers,
+            )
+        }
         VoteInstruction::Withdraw(lamports) => {
             let to = next_keyed_account(keyed_accounts)?;
             vote_state::withdraw(me, lamports, to, &signers)
@@ -346,8 +487,8 @@ mod tests {
         process_instruction::MockInvokeContext,
         rent::Rent,
     };
-    use std::cell::RefCell;
     use std::str::FromStr;
+    use std::{cell::RefCell, rc::Rc};
 
     // these are for 100% coverage in this file
     #[test]
@@ -454,6 +595,23 @@ mod tests {
             )),
             Err(InstructionError::InvalidAccountData),
         );
+        assert_eq!(
+            process_instruction(&compact_update_vote_state(
+                &Pubkey::default(),
+                &Pubkey::default(),
+                VoteStateUpdate::default(),
+            )),
+            Err(InstructionError::InvalidAccountData),
+        );
+        assert_eq!(
+            process_instruction(&compact_update_vote_state_switch(
+                &Pubkey::default(),
+                &Pubkey::default(),
+                VoteStateUpdate::default(),
+                Hash::default(),
+            )),
+            Err(InstructionError::InvalidAccountData),
+        );
         assert_eq!(
             process_instruction(&authorize(
                 &Pubkey::default(),
@@ -491,6 +649,112 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_update_commission_reads_sysvars_for_increases() {
+        let authorized_withdrawer = Pubkey::new_unique();
+        let vote_account = RefCell::new(vote_state::create_account_with_authorized(
+            &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
+            &authorized_withdrawer,
+            10,
+            100,
+        ));
+        let withdrawer_account = RefCell::new(AccountSharedData::default());
+        let vote_pubkey = Pubkey::new_unique();
+        let keyed_accounts = [
+            KeyedAccount::new(&vote_pubkey, false, &vote_account),
+            KeyedAccount::new(&authorized_withdrawer, true, &withdrawer_account),
+        ];
+        let update_commission = |commission, invoke_context: &mut MockInvokeContext| {
+            super::process_instruction(
+                &Pubkey::default(),
+                &keyed_accounts,
+                &bincode::serialize(&VoteInstruction::UpdateCommission(commission)).unwrap(),
+                invoke_context,
+            )
+        };
+        let set_clock_slot = |invoke_context: &mut MockInvokeContext, slot| {
+            let clock = Clock {
+                slot,
+                ..Clock::default()
+            };
+            invoke_context.sysvars = vec![
+                (
+                    sysvar::clock::id(),
+                    Some(Rc::new(bincode::serialize(&clock).unwrap())),
+                ),
+                (
+                    sysvar::epoch_schedule::id(),
+                    Some(Rc::new(
+                        bincode::serialize(&EpochSchedule::without_warmup()).unwrap(),
+                    )),
+                ),
+            ];
+        };
+
+        // Decreases don't need the sysvars
+        let mut invoke_context = MockInvokeContext::default();
+        assert_eq!(update_commission(5, &mut invoke_context), Ok(()));
+        assert_eq!(
+            update_commission(20, &mut invoke_context),
+            Err(InstructionError::UnsupportedSysvar),
+        );
+
+        let slots_per_epoch = EpochSchedule::without_warmup().slots_per_epoch;
+        set_clock_slot(&mut invoke_context, slots_per_epoch - 1);
+        assert_eq!(
+            update_commission(20, &mut invoke_context),
+            Err(VoteError::CommissionUpdateTooLate.into()),
+        );
+        set_clock_slot(&mut invoke_context, slots_per_epoch);
+        assert_eq!(update_commission(20, &mut invoke_context), Ok(()));
+    }
+
+    #[test]
+    fn test_compact_update_vote_state_serialize() {
+        let mut local_vote_state = VoteState::default();
+        (100..=140).for_each(|slot| local_vote_state.process_slot_vote_unchecked(slot));
+        let mut vote_state_update = VoteStateUpdate::new(
+            local_vote_state.votes.clone(),
+            local_vote_state.root_slot,
+            Hash::new_unique(),
+        );
+        vote_state_update.timestamp = Some(42);
+
+        for vote_state_update in vec![
+            vote_state_update.clone(),
+            VoteStateUpdate {
+                root: None,
+                ..vote_state_update.clone()
+            },
+            VoteStateUpdate::default(),
+        ] {
+            let instruction = VoteInstruction::CompactUpdateVoteState(vote_state_update.clone());
+            let data = bincode::serialize(&instruction).unwrap();
+            assert_eq!(
+                limited_deserialize::<VoteInstruction>(&data),
+                Ok(instruction)
+            );
+        }
+
+        // A full tower costs about one byte per slot beyond the fixed fields
+        let data = bincode::serialize(&VoteInstruction::CompactUpdateVoteState(
+            vote_state_update.clone(),
+        ))
+        .unwrap();
+        let full_data = bincode::serialize(&vote_state_update).unwrap();
+        assert_eq!(data.len(), 4 + 8 + 1 + 31 * 2 + 32 + 9);
+        assert!(data.len() * 3 < full_data.len());
+
+        // Lockouts must be above the root and increasing
+        let mut bad_vote_state_update = vote_state_update;
+        bad_vote_state_update.lockouts.swap(0, 1);
+        assert!(bincode::serialize(&VoteInstruction::CompactUpdateVoteState(
+            bad_vote_state_update
+        ))
+        .is_err());
+    }
+
     #[test]
     fn test_minimum_balance() {
         let rent = solana_sdk::rent::Rent::default();
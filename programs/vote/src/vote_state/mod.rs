@@ -9,7 +9,7 @@ use solana_sdk::{
     account::{AccountSharedData, ReadableAccount, WritableAccount},
     account_utils::State,
     clock::{Epoch, Slot, UnixTimestamp},
-    epoch_schedule::MAX_LEADER_SCHEDULE_EPOCH_OFFSET,
+    epoch_schedule::{EpochSchedule, MAX_LEADER_SCHEDULE_EPOCH_OFFSET},
     hash::Hash,
     instruction::InstructionError,
     keyed_account::KeyedAccount,
@@ -65,6 +65,41 @@ impl Vote {
     }
 }
 
+#[derive(Serialize, Default, Deserialize, Debug, PartialEq, Eq, Clone, AbiExample)]
+pub struct VoteStateUpdate {
+    /// The proposed tower, starting with the oldest lockout
+    pub lockouts: VecDeque<Lockout>,
+    /// The proposed root
+    pub root: Option<Slot>,
+    /// signature of the bank's state at the last slot
+    pub hash: Hash,
+    /// processing timestamp of last slot
+    pub timestamp: Option<UnixTimestamp>,
+}
+
+impl VoteStateUpdate {
+    pub fn new(lockouts: VecDeque<Lockout>, root: Option<Slot>, hash: Hash) -> Self {
+        Self {
+            lockouts,
+            root,
+            hash,
+            timestamp: None,
+        }
+    }
+
+    pub fn slots(&self) -> Vec<Slot> {
+        self.lockouts.iter().map(|lockout| lockout.slot).collect()
+    }
+
+    pub fn last_voted_slot(&self) -> Option<Slot> {
+        self.lockouts.back().map(|lockout| lockout.slot)
+    }
+
+    pub fn last_voted_slot_hash(&self) -> Option<(Slot, Hash)> {
+        self.last_voted_slot().map(|slot| (slot, self.hash))
+    }
+}
+
 #[derive(Serialize, Default, Deserialize, Debug, PartialEq, Eq, Clone, AbiExample)]
 pub struct Lockout {
     pub slot: Slot,
@@ -358,6 +393,145 @@ impl VoteState {
         Ok(())
     }
 
+    fn check_vote_state_update_is_valid(
+        &self,
+        vote_state_update: &VoteStateUpdate,
+        slot_hashes: &[(Slot, Hash)],
+    ) -> Result<(), VoteError> {
+        let lockouts = &vote_state_update.lockouts;
+        let last_lockout = lockouts.back().ok_or(VoteError::EmptySlots)?;
+        if lockouts.len() > MAX_LOCKOUT_HISTORY {
+            return Err(VoteError::TooManyVotes);
+        }
+        if self.last_voted_slot() >= Some(last_lockout.slot) {
+            return Err(VoteError::VoteTooOld);
+        }
+
+        // The root may only move forward
+        match (self.root_slot, vote_state_update.root) {
+            (Some(current_root), Some(new_root)) if new_root < current_root => {
+                return Err(VoteError::RootRollBack);
+            }
+            (Some(_), None) => return Err(VoteError::RootRollBack),
+            _ => (),
+        }
+
+        // The proposed tower must look like one built by replaying votes:
+        // increasing slots above the root, decreasing confirmations, and no
+        // lockout that would have expired before the vote on top of it
+        let mut previous_lockout: Option<&Lockout> = None;
+        for lockout in lockouts {
+            if lockout.confirmation_count == 0 {
+                return Err(VoteError::ZeroConfirmations);
+            }
+            if lockout.confirmation_count as usize > MAX_LOCKOUT_HISTORY {
+                return Err(VoteError::ConfirmationTooLarge);
+            }
+            if vote_state_update
+                .root
+                .map_or(false, |root| lockout.slot <= root)
+            {
+                return Err(VoteError::SlotSmallerThanRoot);
+            }
+            if let Some(previous_lockout) = previous_lockout {
+                if previous_lockout.slot >= lockout.slot {
+                    return Err(VoteError::SlotsNotOrdered);
+                }
+                if previous_lockout.confirmation_count <= lockout.confirmation_count {
+                    return Err(VoteError::ConfirmationsNotOrdered);
+                }
+                if previous_lockout.is_expired(lockout.slot) {
+                    return Err(VoteError::NewVoteStateLockoutMismatch);
+                }
+            }
+            previous_lockout = Some(lockout);
+        }
+
+        // Every slot that isn't already in the on-chain tower must be in the
+        // bank's history; slots older than that history can't be checked
+        let (earliest_slot_in_history, _) = slot_hashes.last().ok_or(VoteError::VoteTooOld)?;
+        if last_lockout.slot < *earliest_slot_in_history {
+            return Err(VoteError::VoteTooOld);
+        }
+        for lockout in lockouts {
+            if lockout.slot < *earliest_slot_in_history
+                || self.votes.iter().any(|vote| vote.slot == lockout.slot)
+            {
+                continue;
+            }
+            if slot_hashes
+                .binary_search_by(|(slot, _)| lockout.slot.cmp(slot))
+                .is_err()
+            {
+                info!(
+                    "{} dropped vote state update {:?} failed to match slot: {:?}",
+                    self.node_pubkey, vote_state_update, slot_hashes,
+                );
+                inc_new_counter_info!("dropped-vote-slot", 1);
+                return Err(VoteError::SlotsMismatch);
+            }
+        }
+        let last_slot_hash = slot_hashes
+            .binary_search_by(|(slot, _)| last_lockout.slot.cmp(slot))
+            .map(|index| slot_hashes[index].1)
+            .map_err(|_| VoteError::SlotsMismatch)?;
+        if last_slot_hash != vote_state_update.hash {
+            warn!(
+                "{} dropped vote state update {:?} failed to match hash {} {}",
+                self.node_pubkey, vote_state_update, vote_state_update.hash, last_slot_hash
+            );
+            inc_new_counter_info!("dropped-vote-hash", 1);
+            return Err(VoteError::SlotHashMismatch);
+        }
+        Ok(())
+    }
+
+    /// Replace the tower with the full lockout stack proposed by the validator,
+    /// crediting every current vote that the new root finalizes
+    pub fn process_vote_state_update(
+        &mut self,
+        vote_state_update: &VoteStateUpdate,
+        slot_hashes: &[SlotHash],
+        epoch: Epoch,
+    ) -> Result<(), VoteError> {
+        self.check_vote_state_update_is_valid(vote_state_update, slot_hashes)?;
+
+        let new_root = vote_state_update.root;
+        let new_lockouts = &vote_state_update.lockouts;
+        let mut earned_credits = 0;
+        let mut new_index = 0;
+        for current_vote in &self.votes {
+            if new_root.map_or(false, |new_root| current_vote.slot <= new_root) {
+                earned_credits += 1;
+                continue;
+            }
+            while new_index < new_lockouts.len() && new_lockouts[new_index].slot < current_vote.slot
+            {
+                new_index += 1;
+            }
+            match new_lockouts.get(new_index) {
+                Some(new_vote) if new_vote.slot == current_vote.slot => {
+                    if new_vote.confirmation_count < current_vote.confirmation_count {
+                        return Err(VoteError::ConfirmationRollBack);
+                    }
+                }
+                // A current vote may only be dropped once its lockout has
+                // expired by the next proposed vote
+                Some(new_vote) if !current_vote.is_expired(new_vote.slot) => {
+                    return Err(VoteError::LockoutConflict);
+                }
+                _ => (),
+            }
+        }
+
+        for _ in 0..earned_credits {
+            self.increment_credits(epoch);
+        }
+        self.votes = new_lockouts.clone();
+        self.root_slot = new_root;
+        Ok(())
+    }
+
     pub fn process_slot(&mut self, slot: Slot, epoch: Epoch) {
         // Ignore votes for slots earlier than we already have votes for
         if self
@@ -637,23 +811,41 @@ pub fn update_validator_identity<S: std::hash::BuildHasher>(
     vote_account.set_state(&VoteStateVersions::new_current(vote_state))
 }
 
-/// Update the vote account's commission
-pub fn update_commission<S: std::hash::BuildHasher>(
+/// Update the vote account's commission; increases are rejected unless
+/// `commission_increase_allowed` returns true, so they can't be timed right
+/// before rewards.  It is only called for increases.
+pub fn update_commission<S, F>(
     vote_account: &KeyedAccount,
     commission: u8,
     signers: &HashSet<Pubkey, S>,
-) -> Result<(), InstructionError> {
+    commission_increase_allowed: F,
+) -> Result<(), InstructionError>
+where
+    S: std::hash::BuildHasher,
+    F: FnOnce() -> Result<bool, InstructionError>,
+{
     let mut vote_state: VoteState =
         State::<VoteStateVersions>::state(vote_account)?.convert_to_current();
 
     // current authorized withdrawer must say "yay"
     verify_authorized_signer(&vote_state.authorized_withdrawer, signers)?;
 
+    if commission > vote_state.commission && !commission_increase_allowed()? {
+        return Err(VoteError::CommissionUpdateTooLate.into());
+    }
+
     vote_state.commission = commission;
 
     vote_account.set_state(&VoteStateVersions::new_current(vote_state))
 }
 
+/// Commission increases are only allowed up to the midpoint of an epoch, so
+/// delegators have the rest of the epoch to react before rewards are paid
+pub fn is_commission_increase_allowed(slot: Slot, epoch_schedule: &EpochSchedule) -> bool {
+    let (epoch, slot_index) = epoch_schedule.get_epoch_and_slot_index(slot);
+    slot_index.saturating_mul(2) <= epoch_schedule.get_slots_in_epoch(epoch)
+}
+
 fn verify_authorized_signer<S: std::hash::BuildHasher>(
     authorized: &Pubkey,
     signers: &HashSet<Pubkey, S>,
@@ -745,6 +937,33 @@ pub fn process_vote<S: std::hash::BuildHasher>(
     vote_account.set_state(&VoteStateVersions::new_current(vote_state))
 }
 
+pub fn process_vote_state_update<S: std::hash::BuildHasher>(
+    vote_account: &KeyedAccount,
+    slot_hashes: &[SlotHash],
+    clock: &Clock,
+    vote_state_update: &VoteStateUpdate,
+    signers: &HashSet<Pubkey, S>,
+) -> Result<(), InstructionError> {
+    let versioned = State::<VoteStateVersions>::state(vote_account)?;
+
+    if versioned.is_uninitialized() {
+        return Err(InstructionError::UninitializedAccount);
+    }
+
+    let mut vote_state = versioned.convert_to_current();
+    let authorized_voter = vote_state.get_and_update_authorized_voter(clock.epoch)?;
+    verify_authorized_signer(&authorized_voter, signers)?;
+
+    vote_state.process_vote_state_update(vote_state_update, slot_hashes, clock.epoch)?;
+    if let Some(timestamp) = vote_state_update.timestamp {
+        vote_state_update
+            .last_voted_slot()
+            .ok_or(VoteError::EmptySlots)
+            .and_then(|slot| vote_state.process_timestamp(slot, timestamp))?;
+    }
+    vote_account.set_state(&VoteStateVersions::new_current(vote_state))
+}
+
 pub fn create_account_with_authorized(
     node_pubkey: &Pubkey,
     authorized_voter: &Pubkey,
@@ -780,6 +999,170 @@ pub fn create_account(
     create_account_with_authorized(node_pubkey, vote_pubkey, vote_pubkey, commission, lamports)
 }
 
+/// Compact wire encoding of a `VoteStateUpdate`, for use with `#[serde(with = ...)]`.
+///
+/// The root is sent once (`Slot::MAX` standing in for no root), and each
+/// lockout is sent as a varint offset from the slot before it plus a one byte
+/// confirmation count, so a full tower costs about as much as a few slots.
+pub mod serde_compact_vote_state_update {
+    use super::*;
+    use serde::{
+        de::{self, Deserializer, SeqAccess, Visitor},
+        ser::{SerializeTuple, Serializer},
+        Deserialize, Serialize,
+    };
+    use solana_sdk::short_vec;
+    use std::{convert::TryFrom, fmt};
+
+    // A u64 takes at most ten 7-bit groups
+    const MAX_VAR_U64_LEN: usize = 10;
+
+    /// A u64 serialized as little endian 7-bit groups, with the top bit of
+    /// each byte set when more bytes follow
+    struct VarU64(u64);
+
+    impl Serialize for VarU64 {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut seq = serializer.serialize_tuple(1)?;
+            let mut rem_val = self.0;
+            loop {
+                let elem = (rem_val & 0x7f) as u8;
+                rem_val >>= 7;
+                if rem_val == 0 {
+                    seq.serialize_element(&elem)?;
+                    break;
+                }
+                seq.serialize_element(&(elem | 0x80))?;
+            }
+            seq.end()
+        }
+    }
+
+    struct VarU64Visitor;
+
+    impl<'de> Visitor<'de> for VarU64Visitor {
+        type Value = VarU64;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a varint encoded u64")
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<VarU64, A::Error> {
+            let mut val = 0u64;
+            for nth_byte in 0..MAX_VAR_U64_LEN {
+                let elem: u8 = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(nth_byte, &"more bytes"))?;
+                if elem == 0 && nth_byte != 0 {
+                    return Err(de::Error::invalid_value(
+                        de::Unexpected::Other("alias encoding"),
+                        &"strict form encoding",
+                    ));
+                }
+                let shift = nth_byte as u32 * 7;
+                let elem_val = u64::from(elem & 0x7f);
+                if elem_val.leading_zeros() < shift {
+                    return Err(de::Error::invalid_value(
+                        de::Unexpected::Other("overflow"),
+                        &"a value in the range of u64",
+                    ));
+                }
+                val |= elem_val << shift;
+                if elem & 0x80 == 0 {
+                    return Ok(VarU64(val));
+                }
+            }
+            Err(de::Error::invalid_length(
+                MAX_VAR_U64_LEN + 1,
+                &"ten or fewer bytes",
+            ))
+        }
+    }
+
+    impl<'de> Deserialize<'de> for VarU64 {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<VarU64, D::Error> {
+            deserializer.deserialize_tuple(MAX_VAR_U64_LEN, VarU64Visitor)
+        }
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct LockoutOffset {
+        offset: VarU64,
+        confirmation_count: u8,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct CompactVoteStateUpdate {
+        root: Slot,
+        #[serde(with = "short_vec")]
+        lockout_offsets: Vec<LockoutOffset>,
+        hash: Hash,
+        timestamp: Option<UnixTimestamp>,
+    }
+
+    pub fn serialize<S: Serializer>(
+        vote_state_update: &VoteStateUpdate,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut prev_slot = vote_state_update.root.unwrap_or(0);
+        let lockout_offsets = vote_state_update
+            .lockouts
+            .iter()
+            .map(|lockout| {
+                let offset = lockout.slot.checked_sub(prev_slot);
+                let confirmation_count = u8::try_from(lockout.confirmation_count);
+                prev_slot = lockout.slot;
+                match (offset, confirmation_count) {
+                    (Some(offset), Ok(confirmation_count)) => Ok(LockoutOffset {
+                        offset: VarU64(offset),
+                        confirmation_count,
+                    }),
+                    _ => Err(serde::ser::Error::custom("invalid vote state update")),
+                }
+            })
+            .collect::<Result<_, _>>()?;
+        CompactVoteStateUpdate {
+            root: vote_state_update.root.unwrap_or(Slot::MAX),
+            lockout_offsets,
+            hash: vote_state_update.hash,
+            timestamp: vote_state_update.timestamp,
+        }
+        .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<VoteStateUpdate, D::Error> {
+        let CompactVoteStateUpdate {
+            root,
+            lockout_offsets,
+            hash,
+            timestamp,
+        } = CompactVoteStateUpdate::deserialize(deserializer)?;
+        let root = if root == Slot::MAX { None } else { Some(root) };
+        let mut prev_slot = root.unwrap_or(0);
+        let lockouts = lockout_offsets
+            .into_iter()
+            .map(|lockout_offset| {
+                let slot = prev_slot
+                    .checked_add(lockout_offset.offset.0)
+                    .ok_or_else(|| serde::de::Error::custom("invalid lockout offset"))?;
+                prev_slot = slot;
+                Ok(Lockout {
+                    slot,
+                    confirmation_count: u32::from(lockout_offset.confirmation_count),
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(VoteStateUpdate {
+            lockouts,
+            root,
+            hash,
+            timestamp,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1099,7 +1482,7 @@ mod tests {
             ),
         ];
         let signers: HashSet<Pubkey> = get_signers(keyed_accounts);
-        let res = update_commission(&keyed_accounts[0], 42, &signers);
+        let res = update_commission(&keyed_accounts[0], 42, &signers, || Ok(true));
         assert_eq!(res, Err(InstructionError::MissingRequiredSignature));
 
         let keyed_accounts = &[
@@ -1107,7 +1490,7 @@ mod tests {
             KeyedAccount::new(&authorized_withdrawer, true, &authorized_withdrawer_account),
         ];
         let signers: HashSet<Pubkey> = get_signers(keyed_accounts);
-        let res = update_commission(&keyed_accounts[0], 42, &signers);
+        let res = update_commission(&keyed_accounts[0], 42, &signers, || Ok(true));
         assert_eq!(res, Ok(()));
         let vote_state: VoteState = StateMut::<VoteStateVersions>::state(&*vote_account.borrow())
             .unwrap()
@@ -1119,12 +1502,59 @@ mod tests {
             KeyedAccount::new(&authorized_withdrawer, true, &authorized_withdrawer_account),
         ];
         let signers: HashSet<Pubkey> = get_signers(keyed_accounts);
-        let res = update_commission(&keyed_accounts[0], u8::MAX, &signers);
+        let res = update_commission(&keyed_accounts[0], u8::MAX, &signers, || Ok(true));
         assert_eq!(res, Ok(()));
         let vote_state: VoteState = StateMut::<VoteStateVersions>::state(&*vote_account.borrow())
             .unwrap()
             .convert_to_current();
         assert_eq!(vote_state.commission, u8::MAX);
+
+        // Outside of the first half of the epoch, commission may only go down
+        assert_eq!(
+            update_commission(&keyed_accounts[0], 42, &signers, || Ok(false)),
+            Ok(())
+        );
+        assert_eq!(
+            update_commission(&keyed_accounts[0], 42, &signers, || Ok(false)),
+            Ok(())
+        );
+        assert_eq!(
+            update_commission(&keyed_accounts[0], 43, &signers, || Ok(false)),
+            Err(VoteError::CommissionUpdateTooLate.into())
+        );
+        let vote_state: VoteState = StateMut::<VoteStateVersions>::state(&*vote_account.borrow())
+            .unwrap()
+            .convert_to_current();
+        assert_eq!(vote_state.commission, 42);
+    }
+
+    #[test]
+    fn test_is_commission_increase_allowed() {
+        let epoch_schedule = EpochSchedule::without_warmup();
+        let slots_per_epoch = epoch_schedule.slots_per_epoch;
+        for (slot, expected_allowed) in &[
+            (0, true),
+            (slots_per_epoch / 2, true),
+            (slots_per_epoch / 2 + 1, false),
+            (slots_per_epoch - 1, false),
+            (slots_per_epoch, true),
+            (slots_per_epoch + slots_per_epoch / 2 + 1, false),
+        ] {
+            assert_eq!(
+                is_commission_increase_allowed(*slot, &epoch_schedule),
+                *expected_allowed,
+                "slot {}",
+                slot
+            );
+        }
+
+        // Warmup epochs are shorter, but the rule still splits them in half
+        let epoch_schedule = EpochSchedule::custom(64, 64, true);
+        assert_eq!(epoch_schedule.get_slots_in_epoch(0), 32);
+        assert!(is_commission_increase_allowed(16, &epoch_schedule));
+        assert!(!is_commission_increase_allowed(17, &epoch_schedule));
+        assert!(is_commission_increase_allowed(32 + 32, &epoch_schedule));
+        assert!(!is_commission_increase_allowed(32 + 33, &epoch_schedule));
     }
 
     #[test]
@@ -1614,6 +2044,150 @@ mod tests {
         );
     }
 
+    fn lockouts_from(slots_and_confirmations: &[(Slot, u32)]) -> VecDeque<Lockout> {
+        slots_and_confirmations
+            .iter()
+            .map(|(slot, confirmation_count)| Lockout {
+                slot: *slot,
+                confirmation_count: *confirmation_count,
+            })
+            .collect()
+    }
+
+    fn slot_hashes_through(max_slot: Slot) -> Vec<(Slot, Hash)> {
+        (0..=max_slot)
+            .rev()
+            .map(|slot| (slot, Hash::default()))
+            .collect()
+    }
+
+    #[test]
+    fn test_process_vote_state_update_resyncs_tower() {
+        // The validator voted on every slot, but only its first few votes landed
+        let mut local_vote_state = VoteState::default();
+        (1..=40).for_each(|slot| local_vote_state.process_slot_vote_unchecked(slot));
+        let mut vote_state = VoteState::default();
+        (1..=5).for_each(|slot| vote_state.process_slot_vote_unchecked(slot));
+        assert_eq!(vote_state.credits(), 0);
+
+        let vote_state_update = VoteStateUpdate::new(
+            local_vote_state.votes.clone(),
+            local_vote_state.root_slot,
+            Hash::default(),
+        );
+        assert_eq!(
+            vote_state.process_vote_state_update(&vote_state_update, &slot_hashes_through(40), 0),
+            Ok(())
+        );
+        assert_eq!(vote_state.votes, local_vote_state.votes);
+        assert_eq!(vote_state.root_slot, Some(9));
+        // Only the votes that landed are credited once rooted
+        assert_eq!(vote_state.credits(), 5);
+
+        // Resubmitting the same tower is rejected like a duplicate vote
+        assert_eq!(
+            vote_state.process_vote_state_update(&vote_state_update, &slot_hashes_through(40), 0),
+            Err(VoteError::VoteTooOld)
+        );
+
+        // Further votes continue from the synced tower
+        local_vote_state.process_slot_vote_unchecked(41);
+        let vote_state_update = VoteStateUpdate::new(
+            local_vote_state.votes.clone(),
+            local_vote_state.root_slot,
+            Hash::default(),
+        );
+        assert_eq!(
+            vote_state.process_vote_state_update(&vote_state_update, &slot_hashes_through(41), 0),
+            Ok(())
+        );
+        assert_eq!(vote_state.root_slot, Some(10));
+        assert_eq!(vote_state.credits(), 6);
+    }
+
+    #[test]
+    fn test_process_vote_state_update_invalid() {
+        let mut vote_state = VoteState::default();
+        (1..=3).for_each(|slot| vote_state.process_slot_vote_unchecked(slot));
+        vote_state.root_slot = Some(0);
+        let slot_hashes = slot_hashes_through(10);
+
+        for (lockouts, root, expected_err) in vec![
+            (vec![], Some(0), VoteError::EmptySlots),
+            (vec![(3, 1)], Some(0), VoteError::VoteTooOld),
+            (vec![(5, 1), (4, 1)], Some(0), VoteError::SlotsNotOrdered),
+            (
+                vec![(4, 1), (5, 1)],
+                Some(0),
+                VoteError::ConfirmationsNotOrdered,
+            ),
+            (vec![(4, 2), (5, 0)], Some(0), VoteError::ZeroConfirmations),
+            (
+                vec![(4, 32), (5, 1)],
+                Some(0),
+                VoteError::ConfirmationTooLarge,
+            ),
+            (
+                vec![(4, 2), (5, 1)],
+                Some(4),
+                VoteError::SlotSmallerThanRoot,
+            ),
+            (vec![(4, 1)], None, VoteError::RootRollBack),
+            (
+                vec![(4, 2), (10, 1)],
+                Some(0),
+                VoteError::NewVoteStateLockoutMismatch,
+            ),
+            (
+                vec![(1, 2), (4, 1)],
+                Some(0),
+                VoteError::ConfirmationRollBack,
+            ),
+            (vec![(4, 1)], Some(0), VoteError::LockoutConflict),
+        ] {
+            let vote_state_update =
+                VoteStateUpdate::new(lockouts_from(&lockouts), root, Hash::default());
+            assert_eq!(
+                vote_state
+                    .clone()
+                    .process_vote_state_update(&vote_state_update, &slot_hashes, 0),
+                Err(expected_err),
+                "{:?}",
+                vote_state_update
+            );
+        }
+
+        let mut vote_state_update = VoteStateUpdate::new(
+            lockouts_from(&[(1, 4), (2, 3), (3, 2), (4, 1)]),
+            Some(0),
+            Hash::default(),
+        );
+        let missing_slot_hashes: Vec<_> = slot_hashes_through(10)
+            .into_iter()
+            .filter(|(slot, _)| *slot != 4)
+            .collect();
+        assert_eq!(
+            vote_state.clone().process_vote_state_update(
+                &vote_state_update,
+                &missing_slot_hashes,
+                0
+            ),
+            Err(VoteError::SlotsMismatch)
+        );
+        vote_state_update.hash = hash(&[42]);
+        assert_eq!(
+            vote_state
+                .clone()
+                .process_vote_state_update(&vote_state_update, &slot_hashes, 0),
+            Err(VoteError::SlotHashMismatch)
+        );
+        vote_state_update.hash = Hash::default();
+        assert_eq!(
+            vote_state.process_vote_state_update(&vote_state_update, &slot_hashes, 0),
+            Ok(())
+        );
+    }
+
     #[test]
     fn test_vote_state_commission_split() {
         let vote_state = VoteState::default();
@@ -9,7 +9,7 @@ use solana_sdk::{
 
 use crate::{
     vote_instruction::{self, VoteInstruction},
-    vote_state::Vote,
+    vote_state::{Vote, VoteStateUpdate},
 };
 
 pub fn parse_vote_transaction(tx: &Transaction) -> Option<(Pubkey, Vote, Option<Hash>)> {
@@ -41,6 +41,13 @@ pub fn parse_vote_transaction(tx: &Transaction) -> Option<(Pubkey, Vote, Option<
                             VoteInstruction::VoteSwitch(vote, hash) => {
                                 Some((*key, vote, Some(hash)))
                             }
+                            VoteInstruction::CompactUpdateVoteState(vote_state_update) => {
+                                Some((*key, vote_from_update(vote_state_update), None))
+                            }
+                            VoteInstruction::CompactUpdateVoteStateSwitch(
+                                vote_state_update,
+                                hash,
+                            ) => Some((*key, vote_from_update(vote_state_update), Some(hash))),
                             _ => None,
                         })
                     })
@@ -48,6 +55,16 @@ pub fn parse_vote_transaction(tx: &Transaction) -> Option<(Pubkey, Vote, Option<
     })
 }
 
+// Consumers of vote transactions only track the voted slots, so present a
+// vote state update as a vote for every slot in its tower
+fn vote_from_update(vote_state_update: VoteStateUpdate) -> Vote {
+    Vote {
+        slots: vote_state_update.slots(),
+        hash: vote_state_update.hash,
+        timestamp: vote_state_update.timestamp,
+    }
+}
+
 pub fn new_vote_transaction(
     slots: Vec<Slot>,
     bank_hash: Hash,
@@ -83,6 +100,7 @@ pub fn new_vote_transaction(
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::vote_state::VoteState;
     use solana_sdk::hash::hash;
 
     fn run_test_parse_vote_transaction(input_hash: Option<Hash>) {
@@ -115,6 +133,27 @@ mod test {
         assert!(parse_vote_transaction(&vote_tx).is_none());
     }
 
+    #[test]
+    fn test_parse_compact_update_vote_state_transaction() {
+        let node_keypair = Keypair::new();
+        let vote_keypair = Keypair::new();
+        let auth_voter_keypair = Keypair::new();
+        let mut vote_state = VoteState::default();
+        (1..=3).for_each(|slot| vote_state.process_slot_vote_unchecked(slot));
+        let vote_state_update = VoteStateUpdate::new(vote_state.votes, Some(0), hash(&[42u8]));
+        let vote_ix = vote_instruction::compact_update_vote_state_switch(
+            &vote_keypair.pubkey(),
+            &auth_voter_keypair.pubkey(),
+            vote_state_update,
+            Hash::default(),
+        );
+        let vote_tx = Transaction::new_with_payer(&[vote_ix], Some(&node_keypair.pubkey()));
+        let (key, vote, switch_proof_hash) = parse_vote_transaction(&vote_tx).unwrap();
+        assert_eq!(key, vote_keypair.pubkey());
+        assert_eq!(vote, Vote::new(vec![1, 2, 3], hash(&[42u8])));
+        assert_eq!(switch_proof_hash, Some(Hash::default()));
+    }
+
     #[test]
     fn test_parse_vote_transaction() {
         run_test_parse_vote_transaction(None);
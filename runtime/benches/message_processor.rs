@@ -26,6 +26,7 @@ fn bench_verify_account_changes_data(bencher: &mut Bencher) {
             &post,
             &mut ExecuteDetailsTimings::default(),
             false,
+            false,
         ),
         Ok(())
     );
@@ -39,6 +40,7 @@ fn bench_verify_account_changes_data(bencher: &mut Bencher) {
             &post,
             &mut ExecuteDetailsTimings::default(),
             false,
+            false,
         )
         .unwrap();
     });
@@ -63,6 +65,7 @@ fn bench_verify_account_changes_data(bencher: &mut Bencher) {
             &post,
             &mut ExecuteDetailsTimings::default(),
             false,
+            false,
         )
         .unwrap();
     });
@@ -399,6 +399,8 @@ impl Accounts {
         let fee_config = FeeConfig {
             secp256k1_program_enabled: feature_set
                 .is_active(&feature_set::secp256k1_program_enabled::id()),
+            ed25519_program_enabled: feature_set
+                .is_active(&feature_set::ed25519_program_enabled::id()),
         };
         txs.zip(lock_results)
             .map(|etx| match etx {
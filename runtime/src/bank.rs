@@ -17,6 +17,7 @@ use crate::{
     instruction_recorder::InstructionRecorder,
     log_collector::LogCollector,
     message_processor::{ExecuteDetailsTimings, Executors, MessageProcessor},
+    program_trace::{ProgramTraceCollector, ProgramTraceRecorder, TransactionProgramTraces},
     rent_collector::RentCollector,
     stakes::Stakes,
     status_cache::{SlotDelta, StatusCache},
@@ -35,6 +36,7 @@ use solana_sdk::{
         create_account_shared_data_with_fields as create_account, from_account, Account,
         AccountSharedData, InheritableAccountFields, ReadableAccount,
     },
+    account_utils::StateMut,
     clock::{
         Epoch, Slot, SlotCount, SlotIndex, UnixTimestamp, DEFAULT_TICKS_PER_SECOND,
         INITIAL_RENT_EPOCH, MAX_PROCESSING_AGE, MAX_RECENT_BLOCKHASHES,
@@ -55,7 +57,9 @@ use solana_sdk::{
     native_loader,
     native_token::sol_to_lamports,
     nonce, nonce_account,
-    process_instruction::{BpfComputeBudget, Executor, ProcessInstructionWithContext},
+    process_instruction::{
+        BpfComputeBudget, Executor, ProcessInstructionWithContext, TransactionReturnData,
+    },
     program_utils::limited_deserialize,
     pubkey::Pubkey,
     recent_blockhashes_account,
@@ -73,7 +77,7 @@ use solana_sdk::{
     transaction::{self, Result, Transaction, TransactionError},
 };
 use solana_stake_program::stake_state::{
-    self, Delegation, InflationPointCalculationEvent, PointValue,
+    self, Delegation, InflationPointCalculationEvent, PointValue, Stake, StakeState,
 };
 use solana_vote_program::vote_instruction::VoteInstruction;
 use std::{
@@ -224,6 +228,10 @@ pub struct Builtins {
 
 const MAX_CACHED_EXECUTORS: usize = 100; // 10 MB assuming programs are around 100k
 
+/// Number of blocks across which stake rewards are credited once the
+/// `partitioned_epoch_rewards` feature is active
+pub const NUM_EPOCH_REWARD_PARTITIONS: u64 = 16;
+
 /// LFU Cache of executors
 #[derive(Debug)]
 struct CachedExecutors {
@@ -410,6 +418,9 @@ pub type InnerInstructionsList = Vec<InnerInstructions>;
 /// A list of log messages emitted during a transaction
 pub type TransactionLogMessages = Vec<String>;
 
+/// The compute units consumed by each top-level instruction of a transaction
+pub type InstructionComputeUnits = Vec<u64>;
+
 #[derive(Serialize, Deserialize, AbiExample, AbiEnumVisitor, Debug, PartialEq)]
 pub enum TransactionLogCollectorFilter {
     All,
@@ -670,6 +681,7 @@ impl PartialEq for Bank {
             && *self.stakes.read().unwrap() == *other.stakes.read().unwrap()
             && self.epoch_stakes == other.epoch_stakes
             && self.is_delta.load(Relaxed) == other.is_delta.load(Relaxed)
+            && self.epoch_reward_status == other.epoch_reward_status
     }
 }
 
@@ -717,6 +729,46 @@ pub struct RewardInfo {
     pub post_balance: u64, // Account balance in lamports after `lamports` was applied
 }
 
+/// A stake reward that has been calculated at the start of an epoch but not
+/// yet credited to its account
+#[derive(Debug, PartialEq, Serialize, Deserialize, AbiExample, Clone)]
+pub struct StakeReward {
+    pub stake_pubkey: Pubkey,
+    /// The delegation as it will be stored once the reward is credited
+    pub stake: Stake,
+    /// Lamports to add to the stake account
+    pub reward: u64,
+}
+
+/// Progress of the partitioned epoch reward distribution
+#[derive(Debug, PartialEq, Serialize, Deserialize, AbiExample, AbiEnumVisitor, Clone)]
+pub enum EpochRewardStatus {
+    /// Stake rewards are being credited, one partition per block starting at
+    /// `distribution_starting_block_height`
+    Active {
+        distribution_starting_block_height: u64,
+        stake_rewards_by_partition: Arc<Vec<Vec<StakeReward>>>,
+    },
+    Inactive,
+}
+
+impl Default for EpochRewardStatus {
+    fn default() -> Self {
+        Self::Inactive
+    }
+}
+
+/// Partition a stake account's reward is credited in, derived from the
+/// parent blockhash of the rewards calculation block and the account address
+pub fn epoch_reward_partition_index(
+    parent_blockhash: &Hash,
+    stake_pubkey: &Pubkey,
+    num_partitions: u64,
+) -> usize {
+    let hash = hashv(&[parent_blockhash.as_ref(), stake_pubkey.as_ref()]);
+    (LittleEndian::read_u64(&hash.as_ref()[..8]) % num_partitions) as usize
+}
+
 #[derive(Debug, Default)]
 pub struct OptionalDropCallback(Option<Box<dyn DropCallback + Send + Sync>>);
 
@@ -879,11 +931,18 @@ pub struct Bank {
     // `transaction_log_collector_config`
     pub transaction_log_collector: Arc<RwLock<TransactionLogCollector>>,
 
+    // BPF program traces of the transactions selected by its filter, shared by all banks
+    // descended from the one it was set on
+    pub program_trace_collector: Arc<RwLock<ProgramTraceCollector>>,
+
     pub feature_set: Arc<FeatureSet>,
 
     pub drop_callback: RwLock<OptionalDropCallback>,
 
     pub freeze_started: AtomicBool,
+
+    /// Stake rewards of the current epoch that are yet to be credited
+    pub(crate) epoch_reward_status: EpochRewardStatus,
 }
 
 impl Default for BlockhashQueue {
@@ -1078,6 +1137,7 @@ impl Bank {
             transaction_debug_keys: parent.transaction_debug_keys.clone(),
             transaction_log_collector_config: parent.transaction_log_collector_config.clone(),
             transaction_log_collector: Arc::new(RwLock::new(TransactionLogCollector::default())),
+            program_trace_collector: parent.program_trace_collector.clone(),
             feature_set: parent.feature_set.clone(),
             drop_callback: RwLock::new(OptionalDropCallback(
                 parent
@@ -1089,6 +1149,7 @@ impl Bank {
                     .map(|drop_callback| drop_callback.clone_box()),
             )),
             freeze_started: AtomicBool::new(false),
+            epoch_reward_status: parent.epoch_reward_status.clone(),
         };
 
         datapoint_info!(
@@ -1105,6 +1166,8 @@ impl Bank {
         // Following code may touch AccountsDb, requiring proper ancestors
         let parent_epoch = parent.epoch();
         if parent_epoch < new.epoch() {
+            // an unfinished distribution must be credited before the next one is calculated
+            new.credit_remaining_epoch_rewards(new.block_height);
             new.apply_feature_activations(false);
         }
 
@@ -1119,6 +1182,7 @@ impl Bank {
         new.update_epoch_stakes(leader_schedule_epoch);
         new.update_slot_hashes();
         new.update_rewards(parent_epoch, reward_calc_tracer);
+        new.distribute_partitioned_epoch_rewards();
         new.update_stake_history(Some(parent_epoch));
         new.update_clock(Some(parent_epoch));
         new.update_fees();
@@ -1149,6 +1213,8 @@ impl Bank {
     pub fn warp_from_parent(parent: &Arc<Bank>, collector_id: &Pubkey, slot: Slot) -> Self {
         let parent_timestamp = parent.clock().unix_timestamp;
         let mut new = Bank::new_from_parent(parent, collector_id, slot);
+        // the skipped blocks would have credited the rest of the epoch rewards
+        new.credit_remaining_epoch_rewards(new.block_height + 1);
         new.apply_feature_activations(true);
         new.update_epoch_stakes(new.epoch_schedule().get_epoch(slot));
         new.tick_height.store(new.max_tick_height(), Relaxed);
@@ -1233,9 +1299,11 @@ impl Bank {
             transaction_debug_keys: debug_keys,
             transaction_log_collector_config: new(),
             transaction_log_collector: new(),
+            program_trace_collector: new(),
             feature_set: new(),
             drop_callback: RwLock::new(OptionalDropCallback(None)),
             freeze_started: AtomicBool::new(fields.hash != Hash::default()),
+            epoch_reward_status: new(),
         };
         bank.finish_init(genesis_config, additional_builtins);
 
@@ -1660,6 +1728,9 @@ impl Bank {
             return;
         }
         // if I'm the first Bank in an epoch, count, claim, disburse rewards from Inflation
+        let partitioned_epoch_rewards = self
+            .feature_set
+            .is_active(&feature_set::partitioned_epoch_rewards::id());
 
         let slot_in_year = self.slot_in_year_for_inflation();
         let epoch_duration_in_years = self.epoch_duration_in_years(prev_epoch);
@@ -1677,12 +1748,15 @@ impl Bank {
             (validator_rate * capitalization as f64 * epoch_duration_in_years) as u64;
 
         let old_vote_balance_and_staked = self.stakes.read().unwrap().vote_balance_and_staked();
+        // rewards of a previous distribution may already have been credited by this bank
+        let num_prior_rewards = self.rewards.read().unwrap().len();
 
-        let validator_point_value = self.pay_validator_rewards(
+        let (validator_point_value, stake_rewards) = self.pay_validator_rewards(
             prev_epoch,
             validator_rewards,
             reward_calc_tracer,
             self.stake_program_v2_enabled(),
+            partitioned_epoch_rewards,
         );
 
         if !self
@@ -1707,6 +1781,7 @@ impl Bank {
                     .read()
                     .unwrap()
                     .iter()
+                    .skip(num_prior_rewards)
                     .map(|(_address, reward_info)| {
                         match reward_info.reward_type {
                             RewardType::Voting | RewardType::Staking => reward_info.lamports,
@@ -1718,16 +1793,20 @@ impl Bank {
             .unwrap()
         );
 
+        // stake rewards of a partitioned distribution are paid in the following blocks
+        let stake_rewards_to_distribute: u64 = stake_rewards.iter().map(|r| r.reward).sum();
+
         // verify that we didn't pay any more than we expected to
-        assert!(validator_rewards >= validator_rewards_paid);
+        assert!(validator_rewards >= validator_rewards_paid + stake_rewards_to_distribute);
 
         info!(
-            "distributed inflation: {} (rounded from: {})",
-            validator_rewards_paid, validator_rewards
+            "distributed inflation: {} (rounded from: {}), to be distributed: {}",
+            validator_rewards_paid, validator_rewards, stake_rewards_to_distribute
         );
 
         self.capitalization
             .fetch_add(validator_rewards_paid, Relaxed);
+        self.begin_partitioned_epoch_rewards(stake_rewards, stake_rewards_to_distribute);
 
         let active_stake = if let Some(stake_history_entry) =
             self.stakes.read().unwrap().history().get(&prev_epoch)
@@ -1815,13 +1894,17 @@ impl Bank {
 
     /// iterate over all stakes, redeem vote credits for each stake we can
     ///   successfully load and parse, return the lamport value of one point
+    ///
+    /// if `partitioned` is set, stake accounts are left untouched and their
+    ///   rewards are returned to be credited over the following blocks
     fn pay_validator_rewards(
         &mut self,
         rewarded_epoch: Epoch,
         rewards: u64,
         reward_calc_tracer: &mut Option<impl FnMut(&RewardCalculationEvent)>,
         fix_stake_deactivate: bool,
-    ) -> f64 {
+        partitioned: bool,
+    ) -> (f64, Vec<StakeReward>) {
         let stake_history = self.stakes.read().unwrap().history().clone();
 
         let mut stake_delegation_accounts = self.stake_delegation_accounts(reward_calc_tracer);
@@ -1845,12 +1928,13 @@ impl Bank {
             .sum();
 
         if points == 0 {
-            return 0.0;
+            return (0.0, vec![]);
         }
 
         let point_value = PointValue { rewards, points };
 
         let mut rewards = vec![];
+        let mut stake_rewards = vec![];
         // pay according to point value
         for (vote_pubkey, (stake_group, vote_account)) in stake_delegation_accounts.iter_mut() {
             let mut vote_account_changed = false;
@@ -1875,9 +1959,20 @@ impl Bank {
                     fix_stake_deactivate,
                 );
                 if let Ok((stakers_reward, _voters_reward)) = redeemed {
-                    self.store_account(&stake_pubkey, &stake_account);
                     vote_account_changed = true;
 
+                    if partitioned {
+                        if let Ok(StakeState::Stake(_meta, stake)) = stake_account.state() {
+                            stake_rewards.push(StakeReward {
+                                stake_pubkey: *stake_pubkey,
+                                stake,
+                                reward: stakers_reward,
+                            });
+                        }
+                        continue;
+                    }
+                    self.store_account(&stake_pubkey, &stake_account);
+
                     if stakers_reward > 0 {
                         rewards.push((
                             *stake_pubkey,
@@ -1914,7 +2009,190 @@ impl Bank {
         }
         self.rewards.write().unwrap().append(&mut rewards);
 
-        point_value.rewards as f64 / point_value.points as f64
+        (
+            point_value.rewards as f64 / point_value.points as f64,
+            stake_rewards,
+        )
+    }
+
+    /// partition the calculated stake rewards and lock stake accounts until
+    ///   all partitions are credited, starting with the next block
+    fn begin_partitioned_epoch_rewards(
+        &mut self,
+        stake_rewards: Vec<StakeReward>,
+        total_rewards: u64,
+    ) {
+        if stake_rewards.is_empty() {
+            return;
+        }
+        let num_partitions = NUM_EPOCH_REWARD_PARTITIONS;
+        let parent_blockhash = self.last_blockhash();
+        let mut stake_rewards_by_partition = vec![vec![]; num_partitions as usize];
+        for stake_reward in stake_rewards {
+            let partition_index = epoch_reward_partition_index(
+                &parent_blockhash,
+                &stake_reward.stake_pubkey,
+                num_partitions,
+            );
+            stake_rewards_by_partition[partition_index].push(stake_reward);
+        }
+
+        let distribution_starting_block_height = self.block_height + 1;
+        self.epoch_reward_status = EpochRewardStatus::Active {
+            distribution_starting_block_height,
+            stake_rewards_by_partition: Arc::new(stake_rewards_by_partition),
+        };
+        self.update_epoch_rewards_sysvar(&sysvar::epoch_rewards::EpochRewards {
+            distribution_starting_block_height,
+            num_partitions,
+            parent_blockhash,
+            total_rewards,
+            distributed_rewards: 0,
+            active: true,
+        });
+    }
+
+    /// credit the partition of stake rewards scheduled for this block, if any
+    fn distribute_partitioned_epoch_rewards(&mut self) {
+        let (distribution_starting_block_height, stake_rewards_by_partition) =
+            match &self.epoch_reward_status {
+                EpochRewardStatus::Active {
+                    distribution_starting_block_height,
+                    stake_rewards_by_partition,
+                } => (
+                    *distribution_starting_block_height,
+                    stake_rewards_by_partition.clone(),
+                ),
+                EpochRewardStatus::Inactive => return,
+            };
+        if self.block_height < distribution_starting_block_height {
+            return;
+        }
+        let partition_index = (self.block_height - distribution_starting_block_height) as usize;
+        if let Some(stake_rewards) = stake_rewards_by_partition.get(partition_index) {
+            self.credit_stake_rewards(stake_rewards);
+        }
+        if partition_index + 1 >= stake_rewards_by_partition.len() {
+            self.end_partitioned_epoch_rewards();
+        }
+    }
+
+    /// credit every partition of stake rewards scheduled at or after
+    ///   `block_height`, completing the distribution
+    fn credit_remaining_epoch_rewards(&mut self, block_height: u64) {
+        let stake_rewards_to_credit = match &self.epoch_reward_status {
+            EpochRewardStatus::Active {
+                distribution_starting_block_height,
+                stake_rewards_by_partition,
+            } => {
+                let first_partition_index =
+                    block_height.saturating_sub(*distribution_starting_block_height) as usize;
+                stake_rewards_by_partition
+                    .iter()
+                    .skip(first_partition_index)
+                    .flatten()
+                    .cloned()
+                    .collect::<Vec<_>>()
+            }
+            EpochRewardStatus::Inactive => return,
+        };
+        self.credit_stake_rewards(&stake_rewards_to_credit);
+        self.end_partitioned_epoch_rewards();
+    }
+
+    /// apply precomputed stake rewards to the current state of their
+    ///   accounts, skipping any that are no longer delegated
+    fn credit_stake_rewards(&mut self, stake_rewards: &[StakeReward]) {
+        let mut rewards = vec![];
+        let mut total_rewards_credited = 0;
+        for stake_reward in stake_rewards {
+            let mut stake_account = match self.get_account(&stake_reward.stake_pubkey) {
+                Some(stake_account) => stake_account,
+                None => continue,
+            };
+            let meta = match stake_account.state() {
+                Ok(StakeState::Stake(meta, _stake)) => meta,
+                _ => continue,
+            };
+            let post_balance = match stake_account.lamports.checked_add(stake_reward.reward) {
+                Some(post_balance) => post_balance,
+                None => continue,
+            };
+            if stake_account
+                .set_state(&StakeState::Stake(meta, stake_reward.stake))
+                .is_err()
+            {
+                continue;
+            }
+            stake_account.lamports = post_balance;
+            self.store_account(&stake_reward.stake_pubkey, &stake_account);
+
+            total_rewards_credited += stake_reward.reward;
+            if stake_reward.reward > 0 {
+                rewards.push((
+                    stake_reward.stake_pubkey,
+                    RewardInfo {
+                        reward_type: RewardType::Staking,
+                        lamports: stake_reward.reward as i64,
+                        post_balance,
+                    },
+                ));
+            }
+        }
+        self.rewards.write().unwrap().append(&mut rewards);
+        self.capitalization
+            .fetch_add(total_rewards_credited, Relaxed);
+
+        let mut epoch_rewards = self.epoch_rewards();
+        if let Err(err) = epoch_rewards.distribute(total_rewards_credited) {
+            error!(
+                "credited {} lamports of stake rewards past the epoch total of {}: {:?}",
+                total_rewards_credited, epoch_rewards.total_rewards, err
+            );
+            epoch_rewards.distributed_rewards = epoch_rewards.total_rewards;
+        }
+        self.update_epoch_rewards_sysvar(&epoch_rewards);
+    }
+
+    fn end_partitioned_epoch_rewards(&mut self) {
+        self.epoch_reward_status = EpochRewardStatus::Inactive;
+        let mut epoch_rewards = self.epoch_rewards();
+        epoch_rewards.active = false;
+        self.update_epoch_rewards_sysvar(&epoch_rewards);
+
+        datapoint_info!(
+            "epoch_rewards-distribution_complete",
+            ("slot", self.slot, i64),
+            ("block_height", self.block_height, i64),
+            ("total_rewards", epoch_rewards.total_rewards, i64),
+            (
+                "distributed_rewards",
+                epoch_rewards.distributed_rewards,
+                i64
+            )
+        );
+    }
+
+    pub fn epoch_rewards(&self) -> sysvar::epoch_rewards::EpochRewards {
+        from_account(
+            &self
+                .get_account(&sysvar::epoch_rewards::id())
+                .unwrap_or_default(),
+        )
+        .unwrap_or_default()
+    }
+
+    fn update_epoch_rewards_sysvar(&self, epoch_rewards: &sysvar::epoch_rewards::EpochRewards) {
+        self.update_sysvar_account(&sysvar::epoch_rewards::id(), |account| {
+            create_account(
+                epoch_rewards,
+                self.inherit_specially_retained_account_fields(account),
+            )
+        });
+    }
+
+    pub fn epoch_reward_status(&self) -> &EpochRewardStatus {
+        &self.epoch_reward_status
     }
 
     fn update_recent_blockhashes_locked(&self, locked_blockhash_queue: &BlockhashQueue) {
@@ -2460,19 +2738,41 @@ impl Bank {
     pub fn simulate_transaction(
         &self,
         transaction: Transaction,
-    ) -> (Result<()>, TransactionLogMessages) {
+    ) -> (
+        Result<()>,
+        TransactionLogMessages,
+        Option<TransactionReturnData>,
+        Option<u64>,
+    ) {
         assert!(self.is_frozen(), "simulation bank must be frozen");
 
+        self.simulate_transaction_unchecked(transaction)
+    }
+
+    /// Run transactions against a bank without committing the results; does
+    /// not check if the bank is frozen, enabling use in single-Bank test
+    /// frameworks
+    pub fn simulate_transaction_unchecked(
+        &self,
+        transaction: Transaction,
+    ) -> (
+        Result<()>,
+        TransactionLogMessages,
+        Option<TransactionReturnData>,
+        Option<u64>,
+    ) {
         let txs = &[transaction];
         let batch = self.prepare_simulation_batch(txs);
 
         let mut timings = ExecuteTimings::default();
 
         let (
-            _loaded_accounts,
+            loaded_accounts,
             executed,
             _inner_instructions,
             log_messages,
+            mut return_data,
+            compute_units,
             _retryable_transactions,
             _transaction_count,
             _signature_count,
@@ -2491,10 +2791,22 @@ impl Bank {
         let log_messages = log_messages
             .get(0)
             .map_or(vec![], |messages| messages.to_vec());
+        let return_data = return_data.pop().flatten();
+        // Transactions that failed to load never executed and consumed nothing
+        let units_consumed = loaded_accounts
+            .get(0)
+            .filter(|(load_result, _nonce_rollback)| load_result.is_ok())
+            .and_then(|_| compute_units.get(0))
+            .map(|units| units.iter().sum());
 
         debug!("simulate_transaction: {:?}", timings);
 
-        (transaction_result, log_messages)
+        (
+            transaction_result,
+            log_messages,
+            return_data,
+            units_consumed,
+        )
     }
 
     pub fn unlock_accounts(&self, batch: &mut TransactionBatch) {
@@ -2891,6 +3203,8 @@ impl Bank {
         Vec<TransactionExecutionResult>,
         Vec<Option<InnerInstructionsList>>,
         Vec<TransactionLogMessages>,
+        Vec<Option<TransactionReturnData>>,
+        Vec<InstructionComputeUnits>,
         Vec<usize>,
         u64,
         u64,
@@ -2940,6 +3254,9 @@ impl Bank {
         let mut inner_instructions: Vec<Option<InnerInstructionsList>> =
             Vec::with_capacity(hashed_txs.len());
         let mut transaction_log_messages = Vec::with_capacity(hashed_txs.len());
+        let mut transaction_return_data = Vec::with_capacity(hashed_txs.len());
+        let mut transaction_compute_units = Vec::with_capacity(hashed_txs.len());
+        let program_trace_filter = self.program_trace_collector.read().unwrap().filter.clone();
         let bpf_compute_budget = self
             .bpf_compute_budget
             .unwrap_or_else(BpfComputeBudget::new);
@@ -2948,7 +3265,11 @@ impl Bank {
             .iter_mut()
             .zip(hashed_txs.as_transactions_iter())
             .map(|(accs, tx)| match accs {
-                (Err(e), _nonce_rollback) => (Err(e.clone()), None),
+                (Err(e), _nonce_rollback) => {
+                    transaction_return_data.push(None);
+                    transaction_compute_units.push(vec![]);
+                    (Err(e.clone()), None)
+                }
                 (Ok(loaded_transaction), nonce_rollback) => {
                     signature_count += u64::from(tx.message().header.num_required_signatures);
                     let executors = self.get_executors(&tx.message, &loaded_transaction.loaders);
@@ -2975,6 +3296,13 @@ impl Bank {
                         None
                     };
 
+                    let mut return_data = None;
+                    let mut instruction_compute_units = vec![];
+                    let program_trace_recorder = tx
+                        .signatures
+                        .get(0)
+                        .filter(|signature| program_trace_filter.matches(signature))
+                        .map(|_| ProgramTraceRecorder::default());
                     let process_result = self.message_processor.process_message(
                         tx.message(),
                         &loader_refcells,
@@ -2982,8 +3310,11 @@ impl Bank {
                         &account_dep_refcells,
                         &self.rent_collector,
                         log_collector.clone(),
+                        &mut return_data,
+                        &mut instruction_compute_units,
                         executors.clone(),
                         instruction_recorders.as_deref(),
+                        program_trace_recorder.as_ref(),
                         self.feature_set.clone(),
                         bpf_compute_budget,
                         &mut timings.details,
@@ -2999,6 +3330,16 @@ impl Bank {
 
                         transaction_log_messages.push(log_messages);
                     }
+                    transaction_return_data.push(return_data);
+                    transaction_compute_units.push(instruction_compute_units);
+                    if let Some(program_trace_recorder) = program_trace_recorder {
+                        self.program_trace_collector.write().unwrap().traces.push(
+                            TransactionProgramTraces {
+                                signature: tx.signatures[0],
+                                traces: program_trace_recorder.take_traces(),
+                            },
+                        );
+                    }
 
                     Self::compile_recorded_instructions(
                         &mut inner_instructions,
@@ -3127,6 +3468,8 @@ impl Bank {
             executed,
             inner_instructions,
             transaction_log_messages,
+            transaction_return_data,
+            transaction_compute_units,
             retryable_txs,
             tx_count,
             signature_count,
@@ -3143,6 +3486,7 @@ impl Bank {
 
         let fee_config = FeeConfig {
             secp256k1_program_enabled: self.secp256k1_program_enabled(),
+            ed25519_program_enabled: self.ed25519_program_enabled(),
         };
 
         let results = txs
@@ -3820,6 +4164,7 @@ impl Bank {
 
     /// Process a batch of transactions.
     #[must_use]
+    #[allow(clippy::type_complexity)]
     pub fn load_execute_and_commit_transactions(
         &self,
         batch: &TransactionBatch,
@@ -3833,6 +4178,8 @@ impl Bank {
         TransactionBalancesSet,
         Vec<Option<InnerInstructionsList>>,
         Vec<TransactionLogMessages>,
+        Vec<Option<TransactionReturnData>>,
+        Vec<InstructionComputeUnits>,
     ) {
         let pre_balances = if collect_balances {
             self.collect_balances(batch)
@@ -3845,6 +4192,8 @@ impl Bank {
             executed,
             inner_instructions,
             transaction_logs,
+            transaction_return_data,
+            transaction_compute_units,
             _,
             tx_count,
             signature_count,
@@ -3874,6 +4223,8 @@ impl Bank {
             TransactionBalancesSet::new(pre_balances, post_balances),
             inner_instructions,
             transaction_logs,
+            transaction_return_data,
+            transaction_compute_units,
         )
     }
 
@@ -4704,6 +5055,11 @@ impl Bank {
             .is_active(&feature_set::secp256k1_program_enabled::id())
     }
 
+    pub fn ed25519_program_enabled(&self) -> bool {
+        self.feature_set
+            .is_active(&feature_set::ed25519_program_enabled::id())
+    }
+
     pub fn no_overflow_rent_distribution_enabled(&self) -> bool {
         self.feature_set
             .is_active(&feature_set::no_overflow_rent_distribution::id())
@@ -4998,7 +5354,10 @@ fn is_simple_vote_transaction(transaction: &Transaction) -> bool {
             {
                 return matches!(
                     vote_instruction,
-                    VoteInstruction::Vote(_) | VoteInstruction::VoteSwitch(_, _)
+                    VoteInstruction::Vote(_)
+                        | VoteInstruction::VoteSwitch(_, _)
+                        | VoteInstruction::CompactUpdateVoteState(_)
+                        | VoteInstruction::CompactUpdateVoteStateSwitch(_, _)
                 );
             }
         }
@@ -5031,6 +5390,7 @@ pub(crate) mod tests {
         instruction::{AccountMeta, CompiledInstruction, Instruction, InstructionError},
         keyed_account::KeyedAccount,
         message::{Message, MessageHeader},
+        native_token::LAMPORTS_PER_SOL,
         nonce,
         poh_config::PohConfig,
         process_instruction::InvokeContext,
@@ -5048,7 +5408,8 @@ pub(crate) mod tests {
     use solana_vote_program::{
         vote_instruction,
         vote_state::{
-            self, BlockTimestamp, Vote, VoteInit, VoteState, VoteStateVersions, MAX_LOCKOUT_HISTORY,
+            self, BlockTimestamp, Lockout, Vote, VoteInit, VoteState, VoteStateUpdate,
+            VoteStateVersions, MAX_LOCKOUT_HISTORY,
         },
     };
     use std::{result, thread::Builder, time::Duration};
@@ -7127,6 +7488,266 @@ pub(crate) mod tests {
         }
     }
 
+    /// a bank in epoch 0 with `num_stake_accounts` stake accounts delegated to a
+    ///   vote account that has earned credits; all features are active
+    fn create_bank_with_epoch_rewards(
+        num_stake_accounts: usize,
+    ) -> (Arc<Bank>, Keypair, Vec<Pubkey>) {
+        let GenesisConfigInfo {
+            genesis_config,
+            mint_keypair,
+            ..
+        } = create_genesis_config_with_leader(
+            1_000_000 * LAMPORTS_PER_SOL,
+            &solana_sdk::pubkey::new_rand(),
+            42,
+        );
+        let bank = Arc::new(Bank::new(&genesis_config));
+
+        let vote_id = solana_sdk::pubkey::new_rand();
+        let mut vote_account =
+            vote_state::create_account(&vote_id, &solana_sdk::pubkey::new_rand(), 50, 100);
+        let stake_ids = (0..num_stake_accounts)
+            .map(|_| {
+                let (stake_id, stake_account) =
+                    crate::stakes::tests::create_stake_account(LAMPORTS_PER_SOL, &vote_id);
+                bank.store_account_and_update_capitalization(&stake_id, &stake_account);
+                stake_id
+            })
+            .collect();
+
+        let mut vote_state = VoteState::from(&vote_account).unwrap();
+        for i in 0..MAX_LOCKOUT_HISTORY + 42 {
+            vote_state.process_slot_vote_unchecked(i as u64);
+        }
+        let versioned = VoteStateVersions::Current(Box::new(vote_state));
+        VoteState::to(&versioned, &mut vote_account).unwrap();
+        bank.store_account_and_update_capitalization(&vote_id, &vote_account);
+
+        (bank, mint_keypair, stake_ids)
+    }
+
+    fn stake_rewards_by_partition(bank: &Bank) -> Arc<Vec<Vec<StakeReward>>> {
+        match bank.epoch_reward_status() {
+            EpochRewardStatus::Active {
+                stake_rewards_by_partition,
+                ..
+            } => stake_rewards_by_partition.clone(),
+            EpochRewardStatus::Inactive => panic!("no epoch rewards are being distributed"),
+        }
+    }
+
+    #[test]
+    fn test_partitioned_epoch_rewards() {
+        solana_logger::setup();
+
+        let (bank0, mint_keypair, stake_ids) = create_bank_with_epoch_rewards(64);
+        let pre_balances: HashMap<_, _> = stake_ids
+            .iter()
+            .map(|stake_id| (*stake_id, bank0.get_balance(stake_id)))
+            .collect();
+
+        // the first bank of epoch 1 calculates rewards, but only pays the voters
+        let bank1 = Arc::new(Bank::new_from_parent(
+            &bank0,
+            &Pubkey::default(),
+            bank0.get_slots_in_epoch(bank0.epoch()) + 1,
+        ));
+        for stake_id in &stake_ids {
+            assert_eq!(bank1.get_balance(stake_id), pre_balances[stake_id]);
+        }
+        assert!(bank1
+            .rewards
+            .read()
+            .unwrap()
+            .iter()
+            .all(|(_address, reward)| reward.reward_type == RewardType::Voting));
+
+        let epoch_rewards = bank1.epoch_rewards();
+        assert!(epoch_rewards.active);
+        assert_eq!(
+            epoch_rewards.distribution_starting_block_height,
+            bank1.block_height() + 1
+        );
+        assert_eq!(epoch_rewards.num_partitions, NUM_EPOCH_REWARD_PARTITIONS);
+        assert_eq!(epoch_rewards.parent_blockhash, bank0.last_blockhash());
+        assert_eq!(epoch_rewards.distributed_rewards, 0);
+        assert!(epoch_rewards.total_rewards > 0);
+
+        let stake_rewards_by_partition = stake_rewards_by_partition(&bank1);
+        assert_eq!(
+            stake_rewards_by_partition.len() as u64,
+            NUM_EPOCH_REWARD_PARTITIONS
+        );
+        assert_eq!(
+            stake_rewards_by_partition.iter().flatten().count(),
+            stake_ids.len()
+        );
+        assert_eq!(
+            stake_rewards_by_partition
+                .iter()
+                .flatten()
+                .map(|stake_reward| stake_reward.reward)
+                .sum::<u64>(),
+            epoch_rewards.total_rewards
+        );
+
+        // stake accounts cannot be modified until they are credited
+        let message = Message::new(
+            &[stake_instruction::deactivate_stake(
+                &stake_ids[0],
+                &mint_keypair.pubkey(),
+            )],
+            Some(&mint_keypair.pubkey()),
+        );
+        let tx = Transaction::new(&[&mint_keypair], message, bank1.last_blockhash());
+        assert_eq!(
+            bank1.process_transaction(&tx),
+            Err(TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(
+                    solana_stake_program::stake_instruction::StakeError::EpochRewardsActive as u32
+                )
+            ))
+        );
+
+        // each of the following blocks credits one partition
+        let mut bank = bank1;
+        for (partition_index, stake_rewards) in stake_rewards_by_partition.iter().enumerate() {
+            bank = Arc::new(new_from_parent(&bank));
+            let mut expected_rewards = vec![];
+            for stake_reward in stake_rewards {
+                assert_eq!(
+                    epoch_reward_partition_index(
+                        &epoch_rewards.parent_blockhash,
+                        &stake_reward.stake_pubkey,
+                        NUM_EPOCH_REWARD_PARTITIONS
+                    ),
+                    partition_index
+                );
+                let post_balance = bank.get_balance(&stake_reward.stake_pubkey);
+                assert_eq!(
+                    post_balance,
+                    pre_balances[&stake_reward.stake_pubkey] + stake_reward.reward
+                );
+                let stake_account = bank.get_account(&stake_reward.stake_pubkey).unwrap();
+                assert_eq!(
+                    StakeState::stake_from(&stake_account),
+                    Some(stake_reward.stake)
+                );
+                expected_rewards.push((
+                    stake_reward.stake_pubkey,
+                    RewardInfo {
+                        reward_type: RewardType::Staking,
+                        lamports: stake_reward.reward as i64,
+                        post_balance,
+                    },
+                ));
+            }
+            assert_eq!(*bank.rewards.read().unwrap(), expected_rewards);
+            assert_eq!(
+                bank.epoch_rewards().active,
+                partition_index + 1 < stake_rewards_by_partition.len()
+            );
+        }
+
+        let epoch_rewards = bank.epoch_rewards();
+        assert!(!epoch_rewards.active);
+        assert_eq!(
+            epoch_rewards.distributed_rewards,
+            epoch_rewards.total_rewards
+        );
+        assert_eq!(*bank.epoch_reward_status(), EpochRewardStatus::Inactive);
+        bank.freeze();
+        assert!(bank.calculate_and_verify_capitalization());
+
+        // the stake program is usable again
+        let bank = Arc::new(new_from_parent(&bank));
+        let message = Message::new(
+            &[stake_instruction::deactivate_stake(
+                &stake_ids[1],
+                &mint_keypair.pubkey(),
+            )],
+            Some(&mint_keypair.pubkey()),
+        );
+        let tx = Transaction::new(&[&mint_keypair], message, bank.last_blockhash());
+        assert_eq!(
+            bank.process_transaction(&tx),
+            Err(TransactionError::InstructionError(
+                0,
+                InstructionError::MissingRequiredSignature
+            ))
+        );
+    }
+
+    #[test]
+    fn test_partitioned_epoch_rewards_credited_before_next_epoch() {
+        solana_logger::setup();
+
+        let (bank0, _mint_keypair, stake_ids) = create_bank_with_epoch_rewards(8);
+        let bank1 = Arc::new(Bank::new_from_parent(
+            &bank0,
+            &Pubkey::default(),
+            bank0.get_slots_in_epoch(bank0.epoch()),
+        ));
+        let stake_rewards_by_partition = stake_rewards_by_partition(&bank1);
+        let bank2 = Arc::new(new_from_parent(&bank1));
+        assert!(bank2.epoch_rewards().active);
+
+        // skipping to the next epoch credits whatever is left
+        let bank3 = Bank::new_from_parent(
+            &bank2,
+            &Pubkey::default(),
+            bank2.slot() + bank2.get_slots_in_epoch(bank2.epoch()),
+        );
+        assert_eq!(bank3.epoch(), bank2.epoch() + 1);
+        for stake_reward in stake_rewards_by_partition.iter().flatten() {
+            assert_eq!(
+                bank3.get_balance(&stake_reward.stake_pubkey),
+                bank0.get_balance(&stake_reward.stake_pubkey) + stake_reward.reward
+            );
+        }
+        assert_eq!(
+            bank3
+                .rewards
+                .read()
+                .unwrap()
+                .iter()
+                .filter(|(_address, reward)| reward.reward_type == RewardType::Staking)
+                .count()
+                + bank2.rewards.read().unwrap().len(),
+            stake_ids.len()
+        );
+        // no votes were cast in epoch 1, so there is nothing new to distribute
+        let epoch_rewards = bank3.epoch_rewards();
+        assert!(!epoch_rewards.active);
+        assert_eq!(
+            epoch_rewards.distributed_rewards,
+            epoch_rewards.total_rewards
+        );
+        assert_eq!(*bank3.epoch_reward_status(), EpochRewardStatus::Inactive);
+        bank3.freeze();
+        assert!(bank3.calculate_and_verify_capitalization());
+    }
+
+    #[test]
+    fn test_partitioned_epoch_rewards_warp() {
+        solana_logger::setup();
+
+        let (bank0, _mint_keypair, stake_ids) = create_bank_with_epoch_rewards(8);
+        let bank1 = Bank::warp_from_parent(
+            &bank0,
+            &Pubkey::default(),
+            bank0.get_slots_in_epoch(bank0.epoch()) + 1,
+        );
+        assert!(!bank1.epoch_rewards().active);
+        assert_eq!(*bank1.epoch_reward_status(), EpochRewardStatus::Inactive);
+        for stake_id in &stake_ids {
+            assert!(bank1.get_balance(stake_id) > bank0.get_balance(stake_id));
+        }
+        assert!(bank1.calculate_and_verify_capitalization());
+    }
+
     // Test that purging 0 lamports accounts works.
     #[test]
     fn test_purge_empty_accounts() {
@@ -9759,18 +10380,26 @@ pub(crate) mod tests {
         let txs = vec![tx0, tx1, tx2];
 
         let lock_result = bank0.prepare_batch(txs.iter());
-        let (transaction_results, transaction_balances_set, inner_instructions, transaction_logs) =
-            bank0.load_execute_and_commit_transactions(
-                &lock_result,
-                MAX_PROCESSING_AGE,
-                true,
-                false,
-                false,
-                &mut ExecuteTimings::default(),
-            );
+        let (
+            transaction_results,
+            transaction_balances_set,
+            inner_instructions,
+            transaction_logs,
+            transaction_return_data,
+            transaction_compute_units,
+        ) = bank0.load_execute_and_commit_transactions(
+            &lock_result,
+            MAX_PROCESSING_AGE,
+            true,
+            false,
+            false,
+            &mut ExecuteTimings::default(),
+        );
 
         assert!(inner_instructions[0].iter().all(|ix| ix.is_empty()));
         assert_eq!(transaction_logs.len(), 0);
+        assert_eq!(transaction_return_data, vec![None; 3]);
+        assert_eq!(transaction_compute_units, vec![vec![0], vec![], vec![0]]);
 
         assert_eq!(transaction_balances_set.pre_balances.len(), 3);
         assert_eq!(transaction_balances_set.post_balances.len(), 3);
@@ -10546,6 +11175,31 @@ pub(crate) mod tests {
         );
         assert_eq!(bank.get_balance(&mint_keypair.pubkey()), 496); // transaction fee charged
 
+        // VoteInstruction::CompactUpdateVoteState is allowed.  The transaction fails with a vote
+        // program instruction error because the vote account is not actually setup
+        let tx = Transaction::new_signed_with_payer(
+            &[vote_instruction::compact_update_vote_state(
+                &vote_pubkey,
+                &authorized_voter.pubkey(),
+                VoteStateUpdate::new(
+                    vec![Lockout::new(1)].into_iter().collect(),
+                    None,
+                    Hash::default(),
+                ),
+            )],
+            Some(&mint_keypair.pubkey()),
+            &[&mint_keypair, &authorized_voter],
+            bank.last_blockhash(),
+        );
+        assert_eq!(
+            bank.process_transaction(&tx),
+            Err(TransactionError::InstructionError(
+                0,
+                InstructionError::InvalidAccountOwner
+            ))
+        );
+        assert_eq!(bank.get_balance(&mint_keypair.pubkey()), 494); // transaction fee charged
+
         // Other vote program instructions, like VoteInstruction::UpdateCommission are not allowed
         let tx = Transaction::new_signed_with_payer(
             &[vote_instruction::update_commission(
@@ -10561,7 +11215,7 @@ pub(crate) mod tests {
             bank.process_transaction(&tx),
             Err(TransactionError::ClusterMaintenance)
         );
-        assert_eq!(bank.get_balance(&mint_keypair.pubkey()), 496); // no transaction fee charged
+        assert_eq!(bank.get_balance(&mint_keypair.pubkey()), 494); // no transaction fee charged
     }
 
     #[test]
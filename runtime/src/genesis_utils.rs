@@ -2,7 +2,7 @@ use solana_sdk::{
     account::Account,
     account::AccountSharedData,
     feature::{self, Feature},
-    feature_set::FeatureSet,
+    feature_set::{self, FeatureSet},
     fee_calculator::FeeRateGovernor,
     genesis_config::{ClusterType, GenesisConfig},
     pubkey::Pubkey,
@@ -161,21 +161,33 @@ pub fn create_genesis_config_with_leader(
     }
 }
 
+// Features that development clusters leave inactive at genesis.  Their stakes are far below
+// the raised minimum delegation; tests that need it call `activate_feature` explicitly.
+fn is_excluded_from_development_genesis(feature_id: &Pubkey) -> bool {
+    *feature_id == feature_set::stake_raise_minimum_delegation_to_1_sol::id()
+}
+
 pub fn activate_all_features(genesis_config: &mut GenesisConfig) {
     // Activate all features at genesis in development mode
     for feature_id in FeatureSet::default().inactive {
-        genesis_config.accounts.insert(
-            feature_id,
-            Account::from(feature::create_account(
-                &Feature {
-                    activated_at: Some(0),
-                },
-                std::cmp::max(genesis_config.rent.minimum_balance(Feature::size_of()), 1),
-            )),
-        );
+        if !is_excluded_from_development_genesis(&feature_id) {
+            activate_feature(genesis_config, feature_id);
+        }
     }
 }
 
+pub fn activate_feature(genesis_config: &mut GenesisConfig, feature_id: Pubkey) {
+    genesis_config.accounts.insert(
+        feature_id,
+        Account::from(feature::create_account(
+            &Feature {
+                activated_at: Some(0),
+            },
+            std::cmp::max(genesis_config.rent.minimum_balance(Feature::size_of()), 1),
+        )),
+    );
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn create_genesis_config_with_leader_ex(
     mint_lamports: u64,
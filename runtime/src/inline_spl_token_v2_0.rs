@@ -1,50 +1,6 @@
-// Partial SPL Token v2.0.x declarations inlined to avoid an external dependency on the spl-token crate
-solana_sdk::declare_id!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+// Partial SPL Token v2.0.x declarations, shared with native programs through the SDK
+pub use solana_sdk::inline_spl_token_v2_0::*;
 
 pub(crate) mod new_token_program {
     solana_sdk::declare_id!("t31zsgDmRntje65uXV3LrnWaJtJJpMd4LyJxq2R2VrU");
 }
-
-/*
-    spl_token::state::Account {
-        mint: Pubkey,
-        owner: Pubkey,
-        amount: u64,
-        delegate: COption<Pubkey>,
-        state: AccountState,
-        is_native: COption<u64>,
-        delegated_amount: u64,
-        close_authority: COption<Pubkey>,
-    }
-*/
-pub const SPL_TOKEN_ACCOUNT_MINT_OFFSET: usize = 0;
-pub const SPL_TOKEN_ACCOUNT_OWNER_OFFSET: usize = 32;
-
-pub mod state {
-    const LEN: usize = 165;
-    pub struct Account;
-    impl Account {
-        pub fn get_packed_len() -> usize {
-            LEN
-        }
-    }
-}
-
-pub mod native_mint {
-    solana_sdk::declare_id!("So11111111111111111111111111111111111111112");
-
-    /*
-        Mint {
-            mint_authority: COption::None,
-            supply: 0,
-            decimals: 9,
-            is_initialized: true,
-            freeze_authority: COption::None,
-        }
-    */
-    pub const ACCOUNT_DATA: [u8; 82] = [
-        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 9, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-    ];
-}
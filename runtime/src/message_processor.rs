@@ -1,6 +1,7 @@
 use crate::{
     accounts::Accounts, accounts_index::Ancestors, instruction_recorder::InstructionRecorder,
-    log_collector::LogCollector, native_loader::NativeLoader, rent_collector::RentCollector,
+    log_collector::LogCollector, native_loader::NativeLoader, program_trace::ProgramTraceRecorder,
+    rent_collector::RentCollector,
 };
 use log::*;
 use serde::{Deserialize, Serialize};
@@ -9,8 +10,8 @@ use solana_sdk::{
     account_utils::StateMut,
     bpf_loader_upgradeable::{self, UpgradeableLoaderState},
     feature_set::{
-        cpi_share_ro_and_exec_accounts, demote_sysvar_write_locks, instructions_sysvar_enabled,
-        FeatureSet,
+        cpi_share_ro_and_exec_accounts, demote_sysvar_write_locks, do_support_realloc,
+        instructions_sysvar_enabled, upgradeable_extend_program_data, FeatureSet,
     },
     ic_msg,
     instruction::{CompiledInstruction, Instruction, InstructionError},
@@ -19,10 +20,11 @@ use solana_sdk::{
     native_loader,
     process_instruction::{
         BpfComputeBudget, ComputeMeter, Executor, InvokeContext, Logger,
-        ProcessInstructionWithContext,
+        ProcessInstructionWithContext, ProgramTrace, TransactionReturnData,
     },
     pubkey::Pubkey,
     rent::Rent,
+    system_instruction::MAX_PERMITTED_DATA_LENGTH,
     system_program,
     sysvar::instructions,
     transaction::TransactionError,
@@ -106,6 +108,8 @@ impl PreAccount {
         post: &AccountSharedData,
         timings: &mut ExecuteDetailsTimings,
         outermost_call: bool,
+        do_support_realloc: bool,
+        do_support_extend_program_data: bool,
     ) -> Result<(), InstructionError> {
         let pre = self.account.borrow();
 
@@ -141,14 +145,32 @@ impl PreAccount {
             }
         }
 
-        // Only the system program can change the size of the data
-        //  and only if the system program owns the account
         let data_len_changed = pre.data().len() != post.data().len();
-        if data_len_changed
-            && (!system_program::check_id(program_id) // line coverage used to get branch coverage
-                || !system_program::check_id(&pre.owner))
-        {
-            return Err(InstructionError::AccountDataSizeChanged);
+        if do_support_realloc {
+            // Account data size cannot exceed a maximum length
+            if post.data().len() > MAX_PERMITTED_DATA_LENGTH as usize {
+                return Err(InstructionError::InvalidRealloc);
+            }
+
+            // Only the owner of the account may change the size of the data
+            if data_len_changed && *program_id != pre.owner {
+                return Err(InstructionError::AccountDataSizeChanged);
+            }
+        } else {
+            // Only the system program can change the size of the data
+            //  and only if the system program owns the account,
+            //  the upgradeable loader may also grow the accounts it owns
+            let is_loader_growing_own_account = do_support_extend_program_data
+                && bpf_loader_upgradeable::check_id(program_id)
+                && bpf_loader_upgradeable::check_id(&pre.owner)
+                && pre.data().len() < post.data().len();
+            if data_len_changed
+                && !is_loader_growing_own_account
+                && (!system_program::check_id(program_id) // line coverage used to get branch coverage
+                    || !system_program::check_id(&pre.owner))
+            {
+                return Err(InstructionError::AccountDataSizeChanged);
+            }
         }
 
         // Only the owner may change account data
@@ -261,11 +283,13 @@ pub struct ThisInvokeContext<'a> {
     compute_meter: Rc<RefCell<dyn ComputeMeter>>,
     executors: Rc<RefCell<Executors>>,
     instruction_recorder: Option<InstructionRecorder>,
+    program_trace_recorder: Option<ProgramTraceRecorder>,
     feature_set: Arc<FeatureSet>,
     pub timings: ExecuteDetailsTimings,
     account_db: Arc<Accounts>,
     ancestors: &'a Ancestors,
     sysvars: Vec<(Pubkey, Option<Rc<Vec<u8>>>)>,
+    return_data: (Pubkey, Vec<u8>),
 }
 impl<'a> ThisInvokeContext<'a> {
     #[allow(clippy::too_many_arguments)]
@@ -280,6 +304,7 @@ impl<'a> ThisInvokeContext<'a> {
         bpf_compute_budget: BpfComputeBudget,
         executors: Rc<RefCell<Executors>>,
         instruction_recorder: Option<InstructionRecorder>,
+        program_trace_recorder: Option<ProgramTraceRecorder>,
         feature_set: Arc<FeatureSet>,
         account_db: Arc<Accounts>,
         ancestors: &'a Ancestors,
@@ -300,13 +325,33 @@ impl<'a> ThisInvokeContext<'a> {
             })),
             executors,
             instruction_recorder,
+            program_trace_recorder,
             feature_set,
             timings: ExecuteDetailsTimings::default(),
             account_db,
             ancestors,
             sysvars: vec![],
+            return_data: (*program_id, vec![]),
         }
     }
+
+    /// Take the return data left behind by the instruction, if any was set
+    fn take_return_data(&mut self) -> Option<TransactionReturnData> {
+        let (program_id, data) = std::mem::take(&mut self.return_data);
+        if data.is_empty() {
+            None
+        } else {
+            Some(TransactionReturnData { program_id, data })
+        }
+    }
+
+    /// Compute units consumed by the instruction so far, including any
+    /// cross-program invocations it made
+    fn compute_units_consumed(&self) -> u64 {
+        self.bpf_compute_budget
+            .max_units
+            .saturating_sub(self.compute_meter.borrow().get_remaining())
+    }
 }
 impl<'a> InvokeContext for ThisInvokeContext<'a> {
     fn push(&mut self, key: &Pubkey) -> Result<(), InstructionError> {
@@ -344,6 +389,9 @@ impl<'a> InvokeContext for ThisInvokeContext<'a> {
                 caller_write_privileges,
                 &mut self.timings,
                 self.feature_set.is_active(&demote_sysvar_write_locks::id()),
+                self.feature_set.is_active(&do_support_realloc::id()),
+                self.feature_set
+                    .is_active(&upgradeable_extend_program_data::id()),
             ),
             None => Err(InstructionError::GenericError), // Should never happen
         }
@@ -376,6 +424,22 @@ impl<'a> InvokeContext for ThisInvokeContext<'a> {
             recorder.record_instruction(instruction.clone());
         }
     }
+    fn is_program_tracing_enabled(&self) -> bool {
+        self.program_trace_recorder.is_some()
+    }
+    fn record_program_trace(&self, trace: ProgramTrace) {
+        if let Some(recorder) = &self.program_trace_recorder {
+            recorder.record_trace(trace);
+        }
+    }
+    fn native_invoke(
+        &mut self,
+        instruction: Instruction,
+        keyed_accounts: &[&KeyedAccount],
+        signers_seeds: &[&[&[u8]]],
+    ) -> Result<(), InstructionError> {
+        MessageProcessor::native_invoke(self, instruction, keyed_accounts, signers_seeds)
+    }
     fn is_feature_active(&self, feature_id: &Pubkey) -> bool {
         self.feature_set.is_active(feature_id)
     }
@@ -441,6 +505,13 @@ impl<'a> InvokeContext for ThisInvokeContext<'a> {
         }
         result
     }
+    fn set_return_data(&mut self, data: Vec<u8>) -> Result<(), InstructionError> {
+        self.return_data = (*self.get_caller()?, data);
+        Ok(())
+    }
+    fn get_return_data(&self) -> (Pubkey, &[u8]) {
+        (self.return_data.0, &self.return_data.1)
+    }
 }
 pub struct ThisLogger {
     log_collector: Option<Rc<LogCollector>>,
@@ -825,12 +896,15 @@ impl MessageProcessor {
 
         {
             let invoke_context = invoke_context.borrow();
+            // Native callers have no serialized input buffer that bounds the data length
+            let do_support_realloc = invoke_context.is_feature_active(&do_support_realloc::id());
             for (i, (account, account_ref)) in accounts.iter().zip(account_refs).enumerate() {
                 let account = account.borrow();
                 if message.is_writable(i, demote_sysvar_write_locks) && !account.executable {
                     account_ref.try_account_ref_mut()?.lamports = account.lamports;
                     account_ref.try_account_ref_mut()?.owner = account.owner;
-                    if account_ref.data_len()? != account.data().len()
+                    if !do_support_realloc
+                        && account_ref.data_len()? != account.data().len()
                         && account_ref.data_len()? != 0
                     {
                         // Only support for `CreateAccount` at this time.
@@ -881,6 +955,9 @@ impl MessageProcessor {
                 demote_sysvar_write_locks,
             );
 
+            // Return data is cleared before every cross-program invocation
+            invoke_context.set_return_data(Vec::new())?;
+
             // Invoke callee
             invoke_context.push(program_id)?;
 
@@ -941,6 +1018,7 @@ impl MessageProcessor {
     }
 
     /// Verify the results of an instruction
+    #[allow(clippy::too_many_arguments)]
     pub fn verify(
         message: &Message,
         instruction: &CompiledInstruction,
@@ -950,6 +1028,8 @@ impl MessageProcessor {
         rent: &Rent,
         timings: &mut ExecuteDetailsTimings,
         demote_sysvar_write_locks: bool,
+        do_support_realloc: bool,
+        do_support_extend_program_data: bool,
     ) -> Result<(), InstructionError> {
         // Verify all executable accounts have zero outstanding refs
         Self::verify_account_references(executable_accounts)?;
@@ -973,6 +1053,8 @@ impl MessageProcessor {
                     &account,
                     timings,
                     true,
+                    do_support_realloc,
+                    do_support_extend_program_data,
                 )?;
                 pre_sum += u128::from(pre_accounts[unique_index].lamports());
                 post_sum += u128::from(account.lamports);
@@ -989,6 +1071,7 @@ impl MessageProcessor {
     }
 
     /// Verify the results of a cross-program instruction
+    #[allow(clippy::too_many_arguments)]
     fn verify_and_update(
         message: &Message,
         instruction: &CompiledInstruction,
@@ -999,6 +1082,8 @@ impl MessageProcessor {
         caller_write_privileges: Option<&[bool]>,
         timings: &mut ExecuteDetailsTimings,
         demote_sysvar_write_locks: bool,
+        do_support_realloc: bool,
+        do_support_extend_program_data: bool,
     ) -> Result<(), InstructionError> {
         // Verify the per-account instruction results
         let (mut pre_sum, mut post_sum) = (0_u128, 0_u128);
@@ -1028,6 +1113,8 @@ impl MessageProcessor {
                             &account,
                             timings,
                             false,
+                            do_support_realloc,
+                            do_support_extend_program_data,
                         )?;
                         pre_sum += u128::from(pre_account.lamports());
                         post_sum += u128::from(account.lamports);
@@ -1064,8 +1151,11 @@ impl MessageProcessor {
         account_deps: &[(Pubkey, Rc<RefCell<AccountSharedData>>)],
         rent_collector: &RentCollector,
         log_collector: Option<Rc<LogCollector>>,
+        return_data: &mut Option<TransactionReturnData>,
+        instruction_compute_units: &mut Vec<u64>,
         executors: Rc<RefCell<Executors>>,
         instruction_recorder: Option<InstructionRecorder>,
+        program_trace_recorder: Option<ProgramTraceRecorder>,
         instruction_index: usize,
         feature_set: Arc<FeatureSet>,
         bpf_compute_budget: BpfComputeBudget,
@@ -1089,6 +1179,9 @@ impl MessageProcessor {
             }
         }
 
+        let do_support_realloc = feature_set.is_active(&do_support_realloc::id());
+        let do_support_extend_program_data =
+            feature_set.is_active(&upgradeable_extend_program_data::id());
         let pre_accounts = Self::create_pre_accounts(message, instruction, accounts);
         let program_id = instruction.program_id(&message.account_keys);
         let mut invoke_context = ThisInvokeContext::new(
@@ -1102,6 +1195,7 @@ impl MessageProcessor {
             bpf_compute_budget,
             executors,
             instruction_recorder,
+            program_trace_recorder,
             feature_set,
             account_db,
             ancestors,
@@ -1113,12 +1207,15 @@ impl MessageProcessor {
             accounts,
             demote_sysvar_write_locks,
         );
-        self.process_instruction(
+        let result = self.process_instruction(
             program_id,
             &keyed_accounts,
             &instruction.data,
             &mut invoke_context,
-        )?;
+        );
+        *return_data = invoke_context.take_return_data();
+        instruction_compute_units.push(invoke_context.compute_units_consumed());
+        result?;
         Self::verify(
             message,
             instruction,
@@ -1128,6 +1225,8 @@ impl MessageProcessor {
             &rent_collector.rent,
             timings,
             demote_sysvar_write_locks,
+            do_support_realloc,
+            do_support_extend_program_data,
         )?;
 
         timings.accumulate(&invoke_context.timings);
@@ -1138,6 +1237,9 @@ impl MessageProcessor {
     /// Process a message.
     /// This method calls each instruction in the message over the set of loaded Accounts
     /// The accounts are committed back to the bank only if every instruction succeeds
+    /// The compute units consumed by each executed instruction are appended to
+    /// `instruction_compute_units`
+    /// BPF programs are traced into `program_trace_recorder` if one is given
     #[allow(clippy::too_many_arguments)]
     #[allow(clippy::type_complexity)]
     pub fn process_message(
@@ -1148,8 +1250,11 @@ impl MessageProcessor {
         account_deps: &[(Pubkey, Rc<RefCell<AccountSharedData>>)],
         rent_collector: &RentCollector,
         log_collector: Option<Rc<LogCollector>>,
+        return_data: &mut Option<TransactionReturnData>,
+        instruction_compute_units: &mut Vec<u64>,
         executors: Rc<RefCell<Executors>>,
         instruction_recorders: Option<&[InstructionRecorder]>,
+        program_trace_recorder: Option<&ProgramTraceRecorder>,
         feature_set: Arc<FeatureSet>,
         bpf_compute_budget: BpfComputeBudget,
         timings: &mut ExecuteDetailsTimings,
@@ -1169,8 +1274,11 @@ impl MessageProcessor {
                 account_deps,
                 rent_collector,
                 log_collector.clone(),
+                return_data,
+                instruction_compute_units,
                 executors.clone(),
                 instruction_recorder,
+                program_trace_recorder.cloned(),
                 instruction_index,
                 feature_set.clone(),
                 bpf_compute_budget,
@@ -1229,6 +1337,7 @@ mod tests {
             BpfComputeBudget::default(),
             Rc::new(RefCell::new(Executors::default())),
             None,
+            None,
             Arc::new(FeatureSet::all_enabled()),
             Arc::new(Accounts::default()),
             &ancestors,
@@ -1348,6 +1457,8 @@ mod tests {
     struct Change {
         program_id: Pubkey,
         is_writable: bool,
+        do_support_realloc: bool,
+        do_support_extend_program_data: bool,
         rent: Rent,
         pre: PreAccount,
         post: AccountSharedData,
@@ -1358,6 +1469,8 @@ mod tests {
                 program_id: *program_id,
                 rent: Rent::default(),
                 is_writable: true,
+                do_support_realloc: false,
+                do_support_extend_program_data: false,
                 pre: PreAccount::new(
                     &solana_sdk::pubkey::new_rand(),
                     &AccountSharedData::from(Account {
@@ -1378,6 +1491,14 @@ mod tests {
             self.is_writable = false;
             self
         }
+        pub fn support_realloc(mut self) -> Self {
+            self.do_support_realloc = true;
+            self
+        }
+        pub fn support_extend_program_data(mut self) -> Self {
+            self.do_support_extend_program_data = true;
+            self
+        }
         pub fn executable(mut self, pre: bool, post: bool) -> Self {
             self.pre.account.borrow_mut().executable = pre;
             self.post.executable = post;
@@ -1410,6 +1531,8 @@ mod tests {
                 &self.post,
                 &mut ExecuteDetailsTimings::default(),
                 false,
+                self.do_support_realloc,
+                self.do_support_extend_program_data,
             )
         }
     }
@@ -1601,6 +1724,102 @@ mod tests {
         Err(InstructionError::AccountDataSizeChanged),
         "system program should not be able to change the data length of accounts it does not own"
         );
+        assert_eq!(
+            Change::new(&bpf_loader_upgradeable::id(), &bpf_loader_upgradeable::id())
+                .data(vec![0], vec![0, 0])
+                .verify(),
+            Err(InstructionError::AccountDataSizeChanged),
+            "upgradeable loader should not be able to grow accounts before ExtendProgramData is enabled"
+        );
+        assert_eq!(
+            Change::new(&bpf_loader_upgradeable::id(), &bpf_loader_upgradeable::id())
+                .support_extend_program_data()
+                .data(vec![0], vec![0, 0])
+                .verify(),
+            Ok(()),
+            "upgradeable loader should be able to grow the data len of accounts it owns"
+        );
+        assert_eq!(
+            Change::new(&bpf_loader_upgradeable::id(), &bpf_loader_upgradeable::id())
+                .support_extend_program_data()
+                .data(vec![0, 0], vec![0])
+                .verify(),
+            Err(InstructionError::AccountDataSizeChanged),
+            "upgradeable loader should not be able to shrink the data len"
+        );
+        assert_eq!(
+            Change::new(&alice_program_id, &bpf_loader_upgradeable::id())
+                .support_extend_program_data()
+                .data(vec![0], vec![0, 0])
+                .verify(),
+            Err(InstructionError::AccountDataSizeChanged),
+            "upgradeable loader should not be able to grow accounts it does not own"
+        );
+    }
+
+    #[test]
+    fn test_verify_account_changes_data_len_with_realloc() {
+        let alice_program_id = solana_sdk::pubkey::new_rand();
+        let mallory_program_id = solana_sdk::pubkey::new_rand();
+
+        assert_eq!(
+            Change::new(&alice_program_id, &alice_program_id)
+                .data(vec![0], vec![0, 0])
+                .support_realloc()
+                .verify(),
+            Ok(()),
+            "alice program should be able to grow the data len of accounts it owns"
+        );
+        assert_eq!(
+            Change::new(&alice_program_id, &alice_program_id)
+                .data(vec![0, 0], vec![0])
+                .support_realloc()
+                .verify(),
+            Ok(()),
+            "alice program should be able to shrink the data len of accounts it owns"
+        );
+        assert_eq!(
+            Change::new(&alice_program_id, &mallory_program_id)
+                .data(vec![0], vec![0, 0])
+                .support_realloc()
+                .verify(),
+            Err(InstructionError::AccountDataSizeChanged),
+            "mallory should not be able to change the data len of alice's accounts"
+        );
+        assert_eq!(
+            Change::new(&alice_program_id, &system_program::id())
+                .data(vec![0], vec![0, 0])
+                .support_realloc()
+                .verify(),
+            Err(InstructionError::AccountDataSizeChanged),
+            "system program should not be able to change the data len of accounts it does not own"
+        );
+        assert_eq!(
+            Change::new(&alice_program_id, &alice_program_id)
+                .data(vec![0], vec![0, 0])
+                .read_only()
+                .support_realloc()
+                .verify(),
+            Err(InstructionError::ReadonlyDataModified),
+            "alice should not be able to change the data len of a read-only account"
+        );
+        assert_eq!(
+            Change::new(&alice_program_id, &alice_program_id)
+                .executable(true, true)
+                .data(vec![0], vec![0, 0])
+                .support_realloc()
+                .verify(),
+            Err(InstructionError::ExecutableDataModified),
+            "alice should not be able to change the data len of an executable account"
+        );
+        assert_eq!(
+            Change::new(&alice_program_id, &alice_program_id)
+                .data(vec![0], vec![0; MAX_PERMITTED_DATA_LENGTH as usize + 1])
+                .support_realloc()
+                .verify(),
+            Err(InstructionError::InvalidRealloc),
+            "account data len should not exceed the maximum permitted length"
+        );
     }
 
     #[test]
@@ -1808,8 +2027,11 @@ mod tests {
             &[],
             &rent_collector,
             None,
+            &mut None,
+            &mut Vec::new(),
             executors.clone(),
             None,
+            None,
             Arc::new(FeatureSet::all_enabled()),
             BpfComputeBudget::new(),
             &mut ExecuteDetailsTimings::default(),
@@ -1836,8 +2058,11 @@ mod tests {
             &[],
             &rent_collector,
             None,
+            &mut None,
+            &mut Vec::new(),
             executors.clone(),
             None,
+            None,
             Arc::new(FeatureSet::all_enabled()),
             BpfComputeBudget::new(),
             &mut ExecuteDetailsTimings::default(),
@@ -1868,8 +2093,11 @@ mod tests {
             &[],
             &rent_collector,
             None,
+            &mut None,
+            &mut Vec::new(),
             executors,
             None,
+            None,
             Arc::new(FeatureSet::all_enabled()),
             BpfComputeBudget::new(),
             &mut ExecuteDetailsTimings::default(),
@@ -1885,6 +2113,95 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_process_message_compute_units() {
+        fn mock_process_instruction(
+            _program_id: &Pubkey,
+            _keyed_accounts: &[KeyedAccount],
+            data: &[u8],
+            invoke_context: &mut dyn InvokeContext,
+        ) -> Result<(), InstructionError> {
+            let units = u64::from(data[0]);
+            invoke_context
+                .get_compute_meter()
+                .borrow_mut()
+                .consume(units)?;
+            if data[1] == 0 {
+                Ok(())
+            } else {
+                Err(InstructionError::Custom(0))
+            }
+        }
+
+        let mock_program_id = solana_sdk::pubkey::new_rand();
+        let rent_collector = RentCollector::default();
+        let mut message_processor = MessageProcessor::default();
+        message_processor.add_program(mock_program_id, mock_process_instruction);
+
+        let accounts = vec![AccountSharedData::new_ref(100, 0, &mock_program_id)];
+        let loaders = vec![
+            vec![(
+                mock_program_id,
+                Rc::new(RefCell::new(create_loadable_account_for_test(
+                    "mock_program",
+                ))),
+            )];
+            3
+        ];
+        let executors = Rc::new(RefCell::new(Executors::default()));
+        let ancestors = Ancestors::default();
+        let from_pubkey = solana_sdk::pubkey::new_rand();
+        let process_message = |instructions: &[Instruction]| {
+            let message = Message::new(instructions, Some(&from_pubkey));
+            let mut instruction_compute_units = vec![];
+            let result = message_processor.process_message(
+                &message,
+                &loaders,
+                &accounts,
+                &[],
+                &rent_collector,
+                None,
+                &mut None,
+                &mut instruction_compute_units,
+                executors.clone(),
+                None,
+                None,
+                Arc::new(FeatureSet::all_enabled()),
+                BpfComputeBudget::new(),
+                &mut ExecuteDetailsTimings::default(),
+                Arc::new(Accounts::default()),
+                &ancestors,
+            );
+            (result, instruction_compute_units)
+        };
+        let account_metas = vec![AccountMeta::new(from_pubkey, true)];
+
+        // Each top-level instruction is metered separately
+        let (result, instruction_compute_units) = process_message(&[
+            Instruction::new_with_bytes(mock_program_id, &[100, 0], account_metas.clone()),
+            Instruction::new_with_bytes(mock_program_id, &[50, 0], account_metas.clone()),
+            Instruction::new_with_bytes(mock_program_id, &[0, 0], account_metas.clone()),
+        ]);
+        assert_eq!(result, Ok(()));
+        assert_eq!(instruction_compute_units, vec![100, 50, 0]);
+
+        // Units consumed by a failing instruction are recorded, later
+        // instructions are not executed
+        let (result, instruction_compute_units) = process_message(&[
+            Instruction::new_with_bytes(mock_program_id, &[100, 0], account_metas.clone()),
+            Instruction::new_with_bytes(mock_program_id, &[50, 1], account_metas.clone()),
+            Instruction::new_with_bytes(mock_program_id, &[10, 0], account_metas),
+        ]);
+        assert_eq!(
+            result,
+            Err(TransactionError::InstructionError(
+                1,
+                InstructionError::Custom(0)
+            ))
+        );
+        assert_eq!(instruction_compute_units, vec![100, 50]);
+    }
+
     #[test]
     fn test_process_message_duplicate_accounts() {
         #[derive(Serialize, Deserialize)]
@@ -1987,8 +2304,11 @@ mod tests {
             &[],
             &rent_collector,
             None,
+            &mut None,
+            &mut Vec::new(),
             executors.clone(),
             None,
+            None,
             Arc::new(FeatureSet::all_enabled()),
             BpfComputeBudget::new(),
             &mut ExecuteDetailsTimings::default(),
@@ -2019,8 +2339,11 @@ mod tests {
             &[],
             &rent_collector,
             None,
+            &mut None,
+            &mut Vec::new(),
             executors.clone(),
             None,
+            None,
             Arc::new(FeatureSet::all_enabled()),
             BpfComputeBudget::new(),
             &mut ExecuteDetailsTimings::default(),
@@ -2049,8 +2372,11 @@ mod tests {
             &[],
             &rent_collector,
             None,
+            &mut None,
+            &mut Vec::new(),
             executors,
             None,
+            None,
             Arc::new(FeatureSet::all_enabled()),
             BpfComputeBudget::new(),
             &mut ExecuteDetailsTimings::default(),
@@ -2145,6 +2471,7 @@ mod tests {
             BpfComputeBudget::default(),
             Rc::new(RefCell::new(Executors::default())),
             None,
+            None,
             Arc::new(FeatureSet::all_enabled()),
             Arc::new(Accounts::default()),
             &ancestors,
@@ -0,0 +1,183 @@
+use solana_sdk::{process_instruction::ProgramTrace, signature::Signature};
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, HashSet},
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    path::Path,
+    rc::Rc,
+};
+
+/// Records the instruction traces of the BPF programs invoked by a transaction
+#[derive(Clone, Default)]
+pub struct ProgramTraceRecorder {
+    inner: Rc<RefCell<Vec<ProgramTrace>>>,
+}
+
+impl ProgramTraceRecorder {
+    pub fn record_trace(&self, trace: ProgramTrace) {
+        self.inner.borrow_mut().push(trace);
+    }
+
+    pub fn take_traces(&self) -> Vec<ProgramTrace> {
+        self.inner.take()
+    }
+}
+
+/// Selects the transactions whose BPF programs are traced
+#[derive(Clone, Debug, PartialEq)]
+pub enum ProgramTraceFilter {
+    None,
+    All,
+    OnlySignatures(HashSet<Signature>),
+}
+
+impl Default for ProgramTraceFilter {
+    fn default() -> Self {
+        Self::None
+    }
+}
+
+impl ProgramTraceFilter {
+    pub fn is_enabled(&self) -> bool {
+        *self != Self::None
+    }
+
+    pub fn matches(&self, signature: &Signature) -> bool {
+        match self {
+            Self::None => false,
+            Self::All => true,
+            Self::OnlySignatures(signatures) => signatures.contains(signature),
+        }
+    }
+}
+
+/// The BPF program traces of a single transaction, in the order the
+/// invocations completed
+#[derive(Clone, Debug, PartialEq)]
+pub struct TransactionProgramTraces {
+    pub signature: Signature,
+    pub traces: Vec<ProgramTrace>,
+}
+
+impl TransactionProgramTraces {
+    /// Compute units consumed by each call stack across all traced programs.
+    /// Every stack is rooted at the id of the program it executed in
+    pub fn folded_stacks(&self) -> BTreeMap<String, u64> {
+        let mut folded_stacks = BTreeMap::new();
+        for trace in &self.traces {
+            for (stack, units) in &trace.folded_stacks {
+                *folded_stacks
+                    .entry(format!("{};{}", trace.program_id, stack))
+                    .or_default() += units;
+            }
+        }
+        folded_stacks
+    }
+
+    /// Write the instruction trace of every program invocation to
+    /// `<signature>.<n>.trace` and the folded-stack compute unit profile,
+    /// suitable as flamegraph input, to `<signature>.folded` in `dir`
+    pub fn write_to_dir(&self, dir: &Path) -> io::Result<()> {
+        fs::create_dir_all(dir)?;
+        for (index, trace) in self.traces.iter().enumerate() {
+            let path = dir.join(format!("{}.{}.trace", self.signature, index));
+            let mut file = BufWriter::new(File::create(path)?);
+            writeln!(
+                file,
+                "Program {} invoke [{}]",
+                trace.program_id, trace.invoke_depth
+            )?;
+            for instruction in &trace.instructions {
+                writeln!(file, "{}", instruction)?;
+            }
+            file.flush()?;
+        }
+        let path = dir.join(format!("{}.folded", self.signature));
+        let mut file = BufWriter::new(File::create(path)?);
+        for (stack, units) in self.folded_stacks() {
+            writeln!(file, "{} {}", stack, units)?;
+        }
+        file.flush()
+    }
+}
+
+/// Collects the BPF program traces of the transactions matching `filter`
+#[derive(Debug, Default)]
+pub struct ProgramTraceCollector {
+    pub filter: ProgramTraceFilter,
+    pub traces: Vec<TransactionProgramTraces>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::pubkey::Pubkey;
+
+    #[test]
+    fn test_program_trace_filter() {
+        let signature = Signature::new(&[1; 64]);
+        let other_signature = Signature::new(&[2; 64]);
+
+        assert!(!ProgramTraceFilter::None.is_enabled());
+        assert!(!ProgramTraceFilter::None.matches(&signature));
+        assert!(ProgramTraceFilter::All.matches(&signature));
+
+        let filter = ProgramTraceFilter::OnlySignatures(vec![signature].into_iter().collect());
+        assert!(filter.is_enabled());
+        assert!(filter.matches(&signature));
+        assert!(!filter.matches(&other_signature));
+    }
+
+    #[test]
+    fn test_transaction_program_traces() {
+        let program_id = Pubkey::new_unique();
+        let invoked_program_id = Pubkey::new_unique();
+        let transaction_traces = TransactionProgramTraces {
+            signature: Signature::new(&[1; 64]),
+            traces: vec![
+                ProgramTrace {
+                    program_id,
+                    invoke_depth: 1,
+                    instructions: vec!["a".to_string(), "b".to_string()],
+                    folded_stacks: vec![("entrypoint".to_string(), 2)],
+                },
+                ProgramTrace {
+                    program_id: invoked_program_id,
+                    invoke_depth: 2,
+                    instructions: vec!["c".to_string()],
+                    folded_stacks: vec![("entrypoint".to_string(), 1)],
+                },
+                ProgramTrace {
+                    program_id,
+                    invoke_depth: 1,
+                    instructions: vec!["d".to_string(), "e".to_string(), "f".to_string()],
+                    folded_stacks: vec![
+                        ("entrypoint".to_string(), 1),
+                        ("entrypoint;process".to_string(), 2),
+                    ],
+                },
+            ],
+        };
+
+        let folded_stacks: Vec<_> = transaction_traces.folded_stacks().into_iter().collect();
+        let mut expected = vec![
+            (format!("{};entrypoint", program_id), 3),
+            (format!("{};entrypoint;process", program_id), 2),
+            (format!("{};entrypoint", invoked_program_id), 1),
+        ];
+        expected.sort();
+        assert_eq!(folded_stacks, expected);
+
+        let dir = tempfile::TempDir::new().unwrap();
+        transaction_traces.write_to_dir(dir.path()).unwrap();
+        let signature = transaction_traces.signature;
+        assert_eq!(
+            fs::read_to_string(dir.path().join(format!("{}.1.trace", signature))).unwrap(),
+            format!("Program {} invoke [2]\nc\n", invoked_program_id)
+        );
+        let folded = fs::read_to_string(dir.path().join(format!("{}.folded", signature))).unwrap();
+        assert_eq!(folded.lines().count(), 3);
+        assert!(folded.contains(&format!("{};entrypoint;process 2\n", program_id)));
+    }
+}
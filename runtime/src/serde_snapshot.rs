@@ -4,7 +4,7 @@ use {
         accounts_db::{AccountStorageEntry, AccountsDb, AppendVecId, BankHashInfo},
         accounts_index::{AccountIndex, Ancestors},
         append_vec::AppendVec,
-        bank::{Bank, BankFieldsToDeserialize, BankRc, Builtins},
+        bank::{Bank, BankFieldsToDeserialize, BankRc, Builtins, EpochRewardStatus},
         blockhash_queue::BlockhashQueue,
         epoch_stakes::EpochStakes,
         hardened_unpack::UnpackedAppendVecMap,
@@ -59,6 +59,8 @@ pub(crate) use crate::accounts_db::{SnapshotStorage, SnapshotStorages};
 #[derive(Copy, Clone, Eq, PartialEq)]
 pub(crate) enum SerdeStyle {
     Newer,
+    // Newer, followed by the bank's epoch reward status
+    NewerWithEpochRewardStatus,
 }
 
 const MAX_STREAM_SIZE: u64 = 32 * 1024 * 1024 * 1024;
@@ -134,10 +136,10 @@ where
     R: Read,
 {
     macro_rules! INTO {
-        ($x:ident) => {{
+        ($x:ident, $with_epoch_reward_status:expr) => {{
             let (bank_fields, accounts_db_fields) = $x::deserialize_bank_fields(stream)?;
 
-            let bank = reconstruct_bank_from_fields(
+            let mut bank = reconstruct_bank_from_fields(
                 bank_fields,
                 accounts_db_fields,
                 genesis_config,
@@ -149,11 +151,15 @@ where
                 account_indexes,
                 caching_enabled,
             )?;
+            if $with_epoch_reward_status {
+                bank.epoch_reward_status = deserialize_from::<_, EpochRewardStatus>(&mut *stream)?;
+            }
             Ok(bank)
         }};
     }
     match serde_style {
-        SerdeStyle::Newer => INTO!(TypeContextFuture),
+        SerdeStyle::Newer => INTO!(TypeContextFuture, false),
+        SerdeStyle::NewerWithEpochRewardStatus => INTO!(TypeContextFuture, true),
     }
     .map_err(|err| {
         warn!("bankrc_from_stream error: {:?}", err);
@@ -173,7 +179,7 @@ where
     macro_rules! INTO {
         ($x:ident) => {
             bincode::serialize_into(
-                stream,
+                stream.by_ref(),
                 &SerializableBankAndStorage::<$x> {
                     bank,
                     snapshot_storages,
@@ -183,7 +189,17 @@ where
         };
     }
     match serde_style {
-        SerdeStyle::Newer => INTO!(TypeContextFuture),
+        SerdeStyle::Newer => {
+            // the rewards still to be distributed would be lost on load
+            if bank.epoch_reward_status != EpochRewardStatus::Inactive {
+                return Err(Box::new(bincode::ErrorKind::Custom(
+                    "epoch reward status can't be stored in this snapshot version".to_string(),
+                )));
+            }
+            INTO!(TypeContextFuture)
+        }
+        SerdeStyle::NewerWithEpochRewardStatus => INTO!(TypeContextFuture)
+            .and_then(|_| bincode::serialize_into(stream, &bank.epoch_reward_status)),
     }
     .map_err(|err| {
         warn!("bankrc_to_stream error: {:?}", err);
@@ -4,7 +4,7 @@ use {
     crate::{
         accounts::{create_test_accounts, Accounts},
         accounts_db::get_temp_accounts_paths,
-        bank::{Bank, StatusCacheRc},
+        bank::{Bank, EpochRewardStatus, StakeReward, StatusCacheRc},
         hardened_unpack::UnpackedAppendVecMap,
     },
     bincode::serialize_into,
@@ -16,6 +16,7 @@ use {
         pubkey::Pubkey,
         signature::{Keypair, Signer},
     },
+    solana_stake_program::stake_state::Stake,
     std::{
         io::{BufReader, Cursor},
         path::Path,
@@ -87,11 +88,13 @@ where
     R: Read,
 {
     match serde_style {
-        SerdeStyle::Newer => context_accountsdb_from_stream::<TypeContextFuture, R>(
-            stream,
-            account_paths,
-            unpacked_append_vec_map,
-        ),
+        SerdeStyle::Newer | SerdeStyle::NewerWithEpochRewardStatus => {
+            context_accountsdb_from_stream::<TypeContextFuture, R>(
+                stream,
+                account_paths,
+                unpacked_append_vec_map,
+            )
+        }
     }
 }
 
@@ -107,7 +110,7 @@ where
     W: Write,
 {
     match serde_style {
-        SerdeStyle::Newer => serialize_into(
+        SerdeStyle::Newer | SerdeStyle::NewerWithEpochRewardStatus => serialize_into(
             stream,
             &SerializableAccountsDb::<TypeContextFuture> {
                 accounts_db,
@@ -278,6 +281,77 @@ fn test_bank_serialize_newer() {
     test_bank_serialize_style(SerdeStyle::Newer)
 }
 
+#[test]
+fn test_bank_serialize_newer_with_epoch_reward_status() {
+    test_bank_serialize_style(SerdeStyle::NewerWithEpochRewardStatus)
+}
+
+#[test]
+fn test_bank_serialize_epoch_reward_status() {
+    solana_logger::setup();
+    let (genesis_config, _) = create_genesis_config(500);
+    let bank0 = Arc::new(Bank::new(&genesis_config));
+    let mut bank1 = Bank::new_from_parent(&bank0, &Pubkey::default(), 1);
+    bank1.epoch_reward_status = EpochRewardStatus::Active {
+        distribution_starting_block_height: 2,
+        stake_rewards_by_partition: Arc::new(vec![
+            vec![StakeReward {
+                stake_pubkey: Pubkey::new_unique(),
+                stake: Stake::default(),
+                reward: 42,
+            }],
+            vec![],
+        ]),
+    };
+    bank1.freeze();
+    bank1.squash();
+    bank1.force_flush_accounts_cache();
+
+    let snapshot_storages = bank1.get_snapshot_storages();
+    let serialize_bank = |serde_style, bank: &Bank| {
+        let mut writer = std::io::BufWriter::new(vec![]);
+        crate::serde_snapshot::bank_to_stream(serde_style, &mut writer, bank, &snapshot_storages)
+            .map(|_| writer.into_inner().unwrap())
+    };
+    let deserialize_bank = |serde_style, bank: &Bank, buf: &[u8]| {
+        let (_accounts_dir, dbank_paths) = get_temp_accounts_paths(4).unwrap();
+        let copied_accounts = TempDir::new().unwrap();
+        let unpacked_append_vec_map =
+            copy_append_vecs(&bank.rc.accounts.accounts_db, copied_accounts.path()).unwrap();
+        crate::serde_snapshot::bank_from_stream(
+            serde_style,
+            &mut BufReader::new(buf),
+            &dbank_paths,
+            unpacked_append_vec_map,
+            &genesis_config,
+            &[],
+            None,
+            None,
+            HashSet::new(),
+            false,
+        )
+    };
+
+    let buf = serialize_bank(SerdeStyle::NewerWithEpochRewardStatus, &bank1).unwrap();
+    let dbank = deserialize_bank(SerdeStyle::NewerWithEpochRewardStatus, &bank1, &buf).unwrap();
+    assert_eq!(dbank.epoch_reward_status, bank1.epoch_reward_status);
+
+    // a truncated status is an error, not an inactive one
+    assert!(deserialize_bank(
+        SerdeStyle::NewerWithEpochRewardStatus,
+        &bank1,
+        &buf[..buf.len() - 1]
+    )
+    .is_err());
+
+    // the older style can't carry rewards that are still being distributed
+    assert!(serialize_bank(SerdeStyle::Newer, &bank1).is_err());
+    bank1.epoch_reward_status = EpochRewardStatus::Inactive;
+    let buf = serialize_bank(SerdeStyle::Newer, &bank1).unwrap();
+    let dbank = deserialize_bank(SerdeStyle::Newer, &bank1, &buf).unwrap();
+    assert_eq!(dbank.epoch_reward_status, EpochRewardStatus::Inactive);
+}
+
 #[cfg(all(test, RUSTC_WITH_SPECIALIZATION))]
 mod test_bank_serialize {
     use super::*;
@@ -2,13 +2,15 @@
 use solana_runtime::{
     bank::Bank,
     bank_client::BankClient,
-    genesis_utils::{create_genesis_config_with_leader, GenesisConfigInfo},
+    genesis_utils::{activate_feature, create_genesis_config_with_leader, GenesisConfigInfo},
 };
 use solana_sdk::{
     account::from_account,
     account_utils::StateMut,
     client::SyncClient,
+    feature_set,
     message::Message,
+    native_token::LAMPORTS_PER_SOL,
     pubkey::Pubkey,
     signature::{Keypair, Signer},
     system_instruction::SystemError,
@@ -16,7 +18,7 @@ use solana_sdk::{
     transaction::TransactionError,
 };
 use solana_stake_program::{
-    stake_instruction::{self},
+    stake_instruction::{self, StakeError},
     stake_state::{self, StakeState},
 };
 use solana_vote_program::{
@@ -28,11 +30,21 @@ use std::sync::Arc;
 fn next_epoch(bank: &Arc<Bank>) -> Arc<Bank> {
     bank.squash();
 
-    Arc::new(Bank::new_from_parent(
+    let mut bank = Arc::new(Bank::new_from_parent(
         &bank,
         &Pubkey::default(),
         bank.get_slots_in_epoch(bank.epoch()) + bank.slot(),
-    ))
+    ));
+    // stake accounts are locked until the epoch rewards are fully distributed
+    while bank.epoch_rewards().active {
+        bank.squash();
+        bank = Arc::new(Bank::new_from_parent(
+            &bank,
+            &Pubkey::default(),
+            bank.slot() + 1,
+        ));
+    }
+    bank
 }
 
 fn fill_epoch_with_votes(
@@ -500,6 +512,140 @@ fn test_stake_account_lifetime() {
     assert_eq!(bank.get_balance(&stake_pubkey), lamports - lamports / 2);
 }
 
+#[test]
+fn test_stake_redelegate() {
+    let stake_keypair = Keypair::new();
+    let stake_pubkey = stake_keypair.pubkey();
+
+    let GenesisConfigInfo {
+        genesis_config,
+        mint_keypair,
+        ..
+    } = create_genesis_config_with_leader(
+        100_000_000_000,
+        &solana_sdk::pubkey::new_rand(),
+        1_000_000,
+    );
+    let bank = Bank::new(&genesis_config);
+    let mint_pubkey = mint_keypair.pubkey();
+    let mut bank = Arc::new(bank);
+    let bank_client = BankClient::new_shared(&bank);
+
+    let create_vote_account = || {
+        let vote_keypair = Keypair::new();
+        let identity_keypair = Keypair::new();
+        let message = Message::new(
+            &vote_instruction::create_account(
+                &mint_pubkey,
+                &vote_keypair.pubkey(),
+                &VoteInit {
+                    node_pubkey: identity_keypair.pubkey(),
+                    authorized_voter: vote_keypair.pubkey(),
+                    authorized_withdrawer: vote_keypair.pubkey(),
+                    commission: 50,
+                },
+                10,
+            ),
+            Some(&mint_pubkey),
+        );
+        bank_client
+            .send_and_confirm_message(&[&mint_keypair, &vote_keypair, &identity_keypair], message)
+            .expect("failed to create vote account");
+        vote_keypair.pubkey()
+    };
+    let vote_pubkey = create_vote_account();
+    let new_vote_pubkey = create_vote_account();
+
+    let authorized = stake_state::Authorized::auto(&stake_pubkey);
+    let message = Message::new(
+        &stake_instruction::create_account_and_delegate_stake(
+            &mint_pubkey,
+            &stake_pubkey,
+            &vote_pubkey,
+            &authorized,
+            &stake_state::Lockup::default(),
+            1_000_000,
+        ),
+        Some(&mint_pubkey),
+    );
+    bank_client
+        .send_and_confirm_message(&[&mint_keypair, &stake_keypair], message)
+        .expect("failed to create and delegate stake account");
+
+    let new_stake_keypair = Keypair::new();
+    let new_stake_pubkey = new_stake_keypair.pubkey();
+    let redelegate = |bank: &Arc<Bank>, vote_pubkey: &Pubkey| {
+        let message = Message::new(
+            &stake_instruction::redelegate(
+                &stake_pubkey,
+                &stake_pubkey,
+                vote_pubkey,
+                &new_stake_pubkey,
+            ),
+            Some(&mint_pubkey),
+        );
+        BankClient::new_shared(bank).send_and_confirm_message(
+            &[&mint_keypair, &stake_keypair, &new_stake_keypair],
+            message,
+        )
+    };
+
+    loop {
+        if warmed_up(&bank, &stake_pubkey) {
+            break;
+        }
+        bank = next_epoch(&bank);
+    }
+
+    // neither can stake be redelegated to the vote account it is delegated to
+    assert!(redelegate(&bank, &vote_pubkey).is_err());
+
+    // lamports that aren't staked stay behind
+    BankClient::new_shared(&bank)
+        .transfer_and_confirm(42, &mint_keypair, &stake_pubkey)
+        .unwrap();
+
+    let staked = get_staked(&bank, &stake_pubkey);
+    let lamports = bank.get_balance(&stake_pubkey);
+    assert!(redelegate(&bank, &new_vote_pubkey).is_ok());
+
+    // the stake is moved, but stays effective with the old voter for the rest of the epoch
+    assert_eq!(bank.get_balance(&stake_pubkey), lamports - staked);
+    assert_eq!(bank.get_balance(&new_stake_pubkey), staked);
+    assert_eq!(get_staked(&bank, &stake_pubkey), staked);
+    assert_eq!(get_staked(&bank, &new_stake_pubkey), 0);
+    let new_stake = StakeState::stake_from(&bank.get_account(&new_stake_pubkey).unwrap()).unwrap();
+    assert_eq!(new_stake.delegation.voter_pubkey, new_vote_pubkey);
+    assert_eq!(new_stake.delegation.activation_epoch, bank.epoch());
+
+    // the old stake cools down while the new one warms up
+    loop {
+        if warmed_up(&bank, &new_stake_pubkey) {
+            break;
+        }
+        bank = next_epoch(&bank);
+    }
+    assert_eq!(get_staked(&bank, &stake_pubkey), 0);
+    assert_eq!(get_staked(&bank, &new_stake_pubkey), staked);
+
+    // and whatever is left in the old stake account can be withdrawn
+    let lamports = bank.get_balance(&stake_pubkey);
+    let message = Message::new(
+        &[stake_instruction::withdraw(
+            &stake_pubkey,
+            &stake_pubkey,
+            &solana_sdk::pubkey::new_rand(),
+            lamports,
+            None,
+        )],
+        Some(&mint_pubkey),
+    );
+    assert!(BankClient::new_shared(&bank)
+        .send_and_confirm_message(&[&mint_keypair, &stake_keypair], message)
+        .is_ok());
+    assert_eq!(bank.get_balance(&stake_pubkey), 0);
+}
+
 #[test]
 fn test_create_stake_account_from_seed() {
     let vote_keypair = Keypair::new();
@@ -572,3 +718,93 @@ fn test_create_stake_account_from_seed() {
         panic!("wrong account type found")
     }
 }
+
+#[test]
+fn test_stake_minimum_delegation() {
+    let vote_keypair = Keypair::new();
+    let vote_pubkey = vote_keypair.pubkey();
+    let identity_keypair = Keypair::new();
+    let identity_pubkey = identity_keypair.pubkey();
+
+    let GenesisConfigInfo {
+        mut genesis_config,
+        mint_keypair,
+        ..
+    } = create_genesis_config_with_leader(
+        100 * LAMPORTS_PER_SOL,
+        &solana_sdk::pubkey::new_rand(),
+        1_000_000,
+    );
+    activate_feature(
+        &mut genesis_config,
+        feature_set::stake_raise_minimum_delegation_to_1_sol::id(),
+    );
+    let bank = Arc::new(Bank::new(&genesis_config));
+    let mint_pubkey = mint_keypair.pubkey();
+    let bank_client = BankClient::new_shared(&bank);
+
+    let message = Message::new(
+        &vote_instruction::create_account(
+            &mint_pubkey,
+            &vote_pubkey,
+            &VoteInit {
+                node_pubkey: identity_pubkey,
+                authorized_voter: vote_pubkey,
+                authorized_withdrawer: vote_pubkey,
+                commission: 50,
+            },
+            10,
+        ),
+        Some(&mint_pubkey),
+    );
+    bank_client
+        .send_and_confirm_message(&[&mint_keypair, &vote_keypair, &identity_keypair], message)
+        .expect("failed to create vote account");
+
+    let minimum_delegation = solana_stake_program::get_minimum_delegation(|feature_id| {
+        bank.feature_set.is_active(feature_id)
+    });
+    assert_eq!(minimum_delegation, LAMPORTS_PER_SOL);
+    let rent_exempt_reserve =
+        bank.get_minimum_balance_for_rent_exemption(std::mem::size_of::<StakeState>());
+
+    // Delegating less than the minimum fails
+    let stake_keypair = Keypair::new();
+    let stake_pubkey = stake_keypair.pubkey();
+    let message = Message::new(
+        &stake_instruction::create_account_and_delegate_stake(
+            &mint_pubkey,
+            &stake_pubkey,
+            &vote_pubkey,
+            &stake_state::Authorized::auto(&stake_pubkey),
+            &stake_state::Lockup::default(),
+            rent_exempt_reserve + minimum_delegation - 1,
+        ),
+        Some(&mint_pubkey),
+    );
+    assert_eq!(
+        bank_client
+            .send_and_confirm_message(&[&mint_keypair, &stake_keypair], message)
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(2, StakeError::InsufficientDelegation.into())
+    );
+
+    // Delegating the minimum succeeds
+    let message = Message::new(
+        &stake_instruction::create_account_and_delegate_stake(
+            &mint_pubkey,
+            &stake_pubkey,
+            &vote_pubkey,
+            &stake_state::Authorized::auto(&stake_pubkey),
+            &stake_state::Lockup::default(),
+            rent_exempt_reserve + minimum_delegation,
+        ),
+        Some(&mint_pubkey),
+    );
+    bank_client
+        .send_and_confirm_message(&[&mint_keypair, &stake_keypair], message)
+        .expect("failed to create and delegate stake account");
+    let stake = StakeState::stake_from(&bank.get_account(&stake_pubkey).unwrap()).unwrap();
+    assert_eq!(stake.delegation.stake, minimum_delegation);
+}
@@ -12,6 +12,7 @@ INC_DIRS ?=
 SRC_DIR ?= ./src
 TEST_PREFIX ?= test_
 OUT_DIR ?= ./out
+SOL_MEM_SYSCALLS ?=
 OS := $(shell uname)
 
 LLVM_DIR = $(LOCAL_PATH)../dependencies/bpf-tools/llvm
@@ -34,7 +35,8 @@ C_FLAGS := \
   -fno-builtin \
   -std=c17 \
   $(addprefix -isystem,$(SYSTEM_INC_DIRS)) \
-  $(addprefix -I,$(INC_DIRS))
+  $(addprefix -I,$(INC_DIRS)) \
+  $(if $(SOL_MEM_SYSCALLS),-DSOL_MEM_SYSCALLS)
 
 CXX_FLAGS := \
   $(C_FLAGS) \
@@ -114,6 +116,8 @@ help:
 	@echo '      SRC_DIR=$(SRC_DIR)'
 	@echo '    - Location to place output files:'
 	@echo '      OUT_DIR=$(OUT_DIR)'
+	@echo '    - Use the runtime memory operation syscalls for memcpy, memmove, memcmp and memset:'
+	@echo '      SOL_MEM_SYSCALLS=$(SOL_MEM_SYSCALLS)'
 	@echo '    - Location of LLVM:'
 	@echo '      LLVM_DIR=$(LLVM_DIR)'
 	@echo ''
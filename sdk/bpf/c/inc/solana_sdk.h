@@ -182,6 +182,51 @@ typedef struct {
   bool executable;     /** This account's data contains a loaded program (and is now read-only) */
 } SolAccountInfo;
 
+#if defined(SOL_MEM_SYSCALLS) && !defined(SOL_TEST)
+/**
+ * Memory operation syscalls
+ *
+ * Define SOL_MEM_SYSCALLS before including this header to have the memory
+ * helpers below call into the runtime instead of looping over bytes in BPF.
+ * Programs built this way can only be deployed once the runtime has enabled
+ * the memory operation syscalls.
+ */
+void sol_memcpy_(void *dst, const void *src, uint64_t n);
+void sol_memmove_(void *dst, const void *src, uint64_t n);
+void sol_memcmp_(const void *s1, const void *s2, uint64_t n, int *result);
+void sol_memset_(void *s, uint8_t c, uint64_t n);
+
+/**
+ * Copies memory, the source and destination must not overlap
+ */
+static void sol_memcpy(void *dst, const void *src, int len) {
+  sol_memcpy_(dst, src, len);
+}
+
+/**
+ * Copies memory, the source and destination may overlap
+ */
+static void sol_memmove(void *dst, const void *src, int len) {
+  sol_memmove_(dst, src, len);
+}
+
+/**
+ * Compares memory
+ */
+static int sol_memcmp(const void *s1, const void *s2, int n) {
+  int result;
+  sol_memcmp_(s1, s2, n, &result);
+  return result;
+}
+
+/**
+ * Fill a byte string with a byte value
+ */
+static void *sol_memset(void *b, int c, size_t len) {
+  sol_memset_(b, c, len);
+  return b;
+}
+#else
 /**
  * Copies memory
  */
@@ -191,12 +236,25 @@ static void sol_memcpy(void *dst, const void *src, int len) {
   }
 }
 
+/**
+ * Copies memory, the source and destination may overlap
+ */
+static void sol_memmove(void *dst, const void *src, int len) {
+  if ((uint8_t *)dst <= (const uint8_t *)src) {
+    sol_memcpy(dst, src, len);
+  } else {
+    for (int i = len - 1; i >= 0; i--) {
+      *((uint8_t *)dst + i) = *((const uint8_t *)src + i);
+    }
+  }
+}
+
 /**
  * Compares memory
  */
 static int sol_memcmp(const void *s1, const void *s2, int n) {
   for (int i = 0; i < n; i++) {
-    uint8_t diff = *((uint8_t *)s1 + i) - *((const uint8_t *)s2 + i);
+    int diff = (int)*((const uint8_t *)s1 + i) - (int)*((const uint8_t *)s2 + i);
     if (diff) {
       return diff;
     }
@@ -215,6 +273,7 @@ static void *sol_memset(void *b, int c, size_t len) {
     len--;
   }
 }
+#endif
 
 /**
  * Find length of string
@@ -437,6 +496,76 @@ static uint64_t sol_sha256(
     const uint8_t *result
 );
 
+/**
+ * Length of a Keccak256 hash result
+ */
+#define KECCAK_RESULT_LENGTH 32
+
+/**
+ * Keccak256
+ *
+ * @param bytes Array of byte arrays
+ * @param bytes_len Number of byte arrays
+ * @param result 32 byte array to hold the result
+ */
+static uint64_t sol_keccak256(
+    const SolBytes *bytes,
+    int bytes_len,
+    const uint8_t *result
+);
+
+/**
+ * Length of a Blake3 hash result
+ */
+#define BLAKE3_RESULT_LENGTH 32
+
+/**
+ * Blake3
+ *
+ * @param bytes Array of byte arrays
+ * @param bytes_len Number of byte arrays
+ * @param result 32 byte array to hold the result
+ */
+static uint64_t sol_blake3(
+    const SolBytes *bytes,
+    int bytes_len,
+    const uint8_t *result
+);
+
+/**
+ * Length of a secp256k1 recovered public key
+ */
+#define SECP256K1_RECOVER_RESULT_LENGTH 64
+
+/** Length of a secp256k1 hash */
+#define SECP256K1_RECOVER_HASH_LENGTH 32
+
+/** Length of a secp256k1 signature */
+#define SECP256K1_RECOVER_SIGNATURE_LENGTH 64
+
+/** The hash provided to sol_secp256k1_recover is invalid */
+#define SECP256K1_RECOVER_ERROR_INVALID_HASH 1
+/** The recovery_id provided to sol_secp256k1_recover is invalid */
+#define SECP256K1_RECOVER_ERROR_INVALID_RECOVERY_ID 2
+/** The signature provided to sol_secp256k1_recover is invalid */
+#define SECP256K1_RECOVER_ERROR_INVALID_SIGNATURE 3
+
+/**
+ * Recover the public key that signed a 32 byte hash
+ *
+ * @param hash 32 byte hash that was signed
+ * @param recovery_id Recovery id, between 0 and 3
+ * @param signature 64 byte signature
+ * @param result 64 byte array to hold the recovered public key
+ * @return 0 on success, or one of the SECP256K1_RECOVER_ERROR_* codes
+ */
+static uint64_t sol_secp256k1_recover(
+    const uint8_t *hash,
+    uint64_t recovery_id,
+    const uint8_t *signature,
+    uint8_t *result
+);
+
 /**
  * Account Meta
  */
@@ -567,6 +696,38 @@ static uint64_t sol_invoke(
 
 /**@}*/
 
+/**
+ * Return data
+ * @{
+ */
+
+/**
+ * Maximum size of return data
+ */
+#define MAX_RETURN_DATA 1024
+
+/**
+ * Set the return data of the running program
+ *
+ * Return data is cleared before every cross-program invocation
+ *
+ * @param bytes Return data to set
+ * @param bytes_len Length of the return data, at most MAX_RETURN_DATA
+ */
+void sol_set_return_data(const uint8_t *bytes, uint64_t bytes_len);
+
+/**
+ * Get the return data set by the last invoked program
+ *
+ * @param bytes Buffer to copy the return data into
+ * @param bytes_len Length of the buffer
+ * @param program_id Set to the program id of the program that set the return data
+ * @return Length of the return data, 0 if no return data was set
+ */
+uint64_t sol_get_return_data(uint8_t *bytes, uint64_t bytes_len, SolPubkey *program_id);
+
+/**@}*/
+
 /**
  * Debugging utilities
  * @{
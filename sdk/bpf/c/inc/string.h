@@ -2,6 +2,7 @@
 #include <solana_sdk.h>
 
 #define memcpy sol_memcpy
+#define memmove sol_memmove
 #define memset sol_memset
 #define strlen sol_strlen
 
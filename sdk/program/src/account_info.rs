@@ -1,4 +1,7 @@
-use crate::{clock::Epoch, program_error::ProgramError, pubkey::Pubkey};
+use crate::{
+    clock::Epoch, entrypoint::MAX_PERMITTED_DATA_INCREASE, program_error::ProgramError,
+    pubkey::Pubkey,
+};
 use std::{
     cell::{Ref, RefCell, RefMut},
     cmp, fmt,
@@ -114,6 +117,53 @@ impl<'a> AccountInfo<'a> {
             .map_err(|_| ProgramError::AccountBorrowFailed)
     }
 
+    /// Length of the account's data at the start of the instruction, as
+    /// recorded by the entrypoint in the padding ahead of the account's key
+    fn original_data_len(&self) -> usize {
+        let key_ptr = self.key as *const _ as *const u8;
+        unsafe { *(key_ptr.offset(-4) as *const u32) as usize }
+    }
+
+    /// Resize the account's data in place
+    ///
+    /// Only valid for accounts handed to the program by its entrypoint: the
+    /// new length is also written to the serialized input buffer, which is
+    /// where the runtime picks it up once the instruction completes, and the
+    /// data may only grow into the `MAX_PERMITTED_DATA_INCREASE` bytes the
+    /// runtime reserves after it.  Growing beyond that fails with
+    /// `InvalidRealloc`.  Bytes past the current length are only zeroed if
+    /// `zero_init` is set.
+    pub fn realloc(&self, new_len: usize, zero_init: bool) -> Result<(), ProgramError> {
+        let mut data = self.try_borrow_mut_data()?;
+        let orig_len = data.len();
+
+        if new_len
+            > self
+                .original_data_len()
+                .saturating_add(MAX_PERMITTED_DATA_INCREASE)
+        {
+            return Err(ProgramError::InvalidRealloc);
+        }
+
+        unsafe {
+            // First set the new length in the serialized input buffer
+            #[allow(clippy::cast_ptr_alignment)]
+            let serialized_len_ptr = data.as_mut_ptr().offset(-8) as *mut u64;
+            *serialized_len_ptr = new_len as u64;
+
+            // Then point the local slice at the new length
+            *data = std::slice::from_raw_parts_mut(data.as_mut_ptr(), new_len);
+        }
+
+        if zero_init && new_len > orig_len {
+            for byte in data[orig_len..].iter_mut() {
+                *byte = 0;
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn new(
         key: &'a Pubkey,
         is_signer: bool,
@@ -260,4 +310,70 @@ mod tests {
         assert_eq!(k4, *info2_3_4[2].key);
         assert_eq!(k5, *info5.key);
     }
+
+    #[test]
+    fn test_realloc() {
+        // Lay out an aligned input buffer holding a single account with 4
+        // bytes of data, as the runtime serializes it for the entrypoint
+        const DATA_LEN_OFFSET: usize = 88;
+        let mut input = vec![];
+        input.extend_from_slice(&1u64.to_le_bytes()); // number of accounts
+        input.extend_from_slice(&[std::u8::MAX, 0, 1, 0]); // not a dup, writable
+        input.extend_from_slice(&[0; 4]); // padding
+        input.extend_from_slice(Pubkey::new_unique().as_ref()); // key
+        input.extend_from_slice(Pubkey::new_unique().as_ref()); // owner
+        input.extend_from_slice(&0u64.to_le_bytes()); // lamports
+        assert_eq!(input.len(), DATA_LEN_OFFSET);
+        input.extend_from_slice(&4u64.to_le_bytes()); // data length
+        input.extend_from_slice(&[0xff; 4]); // data
+        input.resize(input.len() + MAX_PERMITTED_DATA_INCREASE, 0xff);
+        input.resize((input.len() + 15) / 16 * 16, 0); // padding to u128
+        input.extend_from_slice(&0u64.to_le_bytes()); // rent epoch
+        input.extend_from_slice(&0u64.to_le_bytes()); // instruction data length
+        input.extend_from_slice(Pubkey::new_unique().as_ref()); // program id
+
+        // Back the buffer with u128s so it's aligned like the runtime's
+        let mut aligned = vec![0u128; (input.len() + 15) / 16];
+        let buffer = aligned.as_mut_ptr() as *mut u8;
+        unsafe { std::ptr::copy_nonoverlapping(input.as_ptr(), buffer, input.len()) };
+        let serialized_data_len =
+            || unsafe { std::ptr::read_unaligned(buffer.add(DATA_LEN_OFFSET) as *const u64) };
+
+        let (_program_id, accounts, _instruction_data) =
+            unsafe { crate::entrypoint::deserialize(buffer) };
+        let info = &accounts[0];
+
+        info.realloc(12, true).unwrap();
+        assert_eq!(info.data_len(), 12);
+        assert_eq!(&info.data.borrow()[..4], &[0xff; 4]);
+        assert_eq!(&info.data.borrow()[4..], &[0; 8]);
+        assert_eq!(serialized_data_len(), 12);
+
+        info.realloc(14, false).unwrap();
+        assert_eq!(info.data_len(), 14);
+        assert_eq!(&info.data.borrow()[12..], &[0xff; 2]);
+
+        info.realloc(2, true).unwrap();
+        assert_eq!(info.data_len(), 2);
+        assert_eq!(serialized_data_len(), 2);
+
+        // Growth is bounded by the length at the start of the instruction
+        info.realloc(4 + MAX_PERMITTED_DATA_INCREASE, false)
+            .unwrap();
+        assert_eq!(
+            info.realloc(4 + MAX_PERMITTED_DATA_INCREASE + 1, false),
+            Err(ProgramError::InvalidRealloc)
+        );
+        assert_eq!(info.data_len(), 4 + MAX_PERMITTED_DATA_INCREASE);
+        assert_eq!(
+            serialized_data_len(),
+            (4 + MAX_PERMITTED_DATA_INCREASE) as u64
+        );
+
+        let _borrowed = info.try_borrow_data().unwrap();
+        assert_eq!(
+            info.realloc(4, true),
+            Err(ProgramError::AccountBorrowFailed)
+        );
+    }
 }
@@ -0,0 +1,55 @@
+//! The `blake3` module provides functions for creating BLAKE3 hashes.
+
+use crate::hash::Hash;
+
+/// Return a BLAKE3 hash for the given data.
+pub fn hashv(vals: &[&[u8]]) -> Hash {
+    // Perform the calculation inline, calling this from within a program is
+    // not supported
+    #[cfg(not(target_arch = "bpf"))]
+    {
+        Hash::new_from_array(crate::program_stubs::sol_blake3(vals))
+    }
+    // Call via a system call to perform the calculation
+    #[cfg(target_arch = "bpf")]
+    {
+        extern "C" {
+            fn sol_blake3(vals: *const u8, val_len: u64, hash_result: *mut u8) -> u64;
+        };
+        let mut hash_result = [0; crate::hash::HASH_BYTES];
+        unsafe {
+            sol_blake3(
+                vals as *const _ as *const u8,
+                vals.len() as u64,
+                &mut hash_result as *mut _ as *mut u8,
+            );
+        }
+        Hash::new_from_array(hash_result)
+    }
+}
+
+/// Return a BLAKE3 hash for the given data.
+pub fn hash(val: &[u8]) -> Hash {
+    hashv(&[val])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blake3() {
+        // Test vector from the BLAKE3 reference implementation
+        assert_eq!(
+            hash(&[]),
+            Hash::new(
+                &hex::decode("af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262")
+                    .unwrap()
+            )
+        );
+        assert_eq!(
+            hashv(&[b"Gaggablaghblagh!", b"flurbos"]),
+            hash(b"Gaggablaghblagh!flurbos")
+        );
+    }
+}
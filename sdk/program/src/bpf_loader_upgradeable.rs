@@ -241,6 +241,28 @@ pub fn close(
     Instruction::new_with_bincode(id(), &UpgradeableLoaderInstruction::Close, metas)
 }
 
+/// Returns the instruction required to extend the size of a program's
+/// ProgramData account
+pub fn extend_program_data(
+    program_address: &Pubkey,
+    authority_address: &Pubkey,
+    payer_address: &Pubkey,
+    additional_bytes: u32,
+) -> Instruction {
+    let (programdata_address, _) = Pubkey::find_program_address(&[program_address.as_ref()], &id());
+    Instruction::new_with_bincode(
+        id(),
+        &UpgradeableLoaderInstruction::ExtendProgramData { additional_bytes },
+        vec![
+            AccountMeta::new(programdata_address, false),
+            AccountMeta::new_readonly(*authority_address, true),
+            AccountMeta::new(*payer_address, true),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(crate::system_program::id(), false),
+        ],
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
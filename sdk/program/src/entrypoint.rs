@@ -171,6 +171,7 @@ pub unsafe fn deserialize<'a>(input: *mut u8) -> (&'a Pubkey, Vec<AccountInfo<'a
             let executable = *(input.add(offset) as *const u8) != 0;
             offset += size_of::<u8>();
 
+            let original_data_len_offset = offset;
             offset += size_of::<u32>(); // padding to u64
 
             let key: &Pubkey = &*(input.add(offset) as *const Pubkey);
@@ -187,6 +188,13 @@ pub unsafe fn deserialize<'a>(input: *mut u8) -> (&'a Pubkey, Vec<AccountInfo<'a
             let data_len = *(input.add(offset) as *const u64) as usize;
             offset += size_of::<u64>();
 
+            // Stash the original length in the padding, `AccountInfo::realloc`
+            // bounds the new length by it
+            #[allow(clippy::cast_ptr_alignment)]
+            {
+                *(input.add(original_data_len_offset) as *mut u32) = data_len as u32;
+            }
+
             let data = Rc::new(RefCell::new({
                 from_raw_parts_mut(input.add(offset), data_len)
             }));
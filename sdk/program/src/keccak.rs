@@ -0,0 +1,55 @@
+//! The `keccak` module provides functions for creating Keccak-256 hashes.
+
+use crate::hash::Hash;
+
+/// Return a Keccak-256 hash for the given data.
+pub fn hashv(vals: &[&[u8]]) -> Hash {
+    // Perform the calculation inline, calling this from within a program is
+    // not supported
+    #[cfg(not(target_arch = "bpf"))]
+    {
+        Hash::new_from_array(crate::program_stubs::sol_keccak256(vals))
+    }
+    // Call via a system call to perform the calculation
+    #[cfg(target_arch = "bpf")]
+    {
+        extern "C" {
+            fn sol_keccak256(vals: *const u8, val_len: u64, hash_result: *mut u8) -> u64;
+        };
+        let mut hash_result = [0; crate::hash::HASH_BYTES];
+        unsafe {
+            sol_keccak256(
+                vals as *const _ as *const u8,
+                vals.len() as u64,
+                &mut hash_result as *mut _ as *mut u8,
+            );
+        }
+        Hash::new_from_array(hash_result)
+    }
+}
+
+/// Return a Keccak-256 hash for the given data.
+pub fn hash(val: &[u8]) -> Hash {
+    hashv(&[val])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keccak256() {
+        // Ethereum's empty code hash
+        assert_eq!(
+            hash(&[]),
+            Hash::new(
+                &hex::decode("c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470")
+                    .unwrap()
+            )
+        );
+        assert_eq!(
+            hashv(&[b"Gaggablaghblagh!", b"flurbos"]),
+            hash(b"Gaggablaghblagh!flurbos")
+        );
+    }
+}
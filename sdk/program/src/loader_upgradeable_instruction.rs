@@ -116,4 +116,22 @@ pub enum UpgradeableLoaderInstruction {
     ///   1. `[writable]` The account to deposit the closed account's lamports.
     ///   2. `[signer]` The account's authority.
     Close,
+
+    /// Extend a ProgramData account by the specified number of bytes so that
+    /// the program can later be upgraded to a larger size.
+    ///
+    /// The payer funds whatever additional lamports are needed to keep the
+    /// ProgramData account rent-exempt at its new size.
+    ///
+    /// # Account references
+    ///   0. `[writable]` The ProgramData account.
+    ///   1. `[signer]` The program's upgrade authority.
+    ///   2. `[writable, signer]` The payer account that will pay for the
+    ///      additional rent.
+    ///   3. `[]` Rent sysvar.
+    ///   4. `[]` System program (`solana_sdk::system_program::id()`).
+    ExtendProgramData {
+        /// Number of bytes to extend the ProgramData account by.
+        additional_bytes: u32,
+    },
 }
@@ -45,6 +45,8 @@ pub enum ProgramError {
     AccountNotRentExempt,
     #[error("Unsupported sysvar")]
     UnsupportedSysvar,
+    #[error("Failed to reallocate account data")]
+    InvalidRealloc,
 }
 
 pub trait PrintProgramError {
@@ -82,6 +84,7 @@ impl PrintProgramError for ProgramError {
             Self::BorshIoError(_) => msg!("Error: BorshIoError"),
             Self::AccountNotRentExempt => msg!("Error: AccountNotRentExempt"),
             Self::UnsupportedSysvar => msg!("Error: UnsupportedSysvar"),
+            Self::InvalidRealloc => msg!("Error: InvalidRealloc"),
         }
     }
 }
@@ -111,6 +114,7 @@ pub const INVALID_SEEDS: u64 = to_builtin!(14);
 pub const BORSH_IO_ERROR: u64 = to_builtin!(15);
 pub const ACCOUNT_NOT_RENT_EXEMPT: u64 = to_builtin!(16);
 pub const UNSUPPORTED_SYSVAR: u64 = to_builtin!(17);
+pub const INVALID_REALLOC: u64 = to_builtin!(18);
 
 impl From<ProgramError> for u64 {
     fn from(error: ProgramError) -> Self {
@@ -131,6 +135,7 @@ impl From<ProgramError> for u64 {
             ProgramError::BorshIoError(_) => BORSH_IO_ERROR,
             ProgramError::AccountNotRentExempt => ACCOUNT_NOT_RENT_EXEMPT,
             ProgramError::UnsupportedSysvar => UNSUPPORTED_SYSVAR,
+            ProgramError::InvalidRealloc => INVALID_REALLOC,
 
             ProgramError::Custom(error) => {
                 if error == 0 {
@@ -160,6 +165,7 @@ impl From<u64> for ProgramError {
             MAX_SEED_LENGTH_EXCEEDED => ProgramError::MaxSeedLengthExceeded,
             INVALID_SEEDS => ProgramError::InvalidSeeds,
             UNSUPPORTED_SYSVAR => ProgramError::UnsupportedSysvar,
+            INVALID_REALLOC => ProgramError::InvalidRealloc,
             CUSTOM_ZERO => ProgramError::Custom(0),
             _ => ProgramError::Custom(error as u32),
         }
@@ -187,6 +193,7 @@ impl TryFrom<InstructionError> for ProgramError {
             Self::Error::BorshIoError(err) => Ok(Self::BorshIoError(err)),
             Self::Error::AccountNotRentExempt => Ok(Self::AccountNotRentExempt),
             Self::Error::UnsupportedSysvar => Ok(Self::UnsupportedSysvar),
+            Self::Error::InvalidRealloc => Ok(Self::InvalidRealloc),
             _ => Err(error),
         }
     }
@@ -214,6 +221,7 @@ where
             MAX_SEED_LENGTH_EXCEEDED => InstructionError::MaxSeedLengthExceeded,
             INVALID_SEEDS => InstructionError::InvalidSeeds,
             UNSUPPORTED_SYSVAR => InstructionError::UnsupportedSysvar,
+            INVALID_REALLOC => InstructionError::InvalidRealloc,
             _ => {
                 // A valid custom error has no bits set in the upper 32
                 if error >> BUILTIN_BIT_SHIFT == 0 {
@@ -0,0 +1,192 @@
+//! @brief Solana Rust-based BPF memory operations
+//!
+//! On BPF these call into the runtime's memory operation syscalls, which are
+//! charged by the number of bytes touched rather than per BPF instruction.
+//! With the `mem-syscalls` feature the compiler builtins used for copies and
+//! comparisons the program does not spell out are routed to the same
+//! syscalls.
+
+/// Memcpy
+///
+/// @param dst - Destination
+/// @param src - Source
+/// @param n - Number of bytes to copy
+///
+/// Panics if `n` is larger than either slice.
+#[inline]
+pub fn sol_memcpy(dst: &mut [u8], src: &[u8], n: usize) {
+    assert!(n <= dst.len() && n <= src.len());
+    #[cfg(target_arch = "bpf")]
+    {
+        extern "C" {
+            fn sol_memcpy_(dst: *mut u8, src: *const u8, n: u64);
+        }
+        unsafe {
+            sol_memcpy_(dst.as_mut_ptr(), src.as_ptr(), n as u64);
+        }
+    }
+
+    #[cfg(not(target_arch = "bpf"))]
+    unsafe {
+        crate::program_stubs::sol_memcpy(dst.as_mut_ptr(), src.as_ptr(), n);
+    }
+}
+
+/// Memmove
+///
+/// @param dst - Destination
+/// @param src - Source
+/// @param n - Number of bytes to copy
+///
+/// # Safety
+///
+/// `dst` and `src` must both be valid for `n` bytes, they may overlap
+#[inline]
+pub unsafe fn sol_memmove(dst: *mut u8, src: *mut u8, n: usize) {
+    #[cfg(target_arch = "bpf")]
+    {
+        extern "C" {
+            fn sol_memmove_(dst: *mut u8, src: *const u8, n: u64);
+        }
+        sol_memmove_(dst, src, n as u64);
+    }
+
+    #[cfg(not(target_arch = "bpf"))]
+    crate::program_stubs::sol_memmove(dst, src, n);
+}
+
+/// Memcmp
+///
+/// @param s1 - Slice to be compared
+/// @param s2 - Slice to be compared
+/// @param n - Number of bytes to compare
+///
+/// Returns the difference between the first pair of bytes that differ, or 0
+/// if the first `n` bytes are equal.  Panics if `n` is larger than either
+/// slice.
+#[inline]
+pub fn sol_memcmp(s1: &[u8], s2: &[u8], n: usize) -> i32 {
+    assert!(n <= s1.len() && n <= s2.len());
+    let mut result = 0;
+
+    #[cfg(target_arch = "bpf")]
+    {
+        extern "C" {
+            fn sol_memcmp_(s1: *const u8, s2: *const u8, n: u64, result: *mut i32);
+        }
+        unsafe {
+            sol_memcmp_(s1.as_ptr(), s2.as_ptr(), n as u64, &mut result as *mut i32);
+        }
+    }
+
+    #[cfg(not(target_arch = "bpf"))]
+    unsafe {
+        crate::program_stubs::sol_memcmp(s1.as_ptr(), s2.as_ptr(), n, &mut result as *mut i32);
+    }
+
+    result
+}
+
+/// Memset
+///
+/// @param s - Slice to be set
+/// @param c - Repeated byte to set
+/// @param n - Number of bytes to set
+///
+/// Panics if `n` is larger than the slice.
+#[inline]
+pub fn sol_memset(s: &mut [u8], c: u8, n: usize) {
+    assert!(n <= s.len());
+    #[cfg(target_arch = "bpf")]
+    {
+        extern "C" {
+            fn sol_memset_(s: *mut u8, c: u8, n: u64);
+        }
+        unsafe {
+            sol_memset_(s.as_mut_ptr(), c, n as u64);
+        }
+    }
+
+    #[cfg(not(target_arch = "bpf"))]
+    unsafe {
+        crate::program_stubs::sol_memset(s.as_mut_ptr(), c, n);
+    }
+}
+
+/// Replacements for the compiler builtins, which otherwise loop over bytes in
+/// BPF.  Programs built with them can only be deployed once the runtime has
+/// enabled the memory operation syscalls.
+#[cfg(all(feature = "mem-syscalls", target_arch = "bpf"))]
+mod builtins {
+    extern "C" {
+        fn sol_memcpy_(dst: *mut u8, src: *const u8, n: u64);
+        fn sol_memmove_(dst: *mut u8, src: *const u8, n: u64);
+        fn sol_memcmp_(s1: *const u8, s2: *const u8, n: u64, result: *mut i32);
+        fn sol_memset_(s: *mut u8, c: u8, n: u64);
+    }
+
+    #[no_mangle]
+    pub unsafe extern "C" fn memcpy(dst: *mut u8, src: *const u8, n: usize) -> *mut u8 {
+        sol_memcpy_(dst, src, n as u64);
+        dst
+    }
+
+    #[no_mangle]
+    pub unsafe extern "C" fn memmove(dst: *mut u8, src: *const u8, n: usize) -> *mut u8 {
+        sol_memmove_(dst, src, n as u64);
+        dst
+    }
+
+    #[no_mangle]
+    pub unsafe extern "C" fn memset(s: *mut u8, c: i32, n: usize) -> *mut u8 {
+        sol_memset_(s, c as u8, n as u64);
+        s
+    }
+
+    #[no_mangle]
+    pub unsafe extern "C" fn memcmp(s1: *const u8, s2: *const u8, n: usize) -> i32 {
+        let mut result = 0;
+        sol_memcmp_(s1, s2, n as u64, &mut result as *mut i32);
+        result
+    }
+
+    #[no_mangle]
+    pub unsafe extern "C" fn bcmp(s1: *const u8, s2: *const u8, n: usize) -> i32 {
+        memcmp(s1, s2, n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memory_operations() {
+        let src = [1u8, 2, 3, 4, 5];
+        let mut dst = [0u8; 5];
+        sol_memcpy(&mut dst, &src, 3);
+        assert_eq!(dst, [1, 2, 3, 0, 0]);
+
+        let mut buf = [1u8, 2, 3, 4, 5];
+        let ptr = buf.as_mut_ptr();
+        unsafe { sol_memmove(ptr.add(1), ptr, 4) };
+        assert_eq!(buf, [1, 1, 2, 3, 4]);
+        unsafe { sol_memmove(ptr, ptr.add(1), 4) };
+        assert_eq!(buf, [1, 2, 3, 4, 4]);
+
+        assert_eq!(sol_memcmp(&src, &[1, 2, 3, 4, 5], 5), 0);
+        assert_eq!(sol_memcmp(&src, &[1, 2, 4], 3), -1);
+        assert_eq!(sol_memcmp(&src, &[1, 2, 1], 3), 2);
+        assert_eq!(sol_memcmp(&src, &[9], 0), 0);
+
+        let mut buf = [0u8; 4];
+        sol_memset(&mut buf, 7, 3);
+        assert_eq!(buf, [7, 7, 7, 0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_memcpy_too_long() {
+        sol_memcpy(&mut [0u8; 2], &[0u8; 4], 3);
+    }
+}
@@ -3,8 +3,13 @@
 #![cfg(not(target_arch = "bpf"))]
 
 use crate::{
-    account_info::AccountInfo, entrypoint::ProgramResult, instruction::Instruction,
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    hash::HASH_BYTES,
+    instruction::Instruction,
     program_error::UNSUPPORTED_SYSVAR,
+    pubkey::Pubkey,
+    secp256k1_recover::{Secp256k1RecoverError, SECP256K1_PUBLIC_KEY_LENGTH},
 };
 use std::sync::{Arc, RwLock};
 
@@ -47,6 +52,77 @@ pub trait SyscallStubs: Sync + Send {
     fn sol_get_rent_sysvar(&self, _var_addr: *mut u8) -> u64 {
         UNSUPPORTED_SYSVAR
     }
+    fn sol_set_return_data(&self, _data: &[u8]) {}
+    fn sol_get_return_data(&self) -> Option<(Pubkey, Vec<u8>)> {
+        None
+    }
+    fn sol_keccak256(&self, vals: &[&[u8]]) -> [u8; HASH_BYTES] {
+        use sha3::{Digest, Keccak256};
+        let mut hasher = Keccak256::new();
+        for val in vals {
+            hasher.update(val);
+        }
+        hasher.finalize().into()
+    }
+    fn sol_blake3(&self, vals: &[&[u8]]) -> [u8; HASH_BYTES] {
+        let mut hasher = blake3::Hasher::new();
+        for val in vals {
+            hasher.update(val);
+        }
+        *hasher.finalize().as_bytes()
+    }
+    fn sol_secp256k1_recover(
+        &self,
+        hash: &[u8],
+        recovery_id: u8,
+        signature: &[u8],
+    ) -> Result<[u8; SECP256K1_PUBLIC_KEY_LENGTH], Secp256k1RecoverError> {
+        let message = secp256k1::Message::parse_slice(hash)
+            .map_err(|_| Secp256k1RecoverError::InvalidHash)?;
+        let recovery_id = secp256k1::RecoveryId::parse(recovery_id)
+            .map_err(|_| Secp256k1RecoverError::InvalidRecoveryId)?;
+        let signature = secp256k1::Signature::parse_slice(signature)
+            .map_err(|_| Secp256k1RecoverError::InvalidSignature)?;
+        let public_key = secp256k1::recover(&message, &signature, &recovery_id)
+            .map_err(|_| Secp256k1RecoverError::InvalidSignature)?;
+        let mut result = [0u8; SECP256K1_PUBLIC_KEY_LENGTH];
+        result.copy_from_slice(&public_key.serialize()[1..]);
+        Ok(result)
+    }
+    /// # Safety
+    unsafe fn sol_memcpy(&self, dst: *mut u8, src: *const u8, n: usize) {
+        if is_overlapping(dst as usize, src as usize, n) {
+            panic!("memcpy does not support overlapping regions");
+        }
+        std::ptr::copy_nonoverlapping(src, dst, n);
+    }
+    /// # Safety
+    unsafe fn sol_memmove(&self, dst: *mut u8, src: *const u8, n: usize) {
+        std::ptr::copy(src, dst, n);
+    }
+    /// # Safety
+    unsafe fn sol_memcmp(&self, s1: *const u8, s2: *const u8, n: usize, result: *mut i32) {
+        let mut i = 0;
+        while i < n {
+            let a = *s1.add(i);
+            let b = *s2.add(i);
+            if a != b {
+                *result = a as i32 - b as i32;
+                return;
+            }
+            i += 1;
+        }
+        *result = 0
+    }
+    /// # Safety
+    unsafe fn sol_memset(&self, s: *mut u8, c: u8, n: usize) {
+        std::ptr::write_bytes(s, c, n);
+    }
+}
+
+/// Check whether two `n` byte regions starting at `a` and `b` overlap
+fn is_overlapping(a: usize, b: usize, n: usize) -> bool {
+    n > 0 && a < b.saturating_add(n) && b < a.saturating_add(n)
 }
 
 struct DefaultSyscallStubs {}
@@ -96,3 +172,50 @@ pub(crate) fn sol_get_fees_sysvar(var_addr: *mut u8) -> u64 {
 pub(crate) fn sol_get_rent_sysvar(var_addr: *mut u8) -> u64 {
     SYSCALL_STUBS.read().unwrap().sol_get_rent_sysvar(var_addr)
 }
+
+pub(crate) fn sol_set_return_data(data: &[u8]) {
+    SYSCALL_STUBS.read().unwrap().sol_set_return_data(data)
+}
+
+pub(crate) fn sol_get_return_data() -> Option<(Pubkey, Vec<u8>)> {
+    SYSCALL_STUBS.read().unwrap().sol_get_return_data()
+}
+
+pub(crate) fn sol_keccak256(vals: &[&[u8]]) -> [u8; HASH_BYTES] {
+    SYSCALL_STUBS.read().unwrap().sol_keccak256(vals)
+}
+
+pub(crate) fn sol_blake3(vals: &[&[u8]]) -> [u8; HASH_BYTES] {
+    SYSCALL_STUBS.read().unwrap().sol_blake3(vals)
+}
+
+pub(crate) fn sol_secp256k1_recover(
+    hash: &[u8],
+    recovery_id: u8,
+    signature: &[u8],
+) -> Result<[u8; SECP256K1_PUBLIC_KEY_LENGTH], Secp256k1RecoverError> {
+    SYSCALL_STUBS
+        .read()
+        .unwrap()
+        .sol_secp256k1_recover(hash, recovery_id, signature)
+}
+
+/// # Safety
+pub(crate) unsafe fn sol_memcpy(dst: *mut u8, src: *const u8, n: usize) {
+    SYSCALL_STUBS.read().unwrap().sol_memcpy(dst, src, n);
+}
+
+/// # Safety
+pub(crate) unsafe fn sol_memmove(dst: *mut u8, src: *const u8, n: usize) {
+    SYSCALL_STUBS.read().unwrap().sol_memmove(dst, src, n);
+}
+
+/// # Safety
+pub(crate) unsafe fn sol_memcmp(s1: *const u8, s2: *const u8, n: usize, result: *mut i32) {
+    SYSCALL_STUBS.read().unwrap().sol_memcmp(s1, s2, n, result);
+}
+
+/// # Safety
+pub(crate) unsafe fn sol_memset(s: *mut u8, c: u8, n: usize) {
+    SYSCALL_STUBS.read().unwrap().sol_memset(s, c, n);
+}
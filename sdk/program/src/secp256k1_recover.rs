@@ -0,0 +1,167 @@
+//! The `secp256k1_recover` module provides secp256k1 public key recovery.
+
+use thiserror::Error;
+
+pub const SECP256K1_HASH_LENGTH: usize = 32;
+pub const SECP256K1_SIGNATURE_LENGTH: usize = 64;
+pub const SECP256K1_PUBLIC_KEY_LENGTH: usize = 64;
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum Secp256k1RecoverError {
+    #[error("The hash provided to a secp256k1_recover is invalid")]
+    InvalidHash,
+    #[error("The recovery_id provided to a secp256k1_recover is invalid")]
+    InvalidRecoveryId,
+    #[error("The signature provided to a secp256k1_recover is invalid")]
+    InvalidSignature,
+}
+
+impl From<u64> for Secp256k1RecoverError {
+    fn from(v: u64) -> Secp256k1RecoverError {
+        match v {
+            1 => Secp256k1RecoverError::InvalidHash,
+            2 => Secp256k1RecoverError::InvalidRecoveryId,
+            3 => Secp256k1RecoverError::InvalidSignature,
+            _ => panic!("Unsupported Secp256k1RecoverError"),
+        }
+    }
+}
+
+impl From<Secp256k1RecoverError> for u64 {
+    fn from(v: Secp256k1RecoverError) -> u64 {
+        match v {
+            Secp256k1RecoverError::InvalidHash => 1,
+            Secp256k1RecoverError::InvalidRecoveryId => 2,
+            Secp256k1RecoverError::InvalidSignature => 3,
+        }
+    }
+}
+
+/// An uncompressed secp256k1 public key, without the leading `0x04` tag byte
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[repr(transparent)]
+pub struct Secp256k1Pubkey(pub [u8; SECP256K1_PUBLIC_KEY_LENGTH]);
+
+impl Secp256k1Pubkey {
+    pub fn new(pubkey_vec: &[u8]) -> Self {
+        let mut pubkey = [0u8; SECP256K1_PUBLIC_KEY_LENGTH];
+        pubkey.copy_from_slice(pubkey_vec);
+        Self(pubkey)
+    }
+
+    pub fn to_bytes(self) -> [u8; SECP256K1_PUBLIC_KEY_LENGTH] {
+        self.0
+    }
+}
+
+/// Recover the public key that signed `hash` with `signature` and `recovery_id`
+pub fn secp256k1_recover(
+    hash: &[u8],
+    recovery_id: u8,
+    signature: &[u8],
+) -> Result<Secp256k1Pubkey, Secp256k1RecoverError> {
+    if hash.len() != SECP256K1_HASH_LENGTH {
+        return Err(Secp256k1RecoverError::InvalidHash);
+    }
+    if signature.len() != SECP256K1_SIGNATURE_LENGTH {
+        return Err(Secp256k1RecoverError::InvalidSignature);
+    }
+
+    // Perform the calculation inline, calling this from within a program is
+    // not supported
+    #[cfg(not(target_arch = "bpf"))]
+    {
+        crate::program_stubs::sol_secp256k1_recover(hash, recovery_id, signature)
+            .map(Secp256k1Pubkey)
+    }
+    // Call via a system call to perform the calculation
+    #[cfg(target_arch = "bpf")]
+    {
+        extern "C" {
+            fn sol_secp256k1_recover(
+                hash: *const u8,
+                recovery_id: u64,
+                signature: *const u8,
+                result: *mut u8,
+            ) -> u64;
+        }
+        let mut pubkey_buffer = [0u8; SECP256K1_PUBLIC_KEY_LENGTH];
+        let result = unsafe {
+            sol_secp256k1_recover(
+                hash.as_ptr(),
+                recovery_id as u64,
+                signature.as_ptr(),
+                pubkey_buffer.as_mut_ptr(),
+            )
+        };
+        match result {
+            0 => Ok(Secp256k1Pubkey(pubkey_buffer)),
+            error => Err(Secp256k1RecoverError::from(error)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_secp256k1_recover_invalid_input() {
+        assert_eq!(
+            secp256k1_recover(&[0; SECP256K1_HASH_LENGTH - 1], 0, &[0; 64]),
+            Err(Secp256k1RecoverError::InvalidHash)
+        );
+        assert_eq!(
+            secp256k1_recover(&[0; SECP256K1_HASH_LENGTH], 0, &[0; 63]),
+            Err(Secp256k1RecoverError::InvalidSignature)
+        );
+        assert_eq!(
+            secp256k1_recover(&[1; SECP256K1_HASH_LENGTH], 4, &[1; 64]),
+            Err(Secp256k1RecoverError::InvalidRecoveryId)
+        );
+        assert_eq!(
+            secp256k1_recover(&[1; SECP256K1_HASH_LENGTH], 0, &[0; 64]),
+            Err(Secp256k1RecoverError::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn test_secp256k1_recover() {
+        let secret_key = secp256k1::SecretKey::parse(&[7; 32]).unwrap();
+        let public_key = secp256k1::PublicKey::from_secret_key(&secret_key);
+        let hash = crate::keccak::hash(b"hello");
+        let message = secp256k1::Message::parse(&hash.to_bytes());
+        let (signature, recovery_id) = secp256k1::sign(&message, &secret_key);
+
+        let recovered = secp256k1_recover(
+            hash.as_ref(),
+            recovery_id.serialize(),
+            &signature.serialize(),
+        )
+        .unwrap();
+        assert_eq!(recovered.to_bytes()[..], public_key.serialize()[1..]);
+
+        // A different hash recovers a different key
+        let recovered = secp256k1_recover(
+            crate::keccak::hash(b"world").as_ref(),
+            recovery_id.serialize(),
+            &signature.serialize(),
+        )
+        .unwrap();
+        assert_ne!(recovered.to_bytes()[..], public_key.serialize()[1..]);
+    }
+
+    #[test]
+    fn test_secp256k1_recover_error_conversion() {
+        for error in &[
+            Secp256k1RecoverError::InvalidHash,
+            Secp256k1RecoverError::InvalidRecoveryId,
+            Secp256k1RecoverError::InvalidSignature,
+        ] {
+            assert_eq!(
+                Secp256k1RecoverError::from(u64::from(error.clone())),
+                *error
+            );
+        }
+    }
+}
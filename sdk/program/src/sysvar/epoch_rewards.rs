@@ -0,0 +1,75 @@
+//! named accounts for synthesized data accounts for bank state, etc.
+//!
+//! this account carries the status of the partitioned epoch reward
+//! distribution; while `active` is set, stake accounts may not be modified
+//!
+use crate::{hash::Hash, program_error::ProgramError, sysvar::Sysvar};
+
+crate::declare_sysvar_id!("SysvarEpochRewards1111111111111111111111111", EpochRewards);
+
+#[repr(C)]
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq, Clone, Copy)]
+pub struct EpochRewards {
+    /// block height of the first block in which rewards are credited
+    pub distribution_starting_block_height: u64,
+    /// number of blocks (partitions) across which rewards are credited
+    pub num_partitions: u64,
+    /// blockhash of the parent of the first reward block, seeds the partitioning
+    pub parent_blockhash: Hash,
+    /// total stake rewards to be credited in this distribution
+    pub total_rewards: u64,
+    /// stake rewards credited so far
+    pub distributed_rewards: u64,
+    /// whether the distribution is still in progress
+    pub active: bool,
+}
+
+impl EpochRewards {
+    /// record `amount` as credited, failing without change if that would
+    ///   exceed `total_rewards`
+    pub fn distribute(&mut self, amount: u64) -> Result<(), ProgramError> {
+        let distributed_rewards = self
+            .distributed_rewards
+            .checked_add(amount)
+            .filter(|distributed_rewards| *distributed_rewards <= self.total_rewards)
+            .ok_or(ProgramError::InvalidArgument)?;
+        self.distributed_rewards = distributed_rewards;
+        Ok(())
+    }
+}
+
+impl Sysvar for EpochRewards {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_epoch_rewards_distribute() {
+        let mut epoch_rewards = EpochRewards {
+            total_rewards: 100,
+            active: true,
+            ..EpochRewards::default()
+        };
+        assert_eq!(epoch_rewards.distribute(60), Ok(()));
+        assert_eq!(epoch_rewards.distribute(40), Ok(()));
+        assert_eq!(epoch_rewards.distributed_rewards, 100);
+    }
+
+    #[test]
+    fn test_epoch_rewards_distribute_too_much() {
+        let mut epoch_rewards = EpochRewards {
+            total_rewards: 100,
+            ..EpochRewards::default()
+        };
+        assert_eq!(
+            epoch_rewards.distribute(101),
+            Err(ProgramError::InvalidArgument)
+        );
+        assert_eq!(
+            epoch_rewards.distribute(u64::MAX),
+            Err(ProgramError::InvalidArgument)
+        );
+        assert_eq!(epoch_rewards.distributed_rewards, 0);
+    }
+}
@@ -3,6 +3,7 @@
 use crate::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
 
 pub mod clock;
+pub mod epoch_rewards;
 pub mod epoch_schedule;
 pub mod fees;
 pub mod instructions;
@@ -15,6 +16,7 @@ pub mod stake_history;
 
 pub fn is_sysvar_id(id: &Pubkey) -> bool {
     clock::check_id(id)
+        || epoch_rewards::check_id(id)
         || epoch_schedule::check_id(id)
         || fees::check_id(id)
         || recent_blockhashes::check_id(id)
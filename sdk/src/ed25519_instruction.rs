@@ -0,0 +1,370 @@
+#![cfg(feature = "full")]
+
+use crate::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signature, Signer},
+};
+use serde_derive::{Deserialize, Serialize};
+use std::convert::TryFrom;
+
+#[derive(Debug, PartialEq)]
+pub enum Ed25519Error {
+    InvalidPublicKey,
+    InvalidSignature,
+    InvalidDataOffsets,
+    InvalidInstructionDataSize,
+}
+
+pub const PUBKEY_SERIALIZED_SIZE: usize = 32;
+pub const SIGNATURE_SERIALIZED_SIZE: usize = 64;
+pub const SIGNATURE_OFFSETS_SERIALIZED_SIZE: usize = 14;
+// One byte for the signature count and one byte of padding, keeping the offsets u16-aligned
+pub const SIGNATURE_OFFSETS_START: usize = 2;
+pub const DATA_START: usize = SIGNATURE_OFFSETS_SERIALIZED_SIZE + SIGNATURE_OFFSETS_START;
+
+/// Instruction index meaning "the ed25519 instruction itself"
+pub const CURRENT_INSTRUCTION_INDEX: u16 = u16::MAX;
+
+#[derive(Default, Serialize, Deserialize, Debug, PartialEq)]
+pub struct Ed25519SignatureOffsets {
+    pub signature_offset: u16, // offset to ed25519 signature of 64 bytes
+    pub signature_instruction_index: u16, // instruction index to find signature
+    pub public_key_offset: u16, // offset to public key of 32 bytes
+    pub public_key_instruction_index: u16, // instruction index to find public key
+    pub message_data_offset: u16, // offset to start of message data
+    pub message_data_size: u16, // size of message data
+    pub message_instruction_index: u16, // index of instruction data to get message data
+}
+
+pub fn new_ed25519_instruction(keypair: &Keypair, message: &[u8]) -> Instruction {
+    let signature = keypair.sign_message(message);
+    new_ed25519_instruction_with_signature(&keypair.pubkey(), &signature, message)
+}
+
+/// Build an instruction verifying a signature produced elsewhere, e.g. by an oracle
+pub fn new_ed25519_instruction_with_signature(
+    pubkey: &Pubkey,
+    signature: &Signature,
+    message: &[u8],
+) -> Instruction {
+    let public_key_offset = DATA_START;
+    let signature_offset = public_key_offset.saturating_add(PUBKEY_SERIALIZED_SIZE);
+    let message_data_offset = signature_offset.saturating_add(SIGNATURE_SERIALIZED_SIZE);
+
+    let mut instruction_data = vec![0u8; message_data_offset.saturating_add(message.len())];
+    let num_signatures = 1;
+    instruction_data[0] = num_signatures;
+    instruction_data[public_key_offset..signature_offset].copy_from_slice(pubkey.as_ref());
+    instruction_data[signature_offset..message_data_offset].copy_from_slice(signature.as_ref());
+    instruction_data[message_data_offset..].copy_from_slice(message);
+
+    let offsets = Ed25519SignatureOffsets {
+        signature_offset: signature_offset as u16,
+        signature_instruction_index: CURRENT_INSTRUCTION_INDEX,
+        public_key_offset: public_key_offset as u16,
+        public_key_instruction_index: CURRENT_INSTRUCTION_INDEX,
+        message_data_offset: message_data_offset as u16,
+        message_data_size: message.len() as u16,
+        message_instruction_index: CURRENT_INSTRUCTION_INDEX,
+    };
+    let writer = std::io::Cursor::new(&mut instruction_data[SIGNATURE_OFFSETS_START..DATA_START]);
+    bincode::serialize_into(writer, &offsets).unwrap();
+
+    Instruction {
+        program_id: solana_sdk::ed25519_program::id(),
+        accounts: vec![],
+        data: instruction_data,
+    }
+}
+
+/// Parse the signature offsets out of ed25519 instruction data, e.g. one loaded from the
+/// instructions sysvar
+pub fn get_signature_offsets(data: &[u8]) -> Result<Vec<Ed25519SignatureOffsets>, Ed25519Error> {
+    if data.len() < SIGNATURE_OFFSETS_START {
+        return Err(Ed25519Error::InvalidInstructionDataSize);
+    }
+    let count = data[0] as usize;
+    if count == 0 && data.len() > SIGNATURE_OFFSETS_START {
+        return Err(Ed25519Error::InvalidInstructionDataSize);
+    }
+    let expected_data_size = count
+        .saturating_mul(SIGNATURE_OFFSETS_SERIALIZED_SIZE)
+        .saturating_add(SIGNATURE_OFFSETS_START);
+    if data.len() < expected_data_size {
+        return Err(Ed25519Error::InvalidInstructionDataSize);
+    }
+    (0..count)
+        .map(|i| {
+            let start = i
+                .saturating_mul(SIGNATURE_OFFSETS_SERIALIZED_SIZE)
+                .saturating_add(SIGNATURE_OFFSETS_START);
+            let end = start.saturating_add(SIGNATURE_OFFSETS_SERIALIZED_SIZE);
+            bincode::deserialize(&data[start..end]).map_err(|_| Ed25519Error::InvalidDataOffsets)
+        })
+        .collect()
+}
+
+pub fn verify_signatures(data: &[u8], instruction_datas: &[&[u8]]) -> Result<(), Ed25519Error> {
+    for offsets in get_signature_offsets(data)? {
+        // Parse out signature
+        let signature = get_data_slice(
+            data,
+            instruction_datas,
+            offsets.signature_instruction_index,
+            offsets.signature_offset,
+            SIGNATURE_SERIALIZED_SIZE,
+        )?;
+        let signature = ed25519_dalek::Signature::try_from(signature)
+            .map_err(|_| Ed25519Error::InvalidSignature)?;
+
+        // Parse out pubkey
+        let pubkey = get_data_slice(
+            data,
+            instruction_datas,
+            offsets.public_key_instruction_index,
+            offsets.public_key_offset,
+            PUBKEY_SERIALIZED_SIZE,
+        )?;
+        let pubkey = ed25519_dalek::PublicKey::from_bytes(pubkey)
+            .map_err(|_| Ed25519Error::InvalidPublicKey)?;
+
+        // Parse out message
+        let message = get_data_slice(
+            data,
+            instruction_datas,
+            offsets.message_instruction_index,
+            offsets.message_data_offset,
+            offsets.message_data_size as usize,
+        )?;
+
+        pubkey
+            .verify_strict(message, &signature)
+            .map_err(|_| Ed25519Error::InvalidSignature)?;
+    }
+    Ok(())
+}
+
+fn get_data_slice<'a>(
+    data: &'a [u8],
+    instruction_datas: &'a [&[u8]],
+    instruction_index: u16,
+    offset_start: u16,
+    size: usize,
+) -> Result<&'a [u8], Ed25519Error> {
+    let instruction = if instruction_index == CURRENT_INSTRUCTION_INDEX {
+        data
+    } else {
+        let signature_index = instruction_index as usize;
+        if signature_index >= instruction_datas.len() {
+            return Err(Ed25519Error::InvalidDataOffsets);
+        }
+        instruction_datas[signature_index]
+    };
+    let start = offset_start as usize;
+    let end = start.saturating_add(size);
+    if end > instruction.len() {
+        return Err(Ed25519Error::InvalidDataOffsets);
+    }
+
+    Ok(&instruction[start..end])
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+
+    fn test_case(
+        num_signatures: u8,
+        offsets: &Ed25519SignatureOffsets,
+    ) -> Result<(), Ed25519Error> {
+        let mut instruction_data = vec![0u8; DATA_START];
+        instruction_data[0] = num_signatures;
+        let writer = std::io::Cursor::new(&mut instruction_data[SIGNATURE_OFFSETS_START..]);
+        bincode::serialize_into(writer, &offsets).unwrap();
+
+        verify_signatures(&instruction_data, &[&[0u8; 100]])
+    }
+
+    #[test]
+    fn test_signature_offsets_serialized_size() {
+        assert_eq!(
+            bincode::serialized_size(&Ed25519SignatureOffsets::default()).unwrap() as usize,
+            SIGNATURE_OFFSETS_SERIALIZED_SIZE
+        );
+    }
+
+    #[test]
+    fn test_invalid_offsets() {
+        let mut instruction_data = vec![0u8; DATA_START];
+        let offsets = Ed25519SignatureOffsets::default();
+        instruction_data[0] = 1;
+        let writer = std::io::Cursor::new(&mut instruction_data[SIGNATURE_OFFSETS_START..]);
+        bincode::serialize_into(writer, &offsets).unwrap();
+        instruction_data.truncate(instruction_data.len() - 1);
+
+        assert_eq!(
+            verify_signatures(&instruction_data, &[&[0u8; 100]]),
+            Err(Ed25519Error::InvalidInstructionDataSize)
+        );
+
+        let offsets = Ed25519SignatureOffsets {
+            signature_instruction_index: 1,
+            ..Ed25519SignatureOffsets::default()
+        };
+        assert_eq!(
+            test_case(1, &offsets),
+            Err(Ed25519Error::InvalidDataOffsets)
+        );
+
+        let offsets = Ed25519SignatureOffsets {
+            message_instruction_index: 1,
+            ..Ed25519SignatureOffsets::default()
+        };
+        assert_eq!(
+            test_case(1, &offsets),
+            Err(Ed25519Error::InvalidDataOffsets)
+        );
+
+        let offsets = Ed25519SignatureOffsets {
+            public_key_instruction_index: 1,
+            ..Ed25519SignatureOffsets::default()
+        };
+        assert_eq!(
+            test_case(1, &offsets),
+            Err(Ed25519Error::InvalidDataOffsets)
+        );
+    }
+
+    #[test]
+    fn test_zero_signatures() {
+        assert_eq!(verify_signatures(&[0, 0], &[]), Ok(()));
+        assert_eq!(
+            verify_signatures(&[0, 0, 0], &[]),
+            Err(Ed25519Error::InvalidInstructionDataSize)
+        );
+        assert_eq!(
+            verify_signatures(&[0], &[]),
+            Err(Ed25519Error::InvalidInstructionDataSize)
+        );
+    }
+
+    #[test]
+    fn test_message_data_offsets() {
+        let offsets = Ed25519SignatureOffsets {
+            message_data_offset: 99,
+            message_data_size: 1,
+            ..Ed25519SignatureOffsets::default()
+        };
+        assert_eq!(test_case(1, &offsets), Err(Ed25519Error::InvalidSignature));
+
+        let offsets = Ed25519SignatureOffsets {
+            message_data_offset: 100,
+            message_data_size: 1,
+            ..Ed25519SignatureOffsets::default()
+        };
+        assert_eq!(
+            test_case(1, &offsets),
+            Err(Ed25519Error::InvalidDataOffsets)
+        );
+
+        let offsets = Ed25519SignatureOffsets {
+            message_data_offset: std::u16::MAX,
+            message_data_size: std::u16::MAX,
+            ..Ed25519SignatureOffsets::default()
+        };
+        assert_eq!(
+            test_case(1, &offsets),
+            Err(Ed25519Error::InvalidDataOffsets)
+        );
+    }
+
+    #[test]
+    fn test_pubkey_offset() {
+        let offsets = Ed25519SignatureOffsets {
+            public_key_offset: std::u16::MAX,
+            ..Ed25519SignatureOffsets::default()
+        };
+        assert_eq!(
+            test_case(1, &offsets),
+            Err(Ed25519Error::InvalidDataOffsets)
+        );
+
+        let offsets = Ed25519SignatureOffsets {
+            public_key_offset: 100 - PUBKEY_SERIALIZED_SIZE as u16 + 1,
+            ..Ed25519SignatureOffsets::default()
+        };
+        assert_eq!(
+            test_case(1, &offsets),
+            Err(Ed25519Error::InvalidDataOffsets)
+        );
+    }
+
+    #[test]
+    fn test_signature_offset() {
+        let offsets = Ed25519SignatureOffsets {
+            signature_offset: std::u16::MAX,
+            ..Ed25519SignatureOffsets::default()
+        };
+        assert_eq!(
+            test_case(1, &offsets),
+            Err(Ed25519Error::InvalidDataOffsets)
+        );
+
+        let offsets = Ed25519SignatureOffsets {
+            signature_offset: 100 - SIGNATURE_SERIALIZED_SIZE as u16 + 1,
+            ..Ed25519SignatureOffsets::default()
+        };
+        assert_eq!(
+            test_case(1, &offsets),
+            Err(Ed25519Error::InvalidDataOffsets)
+        );
+    }
+
+    #[test]
+    fn test_signature_from_other_instruction() {
+        let keypair = Keypair::new();
+        let message = b"hello";
+        let signature = keypair.sign_message(message);
+
+        // Public key and signature live in the ed25519 instruction, the message in instruction 0
+        let mut instruction_data = vec![0u8; DATA_START];
+        instruction_data[0] = 1;
+        let public_key_offset = instruction_data.len();
+        instruction_data.extend_from_slice(keypair.pubkey().as_ref());
+        let signature_offset = instruction_data.len();
+        instruction_data.extend_from_slice(signature.as_ref());
+        let offsets = Ed25519SignatureOffsets {
+            signature_offset: signature_offset as u16,
+            signature_instruction_index: CURRENT_INSTRUCTION_INDEX,
+            public_key_offset: public_key_offset as u16,
+            public_key_instruction_index: CURRENT_INSTRUCTION_INDEX,
+            message_data_offset: 0,
+            message_data_size: message.len() as u16,
+            message_instruction_index: 0,
+        };
+        let writer = std::io::Cursor::new(&mut instruction_data[SIGNATURE_OFFSETS_START..]);
+        bincode::serialize_into(writer, &offsets).unwrap();
+
+        assert_eq!(verify_signatures(&instruction_data, &[message]), Ok(()));
+        assert_eq!(
+            verify_signatures(&instruction_data, &[b"world"]),
+            Err(Ed25519Error::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn test_get_signature_offsets() {
+        let keypair = Keypair::new();
+        let instruction = new_ed25519_instruction(&keypair, b"hello");
+        let offsets = get_signature_offsets(&instruction.data).unwrap();
+        assert_eq!(offsets.len(), 1);
+        let public_key_offset = offsets[0].public_key_offset as usize;
+        assert_eq!(
+            &instruction.data[public_key_offset..public_key_offset + PUBKEY_SERIALIZED_SIZE],
+            keypair.pubkey().as_ref()
+        );
+        let message_data_offset = offsets[0].message_data_offset as usize;
+        assert_eq!(&instruction.data[message_data_offset..], b"hello");
+    }
+}
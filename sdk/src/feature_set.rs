@@ -131,6 +131,70 @@ pub mod sysvar_via_syscall {
     solana_sdk::declare_id!("7411E6gFQLDhQkdRjmpXwM1hzHMMoYQUjHicmvGPC1Nf");
 }
 
+pub mod address_lookup_table_program_enabled {
+    solana_sdk::declare_id!("3KZZ6Ks1885aGBQ45fwRcPXVBCtzUvxhUTkwKMR41Tca");
+}
+
+pub mod return_data_syscall_enabled {
+    solana_sdk::declare_id!("DwScAzPUjuv65TMbDnFY7AgwmotzWy3xpEJMXM3hZFaB");
+}
+
+pub mod ed25519_program_enabled {
+    solana_sdk::declare_id!("6ppMXNYLhVd7GcsZ5uV11wQEW7spppiMVfqQv5SXhDpX");
+}
+
+pub mod keccak256_syscall_enabled {
+    solana_sdk::declare_id!("vXqCTpigkb9nxQ2PpPDL8QWGb4rDywvrTD7xTSBaeDD");
+}
+
+pub mod blake3_syscall_enabled {
+    solana_sdk::declare_id!("Anj9idM6pkTYrwSwtEUByxG9wdadgr1WAgDiKcBPCuYo");
+}
+
+pub mod secp256k1_recover_syscall_enabled {
+    solana_sdk::declare_id!("J5Bye7TLHBp929HZizSCNZ9Fnv7ziyuddzqMgLBsepiU");
+}
+
+pub mod mem_syscalls_enabled {
+    solana_sdk::declare_id!("GvKvQt4zj2duJQJNBXVYdbdkbxHQGWwE8i68TZFAfm19");
+}
+
+pub mod upgradeable_extend_program_data {
+    solana_sdk::declare_id!("7N8D7EXkR86N3kiKqf7g9HmLHJdnoF5hQFEQkpCNQHsH");
+}
+
+pub mod do_support_realloc {
+    solana_sdk::declare_id!("GktGVVGVzVzvADJtbhhiC1ydLiatiFua2qsiYrpG88xs");
+}
+
+pub mod stake_redelegate_instruction {
+    solana_sdk::declare_id!("7F7Y6Wh1CEgTabGMLqJbmEcA4oBn2YTNeTdpDSbdgqCi");
+}
+
+pub mod stake_deactivate_delinquent_instruction {
+    solana_sdk::declare_id!("2w8fvr1qoLAUSkgQufKZThpF3c5xWHxVK14uyQ48ha8j");
+}
+
+pub mod allow_votes_to_directly_update_vote_state {
+    solana_sdk::declare_id!("5pHfQt1VrbgC7LC1EkJLJKAcSyaKzpcjCSDVQsLvD2AT");
+}
+
+pub mod commission_updates_only_allowed_in_first_half_of_epoch {
+    solana_sdk::declare_id!("BMtaDJcPe8twT2gRMeZSq3hPNji3sf3STxKbJTGAAiQn");
+}
+
+pub mod stake_raise_minimum_delegation_to_1_sol {
+    solana_sdk::declare_id!("5squChLx6M9FaCXUsDP7YDbHRFtTtgtdRBGDgtCdquZK");
+}
+
+pub mod partitioned_epoch_rewards {
+    solana_sdk::declare_id!("HmcW3KsPD823mjWS47o5RC9XVHERj5kyEm6MY3mUdd5X");
+}
+
+pub mod vest_token_grants {
+    solana_sdk::declare_id!("2j3dke4JGj5kVh8TsxSyTgMfukJoCA3c37ssmuDjQ2Je");
+}
+
 lazy_static! {
     /// Map of feature identifiers to user-visible description
     pub static ref FEATURE_NAMES: HashMap<Pubkey, &'static str> = [
@@ -164,6 +228,22 @@ lazy_static! {
         (upgradeable_close_instruction::id(), "close upgradeable buffer accounts"),
         (demote_sysvar_write_locks::id(), "demote builtins and sysvar write locks to readonly #15497"),
         (sysvar_via_syscall::id(), "Provide sysvars via syscalls"),
+        (address_lookup_table_program_enabled::id(), "enable the address lookup table program"),
+        (return_data_syscall_enabled::id(), "enable sol_{set,get}_return_data syscall"),
+        (ed25519_program_enabled::id(), "enable builtin ed25519 signature verify program"),
+        (keccak256_syscall_enabled::id(), "keccak256 syscall"),
+        (blake3_syscall_enabled::id(), "blake3 syscall"),
+        (secp256k1_recover_syscall_enabled::id(), "secp256k1_recover syscall"),
+        (mem_syscalls_enabled::id(), "memcpy, memmove, memcmp and memset syscalls"),
+        (upgradeable_extend_program_data::id(), "upgradeable loader ExtendProgramData instruction"),
+        (do_support_realloc::id(), "support account data reallocation"),
+        (stake_redelegate_instruction::id(), "enable the redelegate stake instruction"),
+        (stake_deactivate_delinquent_instruction::id(), "enable the deactivate delinquent stake instruction"),
+        (allow_votes_to_directly_update_vote_state::id(), "enable direct vote state update"),
+        (commission_updates_only_allowed_in_first_half_of_epoch::id(), "only allow vote account commission increases in the first half of an epoch"),
+        (stake_raise_minimum_delegation_to_1_sol::id(), "raise minimum stake delegation to 1.0 SOL"),
+        (partitioned_epoch_rewards::id(), "distribute epoch rewards across multiple blocks"),
+        (vest_token_grants::id(), "enable SPL token grants in the vest program"),
         /*************** ADD NEW FEATURES HERE ***************/
     ]
     .iter()
@@ -0,0 +1,96 @@
+// Partial SPL Token v2.0.x declarations inlined to avoid an external dependency on the spl-token crate
+solana_sdk::declare_id!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+
+/*
+    spl_token::state::Account {
+        mint: Pubkey,
+        owner: Pubkey,
+        amount: u64,
+        delegate: COption<Pubkey>,
+        state: AccountState,
+        is_native: COption<u64>,
+        delegated_amount: u64,
+        close_authority: COption<Pubkey>,
+    }
+*/
+pub const SPL_TOKEN_ACCOUNT_MINT_OFFSET: usize = 0;
+pub const SPL_TOKEN_ACCOUNT_OWNER_OFFSET: usize = 32;
+pub const SPL_TOKEN_ACCOUNT_AMOUNT_OFFSET: usize = 64;
+
+pub mod state {
+    const LEN: usize = 165;
+    pub struct Account;
+    impl Account {
+        pub fn get_packed_len() -> usize {
+            LEN
+        }
+    }
+}
+
+pub mod instruction {
+    use solana_sdk::{
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+        sysvar,
+    };
+
+    /*
+        spl_token::instruction::TokenInstruction {
+            InitializeAccount = 1,
+            Transfer { amount: u64 } = 3,
+        }
+    */
+    const INITIALIZE_ACCOUNT_TAG: u8 = 1;
+    const TRANSFER_TAG: u8 = 3;
+
+    pub fn initialize_account(account: &Pubkey, mint: &Pubkey, owner: &Pubkey) -> Instruction {
+        Instruction::new_with_bytes(
+            super::id(),
+            &[INITIALIZE_ACCOUNT_TAG],
+            vec![
+                AccountMeta::new(*account, false),
+                AccountMeta::new_readonly(*mint, false),
+                AccountMeta::new_readonly(*owner, false),
+                AccountMeta::new_readonly(sysvar::rent::id(), false),
+            ],
+        )
+    }
+
+    pub fn transfer(
+        source: &Pubkey,
+        destination: &Pubkey,
+        authority: &Pubkey,
+        amount: u64,
+    ) -> Instruction {
+        let mut data = vec![TRANSFER_TAG];
+        data.extend_from_slice(&amount.to_le_bytes());
+        Instruction::new_with_bytes(
+            super::id(),
+            &data,
+            vec![
+                AccountMeta::new(*source, false),
+                AccountMeta::new(*destination, false),
+                AccountMeta::new_readonly(*authority, true),
+            ],
+        )
+    }
+}
+
+pub mod native_mint {
+    solana_sdk::declare_id!("So11111111111111111111111111111111111111112");
+
+    /*
+        Mint {
+            mint_authority: COption::None,
+            supply: 0,
+            decimals: 9,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        }
+    */
+    pub const ACCOUNT_DATA: [u8; 82] = [
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 9, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    ];
+}
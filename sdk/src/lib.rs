@@ -14,6 +14,7 @@ pub mod builtins;
 pub mod client;
 pub mod commitment_config;
 pub mod deserialize_utils;
+pub mod ed25519_instruction;
 pub mod entrypoint;
 pub mod entrypoint_deprecated;
 pub mod entrypoint_native;
@@ -24,6 +25,7 @@ pub mod genesis_config;
 pub mod hard_forks;
 pub mod hash;
 pub mod inflation;
+pub mod inline_spl_token_v2_0;
 pub mod keyed_account;
 pub mod log;
 pub mod native_loader;
@@ -70,6 +70,42 @@ pub trait InvokeContext {
     );
     /// Get sysvar data
     fn get_sysvar_data(&mut self, id: &Pubkey) -> Option<Rc<Vec<u8>>>;
+    /// Set the return data of the currently executing program
+    fn set_return_data(&mut self, data: Vec<u8>) -> Result<(), InstructionError>;
+    /// Get the return data and the id of the program that set it
+    fn get_return_data(&self) -> (Pubkey, &[u8]);
+    /// Whether BPF programs should be run with instruction tracing
+    fn is_program_tracing_enabled(&self) -> bool;
+    /// Record the instruction trace of an executed BPF program
+    fn record_program_trace(&self, trace: ProgramTrace);
+    /// Invoke `instruction` from a native program, signing for the program
+    /// derived addresses of `signers_seeds`
+    fn native_invoke(
+        &mut self,
+        instruction: Instruction,
+        keyed_accounts: &[&KeyedAccount],
+        signers_seeds: &[&[&[u8]]],
+    ) -> Result<(), InstructionError>;
+}
+
+/// Return data set by the last program to call `sol_set_return_data`
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct TransactionReturnData {
+    pub program_id: Pubkey,
+    pub data: Vec<u8>,
+}
+
+/// Instruction trace of a single BPF program invocation
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ProgramTrace {
+    pub program_id: Pubkey,
+    /// Invocation depth of the program, 1 for top-level instructions
+    pub invoke_depth: usize,
+    /// One symbolized line per executed BPF instruction
+    pub instructions: Vec<String>,
+    /// Compute units consumed by BPF instructions, keyed by the call stack
+    /// they executed in with frames separated by `;`
+    pub folded_stacks: Vec<(String, u64)>,
 }
 
 /// Convenience macro to log a message with an `Rc<RefCell<dyn Logger>>`
@@ -122,6 +158,16 @@ pub struct BpfComputeBudget {
     pub sha256_base_cost: u64,
     /// Incremental number of units consumed by SHA256 (based on bytes)
     pub sha256_byte_cost: u64,
+    /// Base number of compute units consumed to call keccak256
+    pub keccak256_base_cost: u64,
+    /// Incremental number of units consumed by keccak256 (based on bytes)
+    pub keccak256_byte_cost: u64,
+    /// Base number of compute units consumed to call blake3
+    pub blake3_base_cost: u64,
+    /// Incremental number of units consumed by blake3 (based on bytes)
+    pub blake3_byte_cost: u64,
+    /// Number of compute units consumed to call secp256k1_recover
+    pub secp256k1_recover_cost: u64,
     /// Maximum BPF to BPF call depth
     pub max_call_depth: usize,
     /// Size of a stack frame in bytes, must match the size specified in the LLVM BPF backend
@@ -134,6 +180,15 @@ pub struct BpfComputeBudget {
     pub cpi_bytes_per_unit: u64,
     /// Base number of compute units consumed to get a sysvar
     pub sysvar_base_cost: u64,
+    /// Base number of compute units consumed to call a syscall without a
+    /// dedicated cost, such as `sol_set_return_data`
+    pub syscall_base_cost: u64,
+    /// Base number of compute units consumed by a memory operation syscall,
+    /// such as `sol_memcpy_`
+    pub mem_op_base_cost: u64,
+    /// Number of bytes per compute unit charged by a memory operation syscall
+    /// beyond its base cost
+    pub mem_op_bytes_per_unit: u64,
 }
 impl Default for BpfComputeBudget {
     fn default() -> Self {
@@ -151,12 +206,20 @@ impl BpfComputeBudget {
             max_invoke_depth: 4,
             sha256_base_cost: 85,
             sha256_byte_cost: 1,
+            keccak256_base_cost: 85,
+            keccak256_byte_cost: 1,
+            blake3_base_cost: 85,
+            blake3_byte_cost: 1,
+            secp256k1_recover_cost: 25_000,
             max_call_depth: 64,
             stack_frame_size: 4_096,
             log_pubkey_units: 100,
             max_cpi_instruction_size: 1280, // IPv6 Min MTU size
             cpi_bytes_per_unit: 250,        // ~50MB at 200,000 units
             sysvar_base_cost: 100,
+            syscall_base_cost: 100,
+            mem_op_base_cost: 10,
+            mem_op_bytes_per_unit: 64,
         }
     }
 }
@@ -285,6 +348,7 @@ pub struct MockInvokeContext {
     pub accounts: Vec<(Pubkey, Rc<RefCell<AccountSharedData>>)>,
     pub invoke_depth: usize,
     pub sysvars: Vec<(Pubkey, Option<Rc<Vec<u8>>>)>,
+    pub return_data: (Pubkey, Vec<u8>),
 }
 impl Default for MockInvokeContext {
     fn default() -> Self {
@@ -299,6 +363,7 @@ impl Default for MockInvokeContext {
             accounts: vec![],
             invoke_depth: 0,
             sysvars: vec![],
+            return_data: (Pubkey::default(), vec![]),
         }
     }
 }
@@ -366,4 +431,23 @@ impl InvokeContext for MockInvokeContext {
             .iter()
             .find_map(|(key, sysvar)| if id == key { sysvar.clone() } else { None })
     }
+    fn set_return_data(&mut self, data: Vec<u8>) -> Result<(), InstructionError> {
+        self.return_data = (self.key, data);
+        Ok(())
+    }
+    fn get_return_data(&self) -> (Pubkey, &[u8]) {
+        (self.return_data.0, &self.return_data.1)
+    }
+    fn is_program_tracing_enabled(&self) -> bool {
+        false
+    }
+    fn record_program_trace(&self, _trace: ProgramTrace) {}
+    fn native_invoke(
+        &mut self,
+        _instruction: Instruction,
+        _keyed_accounts: &[&KeyedAccount],
+        _signers_seeds: &[&[&[u8]]],
+    ) -> Result<(), InstructionError> {
+        Err(InstructionError::UnsupportedProgramId)
+    }
 }
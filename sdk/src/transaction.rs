@@ -2,9 +2,11 @@
 
 #![cfg(feature = "full")]
 
+use crate::ed25519_instruction::verify_signatures;
 use crate::sanitize::{Sanitize, SanitizeError};
 use crate::secp256k1_instruction::verify_eth_addresses;
 use crate::{
+    feature_set::{self, FeatureSet},
     hash::Hash,
     instruction::{CompiledInstruction, Instruction, InstructionError},
     message::Message,
@@ -357,7 +359,24 @@ impl Transaction {
             .collect()
     }
 
+    /// Verify the instructions of the precompiled programs enabled in the
+    /// default feature set, i.e. only those of the secp256k1 program
     pub fn verify_precompiles(&self) -> Result<()> {
+        self.verify_precompiles_with_features(&FeatureSet::default())
+    }
+
+    /// Verify the instructions of the precompiled programs enabled in
+    /// `feature_set`. The secp256k1 program is always verified.
+    pub fn verify_precompiles_with_features(&self, feature_set: &FeatureSet) -> Result<()> {
+        let ed25519_program_enabled =
+            feature_set.is_active(&feature_set::ed25519_program_enabled::id());
+        let instruction_datas = || -> Vec<&[u8]> {
+            self.message()
+                .instructions
+                .iter()
+                .map(|instruction| instruction.data.as_ref())
+                .collect()
+        };
         for instruction in &self.message().instructions {
             // The Transaction may not be sanitized at this point
             if instruction.program_id_index as usize >= self.message().account_keys.len() {
@@ -365,15 +384,12 @@ impl Transaction {
             }
             let program_id = &self.message().account_keys[instruction.program_id_index as usize];
             if crate::secp256k1_program::check_id(program_id) {
-                let instruction_datas: Vec<_> = self
-                    .message()
-                    .instructions
-                    .iter()
-                    .map(|instruction| instruction.data.as_ref())
-                    .collect();
                 let data = &instruction.data;
-                let e = verify_eth_addresses(data, &instruction_datas);
+                let e = verify_eth_addresses(data, &instruction_datas());
                 e.map_err(|_| TransactionError::InvalidAccountIndex)?;
+            } else if ed25519_program_enabled && crate::ed25519_program::check_id(program_id) {
+                verify_signatures(&instruction.data, &instruction_datas())
+                    .map_err(|_| TransactionError::InvalidAccountIndex)?;
             }
         }
         Ok(())
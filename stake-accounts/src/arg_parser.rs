@@ -1,16 +1,19 @@
 use crate::args::{
     Args, AuthorizeArgs, Command, CountArgs, MoveArgs, NewArgs, QueryArgs, RebaseArgs,
-    SetLockupArgs,
+    SetLockupArgs, SplitAndDelegateArgs, TransactionArgs,
 };
-use clap::{value_t, value_t_or_exit, App, Arg, ArgMatches, SubCommand};
+use clap::{value_t, value_t_or_exit, values_t_or_exit, App, Arg, ArgMatches, SubCommand};
 use solana_clap_utils::{
-    input_parsers::unix_timestamp_from_rfc3339_datetime,
+    input_parsers::{pubkeys_sigs_of, unix_timestamp_from_rfc3339_datetime, value_of},
     input_validators::{is_amount, is_rfc3339_datetime, is_valid_pubkey, is_valid_signer},
+    nonce::{NonceArgs, NONCE_ARG, NONCE_AUTHORITY_ARG},
+    offline::{OfflineArgs, BLOCKHASH_ARG, DUMP_TRANSACTION_MESSAGE, SIGNER_ARG, SIGN_ONLY_ARG},
 };
 use solana_cli_config::CONFIG_FILE;
-use solana_sdk::native_token::sol_to_lamports;
+use solana_sdk::{native_token::sol_to_lamports, pubkey::Pubkey};
 use std::ffi::OsString;
 use std::process::exit;
+use std::str::FromStr;
 
 fn fee_payer_arg<'a, 'b>() -> Arg<'a, 'b> {
     solana_clap_utils::fee_payer::fee_payer_arg().required(true)
@@ -117,6 +120,44 @@ fn lockup_date_arg<'a, 'b>() -> Arg<'a, 'b> {
         .help("The date and time at which each account will be available for withdrawl")
 }
 
+fn db_path_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("db_path")
+        .long("db-path")
+        .takes_value(true)
+        .value_name("FILE")
+        .help(
+            "Location for storing the progress database. \
+            The database records each transaction as it is sent and finalized, \
+            so that an interrupted command resumes where it left off.",
+        )
+}
+
+// Parse VOTE_PUBKEY=WEIGHT
+fn parse_vote_weight(string: &str) -> Result<(Pubkey, u64), String> {
+    let mut parts = string.splitn(2, '=');
+    let vote_pubkey = parts.next().unwrap_or_default();
+    let weight = parts
+        .next()
+        .ok_or_else(|| format!("missing weight in {}", string))?;
+    let vote_pubkey = Pubkey::from_str(vote_pubkey).map_err(|err| format!("{}", err))?;
+    let weight = weight.parse::<u64>().map_err(|err| format!("{}", err))?;
+    Ok((vote_pubkey, weight))
+}
+
+fn is_vote_weight(string: String) -> Result<(), String> {
+    parse_vote_weight(&string).map(|_| ())
+}
+
+trait TransactionSubCommand {
+    fn transaction_args(self) -> Self;
+}
+
+impl TransactionSubCommand for App<'_, '_> {
+    fn transaction_args(self) -> Self {
+        self.offline_args().nonce_args(false).arg(db_path_arg())
+    }
+}
+
 fn num_accounts_arg<'a, 'b>() -> Arg<'a, 'b> {
     Arg::with_name("num_accounts")
         .long("num-accounts")
@@ -199,7 +240,8 @@ where
                         .default_value("0")
                         .value_name("NUMBER")
                         .help("Index of the derived account to create"),
-                ),
+                )
+                .transaction_args(),
         )
         .subcommand(
             SubCommand::with_name("count")
@@ -227,7 +269,8 @@ where
                 .arg(withdraw_authority_arg())
                 .arg(new_stake_authority_arg())
                 .arg(new_withdraw_authority_arg())
-                .arg(num_accounts_arg()),
+                .arg(num_accounts_arg())
+                .transaction_args(),
         )
         .subcommand(
             SubCommand::with_name("set-lockup")
@@ -250,7 +293,8 @@ where
                         .takes_value(true)
                         .value_name("NUMBER")
                         .help("Years to unlock after the cliff"),
-                ),
+                )
+                .transaction_args(),
         )
         .subcommand(
             SubCommand::with_name("rebase")
@@ -259,7 +303,8 @@ where
                 .arg(base_pubkey_arg().index(1))
                 .arg(new_base_keypair_arg().index(2))
                 .arg(stake_authority_arg())
-                .arg(num_accounts_arg()),
+                .arg(num_accounts_arg())
+                .transaction_args(),
         )
         .subcommand(
             SubCommand::with_name("move")
@@ -271,11 +316,64 @@ where
                 .arg(withdraw_authority_arg())
                 .arg(new_stake_authority_arg())
                 .arg(new_withdraw_authority_arg())
-                .arg(num_accounts_arg()),
+                .arg(num_accounts_arg())
+                .transaction_args(),
+        )
+        .subcommand(
+            SubCommand::with_name("split-and-delegate")
+                .about("Split a stake account across vote accounts and delegate each split")
+                .arg(fee_payer_arg())
+                .arg(
+                    Arg::with_name("stake_account_address")
+                        .required(true)
+                        .index(1)
+                        .takes_value(true)
+                        .value_name("STAKE_ACCOUNT_ADDRESS")
+                        .validator(is_valid_pubkey)
+                        .help("Stake account to split"),
+                )
+                .arg(new_base_keypair_arg().index(2))
+                .arg(
+                    Arg::with_name("amount")
+                        .required(true)
+                        .index(3)
+                        .takes_value(true)
+                        .value_name("AMOUNT")
+                        .validator(is_amount)
+                        .help("Amount to split across the vote accounts, in SOL"),
+                )
+                .arg(stake_authority_arg())
+                .arg(
+                    Arg::with_name("vote_account")
+                        .long("vote-account")
+                        .required(true)
+                        .takes_value(true)
+                        .multiple(true)
+                        .number_of_values(1)
+                        .value_name("VOTE_PUBKEY=WEIGHT")
+                        .validator(is_vote_weight)
+                        .help(
+                            "Vote account to delegate to and its share of the amount, \
+                            relative to the other vote accounts' weights",
+                        ),
+                )
+                .transaction_args(),
         )
         .get_matches_from(args)
 }
 
+fn parse_transaction_args(matches: &ArgMatches<'_>) -> TransactionArgs<String, String> {
+    TransactionArgs {
+        blockhash: value_of(matches, BLOCKHASH_ARG.name),
+        sign_only: matches.is_present(SIGN_ONLY_ARG.name),
+        dump_transaction_message: matches.is_present(DUMP_TRANSACTION_MESSAGE.name),
+        signers: pubkeys_sigs_of(matches, SIGNER_ARG.name).unwrap_or_default(),
+        nonce_account: value_t!(matches, NONCE_ARG.name, String).ok(),
+        nonce_authority: value_t!(matches, NONCE_AUTHORITY_ARG.name, String).ok(),
+        db_path: value_t!(matches, "db_path", String).ok(),
+    }
+}
+
 fn parse_new_args(matches: &ArgMatches<'_>) -> NewArgs<String, String> {
     NewArgs {
         fee_payer: value_t_or_exit!(matches, "fee_payer", String),
@@ -285,6 +383,7 @@ fn parse_new_args(matches: &ArgMatches<'_>) -> NewArgs<String, String> {
         stake_authority: value_t_or_exit!(matches, "stake_authority", String),
         withdraw_authority: value_t_or_exit!(matches, "withdraw_authority", String),
         index: value_t_or_exit!(matches, "index", usize),
+        transaction_args: parse_transaction_args(matches),
     }
 }
 
@@ -310,6 +409,7 @@ fn parse_authorize_args(matches: &ArgMatches<'_>) -> AuthorizeArgs<String, Strin
         new_stake_authority: value_t_or_exit!(matches, "new_stake_authority", String),
         new_withdraw_authority: value_t_or_exit!(matches, "new_withdraw_authority", String),
         num_accounts: value_t_or_exit!(matches, "num_accounts", usize),
+        transaction_args: parse_transaction_args(matches),
     }
 }
 
@@ -324,6 +424,7 @@ fn parse_set_lockup_args(matches: &ArgMatches<'_>) -> SetLockupArgs<String, Stri
         num_accounts: value_t_or_exit!(matches, "num_accounts", usize),
         no_wait: matches.is_present("no_wait"),
         unlock_years: value_t!(matches, "unlock_years", f64).ok(),
+        transaction_args: parse_transaction_args(matches),
     }
 }
 
@@ -334,6 +435,7 @@ fn parse_rebase_args(matches: &ArgMatches<'_>) -> RebaseArgs<String, String> {
         new_base_keypair: value_t_or_exit!(matches, "new_base_keypair", String),
         stake_authority: value_t_or_exit!(matches, "stake_authority", String),
         num_accounts: value_t_or_exit!(matches, "num_accounts", usize),
+        transaction_args: parse_transaction_args(matches),
     }
 }
 
@@ -344,6 +446,21 @@ fn parse_move_args(matches: &ArgMatches<'_>) -> MoveArgs<String, String> {
     }
 }
 
+fn parse_split_and_delegate_args(matches: &ArgMatches<'_>) -> SplitAndDelegateArgs<String, String> {
+    SplitAndDelegateArgs {
+        fee_payer: value_t_or_exit!(matches, "fee_payer", String),
+        stake_account_address: value_t_or_exit!(matches, "stake_account_address", String),
+        new_base_keypair: value_t_or_exit!(matches, "new_base_keypair", String),
+        stake_authority: value_t_or_exit!(matches, "stake_authority", String),
+        lamports: sol_to_lamports(value_t_or_exit!(matches, "amount", f64)),
+        vote_weights: values_t_or_exit!(matches, "vote_account", String)
+            .iter()
+            .map(|vote_weight| parse_vote_weight(vote_weight).unwrap())
+            .collect(),
+        transaction_args: parse_transaction_args(matches),
+    }
+}
+
 pub(crate) fn parse_args<I, T>(args: I) -> Args<String, String>
 where
     I: IntoIterator<Item = T>,
@@ -362,6 +479,9 @@ where
         ("set-lockup", Some(matches)) => Command::SetLockup(parse_set_lockup_args(matches)),
         ("rebase", Some(matches)) => Command::Rebase(parse_rebase_args(matches)),
         ("move", Some(matches)) => Command::Move(Box::new(parse_move_args(matches))),
+        ("split-and-delegate", Some(matches)) => {
+            Command::SplitAndDelegate(parse_split_and_delegate_args(matches))
+        }
         _ => {
             eprintln!("{}", matches.usage());
             exit(1);
@@ -1,14 +1,29 @@
 use clap::ArgMatches;
-use solana_clap_utils::keypair::{pubkey_from_path, signer_from_path};
+use solana_clap_utils::keypair::{
+    presigner_from_pubkey_sigs, pubkey_from_path, signer_from_path_with_config,
+    SignerFromPathConfig,
+};
 use solana_remote_wallet::remote_wallet::RemoteWalletManager;
 use solana_sdk::{
     clock::{Epoch, UnixTimestamp},
+    hash::Hash,
     pubkey::Pubkey,
-    signature::Signer,
+    signature::{Signature, Signer},
 };
 use std::error::Error;
+use std::str::FromStr;
 use std::sync::Arc;
 
+pub(crate) struct TransactionArgs<P, K> {
+    pub blockhash: Option<Hash>,
+    pub sign_only: bool,
+    pub dump_transaction_message: bool,
+    pub signers: Vec<(Pubkey, Signature)>,
+    pub nonce_account: Option<P>,
+    pub nonce_authority: Option<K>,
+    pub db_path: Option<String>,
+}
+
 pub(crate) struct NewArgs<P, K> {
     pub fee_payer: K,
     pub funding_keypair: K,
@@ -17,6 +32,7 @@ pub(crate) struct NewArgs<P, K> {
     pub stake_authority: P,
     pub withdraw_authority: P,
     pub index: usize,
+    pub transaction_args: TransactionArgs<P, K>,
 }
 
 pub(crate) struct CountArgs<P> {
@@ -36,6 +52,7 @@ pub(crate) struct AuthorizeArgs<P, K> {
     pub new_stake_authority: P,
     pub new_withdraw_authority: P,
     pub num_accounts: usize,
+    pub transaction_args: TransactionArgs<P, K>,
 }
 
 pub(crate) struct SetLockupArgs<P, K> {
@@ -48,6 +65,7 @@ pub(crate) struct SetLockupArgs<P, K> {
     pub num_accounts: usize,
     pub no_wait: bool,
     pub unlock_years: Option<f64>,
+    pub transaction_args: TransactionArgs<P, K>,
 }
 
 pub(crate) struct RebaseArgs<P, K> {
@@ -56,6 +74,7 @@ pub(crate) struct RebaseArgs<P, K> {
     pub new_base_keypair: K,
     pub stake_authority: K,
     pub num_accounts: usize,
+    pub transaction_args: TransactionArgs<P, K>,
 }
 
 pub(crate) struct MoveArgs<P, K> {
@@ -63,6 +82,16 @@ pub(crate) struct MoveArgs<P, K> {
     pub authorize_args: AuthorizeArgs<P, K>,
 }
 
+pub(crate) struct SplitAndDelegateArgs<P, K> {
+    pub fee_payer: K,
+    pub stake_account_address: P,
+    pub new_base_keypair: K,
+    pub stake_authority: K,
+    pub lamports: u64,
+    pub vote_weights: Vec<(Pubkey, u64)>,
+    pub transaction_args: TransactionArgs<P, K>,
+}
+
 pub(crate) enum Command<P, K> {
     New(NewArgs<P, K>),
     Count(CountArgs<P>),
@@ -72,6 +101,7 @@ pub(crate) enum Command<P, K> {
     SetLockup(SetLockupArgs<P, K>),
     Rebase(RebaseArgs<P, K>),
     Move(Box<MoveArgs<P, K>>),
+    SplitAndDelegate(SplitAndDelegateArgs<P, K>),
 }
 
 pub(crate) struct Args<P, K> {
@@ -80,20 +110,45 @@ pub(crate) struct Args<P, K> {
     pub command: Command<P, K>,
 }
 
-fn resolve_stake_authority(
+// Return a presigner if `key_url` is a pubkey with a signature passed via --signer. Otherwise,
+// resolve the signer from its path, standing in a null signer for a pubkey in sign-only mode.
+fn resolve_signer(
     wallet_manager: &mut Option<Arc<RemoteWalletManager>>,
     key_url: &str,
+    keypair_name: &str,
+    transaction_args: &TransactionArgs<String, String>,
 ) -> Result<Box<dyn Signer>, Box<dyn Error>> {
+    if let Ok(pubkey) = Pubkey::from_str(key_url) {
+        if let Some(presigner) = presigner_from_pubkey_sigs(&pubkey, &transaction_args.signers) {
+            return Ok(Box::new(presigner));
+        }
+    }
     let matches = ArgMatches::default();
-    signer_from_path(&matches, key_url, "stake authority", wallet_manager)
+    let config = SignerFromPathConfig {
+        allow_null_signer: transaction_args.sign_only,
+    };
+    signer_from_path_with_config(&matches, key_url, keypair_name, wallet_manager, &config)
+}
+
+fn resolve_stake_authority(
+    wallet_manager: &mut Option<Arc<RemoteWalletManager>>,
+    key_url: &str,
+    transaction_args: &TransactionArgs<String, String>,
+) -> Result<Box<dyn Signer>, Box<dyn Error>> {
+    resolve_signer(wallet_manager, key_url, "stake authority", transaction_args)
 }
 
 fn resolve_withdraw_authority(
     wallet_manager: &mut Option<Arc<RemoteWalletManager>>,
     key_url: &str,
+    transaction_args: &TransactionArgs<String, String>,
 ) -> Result<Box<dyn Signer>, Box<dyn Error>> {
-    let matches = ArgMatches::default();
-    signer_from_path(&matches, key_url, "withdraw authority", wallet_manager)
+    resolve_signer(
+        wallet_manager,
+        key_url,
+        "withdraw authority",
+        transaction_args,
+    )
 }
 
 fn resolve_new_stake_authority(
@@ -115,17 +170,17 @@ fn resolve_new_withdraw_authority(
 fn resolve_fee_payer(
     wallet_manager: &mut Option<Arc<RemoteWalletManager>>,
     key_url: &str,
+    transaction_args: &TransactionArgs<String, String>,
 ) -> Result<Box<dyn Signer>, Box<dyn Error>> {
-    let matches = ArgMatches::default();
-    signer_from_path(&matches, key_url, "fee-payer", wallet_manager)
+    resolve_signer(wallet_manager, key_url, "fee-payer", transaction_args)
 }
 
 fn resolve_custodian(
     wallet_manager: &mut Option<Arc<RemoteWalletManager>>,
     key_url: &str,
+    transaction_args: &TransactionArgs<String, String>,
 ) -> Result<Box<dyn Signer>, Box<dyn Error>> {
-    let matches = ArgMatches::default();
-    signer_from_path(&matches, key_url, "custodian", wallet_manager)
+    resolve_signer(wallet_manager, key_url, "custodian", transaction_args)
 }
 
 fn resolve_new_custodian(
@@ -154,20 +209,64 @@ fn resolve_base_pubkey(
 fn resolve_new_base_keypair(
     wallet_manager: &mut Option<Arc<RemoteWalletManager>>,
     key_url: &str,
+    transaction_args: &TransactionArgs<String, String>,
 ) -> Result<Box<dyn Signer>, Box<dyn Error>> {
+    resolve_signer(wallet_manager, key_url, "new base pubkey", transaction_args)
+}
+
+fn resolve_transaction_args(
+    wallet_manager: &mut Option<Arc<RemoteWalletManager>>,
+    args: &TransactionArgs<String, String>,
+) -> Result<TransactionArgs<Pubkey, Box<dyn Signer>>, Box<dyn Error>> {
     let matches = ArgMatches::default();
-    signer_from_path(&matches, key_url, "new base pubkey", wallet_manager)
+    let nonce_account = match &args.nonce_account {
+        None => None,
+        Some(key_url) => Some(pubkey_from_path(
+            &matches,
+            key_url,
+            "nonce account",
+            wallet_manager,
+        )?),
+    };
+    let nonce_authority = match &args.nonce_authority {
+        None => None,
+        Some(key_url) => Some(resolve_signer(
+            wallet_manager,
+            key_url,
+            "nonce authority",
+            args,
+        )?),
+    };
+    let resolved_args = TransactionArgs {
+        blockhash: args.blockhash,
+        sign_only: args.sign_only,
+        dump_transaction_message: args.dump_transaction_message,
+        signers: args.signers.clone(),
+        nonce_account,
+        nonce_authority,
+        db_path: args.db_path.clone(),
+    };
+    Ok(resolved_args)
 }
 
 fn resolve_authorize_args(
     wallet_manager: &mut Option<Arc<RemoteWalletManager>>,
     args: &AuthorizeArgs<String, String>,
 ) -> Result<AuthorizeArgs<Pubkey, Box<dyn Signer>>, Box<dyn Error>> {
+    let transaction_args = &args.transaction_args;
     let resolved_args = AuthorizeArgs {
-        fee_payer: resolve_fee_payer(wallet_manager, &args.fee_payer)?,
+        fee_payer: resolve_fee_payer(wallet_manager, &args.fee_payer, transaction_args)?,
         base_pubkey: resolve_base_pubkey(wallet_manager, &args.base_pubkey)?,
-        stake_authority: resolve_stake_authority(wallet_manager, &args.stake_authority)?,
-        withdraw_authority: resolve_withdraw_authority(wallet_manager, &args.withdraw_authority)?,
+        stake_authority: resolve_stake_authority(
+            wallet_manager,
+            &args.stake_authority,
+            transaction_args,
+        )?,
+        withdraw_authority: resolve_withdraw_authority(
+            wallet_manager,
+            &args.withdraw_authority,
+            transaction_args,
+        )?,
         new_stake_authority: resolve_new_stake_authority(
             wallet_manager,
             &args.new_stake_authority,
@@ -177,6 +276,7 @@ fn resolve_authorize_args(
             &args.new_withdraw_authority,
         )?,
         num_accounts: args.num_accounts,
+        transaction_args: resolve_transaction_args(wallet_manager, transaction_args)?,
     };
     Ok(resolved_args)
 }
@@ -185,16 +285,18 @@ fn resolve_set_lockup_args(
     wallet_manager: &mut Option<Arc<RemoteWalletManager>>,
     args: &SetLockupArgs<String, String>,
 ) -> Result<SetLockupArgs<Pubkey, Box<dyn Signer>>, Box<dyn Error>> {
+    let transaction_args = &args.transaction_args;
     let resolved_args = SetLockupArgs {
-        fee_payer: resolve_fee_payer(wallet_manager, &args.fee_payer)?,
+        fee_payer: resolve_fee_payer(wallet_manager, &args.fee_payer, transaction_args)?,
         base_pubkey: resolve_base_pubkey(wallet_manager, &args.base_pubkey)?,
-        custodian: resolve_custodian(wallet_manager, &args.custodian)?,
+        custodian: resolve_custodian(wallet_manager, &args.custodian, transaction_args)?,
         lockup_epoch: args.lockup_epoch,
         lockup_date: args.lockup_date,
         new_custodian: resolve_new_custodian(wallet_manager, &args.new_custodian)?,
         num_accounts: args.num_accounts,
         no_wait: args.no_wait,
         unlock_years: args.unlock_years,
+        transaction_args: resolve_transaction_args(wallet_manager, transaction_args)?,
     };
     Ok(resolved_args)
 }
@@ -203,12 +305,53 @@ fn resolve_rebase_args(
     wallet_manager: &mut Option<Arc<RemoteWalletManager>>,
     args: &RebaseArgs<String, String>,
 ) -> Result<RebaseArgs<Pubkey, Box<dyn Signer>>, Box<dyn Error>> {
+    let transaction_args = &args.transaction_args;
     let resolved_args = RebaseArgs {
-        fee_payer: resolve_fee_payer(wallet_manager, &args.fee_payer)?,
+        fee_payer: resolve_fee_payer(wallet_manager, &args.fee_payer, transaction_args)?,
         base_pubkey: resolve_base_pubkey(wallet_manager, &args.base_pubkey)?,
-        new_base_keypair: resolve_new_base_keypair(wallet_manager, &args.new_base_keypair)?,
-        stake_authority: resolve_stake_authority(wallet_manager, &args.stake_authority)?,
+        new_base_keypair: resolve_new_base_keypair(
+            wallet_manager,
+            &args.new_base_keypair,
+            transaction_args,
+        )?,
+        stake_authority: resolve_stake_authority(
+            wallet_manager,
+            &args.stake_authority,
+            transaction_args,
+        )?,
         num_accounts: args.num_accounts,
+        transaction_args: resolve_transaction_args(wallet_manager, transaction_args)?,
+    };
+    Ok(resolved_args)
+}
+
+fn resolve_split_and_delegate_args(
+    wallet_manager: &mut Option<Arc<RemoteWalletManager>>,
+    args: &SplitAndDelegateArgs<String, String>,
+) -> Result<SplitAndDelegateArgs<Pubkey, Box<dyn Signer>>, Box<dyn Error>> {
+    let matches = ArgMatches::default();
+    let transaction_args = &args.transaction_args;
+    let resolved_args = SplitAndDelegateArgs {
+        fee_payer: resolve_fee_payer(wallet_manager, &args.fee_payer, transaction_args)?,
+        stake_account_address: pubkey_from_path(
+            &matches,
+            &args.stake_account_address,
+            "stake account address",
+            wallet_manager,
+        )?,
+        new_base_keypair: resolve_new_base_keypair(
+            wallet_manager,
+            &args.new_base_keypair,
+            transaction_args,
+        )?,
+        stake_authority: resolve_stake_authority(
+            wallet_manager,
+            &args.stake_authority,
+            transaction_args,
+        )?,
+        lamports: args.lamports,
+        vote_weights: args.vote_weights.clone(),
+        transaction_args: resolve_transaction_args(wallet_manager, transaction_args)?,
     };
     Ok(resolved_args)
 }
@@ -220,19 +363,24 @@ pub(crate) fn resolve_command(
     let matches = ArgMatches::default();
     match command {
         Command::New(args) => {
+            let transaction_args = &args.transaction_args;
             let resolved_args = NewArgs {
-                fee_payer: resolve_fee_payer(&mut wallet_manager, &args.fee_payer)?,
-                funding_keypair: signer_from_path(
-                    &matches,
+                fee_payer: resolve_fee_payer(
+                    &mut wallet_manager,
+                    &args.fee_payer,
+                    transaction_args,
+                )?,
+                funding_keypair: resolve_signer(
+                    &mut wallet_manager,
                     &args.funding_keypair,
                     "funding keypair",
-                    &mut wallet_manager,
+                    transaction_args,
                 )?,
-                base_keypair: signer_from_path(
-                    &matches,
+                base_keypair: resolve_signer(
+                    &mut wallet_manager,
                     &args.base_keypair,
                     "base keypair",
-                    &mut wallet_manager,
+                    transaction_args,
                 )?,
                 stake_authority: pubkey_from_path(
                     &matches,
@@ -248,6 +396,7 @@ pub(crate) fn resolve_command(
                 )?,
                 lamports: args.lamports,
                 index: args.index,
+                transaction_args: resolve_transaction_args(&mut wallet_manager, transaction_args)?,
             };
             Ok(Command::New(resolved_args))
         }
@@ -290,5 +439,9 @@ pub(crate) fn resolve_command(
             };
             Ok(Command::Move(Box::new(resolved_args)))
         }
+        Command::SplitAndDelegate(args) => {
+            let resolved_args = resolve_split_and_delegate_args(&mut wallet_manager, &args)?;
+            Ok(Command::SplitAndDelegate(resolved_args))
+        }
     }
 }
@@ -0,0 +1,91 @@
+use chrono::prelude::*;
+use pickledb::{error::Error, PickleDb, PickleDbDumpPolicy};
+use serde::{Deserialize, Serialize};
+use solana_sdk::{message::Message, transaction::Transaction};
+use std::{fs, path::Path};
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+pub(crate) struct TransactionInfo {
+    pub transaction: Transaction,
+    pub finalized_date: Option<DateTime<Utc>>,
+}
+
+pub(crate) fn open_db(path: &str) -> Result<PickleDb, Error> {
+    let policy = PickleDbDumpPolicy::DumpUponRequest;
+    let path = Path::new(path);
+    let db = if path.exists() {
+        PickleDb::load_yaml(path, policy)?
+    } else {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).unwrap();
+        }
+        PickleDb::new_yaml(path, policy)
+    };
+    Ok(db)
+}
+
+// Identify a message by the hash of its instructions and accounts, before a blockhash or
+// nonce instruction is added, so that a restarted command finds the same record.
+fn message_key(message: &Message) -> String {
+    message.hash().to_string()
+}
+
+pub(crate) fn get_transaction_info(db: &PickleDb, message: &Message) -> Option<TransactionInfo> {
+    db.get::<TransactionInfo>(&message_key(message))
+}
+
+pub(crate) fn set_transaction_info(
+    db: &mut PickleDb,
+    message: &Message,
+    transaction: &Transaction,
+) -> Result<(), Error> {
+    let transaction_info = TransactionInfo {
+        transaction: transaction.clone(),
+        finalized_date: None,
+    };
+    db.set(&message_key(message), &transaction_info)?;
+    db.dump()?;
+    Ok(())
+}
+
+pub(crate) fn set_finalized(db: &mut PickleDb, message: &Message) -> Result<(), Error> {
+    let key = message_key(message);
+    if let Some(mut transaction_info) = db.get::<TransactionInfo>(&key) {
+        transaction_info.finalized_date = Some(Utc::now());
+        db.set(&key, &transaction_info)?;
+        db.dump()?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::{pubkey::Pubkey, system_instruction};
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_resume_from_db() {
+        let db_file = NamedTempFile::new().unwrap();
+        let db_path = db_file.path().to_str().unwrap();
+        let mut db = PickleDb::new_yaml(db_file.path(), PickleDbDumpPolicy::DumpUponRequest);
+
+        let from_pubkey = solana_sdk::pubkey::new_rand();
+        let instruction = system_instruction::transfer(&from_pubkey, &Pubkey::default(), 1);
+        let message = Message::new(&[instruction], Some(&from_pubkey));
+        assert_eq!(get_transaction_info(&db, &message), None);
+
+        let transaction = Transaction::new_unsigned(message.clone());
+        set_transaction_info(&mut db, &message, &transaction).unwrap();
+        let transaction_info = get_transaction_info(&db, &message).unwrap();
+        assert_eq!(transaction_info.transaction, transaction);
+        assert_eq!(transaction_info.finalized_date, None);
+
+        // Ensure the record survives a restart.
+        set_finalized(&mut db, &message).unwrap();
+        let db = open_db(db_path).unwrap();
+        let transaction_info = get_transaction_info(&db, &message).unwrap();
+        assert_eq!(transaction_info.transaction, transaction);
+        assert!(transaction_info.finalized_date.is_some());
+    }
+}
@@ -1,15 +1,22 @@
 #![allow(clippy::integer_arithmetic)]
 mod arg_parser;
 mod args;
+mod db;
 mod stake_accounts;
 
 use crate::arg_parser::parse_args;
 use crate::args::{
     resolve_command, AuthorizeArgs, Command, MoveArgs, NewArgs, RebaseArgs, SetLockupArgs,
+    SplitAndDelegateArgs, TransactionArgs,
 };
+use pickledb::PickleDb;
 use solana_cli_config::Config;
-use solana_client::client_error::ClientError;
-use solana_client::rpc_client::RpcClient;
+use solana_cli_output::{return_signers_with_config, OutputFormat, ReturnSignersConfig};
+use solana_client::{
+    blockhash_query::{BlockhashQuery, Source},
+    client_error::ClientError,
+    rpc_client::RpcClient,
+};
 use solana_sdk::{
     message::Message,
     native_token::lamports_to_sol,
@@ -65,10 +72,22 @@ fn get_lockups(
         .collect()
 }
 
+// Add the nonce authority, if any, to the signers of each transaction.
+fn with_nonce_authority<'a>(
+    signers: Vec<&'a dyn Signer>,
+    transaction_args: &'a TransactionArgs<Pubkey, Box<dyn Signer>>,
+) -> Vec<&'a dyn Signer> {
+    let mut signers = signers;
+    if let Some(nonce_authority) = &transaction_args.nonce_authority {
+        signers.push(&**nonce_authority);
+    }
+    unique_signers(signers)
+}
+
 fn process_new_stake_account(
     client: &RpcClient,
     args: &NewArgs<Pubkey, Box<dyn Signer>>,
-) -> Result<Signature, ClientError> {
+) -> Result<(), Box<dyn Error>> {
     let message = stake_accounts::new_stake_account(
         &args.fee_payer.pubkey(),
         &args.funding_keypair.pubkey(),
@@ -79,19 +98,28 @@ fn process_new_stake_account(
         &Pubkey::default(),
         args.index,
     );
-    let signers = unique_signers(vec![
-        &*args.fee_payer,
-        &*args.funding_keypair,
-        &*args.base_keypair,
-    ]);
-    let signature = send_and_confirm_message(client, message, &signers, false)?;
-    Ok(signature)
+    let signers = with_nonce_authority(
+        vec![
+            &*args.fee_payer,
+            &*args.funding_keypair,
+            &*args.base_keypair,
+        ],
+        &args.transaction_args,
+    );
+    send_and_confirm_messages(
+        client,
+        vec![message],
+        &signers,
+        &args.transaction_args,
+        false,
+    )?;
+    Ok(())
 }
 
 fn process_authorize_stake_accounts(
     client: &RpcClient,
     args: &AuthorizeArgs<Pubkey, Box<dyn Signer>>,
-) -> Result<(), ClientError> {
+) -> Result<(), Box<dyn Error>> {
     let messages = stake_accounts::authorize_stake_accounts(
         &args.fee_payer.pubkey(),
         &args.base_pubkey,
@@ -101,19 +129,22 @@ fn process_authorize_stake_accounts(
         &args.new_withdraw_authority,
         args.num_accounts,
     );
-    let signers = unique_signers(vec![
-        &*args.fee_payer,
-        &*args.stake_authority,
-        &*args.withdraw_authority,
-    ]);
-    send_and_confirm_messages(client, messages, &signers, false)?;
+    let signers = with_nonce_authority(
+        vec![
+            &*args.fee_payer,
+            &*args.stake_authority,
+            &*args.withdraw_authority,
+        ],
+        &args.transaction_args,
+    );
+    send_and_confirm_messages(client, messages, &signers, &args.transaction_args, false)?;
     Ok(())
 }
 
 fn process_lockup_stake_accounts(
     client: &RpcClient,
     args: &SetLockupArgs<Pubkey, Box<dyn Signer>>,
-) -> Result<(), ClientError> {
+) -> Result<(), Box<dyn Error>> {
     let addresses =
         stake_accounts::derive_stake_account_addresses(&args.base_pubkey, args.num_accounts);
     let existing_lockups = get_lockups(&client, addresses)?;
@@ -134,15 +165,24 @@ fn process_lockup_stake_accounts(
         eprintln!("No work to do");
         return Ok(());
     }
-    let signers = unique_signers(vec![&*args.fee_payer, &*args.custodian]);
-    send_and_confirm_messages(client, messages, &signers, args.no_wait)?;
+    let signers = with_nonce_authority(
+        vec![&*args.fee_payer, &*args.custodian],
+        &args.transaction_args,
+    );
+    send_and_confirm_messages(
+        client,
+        messages,
+        &signers,
+        &args.transaction_args,
+        args.no_wait,
+    )?;
     Ok(())
 }
 
 fn process_rebase_stake_accounts(
     client: &RpcClient,
     args: &RebaseArgs<Pubkey, Box<dyn Signer>>,
-) -> Result<(), ClientError> {
+) -> Result<(), Box<dyn Error>> {
     let addresses =
         stake_accounts::derive_stake_account_addresses(&args.base_pubkey, args.num_accounts);
     let balances = get_balances(&client, addresses)?;
@@ -157,19 +197,22 @@ fn process_rebase_stake_accounts(
         eprintln!("No accounts found");
         return Ok(());
     }
-    let signers = unique_signers(vec![
-        &*args.fee_payer,
-        &*args.new_base_keypair,
-        &*args.stake_authority,
-    ]);
-    send_and_confirm_messages(client, messages, &signers, false)?;
+    let signers = with_nonce_authority(
+        vec![
+            &*args.fee_payer,
+            &*args.new_base_keypair,
+            &*args.stake_authority,
+        ],
+        &args.transaction_args,
+    );
+    send_and_confirm_messages(client, messages, &signers, &args.transaction_args, false)?;
     Ok(())
 }
 
 fn process_move_stake_accounts(
     client: &RpcClient,
     move_args: &MoveArgs<Pubkey, Box<dyn Signer>>,
-) -> Result<(), ClientError> {
+) -> Result<(), Box<dyn Error>> {
     let authorize_args = &move_args.authorize_args;
     let args = &move_args.rebase_args;
     let addresses =
@@ -189,46 +232,197 @@ fn process_move_stake_accounts(
         eprintln!("No accounts found");
         return Ok(());
     }
-    let signers = unique_signers(vec![
-        &*args.fee_payer,
-        &*args.new_base_keypair,
-        &*args.stake_authority,
-        &*authorize_args.withdraw_authority,
-    ]);
-    send_and_confirm_messages(client, messages, &signers, false)?;
+    let signers = with_nonce_authority(
+        vec![
+            &*args.fee_payer,
+            &*args.new_base_keypair,
+            &*args.stake_authority,
+            &*authorize_args.withdraw_authority,
+        ],
+        &args.transaction_args,
+    );
+    send_and_confirm_messages(client, messages, &signers, &args.transaction_args, false)?;
     Ok(())
 }
 
-fn send_and_confirm_message<S: Signers>(
+fn process_split_and_delegate_stake_account(
     client: &RpcClient,
-    message: Message,
-    signers: &S,
+    args: &SplitAndDelegateArgs<Pubkey, Box<dyn Signer>>,
+) -> Result<(), Box<dyn Error>> {
+    let messages = stake_accounts::split_and_delegate_stake_account(
+        &args.fee_payer.pubkey(),
+        &args.stake_account_address,
+        &args.new_base_keypair.pubkey(),
+        &args.stake_authority.pubkey(),
+        args.lamports,
+        &args.vote_weights,
+    );
+    if messages.is_empty() {
+        eprintln!("No work to do");
+        return Ok(());
+    }
+    let signers = with_nonce_authority(
+        vec![
+            &*args.fee_payer,
+            &*args.new_base_keypair,
+            &*args.stake_authority,
+        ],
+        &args.transaction_args,
+    );
+    send_and_confirm_messages(client, messages, &signers, &args.transaction_args, false)?;
+    Ok(())
+}
+
+fn send_transaction(
+    client: &RpcClient,
+    transaction: &Transaction,
     no_wait: bool,
 ) -> Result<Signature, ClientError> {
-    let mut transaction = Transaction::new_unsigned(message);
-
-    let (blockhash, _fee_calculator) =
-        client.get_new_blockhash(&transaction.message().recent_blockhash)?;
-    transaction.try_sign(signers, blockhash)?;
-
     if no_wait {
-        client.send_transaction(&transaction)
+        client.send_transaction(transaction)
     } else {
-        client.send_and_confirm_transaction_with_spinner(&transaction)
+        client.send_and_confirm_transaction_with_spinner(transaction)
     }
 }
 
+// Return true if the progress database shows `message` has already been processed. A
+// transaction that was sent but never confirmed is resent while its blockhash is still valid.
+fn resume_message(
+    client: &RpcClient,
+    db: &mut PickleDb,
+    message: &Message,
+    source: &Source,
+    no_wait: bool,
+) -> Result<bool, Box<dyn Error>> {
+    let transaction_info = match db::get_transaction_info(db, message) {
+        Some(transaction_info) => transaction_info,
+        None => return Ok(false),
+    };
+    if transaction_info.finalized_date.is_some() {
+        return Ok(true);
+    }
+    let transaction = &transaction_info.transaction;
+    match client.get_signature_status(&transaction.signatures[0])? {
+        Some(Ok(())) => {
+            db::set_finalized(db, message)?;
+            return Ok(true);
+        }
+        Some(Err(err)) => {
+            eprintln!(
+                "Error in transaction with signature {}: {}",
+                transaction.signatures[0], err
+            );
+            return Ok(false);
+        }
+        None => {}
+    }
+    let blockhash = transaction.message.recent_blockhash;
+    if source
+        .get_fee_calculator(client, &blockhash, client.commitment())?
+        .is_none()
+    {
+        // The transaction can no longer land. Sign it again.
+        return Ok(false);
+    }
+    let signature = send_transaction(client, transaction, no_wait)?;
+    if !no_wait {
+        db::set_finalized(db, message)?;
+    }
+    println!("{}", signature);
+    Ok(true)
+}
+
 fn send_and_confirm_messages<S: Signers>(
     client: &RpcClient,
     messages: Vec<Message>,
     signers: &S,
+    transaction_args: &TransactionArgs<Pubkey, Box<dyn Signer>>,
     no_wait: bool,
-) -> Result<Vec<Signature>, ClientError> {
+) -> Result<Vec<Signature>, Box<dyn Error>> {
+    let mut db = match &transaction_args.db_path {
+        Some(db_path) => Some(db::open_db(db_path)?),
+        None => None,
+    };
+    let source = transaction_args
+        .nonce_account
+        .map(Source::NonceAccount)
+        .unwrap_or(Source::Cluster);
+    let mut blockhash_query = BlockhashQuery::new(
+        transaction_args.blockhash,
+        transaction_args.sign_only,
+        transaction_args.nonce_account,
+    );
+
+    // A signature made offline covers exactly one transaction, as does a nonce value.
+    let one_transaction_per_run =
+        transaction_args.sign_only || !transaction_args.signers.is_empty();
+
+    let num_messages = messages.len();
     let mut signatures = vec![];
-    for message in messages {
-        let signature = send_and_confirm_message(client, message, signers, no_wait)?;
+    for (i, message) in messages.into_iter().enumerate() {
+        if let Some(db) = &mut db {
+            let finalized = if transaction_args.sign_only {
+                db::get_transaction_info(db, &message)
+                    .map(|transaction_info| transaction_info.finalized_date.is_some())
+                    .unwrap_or(false)
+            } else {
+                resume_message(client, db, &message, &source, no_wait)?
+            };
+            if finalized {
+                continue;
+            }
+        }
+        if one_transaction_per_run && !signatures.is_empty() {
+            eprintln!(
+                "{} transaction(s) remaining. Rerun with a new blockhash to continue.",
+                num_messages - i
+            );
+            break;
+        }
+
+        let nonced_message = match transaction_args.nonce_account {
+            Some(nonce_account) => {
+                let nonce_authority = transaction_args
+                    .nonce_authority
+                    .as_ref()
+                    .map(|nonce_authority| nonce_authority.pubkey())
+                    .unwrap_or(message.account_keys[0]);
+                stake_accounts::add_nonce_instruction(&message, &nonce_account, &nonce_authority)
+            }
+            None => message.clone(),
+        };
+        let mut transaction = Transaction::new_unsigned(nonced_message);
+        let (blockhash, _fee_calculator) =
+            blockhash_query.get_blockhash_and_fee_calculator(client, client.commitment())?;
+
+        if transaction_args.sign_only {
+            transaction.try_partial_sign(signers, blockhash)?;
+            let config = ReturnSignersConfig {
+                dump_transaction_message: transaction_args.dump_transaction_message,
+            };
+            println!(
+                "{}",
+                return_signers_with_config(&transaction, &OutputFormat::Display, &config)?
+            );
+            signatures.push(transaction.signatures[0]);
+            continue;
+        }
+
+        transaction.try_sign(signers, blockhash)?;
+        if let Some(db) = &mut db {
+            db::set_transaction_info(db, &message, &transaction)?;
+        }
+        let signature = send_transaction(client, &transaction, no_wait)?;
+        if let Some(db) = &mut db {
+            if !no_wait {
+                db::set_finalized(db, &message)?;
+            }
+        }
         signatures.push(signature);
         println!("{}", signature);
+
+        // The supplied blockhash or nonce value is spent. Fetch a new one for the next transaction.
+        blockhash_query = BlockhashQuery::new(None, false, transaction_args.nonce_account);
     }
     Ok(signatures)
 }
@@ -278,6 +472,9 @@ fn main() -> Result<(), Box<dyn Error>> {
         Command::Move(args) => {
             process_move_stake_accounts(&client, &args)?;
         }
+        Command::SplitAndDelegate(args) => {
+            process_split_and_delegate_stake_account(&client, &args)?;
+        }
     }
     Ok(())
 }
@@ -1,5 +1,8 @@
 use solana_sdk::{
-    clock::SECONDS_PER_DAY, instruction::Instruction, message::Message, pubkey::Pubkey,
+    clock::SECONDS_PER_DAY,
+    instruction::{AccountMeta, Instruction},
+    message::Message,
+    pubkey::Pubkey,
 };
 use solana_stake_program::{
     stake_instruction::{self, LockupArgs},
@@ -275,6 +278,92 @@ pub(crate) fn move_stake_accounts(
         .collect()
 }
 
+// Split `lamports` from a stake account into one new derived account per vote account,
+// in proportion to each vote account's weight, and delegate each new account.
+pub(crate) fn split_and_delegate_stake_account(
+    fee_payer_pubkey: &Pubkey,
+    stake_account_address: &Pubkey,
+    new_base_pubkey: &Pubkey,
+    stake_authority_pubkey: &Pubkey,
+    lamports: u64,
+    vote_weights: &[(Pubkey, u64)],
+) -> Vec<Message> {
+    let total_weight: u128 = vote_weights.iter().map(|(_, weight)| *weight as u128).sum();
+    if total_weight == 0 {
+        return vec![];
+    }
+    let mut remaining_lamports = lamports;
+    vote_weights
+        .iter()
+        .enumerate()
+        .filter_map(|(i, (vote_pubkey, weight))| {
+            // The last vote account absorbs any rounding remainder.
+            let split_lamports = if i == vote_weights.len() - 1 {
+                remaining_lamports
+            } else {
+                (lamports as u128 * *weight as u128 / total_weight) as u64
+            };
+            remaining_lamports -= split_lamports;
+            if split_lamports == 0 {
+                return None;
+            }
+            let new_stake_account_address = derive_stake_account_address(new_base_pubkey, i);
+            let mut instructions = stake_instruction::split_with_seed(
+                stake_account_address,
+                stake_authority_pubkey,
+                split_lamports,
+                &new_stake_account_address,
+                new_base_pubkey,
+                &i.to_string(),
+            );
+            instructions.push(stake_instruction::delegate_stake(
+                &new_stake_account_address,
+                stake_authority_pubkey,
+                vote_pubkey,
+            ));
+            Some(Message::new(&instructions, Some(fee_payer_pubkey)))
+        })
+        .collect()
+}
+
+// Return a copy of `message` that first advances the given nonce account, so that it can be
+// signed with the nonce account's stored blockhash.
+pub(crate) fn add_nonce_instruction(
+    message: &Message,
+    nonce_account_pubkey: &Pubkey,
+    nonce_authority_pubkey: &Pubkey,
+) -> Message {
+    let instructions = message
+        .instructions
+        .iter()
+        .map(|instruction| {
+            let accounts = instruction
+                .accounts
+                .iter()
+                .map(|i| {
+                    let i = *i as usize;
+                    AccountMeta {
+                        pubkey: message.account_keys[i],
+                        is_signer: message.is_signer(i),
+                        is_writable: message.is_writable(i, false),
+                    }
+                })
+                .collect();
+            Instruction {
+                program_id: message.account_keys[instruction.program_id_index as usize],
+                accounts,
+                data: instruction.data.clone(),
+            }
+        })
+        .collect();
+    Message::new_with_nonce(
+        instructions,
+        message.account_keys.first(),
+        nonce_account_pubkey,
+        nonce_authority_pubkey,
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -283,12 +372,16 @@ mod tests {
         account::AccountSharedData,
         client::SyncClient,
         genesis_config::create_genesis_config,
+        native_token::sol_to_lamports,
+        program_utils::limited_deserialize,
         signature::{Keypair, Signer},
     };
-    use solana_stake_program::stake_state::StakeState;
+    use solana_stake_program::{stake_instruction::StakeInstruction, stake_state::StakeState};
+    use solana_vote_program::{vote_instruction, vote_state::VoteInit};
 
     fn create_bank(lamports: u64) -> (Bank, Keypair, u64) {
-        let (genesis_config, mint_keypair) = create_genesis_config(lamports);
+        let (mut genesis_config, mint_keypair) = create_genesis_config(lamports);
+        solana_stake_program::config::add_genesis_account(&mut genesis_config);
         let bank = Bank::new(&genesis_config);
         let rent = bank.get_minimum_balance_for_rent_exemption(std::mem::size_of::<StakeState>());
         (bank, mint_keypair, rent)
@@ -660,6 +753,184 @@ mod tests {
         assert_eq!(authorized.withdrawer, new_withdraw_authority_pubkey);
     }
 
+    fn create_vote_account<C: SyncClient>(client: &C, funding_keypair: &Keypair) -> Pubkey {
+        let vote_keypair = Keypair::new();
+        let node_keypair = Keypair::new();
+        let vote_init = VoteInit {
+            node_pubkey: node_keypair.pubkey(),
+            authorized_voter: vote_keypair.pubkey(),
+            authorized_withdrawer: vote_keypair.pubkey(),
+            commission: 0,
+        };
+        let instructions = vote_instruction::create_account(
+            &funding_keypair.pubkey(),
+            &vote_keypair.pubkey(),
+            &vote_init,
+            sol_to_lamports(1.0),
+        );
+        let message = Message::new(&instructions, Some(&funding_keypair.pubkey()));
+        let signers = [funding_keypair, &vote_keypair, &node_keypair];
+        client.send_and_confirm_message(&signers, message).unwrap();
+        vote_keypair.pubkey()
+    }
+
+    #[test]
+    fn test_split_and_delegate_weights() {
+        let stake_account_address = solana_sdk::pubkey::new_rand();
+        let new_base_pubkey = solana_sdk::pubkey::new_rand();
+        let vote_weights: Vec<_> = [1, 1, 1]
+            .iter()
+            .map(|weight| (solana_sdk::pubkey::new_rand(), *weight))
+            .collect();
+        let get_split_lamports = |messages: &[Message]| {
+            messages
+                .iter()
+                .map(|message| {
+                    let instruction = &message.instructions[1];
+                    limited_deserialize(&instruction.data)
+                        .map(|instruction| match instruction {
+                            StakeInstruction::Split(lamports) => lamports,
+                            _ => panic!("expected split"),
+                        })
+                        .unwrap()
+                })
+                .collect::<Vec<u64>>()
+        };
+
+        // The last vote account picks up the rounding remainder.
+        let messages = split_and_delegate_stake_account(
+            &Pubkey::default(),
+            &stake_account_address,
+            &new_base_pubkey,
+            &Pubkey::default(),
+            100,
+            &vote_weights,
+        );
+        assert_eq!(get_split_lamports(&messages), vec![33, 33, 34]);
+
+        // Vote accounts with no share are skipped.
+        let vote_weights = vec![vote_weights[0], (vote_weights[1].0, 0)];
+        let messages = split_and_delegate_stake_account(
+            &Pubkey::default(),
+            &stake_account_address,
+            &new_base_pubkey,
+            &Pubkey::default(),
+            100,
+            &vote_weights,
+        );
+        assert_eq!(get_split_lamports(&messages), vec![100]);
+
+        assert!(split_and_delegate_stake_account(
+            &Pubkey::default(),
+            &stake_account_address,
+            &new_base_pubkey,
+            &Pubkey::default(),
+            100,
+            &[],
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn test_split_and_delegate_stake_account() {
+        let (bank, funding_keypair, _rent) = create_bank(sol_to_lamports(100.0));
+        let funding_pubkey = funding_keypair.pubkey();
+        let bank_client = BankClient::new(bank);
+        let fee_payer_keypair = create_account(&bank_client, &funding_keypair, 1);
+        let fee_payer_pubkey = fee_payer_keypair.pubkey();
+
+        let base_keypair = Keypair::new();
+        let base_pubkey = base_keypair.pubkey();
+        let stake_authority_keypair = Keypair::new();
+        let stake_authority_pubkey = stake_authority_keypair.pubkey();
+
+        let message = new_stake_account(
+            &fee_payer_pubkey,
+            &funding_pubkey,
+            &base_pubkey,
+            sol_to_lamports(5.0),
+            &stake_authority_pubkey,
+            &solana_sdk::pubkey::new_rand(),
+            &Pubkey::default(),
+            0,
+        );
+        let signers = [&funding_keypair, &fee_payer_keypair, &base_keypair];
+        bank_client
+            .send_and_confirm_message(&signers, message)
+            .unwrap();
+
+        let vote_weights = vec![
+            (create_vote_account(&bank_client, &funding_keypair), 1),
+            (create_vote_account(&bank_client, &funding_keypair), 3),
+        ];
+        let new_base_keypair = Keypair::new();
+        let new_base_pubkey = new_base_keypair.pubkey();
+        let stake_account_address = derive_stake_account_address(&base_pubkey, 0);
+        let messages = split_and_delegate_stake_account(
+            &fee_payer_pubkey,
+            &stake_account_address,
+            &new_base_pubkey,
+            &stake_authority_pubkey,
+            sol_to_lamports(4.0),
+            &vote_weights,
+        );
+        assert_eq!(messages.len(), 2);
+
+        let signers = [
+            &fee_payer_keypair,
+            &new_base_keypair,
+            &stake_authority_keypair,
+        ];
+        for message in messages {
+            bank_client
+                .send_and_confirm_message(&signers, message)
+                .unwrap();
+        }
+
+        for (i, (vote_pubkey, weight)) in vote_weights.iter().enumerate() {
+            let account = get_account_at(&bank_client, &new_base_pubkey, i);
+            assert_eq!(account.lamports, sol_to_lamports(*weight as f64));
+            let delegation = StakeState::delegation_from(&account).unwrap();
+            assert_eq!(delegation.voter_pubkey, *vote_pubkey);
+        }
+        assert_eq!(
+            bank_client.get_balance(&stake_account_address).unwrap(),
+            sol_to_lamports(1.0)
+        );
+    }
+
+    #[test]
+    fn test_add_nonce_instruction() {
+        let fee_payer_pubkey = solana_sdk::pubkey::new_rand();
+        let stake_authority_pubkey = solana_sdk::pubkey::new_rand();
+        let nonce_account_pubkey = solana_sdk::pubkey::new_rand();
+        let nonce_authority_pubkey = solana_sdk::pubkey::new_rand();
+        let instructions = authorize_stake_accounts_instructions(
+            &solana_sdk::pubkey::new_rand(),
+            &stake_authority_pubkey,
+            &stake_authority_pubkey,
+            &solana_sdk::pubkey::new_rand(),
+            &solana_sdk::pubkey::new_rand(),
+        );
+        let message = Message::new(&instructions, Some(&fee_payer_pubkey));
+
+        let nonced_message =
+            add_nonce_instruction(&message, &nonce_account_pubkey, &nonce_authority_pubkey);
+        assert_eq!(
+            nonced_message,
+            Message::new_with_nonce(
+                instructions,
+                Some(&fee_payer_pubkey),
+                &nonce_account_pubkey,
+                &nonce_authority_pubkey,
+            )
+        );
+        assert_eq!(
+            nonced_message.program_id(0),
+            Some(&solana_sdk::system_program::id())
+        );
+    }
+
     #[test]
     fn test_extend_lockup() {
         let lockup = LockupArgs {
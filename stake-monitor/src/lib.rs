@@ -25,6 +25,8 @@ pub enum AccountOperation {
     SystemAccountEnroll,
     FailedToMaintainMinimumBalance,
     MergeSource,
+    RedelegateSource,
+    RedelegateDestination,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -138,7 +140,9 @@ fn process_transaction(
                     StakeInstruction::Authorize(_, _)
                     | StakeInstruction::AuthorizeWithSeed(_)
                     | StakeInstruction::DelegateStake
-                    | StakeInstruction::Deactivate => {
+                    | StakeInstruction::Deactivate
+                    | StakeInstruction::DeactivateDelinquent
+                    | StakeInstruction::GetMinimumDelegation => {
                         // These instructions are always permitted
                     }
                     StakeInstruction::Split(lamports) => {
@@ -200,6 +204,46 @@ fn process_transaction(
                             }
                         }
                     }
+                    StakeInstruction::Redelegate => {
+                        // Redelegate is permitted and, like Split, propagates compliance to the
+                        // account receiving the stake
+                        let source_stake_account_index = instruction.accounts[0] as usize;
+                        let new_stake_account_index = instruction.accounts[1] as usize;
+
+                        let source_stake_pubkey =
+                            message.account_keys[source_stake_account_index].to_string();
+                        let new_stake_pubkey =
+                            message.account_keys[new_stake_account_index].to_string();
+                        let lamports = meta.post_balances[new_stake_account_index]
+                            .saturating_sub(meta.pre_balances[new_stake_account_index]);
+
+                        if let Some(mut source_account_info) =
+                            accounts.get_mut(&source_stake_pubkey)
+                        {
+                            if source_account_info.compliant_since.is_some() {
+                                source_account_info
+                                    .transactions
+                                    .push(AccountTransactionInfo {
+                                        op: AccountOperation::RedelegateSource,
+                                        slot,
+                                        signature: signature.clone(),
+                                    });
+                                source_account_info.lamports =
+                                    source_account_info.lamports.saturating_sub(lamports);
+
+                                let new_account_info = AccountInfo {
+                                    compliant_since: source_account_info.compliant_since,
+                                    lamports,
+                                    transactions: vec![AccountTransactionInfo {
+                                        op: AccountOperation::RedelegateDestination,
+                                        slot,
+                                        signature: signature.clone(),
+                                    }],
+                                };
+                                accounts.insert(new_stake_pubkey, new_account_info);
+                            }
+                        }
+                    }
                     StakeInstruction::Withdraw(_) => {
                         // Withdrawing is not permitted
 
@@ -664,7 +664,7 @@ mod tests {
     use solana_sdk::{hash::Hash, signature::Keypair, system_transaction};
     use solana_storage_proto::convert::generated;
     use solana_transaction_status::{
-        ConfirmedBlock, TransactionStatusMeta, TransactionWithStatusMeta,
+        ConfirmedBlock, TransactionComputeUnits, TransactionStatusMeta, TransactionWithStatusMeta,
     };
     use std::convert::TryInto;
 
@@ -684,6 +684,8 @@ mod tests {
                 log_messages: Some(vec![]),
                 pre_token_balances: Some(vec![]),
                 post_token_balances: Some(vec![]),
+                return_data: None,
+                compute_units: Some(TransactionComputeUnits::new(vec![150])),
             }),
         };
         let block = ConfirmedBlock {
@@ -735,6 +737,7 @@ mod tests {
                 meta.log_messages = None; // Legacy bincode implementation does not support log_messages
                 meta.pre_token_balances = None; // Legacy bincode implementation does not support token balances
                 meta.post_token_balances = None; // Legacy bincode implementation does not support token balances
+                meta.compute_units = None; // Legacy bincode implementation does not support compute units
             }
             assert_eq!(block, bincode_block.into());
         } else {
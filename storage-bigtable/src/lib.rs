@@ -188,6 +188,8 @@ impl From<StoredConfirmedBlockTransactionStatusMeta> for TransactionStatusMeta {
             log_messages: None,
             pre_token_balances: None,
             post_token_balances: None,
+            return_data: None,
+            compute_units: None,
         }
     }
 }
@@ -65,6 +65,10 @@ pub struct TransactionStatusMeta {
     pub pre_token_balances: ::prost::alloc::vec::Vec<TokenBalance>,
     #[prost(message, repeated, tag = "8")]
     pub post_token_balances: ::prost::alloc::vec::Vec<TokenBalance>,
+    #[prost(message, optional, tag = "9")]
+    pub return_data: ::core::option::Option<ReturnData>,
+    #[prost(message, optional, tag = "10")]
+    pub compute_units: ::core::option::Option<ComputeUnits>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct TransactionError {
@@ -97,6 +101,20 @@ pub struct TokenBalance {
     pub ui_token_amount: ::core::option::Option<UiTokenAmount>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReturnData {
+    #[prost(bytes = "vec", tag = "1")]
+    pub program_id: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub data: ::prost::alloc::vec::Vec<u8>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ComputeUnits {
+    #[prost(uint64, tag = "1")]
+    pub consumed: u64,
+    #[prost(uint64, repeated, tag = "2")]
+    pub instructions: ::prost::alloc::vec::Vec<u64>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct UiTokenAmount {
     #[prost(double, tag = "1")]
     pub ui_amount: f64,
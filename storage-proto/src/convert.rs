@@ -5,6 +5,7 @@ use solana_sdk::{
     instruction::CompiledInstruction,
     instruction::InstructionError,
     message::{Message, MessageHeader},
+    process_instruction::TransactionReturnData,
     pubkey::Pubkey,
     signature::Signature,
     transaction::Transaction,
@@ -12,7 +13,8 @@ use solana_sdk::{
 };
 use solana_transaction_status::{
     ConfirmedBlock, InnerInstructions, Reward, RewardType, TransactionByAddrInfo,
-    TransactionStatusMeta, TransactionTokenBalance, TransactionWithStatusMeta,
+    TransactionComputeUnits, TransactionStatusMeta, TransactionTokenBalance,
+    TransactionWithStatusMeta,
 };
 use std::{
     convert::{TryFrom, TryInto},
@@ -267,6 +269,8 @@ impl From<TransactionStatusMeta> for generated::TransactionStatusMeta {
             log_messages,
             pre_token_balances,
             post_token_balances,
+            return_data,
+            compute_units,
         } = value;
         let err = match status {
             Ok(()) => None,
@@ -290,6 +294,8 @@ impl From<TransactionStatusMeta> for generated::TransactionStatusMeta {
             .into_iter()
             .map(|balance| balance.into())
             .collect();
+        let return_data = return_data.map(|return_data| return_data.into());
+        let compute_units = compute_units.map(|compute_units| compute_units.into());
 
         Self {
             err,
@@ -300,6 +306,8 @@ impl From<TransactionStatusMeta> for generated::TransactionStatusMeta {
             log_messages,
             pre_token_balances,
             post_token_balances,
+            return_data,
+            compute_units,
         }
     }
 }
@@ -324,6 +332,8 @@ impl TryFrom<generated::TransactionStatusMeta> for TransactionStatusMeta {
             log_messages,
             pre_token_balances,
             post_token_balances,
+            return_data,
+            compute_units,
         } = value;
         let status = match &err {
             None => Ok(()),
@@ -348,6 +358,8 @@ impl TryFrom<generated::TransactionStatusMeta> for TransactionStatusMeta {
                 .map(|balance| balance.into())
                 .collect(),
         );
+        let return_data = return_data.map(|return_data| return_data.into());
+        let compute_units = compute_units.map(|compute_units| compute_units.into());
         Ok(Self {
             status,
             fee,
@@ -357,6 +369,8 @@ impl TryFrom<generated::TransactionStatusMeta> for TransactionStatusMeta {
             log_messages,
             pre_token_balances,
             post_token_balances,
+            return_data,
+            compute_units,
         })
     }
 }
@@ -421,6 +435,42 @@ impl From<generated::TokenBalance> for TransactionTokenBalance {
     }
 }
 
+impl From<TransactionReturnData> for generated::ReturnData {
+    fn from(value: TransactionReturnData) -> Self {
+        Self {
+            program_id: value.program_id.to_bytes().into(),
+            data: value.data,
+        }
+    }
+}
+
+impl From<generated::ReturnData> for TransactionReturnData {
+    fn from(value: generated::ReturnData) -> Self {
+        Self {
+            program_id: Pubkey::new(&value.program_id),
+            data: value.data,
+        }
+    }
+}
+
+impl From<TransactionComputeUnits> for generated::ComputeUnits {
+    fn from(value: TransactionComputeUnits) -> Self {
+        Self {
+            consumed: value.consumed,
+            instructions: value.instructions,
+        }
+    }
+}
+
+impl From<generated::ComputeUnits> for TransactionComputeUnits {
+    fn from(value: generated::ComputeUnits) -> Self {
+        Self {
+            consumed: value.consumed,
+            instructions: value.instructions,
+        }
+    }
+}
+
 impl From<CompiledInstruction> for generated::CompiledInstruction {
     fn from(value: CompiledInstruction) -> Self {
         Self {
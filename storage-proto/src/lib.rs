@@ -3,9 +3,13 @@ use solana_account_decoder::{
     parse_token::{real_number_string_trimmed, UiTokenAmount},
     StringAmount,
 };
-use solana_sdk::{deserialize_utils::default_on_eof, transaction::Result};
+use solana_sdk::{
+    deserialize_utils::default_on_eof, process_instruction::TransactionReturnData,
+    transaction::Result,
+};
 use solana_transaction_status::{
-    InnerInstructions, Reward, RewardType, TransactionStatusMeta, TransactionTokenBalance,
+    InnerInstructions, Reward, RewardType, TransactionComputeUnits, TransactionStatusMeta,
+    TransactionTokenBalance,
 };
 use std::str::FromStr;
 
@@ -150,6 +154,10 @@ pub struct StoredTransactionStatusMeta {
     pub pre_token_balances: Option<Vec<StoredTransactionTokenBalance>>,
     #[serde(deserialize_with = "default_on_eof")]
     pub post_token_balances: Option<Vec<StoredTransactionTokenBalance>>,
+    #[serde(deserialize_with = "default_on_eof")]
+    pub return_data: Option<TransactionReturnData>,
+    #[serde(deserialize_with = "default_on_eof")]
+    pub compute_units: Option<TransactionComputeUnits>,
 }
 
 impl From<StoredTransactionStatusMeta> for TransactionStatusMeta {
@@ -163,6 +171,8 @@ impl From<StoredTransactionStatusMeta> for TransactionStatusMeta {
             log_messages,
             pre_token_balances,
             post_token_balances,
+            return_data,
+            compute_units,
         } = value;
         Self {
             status,
@@ -175,6 +185,8 @@ impl From<StoredTransactionStatusMeta> for TransactionStatusMeta {
                 .map(|balances| balances.into_iter().map(|balance| balance.into()).collect()),
             post_token_balances: post_token_balances
                 .map(|balances| balances.into_iter().map(|balance| balance.into()).collect()),
+            return_data,
+            compute_units,
         }
     }
 }
@@ -190,6 +202,8 @@ impl From<TransactionStatusMeta> for StoredTransactionStatusMeta {
             log_messages,
             pre_token_balances,
             post_token_balances,
+            return_data,
+            compute_units,
         } = value;
         Self {
             status,
@@ -202,6 +216,8 @@ impl From<TransactionStatusMeta> for StoredTransactionStatusMeta {
                 .map(|balances| balances.into_iter().map(|balance| balance.into()).collect()),
             post_token_balances: post_token_balances
                 .map(|balances| balances.into_iter().map(|balance| balance.into()).collect()),
+            return_data,
+            compute_units,
         }
     }
 }
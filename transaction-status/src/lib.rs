@@ -26,6 +26,7 @@ use solana_sdk::{
     deserialize_utils::default_on_eof,
     instruction::CompiledInstruction,
     message::{Message, MessageHeader},
+    process_instruction::TransactionReturnData,
     pubkey::Pubkey,
     sanitize::Sanitize,
     signature::Signature,
@@ -170,6 +171,24 @@ impl From<InnerInstructions> for UiInnerInstructions {
     }
 }
 
+/// Compute units consumed by a transaction, in total and by each of its
+/// top-level instructions
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionComputeUnits {
+    pub consumed: u64,
+    pub instructions: Vec<u64>,
+}
+
+impl TransactionComputeUnits {
+    pub fn new(instructions: Vec<u64>) -> Self {
+        Self {
+            consumed: instructions.iter().sum(),
+            instructions,
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TransactionStatusMeta {
@@ -185,6 +204,10 @@ pub struct TransactionStatusMeta {
     pub pre_token_balances: Option<Vec<TransactionTokenBalance>>,
     #[serde(deserialize_with = "default_on_eof")]
     pub post_token_balances: Option<Vec<TransactionTokenBalance>>,
+    #[serde(deserialize_with = "default_on_eof")]
+    pub return_data: Option<TransactionReturnData>,
+    #[serde(deserialize_with = "default_on_eof")]
+    pub compute_units: Option<TransactionComputeUnits>,
 }
 
 impl Default for TransactionStatusMeta {
@@ -198,6 +221,35 @@ impl Default for TransactionStatusMeta {
             log_messages: None,
             pre_token_balances: None,
             post_token_balances: None,
+            return_data: None,
+            compute_units: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum UiReturnDataEncoding {
+    Base64,
+}
+
+/// A duplicate representation of TransactionReturnData with the data encoded
+/// for JSON
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UiTransactionReturnData {
+    pub program_id: String,
+    pub data: (String, UiReturnDataEncoding),
+}
+
+impl From<TransactionReturnData> for UiTransactionReturnData {
+    fn from(return_data: TransactionReturnData) -> Self {
+        Self {
+            program_id: return_data.program_id.to_string(),
+            data: (
+                base64::encode(return_data.data),
+                UiReturnDataEncoding::Base64,
+            ),
         }
     }
 }
@@ -215,6 +267,10 @@ pub struct UiTransactionStatusMeta {
     pub log_messages: Option<Vec<String>>,
     pub pre_token_balances: Option<Vec<UiTransactionTokenBalance>>,
     pub post_token_balances: Option<Vec<UiTransactionTokenBalance>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub return_data: Option<UiTransactionReturnData>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compute_units: Option<TransactionComputeUnits>,
 }
 
 impl UiTransactionStatusMeta {
@@ -237,6 +293,8 @@ impl UiTransactionStatusMeta {
             post_token_balances: meta
                 .post_token_balances
                 .map(|balance| balance.into_iter().map(|balance| balance.into()).collect()),
+            return_data: meta.return_data.map(|return_data| return_data.into()),
+            compute_units: meta.compute_units,
         }
     }
 }
@@ -259,6 +317,8 @@ impl From<TransactionStatusMeta> for UiTransactionStatusMeta {
             post_token_balances: meta
                 .post_token_balances
                 .map(|balance| balance.into_iter().map(|balance| balance.into()).collect()),
+            return_data: meta.return_data.map(|return_data| return_data.into()),
+            compute_units: meta.compute_units,
         }
     }
 }
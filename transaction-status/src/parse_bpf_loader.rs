@@ -142,6 +142,20 @@ pub fn parse_bpf_upgradeable_loader(
                 }),
             })
         }
+        UpgradeableLoaderInstruction::ExtendProgramData { additional_bytes } => {
+            check_num_bpf_upgradeable_loader_accounts(&instruction.accounts, 5)?;
+            Ok(ParsedInstructionEnum {
+                instruction_type: "extendProgramData".to_string(),
+                info: json!({
+                    "additionalBytes": additional_bytes,
+                    "programDataAccount": account_keys[instruction.accounts[0] as usize].to_string(),
+                    "authority": account_keys[instruction.accounts[1] as usize].to_string(),
+                    "payerAccount": account_keys[instruction.accounts[2] as usize].to_string(),
+                    "rentSysvar": account_keys[instruction.accounts[3] as usize].to_string(),
+                    "systemProgram": account_keys[instruction.accounts[4] as usize].to_string(),
+                }),
+            })
+        }
     }
 }
 
@@ -156,7 +170,7 @@ fn check_num_bpf_upgradeable_loader_accounts(
 mod test {
     use super::*;
     use serde_json::Value;
-    use solana_sdk::{message::Message, pubkey};
+    use solana_sdk::{message::Message, pubkey, system_program, sysvar};
 
     #[test]
     fn test_parse_bpf_loader_instructions() {
@@ -378,5 +392,26 @@ mod test {
             }
         );
         assert!(parse_bpf_upgradeable_loader(&message.instructions[0], &keys[0..1]).is_err());
+
+        let instruction = solana_sdk::bpf_loader_upgradeable::extend_program_data(
+            &keys[0], &keys[1], &keys[2], 42,
+        );
+        let programdata_address = instruction.accounts[0].pubkey;
+        let message = Message::new(&[instruction], None);
+        assert_eq!(
+            parse_bpf_upgradeable_loader(&message.instructions[0], &message.account_keys).unwrap(),
+            ParsedInstructionEnum {
+                instruction_type: "extendProgramData".to_string(),
+                info: json!({
+                    "additionalBytes": 42,
+                    "programDataAccount": programdata_address.to_string(),
+                    "authority": keys[1].to_string(),
+                    "payerAccount": keys[2].to_string(),
+                    "rentSysvar": sysvar::rent::id().to_string(),
+                    "systemProgram": system_program::id().to_string(),
+                }),
+            }
+        );
+        assert!(parse_bpf_upgradeable_loader(&message.instructions[0], &keys[0..1]).is_err());
     }
 }
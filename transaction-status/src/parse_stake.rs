@@ -2,7 +2,7 @@ use crate::parse_instruction::{
     check_num_accounts, ParsableProgram, ParseInstructionError, ParsedInstructionEnum,
 };
 use bincode::deserialize;
-use serde_json::{json, Map};
+use serde_json::{json, Map, Value};
 use solana_sdk::{instruction::CompiledInstruction, pubkey::Pubkey};
 use solana_stake_program::stake_instruction::StakeInstruction;
 
@@ -14,6 +14,8 @@ pub fn parse_stake(
         .map_err(|_| ParseInstructionError::InstructionNotParsable(ParsableProgram::Stake))?;
     match instruction.accounts.iter().max() {
         Some(index) if (*index as usize) < account_keys.len() => {}
+        // the only instruction that takes no accounts
+        None if stake_instruction == StakeInstruction::GetMinimumDelegation => {}
         _ => {
             // Runtime should prevent this from ever happening
             return Err(ParseInstructionError::InstructionKeyMismatch(
@@ -185,6 +187,37 @@ pub fn parse_stake(
                 info: value,
             })
         }
+        StakeInstruction::Redelegate => {
+            check_num_stake_accounts(&instruction.accounts, 7)?;
+            Ok(ParsedInstructionEnum {
+                instruction_type: "redelegate".to_string(),
+                info: json!({
+                    "stakeAccount": account_keys[instruction.accounts[0] as usize].to_string(),
+                    "newStakeAccount": account_keys[instruction.accounts[1] as usize].to_string(),
+                    "voteAccount": account_keys[instruction.accounts[2] as usize].to_string(),
+                    "clockSysvar": account_keys[instruction.accounts[3] as usize].to_string(),
+                    "stakeHistorySysvar": account_keys[instruction.accounts[4] as usize].to_string(),
+                    "stakeConfigAccount": account_keys[instruction.accounts[5] as usize].to_string(),
+                    "stakeAuthority": account_keys[instruction.accounts[6] as usize].to_string(),
+                }),
+            })
+        }
+        StakeInstruction::DeactivateDelinquent => {
+            check_num_stake_accounts(&instruction.accounts, 4)?;
+            Ok(ParsedInstructionEnum {
+                instruction_type: "deactivateDelinquent".to_string(),
+                info: json!({
+                    "stakeAccount": account_keys[instruction.accounts[0] as usize].to_string(),
+                    "voteAccount": account_keys[instruction.accounts[1] as usize].to_string(),
+                    "referenceVoteAccount": account_keys[instruction.accounts[2] as usize].to_string(),
+                    "clockSysvar": account_keys[instruction.accounts[3] as usize].to_string(),
+                }),
+            })
+        }
+        StakeInstruction::GetMinimumDelegation => Ok(ParsedInstructionEnum {
+            instruction_type: "getMinimumDelegation".to_string(),
+            info: Value::Null,
+        }),
     }
 }
 
@@ -205,7 +238,7 @@ mod test {
     #[allow(clippy::same_item_push)]
     fn test_parse_stake_instruction() {
         let mut keys: Vec<Pubkey> = vec![];
-        for _ in 0..6 {
+        for _ in 0..7 {
             keys.push(Pubkey::new_unique());
         }
 
@@ -393,6 +426,52 @@ mod test {
         );
         assert!(parse_stake(&message.instructions[0], &keys[0..4]).is_err());
 
+        let instructions = stake_instruction::redelegate(&keys[1], &keys[0], &keys[2], &keys[3]);
+        let message = Message::new(&instructions, None);
+        assert_eq!(
+            parse_stake(&message.instructions[1], &keys[0..7]).unwrap(),
+            ParsedInstructionEnum {
+                instruction_type: "redelegate".to_string(),
+                info: json!({
+                    "stakeAccount": keys[2].to_string(),
+                    "newStakeAccount": keys[1].to_string(),
+                    "voteAccount": keys[3].to_string(),
+                    "clockSysvar": keys[4].to_string(),
+                    "stakeHistorySysvar": keys[5].to_string(),
+                    "stakeConfigAccount": keys[6].to_string(),
+                    "stakeAuthority": keys[0].to_string(),
+                }),
+            }
+        );
+        assert!(parse_stake(&message.instructions[1], &keys[0..6]).is_err());
+
+        let instruction =
+            stake_instruction::deactivate_delinquent_stake(&keys[1], &keys[2], &keys[3]);
+        let message = Message::new(&[instruction], None);
+        assert_eq!(
+            parse_stake(&message.instructions[0], &keys[0..4]).unwrap(),
+            ParsedInstructionEnum {
+                instruction_type: "deactivateDelinquent".to_string(),
+                info: json!({
+                    "stakeAccount": keys[0].to_string(),
+                    "voteAccount": keys[1].to_string(),
+                    "referenceVoteAccount": keys[2].to_string(),
+                    "clockSysvar": keys[3].to_string(),
+                }),
+            }
+        );
+        assert!(parse_stake(&message.instructions[0], &keys[0..3]).is_err());
+
+        let instruction = stake_instruction::get_minimum_delegation();
+        let message = Message::new(&[instruction], None);
+        assert_eq!(
+            parse_stake(&message.instructions[0], &keys[0..1]).unwrap(),
+            ParsedInstructionEnum {
+                instruction_type: "getMinimumDelegation".to_string(),
+                info: Value::Null,
+            }
+        );
+
         let seed = "test_seed";
         let instruction = stake_instruction::authorize_with_seed(
             &keys[1],
@@ -1,21 +1,36 @@
 use {
     jsonrpc_core::{MetaIoHandler, Metadata, Result},
-    jsonrpc_core_client::{transports::ipc, RpcError},
-    jsonrpc_derive::rpc,
     jsonrpc_ipc_server::{RequestContext, ServerBuilder},
     jsonrpc_server_utils::tokio,
     log::*,
-    solana_core::validator::{ValidatorExit, ValidatorStartProgress},
-    solana_sdk::signature::{read_keypair_file, Keypair, Signer},
+    solana_admin_rpc::AdminRpc,
+    solana_core::{
+        cluster_info::ClusterInfo,
+        consensus::Tower,
+        gossip_inspect::{GossipInspectConfig, GossipInspection},
+        validator::{ValidatorExit, ValidatorStartProgress},
+    },
+    solana_sdk::{
+        pubkey::Pubkey,
+        signature::{read_keypair_file, Keypair, Signer},
+    },
     std::{
         net::SocketAddr,
-        path::Path,
+        path::{Path, PathBuf},
+        str::FromStr,
         sync::{Arc, RwLock},
         thread::Builder,
         time::SystemTime,
     },
 };
 
+pub use solana_admin_rpc::{connect, runtime};
+
+#[derive(Clone)]
+pub struct AdminRpcRequestMetadataPostInit {
+    pub cluster_info: Arc<ClusterInfo>,
+}
+
 #[derive(Clone)]
 pub struct AdminRpcRequestMetadata {
     pub rpc_addr: Option<SocketAddr>,
@@ -23,33 +38,25 @@ pub struct AdminRpcRequestMetadata {
     pub start_progress: Arc<RwLock<ValidatorStartProgress>>,
     pub validator_exit: Arc<RwLock<ValidatorExit>>,
     pub authorized_voter_keypairs: Arc<RwLock<Vec<Arc<Keypair>>>>,
+    pub ledger_path: PathBuf,
+    /// Populated once the validator has finished starting up
+    pub post_init: Arc<RwLock<Option<AdminRpcRequestMetadataPostInit>>>,
 }
 impl Metadata for AdminRpcRequestMetadata {}
 
-#[rpc]
-pub trait AdminRpc {
-    type Metadata;
-
-    #[rpc(meta, name = "exit")]
-    fn exit(&self, meta: Self::Metadata) -> Result<()>;
-
-    #[rpc(meta, name = "rpcAddress")]
-    fn rpc_addr(&self, meta: Self::Metadata) -> Result<Option<SocketAddr>>;
-
-    #[rpc(name = "setLogFilter")]
-    fn set_log_filter(&self, filter: String) -> Result<()>;
-
-    #[rpc(meta, name = "startTime")]
-    fn start_time(&self, meta: Self::Metadata) -> Result<SystemTime>;
-
-    #[rpc(meta, name = "startProgress")]
-    fn start_progress(&self, meta: Self::Metadata) -> Result<ValidatorStartProgress>;
-
-    #[rpc(meta, name = "addAuthorizedVoter")]
-    fn add_authorized_voter(&self, meta: Self::Metadata, keypair_file: String) -> Result<()>;
-
-    #[rpc(meta, name = "removeAllAuthorizedVoters")]
-    fn remove_all_authorized_voters(&self, meta: Self::Metadata) -> Result<()>;
+impl AdminRpcRequestMetadata {
+    fn with_post_init<F, R>(&self, func: F) -> Result<R>
+    where
+        F: FnOnce(&AdminRpcRequestMetadataPostInit) -> Result<R>,
+    {
+        if let Some(post_init) = self.post_init.read().unwrap().as_ref() {
+            func(post_init)
+        } else {
+            Err(jsonrpc_core::error::Error::invalid_params(
+                "Retry once validator start up is complete",
+            ))
+        }
+    }
 }
 
 pub struct AdminRpcImpl;
@@ -119,6 +126,69 @@ impl AdminRpc for AdminRpcImpl {
         //meta.authorized_voter_keypairs.write().unwrap().clear();
         Ok(())
     }
+
+    fn set_identity(
+        &self,
+        meta: Self::Metadata,
+        keypair_file: String,
+        require_tower: bool,
+    ) -> Result<()> {
+        debug!("set_identity request received");
+
+        let identity_keypair = read_keypair_file(&keypair_file).map_err(|err| {
+            jsonrpc_core::error::Error::invalid_params(format!(
+                "Failed to read identity keypair from {}: {}",
+                keypair_file, err
+            ))
+        })?;
+
+        meta.with_post_init(|post_init| {
+            if require_tower {
+                let _ = Tower::restore(&meta.ledger_path, &identity_keypair.pubkey()).map_err(
+                    |err| {
+                        jsonrpc_core::error::Error::invalid_params(format!(
+                            "Unable to load tower file for identity {}: {}",
+                            identity_keypair.pubkey(),
+                            err
+                        ))
+                    },
+                )?;
+            }
+
+            solana_metrics::set_host_id(identity_keypair.pubkey().to_string());
+            post_init
+                .cluster_info
+                .set_keypair(Arc::new(identity_keypair));
+            warn!("Identity set to {}", post_init.cluster_info.id());
+            Ok(())
+        })
+    }
+
+    fn gossip_inspect(
+        &self,
+        meta: Self::Metadata,
+        config: Option<GossipInspectConfig>,
+    ) -> Result<GossipInspection> {
+        debug!("gossip_inspect request received");
+        let config = config.unwrap_or_default();
+        let origin = config
+            .origin
+            .as_ref()
+            .map(|origin| {
+                Pubkey::from_str(origin).map_err(|err| {
+                    jsonrpc_core::error::Error::invalid_params(format!(
+                        "Invalid origin {}: {}",
+                        origin, err
+                    ))
+                })
+            })
+            .transpose()?;
+        meta.with_post_init(|post_init| {
+            Ok(post_init
+                .cluster_info
+                .inspect_gossip(config.label.as_deref(), origin.as_ref()))
+        })
+    }
 }
 
 // Start the Admin RPC interface
@@ -164,20 +234,3 @@ pub fn run(ledger_path: &Path, metadata: AdminRpcRequestMetadata) {
         })
         .unwrap();
 }
-
-// Connect to the Admin RPC interface
-pub async fn connect(ledger_path: &Path) -> std::result::Result<gen_client::Client, RpcError> {
-    let admin_rpc_path = ledger_path.join("admin.rpc");
-    if !admin_rpc_path.exists() {
-        Err(RpcError::Client(format!(
-            "{} does not exist",
-            admin_rpc_path.display()
-        )))
-    } else {
-        ipc::connect::<_, gen_client::Client>(&format!("{}", admin_rpc_path.display())).await
-    }
-}
-
-pub fn runtime() -> jsonrpc_server_utils::tokio::runtime::Runtime {
-    jsonrpc_server_utils::tokio::runtime::Runtime::new().expect("new tokio runtime")
-}
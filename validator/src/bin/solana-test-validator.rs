@@ -30,7 +30,7 @@ use {
         net::{IpAddr, Ipv4Addr, SocketAddr},
         path::{Path, PathBuf},
         process::exit,
-        sync::mpsc::channel,
+        sync::{mpsc::channel, Arc},
         time::{Duration, SystemTime, UNIX_EPOCH},
     },
 };
@@ -415,6 +415,8 @@ fn main() {
             start_time: std::time::SystemTime::now(),
             validator_exit: genesis.validator_exit.clone(),
             authorized_voter_keypairs: genesis.authorized_voter_keypairs.clone(),
+            ledger_path: ledger_path.clone(),
+            post_init: Arc::default(),
         },
     );
     let dashboard = if output == Output::Dashboard {
@@ -1,15 +1,22 @@
 use {
     crate::{admin_rpc_service, new_spinner_progress_bar, println_name_value},
     console::style,
-    indicatif::ProgressBar,
+    indicatif::{MultiProgress, ProgressBar, ProgressDrawTarget, ProgressStyle},
     solana_client::{
-        client_error, rpc_client::RpcClient, rpc_request, rpc_response::RpcContactInfo,
+        client_error,
+        rpc_client::RpcClient,
+        rpc_request,
+        rpc_response::{RpcContactInfo, RpcVoteAccountInfo, RpcVoteAccountStatus},
     },
     solana_core::validator::ValidatorStartProgress,
     solana_sdk::{
-        clock::Slot, commitment_config::CommitmentConfig, native_token::Sol, pubkey::Pubkey,
+        clock::{Epoch, Slot, DEFAULT_MS_PER_SLOT},
+        commitment_config::CommitmentConfig,
+        native_token::Sol,
+        pubkey::Pubkey,
     },
     std::{
+        collections::HashSet,
         io,
         net::SocketAddr,
         path::{Path, PathBuf},
@@ -22,6 +29,11 @@ use {
     },
 };
 
+/// Number of upcoming leader slots listed
+const MAX_UPCOMING_LEADER_SLOTS: usize = 4;
+/// Number of past leader slots checked for produced blocks
+const MAX_RECENT_LEADER_SLOTS: usize = 16;
+
 pub struct Dashboard {
     progress_bar: ProgressBar,
     ledger_path: PathBuf,
@@ -105,8 +117,15 @@ impl Dashboard {
                 }
             }
 
-            let progress_bar = new_spinner_progress_bar();
+            let multi_progress = MultiProgress::with_draw_target(ProgressDrawTarget::stdout());
+            let leader_bars: Vec<_> = (0..3)
+                .map(|_| multi_progress.add(new_message_bar()))
+                .collect();
+            let progress_bar = multi_progress.add(new_spinner_progress_bar());
+            let multi_progress_thread = thread::spawn(move || multi_progress.join());
+
             let mut snapshot_slot = None;
+            let mut leader_stats = None;
             for i in 0.. {
                 if exit.load(Ordering::Relaxed) {
                     break;
@@ -125,6 +144,27 @@ impl Dashboard {
                         identity_balance,
                         health,
                     )) => {
+                        if i % 10 == 0 {
+                            leader_stats = get_leader_stats(
+                                &rpc_client,
+                                &identity,
+                                finalized_slot,
+                                leader_stats.take(),
+                            )
+                            .ok();
+                        }
+                        let leader_messages = match &leader_stats {
+                            Some(leader_stats) => leader_stats.messages(processed_slot),
+                            None => vec!["Leader schedule unavailable".to_string()],
+                        };
+                        for (bar, message) in leader_bars.iter().zip(
+                            leader_messages
+                                .into_iter()
+                                .chain(std::iter::repeat_with(String::new)),
+                        ) {
+                            bar.set_message(&message);
+                        }
+
                         let uptime = {
                             let uptime =
                                 chrono::Duration::from_std(start_time.elapsed().unwrap()).unwrap();
@@ -151,7 +191,11 @@ impl Dashboard {
                             if max_retransmit_slot == 0 {
                                 "".to_string()
                             } else {
-                                format!("| Max Slot: {} ", max_retransmit_slot)
+                                format!(
+                                    "| Max Slot: {} ({} ahead) ",
+                                    max_retransmit_slot,
+                                    max_retransmit_slot.saturating_sub(processed_slot)
+                                )
                             },
                             processed_slot,
                             confirmed_slot,
@@ -171,10 +215,211 @@ impl Dashboard {
                     }
                 }
             }
+
+            for bar in leader_bars.iter().chain(std::iter::once(&progress_bar)) {
+                if !bar.is_finished() {
+                    bar.abandon();
+                }
+            }
+            let _ = multi_progress_thread.join();
         }
     }
 }
 
+/// Creates a new progress bar that only displays a message, for the lines
+/// of the dashboard above the status line
+fn new_message_bar() -> ProgressBar {
+    let progress_bar = ProgressBar::new(42);
+    progress_bar.set_style(ProgressStyle::default_spinner().template("  {wide_msg}"));
+    progress_bar
+}
+
+fn format_eta(eta: Duration) -> String {
+    let secs = eta.as_secs();
+    if secs >= 3600 {
+        format!("{}h {}m", secs / 3600, secs % 3600 / 60)
+    } else if secs >= 60 {
+        format!("{}m {}s", secs / 60, secs % 60)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
+/// Leader schedule and voting details for the validator identity. These take
+/// several RPC calls, so are refreshed less often than the status line.
+struct LeaderStats {
+    epoch: Epoch,
+    /// Leader slots of the identity in the current epoch, in ascending order
+    leader_slots: Vec<Slot>,
+    /// The most recent leader slots up to the finalized slot, and whether a
+    /// block was produced in each
+    recent_leader_slots: Vec<(Slot, bool)>,
+    /// Recently observed time per slot, used to estimate leader slot ETAs
+    slot_duration: Duration,
+    vote_account: Option<RpcVoteAccountInfo>,
+}
+
+impl LeaderStats {
+    fn messages(&self, processed_slot: Slot) -> Vec<String> {
+        let upcoming_leader_slots: Vec<_> = self
+            .leader_slots
+            .iter()
+            .filter(|slot| **slot > processed_slot)
+            .collect();
+        let upcoming = match upcoming_leader_slots.first() {
+            None => "Upcoming Leader Slots: none this epoch".to_string(),
+            Some(next_leader_slot) => format!(
+                "Upcoming Leader Slots: {} (in {}){} | {} remaining this epoch",
+                next_leader_slot,
+                format_eta(self.slot_duration * (*next_leader_slot - processed_slot) as u32),
+                upcoming_leader_slots
+                    .iter()
+                    .skip(1)
+                    .take(MAX_UPCOMING_LEADER_SLOTS - 1)
+                    .map(|slot| format!(", {}", slot))
+                    .collect::<String>(),
+                upcoming_leader_slots.len(),
+            ),
+        };
+
+        let skipped_slots: Vec<_> = self
+            .recent_leader_slots
+            .iter()
+            .filter(|(_slot, produced)| !produced)
+            .map(|(slot, _produced)| slot.to_string())
+            .collect();
+        let recent = if self.recent_leader_slots.is_empty() {
+            "Recent Leader Slots: none this epoch".to_string()
+        } else {
+            format!(
+                "Recent Leader Slots: {} of {} produced{}",
+                self.recent_leader_slots.len() - skipped_slots.len(),
+                self.recent_leader_slots.len(),
+                if skipped_slots.is_empty() {
+                    "".to_string()
+                } else {
+                    format!(
+                        " | {}",
+                        style(format!("Skipped: {}", skipped_slots.join(", "))).yellow()
+                    )
+                },
+            )
+        };
+
+        let votes = match &self.vote_account {
+            None => "Vote Account: none".to_string(),
+            Some(vote_account) => format!(
+                "Vote Account: {} | Last Vote: {} ({} behind) | Root Slot: {} | \
+                 Credits This Epoch: {}",
+                vote_account.vote_pubkey,
+                vote_account.last_vote,
+                processed_slot.saturating_sub(vote_account.last_vote),
+                vote_account.root_slot,
+                vote_account
+                    .epoch_credits
+                    .iter()
+                    .find(|(epoch, _credits, _prev_credits)| *epoch == self.epoch)
+                    .map(|(_epoch, credits, prev_credits)| credits.saturating_sub(*prev_credits))
+                    .unwrap_or_default(),
+            ),
+        };
+
+        vec![upcoming, recent, votes]
+    }
+}
+
+fn get_leader_stats(
+    rpc_client: &RpcClient,
+    identity: &Pubkey,
+    finalized_slot: Slot,
+    previous_leader_stats: Option<LeaderStats>,
+) -> client_error::Result<LeaderStats> {
+    let epoch_info = rpc_client.get_epoch_info()?;
+    let vote_pubkey = previous_leader_stats
+        .as_ref()
+        .and_then(|leader_stats| leader_stats.vote_account.as_ref())
+        .map(|vote_account| vote_account.vote_pubkey.clone());
+    let first_slot_in_epoch = epoch_info.absolute_slot - epoch_info.slot_index;
+
+    // The leader schedule is fixed for the epoch, so only fetch it once
+    let leader_slots = match previous_leader_stats {
+        Some(leader_stats) if leader_stats.epoch == epoch_info.epoch => leader_stats.leader_slots,
+        _ => {
+            let mut leader_slots: Vec<_> = rpc_client
+                .get_leader_schedule(Some(epoch_info.absolute_slot))?
+                .and_then(|mut leader_schedule| leader_schedule.remove(&identity.to_string()))
+                .unwrap_or_default()
+                .into_iter()
+                .map(|slot_index| first_slot_in_epoch + slot_index as Slot)
+                .collect();
+            leader_slots.sort_unstable();
+            leader_slots
+        }
+    };
+
+    let mut recent_leader_slots: Vec<_> = leader_slots
+        .iter()
+        .copied()
+        .filter(|slot| *slot <= finalized_slot)
+        .rev()
+        .take(MAX_RECENT_LEADER_SLOTS)
+        .collect();
+    recent_leader_slots.reverse();
+    let recent_leader_slots = match (recent_leader_slots.first(), recent_leader_slots.last()) {
+        (Some(first_slot), Some(last_slot)) => {
+            let blocks: HashSet<_> = rpc_client
+                .get_confirmed_blocks(*first_slot, Some(*last_slot))?
+                .into_iter()
+                .collect();
+            recent_leader_slots
+                .iter()
+                .map(|slot| (*slot, blocks.contains(slot)))
+                .collect()
+        }
+        _ => vec![],
+    };
+
+    let slot_duration = rpc_client
+        .get_recent_performance_samples(Some(1))?
+        .first()
+        .filter(|sample| sample.num_slots > 0)
+        .map(|sample| {
+            Duration::from_secs(u64::from(sample.sample_period_secs)) / sample.num_slots as u32
+        })
+        .unwrap_or_else(|| Duration::from_millis(DEFAULT_MS_PER_SLOT));
+
+    let vote_account = find_vote_account(
+        rpc_client.get_vote_accounts_with_commitment(CommitmentConfig::processed())?,
+        identity,
+        vote_pubkey.as_deref(),
+    );
+
+    Ok(LeaderStats {
+        epoch: epoch_info.epoch,
+        leader_slots,
+        recent_leader_slots,
+        slot_duration,
+        vote_account,
+    })
+}
+
+/// Finds the vote account of the identity, by the vote pubkey once it has
+/// been found by the node pubkey
+fn find_vote_account(
+    vote_accounts: RpcVoteAccountStatus,
+    identity: &Pubkey,
+    vote_pubkey: Option<&str>,
+) -> Option<RpcVoteAccountInfo> {
+    vote_accounts
+        .current
+        .into_iter()
+        .chain(vote_accounts.delinquent.into_iter())
+        .find(|vote_account| match vote_pubkey {
+            Some(vote_pubkey) => vote_account.vote_pubkey == vote_pubkey,
+            None => vote_account.node_pubkey == identity.to_string(),
+        })
+}
+
 async fn wait_for_validator_startup(
     ledger_path: &Path,
     exit: &Arc<AtomicBool>,
@@ -286,3 +531,98 @@ fn get_validator_stats(
         health,
     ))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_vote_account(vote_pubkey: &Pubkey, node_pubkey: &Pubkey) -> RpcVoteAccountInfo {
+        RpcVoteAccountInfo {
+            vote_pubkey: vote_pubkey.to_string(),
+            node_pubkey: node_pubkey.to_string(),
+            activated_stake: 42,
+            commission: 0,
+            epoch_vote_account: true,
+            epoch_credits: vec![(1, 10, 0), (2, 25, 10)],
+            last_vote: 95,
+            root_slot: 64,
+        }
+    }
+
+    #[test]
+    fn test_format_eta() {
+        assert_eq!(format_eta(Duration::from_millis(400)), "0s");
+        assert_eq!(format_eta(Duration::from_secs(59)), "59s");
+        assert_eq!(format_eta(Duration::from_secs(60)), "1m 0s");
+        assert_eq!(format_eta(Duration::from_secs(3599)), "59m 59s");
+        assert_eq!(format_eta(Duration::from_secs(3600)), "1h 0m");
+        assert_eq!(format_eta(Duration::from_secs(26 * 3600 + 61)), "26h 1m");
+    }
+
+    #[test]
+    fn test_leader_stats_messages() {
+        console::set_colors_enabled(false);
+        let vote_pubkey = Pubkey::new_unique();
+        let leader_stats = LeaderStats {
+            epoch: 2,
+            leader_slots: vec![80, 90, 104, 105, 106, 107, 108],
+            recent_leader_slots: vec![(80, true), (90, false)],
+            slot_duration: Duration::from_millis(500),
+            vote_account: Some(new_vote_account(&vote_pubkey, &Pubkey::new_unique())),
+        };
+        assert_eq!(
+            leader_stats.messages(100),
+            vec![
+                "Upcoming Leader Slots: 104 (in 2s), 105, 106, 107 | 5 remaining this epoch"
+                    .to_string(),
+                "Recent Leader Slots: 1 of 2 produced | Skipped: 90".to_string(),
+                format!(
+                    "Vote Account: {} | Last Vote: 95 (5 behind) | Root Slot: 64 | \
+                     Credits This Epoch: 15",
+                    vote_pubkey
+                ),
+            ]
+        );
+
+        let leader_stats = LeaderStats {
+            leader_slots: vec![],
+            recent_leader_slots: vec![],
+            vote_account: None,
+            ..leader_stats
+        };
+        assert_eq!(
+            leader_stats.messages(100),
+            vec![
+                "Upcoming Leader Slots: none this epoch".to_string(),
+                "Recent Leader Slots: none this epoch".to_string(),
+                "Vote Account: none".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_vote_account() {
+        let identity = Pubkey::new_unique();
+        let vote_pubkey = Pubkey::new_unique();
+        let other_vote_pubkey = Pubkey::new_unique();
+        let vote_accounts = RpcVoteAccountStatus {
+            current: vec![new_vote_account(&other_vote_pubkey, &Pubkey::new_unique())],
+            delinquent: vec![new_vote_account(&vote_pubkey, &identity)],
+        };
+
+        let find = |vote_pubkey: Option<&Pubkey>| {
+            find_vote_account(
+                vote_accounts.clone(),
+                &identity,
+                vote_pubkey.map(ToString::to_string).as_deref(),
+            )
+            .map(|vote_account| vote_account.vote_pubkey)
+        };
+        assert_eq!(find(None), Some(vote_pubkey.to_string()));
+        assert_eq!(
+            find(Some(&other_vote_pubkey)),
+            Some(other_vote_pubkey.to_string())
+        );
+        assert_eq!(find(Some(&Pubkey::new_unique())), None);
+    }
+}
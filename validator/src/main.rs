@@ -7,7 +7,7 @@ use {
     console::style,
     fd_lock::FdLock,
     log::*,
-    rand::{seq::SliceRandom, thread_rng, Rng},
+    rand::{seq::SliceRandom, thread_rng},
     solana_clap_utils::{
         input_parsers::{keypair_of, keypairs_of, pubkey_of, value_of},
         input_validators::{
@@ -34,7 +34,7 @@ use {
             is_snapshot_config_invalid, Validator, ValidatorConfig, ValidatorStartProgress,
         },
     },
-    solana_download_utils::{download_genesis_if_missing, download_snapshot},
+    solana_download_utils::{download_genesis_if_missing, download_snapshot_from_peers},
     solana_ledger::blockstore_db::BlockstoreRecoveryMode,
     solana_perf::recycler::enable_recycler_warming,
     solana_runtime::{
@@ -379,6 +379,7 @@ fn start_gossip_node(
     (cluster_info, gossip_exit_flag, gossip_service)
 }
 
+#[allow(clippy::type_complexity)]
 fn get_rpc_node(
     cluster_info: &ClusterInfo,
     cluster_entrypoints: &[ContactInfo],
@@ -387,7 +388,7 @@ fn get_rpc_node(
     snapshot_not_required: bool,
     no_untrusted_rpc: bool,
     snapshot_output_dir: &Path,
-) -> Option<(ContactInfo, Option<(Slot, Hash)>)> {
+) -> Option<(Vec<ContactInfo>, Option<(Slot, Hash)>)> {
     let mut blacklist_timeout = Instant::now();
     let mut newer_cluster_snapshot_timeout = None;
     let mut retry_reason = None;
@@ -547,9 +548,10 @@ fn get_rpc_node(
         };
 
         if !eligible_rpc_peers.is_empty() {
-            let contact_info =
-                &eligible_rpc_peers[thread_rng().gen_range(0, eligible_rpc_peers.len())];
-            return Some((contact_info.clone(), highest_snapshot_hash));
+            // The first peer is used for RPC, the rest are extra snapshot sources
+            let mut eligible_rpc_peers = eligible_rpc_peers;
+            eligible_rpc_peers.shuffle(&mut thread_rng());
+            return Some((eligible_rpc_peers, highest_snapshot_hash));
         } else {
             retry_reason = Some("No snapshots available".to_owned());
         }
@@ -860,7 +862,8 @@ fn rpc_bootstrap(
         if rpc_node_details.is_none() {
             return;
         }
-        let (rpc_contact_info, snapshot_hash) = rpc_node_details.unwrap();
+        let (rpc_nodes, snapshot_hash) = rpc_node_details.unwrap();
+        let rpc_contact_info = &rpc_nodes[0];
 
         info!(
             "Using RPC service from node {}: {:?}",
@@ -949,8 +952,10 @@ fn rpc_bootstrap(
                                 gossip.take().unwrap();
                             cluster_info.save_contact_info();
                             gossip_exit_flag.store(true, Ordering::Relaxed);
-                            let ret = download_snapshot(
-                                &rpc_contact_info.rpc,
+                            let rpc_addrs: Vec<_> =
+                                rpc_nodes.iter().map(|rpc_node| rpc_node.rpc).collect();
+                            let ret = download_snapshot_from_peers(
+                                &rpc_addrs,
                                 &snapshot_output_dir,
                                 snapshot_hash,
                                 use_progress_bar,
@@ -1813,6 +1818,27 @@ pub fn main() {
             SubCommand::with_name("run")
             .about("Run the validator")
         )
+        .subcommand(
+            SubCommand::with_name("set-identity")
+            .about("Set the validator identity")
+            .arg(
+                Arg::with_name("identity")
+                    .index(1)
+                    .value_name("KEYPAIR")
+                    .required(true)
+                    .takes_value(true)
+                    .validator(is_keypair)
+                    .help("Validator identity keypair")
+            )
+            .arg(
+                clap::Arg::with_name("require_tower")
+                    .long("require-tower")
+                    .takes_value(false)
+                    .help("Refuse to set the validator identity if saved tower state is not found"),
+            )
+            .after_help("Note: the new identity only applies to the \
+                         currently running validator instance")
+        )
         .subcommand(
             SubCommand::with_name("set-log-filter")
             .about("Adjust the validator log filter")
@@ -1927,6 +1953,30 @@ pub fn main() {
             monitor_validator(&ledger_path);
             return;
         }
+        ("set-identity", Some(subcommand_matches)) => {
+            let identity_keypair = value_t_or_exit!(subcommand_matches, "identity", String);
+            let require_tower = subcommand_matches.is_present("require_tower");
+
+            let identity_keypair = fs::canonicalize(&identity_keypair).unwrap_or_else(|err| {
+                println!("Unable to access path: {}: {:?}", identity_keypair, err);
+                exit(1);
+            });
+            println!("Validator identity: {}", identity_keypair.display());
+
+            let admin_client = admin_rpc_service::connect(&ledger_path);
+            admin_rpc_service::runtime()
+                .block_on(async move {
+                    admin_client
+                        .await?
+                        .set_identity(identity_keypair.display().to_string(), require_tower)
+                        .await
+                })
+                .unwrap_or_else(|err| {
+                    println!("setIdentity request failed: {}", err);
+                    exit(1);
+                });
+            return;
+        }
         ("set-log-filter", Some(subcommand_matches)) => {
             let filter = value_t_or_exit!(subcommand_matches, "filter", String);
             let admin_client = admin_rpc_service::connect(&ledger_path);
@@ -2065,7 +2115,6 @@ pub fn main() {
             enable_bigtable_ledger_storage: matches
                 .is_present("enable_rpc_bigtable_ledger_storage"),
             enable_bigtable_ledger_upload: matches.is_present("enable_bigtable_ledger_upload"),
-            identity_pubkey: identity_keypair.pubkey(),
             faucet_addr: matches.value_of("rpc_faucet_addr").map(|address| {
                 solana_net_utils::parse_host_port(address).expect("failed to parse faucet address")
             }),
@@ -2342,6 +2391,7 @@ pub fn main() {
     info!("Starting validator with: {:#?}", std::env::args_os());
 
     let start_progress = Arc::new(RwLock::new(ValidatorStartProgress::default()));
+    let admin_service_post_init = Arc::new(RwLock::new(None));
     admin_rpc_service::run(
         &ledger_path,
         admin_rpc_service::AdminRpcRequestMetadata {
@@ -2350,6 +2400,8 @@ pub fn main() {
             validator_exit: validator_config.validator_exit.clone(),
             start_progress: start_progress.clone(),
             authorized_voter_keypairs: authorized_voter_keypairs.clone(),
+            ledger_path: ledger_path.clone(),
+            post_init: admin_service_post_init.clone(),
         },
     );
 
@@ -2489,6 +2541,10 @@ pub fn main() {
         should_check_duplicate_instance,
         start_progress,
     );
+    *admin_service_post_init.write().unwrap() =
+        Some(admin_rpc_service::AdminRpcRequestMetadataPostInit {
+            cluster_info: validator.cluster_info.clone(),
+        });
 
     if let Some(filename) = init_complete_file {
         File::create(filename).unwrap_or_else(|_| {
@@ -0,0 +1,280 @@
+use crate::args::{Args, Command, NewArgs, RevokeArgs, ShowArgs, WithdrawArgs};
+use clap::{value_t_or_exit, App, Arg, ArgMatches, SubCommand};
+use solana_clap_utils::{
+    input_parsers::unix_timestamp_from_rfc3339_datetime,
+    input_validators::{is_parsable, is_rfc3339_datetime, is_valid_pubkey, is_valid_signer},
+};
+use solana_cli_config::CONFIG_FILE;
+use std::ffi::OsString;
+use std::process::exit;
+
+fn fee_payer_arg<'a, 'b>() -> Arg<'a, 'b> {
+    solana_clap_utils::fee_payer::fee_payer_arg().required(true)
+}
+
+fn grant_pubkey_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("grant_pubkey")
+        .required(true)
+        .takes_value(true)
+        .value_name("GRANT_PUBKEY")
+        .validator(is_valid_pubkey)
+        .help("Address of the token grant")
+}
+
+fn amount_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name("amount")
+        .required(true)
+        .takes_value(true)
+        .value_name("AMOUNT")
+        .validator(is_parsable::<u64>)
+        .help("Number of tokens, in the mint's base units")
+}
+
+fn date_arg<'a, 'b>(name: &'a str, long: &'a str, help: &'a str) -> Arg<'a, 'b> {
+    Arg::with_name(name)
+        .long(long)
+        .takes_value(true)
+        .value_name("RFC3339 DATETIME")
+        .validator(is_rfc3339_datetime)
+        .help(help)
+}
+
+pub(crate) fn get_matches<'a, I, T>(args: I) -> ArgMatches<'a>
+where
+    I: IntoIterator<Item = T>,
+    T: Into<OsString> + Clone,
+{
+    let default_config_file = CONFIG_FILE.as_ref().unwrap();
+    App::new("solana-vest-accounts")
+        .about("about")
+        .version("version")
+        .arg(
+            Arg::with_name("config_file")
+                .long("config")
+                .takes_value(true)
+                .value_name("FILEPATH")
+                .default_value(default_config_file)
+                .help("Config file"),
+        )
+        .arg(
+            Arg::with_name("url")
+                .long("url")
+                .global(true)
+                .takes_value(true)
+                .value_name("URL")
+                .help("RPC entrypoint address. i.e. http://devnet.solana.com"),
+        )
+        .subcommand(
+            SubCommand::with_name("new")
+                .about("Create a token grant vesting on a cliff plus linear schedule")
+                .arg(fee_payer_arg())
+                .arg(
+                    Arg::with_name("funding_token_pubkey")
+                        .required(true)
+                        .index(1)
+                        .takes_value(true)
+                        .value_name("FUNDING_TOKEN_ACCOUNT")
+                        .validator(is_valid_pubkey)
+                        .help("Token account to fund the grant"),
+                )
+                .arg(
+                    Arg::with_name("grant_keypair")
+                        .required(true)
+                        .index(2)
+                        .takes_value(true)
+                        .value_name("GRANT_KEYPAIR")
+                        .validator(is_valid_signer)
+                        .help("Keypair of the new grant account"),
+                )
+                .arg(
+                    Arg::with_name("escrow_keypair")
+                        .required(true)
+                        .index(3)
+                        .takes_value(true)
+                        .value_name("ESCROW_KEYPAIR")
+                        .validator(is_valid_signer)
+                        .help("Keypair of the new token account holding the granted tokens"),
+                )
+                .arg(amount_arg().index(4))
+                .arg(
+                    Arg::with_name("funding_authority")
+                        .long("funding-authority")
+                        .required(true)
+                        .takes_value(true)
+                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
+                        .help("Owner of the funding token account"),
+                )
+                .arg(
+                    Arg::with_name("mint_pubkey")
+                        .long("mint")
+                        .required(true)
+                        .takes_value(true)
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .help("Mint of the granted tokens"),
+                )
+                .arg(
+                    Arg::with_name("terminator")
+                        .long("terminator")
+                        .required(true)
+                        .takes_value(true)
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .help("Authority to revoke unvested tokens"),
+                )
+                .arg(
+                    Arg::with_name("payee")
+                        .long("payee")
+                        .required(true)
+                        .takes_value(true)
+                        .value_name("PUBKEY")
+                        .validator(is_valid_pubkey)
+                        .help("Authority to withdraw vested tokens"),
+                )
+                .arg(
+                    date_arg(
+                        "start_date",
+                        "start-date",
+                        "The date and time at which tokens begin to vest",
+                    )
+                    .required(true),
+                )
+                .arg(date_arg(
+                    "cliff_date",
+                    "cliff-date",
+                    "The date and time before which no tokens are released [default: start date]",
+                ))
+                .arg(
+                    date_arg(
+                        "end_date",
+                        "end-date",
+                        "The date and time at which all tokens are vested",
+                    )
+                    .required(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("withdraw")
+                .about("Withdraw vested tokens from a grant")
+                .arg(fee_payer_arg())
+                .arg(grant_pubkey_arg().index(1))
+                .arg(
+                    Arg::with_name("destination_pubkey")
+                        .required(true)
+                        .index(2)
+                        .takes_value(true)
+                        .value_name("DESTINATION_TOKEN_ACCOUNT")
+                        .validator(is_valid_pubkey)
+                        .help("Token account to receive the tokens"),
+                )
+                .arg(amount_arg().index(3))
+                .arg(
+                    Arg::with_name("payee")
+                        .long("payee")
+                        .required(true)
+                        .takes_value(true)
+                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
+                        .help("Payee of the grant"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("revoke")
+                .about("Revoke the unvested tokens of a grant")
+                .arg(fee_payer_arg())
+                .arg(grant_pubkey_arg().index(1))
+                .arg(
+                    Arg::with_name("refund_pubkey")
+                        .required(true)
+                        .index(2)
+                        .takes_value(true)
+                        .value_name("REFUND_TOKEN_ACCOUNT")
+                        .validator(is_valid_pubkey)
+                        .help("Token account to receive the unvested tokens"),
+                )
+                .arg(
+                    Arg::with_name("terminator")
+                        .long("terminator")
+                        .required(true)
+                        .takes_value(true)
+                        .value_name("KEYPAIR")
+                        .validator(is_valid_signer)
+                        .help("Terminator of the grant"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("show")
+                .about("Show the state of a token grant")
+                .arg(grant_pubkey_arg().index(1)),
+        )
+        .get_matches_from(args)
+}
+
+fn parse_new_args(matches: &ArgMatches<'_>) -> NewArgs<String, String> {
+    NewArgs {
+        fee_payer: value_t_or_exit!(matches, "fee_payer", String),
+        funding_token_pubkey: value_t_or_exit!(matches, "funding_token_pubkey", String),
+        funding_authority: value_t_or_exit!(matches, "funding_authority", String),
+        grant_keypair: value_t_or_exit!(matches, "grant_keypair", String),
+        escrow_keypair: value_t_or_exit!(matches, "escrow_keypair", String),
+        mint_pubkey: value_t_or_exit!(matches, "mint_pubkey", String),
+        terminator: value_t_or_exit!(matches, "terminator", String),
+        payee: value_t_or_exit!(matches, "payee", String),
+        amount: value_t_or_exit!(matches, "amount", u64),
+        start_date: unix_timestamp_from_rfc3339_datetime(matches, "start_date").unwrap(),
+        cliff_date: unix_timestamp_from_rfc3339_datetime(matches, "cliff_date"),
+        end_date: unix_timestamp_from_rfc3339_datetime(matches, "end_date").unwrap(),
+    }
+}
+
+fn parse_withdraw_args(matches: &ArgMatches<'_>) -> WithdrawArgs<String, String> {
+    WithdrawArgs {
+        fee_payer: value_t_or_exit!(matches, "fee_payer", String),
+        grant_pubkey: value_t_or_exit!(matches, "grant_pubkey", String),
+        payee: value_t_or_exit!(matches, "payee", String),
+        destination_pubkey: value_t_or_exit!(matches, "destination_pubkey", String),
+        amount: value_t_or_exit!(matches, "amount", u64),
+    }
+}
+
+fn parse_revoke_args(matches: &ArgMatches<'_>) -> RevokeArgs<String, String> {
+    RevokeArgs {
+        fee_payer: value_t_or_exit!(matches, "fee_payer", String),
+        grant_pubkey: value_t_or_exit!(matches, "grant_pubkey", String),
+        terminator: value_t_or_exit!(matches, "terminator", String),
+        refund_pubkey: value_t_or_exit!(matches, "refund_pubkey", String),
+    }
+}
+
+fn parse_show_args(matches: &ArgMatches<'_>) -> ShowArgs<String> {
+    ShowArgs {
+        grant_pubkey: value_t_or_exit!(matches, "grant_pubkey", String),
+    }
+}
+
+pub(crate) fn parse_args<I, T>(args: I) -> Args<String, String>
+where
+    I: IntoIterator<Item = T>,
+    T: Into<OsString> + Clone,
+{
+    let matches = get_matches(args);
+    let config_file = matches.value_of("config_file").unwrap().to_string();
+    let url = matches.value_of("url").map(|x| x.to_string());
+
+    let command = match matches.subcommand() {
+        ("new", Some(matches)) => Command::New(Box::new(parse_new_args(matches))),
+        ("withdraw", Some(matches)) => Command::Withdraw(parse_withdraw_args(matches)),
+        ("revoke", Some(matches)) => Command::Revoke(parse_revoke_args(matches)),
+        ("show", Some(matches)) => Command::Show(parse_show_args(matches)),
+        _ => {
+            eprintln!("{}", matches.usage());
+            exit(1);
+        }
+    };
+    Args {
+        config_file,
+        url,
+        command,
+    }
+}
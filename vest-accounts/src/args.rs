@@ -0,0 +1,165 @@
+use clap::ArgMatches;
+use solana_clap_utils::keypair::{pubkey_from_path, signer_from_path};
+use solana_remote_wallet::remote_wallet::RemoteWalletManager;
+use solana_sdk::{clock::UnixTimestamp, pubkey::Pubkey, signature::Signer};
+use std::error::Error;
+use std::sync::Arc;
+
+pub(crate) struct NewArgs<P, K> {
+    pub fee_payer: K,
+    pub funding_token_pubkey: P,
+    pub funding_authority: K,
+    pub grant_keypair: K,
+    pub escrow_keypair: K,
+    pub mint_pubkey: P,
+    pub terminator: P,
+    pub payee: P,
+    pub amount: u64,
+    pub start_date: UnixTimestamp,
+    pub cliff_date: Option<UnixTimestamp>,
+    pub end_date: UnixTimestamp,
+}
+
+pub(crate) struct WithdrawArgs<P, K> {
+    pub fee_payer: K,
+    pub grant_pubkey: P,
+    pub payee: K,
+    pub destination_pubkey: P,
+    pub amount: u64,
+}
+
+pub(crate) struct RevokeArgs<P, K> {
+    pub fee_payer: K,
+    pub grant_pubkey: P,
+    pub terminator: K,
+    pub refund_pubkey: P,
+}
+
+pub(crate) struct ShowArgs<P> {
+    pub grant_pubkey: P,
+}
+
+pub(crate) enum Command<P, K> {
+    New(Box<NewArgs<P, K>>),
+    Withdraw(WithdrawArgs<P, K>),
+    Revoke(RevokeArgs<P, K>),
+    Show(ShowArgs<P>),
+}
+
+pub(crate) struct Args<P, K> {
+    pub config_file: String,
+    pub url: Option<String>,
+    pub command: Command<P, K>,
+}
+
+fn resolve_fee_payer(
+    wallet_manager: &mut Option<Arc<RemoteWalletManager>>,
+    key_url: &str,
+) -> Result<Box<dyn Signer>, Box<dyn Error>> {
+    let matches = ArgMatches::default();
+    signer_from_path(&matches, key_url, "fee-payer", wallet_manager)
+}
+
+fn resolve_grant_pubkey(
+    wallet_manager: &mut Option<Arc<RemoteWalletManager>>,
+    key_url: &str,
+) -> Result<Pubkey, Box<dyn Error>> {
+    let matches = ArgMatches::default();
+    pubkey_from_path(&matches, key_url, "grant pubkey", wallet_manager)
+}
+
+pub(crate) fn resolve_command(
+    command: &Command<String, String>,
+) -> Result<Command<Pubkey, Box<dyn Signer>>, Box<dyn Error>> {
+    let mut wallet_manager = None;
+    let matches = ArgMatches::default();
+    match command {
+        Command::New(args) => {
+            let resolved_args = NewArgs {
+                fee_payer: resolve_fee_payer(&mut wallet_manager, &args.fee_payer)?,
+                funding_token_pubkey: pubkey_from_path(
+                    &matches,
+                    &args.funding_token_pubkey,
+                    "funding token account",
+                    &mut wallet_manager,
+                )?,
+                funding_authority: signer_from_path(
+                    &matches,
+                    &args.funding_authority,
+                    "funding authority",
+                    &mut wallet_manager,
+                )?,
+                grant_keypair: signer_from_path(
+                    &matches,
+                    &args.grant_keypair,
+                    "grant keypair",
+                    &mut wallet_manager,
+                )?,
+                escrow_keypair: signer_from_path(
+                    &matches,
+                    &args.escrow_keypair,
+                    "escrow keypair",
+                    &mut wallet_manager,
+                )?,
+                mint_pubkey: pubkey_from_path(
+                    &matches,
+                    &args.mint_pubkey,
+                    "mint",
+                    &mut wallet_manager,
+                )?,
+                terminator: pubkey_from_path(
+                    &matches,
+                    &args.terminator,
+                    "terminator",
+                    &mut wallet_manager,
+                )?,
+                payee: pubkey_from_path(&matches, &args.payee, "payee", &mut wallet_manager)?,
+                amount: args.amount,
+                start_date: args.start_date,
+                cliff_date: args.cliff_date,
+                end_date: args.end_date,
+            };
+            Ok(Command::New(Box::new(resolved_args)))
+        }
+        Command::Withdraw(args) => {
+            let resolved_args = WithdrawArgs {
+                fee_payer: resolve_fee_payer(&mut wallet_manager, &args.fee_payer)?,
+                grant_pubkey: resolve_grant_pubkey(&mut wallet_manager, &args.grant_pubkey)?,
+                payee: signer_from_path(&matches, &args.payee, "payee", &mut wallet_manager)?,
+                destination_pubkey: pubkey_from_path(
+                    &matches,
+                    &args.destination_pubkey,
+                    "destination token account",
+                    &mut wallet_manager,
+                )?,
+                amount: args.amount,
+            };
+            Ok(Command::Withdraw(resolved_args))
+        }
+        Command::Revoke(args) => {
+            let resolved_args = RevokeArgs {
+                fee_payer: resolve_fee_payer(&mut wallet_manager, &args.fee_payer)?,
+                grant_pubkey: resolve_grant_pubkey(&mut wallet_manager, &args.grant_pubkey)?,
+                terminator: signer_from_path(
+                    &matches,
+                    &args.terminator,
+                    "terminator",
+                    &mut wallet_manager,
+                )?,
+                refund_pubkey: pubkey_from_path(
+                    &matches,
+                    &args.refund_pubkey,
+                    "refund token account",
+                    &mut wallet_manager,
+                )?,
+            };
+            Ok(Command::Revoke(resolved_args))
+        }
+        Command::Show(args) => {
+            let resolved_args = ShowArgs {
+                grant_pubkey: resolve_grant_pubkey(&mut wallet_manager, &args.grant_pubkey)?,
+            };
+            Ok(Command::Show(resolved_args))
+        }
+    }
+}
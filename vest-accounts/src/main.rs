@@ -0,0 +1,169 @@
+#![allow(clippy::integer_arithmetic)]
+mod arg_parser;
+mod args;
+mod vest_accounts;
+
+use crate::arg_parser::parse_args;
+use crate::args::{resolve_command, Command, NewArgs, RevokeArgs, WithdrawArgs};
+use solana_cli_config::Config;
+use solana_client::client_error::ClientError;
+use solana_client::rpc_client::RpcClient;
+use solana_runtime::inline_spl_token_v2_0;
+use solana_sdk::{
+    account::from_account,
+    message::Message,
+    pubkey::Pubkey,
+    signature::{unique_signers, Signature, Signer},
+    signers::Signers,
+    sysvar::{self, clock::Clock},
+    transaction::Transaction,
+};
+use solana_vest_program::token_grant_state::{TokenGrantState, TokenVestSchedule};
+use std::env;
+use std::error::Error;
+
+fn get_token_grant(
+    client: &RpcClient,
+    address: &Pubkey,
+) -> Result<TokenGrantState, Box<dyn Error>> {
+    let account = client.get_account(address)?;
+    if account.owner != solana_vest_program::id() {
+        return Err(format!("{} is not a vest account", address).into());
+    }
+    Ok(TokenGrantState::deserialize(&account.data)?)
+}
+
+fn get_unix_timestamp(client: &RpcClient) -> Result<i64, Box<dyn Error>> {
+    let account = client.get_account(&sysvar::clock::id())?;
+    let clock: Clock = from_account(&account).ok_or("failed to deserialize the clock sysvar")?;
+    Ok(clock.unix_timestamp)
+}
+
+fn process_new_token_grant(
+    client: &RpcClient,
+    args: &NewArgs<Pubkey, Box<dyn Signer>>,
+) -> Result<Signature, Box<dyn Error>> {
+    let schedule = TokenVestSchedule {
+        start_unix_timestamp: args.start_date,
+        cliff_unix_timestamp: args.cliff_date.unwrap_or(args.start_date),
+        end_unix_timestamp: args.end_date,
+    };
+    if !schedule.is_valid() {
+        return Err("the schedule must start before its cliff and end after it".into());
+    }
+    let escrow_lamports = client.get_minimum_balance_for_rent_exemption(
+        inline_spl_token_v2_0::state::Account::get_packed_len(),
+    )?;
+    let grant_lamports =
+        client.get_minimum_balance_for_rent_exemption(TokenGrantState::size_of())?;
+    let message = vest_accounts::new_token_grant(
+        &args.fee_payer.pubkey(),
+        &args.funding_token_pubkey,
+        &args.funding_authority.pubkey(),
+        &args.grant_keypair.pubkey(),
+        &args.escrow_keypair.pubkey(),
+        &args.mint_pubkey,
+        &args.terminator,
+        &args.payee,
+        schedule,
+        args.amount,
+        escrow_lamports,
+        grant_lamports,
+    );
+    let signers = unique_signers(vec![
+        &*args.fee_payer,
+        &*args.funding_authority,
+        &*args.grant_keypair,
+        &*args.escrow_keypair,
+    ]);
+    let signature = send_and_confirm_message(client, message, &signers)?;
+    Ok(signature)
+}
+
+fn process_withdraw_tokens(
+    client: &RpcClient,
+    args: &WithdrawArgs<Pubkey, Box<dyn Signer>>,
+) -> Result<Signature, Box<dyn Error>> {
+    let grant_state = get_token_grant(client, &args.grant_pubkey)?;
+    let message = vest_accounts::withdraw_tokens(
+        &args.fee_payer.pubkey(),
+        &args.grant_pubkey,
+        &args.payee.pubkey(),
+        &grant_state.escrow_pubkey,
+        &args.destination_pubkey,
+        args.amount,
+    );
+    let signers = unique_signers(vec![&*args.fee_payer, &*args.payee]);
+    let signature = send_and_confirm_message(client, message, &signers)?;
+    Ok(signature)
+}
+
+fn process_revoke_token_grant(
+    client: &RpcClient,
+    args: &RevokeArgs<Pubkey, Box<dyn Signer>>,
+) -> Result<Signature, Box<dyn Error>> {
+    let grant_state = get_token_grant(client, &args.grant_pubkey)?;
+    let message = vest_accounts::revoke_token_grant(
+        &args.fee_payer.pubkey(),
+        &args.grant_pubkey,
+        &args.terminator.pubkey(),
+        &grant_state.escrow_pubkey,
+        &args.refund_pubkey,
+    );
+    let signers = unique_signers(vec![&*args.fee_payer, &*args.terminator]);
+    let signature = send_and_confirm_message(client, message, &signers)?;
+    Ok(signature)
+}
+
+fn send_and_confirm_message<S: Signers>(
+    client: &RpcClient,
+    message: Message,
+    signers: &S,
+) -> Result<Signature, ClientError> {
+    let mut transaction = Transaction::new_unsigned(message);
+
+    let (blockhash, _fee_calculator) =
+        client.get_new_blockhash(&transaction.message().recent_blockhash)?;
+    transaction.try_sign(signers, blockhash)?;
+
+    client.send_and_confirm_transaction_with_spinner(&transaction)
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let command_args = parse_args(env::args_os());
+    let config = Config::load(&command_args.config_file)?;
+    let json_rpc_url = command_args.url.unwrap_or(config.json_rpc_url);
+    let client = RpcClient::new(json_rpc_url);
+
+    match resolve_command(&command_args.command)? {
+        Command::New(args) => {
+            let signature = process_new_token_grant(&client, &args)?;
+            println!("{}", signature);
+        }
+        Command::Withdraw(args) => {
+            let signature = process_withdraw_tokens(&client, &args)?;
+            println!("{}", signature);
+        }
+        Command::Revoke(args) => {
+            let signature = process_revoke_token_grant(&client, &args)?;
+            println!("{}", signature);
+        }
+        Command::Show(args) => {
+            let grant_state = get_token_grant(&client, &args.grant_pubkey)?;
+            let unix_timestamp = get_unix_timestamp(&client)?;
+            let schedule = &grant_state.schedule;
+            println!("Terminator: {}", grant_state.terminator_pubkey);
+            println!("Payee: {}", grant_state.payee_pubkey);
+            println!("Mint: {}", grant_state.mint_pubkey);
+            println!("Escrow: {}", grant_state.escrow_pubkey);
+            println!("Start: {}", schedule.start_unix_timestamp);
+            println!("Cliff: {}", schedule.cliff_unix_timestamp);
+            println!("End: {}", schedule.end_unix_timestamp);
+            println!("Total: {}", grant_state.total_amount);
+            println!("Vested: {}", grant_state.vested_amount(unix_timestamp));
+            println!("Withdrawn: {}", grant_state.withdrawn_amount);
+            println!("Revoked: {}", grant_state.is_revoked);
+        }
+    }
+    Ok(())
+}
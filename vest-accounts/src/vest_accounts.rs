@@ -0,0 +1,145 @@
+use solana_runtime::inline_spl_token_v2_0;
+use solana_sdk::{message::Message, pubkey::Pubkey, system_instruction};
+use solana_vest_program::{token_grant_state::TokenVestSchedule, vest_instruction};
+
+/// Create an escrow token account owned by the grant's escrow authority, fund
+/// it from `funding_token_pubkey`, and declare a grant of its tokens.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn new_token_grant(
+    fee_payer_pubkey: &Pubkey,
+    funding_token_pubkey: &Pubkey,
+    funding_authority_pubkey: &Pubkey,
+    grant_pubkey: &Pubkey,
+    escrow_pubkey: &Pubkey,
+    mint_pubkey: &Pubkey,
+    terminator_pubkey: &Pubkey,
+    payee_pubkey: &Pubkey,
+    schedule: TokenVestSchedule,
+    amount: u64,
+    escrow_lamports: u64,
+    grant_lamports: u64,
+) -> Message {
+    let escrow_authority = vest_instruction::get_escrow_authority(grant_pubkey).0;
+    let mut instructions = vec![
+        system_instruction::create_account(
+            fee_payer_pubkey,
+            escrow_pubkey,
+            escrow_lamports,
+            inline_spl_token_v2_0::state::Account::get_packed_len() as u64,
+            &inline_spl_token_v2_0::id(),
+        ),
+        inline_spl_token_v2_0::instruction::initialize_account(
+            escrow_pubkey,
+            mint_pubkey,
+            &escrow_authority,
+        ),
+        inline_spl_token_v2_0::instruction::transfer(
+            funding_token_pubkey,
+            escrow_pubkey,
+            funding_authority_pubkey,
+            amount,
+        ),
+    ];
+    instructions.extend(vest_instruction::create_token_grant(
+        fee_payer_pubkey,
+        grant_pubkey,
+        escrow_pubkey,
+        terminator_pubkey,
+        payee_pubkey,
+        schedule,
+        amount,
+        grant_lamports,
+    ));
+    Message::new(&instructions, Some(fee_payer_pubkey))
+}
+
+pub(crate) fn withdraw_tokens(
+    fee_payer_pubkey: &Pubkey,
+    grant_pubkey: &Pubkey,
+    payee_pubkey: &Pubkey,
+    escrow_pubkey: &Pubkey,
+    destination_pubkey: &Pubkey,
+    amount: u64,
+) -> Message {
+    let instruction = vest_instruction::withdraw_tokens(
+        grant_pubkey,
+        payee_pubkey,
+        escrow_pubkey,
+        destination_pubkey,
+        amount,
+    );
+    Message::new(&[instruction], Some(fee_payer_pubkey))
+}
+
+pub(crate) fn revoke_token_grant(
+    fee_payer_pubkey: &Pubkey,
+    grant_pubkey: &Pubkey,
+    terminator_pubkey: &Pubkey,
+    escrow_pubkey: &Pubkey,
+    refund_pubkey: &Pubkey,
+) -> Message {
+    let instruction = vest_instruction::revoke_token_grant(
+        grant_pubkey,
+        terminator_pubkey,
+        escrow_pubkey,
+        refund_pubkey,
+    );
+    Message::new(&[instruction], Some(fee_payer_pubkey))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::program_utils::limited_deserialize;
+    use solana_vest_program::vest_instruction::VestInstruction;
+
+    #[test]
+    fn test_new_token_grant() {
+        let fee_payer_pubkey = solana_sdk::pubkey::new_rand();
+        let grant_pubkey = solana_sdk::pubkey::new_rand();
+        let escrow_pubkey = solana_sdk::pubkey::new_rand();
+        let mint_pubkey = solana_sdk::pubkey::new_rand();
+        let schedule = TokenVestSchedule {
+            start_unix_timestamp: 100,
+            cliff_unix_timestamp: 125,
+            end_unix_timestamp: 200,
+        };
+        let message = new_token_grant(
+            &fee_payer_pubkey,
+            &solana_sdk::pubkey::new_rand(),
+            &fee_payer_pubkey,
+            &grant_pubkey,
+            &escrow_pubkey,
+            &mint_pubkey,
+            &solana_sdk::pubkey::new_rand(),
+            &solana_sdk::pubkey::new_rand(),
+            schedule,
+            42,
+            1,
+            1,
+        );
+        assert_eq!(message.instructions.len(), 5);
+        assert_eq!(message.header.num_required_signatures, 3);
+
+        // The escrow must be owned by the grant's escrow authority.
+        let escrow_authority = vest_instruction::get_escrow_authority(&grant_pubkey).0;
+        let initialize_account = &message.instructions[1];
+        assert_eq!(
+            message.account_keys[initialize_account.accounts[2] as usize],
+            escrow_authority
+        );
+
+        let initialize_grant = &message.instructions[4];
+        match limited_deserialize(&initialize_grant.data).unwrap() {
+            VestInstruction::InitializeTokenGrant {
+                schedule: grant_schedule,
+                total_amount,
+                ..
+            } => {
+                assert_eq!(grant_schedule, schedule);
+                assert_eq!(total_amount, 42);
+            }
+            instruction => panic!("unexpected instruction {:?}", instruction),
+        }
+    }
+}